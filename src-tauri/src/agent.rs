@@ -1,6 +1,7 @@
 use parking_lot::Mutex;
 use portable_pty::{CommandBuilder, PtySize, native_pty_system};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::process::Command;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -563,6 +564,125 @@ pub(crate) fn detect_installed_ides() -> Vec<String> {
     installed
 }
 
+/// A CLI coding agent TUICommander can detect, classify and spawn.
+pub(crate) struct KnownAgent {
+    /// Agent type id (`agent_type`, `classify_agent` result).
+    pub(crate) name: &'static str,
+    /// Executable looked up on PATH and in known install locations.
+    pub(crate) binary: &'static str,
+    /// Args that print the version; the first stdout line is reported.
+    pub(crate) version_args: &'static [&'static str],
+    /// Default spawn args before the model flag and prompt.
+    pub(crate) spawn_args: &'static [&'static str],
+    /// Flag that selects a model, if the CLI has one.
+    pub(crate) model_flag: Option<&'static str>,
+    /// Flag that precedes the prompt; `None` passes it positionally.
+    pub(crate) prompt_flag: Option<&'static str>,
+}
+
+impl KnownAgent {
+    const fn new(name: &'static str, binary: &'static str) -> Self {
+        Self {
+            name,
+            binary,
+            version_args: &["--version"],
+            spawn_args: &[],
+            model_flag: Some("--model"),
+            prompt_flag: None,
+        }
+    }
+}
+
+/// Every agent we know about. Adding an agent is one entry here.
+pub(crate) const KNOWN_AGENTS: &[KnownAgent] = &[
+    KnownAgent::new("claude", "claude"),
+    KnownAgent::new("gemini", "gemini"),
+    KnownAgent {
+        prompt_flag: Some("--prompt"),
+        ..KnownAgent::new("opencode", "opencode")
+    },
+    KnownAgent {
+        spawn_args: &["--yes-always"],
+        prompt_flag: Some("--message"),
+        ..KnownAgent::new("aider", "aider")
+    },
+    KnownAgent::new("codex", "codex"),
+    KnownAgent {
+        model_flag: None,
+        ..KnownAgent::new("amp", "amp")
+    },
+    KnownAgent {
+        model_flag: None,
+        ..KnownAgent::new("cursor", "cursor-agent")
+    },
+    KnownAgent {
+        // `goose session` takes no prompt; `goose run` does.
+        spawn_args: &["run"],
+        prompt_flag: Some("-t"),
+        ..KnownAgent::new("goose", "goose")
+    },
+    KnownAgent::new("grok", "grok"),
+    KnownAgent {
+        model_flag: None,
+        ..KnownAgent::new("cline", "cline")
+    },
+];
+
+/// Look up a known agent by type id.
+pub(crate) fn known_agent(name: &str) -> Option<&'static KnownAgent> {
+    KNOWN_AGENTS.iter().find(|a| a.name == name)
+}
+
+/// Look up a known agent by executable name.
+pub(crate) fn known_agent_by_binary(binary: &str) -> Option<&'static KnownAgent> {
+    KNOWN_AGENTS.iter().find(|a| a.binary == binary)
+}
+
+/// Default args for an agent spawn that passes no explicit `args`.
+///
+/// Claude (and spawns without a known agent type) keep the Claude-style flags
+/// (`--print`, `--output-format`, `--model`); other known agents use their
+/// registry entry: `spawn_args`, the model flag, then the prompt.
+pub(crate) fn default_spawn_args(
+    agent_type: Option<&str>,
+    prompt: &str,
+    model: Option<&str>,
+    print_mode: bool,
+    output_format: Option<&str>,
+) -> Vec<String> {
+    let mut args: Vec<String> = Vec::new();
+    match agent_type
+        .and_then(known_agent)
+        .filter(|a| a.name != "claude")
+    {
+        Some(agent) => {
+            args.extend(agent.spawn_args.iter().map(|a| a.to_string()));
+            if let (Some(flag), Some(model)) = (agent.model_flag, model) {
+                args.push(flag.to_string());
+                args.push(model.to_string());
+            }
+            if let Some(flag) = agent.prompt_flag {
+                args.push(flag.to_string());
+            }
+        }
+        None => {
+            if print_mode {
+                args.push("--print".to_string());
+            }
+            if let Some(format) = output_format {
+                args.push("--output-format".to_string());
+                args.push(format.to_string());
+            }
+            if let Some(model) = model {
+                args.push("--model".to_string());
+                args.push(model.to_string());
+            }
+        }
+    }
+    args.push(prompt.to_string());
+    args
+}
+
 /// Agent binary detection result
 #[derive(Clone, Serialize)]
 pub(crate) struct AgentBinaryDetection {
@@ -634,7 +754,7 @@ pub(crate) fn detect_agent_binary(binary: String) -> AgentBinaryDetection {
         // `where` on Windows may return multiple lines; take the first
         let path = path.lines().next().unwrap_or("").to_string();
         if !path.is_empty() && std::path::Path::new(&path).exists() {
-            let version = get_binary_version(&path);
+            let version = get_binary_version(&path, &binary);
             return AgentBinaryDetection {
                 path: Some(path),
                 version,
@@ -645,7 +765,7 @@ pub(crate) fn detect_agent_binary(binary: String) -> AgentBinaryDetection {
     // Fall back to known locations
     for candidate in &candidates {
         if !candidate.is_empty() && std::path::Path::new(candidate).exists() {
-            let version = get_binary_version(candidate);
+            let version = get_binary_version(candidate, &binary);
             return AgentBinaryDetection {
                 path: Some(candidate.clone()),
                 version,
@@ -767,31 +887,30 @@ fn detect_binary_path_only(binary: &str) -> AgentBinaryDetection {
 }
 
 /// Get version of a binary (try --version or -v)
-fn get_binary_version(path: &str) -> Option<String> {
-    // Try --version first
-    let mut cmd = Command::new(path);
-    cmd.arg("--version");
-    crate::cli::apply_no_window(&mut cmd);
-    if let Ok(output) = cmd.output()
-        && output.status.success()
-    {
-        let version = String::from_utf8_lossy(&output.stdout);
-        let first_line = version.lines().next().unwrap_or("").trim();
-        if !first_line.is_empty() {
-            return Some(first_line.to_string());
+/// Probe a binary's version: the registry's `version_args` for known agents,
+/// then `--version`, then `-v`.
+fn get_binary_version(path: &str, binary: &str) -> Option<String> {
+    let registered = known_agent_by_binary(binary).map(|a| a.version_args);
+    let probes = registered
+        .into_iter()
+        .chain([&["--version"][..], &["-v"][..]]);
+    let mut tried: Vec<&[&str]> = Vec::new();
+    for args in probes {
+        if tried.contains(&args) {
+            continue;
         }
-    }
-    // Try -v
-    let mut cmd = Command::new(path);
-    cmd.arg("-v");
-    crate::cli::apply_no_window(&mut cmd);
-    if let Ok(output) = cmd.output()
-        && output.status.success()
-    {
-        let version = String::from_utf8_lossy(&output.stdout);
-        let first_line = version.lines().next().unwrap_or("").trim();
-        if !first_line.is_empty() {
-            return Some(first_line.to_string());
+        tried.push(args);
+        let mut cmd = Command::new(path);
+        cmd.args(args);
+        crate::cli::apply_no_window(&mut cmd);
+        if let Ok(output) = cmd.output()
+            && output.status.success()
+        {
+            let version = String::from_utf8_lossy(&output.stdout);
+            let first_line = version.lines().next().unwrap_or("").trim();
+            if !first_line.is_empty() {
+                return Some(first_line.to_string());
+            }
         }
     }
     None
@@ -807,15 +926,24 @@ pub(crate) fn detect_claude_binary() -> Result<String, String> {
     })
 }
 
-/// Spawn an agent in a PTY
-#[cfg(feature = "desktop")]
-#[tauri::command]
-pub(crate) async fn spawn_agent(
-    _app: AppHandle,
-    state: State<'_, Arc<AppState>>,
-    pty_config: PtyConfig,
-    agent_config: AgentConfig,
-) -> Result<String, String> {
+/// Everything `spawn_agent` needs to launch an agent process, resolved up front.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ResolvedAgentSpawn {
+    pub command: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    /// Env vars added on top of the inherited environment: repo env file first,
+    /// then `pty_config.env` (which wins on conflicts; see `env_file` for the
+    /// full merge order).
+    pub env_additions: BTreeMap<String, String>,
+}
+
+/// Resolve binary, args, cwd and env for an agent spawn without starting it.
+/// Shared by `spawn_agent` and `dry_run_spawn` so the two cannot drift.
+pub(crate) fn resolve_agent_spawn(
+    pty_config: &PtyConfig,
+    agent_config: &AgentConfig,
+) -> Result<ResolvedAgentSpawn, String> {
     // Determine binary path - use provided path, detect by type, or fall back to claude
     let binary_path = if let Some(ref path) = agent_config.binary_path {
         let expanded = crate::cli::expand_tilde(path);
@@ -828,14 +956,160 @@ pub(crate) async fn spawn_agent(
         }
         expanded
     } else if let Some(ref agent_type) = agent_config.agent_type {
-        let detection = detect_agent_binary(agent_type.clone());
+        let binary = known_agent(agent_type).map_or(agent_type.as_str(), |a| a.binary);
+        let detection = detect_agent_binary(binary.to_string());
         detection
             .path
-            .ok_or_else(|| format!("Agent binary '{agent_type}' not found"))?
+            .ok_or_else(|| format!("Agent binary '{binary}' not found"))?
     } else {
         detect_claude_binary()?
     };
 
+    // If custom args are provided, use them directly
+    let args = if let Some(ref args) = agent_config.args {
+        args.clone()
+    } else {
+        default_spawn_args(
+            agent_config.agent_type.as_deref(),
+            &agent_config.prompt,
+            agent_config.model.as_deref(),
+            agent_config.print_mode,
+            agent_config.output_format.as_deref(),
+        )
+    };
+
+    let cwd = agent_config
+        .cwd
+        .as_ref()
+        .or(pty_config.cwd.as_ref())
+        .map(|c| crate::cli::expand_tilde(c));
+
+    // Repo env file first so explicit env flags below take precedence
+    let mut env_additions = BTreeMap::new();
+    if let Some(ref cwd) = cwd {
+        for (key, value) in crate::env_file::load_repo_env_for_cwd(cwd)? {
+            env_additions.insert(key, value);
+        }
+    }
+    // Per-spawn env last: feature flags from Settings → Agents and any
+    // caller-supplied overrides (model, proxy, …)
+    crate::env_file::validate_spawn_env(&pty_config.env)?;
+    for (key, value) in &pty_config.env {
+        env_additions.insert(key.clone(), value.clone());
+    }
+
+    Ok(ResolvedAgentSpawn {
+        command: binary_path,
+        args,
+        cwd,
+        env_additions,
+    })
+}
+
+/// Whether an env var should be masked in dry-run output, by name or by value.
+fn is_secret_env(key: &str, value: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "SECRET",
+        "PASSWORD",
+        "PASSWD",
+        "TOKEN",
+        "API_KEY",
+        "APIKEY",
+        "PRIVATE_KEY",
+        "CREDENTIAL",
+    ];
+    let upper = key.to_uppercase();
+    MARKERS.iter().any(|m| upper.contains(m))
+        || crate::ai_agent::tools::redact_secrets(value) != value
+}
+
+/// Report what `spawn_agent` would execute — command, args, cwd and added env —
+/// without opening a PTY. Secret-looking env values are masked.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn dry_run_spawn(
+    pty_config: PtyConfig,
+    agent_config: AgentConfig,
+) -> Result<ResolvedAgentSpawn, String> {
+    let mut resolved = resolve_agent_spawn(&pty_config, &agent_config)?;
+    for (key, value) in resolved.env_additions.iter_mut() {
+        if is_secret_env(key, value) {
+            *value = "********".to_string();
+        }
+    }
+    Ok(resolved)
+}
+
+/// One variable in [`explain_env`]'s layered view.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub(crate) struct EnvExplainEntry {
+    pub(crate) key: String,
+    /// Effective value; secret-looking values are masked
+    pub(crate) value: String,
+    /// Layer whose value wins
+    pub(crate) source: crate::env_file::EnvSource,
+    /// Lower layers that also set the key and were overridden, lowest first
+    pub(crate) overrides: Vec<crate::env_file::EnvSource>,
+}
+
+/// Merge `inherited` with the repo layers the way the spawn path applies them
+/// (later wins) and record each key's winning source. Sorted by key.
+fn explain_env_entries(
+    inherited: impl IntoIterator<Item = (String, String)>,
+    repo_layers: Vec<(crate::env_file::EnvSource, String, String)>,
+) -> Vec<EnvExplainEntry> {
+    let mut merged: BTreeMap<String, EnvExplainEntry> = BTreeMap::new();
+    let layers = inherited
+        .into_iter()
+        .map(|(k, v)| (crate::env_file::EnvSource::Inherited, k, v))
+        .chain(repo_layers);
+    for (source, key, value) in layers {
+        let entry = merged
+            .entry(key.clone())
+            .or_insert_with(|| EnvExplainEntry {
+                key,
+                value: String::new(),
+                source,
+                overrides: Vec::new(),
+            });
+        if entry.source != source && !entry.overrides.contains(&entry.source) {
+            entry.overrides.push(entry.source);
+        }
+        entry.source = source;
+        entry.value = value;
+    }
+    merged
+        .into_values()
+        .map(|mut e| {
+            if is_secret_env(&e.key, &e.value) {
+                e.value = "********".to_string();
+            }
+            e
+        })
+        .collect()
+}
+
+/// Explain the environment a session spawned in `repo_path` gets: every
+/// variable with its effective value (secrets masked), the winning source
+/// (inherited, `.env` file, repo settings) and the layers it overrode.
+/// Uses the same repo layering as the spawn path.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn explain_env(repo_path: String) -> Result<Vec<EnvExplainEntry>, String> {
+    let layers = crate::env_file::repo_env_layers_for_cwd(&crate::cli::expand_tilde(&repo_path))?;
+    Ok(explain_env_entries(std::env::vars(), layers))
+}
+
+/// Spawn an agent in a PTY
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn spawn_agent(
+    _app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    pty_config: PtyConfig,
+    agent_config: AgentConfig,
+) -> Result<String, String> {
+    crate::pty::ensure_session_slot(&state)?;
+    let resolved = resolve_agent_spawn(&pty_config, &agent_config)?;
+
     let session_id = Uuid::new_v4().to_string();
     let pty_system = native_pty_system();
 
@@ -849,44 +1123,18 @@ pub(crate) async fn spawn_agent(
         .map_err(|e| format!("Failed to open PTY: {e}"))?;
 
     // Build agent command
-    let mut cmd = CommandBuilder::new(&binary_path);
-
-    // If custom args are provided, use them directly
-    if let Some(ref args) = agent_config.args {
-        for arg in args {
-            cmd.arg(arg);
-        }
-    } else {
-        // Default Claude-style args for backward compatibility
-        if agent_config.print_mode {
-            cmd.arg("--print");
-        }
-
-        if let Some(ref format) = agent_config.output_format {
-            cmd.arg("--output-format");
-            cmd.arg(format);
-        }
-
-        if let Some(ref model) = agent_config.model {
-            cmd.arg("--model");
-            cmd.arg(model);
-        }
-
-        // Add prompt
-        cmd.arg(&agent_config.prompt);
+    let mut cmd = CommandBuilder::new(&resolved.command);
+    for arg in &resolved.args {
+        cmd.arg(arg);
     }
-
-    if let Some(ref cwd) = agent_config.cwd {
-        cmd.cwd(crate::cli::expand_tilde(cwd));
-    } else if let Some(ref cwd) = pty_config.cwd {
-        cmd.cwd(crate::cli::expand_tilde(cwd));
+    if let Some(ref cwd) = resolved.cwd {
+        cmd.cwd(cwd);
     }
-
-    // Inject env flags (feature flags configured in Settings → Agents)
-    for (key, value) in &pty_config.env {
+    for (key, value) in &resolved.env_additions {
         cmd.env(key, value);
     }
 
+    let command_line = cmd.as_unix_command_line().ok();
     let child = pair
         .slave
         .spawn_command(cmd)
@@ -901,6 +1149,15 @@ pub(crate) async fn spawn_agent(
         .master
         .try_clone_reader()
         .map_err(|e| format!("Failed to get PTY reader: {e}"))?;
+    let transcript = crate::transcript::TranscriptSettings::from_config(&state.config.read());
+    let reader = crate::transcript::wrap_agent_reader(
+        reader,
+        transcript.as_ref(),
+        agent_config.cwd.as_deref().or(pty_config.cwd.as_deref()),
+        &session_id,
+    );
+
+    crate::spawn_ledger::record_spawn(&state.data_dir, child.process_id(), &session_id);
 
     // Store session (master handle kept for resize support)
     let paused = Arc::new(AtomicBool::new(false));
@@ -914,9 +1171,11 @@ pub(crate) async fn spawn_agent(
             worktree: None,
             cwd: agent_config.cwd.clone(),
             display_name: None,
-            shell: binary_path.clone(),
+            shell: resolved.command.clone(),
+            token_usage: Default::default(),
         }),
     );
+    state.record_session_opened(&session_id, command_line);
     state.metrics.total_spawned.fetch_add(1, Ordering::Relaxed);
     state
         .metrics
@@ -1070,6 +1329,143 @@ mod tests {
         assert_eq!(out, vec!["--wait", "--reuse-window"]);
     }
 
+    #[test]
+    fn explain_env_reports_winning_source_per_key() {
+        use crate::env_file::EnvSource;
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::write(
+            repo.path().join(".env"),
+            "GITHUB_TOKEN=ghp_fromfile\nFROM_FILE=1\nPATH=/file/bin\n",
+        )
+        .unwrap();
+        let entry = crate::config::RepoSettingsEntry {
+            env_file: Some(".env".to_string()),
+            env: [("GITHUB_TOKEN".to_string(), "ghp_fromsettings".to_string())].into(),
+            ..Default::default()
+        };
+        let layers = crate::env_file::repo_env_layers(repo.path(), &entry).unwrap();
+        let inherited = [
+            ("PATH", "/usr/bin"),
+            ("HOME", "/home/me"),
+            ("GITHUB_TOKEN", "ghp_inherited"),
+        ]
+        .map(|(k, v)| (k.to_string(), v.to_string()));
+        let entries = explain_env_entries(inherited, layers);
+        let get = |k: &str| entries.iter().find(|e| e.key == k).unwrap();
+
+        let token = get("GITHUB_TOKEN");
+        assert_eq!(token.source, EnvSource::RepoSettings);
+        assert_eq!(
+            token.overrides,
+            vec![EnvSource::Inherited, EnvSource::EnvFile]
+        );
+        assert_eq!(token.value, "********");
+
+        let path = get("PATH");
+        assert_eq!(path.source, EnvSource::EnvFile);
+        assert_eq!(path.value, "/file/bin");
+        assert_eq!(path.overrides, vec![EnvSource::Inherited]);
+
+        assert_eq!(get("FROM_FILE").source, EnvSource::EnvFile);
+        assert_eq!(get("HOME").source, EnvSource::Inherited);
+        assert!(get("HOME").overrides.is_empty());
+        let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["FROM_FILE", "GITHUB_TOKEN", "HOME", "PATH"]);
+    }
+
+    #[test]
+    fn dry_run_spawn_resolves_default_args_and_masks_secrets() {
+        let binary = std::env::current_exe()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let pty_config = PtyConfig {
+            rows: 24,
+            cols: 80,
+            shell: None,
+            cwd: None,
+            tuic_session: None,
+            env: [
+                ("ANTHROPIC_API_KEY".to_string(), "plain-value".to_string()),
+                ("FEATURE_X".to_string(), "1".to_string()),
+                (
+                    "EXTRA".to_string(),
+                    "sk-abcdefghijklmnopqrstuvwxyz".to_string(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            agent_type: None,
+        };
+        let agent_config = AgentConfig {
+            prompt: "fix the bug".to_string(),
+            cwd: None,
+            model: Some("opus".to_string()),
+            print_mode: true,
+            output_format: Some("json".to_string()),
+            agent_type: None,
+            binary_path: Some(binary.clone()),
+            args: None,
+        };
+
+        let resolved = dry_run_spawn(pty_config, agent_config).unwrap();
+        assert_eq!(resolved.command, binary);
+        assert_eq!(
+            resolved.args,
+            vec![
+                "--print",
+                "--output-format",
+                "json",
+                "--model",
+                "opus",
+                "fix the bug"
+            ]
+        );
+        assert_eq!(resolved.cwd, None);
+        assert_eq!(resolved.env_additions["FEATURE_X"], "1");
+        assert_eq!(resolved.env_additions["ANTHROPIC_API_KEY"], "********");
+        assert_eq!(resolved.env_additions["EXTRA"], "********");
+    }
+
+    #[test]
+    fn known_agents_have_unique_names_and_binaries() {
+        for (i, agent) in KNOWN_AGENTS.iter().enumerate() {
+            assert!(!agent.version_args.is_empty(), "{}", agent.name);
+            for other in &KNOWN_AGENTS[i + 1..] {
+                assert_ne!(agent.name, other.name);
+                assert_ne!(agent.binary, other.binary);
+            }
+        }
+        for name in ["opencode", "amp", "cline"] {
+            assert!(known_agent(name).is_some(), "missing {name}");
+        }
+    }
+
+    #[test]
+    fn default_spawn_args_follow_the_registry() {
+        assert_eq!(
+            default_spawn_args(Some("aider"), "fix it", Some("sonnet"), true, Some("json")),
+            vec!["--yes-always", "--model", "sonnet", "--message", "fix it"]
+        );
+        assert_eq!(
+            default_spawn_args(Some("opencode"), "fix it", Some("gpt-5"), false, None),
+            vec!["--model", "gpt-5", "--prompt", "fix it"]
+        );
+        assert_eq!(
+            default_spawn_args(Some("goose"), "fix it", Some("gpt-5"), false, None),
+            vec!["run", "--model", "gpt-5", "-t", "fix it"]
+        );
+        assert_eq!(
+            default_spawn_args(Some("cline"), "fix it", Some("ignored"), false, None),
+            vec!["fix it"]
+        );
+        // Unknown agent types fall back to the Claude-style flags
+        assert_eq!(
+            default_spawn_args(Some("mystery"), "fix it", None, true, None),
+            vec!["--print", "fix it"]
+        );
+    }
+
     #[test]
     fn open_in_custom_rejects_empty_executable() {
         let err = open_in_custom("  ".to_string(), vec![], ctx(None, "/x", None, None, None));
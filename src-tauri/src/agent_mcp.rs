@@ -34,6 +34,17 @@ fn default_stdio_type() -> String {
     "stdio".to_string()
 }
 
+impl TuicMcpEntry {
+    fn for_bridge(bridge_path: &str) -> Self {
+        Self {
+            transport_type: "stdio".to_string(),
+            command: bridge_path.to_string(),
+            args: vec![],
+            env: BTreeMap::new(),
+        }
+    }
+}
+
 const TUIC_MCP_KEY: &str = "tuicommander";
 
 /// Get the home directory, panicking on failure (should never happen in practice)
@@ -250,12 +261,7 @@ fn ensure_agent_mcp_entry(
         }
     }
 
-    let entry = TuicMcpEntry {
-        transport_type: "stdio".to_string(),
-        command: bridge_path.to_string(),
-        args: vec![],
-        env: BTreeMap::new(),
-    };
+    let entry = TuicMcpEntry::for_bridge(bridge_path);
     let entry_value = match serde_json::to_value(&entry) {
         Ok(v) => v,
         Err(e) => {
@@ -432,10 +438,231 @@ pub(crate) fn ensure_mcp_configs(disabled: &[String]) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Registration preview
+// ---------------------------------------------------------------------------
+
+/// What `install_agent_mcp` would write, computed without touching the file.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct McpRegistrationPreview {
+    pub(crate) config_path: String,
+    /// Key path of the entry inside the config, e.g. `["mcpServers", "tuicommander"]`.
+    pub(crate) key_path: Vec<String>,
+    pub(crate) entry_name: String,
+    /// The entry that would be written.
+    pub(crate) proposed: serde_json::Value,
+    /// The entry currently stored under `entry_name`, if any.
+    pub(crate) existing: Option<serde_json::Value>,
+    /// True when `existing` already equals `proposed` (install is a no-op).
+    pub(crate) unchanged: bool,
+}
+
+impl McpRegistrationPreview {
+    fn new(
+        config_path: &std::path::Path,
+        key_path: &[&str],
+        proposed: serde_json::Value,
+        existing: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            config_path: config_path.to_string_lossy().to_string(),
+            key_path: key_path
+                .iter()
+                .chain(std::iter::once(&TUIC_MCP_KEY))
+                .map(|k| (*k).to_string())
+                .collect(),
+            entry_name: TUIC_MCP_KEY.to_string(),
+            unchanged: existing.as_ref() == Some(&proposed),
+            proposed,
+            existing,
+        }
+    }
+}
+
+/// Preview the entry for a JSON-configured agent.
+fn preview_json_registration(
+    config_path: &std::path::Path,
+    key_path: &[&str],
+    bridge_path: &str,
+) -> Result<McpRegistrationPreview, String> {
+    let proposed = serde_json::to_value(TuicMcpEntry::for_bridge(bridge_path))
+        .map_err(|e| format!("Failed to serialize MCP entry: {e}"))?;
+    let root = read_json_file(config_path);
+    let existing = navigate(&root, key_path)
+        .and_then(|v| v.get(TUIC_MCP_KEY))
+        .cloned();
+    Ok(McpRegistrationPreview::new(
+        config_path,
+        key_path,
+        proposed,
+        existing,
+    ))
+}
+
+/// Preview the Codex TOML entry, rendered as JSON for display.
+fn preview_codex_registration(
+    config_path: &std::path::Path,
+    bridge_path: &str,
+) -> McpRegistrationPreview {
+    let root = read_toml_file(config_path);
+    let existing = root
+        .get("mcp_servers")
+        .and_then(|s| s.get(TUIC_MCP_KEY))
+        .and_then(|e| serde_json::to_value(e).ok());
+    McpRegistrationPreview::new(
+        config_path,
+        &["mcp_servers"],
+        serde_json::json!({ "command": bridge_path }),
+        existing,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Registered server listing
+// ---------------------------------------------------------------------------
+
+/// One MCP server registered in an agent's config (ours or any other tool's).
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct McpServerEntry {
+    pub(crate) name: String,
+    /// `stdio`, `http`, `sse`, … (explicit `type`, else inferred from `url`/`command`).
+    pub(crate) transport: String,
+    /// URL for remote servers, `command args…` for stdio ones.
+    pub(crate) url_or_command: String,
+    /// Points at our bridge — under our key or not. More than one `is_ours`
+    /// entry means a duplicate registration.
+    pub(crate) is_ours: bool,
+}
+
+impl McpServerEntry {
+    fn from_json(name: &str, entry: &serde_json::Value) -> Self {
+        let url = entry
+            .get("url")
+            .or_else(|| entry.get("serverUrl"))
+            .and_then(|v| v.as_str());
+        // Zed nests the command: `"command": { "path": …, "args": [...] }`.
+        let command_obj = entry.get("command").filter(|c| c.is_object());
+        let command = command_obj
+            .and_then(|c| c.get("path"))
+            .or_else(|| entry.get("command"))
+            .and_then(|v| v.as_str());
+        let args: Vec<&str> = command_obj
+            .and_then(|c| c.get("args"))
+            .or_else(|| entry.get("args"))
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        let transport = entry
+            .get("type")
+            .or_else(|| entry.get("transport"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| if url.is_some() { "http" } else { "stdio" }.to_string());
+        let url_or_command = match (url, command) {
+            (Some(url), _) => url.to_string(),
+            (None, Some(cmd)) => std::iter::once(cmd)
+                .chain(args)
+                .collect::<Vec<_>>()
+                .join(" "),
+            (None, None) => String::new(),
+        };
+        let is_ours = name == TUIC_MCP_KEY || command.is_some_and(is_bridge_command);
+        Self {
+            name: name.to_string(),
+            transport,
+            url_or_command,
+            is_ours,
+        }
+    }
+}
+
+/// Whether a stdio command launches our bridge binary.
+fn is_bridge_command(command: &str) -> bool {
+    std::path::Path::new(command)
+        .file_stem()
+        .is_some_and(|stem| stem == BRIDGE_NAME)
+}
+
+/// List every server in a JSON MCP config. A missing file is an empty list;
+/// an unparseable one is an error (unlike `read_json_file`, which would hide it).
+fn list_json_mcp_servers(
+    config_path: &std::path::Path,
+    key_path: &[&str],
+) -> Result<Vec<McpServerEntry>, String> {
+    let content = match std::fs::read_to_string(config_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {e}", config_path.display())),
+    };
+    let root: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Malformed MCP config {}: {e}", config_path.display()))?;
+    Ok(navigate(&root, key_path)
+        .and_then(|v| v.as_object())
+        .map(|servers| {
+            servers
+                .iter()
+                .map(|(name, entry)| McpServerEntry::from_json(name, entry))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// List every server under `[mcp_servers]` in Codex's TOML config.
+fn list_codex_mcp_servers(config_path: &std::path::Path) -> Result<Vec<McpServerEntry>, String> {
+    let content = match std::fs::read_to_string(config_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {e}", config_path.display())),
+    };
+    let root: toml::Value = toml::from_str(&content)
+        .map_err(|e| format!("Malformed MCP config {}: {e}", config_path.display()))?;
+    Ok(root
+        .get("mcp_servers")
+        .and_then(|v| v.as_table())
+        .map(|servers| {
+            servers
+                .iter()
+                .filter_map(|(name, entry)| {
+                    let json = serde_json::to_value(entry).ok()?;
+                    Some(McpServerEntry::from_json(name, &json))
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
 // ---------------------------------------------------------------------------
 // Tauri commands
 // ---------------------------------------------------------------------------
 
+/// List all MCP servers registered in an agent's MCP config, flagging ours
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn list_mcp_servers(agent_type: String) -> Result<Vec<McpServerEntry>, String> {
+    if agent_type == "codex" {
+        return list_codex_mcp_servers(&codex_config_path());
+    }
+    let spec = get_mcp_config_spec(&agent_type)
+        .ok_or_else(|| format!("Agent '{agent_type}' does not support MCP configuration"))?;
+    list_json_mcp_servers(&spec.config_path, &spec.key_path)
+}
+
+/// Show what `install_agent_mcp` would write and whether it replaces an existing entry
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn preview_mcp_registration(
+    agent_type: String,
+) -> Result<McpRegistrationPreview, String> {
+    let bridge_path = detect_bridge_binary();
+    if agent_type == "codex" {
+        return Ok(preview_codex_registration(
+            &codex_config_path(),
+            &bridge_path,
+        ));
+    }
+    let spec = get_mcp_config_spec(&agent_type)
+        .ok_or_else(|| format!("Agent '{agent_type}' does not support MCP configuration"))?;
+    preview_json_registration(&spec.config_path, &spec.key_path, &bridge_path)
+}
+
 /// Check MCP installation status for an agent
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn get_agent_mcp_status(agent_type: String) -> AgentMcpStatus {
@@ -501,12 +728,7 @@ pub(crate) fn install_agent_mcp(
     let spec = get_mcp_config_spec(&agent_type)
         .ok_or_else(|| format!("Agent '{agent_type}' does not support MCP configuration"))?;
 
-    let entry = TuicMcpEntry {
-        transport_type: "stdio".to_string(),
-        command: bridge_path,
-        args: vec![],
-        env: BTreeMap::new(),
-    };
+    let entry = TuicMcpEntry::for_bridge(&bridge_path);
     let entry_value =
         serde_json::to_value(&entry).map_err(|e| format!("Failed to serialize MCP entry: {e}"))?;
 
@@ -605,12 +827,7 @@ pub(crate) struct McpBridgeInfo {
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn get_mcp_bridge_info() -> McpBridgeInfo {
     let bridge_path = detect_bridge_binary();
-    let entry = TuicMcpEntry {
-        transport_type: "stdio".to_string(),
-        command: bridge_path.clone(),
-        args: vec![],
-        env: BTreeMap::new(),
-    };
+    let entry = TuicMcpEntry::for_bridge(&bridge_path);
     let wrapper = serde_json::json!({
         "tuicommander": entry,
     });
@@ -621,6 +838,77 @@ pub(crate) fn get_mcp_bridge_info() -> McpBridgeInfo {
     }
 }
 
+/// Host an HTTP MCP client should dial for the remote-access listener.
+/// A wildcard bind (or none resolved) is reachable on loopback, where the
+/// desktop auth bypass applies; a specific bind address is the only one the
+/// listener accepts, so it is used as-is.
+#[cfg_attr(not(feature = "desktop"), allow(dead_code))]
+fn mcp_http_host(bind_ip: Option<std::net::IpAddr>) -> std::net::IpAddr {
+    match bind_ip {
+        Some(ip) if !ip.is_unspecified() => ip,
+        _ => std::net::Ipv4Addr::LOCALHOST.into(),
+    }
+}
+
+/// Build a `.mcp.json` document registering TuiCommander over `transport`.
+///
+/// `"http"` points at the remote-access listener on `endpoint` (see
+/// [`mcp_http_host`]); `"stdio"` launches the bridge binary.
+#[cfg_attr(not(feature = "desktop"), allow(dead_code))]
+fn mcp_config_snippet(
+    transport: &str,
+    endpoint: Option<std::net::SocketAddr>,
+    bridge_path: &str,
+) -> Result<serde_json::Value, String> {
+    let entry = match transport {
+        "http" => {
+            let endpoint = endpoint.ok_or_else(|| {
+                "HTTP transport needs remote access enabled; use the stdio bridge instead"
+                    .to_string()
+            })?;
+            serde_json::json!({
+                "type": "http",
+                "url": format!("http://{endpoint}/mcp"),
+            })
+        }
+        "stdio" => serde_json::to_value(TuicMcpEntry::for_bridge(bridge_path))
+            .map_err(|e| e.to_string())?,
+        other => {
+            return Err(format!(
+                "Unknown MCP transport '{other}' (expected http or stdio)"
+            ));
+        }
+    };
+    Ok(serde_json::json!({ "mcpServers": { TUIC_MCP_KEY: entry } }))
+}
+
+/// Return a ready-to-paste `.mcp.json` for the requested transport
+/// (`http` or `stdio`), using the port the server is currently listening on
+/// and the host its configured `bind_address` accepts.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn generate_mcp_config_snippet(
+    transport: String,
+    state: tauri::State<'_, std::sync::Arc<crate::state::AppState>>,
+) -> Result<String, String> {
+    let endpoint = match state.remote_port.load(std::sync::atomic::Ordering::Relaxed) {
+        0 => None,
+        port => {
+            let server = state.config.read().services.server.clone();
+            // Unresolvable bind addresses fall back to 127.0.0.1 in start_server.
+            let bind_ip = crate::configured_bind_ip(&server).ok().flatten();
+            Some(std::net::SocketAddr::new(mcp_http_host(bind_ip), port))
+        }
+    };
+    let bridge_path = if transport == "stdio" {
+        detect_bridge_binary()
+    } else {
+        String::new()
+    };
+    let snippet = mcp_config_snippet(&transport, endpoint, &bridge_path)?;
+    serde_json::to_string_pretty(&snippet).map_err(|e| e.to_string())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -630,6 +918,161 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn mcp_config_snippet_uses_current_port_or_bridge() {
+        let http =
+            mcp_config_snippet("http", Some("127.0.0.1:45123".parse().unwrap()), "/b").unwrap();
+        let text = serde_json::to_string_pretty(&http).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+        let entry = &parsed["mcpServers"]["tuicommander"];
+        assert_eq!(entry["type"], "http");
+        assert_eq!(entry["url"], "http://127.0.0.1:45123/mcp");
+
+        let stdio = mcp_config_snippet("stdio", None, "/opt/tuic-bridge").unwrap();
+        let entry = &stdio["mcpServers"]["tuicommander"];
+        assert_eq!(entry["type"], "stdio");
+        assert_eq!(entry["command"], "/opt/tuic-bridge");
+
+        assert!(mcp_config_snippet("http", None, "/b").is_err());
+        assert!(mcp_config_snippet("sse", Some("127.0.0.1:1".parse().unwrap()), "/b").is_err());
+    }
+
+    #[test]
+    fn mcp_http_host_follows_bind_address() {
+        let localhost: std::net::IpAddr = std::net::Ipv4Addr::LOCALHOST.into();
+        assert_eq!(mcp_http_host(None), localhost);
+        assert_eq!(mcp_http_host(Some("0.0.0.0".parse().unwrap())), localhost);
+        assert_eq!(mcp_http_host(Some("::".parse().unwrap())), localhost);
+
+        let tailscale: std::net::IpAddr = "100.64.0.5".parse().unwrap();
+        assert_eq!(mcp_http_host(Some(tailscale)), tailscale);
+        let http = mcp_config_snippet(
+            "http",
+            Some(std::net::SocketAddr::new(tailscale, 9876)),
+            "/b",
+        )
+        .unwrap();
+        assert_eq!(
+            http["mcpServers"]["tuicommander"]["url"],
+            "http://100.64.0.5:9876/mcp"
+        );
+
+        let v6 = std::net::SocketAddr::new("fd7a:115c::5".parse().unwrap(), 9876);
+        let http = mcp_config_snippet("http", Some(v6), "/b").unwrap();
+        assert_eq!(
+            http["mcpServers"]["tuicommander"]["url"],
+            "http://[fd7a:115c::5]:9876/mcp"
+        );
+    }
+
+    #[test]
+    fn preview_reports_existing_entry_or_proposed_json() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("mcp.json");
+
+        // No config yet: only the proposed entry.
+        let preview = preview_json_registration(&path, &["mcpServers"], "/new/bridge").unwrap();
+        assert_eq!(preview.key_path, vec!["mcpServers", "tuicommander"]);
+        assert_eq!(preview.proposed["command"], "/new/bridge");
+        assert_eq!(preview.proposed["args"], serde_json::json!([]));
+        assert!(preview.existing.is_none());
+        assert!(!preview.unchanged);
+        assert!(!path.exists(), "preview must not create the file");
+
+        // Existing entry with a different command is reported as-is.
+        let old = serde_json::json!({ "command": "/old/bridge" });
+        std::fs::write(
+            &path,
+            serde_json::json!({ "mcpServers": { "tuicommander": old, "other": {} } }).to_string(),
+        )
+        .unwrap();
+        let preview = preview_json_registration(&path, &["mcpServers"], "/new/bridge").unwrap();
+        assert_eq!(preview.existing, Some(old));
+        assert!(!preview.unchanged);
+
+        // After install the preview is a no-op.
+        ensure_agent_mcp_entry(&path, &["mcpServers"], "/new/bridge", "test");
+        let preview = preview_json_registration(&path, &["mcpServers"], "/new/bridge").unwrap();
+        assert!(preview.unchanged);
+    }
+
+    #[test]
+    fn list_servers_parses_claude_config_and_flags_ours() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".claude.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "numStartups": 3,
+                "mcpServers": {
+                    "tuicommander": { "type": "stdio", "command": "/Applications/TUICommander.app/Contents/MacOS/tuic-bridge", "args": [], "env": {} },
+                    "tuic-old": { "command": "/usr/local/bin/tuic-bridge" },
+                    "github": { "type": "http", "url": "https://api.githubcopilot.com/mcp/" },
+                    "playwright": { "command": "npx", "args": ["@playwright/mcp@latest"] },
+                    "events": { "type": "sse", "url": "http://localhost:9000/sse" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let servers = list_json_mcp_servers(&path, &["mcpServers"]).unwrap();
+        let by_name = |n: &str| servers.iter().find(|s| s.name == n).unwrap();
+        assert_eq!(servers.len(), 5);
+
+        let ours = by_name("tuicommander");
+        assert!(ours.is_ours);
+        assert_eq!(ours.transport, "stdio");
+        assert!(ours.url_or_command.ends_with("tuic-bridge"));
+        // Stale duplicate registered under another name.
+        assert!(by_name("tuic-old").is_ours);
+        assert_eq!(by_name("tuic-old").transport, "stdio");
+
+        let github = by_name("github");
+        assert!(!github.is_ours);
+        assert_eq!(github.transport, "http");
+        assert_eq!(github.url_or_command, "https://api.githubcopilot.com/mcp/");
+        assert_eq!(
+            by_name("playwright").url_or_command,
+            "npx @playwright/mcp@latest"
+        );
+        assert_eq!(by_name("events").transport, "sse");
+    }
+
+    #[test]
+    fn list_servers_handles_missing_and_malformed_config() {
+        let dir = TempDir::new().unwrap();
+        let missing = dir.path().join("nope.json");
+        assert!(
+            list_json_mcp_servers(&missing, &["mcpServers"])
+                .unwrap()
+                .is_empty()
+        );
+
+        let broken = dir.path().join("broken.json");
+        std::fs::write(&broken, "{ \"mcpServers\": {").unwrap();
+        let err = list_json_mcp_servers(&broken, &["mcpServers"]).unwrap_err();
+        assert!(err.contains("Malformed"), "{err}");
+
+        let codex = dir.path().join("config.toml");
+        std::fs::write(
+            &codex,
+            "[mcp_servers.tuicommander]\ncommand = \"tuic-bridge\"\n\n[mcp_servers.docs]\nurl = \"https://example.com/mcp\"\n",
+        )
+        .unwrap();
+        let servers = list_codex_mcp_servers(&codex).unwrap();
+        assert_eq!(servers.len(), 2);
+        assert!(
+            servers
+                .iter()
+                .any(|s| s.name == "tuicommander" && s.is_ours)
+        );
+        assert!(
+            servers
+                .iter()
+                .any(|s| s.name == "docs" && s.transport == "http")
+        );
+    }
+
     #[test]
     fn unsupported_agent_returns_not_supported() {
         let status = get_agent_mcp_status("aider".to_string());
@@ -0,0 +1,350 @@
+//! ANSI SGR → HTML conversion.
+//!
+//! Shared by everything that renders captured terminal output outside the
+//! terminal itself (log viewer, exports, snapshots). Bytes are run through
+//! the same vte parser the terminal uses, so non-SGR escapes (cursor moves,
+//! OSC titles, …) are dropped rather than leaking into the markup. Styling is
+//! emitted as one inline-styled `<span>` per run of identical attributes, so
+//! spans never nest and a reset (or the end of input) only has to close one.
+
+use alacritty_terminal::vte::{Params, Parser, Perform};
+use std::fmt::Write as _;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Color {
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn css(self) -> String {
+        let (r, g, b) = match self {
+            Self::Rgb(r, g, b) => (r, g, b),
+            Self::Indexed(i) => indexed_rgb(i),
+        };
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// xterm's default palette for indices 0–15.
+const BASE_PALETTE: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xcd, 0x00, 0x00),
+    (0x00, 0xcd, 0x00),
+    (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee),
+    (0xcd, 0x00, 0xcd),
+    (0x00, 0xcd, 0xcd),
+    (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f),
+    (0xff, 0x00, 0x00),
+    (0x00, 0xff, 0x00),
+    (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff),
+    (0xff, 0x00, 0xff),
+    (0x00, 0xff, 0xff),
+    (0xff, 0xff, 0xff),
+];
+
+/// RGB for a 256-color index: 16 base colors, a 6×6×6 cube, then 24 grays.
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => BASE_PALETTE[usize::from(index)],
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = index - 16;
+            (level(i / 36), level((i / 6) % 6), level(i % 6))
+        }
+        232..=255 => {
+            let v = 8 + (index - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Style {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+}
+
+impl Style {
+    fn css(&self) -> String {
+        let mut css = String::new();
+        if let Some(fg) = self.fg {
+            let _ = write!(css, "color:{};", fg.css());
+        }
+        if let Some(bg) = self.bg {
+            let _ = write!(css, "background-color:{};", bg.css());
+        }
+        if self.bold {
+            css.push_str("font-weight:bold;");
+        }
+        if self.dim {
+            css.push_str("opacity:0.7;");
+        }
+        if self.italic {
+            css.push_str("font-style:italic;");
+        }
+        match (self.underline, self.strikethrough) {
+            (true, true) => css.push_str("text-decoration:underline line-through;"),
+            (true, false) => css.push_str("text-decoration:underline;"),
+            (false, true) => css.push_str("text-decoration:line-through;"),
+            (false, false) => {}
+        }
+        css.pop(); // trailing ';'
+        css
+    }
+
+    /// Apply one SGR parameter list (e.g. `1;38;5;208`).
+    fn apply_sgr(&mut self, params: &Params) {
+        let params: Vec<&[u16]> = params.iter().collect();
+        if params.is_empty() {
+            *self = Self::default();
+            return;
+        }
+        let mut i = 0;
+        while i < params.len() {
+            let param = params[i];
+            match param[0] {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                // `4:0` is the colon form of "underline off".
+                4 => self.underline = param.get(1) != Some(&0),
+                9 => self.strikethrough = true,
+                21 | 22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                29 => self.strikethrough = false,
+                n @ 30..=37 => self.fg = Some(Color::Indexed((n - 30) as u8)),
+                39 => self.fg = None,
+                n @ 40..=47 => self.bg = Some(Color::Indexed((n - 40) as u8)),
+                49 => self.bg = None,
+                n @ 90..=97 => self.fg = Some(Color::Indexed((n - 90 + 8) as u8)),
+                n @ 100..=107 => self.bg = Some(Color::Indexed((n - 100 + 8) as u8)),
+                38 | 48 => {
+                    let (color, consumed) = extended_color(&params[i..]);
+                    let slot = if param[0] == 38 {
+                        &mut self.fg
+                    } else {
+                        &mut self.bg
+                    };
+                    if color.is_some() {
+                        *slot = color;
+                    }
+                    i += consumed;
+                    continue;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+fn rgb(r: u16, g: u16, b: u16) -> Option<Color> {
+    Some(Color::Rgb(
+        u8::try_from(r).ok()?,
+        u8::try_from(g).ok()?,
+        u8::try_from(b).ok()?,
+    ))
+}
+
+/// Parse a `38`/`48` extended color starting at `params[0]`, in either the
+/// colon form (`38:5:n`, `38:2::r:g:b`) or the semicolon form (`38;5;n`,
+/// `38;2;r;g;b`). Returns the color and how many params it consumed.
+fn extended_color(params: &[&[u16]]) -> (Option<Color>, usize) {
+    let indexed = |v: u16| u8::try_from(v).ok().map(Color::Indexed);
+    let head = params[0];
+    if head.len() > 1 {
+        let color = match head[1] {
+            5 => head.get(2).copied().and_then(indexed),
+            // The color-space id slot is optional: `38:2:r:g:b` or `38:2:id:r:g:b`.
+            2 => match head[2..] {
+                [_, r, g, b, ..] | [r, g, b] => rgb(r, g, b),
+                _ => None,
+            },
+            _ => None,
+        };
+        return (color, 1);
+    }
+    let arg = |n: usize| params.get(n).map(|p| p[0]);
+    match arg(1) {
+        Some(5) => (arg(2).and_then(indexed), 3),
+        Some(2) => {
+            let color = match (arg(2), arg(3), arg(4)) {
+                (Some(r), Some(g), Some(b)) => rgb(r, g, b),
+                _ => None,
+            };
+            (color, 5)
+        }
+        _ => (None, 1),
+    }
+}
+
+struct HtmlWriter {
+    out: String,
+    style: Style,
+    /// Style of the currently open `<span>`, if any.
+    open: Option<Style>,
+}
+
+impl HtmlWriter {
+    /// Make sure the open span (if any) matches the current style before text
+    /// is written. Spans are opened lazily so style changes with no text in
+    /// between never produce empty elements.
+    fn sync_span(&mut self) {
+        if self.open == Some(self.style) {
+            return;
+        }
+        if self.open.take().is_some() {
+            self.out.push_str("</span>");
+        }
+        if self.style != Style::default() {
+            let _ = write!(self.out, "<span style=\"{}\">", self.style.css());
+            self.open = Some(self.style);
+        }
+    }
+
+    fn finish(mut self) -> String {
+        if self.open.is_some() {
+            self.out.push_str("</span>");
+        }
+        self.out
+    }
+}
+
+impl Perform for HtmlWriter {
+    fn print(&mut self, c: char) {
+        self.sync_span();
+        match c {
+            '&' => self.out.push_str("&amp;"),
+            '<' => self.out.push_str("&lt;"),
+            '>' => self.out.push_str("&gt;"),
+            '"' => self.out.push_str("&quot;"),
+            '\'' => self.out.push_str("&#39;"),
+            _ => self.out.push(c),
+        }
+    }
+
+    fn execute(&mut self, byte: u8) {
+        if matches!(byte, b'\n' | b'\t') {
+            self.sync_span();
+            self.out.push(char::from(byte));
+        }
+    }
+
+    fn csi_dispatch(&mut self, params: &Params, _intermediates: &[u8], ignore: bool, action: char) {
+        if action == 'm' && !ignore {
+            self.style.apply_sgr(params);
+        }
+    }
+}
+
+/// Convert terminal output to HTML. SGR styling (16/256/truecolor, bold, dim,
+/// italic, underline, strikethrough) becomes inline-styled spans; text is
+/// HTML-escaped and every other escape sequence is dropped.
+pub(crate) fn to_html(bytes: &[u8]) -> String {
+    let mut writer = HtmlWriter {
+        out: String::with_capacity(bytes.len()),
+        style: Style::default(),
+        open: None,
+    };
+    Parser::new().advance(&mut writer, bytes);
+    writer.finish()
+}
+
+/// Convert ANSI-colored text (logs, captured output) to HTML
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn ansi_to_html(text: String) -> String {
+    to_html(text.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_is_escaped() {
+        assert_eq!(
+            to_html(b"a < b && \"c\"\n"),
+            "a &lt; b &amp;&amp; &quot;c&quot;\n"
+        );
+    }
+
+    #[test]
+    fn basic_color() {
+        assert_eq!(
+            to_html(b"\x1b[31mred\x1b[0m plain"),
+            "<span style=\"color:#cd0000\">red</span> plain"
+        );
+        assert_eq!(
+            to_html(b"\x1b[92;44mhi\x1b[m"),
+            "<span style=\"color:#00ff00;background-color:#0000ee\">hi</span>"
+        );
+    }
+
+    #[test]
+    fn bold_and_color_combine() {
+        assert_eq!(
+            to_html(b"\x1b[1m\x1b[32mok\x1b[22m!\x1b[39m"),
+            "<span style=\"color:#00cd00;font-weight:bold\">ok</span>\
+             <span style=\"color:#00cd00\">!</span>"
+        );
+    }
+
+    #[test]
+    fn color_256() {
+        // 208 = cube (5, 2, 0) → ff8700; 244 = gray 8 + 12*10.
+        assert_eq!(
+            to_html(b"\x1b[38;5;208ma\x1b[48;5;244mb"),
+            "<span style=\"color:#ff8700\">a</span>\
+             <span style=\"color:#ff8700;background-color:#808080\">b</span>"
+        );
+        assert_eq!(
+            to_html(b"\x1b[38:5:1mc"),
+            "<span style=\"color:#cd0000\">c</span>"
+        );
+    }
+
+    #[test]
+    fn truecolor() {
+        assert_eq!(
+            to_html(b"\x1b[38;2;18;52;86;1mx"),
+            "<span style=\"color:#123456;font-weight:bold\">x</span>"
+        );
+        assert_eq!(
+            to_html(b"\x1b[48:2::255:0:128my"),
+            "<span style=\"background-color:#ff0080\">y</span>"
+        );
+    }
+
+    #[test]
+    fn reset_closes_open_span_and_unclosed_span_is_closed_at_end() {
+        let html = to_html(b"\x1b[1;4;31mA\x1b[0mB\x1b[3mC");
+        assert_eq!(
+            html,
+            "<span style=\"color:#cd0000;font-weight:bold;text-decoration:underline\">A</span>\
+             B<span style=\"font-style:italic\">C</span>"
+        );
+        assert_eq!(
+            html.matches("<span").count(),
+            html.matches("</span>").count()
+        );
+    }
+
+    #[test]
+    fn non_sgr_escapes_are_dropped() {
+        assert_eq!(to_html(b"\x1b]0;title\x07\x1b[2K\x1b[1Gdone\r\n"), "done\n");
+    }
+}
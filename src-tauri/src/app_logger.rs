@@ -334,36 +334,154 @@ impl tracing::field::Visit for LogVisitor {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Tracing → JSON stderr layer
+// ---------------------------------------------------------------------------
+
+/// A `tracing_subscriber::Layer` that writes each event as one JSON object per
+/// line: `{ts, level, target, message, fields}`. Replaces the human-readable
+/// fmt layer on stderr when [`LogFormat::Json`] is selected.
+///
+/// [`LogFormat::Json`]: crate::config::LogFormat::Json
+struct JsonLogLayer<W> {
+    make_writer: W,
+}
+
+impl<S, W> tracing_subscriber::Layer<S> for JsonLogLayer<W>
+where
+    S: tracing::Subscriber,
+    W: for<'a> tracing_subscriber::fmt::MakeWriter<'a> + 'static,
+{
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut visitor = LogVisitor::default();
+        event.record(&mut visitor);
+
+        let mut fields = visitor.extra.take().unwrap_or_default();
+        if let Some(source) = visitor.source.take() {
+            fields.insert("source".to_string(), source);
+        }
+        if let Some(audience) = visitor.audience.take() {
+            fields.insert("audience".to_string(), audience);
+        }
+        let meta = event.metadata();
+        let line = serde_json::json!({
+            "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            "level": meta.level().as_str(),
+            "target": meta.target(),
+            "message": visitor.message.unwrap_or_default(),
+            "fields": fields,
+        });
+
+        // serde_json escapes embedded newlines, so this is always one line.
+        let mut buf = line.to_string();
+        buf.push('\n');
+        let _ = std::io::Write::write_all(&mut self.make_writer.make_writer(), buf.as_bytes());
+    }
+}
+
+/// Resolve the stderr log format: `TUIC_LOG_FORMAT` (`text`/`json`) wins,
+/// then `log_format` in config.json. The file is peeked directly rather than
+/// via `load_app_config()` because tracing isn't up yet to report its errors.
+fn resolve_log_format() -> crate::config::LogFormat {
+    let from_str = |s: &str| {
+        serde_json::from_value(serde_json::Value::String(s.trim().to_ascii_lowercase())).ok()
+    };
+    if let Some(format) = std::env::var("TUIC_LOG_FORMAT")
+        .ok()
+        .and_then(|v| from_str(&v))
+    {
+        return format;
+    }
+    std::fs::read_to_string(crate::config::config_dir().join(crate::config::APP_CONFIG_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        .and_then(|v| v.get("log_format")?.as_str().and_then(from_str))
+        .unwrap_or_default()
+}
+
+/// Resolve the level filter: `RUST_LOG` wins, then `log_level` in
+/// config.json, then `info`. Peeked like [`resolve_log_format`]. An invalid
+/// `log_level` falls back to `info`; the error is returned so it can be
+/// logged once the subscriber is up.
+fn resolve_log_filter() -> (tracing_subscriber::EnvFilter, Option<String>) {
+    if let Ok(filter) = tracing_subscriber::EnvFilter::try_from_default_env() {
+        return (filter, None);
+    }
+    let configured =
+        std::fs::read_to_string(crate::config::config_dir().join(crate::config::APP_CONFIG_FILE))
+            .ok()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+            .and_then(|v| v.get("log_level")?.as_str().map(|l| l.trim().to_string()))
+            .filter(|l| !l.is_empty());
+    match configured {
+        Some(level) => match tracing_subscriber::EnvFilter::try_new(&level) {
+            Ok(filter) => (filter, None),
+            Err(e) => (
+                tracing_subscriber::EnvFilter::new("info"),
+                Some(format!("Invalid log_level {level:?} ({e}), using info")),
+            ),
+        },
+        None => (tracing_subscriber::EnvFilter::new("info"), None),
+    }
+}
+
 /// Max age for rotated log files before cleanup.
 const LOG_RETENTION_DAYS: u64 = 5;
 
+/// File name prefix of the daily log; the appender adds a `.YYYY-MM-DD` suffix.
+const LOG_FILE_PREFIX: &str = "tuic.log";
+
+/// Directory holding the rotated log files.
+pub(crate) fn log_dir() -> std::path::PathBuf {
+    crate::config::config_dir().join("logs")
+}
+
 /// Initialise the global `tracing` subscriber.
 ///
 /// Sets up three layers:
-/// 1. **fmt** — writes structured logs to stderr (visible in `tauri dev`).
+/// 1. **fmt** — writes structured logs to stderr (visible in `tauri dev`);
+///    one JSON object per line instead when [`resolve_log_format`] says so.
 /// 2. **RingBufferLayer** — forwards events into the shared [`LogRingBuffer`]
 ///    so they appear in the ErrorLogPanel and the `/logs` HTTP endpoint.
 /// 3. **file** — daily-rotated log files in the config dir's `logs/` folder,
 ///    surviving WebView crashes. Old files (> 5 days) are cleaned up at init.
 ///
-/// The default level is `info`; override with `RUST_LOG=debug` etc.
+/// The level comes from [`resolve_log_filter`] (`RUST_LOG`, then `log_level`).
+/// The subscriber is process-global, so the HTTP server, PTY reader threads
+/// and `spawn_blocking` workers all log through it.
 /// Must be called exactly once, early in `run()`.
 pub(crate) fn init_tracing(buffer: Arc<Mutex<LogRingBuffer>>) {
-    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
-
-    let fmt_layer = tracing_subscriber::fmt::layer()
-        .with_target(true)
-        .with_thread_ids(false)
-        .with_writer(std::io::stderr);
+    let (env_filter, filter_error) = resolve_log_filter();
+
+    let (fmt_layer, json_layer) = match resolve_log_format() {
+        crate::config::LogFormat::Text => (
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .with_target(true)
+                    .with_thread_ids(false)
+                    .with_writer(std::io::stderr),
+            ),
+            None,
+        ),
+        crate::config::LogFormat::Json => (
+            None,
+            Some(JsonLogLayer {
+                make_writer: std::io::stderr,
+            }),
+        ),
+    };
 
     let ring_layer = RingBufferLayer { buffer };
 
-    let log_dir = crate::config::config_dir().join("logs");
+    let log_dir = log_dir();
     let _ = std::fs::create_dir_all(&log_dir);
     cleanup_old_logs(&log_dir);
 
-    let file_appender = tracing_appender::rolling::daily(&log_dir, "tuic.log");
+    let file_appender = tracing_appender::rolling::daily(&log_dir, LOG_FILE_PREFIX);
     let file_layer = tracing_subscriber::fmt::layer()
         .with_target(true)
         .with_thread_ids(true)
@@ -373,6 +491,7 @@ pub(crate) fn init_tracing(buffer: Arc<Mutex<LogRingBuffer>>) {
     let registry = tracing_subscriber::registry()
         .with(env_filter)
         .with(fmt_layer)
+        .with(json_layer)
         .with(ring_layer)
         .with(file_layer);
 
@@ -385,6 +504,10 @@ pub(crate) fn init_tracing(buffer: Arc<Mutex<LogRingBuffer>>) {
     {
         registry.init();
     }
+
+    if let Some(error) = filter_error {
+        tracing::warn!(source = "config", "{error}");
+    }
 }
 
 /// Remove log files older than [`LOG_RETENTION_DAYS`].
@@ -396,7 +519,12 @@ fn cleanup_old_logs(log_dir: &std::path::Path) {
     };
     for entry in entries.flatten() {
         let path = entry.path();
-        if path.extension().and_then(|e| e.to_str()) != Some("log") {
+        // Rotated files are `tuic.log.YYYY-MM-DD`, so match on the prefix.
+        let is_log = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(LOG_FILE_PREFIX));
+        if !is_log {
             continue;
         }
         let Ok(meta) = path.metadata() else { continue };
@@ -409,10 +537,34 @@ fn cleanup_old_logs(log_dir: &std::path::Path) {
     }
 }
 
+/// The log file currently being written: the most recently modified one.
+fn current_log_file(log_dir: &std::path::Path) -> Option<std::path::PathBuf> {
+    std::fs::read_dir(log_dir)
+        .ok()?
+        .flatten()
+        .filter(|e| {
+            e.file_name()
+                .to_str()
+                .is_some_and(|n| n.starts_with(LOG_FILE_PREFIX))
+        })
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
 // ---------------------------------------------------------------------------
 // Tauri commands
 // ---------------------------------------------------------------------------
 
+/// Absolute path of today's log file, for "show log file" in bug reports.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn get_log_path() -> Result<String, String> {
+    let dir = log_dir();
+    current_log_file(&dir)
+        .map(|p| p.to_string_lossy().to_string())
+        .ok_or_else(|| format!("No log file in {}", dir.display()))
+}
+
 /// Push a log entry from the frontend into the Rust ring buffer.
 #[cfg(feature = "desktop")]
 #[tauri::command]
@@ -480,6 +632,93 @@ pub(crate) fn log_via_state(state: &Arc<AppState>, level: &str, source: &str, me
 mod tests {
     use super::*;
 
+    #[test]
+    fn current_log_file_picks_newest_rotated_file() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(current_log_file(dir.path()), None);
+
+        let old = dir.path().join("tuic.log.2026-01-01");
+        let new = dir.path().join("tuic.log.2026-01-02");
+        std::fs::write(&old, "old").unwrap();
+        std::fs::write(dir.path().join("other.txt"), "x").unwrap();
+        std::fs::write(&new, "new").unwrap();
+        let past = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        std::fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(past)
+            .unwrap();
+
+        assert_eq!(current_log_file(dir.path()), Some(new));
+    }
+
+    #[test]
+    fn cleanup_old_logs_removes_expired_rotated_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let expired = dir.path().join("tuic.log.2026-01-01");
+        let fresh = dir.path().join("tuic.log.2026-01-09");
+        std::fs::write(&expired, "old").unwrap();
+        std::fs::write(&fresh, "new").unwrap();
+        let past = std::time::SystemTime::now()
+            - std::time::Duration::from_secs((LOG_RETENTION_DAYS + 1) * 24 * 3600);
+        std::fs::File::options()
+            .write(true)
+            .open(&expired)
+            .unwrap()
+            .set_modified(past)
+            .unwrap();
+
+        cleanup_old_logs(dir.path());
+        assert!(!expired.exists());
+        assert!(fresh.exists());
+    }
+
+    #[test]
+    fn json_layer_writes_one_valid_json_line_per_event() {
+        #[derive(Clone, Default)]
+        struct Sink(Arc<Mutex<Vec<u8>>>);
+        impl std::io::Write for Sink {
+            fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().extend_from_slice(data);
+                Ok(data.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let sink = Sink::default();
+        let writer = sink.clone();
+        let subscriber = tracing_subscriber::registry().with(JsonLogLayer {
+            make_writer: move || writer.clone(),
+        });
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(
+                source = "git",
+                repo = "/r",
+                attempts = 3u64,
+                "fetch failed:\nauth"
+            );
+        });
+
+        let out = String::from_utf8(sink.0.lock().clone()).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 1, "expected a single line, got {out:?}");
+        let v: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(v["level"], "WARN");
+        assert_eq!(v["target"], module_path!());
+        assert_eq!(v["message"], "fetch failed:\nauth");
+        assert_eq!(v["fields"]["source"], "git");
+        assert_eq!(v["fields"]["repo"], "/r");
+        assert_eq!(v["fields"]["attempts"], "3");
+        assert!(
+            chrono::DateTime::parse_from_rfc3339(v["ts"].as_str().unwrap()).is_ok(),
+            "ts must be RFC 3339: {}",
+            v["ts"]
+        );
+    }
+
     #[test]
     fn push_assigns_monotonic_ids() {
         let mut buf = LogRingBuffer::new(10);
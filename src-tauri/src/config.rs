@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 
 /// Test-only override for the config directory.
@@ -158,18 +158,72 @@ pub(crate) fn load_json_config<T: DeserializeOwned + Default>(filename: &str) ->
         Ok(v) => v,
         Err(e) => {
             tracing::error!(path = %path.display(), "Corrupt config: {e}. Using defaults.");
+            backup_corrupt_config(&path);
             T::default()
         }
     }
 }
 
+/// Backups of corrupt config files moved aside at load time, not yet shown to the user.
+static CORRUPT_CONFIG_BACKUPS: std::sync::Mutex<Vec<PathBuf>> = std::sync::Mutex::new(Vec::new());
+
+/// Move a config file that failed to parse to `<name>.corrupt-<unix_secs>` so
+/// the next save doesn't destroy it, and queue a notice for the frontend.
+fn backup_corrupt_config(path: &std::path::Path) {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".corrupt-{secs}"));
+    let backup = PathBuf::from(backup);
+    match std::fs::rename(path, &backup) {
+        Ok(()) => {
+            tracing::warn!(path = %path.display(), backup = %backup.display(), "Backed up corrupt config; starting from defaults");
+            CORRUPT_CONFIG_BACKUPS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(backup);
+        }
+        Err(e) => {
+            tracing::error!(path = %path.display(), "Failed to back up corrupt config: {e}");
+        }
+    }
+}
+
+/// Drain the backup paths of configs found corrupt since the last call.
+/// The frontend calls this once at startup to warn the user.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn take_config_recovery_notices() -> Vec<String> {
+    std::mem::take(
+        &mut *CORRUPT_CONFIG_BACKUPS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()),
+    )
+    .into_iter()
+    .map(|p| p.to_string_lossy().into_owned())
+    .collect()
+}
+
 /// Atomically write `data` to `target` via temp+rename with 0600 perms.
 pub(crate) fn persist_atomic(target: &std::path::Path, data: &[u8]) -> Result<(), String> {
     if let Some(dir) = target.parent() {
         std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {e}"))?;
     }
     let temp = target.with_extension(format!("tmp.{}", std::process::id()));
-    std::fs::write(&temp, data).map_err(|e| format!("Failed to write temp file: {e}"))?;
+    {
+        use std::io::Write;
+        let mut file =
+            std::fs::File::create(&temp).map_err(|e| format!("Failed to write temp file: {e}"))?;
+        // Flush to disk before the rename so a crash can't leave the target
+        // pointing at a truncated file.
+        file.write_all(data)
+            .and_then(|()| file.sync_all())
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&temp);
+                format!("Failed to write temp file: {e}")
+            })?;
+    }
 
     #[cfg(unix)]
     {
@@ -208,6 +262,16 @@ pub(crate) enum SplitTabMode {
     Unified,
 }
 
+/// Stderr log line format. `Json` writes one object per line for log
+/// shippers (journald, Loki); the rotated log file stays human-readable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
 /// Tab ordering mode for the tab bar
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -293,6 +357,17 @@ pub(crate) struct ServerConfig {
     pub(crate) port: u16,
     #[serde(default)]
     pub(crate) ipv6_enabled: bool,
+    /// IP the remote-access listener binds to. `0.0.0.0` (default) binds every
+    /// interface; a specific address (e.g. the Tailscale IP) must exist on
+    /// this machine, otherwise the server falls back to `127.0.0.1`.
+    #[serde(default = "default_bind_address")]
+    pub(crate) bind_address: String,
+    /// Restrict the listener to one network interface (e.g. `tailscale0`),
+    /// binding that interface's address. Only applies when `bind_address` is
+    /// the wildcard; IPv6 addresses are considered only with `ipv6_enabled`.
+    /// An interface with no usable address falls back to `127.0.0.1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) bind_interface: Option<String>,
 }
 
 impl Default for ServerConfig {
@@ -301,6 +376,8 @@ impl Default for ServerConfig {
             enabled: false,
             port: default_remote_port(),
             ipv6_enabled: false,
+            bind_address: default_bind_address(),
+            bind_interface: None,
         }
     }
 }
@@ -321,6 +398,13 @@ pub(crate) struct AuthConfig {
     pub(crate) auth_rate_limit_max: u32,
     #[serde(default = "default_auth_rate_limit_window_secs")]
     pub(crate) auth_rate_limit_window_secs: u64,
+    /// Turn remote access off (config flipped, server restarted localhost-only)
+    /// once this many distinct IPs failed auth within
+    /// `auto_disable_remote_window_secs`. 0 = disabled.
+    #[serde(default)]
+    pub(crate) auto_disable_remote_after_failures: u32,
+    #[serde(default = "default_auto_disable_remote_window_secs")]
+    pub(crate) auto_disable_remote_window_secs: u64,
 }
 
 fn default_auth_rate_limit_max() -> u32 {
@@ -329,6 +413,9 @@ fn default_auth_rate_limit_max() -> u32 {
 fn default_auth_rate_limit_window_secs() -> u64 {
     300
 }
+fn default_auto_disable_remote_window_secs() -> u64 {
+    600
+}
 
 impl Default for AuthConfig {
     fn default() -> Self {
@@ -340,11 +427,13 @@ impl Default for AuthConfig {
             lan_auth_bypass: false,
             auth_rate_limit_max: default_auth_rate_limit_max(),
             auth_rate_limit_window_secs: default_auth_rate_limit_window_secs(),
+            auto_disable_remote_after_failures: 0,
+            auto_disable_remote_window_secs: default_auto_disable_remote_window_secs(),
         }
     }
 }
 
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
 #[serde(tag = "mode", rename_all = "lowercase")]
 pub(crate) enum TlsConfig {
     #[default]
@@ -420,6 +509,23 @@ impl Default for PushConfig {
     }
 }
 
+/// Named remote-access preset (e.g. "home" = LAN without auth, "travel" =
+/// Tailscale IP with auth). Applying one copies these settings over
+/// `server` / `auth` / `tls`; the on/off switch and credentials stay global.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct RemoteProfile {
+    #[serde(default = "default_bind_address")]
+    pub(crate) bind_address: String,
+    #[serde(default = "default_remote_port")]
+    pub(crate) port: u16,
+    #[serde(default)]
+    pub(crate) ipv6_enabled: bool,
+    #[serde(default)]
+    pub(crate) lan_auth_bypass: bool,
+    #[serde(default)]
+    pub(crate) tls: TlsConfig,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub(crate) struct ServicesConfig {
     #[serde(default)]
@@ -432,9 +538,46 @@ pub(crate) struct ServicesConfig {
     pub(crate) relay: RelayConfig,
     #[serde(default)]
     pub(crate) push: PushConfig,
+    /// Named remote-access presets, applied by `switch_remote_profile`.
+    #[serde(default)]
+    pub(crate) remote_profiles: std::collections::BTreeMap<String, RemoteProfile>,
+    /// Profile last applied by `switch_remote_profile`.
+    #[serde(default)]
+    pub(crate) active_remote_profile: Option<String>,
 }
 
 impl ServicesConfig {
+    /// Whether switching from `self` to `new` needs a server restart: any
+    /// listener, auth or TLS setting differs (everything a profile can change).
+    pub(crate) fn listener_changed(&self, new: &ServicesConfig) -> bool {
+        self.server.enabled != new.server.enabled
+            || self.server.port != new.server.port
+            || self.auth.username != new.auth.username
+            || self.auth.password_hash != new.auth.password_hash
+            || self.server.ipv6_enabled != new.server.ipv6_enabled
+            || self.server.bind_address != new.server.bind_address
+            || self.server.bind_interface != new.server.bind_interface
+            || self.auth.lan_auth_bypass != new.auth.lan_auth_bypass
+            || self.tls != new.tls
+    }
+
+    /// Copy profile `name` over the effective server/auth/TLS settings and mark
+    /// it active. Errors for an unknown name without touching anything.
+    pub(crate) fn apply_remote_profile(&mut self, name: &str) -> Result<(), String> {
+        let profile = self
+            .remote_profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown remote access profile '{name}'"))?;
+        self.server.bind_address = profile.bind_address;
+        self.server.port = profile.port;
+        self.server.ipv6_enabled = profile.ipv6_enabled;
+        self.auth.lan_auth_bypass = profile.lan_auth_bypass;
+        self.tls = profile.tls;
+        self.active_remote_profile = Some(name.to_string());
+        Ok(())
+    }
+
     #[allow(dead_code)]
     pub(crate) fn validate(&self) -> Vec<String> {
         let mut warnings = Vec::new();
@@ -602,6 +745,105 @@ pub(crate) struct AppConfig {
     /// Show GitLens-style inline git blame on the active line in the code editor.
     #[serde(default = "default_true")]
     pub(crate) inline_blame_enabled: bool,
+    /// When set, agent-spawned sessions append their full output to
+    /// `<dir>/<repo-slug>/<timestamp>-<session>.log` (see `transcript.rs`).
+    #[serde(default)]
+    pub(crate) agent_transcript_dir: Option<String>,
+    /// Total size cap for the transcript dir in MB; oldest files are pruned first. 0 = no cap.
+    #[serde(default = "default_agent_transcript_max_mb")]
+    pub(crate) agent_transcript_max_mb: u64,
+    /// Pass transcript output through secret redaction before writing.
+    #[serde(default = "default_true")]
+    pub(crate) agent_transcript_redact: bool,
+    /// Structured output events the PTY reader may emit, by wire `type` tag
+    /// (`"status-line"`, `"question"`, …). `None` = all enabled. Detection
+    /// still runs (shell-state tracking relies on it); only emission is dropped.
+    #[serde(default)]
+    pub(crate) enabled_output_events: Option<Vec<String>>,
+    /// Quiet period in ms before a burst of PTY resizes is applied (only the
+    /// last size). 0 = apply every resize immediately.
+    #[serde(default = "default_resize_debounce_ms")]
+    pub(crate) resize_debounce_ms: u64,
+    /// Skip background GitHub/git polling while the window is unfocused
+    /// (see `set_poll_active`).
+    #[serde(default = "default_true")]
+    pub(crate) pause_polling_when_unfocused: bool,
+    /// Extra attempts when opening a PTY or spawning its shell fails with a
+    /// transient error (fd exhaustion, EAGAIN). Permanent errors never retry.
+    #[serde(default = "default_pty_spawn_retries")]
+    pub(crate) pty_spawn_retries: u32,
+    /// Seconds of silence after input (same foreground process) before
+    /// `session-possibly-wedged` is emitted. 0 = disabled.
+    #[serde(default)]
+    pub(crate) pty_wedge_timeout_secs: u64,
+    /// Extra rate-limit / API-error regexes (e.g. for in-house LLM proxies),
+    /// checked after the built-in patterns. Invalid regexes are logged and skipped.
+    #[serde(default)]
+    pub(crate) custom_output_patterns: Vec<CustomOutputPattern>,
+    /// Built-in output detectors to skip entirely, by wire `type` tag
+    /// (`"question"`, `"rate-limit"`, `"plan-file"`, `"intent"`). Unlike
+    /// `enabled_output_events` the detector never runs. Read when a session starts.
+    #[serde(default)]
+    pub(crate) disabled_parsers: BTreeSet<String>,
+    /// Cap on concurrent PTY sessions. Clamped to 1..=1000 at use
+    /// (see `AppState::max_sessions`).
+    #[serde(default = "default_max_concurrent_sessions")]
+    pub(crate) max_concurrent_sessions: usize,
+    /// Reconstruct typed lines from keystrokes and emit them (`user-input`
+    /// events, slash-command detection, last-prompt capture). When off, the
+    /// input buffer still tracks line editing for slash mode and submit
+    /// detection but never surfaces what was typed.
+    #[serde(default = "default_true")]
+    pub(crate) reconstruct_user_input: bool,
+    /// Allow the MCP `fs` tool to write files and create directories.
+    /// Reads and listings are always available; writes are loopback-only
+    /// even when this is on.
+    #[serde(default)]
+    pub(crate) mcp_allow_file_writes: bool,
+    /// Stderr log format; read once at startup. `TUIC_LOG_FORMAT` overrides.
+    #[serde(default)]
+    pub(crate) log_format: LogFormat,
+    /// Log level filter (`"debug"`, `"warn"`, or `tracing` directives such as
+    /// `"info,tuicommander_lib::pty=debug"`); read once at startup. `RUST_LOG`
+    /// overrides. `None` = `info`.
+    #[serde(default)]
+    pub(crate) log_level: Option<String>,
+    /// Size cap for session transcript exports; the oldest output is dropped
+    /// (with a marker) past it. 0 = no cap.
+    #[serde(default = "default_max_export_bytes")]
+    pub(crate) max_export_bytes: u64,
+    /// Pass session transcript exports through secret redaction.
+    #[serde(default = "default_true")]
+    pub(crate) redact_secrets: bool,
+}
+
+/// Which parsed event a [`CustomOutputPattern`] produces.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CustomPatternKind {
+    RateLimit,
+    ApiError,
+    /// Marks the end of an agent turn (see `ParsedEvent::TaskComplete`).
+    TaskComplete,
+    /// Usage footer with `input`, `output` and/or `cost` named groups
+    /// (see `ParsedEvent::TokenUsage`).
+    TokenUsage,
+}
+
+/// User-defined output detection pattern (see `output_parser::compile_custom_patterns`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CustomOutputPattern {
+    /// Reported as `pattern_name` on the emitted event.
+    pub(crate) name: String,
+    pub(crate) regex: String,
+    pub(crate) kind: CustomPatternKind,
+    /// `api_error` only: "server", "auth", "unknown" (default "unknown").
+    #[serde(default)]
+    pub(crate) error_kind: Option<String>,
+    /// `rate_limit` only: fixed retry delay. A first capture group holding
+    /// seconds takes precedence; 60s when neither is present.
+    #[serde(default)]
+    pub(crate) retry_after_ms: Option<u64>,
 }
 
 /// A user-defined launcher for the "Open in" menu. The executable is spawned
@@ -682,6 +924,30 @@ fn default_remote_port() -> u16 {
     9876
 }
 
+fn default_bind_address() -> String {
+    "0.0.0.0".to_string()
+}
+
+fn default_agent_transcript_max_mb() -> u64 {
+    1024
+}
+
+fn default_resize_debounce_ms() -> u64 {
+    50
+}
+
+fn default_pty_spawn_retries() -> u32 {
+    2
+}
+
+fn default_max_concurrent_sessions() -> usize {
+    crate::state::MAX_CONCURRENT_SESSIONS
+}
+
+fn default_max_export_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -733,6 +999,23 @@ impl Default for AppConfig {
             standby_timeout_minutes: default_standby_timeout(),
             custom_launchers: Vec::new(),
             inline_blame_enabled: true,
+            agent_transcript_dir: None,
+            agent_transcript_max_mb: default_agent_transcript_max_mb(),
+            agent_transcript_redact: true,
+            enabled_output_events: None,
+            resize_debounce_ms: default_resize_debounce_ms(),
+            pause_polling_when_unfocused: true,
+            pty_spawn_retries: default_pty_spawn_retries(),
+            pty_wedge_timeout_secs: 0,
+            custom_output_patterns: Vec::new(),
+            log_format: LogFormat::Text,
+            log_level: None,
+            max_export_bytes: default_max_export_bytes(),
+            redact_secrets: true,
+            disabled_parsers: BTreeSet::new(),
+            max_concurrent_sessions: default_max_concurrent_sessions(),
+            reconstruct_user_input: true,
+            mcp_allow_file_writes: false,
         }
     }
 }
@@ -742,6 +1025,13 @@ impl AppConfig {
     pub(crate) fn is_experimental_enabled(&self, sub_flag: bool) -> bool {
         self.experimental_features_enabled && sub_flag
     }
+
+    /// Whether parsed output events of wire type `kind` should be emitted.
+    pub(crate) fn output_event_enabled(&self, kind: &str) -> bool {
+        self.enabled_output_events
+            .as_ref()
+            .is_none_or(|enabled| enabled.iter().any(|e| e == kind))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -784,6 +1074,45 @@ pub(crate) struct NotificationConfig {
     pub(crate) sounds: NotificationSounds,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) audio_device: Option<String>,
+    /// Minimum seconds between notifications of the same type for the same
+    /// session (0 = no throttle).
+    #[serde(default)]
+    pub(crate) min_interval_secs: u64,
+    /// Local-time window during which only question/error notifications fire.
+    #[serde(default)]
+    pub(crate) quiet_hours: Option<QuietHours>,
+}
+
+/// Daily quiet-hours window as local `HH:MM` times. `start > end` wraps
+/// past midnight (e.g. 22:00–07:00).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct QuietHours {
+    pub(crate) start: String,
+    pub(crate) end: String,
+}
+
+impl QuietHours {
+    fn parse_minutes(hhmm: &str) -> Option<u32> {
+        let (h, m) = hhmm.trim().split_once(':')?;
+        let (h, m): (u32, u32) = (h.parse().ok()?, m.parse().ok()?);
+        (h < 24 && m < 60).then_some(h * 60 + m)
+    }
+
+    /// True if `minute_of_day` (0..1440) falls inside the window. An
+    /// unparseable or empty (start == end) window never matches.
+    pub(crate) fn contains(&self, minute_of_day: u32) -> bool {
+        let (Some(start), Some(end)) = (
+            Self::parse_minutes(&self.start),
+            Self::parse_minutes(&self.end),
+        ) else {
+            return false;
+        };
+        if start <= end {
+            (start..end).contains(&minute_of_day)
+        } else {
+            minute_of_day >= start || minute_of_day < end
+        }
+    }
 }
 
 fn default_true() -> bool {
@@ -801,6 +1130,8 @@ impl Default for NotificationConfig {
             volume: 0.5,
             sounds: NotificationSounds::default(),
             audio_device: None,
+            min_interval_secs: 0,
+            quiet_hours: None,
         }
     }
 }
@@ -844,6 +1175,19 @@ pub(crate) struct UIPrefsConfig {
     pub(crate) diff_view_mode: String,
     #[serde(default)]
     pub(crate) detached_panels: std::collections::HashMap<String, String>,
+    /// Authoritative active repo + checkout. Owned by `set_active_selection`;
+    /// `save_ui_prefs` keeps the stored value when the caller omits it.
+    #[serde(default)]
+    pub(crate) active_selection: Option<ActiveSelection>,
+}
+
+/// The repo and checkout whose state the UI shows (see `set_active_selection`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ActiveSelection {
+    pub(crate) repo_path: String,
+    /// Linked worktree being shown; `None` = the repo's main checkout.
+    #[serde(default)]
+    pub(crate) worktree_path: Option<String>,
 }
 
 fn default_diff_view_mode() -> String {
@@ -869,6 +1213,7 @@ impl Default for UIPrefsConfig {
             settings_nav_width: default_settings_nav_width(),
             diff_view_mode: default_diff_view_mode(),
             detached_panels: std::collections::HashMap::new(),
+            active_selection: None,
         }
     }
 }
@@ -1002,9 +1347,21 @@ pub(crate) struct RepoSettingsEntry {
     /// Allowlist of upstream MCP server names relevant to this repo (None = all)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) mcp_upstreams: Option<Vec<String>>,
+    /// Repo-relative `.env`-style file merged into spawned agent/worktree
+    /// session environments (None = no env file). Must resolve inside the repo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) env_file: Option<String>,
+    /// Variables merged into spawned agent/worktree session environments on
+    /// top of `env_file` (a key set in both takes this value).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub(crate) env: HashMap<String, String>,
     /// Human-readable labels for branches/worktrees, keyed by branch name
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub(crate) branch_labels: HashMap<String, String>,
+    /// Absolute directory for new worktrees, overriding `worktree_storage` and
+    /// the repo defaults (None = use them). Must be writable and outside the repo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) worktrees_dir: Option<String>,
 }
 
 impl RepoSettingsEntry {
@@ -1027,7 +1384,10 @@ impl RepoSettingsEntry {
             || self.auto_fetch_interval_minutes.is_some()
             || self.auto_delete_on_pr_close.is_some()
             || self.mcp_upstreams.is_some()
+            || self.env_file.is_some()
+            || !self.env.is_empty()
             || !self.branch_labels.is_empty()
+            || self.worktrees_dir.is_some()
     }
 }
 
@@ -1115,6 +1475,13 @@ pub(crate) struct PromptEntry {
     pub(crate) text: String,
     #[serde(default)]
     pub(crate) pinned: bool,
+    /// Times the prompt was actually sent (previews don't count).
+    /// Backend-owned: [`save_prompt_library`] keeps the stored value.
+    #[serde(default)]
+    pub(crate) use_count: u64,
+    /// Unix millis of the last send.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) last_used_at: Option<u64>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Default)]
@@ -1191,7 +1558,7 @@ pub(crate) struct AgentsConfig {
 // Tauri commands — one load/save pair per config type
 // ---------------------------------------------------------------------------
 
-const APP_CONFIG_FILE: &str = "config.json";
+pub(crate) const APP_CONFIG_FILE: &str = "config.json";
 const NOTIFICATION_CONFIG_FILE: &str = "notifications.json";
 const UI_PREFS_FILE: &str = "ui-prefs.json";
 const REPO_SETTINGS_FILE: &str = "repo-settings.json";
@@ -1204,6 +1571,7 @@ const PANE_LAYOUT_FILE: &str = "pane-layout.json";
 const AGENTS_CONFIG_FILE: &str = "agents.json";
 const ACTIVITY_FILE: &str = "activity.json";
 const AI_PROMPTS_FILE: &str = "ai-prompts.json";
+const RECENT_FILES_FILE: &str = "recent-files.json";
 
 // App config
 
@@ -1297,6 +1665,7 @@ pub(crate) fn load_app_config() -> AppConfig {
         Ok(v) => v,
         Err(e) => {
             tracing::error!(path = %path.display(), "Corrupt config: {e}. Using defaults.");
+            backup_corrupt_config(&path);
             return AppConfig::default();
         }
     };
@@ -1305,13 +1674,84 @@ pub(crate) fn load_app_config() -> AppConfig {
         Ok(cfg) => cfg,
         Err(e) => {
             tracing::error!(path = %path.display(), "Config deserialization failed after migration: {e}. Using defaults.");
+            backup_corrupt_config(&path);
             AppConfig::default()
         }
     }
 }
 
+/// Whether `hash` has the shape of a bcrypt hash (`$2b$12$` + 53 chars).
+fn is_bcrypt_hash_shape(hash: &str) -> bool {
+    let b = hash.as_bytes();
+    b.len() == 60
+        && b[0] == b'$'
+        && b[1] == b'2'
+        && matches!(b[2], b'a' | b'b' | b'x' | b'y')
+        && b[3] == b'$'
+        && b[4].is_ascii_digit()
+        && b[5].is_ascii_digit()
+        && b[6] == b'$'
+        && b[7..]
+            .iter()
+            .all(|c| c.is_ascii_alphanumeric() || *c == b'.' || *c == b'/')
+}
+
+impl AppConfig {
+    /// Invariants checked before a save, so an invalid config never reaches disk.
+    pub(crate) fn validate_for_save(&self) -> Result<(), String> {
+        if self.services.server.port == 0 {
+            return Err("Invalid remote access port: must be between 1 and 65535".to_string());
+        }
+        let hash = &self.services.auth.password_hash;
+        if !hash.is_empty() && !is_bcrypt_hash_shape(hash) {
+            return Err("Invalid remote access password hash: not a bcrypt hash".to_string());
+        }
+        let bind = self.services.server.bind_address.trim();
+        if !bind.is_empty() && bind.parse::<std::net::IpAddr>().is_err() {
+            return Err(format!(
+                "Invalid remote access bind address '{bind}': not an IP address"
+            ));
+        }
+        for (name, profile) in &self.services.remote_profiles {
+            if name.trim().is_empty() {
+                return Err("Invalid remote access profile: name is empty".to_string());
+            }
+            if profile.port == 0 {
+                return Err(format!(
+                    "Invalid remote access profile '{name}': port must be between 1 and 65535"
+                ));
+            }
+            let bind = profile.bind_address.trim();
+            if !bind.is_empty() && bind.parse::<std::net::IpAddr>().is_err() {
+                return Err(format!(
+                    "Invalid remote access profile '{name}': bind address '{bind}' is not an IP address"
+                ));
+            }
+            if let TlsConfig::Manual {
+                cert_path,
+                key_path,
+            } = &profile.tls
+                && (cert_path.trim().is_empty() || key_path.trim().is_empty())
+            {
+                return Err(format!(
+                    "Invalid remote access profile '{name}': manual TLS needs cert and key paths"
+                ));
+            }
+        }
+        if let Some(active) = &self.services.active_remote_profile
+            && !self.services.remote_profiles.contains_key(active)
+        {
+            return Err(format!(
+                "Active remote access profile '{active}' does not exist"
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn save_app_config(config: AppConfig) -> Result<(), String> {
+    config.validate_for_save()?;
     save_json_config(APP_CONFIG_FILE, &config)
 }
 
@@ -1321,9 +1761,23 @@ pub(crate) fn load_notification_config() -> NotificationConfig {
     load_json_config(NOTIFICATION_CONFIG_FILE)
 }
 
-#[cfg_attr(feature = "desktop", tauri::command)]
-pub(crate) fn save_notification_config(config: NotificationConfig) -> Result<(), String> {
-    save_json_config(NOTIFICATION_CONFIG_FILE, &config)
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn save_notification_config(
+    state: tauri::State<'_, std::sync::Arc<crate::AppState>>,
+    config: NotificationConfig,
+) -> Result<(), String> {
+    save_notification_config_impl(&state, config)
+}
+
+/// Persist `config` and make it the live config for notification throttling.
+pub(crate) fn save_notification_config_impl(
+    state: &crate::AppState,
+    config: NotificationConfig,
+) -> Result<(), String> {
+    save_json_config(NOTIFICATION_CONFIG_FILE, &config)?;
+    state.notification_throttle.set_config(config);
+    Ok(())
 }
 
 // UI prefs
@@ -1333,10 +1787,86 @@ pub(crate) fn load_ui_prefs() -> UIPrefsConfig {
 }
 
 #[cfg_attr(feature = "desktop", tauri::command)]
-pub(crate) fn save_ui_prefs(config: UIPrefsConfig) -> Result<(), String> {
+pub(crate) fn save_ui_prefs(mut config: UIPrefsConfig) -> Result<(), String> {
+    // Layout saves from the frontend don't carry the backend-owned selection.
+    if config.active_selection.is_none() {
+        config.active_selection = load_ui_prefs().active_selection;
+    }
     save_json_config(UI_PREFS_FILE, &config)
 }
 
+/// Maximum number of recent files remembered per repo.
+pub(crate) const MAX_RECENT_FILES: usize = 20;
+
+/// Move `file` to the front of `list`, dropping duplicates and capping the length.
+fn push_recent_file(list: &mut Vec<String>, file: &str) {
+    list.retain(|f| f != file);
+    list.insert(0, file.to_string());
+    list.truncate(MAX_RECENT_FILES);
+}
+
+/// Recently opened files per repo path, most recent first (`recent-files.json`).
+/// Kept out of `ui-prefs.json` so recording an open never races a layout save.
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct RecentFilesStore {
+    #[serde(default)]
+    repos: std::collections::HashMap<String, Vec<String>>,
+}
+
+/// Serializes the load→modify→save cycle of `recent-files.json`.
+static RECENT_FILES_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Record a file the user explicitly opened (tab, palette, drop) in the repo's
+/// recent list. Files that no longer exist are pruned while the list is rewritten.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn record_recent_file(repo_path: String, file: String) -> Result<(), String> {
+    let rel = std::path::Path::new(&file);
+    if file.is_empty()
+        || rel.is_absolute()
+        || rel
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err(format!("Invalid file path: {file}"));
+    }
+    let _lock = RECENT_FILES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store: RecentFilesStore = load_json_config(RECENT_FILES_FILE);
+    let root = std::path::Path::new(&repo_path);
+    let list = store.repos.entry(repo_path.clone()).or_default();
+    push_recent_file(list, &file);
+    list.retain(|f| root.join(f).is_file());
+    if list.is_empty() {
+        store.repos.remove(&repo_path);
+    }
+    save_json_config(RECENT_FILES_FILE, &store)
+}
+
+/// Recently opened files for a repo, most recent first. Files that no longer
+/// exist are left out of the result; the stored list is only pruned on the
+/// next `record_recent_file`, so reading never writes.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn get_recent_files(repo_path: String) -> Vec<String> {
+    let store: RecentFilesStore = load_json_config(RECENT_FILES_FILE);
+    let root = std::path::Path::new(&repo_path);
+    store
+        .repos
+        .get(&repo_path)
+        .map(|list| {
+            list.iter()
+                .filter(|f| root.join(f).is_file())
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Persist the active selection into the UI prefs file.
+pub(crate) fn save_active_selection(selection: Option<ActiveSelection>) -> Result<(), String> {
+    let mut prefs = load_ui_prefs();
+    prefs.active_selection = selection;
+    save_json_config(UI_PREFS_FILE, &prefs)
+}
+
 // Repo settings
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn load_repo_settings() -> RepoSettingsMap {
@@ -1394,6 +1924,163 @@ pub(crate) fn check_has_custom_settings(path: String) -> bool {
         .is_some_and(|entry| entry.has_custom_settings())
 }
 
+/// Categories of per-repo overrides that `reset_repo_settings` can clear
+/// independently. Display name and branch labels are repo data, not overrides,
+/// and are never reset.
+const REPO_SETTINGS_CATEGORIES: &[&str] = &[
+    "branch",
+    "files",
+    "scripts",
+    "worktree",
+    "merge",
+    "fetch",
+    "appearance",
+    "mcp",
+    "env",
+];
+
+/// Clear every override belonging to `category` so it inherits again.
+fn clear_repo_settings_category(entry: &mut RepoSettingsEntry, category: &str) {
+    match category {
+        "branch" => entry.base_branch = None,
+        "files" => {
+            entry.copy_ignored_files = None;
+            entry.copy_untracked_files = None;
+        }
+        "scripts" => {
+            entry.setup_script = None;
+            entry.run_script = None;
+            entry.archive_script = None;
+        }
+        "worktree" => {
+            entry.worktree_storage = None;
+            entry.worktrees_dir = None;
+            entry.prompt_on_create = None;
+            entry.delete_branch_on_remove = None;
+            entry.auto_archive_merged = None;
+            entry.orphan_cleanup = None;
+            entry.after_merge = None;
+            entry.auto_delete_on_pr_close = None;
+        }
+        "merge" => entry.pr_merge_strategy = None,
+        "fetch" => entry.auto_fetch_interval_minutes = None,
+        "appearance" => entry.color = String::new(),
+        "mcp" => entry.mcp_upstreams = None,
+        "env" => {
+            entry.env_file = None;
+            entry.env.clear();
+        }
+        _ => {}
+    }
+}
+
+/// Layer a repo's overrides (if any) over the global defaults.
+fn resolve_effective_repo_settings(
+    entry: Option<&RepoSettingsEntry>,
+    defaults: &RepoDefaultsConfig,
+) -> RepoDefaultsConfig {
+    let mut eff = defaults.clone();
+    let Some(e) = entry else {
+        return eff;
+    };
+    if let Some(v) = &e.base_branch {
+        eff.base_branch = v.clone();
+    }
+    if let Some(v) = e.copy_ignored_files {
+        eff.copy_ignored_files = v;
+    }
+    if let Some(v) = e.copy_untracked_files {
+        eff.copy_untracked_files = v;
+    }
+    if let Some(v) = &e.setup_script {
+        eff.setup_script = v.clone();
+    }
+    if let Some(v) = &e.run_script {
+        eff.run_script = v.clone();
+    }
+    if let Some(v) = &e.archive_script {
+        eff.archive_script = v.clone();
+    }
+    if let Some(v) = &e.worktree_storage {
+        eff.worktree_storage = v.clone();
+    }
+    if let Some(v) = e.prompt_on_create {
+        eff.prompt_on_create = v;
+    }
+    if let Some(v) = e.delete_branch_on_remove {
+        eff.delete_branch_on_remove = v;
+    }
+    if let Some(v) = e.auto_archive_merged {
+        eff.auto_archive_merged = v;
+    }
+    if let Some(v) = &e.orphan_cleanup {
+        eff.orphan_cleanup = v.clone();
+    }
+    if let Some(v) = &e.pr_merge_strategy {
+        eff.pr_merge_strategy = v.clone();
+    }
+    if let Some(v) = &e.after_merge {
+        eff.after_merge = v.clone();
+    }
+    if let Some(v) = e.auto_fetch_interval_minutes {
+        eff.auto_fetch_interval_minutes = v;
+    }
+    if let Some(v) = &e.auto_delete_on_pr_close {
+        eff.auto_delete_on_pr_close = v.clone();
+    }
+    eff
+}
+
+/// Reset `categories` (all when `None`) of `repo_path`'s overrides in `settings`.
+/// Unknown categories are rejected before anything is modified.
+fn reset_repo_settings_in(
+    settings: &mut RepoSettingsMap,
+    defaults: &RepoDefaultsConfig,
+    repo_path: &str,
+    categories: Option<&[String]>,
+) -> Result<RepoDefaultsConfig, String> {
+    if let Some(cats) = categories
+        && let Some(bad) = cats
+            .iter()
+            .find(|c| !REPO_SETTINGS_CATEGORIES.contains(&c.as_str()))
+    {
+        return Err(format!(
+            "Unknown settings category '{bad}' (expected one of: {})",
+            REPO_SETTINGS_CATEGORIES.join(", ")
+        ));
+    }
+    if let Some(entry) = settings.repos.get_mut(repo_path) {
+        let targets: Vec<&str> = match categories {
+            Some(cats) => cats.iter().map(String::as_str).collect(),
+            None => REPO_SETTINGS_CATEGORIES.to_vec(),
+        };
+        for category in targets {
+            clear_repo_settings_category(entry, category);
+        }
+    }
+    Ok(resolve_effective_repo_settings(
+        settings.repos.get(repo_path),
+        defaults,
+    ))
+}
+
+/// Clear per-repo overrides so the repo falls back to the global repo defaults.
+/// `categories = None` resets every category. Returns the resulting effective settings.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn reset_repo_settings(
+    repo_path: String,
+    categories: Option<Vec<String>>,
+) -> Result<RepoDefaultsConfig, String> {
+    let mut settings: RepoSettingsMap = load_json_config(REPO_SETTINGS_FILE);
+    let defaults = load_repo_defaults();
+    let effective =
+        reset_repo_settings_in(&mut settings, &defaults, &repo_path, categories.as_deref())?;
+    if settings.repos.contains_key(&repo_path) {
+        save_json_config(REPO_SETTINGS_FILE, &settings)?;
+    }
+    Ok(effective)
+}
+
 // Repo local config (.tuic.json in repo root)
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn load_repo_local_config(repo_path: String) -> Option<RepoLocalConfig> {
@@ -1551,17 +2238,143 @@ fn resolve_setup_script_from(
     None
 }
 
-// Repositories (opaque JSON — schema owned by frontend)
+// Repositories (opaque JSON — schema owned by frontend, except ordering:
+// `repoOrder`, each group's `repoOrder` and per-repo `pinned` are normalized
+// here so the desktop and remote UIs agree on sidebar order)
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn load_repositories() -> serde_json::Value {
-    load_json_config(REPOSITORIES_FILE)
+    let mut config = load_json_config(REPOSITORIES_FILE);
+    normalize_repo_order(&mut config);
+    config
+}
+
+/// Root paths of all registered repositories (keys of `repositories`).
+pub(crate) fn registered_repo_paths() -> Vec<String> {
+    load_repositories()
+        .get("repositories")
+        .and_then(|r| r.as_object())
+        .map(|obj| obj.keys().cloned().collect())
+        .unwrap_or_default()
 }
 
 #[cfg_attr(feature = "desktop", tauri::command)]
-pub(crate) fn save_repositories(config: serde_json::Value) -> Result<(), String> {
+pub(crate) fn save_repositories(mut config: serde_json::Value) -> Result<(), String> {
+    normalize_repo_order(&mut config);
     save_json_config(REPOSITORIES_FILE, &config)
 }
 
+/// Move the listed repo paths to the front of the ungrouped order (in the
+/// given sequence), keep the rest in their previous order, and persist.
+/// Pinned repos still sort first. Returns the normalized repositories config.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn reorder_repositories(
+    state: tauri::State<'_, std::sync::Arc<crate::AppState>>,
+    order: Vec<String>,
+) -> Result<serde_json::Value, String> {
+    reorder_repositories_impl(&state, order)
+}
+
+/// Persist the new order and emit `repositories-changed` so open windows and
+/// remote clients reload it.
+pub(crate) fn reorder_repositories_impl(
+    state: &crate::AppState,
+    order: Vec<String>,
+) -> Result<serde_json::Value, String> {
+    let mut config: serde_json::Value = load_json_config(REPOSITORIES_FILE);
+    apply_repo_order(&mut config, &order);
+    save_json_config(REPOSITORIES_FILE, &config)?;
+    let repo_order: Vec<String> = config
+        .get("repoOrder")
+        .and_then(|o| serde_json::from_value(o.clone()).ok())
+        .unwrap_or_default();
+    #[cfg(feature = "desktop")]
+    if let Some(app) = state.app_handle.read().as_ref() {
+        use tauri::Emitter;
+        let _ = app.emit(
+            "repositories-changed",
+            serde_json::json!({ "repo_order": repo_order }),
+        );
+    }
+    let _ = state
+        .event_bus
+        .send(crate::state::AppEvent::RepositoriesChanged { repo_order });
+    Ok(config)
+}
+
+fn apply_repo_order(config: &mut serde_json::Value, order: &[String]) {
+    let Some(obj) = config.as_object_mut() else {
+        return;
+    };
+    let previous = obj.remove("repoOrder").unwrap_or_default();
+    let mut merged: Vec<serde_json::Value> = order.iter().cloned().map(Into::into).collect();
+    merged.extend(previous.as_array().into_iter().flatten().cloned());
+    obj.insert("repoOrder".to_string(), merged.into());
+    normalize_repo_order(config);
+}
+
+/// Normalize ordering fields in a repositories config: every repo gets a
+/// boolean `pinned` (default false); `repoOrder` lists each ungrouped repo
+/// exactly once (saved order first, unknown paths dropped, unlisted repos
+/// appended); group orders drop unknown paths; pinned repos sort first,
+/// otherwise the saved order is kept. A config without `repos` is untouched.
+fn normalize_repo_order(config: &mut serde_json::Value) {
+    use serde_json::Value;
+    use std::collections::HashSet;
+
+    let Some(obj) = config.as_object_mut() else {
+        return;
+    };
+    let Some(repos) = obj.get_mut("repos").and_then(Value::as_object_mut) else {
+        return;
+    };
+    let mut pinned: HashSet<String> = HashSet::new();
+    for (path, repo) in repos.iter_mut() {
+        let Some(repo) = repo.as_object_mut() else {
+            continue;
+        };
+        let is_pinned = repo.get("pinned").and_then(Value::as_bool).unwrap_or(false);
+        repo.insert("pinned".to_string(), is_pinned.into());
+        if is_pinned {
+            pinned.insert(path.clone());
+        }
+    }
+    let known: Vec<String> = repos.keys().cloned().collect();
+
+    // Keep the first occurrence of each known path, then float pinned ones up.
+    let order_paths = |saved: Option<&Value>, allowed: &dyn Fn(&str) -> bool| -> Vec<String> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut paths: Vec<String> = saved
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .filter(|p| known.iter().any(|k| k == *p) && allowed(p))
+            .filter(|p| seen.insert(p.to_string()))
+            .map(str::to_string)
+            .collect();
+        paths.sort_by_key(|p| !pinned.contains(p));
+        paths
+    };
+
+    let mut grouped: HashSet<String> = HashSet::new();
+    if let Some(groups) = obj.get_mut("groups").and_then(Value::as_object_mut) {
+        for group in groups.values_mut().filter_map(Value::as_object_mut) {
+            let paths = order_paths(group.get("repoOrder"), &|p| !grouped.contains(p));
+            grouped.extend(paths.iter().cloned());
+            group.insert("repoOrder".to_string(), paths.into());
+        }
+    }
+    let mut ungrouped = order_paths(obj.get("repoOrder"), &|p| !grouped.contains(p));
+    for path in &known {
+        if !grouped.contains(path) && !ungrouped.contains(path) {
+            ungrouped.push(path.clone());
+        }
+    }
+    ungrouped.sort_by_key(|p| !pinned.contains(p));
+    obj.insert("repoOrder".to_string(), ungrouped.into());
+}
+
 // Pane layout (schema owned by frontend)
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn load_pane_layout() -> serde_json::Value {
@@ -1574,16 +2387,65 @@ pub(crate) fn save_pane_layout(layout: serde_json::Value) -> Result<(), String>
 }
 
 // Prompt library
+
+/// Load the prompt library. `sort` orders entries by usage: `most_used`
+/// (use count, then recency) or `recent` (last send first); anything else
+/// keeps the stored order.
 #[cfg_attr(feature = "desktop", tauri::command)]
-pub(crate) fn load_prompt_library() -> PromptLibraryConfig {
-    load_json_config(PROMPT_LIBRARY_FILE)
+pub(crate) fn load_prompt_library(sort: Option<String>) -> PromptLibraryConfig {
+    let mut config: PromptLibraryConfig = load_json_config(PROMPT_LIBRARY_FILE);
+    match sort.as_deref() {
+        Some("most_used") => config.prompts.sort_by(|a, b| {
+            b.use_count
+                .cmp(&a.use_count)
+                .then(b.last_used_at.cmp(&a.last_used_at))
+        }),
+        Some("recent") => config
+            .prompts
+            .sort_by(|a, b| b.last_used_at.cmp(&a.last_used_at)),
+        _ => {}
+    }
+    config
 }
 
+/// Persist the prompt library. Usage stats are owned by the backend
+/// ([`record_prompt_usage`]), so the stored values win over whatever the
+/// caller sent — a stale frontend copy can't reset the counters.
 #[cfg_attr(feature = "desktop", tauri::command)]
-pub(crate) fn save_prompt_library(config: PromptLibraryConfig) -> Result<(), String> {
+pub(crate) fn save_prompt_library(mut config: PromptLibraryConfig) -> Result<(), String> {
+    let stored: PromptLibraryConfig = load_json_config(PROMPT_LIBRARY_FILE);
+    let usage: HashMap<&str, (u64, Option<u64>)> = stored
+        .prompts
+        .iter()
+        .map(|p| (p.id.as_str(), (p.use_count, p.last_used_at)))
+        .collect();
+    for entry in &mut config.prompts {
+        if let Some(&(count, last)) = usage.get(entry.id.as_str()) {
+            entry.use_count = count;
+            entry.last_used_at = last;
+        }
+    }
     save_json_config(PROMPT_LIBRARY_FILE, &config)
 }
 
+/// Count a send of prompt `id`: bump `use_count` and stamp `last_used_at`.
+/// Returns `false` when no entry has that id.
+pub(crate) fn record_prompt_usage(id: &str) -> Result<bool, String> {
+    let mut config: PromptLibraryConfig = load_json_config(PROMPT_LIBRARY_FILE);
+    let Some(entry) = config.prompts.iter_mut().find(|p| p.id == id) else {
+        return Ok(false);
+    };
+    entry.use_count += 1;
+    entry.last_used_at = Some(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64,
+    );
+    save_json_config(PROMPT_LIBRARY_FILE, &config)?;
+    Ok(true)
+}
+
 // Notes (opaque JSON — schema owned by frontend)
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn load_notes() -> serde_json::Value {
@@ -1617,6 +2479,149 @@ pub(crate) fn save_keybindings(config: serde_json::Value) -> Result<(), String>
     save_json_config(KEYBINDINGS_FILE, &config)
 }
 
+// Keybinding profiles — portable, versioned export of keybindings.json (and
+// optionally the layout part of ui-prefs.json) for moving a setup between machines.
+
+/// Schema version written by `export_keybindings`; `import_keybindings` rejects others.
+pub(crate) const KEYBINDINGS_PROFILE_VERSION: u32 = 1;
+
+/// One entry of `keybindings.json`: `key: None` means explicitly unbound.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct KeybindingOverride {
+    action: String,
+    key: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeybindingsProfile {
+    schema_version: u32,
+    #[serde(default)]
+    keybindings: Vec<KeybindingOverride>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ui_prefs: Option<serde_json::Value>,
+}
+
+/// Result of `import_keybindings`.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct KeybindingsImportReport {
+    /// Overrides merged into `keybindings.json`.
+    pub(crate) imported: usize,
+    /// Actions in the profile this install doesn't know, left out of the merge.
+    pub(crate) dropped: Vec<String>,
+    pub(crate) ui_prefs_imported: bool,
+}
+
+/// Stored overrides; anything that isn't a well-formed override array reads as empty.
+fn load_keybinding_overrides() -> Vec<KeybindingOverride> {
+    serde_json::from_value(load_keybindings()).unwrap_or_default()
+}
+
+/// Export keybinding overrides as a portable JSON profile. With
+/// `include_ui_prefs`, panel layout prefs are added, minus the machine-specific
+/// active selection.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn export_keybindings(include_ui_prefs: Option<bool>) -> Result<String, String> {
+    let ui_prefs = if include_ui_prefs.unwrap_or(false) {
+        let mut prefs = serde_json::to_value(load_ui_prefs())
+            .map_err(|e| format!("Failed to serialize UI prefs: {e}"))?;
+        if let Some(obj) = prefs.as_object_mut() {
+            obj.remove("active_selection");
+        }
+        Some(prefs)
+    } else {
+        None
+    };
+    let profile = KeybindingsProfile {
+        schema_version: KEYBINDINGS_PROFILE_VERSION,
+        keybindings: load_keybinding_overrides(),
+        ui_prefs,
+    };
+    serde_json::to_string_pretty(&profile).map_err(|e| format!("Failed to serialize profile: {e}"))
+}
+
+/// Overlay the layout fields of an imported `ui_prefs` object on the stored
+/// prefs. Fields the profile omits keep their local value, and the local
+/// `active_selection` is never replaced by another machine's.
+fn merge_imported_ui_prefs(imported: serde_json::Value) -> Result<UIPrefsConfig, String> {
+    let serde_json::Value::Object(imported) = imported else {
+        return Err("Invalid ui_prefs in profile: expected an object".to_string());
+    };
+    let mut current = serde_json::to_value(load_ui_prefs())
+        .map_err(|e| format!("Failed to serialize UI prefs: {e}"))?;
+    if let Some(obj) = current.as_object_mut() {
+        for (key, value) in imported {
+            if key != "active_selection" {
+                obj.insert(key, value);
+            }
+        }
+    }
+    serde_json::from_value(current).map_err(|e| format!("Invalid ui_prefs in profile: {e}"))
+}
+
+/// Import a profile produced by `export_keybindings`, merging its overrides
+/// over the stored ones (same action → profile wins, others are kept).
+///
+/// `known_actions` is the frontend's action list (it owns the schema); actions
+/// outside it are dropped and reported. `plugin:` actions are always kept, as
+/// on load, since plugins register theirs later. `None` accepts every action.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn import_keybindings(
+    json: String,
+    known_actions: Option<Vec<String>>,
+) -> Result<KeybindingsImportReport, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid keybinding profile: {e}"))?;
+    match value.get("schema_version").and_then(|v| v.as_u64()) {
+        Some(v) if v == u64::from(KEYBINDINGS_PROFILE_VERSION) => {}
+        Some(v) => {
+            return Err(format!(
+                "Unsupported keybinding profile version {v} (this version of TUICommander reads version {KEYBINDINGS_PROFILE_VERSION})"
+            ));
+        }
+        None => return Err("Not a keybinding profile: missing schema_version".to_string()),
+    }
+    let profile: KeybindingsProfile =
+        serde_json::from_value(value).map_err(|e| format!("Invalid keybinding profile: {e}"))?;
+
+    let mut report = KeybindingsImportReport::default();
+    let mut merged = load_keybinding_overrides();
+    for entry in profile.keybindings {
+        let known = entry.action.starts_with("plugin:")
+            || known_actions
+                .as_ref()
+                .is_none_or(|known| known.contains(&entry.action));
+        if entry.action.is_empty() || !known {
+            report.dropped.push(entry.action);
+            continue;
+        }
+        match merged.iter_mut().find(|e| e.action == entry.action) {
+            Some(existing) => existing.key = entry.key,
+            None => merged.push(entry),
+        }
+        report.imported += 1;
+    }
+
+    // Build the merged prefs before writing anything, so a bad `ui_prefs`
+    // rejects the whole import instead of leaving the bindings half-applied.
+    let prefs = match profile.ui_prefs {
+        Some(imported) => Some(merge_imported_ui_prefs(imported)?),
+        None => None,
+    };
+    save_json_config(KEYBINDINGS_FILE, &merged)?;
+    if let Some(prefs) = prefs {
+        save_json_config(UI_PREFS_FILE, &prefs)?;
+        report.ui_prefs_imported = true;
+    }
+    if !report.dropped.is_empty() {
+        tracing::warn!(
+            source = "config",
+            dropped = ?report.dropped,
+            "Keybinding profile import dropped unknown actions"
+        );
+    }
+    Ok(report)
+}
+
 // Agents config
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn load_agents_config() -> AgentsConfig {
@@ -1797,6 +2802,8 @@ mod tests {
                     enabled: true,
                     port: 8080,
                     ipv6_enabled: true,
+                    bind_address: "100.64.0.5".to_string(),
+                    bind_interface: Some("tailscale0".to_string()),
                 },
                 auth: AuthConfig {
                     username: "admin".to_string(),
@@ -1812,6 +2819,8 @@ mod tests {
                     vapid_subject: "mailto:test@example.com".to_string(),
                     ..PushConfig::default()
                 },
+                remote_profiles: Default::default(),
+                active_remote_profile: None,
             },
             confirm_before_quit: false,
             confirm_before_closing_tab: true,
@@ -1850,6 +2859,29 @@ mod tests {
             standby_timeout_minutes: 5,
             custom_launchers: Vec::new(),
             inline_blame_enabled: true,
+            agent_transcript_dir: None,
+            agent_transcript_max_mb: 1024,
+            agent_transcript_redact: true,
+            enabled_output_events: Some(vec!["question".to_string()]),
+            resize_debounce_ms: 0,
+            pause_polling_when_unfocused: false,
+            pty_spawn_retries: 5,
+            pty_wedge_timeout_secs: 30,
+            custom_output_patterns: vec![CustomOutputPattern {
+                name: "proxy-429".to_string(),
+                regex: r"PROXY_THROTTLED".to_string(),
+                kind: CustomPatternKind::RateLimit,
+                error_kind: None,
+                retry_after_ms: Some(5000),
+            }],
+            log_format: LogFormat::Json,
+            log_level: Some("debug".to_string()),
+            max_export_bytes: 4096,
+            redact_secrets: false,
+            disabled_parsers: BTreeSet::from(["question".to_string()]),
+            max_concurrent_sessions: 8,
+            reconstruct_user_input: false,
+            mcp_allow_file_writes: true,
         };
         let loaded: AppConfig = round_trip_in_dir(dir.path(), "config.json", &cfg);
         assert_eq!(loaded.shell.as_deref(), Some("/bin/zsh"));
@@ -1858,6 +2890,12 @@ mod tests {
         assert_eq!(loaded.default_font_size, 18);
         assert!(loaded.mcp_server_enabled);
         assert_eq!(loaded.mcp_port, 4000);
+        assert_eq!(loaded.custom_output_patterns, cfg.custom_output_patterns);
+        assert_eq!(loaded.disabled_parsers, cfg.disabled_parsers);
+        assert_eq!(loaded.log_format, LogFormat::Json);
+        assert_eq!(loaded.log_level.as_deref(), Some("debug"));
+        assert_eq!(loaded.max_export_bytes, 4096);
+        assert!(!loaded.redact_secrets);
         assert!(loaded.services.server.enabled);
         assert_eq!(loaded.services.server.port, 8080);
         assert_eq!(loaded.services.auth.username, "admin");
@@ -1883,6 +2921,9 @@ mod tests {
         );
         assert!(!loaded.intent_tab_title);
         assert!(!loaded.suggest_followups);
+        assert!(loaded.output_event_enabled("question"));
+        assert!(!loaded.output_event_enabled("status-line"));
+        assert!(AppConfig::default().output_event_enabled("status-line"));
     }
 
     #[test]
@@ -2039,6 +3080,11 @@ mod tests {
                 info: true,
             },
             audio_device: Some("Test Speaker".to_string()),
+            min_interval_secs: 45,
+            quiet_hours: Some(QuietHours {
+                start: "22:00".to_string(),
+                end: "07:00".to_string(),
+            }),
         };
         let loaded: NotificationConfig = round_trip_in_dir(dir.path(), "notifications.json", &cfg);
         assert!(!loaded.enabled);
@@ -2046,6 +3092,31 @@ mod tests {
         assert!(loaded.sounds.question);
         assert!(!loaded.sounds.error);
         assert_eq!(loaded.audio_device.as_deref(), Some("Test Speaker"));
+        assert_eq!(loaded.min_interval_secs, 45);
+        assert_eq!(loaded.quiet_hours, cfg.quiet_hours);
+    }
+
+    #[test]
+    fn quiet_hours_window_wraps_midnight() {
+        let overnight = QuietHours {
+            start: "22:00".to_string(),
+            end: "07:00".to_string(),
+        };
+        assert!(overnight.contains(23 * 60));
+        assert!(overnight.contains(0));
+        assert!(!overnight.contains(7 * 60));
+        assert!(!overnight.contains(12 * 60));
+        let lunch = QuietHours {
+            start: "12:00".to_string(),
+            end: "13:30".to_string(),
+        };
+        assert!(lunch.contains(12 * 60 + 45));
+        assert!(!lunch.contains(13 * 60 + 30));
+        let bogus = QuietHours {
+            start: "25:00".to_string(),
+            end: "07:00".to_string(),
+        };
+        assert!(!bogus.contains(60));
     }
 
     #[test]
@@ -2071,6 +3142,7 @@ mod tests {
                 "activity".to_string(),
                 "panel-activity".to_string(),
             )]),
+            active_selection: None,
         };
         let loaded: UIPrefsConfig = round_trip_in_dir(dir.path(), "ui-prefs.json", &cfg);
         assert!(!loaded.sidebar_visible);
@@ -2086,6 +3158,65 @@ mod tests {
         assert_eq!(loaded.diff_view_mode, "split");
     }
 
+    #[test]
+    fn recent_files_order_dedupe_and_prune() {
+        let dir = TempDir::new().unwrap();
+        let _guard = set_config_dir_override(dir.path().join("config"));
+        let repo = dir.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        for f in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(repo.join(f), f).unwrap();
+        }
+        let repo_path = repo.to_string_lossy().to_string();
+
+        for f in ["a.txt", "b.txt", "c.txt"] {
+            record_recent_file(repo_path.clone(), f.to_string()).unwrap();
+        }
+        assert_eq!(
+            get_recent_files(repo_path.clone()),
+            ["c.txt", "b.txt", "a.txt"]
+        );
+
+        // Re-opening moves the file to the front instead of duplicating it.
+        record_recent_file(repo_path.clone(), "a.txt".to_string()).unwrap();
+        assert_eq!(
+            get_recent_files(repo_path.clone()),
+            ["a.txt", "c.txt", "b.txt"]
+        );
+
+        // Deleted files are left out of the result, but reading doesn't rewrite the store.
+        std::fs::remove_file(repo.join("c.txt")).unwrap();
+        let stored = std::fs::read(config_dir().join(RECENT_FILES_FILE)).unwrap();
+        assert_eq!(get_recent_files(repo_path.clone()), ["a.txt", "b.txt"]);
+        assert_eq!(
+            std::fs::read(config_dir().join(RECENT_FILES_FILE)).unwrap(),
+            stored
+        );
+
+        // The next record prunes it from the store.
+        record_recent_file(repo_path.clone(), "b.txt".to_string()).unwrap();
+        let store: RecentFilesStore = load_json_config(RECENT_FILES_FILE);
+        assert_eq!(store.repos[&repo_path], ["b.txt", "a.txt"]);
+
+        // Layout saves from the frontend don't touch the list.
+        save_ui_prefs(UIPrefsConfig::default()).unwrap();
+        assert_eq!(get_recent_files(repo_path.clone()), ["b.txt", "a.txt"]);
+
+        // Paths that escape the repo are never recorded.
+        assert!(record_recent_file(repo_path.clone(), "../x".to_string()).is_err());
+        assert!(record_recent_file(repo_path, "/etc/passwd".to_string()).is_err());
+    }
+
+    #[test]
+    fn recent_files_are_capped() {
+        let mut list = Vec::new();
+        for i in 0..(MAX_RECENT_FILES + 5) {
+            push_recent_file(&mut list, &format!("f{i}"));
+        }
+        assert_eq!(list.len(), MAX_RECENT_FILES);
+        assert_eq!(list[0], format!("f{}", MAX_RECENT_FILES + 4));
+    }
+
     #[test]
     fn repo_settings_round_trip() {
         let dir = TempDir::new().unwrap();
@@ -2112,7 +3243,10 @@ mod tests {
                 auto_fetch_interval_minutes: None,
                 auto_delete_on_pr_close: None,
                 mcp_upstreams: None,
+                env_file: None,
+                env: HashMap::new(),
                 branch_labels: HashMap::new(),
+                worktrees_dir: None,
             },
         );
         let loaded: RepoSettingsMap = round_trip_in_dir(dir.path(), "repo-settings.json", &map);
@@ -2134,6 +3268,7 @@ mod tests {
                 label: "Test prompt".to_string(),
                 text: "Hello world".to_string(),
                 pinned: true,
+                ..Default::default()
             }],
         };
         let loaded: PromptLibraryConfig =
@@ -2232,6 +3367,199 @@ mod tests {
         assert_eq!(mode, 0o600, "Config file should be owner-only (0600)");
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn save_app_config_writes_atomically_and_validates() {
+        let dir = TempDir::new().unwrap();
+        let _guard = set_config_dir_override(dir.path().to_path_buf());
+        let target = dir.path().join(APP_CONFIG_FILE);
+
+        let mut cfg = AppConfig {
+            font_size: 17,
+            ..AppConfig::default()
+        };
+        cfg.services.auth.password_hash = bcrypt::hash("pw", 4).unwrap();
+        save_app_config(cfg.clone()).unwrap();
+        assert_eq!(load_app_config().font_size, 17);
+        let leftovers: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().contains(".tmp."))
+            .collect();
+        assert!(leftovers.is_empty(), "temp file must be renamed away");
+
+        // Invalid configs are rejected and the file on disk is left untouched.
+        let before = fs::read_to_string(&target).unwrap();
+        let mut bad_port = cfg.clone();
+        bad_port.services.server.port = 0;
+        assert!(save_app_config(bad_port).is_err());
+        let mut bad_hash = cfg;
+        bad_hash.services.auth.password_hash = "plaintext".to_string();
+        assert!(save_app_config(bad_hash).is_err());
+        assert_eq!(fs::read_to_string(&target).unwrap(), before);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn corrupt_app_config_is_backed_up_and_defaults_used() {
+        let dir = TempDir::new().unwrap();
+        let _guard = set_config_dir_override(dir.path().to_path_buf());
+        take_config_recovery_notices();
+        let target = dir.path().join(APP_CONFIG_FILE);
+        fs::write(&target, "{ \"font_size\": 1").unwrap();
+
+        let cfg = load_app_config();
+        assert_eq!(cfg.font_size, AppConfig::default().font_size);
+        assert!(!target.exists(), "corrupt file is moved aside");
+
+        let notices = take_config_recovery_notices();
+        assert_eq!(notices.len(), 1);
+        assert!(notices[0].contains(".corrupt-"));
+        assert_eq!(
+            fs::read_to_string(&notices[0]).unwrap(),
+            "{ \"font_size\": 1"
+        );
+        assert!(
+            take_config_recovery_notices().is_empty(),
+            "notices are drained"
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn keybindings_profile_export_import_is_idempotent() {
+        let dir = TempDir::new().unwrap();
+        let _guard = set_config_dir_override(dir.path().to_path_buf());
+        save_keybindings(serde_json::json!([
+            {"action": "split-vertical", "key": "Cmd+Shift+V"},
+            {"action": "toggle-sidebar", "key": null},
+        ]))
+        .unwrap();
+
+        let exported = export_keybindings(Some(true)).unwrap();
+        let profile: serde_json::Value = serde_json::from_str(&exported).unwrap();
+        assert_eq!(profile["schema_version"], KEYBINDINGS_PROFILE_VERSION);
+        assert!(profile["ui_prefs"].get("active_selection").is_none());
+
+        let known = vec!["split-vertical".to_string(), "toggle-sidebar".to_string()];
+        let report = import_keybindings(exported.clone(), Some(known)).unwrap();
+        assert_eq!(report.imported, 2);
+        assert!(report.dropped.is_empty());
+        assert!(report.ui_prefs_imported);
+        assert_eq!(export_keybindings(Some(true)).unwrap(), exported);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn keybindings_profile_import_merges_and_drops_unknown_actions() {
+        let dir = TempDir::new().unwrap();
+        let _guard = set_config_dir_override(dir.path().to_path_buf());
+        save_keybindings(serde_json::json!([
+            {"action": "split-vertical", "key": "Cmd+Shift+V"},
+            {"action": "zoom-in", "key": "Cmd+="},
+        ]))
+        .unwrap();
+        let profile = serde_json::json!({
+            "schema_version": 1,
+            "keybindings": [
+                {"action": "zoom-in", "key": "Cmd+Plus"},
+                {"action": "plugin:demo:run", "key": "Cmd+R"},
+                {"action": "no-such-action", "key": "Cmd+X"},
+            ],
+        });
+        let known = vec!["split-vertical".to_string(), "zoom-in".to_string()];
+        let report = import_keybindings(profile.to_string(), Some(known)).unwrap();
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.dropped, vec!["no-such-action"]);
+        assert!(!report.ui_prefs_imported);
+        assert_eq!(
+            load_keybindings(),
+            serde_json::json!([
+                {"action": "split-vertical", "key": "Cmd+Shift+V"},
+                {"action": "zoom-in", "key": "Cmd+Plus"},
+                {"action": "plugin:demo:run", "key": "Cmd+R"},
+            ])
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn keybindings_profile_rejects_unknown_version() {
+        let dir = TempDir::new().unwrap();
+        let _guard = set_config_dir_override(dir.path().to_path_buf());
+        save_keybindings(serde_json::json!([{"action": "zoom-in", "key": "Cmd+="}])).unwrap();
+
+        let err = import_keybindings(r#"{"schema_version": 99, "keybindings": []}"#.into(), None)
+            .unwrap_err();
+        assert!(
+            err.contains("Unsupported keybinding profile version 99"),
+            "{err}"
+        );
+        let err =
+            import_keybindings(r#"[{"action": "zoom-in", "key": null}]"#.into(), None).unwrap_err();
+        assert!(err.contains("missing schema_version"), "{err}");
+        // A rejected import leaves the stored bindings untouched.
+        assert_eq!(
+            load_keybindings(),
+            serde_json::json!([{"action": "zoom-in", "key": "Cmd+="}])
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn keybindings_profile_with_invalid_ui_prefs_writes_nothing() {
+        let dir = TempDir::new().unwrap();
+        let _guard = set_config_dir_override(dir.path().to_path_buf());
+        save_keybindings(serde_json::json!([{"action": "zoom-in", "key": "Cmd+="}])).unwrap();
+        let profile = serde_json::json!({
+            "schema_version": 1,
+            "keybindings": [{"action": "zoom-in", "key": "Cmd+Plus"}],
+            "ui_prefs": {"sidebar_width": "wide"},
+        });
+
+        let err = import_keybindings(profile.to_string(), None).unwrap_err();
+        assert!(err.contains("Invalid ui_prefs"), "{err}");
+        assert_eq!(
+            load_keybindings(),
+            serde_json::json!([{"action": "zoom-in", "key": "Cmd+="}])
+        );
+        assert!(!dir.path().join(UI_PREFS_FILE).exists());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn keybindings_profile_ui_prefs_merge_over_local_ones() {
+        let dir = TempDir::new().unwrap();
+        let _guard = set_config_dir_override(dir.path().to_path_buf());
+        let selection = ActiveSelection {
+            repo_path: "/local/repo".to_string(),
+            worktree_path: None,
+        };
+        save_ui_prefs(UIPrefsConfig {
+            git_panel_width: 420,
+            active_selection: Some(selection.clone()),
+            ..UIPrefsConfig::default()
+        })
+        .unwrap();
+        let profile = serde_json::json!({
+            "schema_version": 1,
+            "ui_prefs": {
+                "sidebar_width": 333,
+                "active_selection": {"repo_path": "/other/machine"},
+            },
+        });
+
+        let report = import_keybindings(profile.to_string(), None).unwrap();
+        assert!(report.ui_prefs_imported);
+        let prefs = load_ui_prefs();
+        assert_eq!(prefs.sidebar_width, 333);
+        assert_eq!(
+            prefs.git_panel_width, 420,
+            "omitted fields keep local values"
+        );
+        assert_eq!(prefs.active_selection, Some(selection));
+    }
+
     #[test]
     fn has_custom_settings_true_when_base_branch_changed() {
         let entry = RepoSettingsEntry {
@@ -2739,6 +4067,98 @@ mod tests {
         assert!(result.is_ok(), "Should succeed even if dir doesn't exist");
     }
 
+    fn profile(bind: &str, port: u16, lan_auth_bypass: bool) -> RemoteProfile {
+        RemoteProfile {
+            bind_address: bind.to_string(),
+            port,
+            ipv6_enabled: false,
+            lan_auth_bypass,
+            tls: TlsConfig::Off,
+        }
+    }
+
+    #[test]
+    fn switching_remote_profile_updates_effective_config() {
+        let mut config = AppConfig::default();
+        config.services.auth.username = "me".to_string();
+        config
+            .services
+            .remote_profiles
+            .insert("home".to_string(), profile("0.0.0.0", 9877, true));
+        config
+            .services
+            .remote_profiles
+            .insert("travel".to_string(), profile("100.64.0.5", 9443, false));
+        assert!(config.validate_for_save().is_ok());
+
+        config.services.apply_remote_profile("travel").unwrap();
+        assert_eq!(config.services.server.bind_address, "100.64.0.5");
+        assert_eq!(config.services.server.port, 9443);
+        assert!(!config.services.auth.lan_auth_bypass);
+        assert_eq!(
+            config.services.active_remote_profile.as_deref(),
+            Some("travel")
+        );
+        // Credentials are not part of a profile.
+        assert_eq!(config.services.auth.username, "me");
+
+        config.services.apply_remote_profile("home").unwrap();
+        assert_eq!(config.services.server.port, 9877);
+        assert!(config.services.auth.lan_auth_bypass);
+        assert!(config.validate_for_save().is_ok());
+    }
+
+    #[test]
+    fn switching_tls_or_lan_bypass_profile_restarts_server() {
+        let mut config = AppConfig::default();
+        let mut secure = profile("0.0.0.0", default_remote_port(), false);
+        secure.tls = TlsConfig::Manual {
+            cert_path: "/etc/tuic/cert.pem".to_string(),
+            key_path: "/etc/tuic/key.pem".to_string(),
+        };
+        config
+            .services
+            .remote_profiles
+            .insert("secure".to_string(), secure);
+        config.services.remote_profiles.insert(
+            "lan".to_string(),
+            profile("0.0.0.0", default_remote_port(), true),
+        );
+
+        let before = config.services.clone();
+        config.services.apply_remote_profile("secure").unwrap();
+        // Same bind address and port: only TLS differs.
+        assert!(before.listener_changed(&config.services));
+
+        let before = config.services.clone();
+        config.services.apply_remote_profile("lan").unwrap();
+        assert!(before.listener_changed(&config.services));
+
+        let before = config.services.clone();
+        config.services.apply_remote_profile("lan").unwrap();
+        assert!(!before.listener_changed(&config.services));
+    }
+
+    #[test]
+    fn unknown_or_invalid_remote_profile_errors() {
+        let mut config = AppConfig::default();
+        let before = config.services.server.port;
+        let err = config.services.apply_remote_profile("nope").unwrap_err();
+        assert!(err.contains("nope"));
+        assert_eq!(config.services.server.port, before);
+        assert!(config.services.active_remote_profile.is_none());
+
+        config.services.active_remote_profile = Some("gone".to_string());
+        assert!(config.validate_for_save().is_err());
+
+        config.services.active_remote_profile = None;
+        config
+            .services
+            .remote_profiles
+            .insert("bad".to_string(), profile("not-an-ip", 9877, false));
+        assert!(config.validate_for_save().is_err());
+    }
+
     #[test]
     fn repo_local_config_loads_valid_json() {
         let dir = TempDir::new().unwrap();
@@ -3108,6 +4528,116 @@ mod tests {
         );
     }
 
+    fn entry_with_overrides() -> RepoSettingsEntry {
+        RepoSettingsEntry {
+            path: "/repo".to_string(),
+            base_branch: Some("develop".to_string()),
+            copy_ignored_files: Some(true),
+            setup_script: Some("pnpm install".to_string()),
+            pr_merge_strategy: Some(MergeStrategy::Rebase),
+            color: "#ff0000".to_string(),
+            branch_labels: HashMap::from([("feat".to_string(), "Feature".to_string())]),
+            ..RepoSettingsEntry::default()
+        }
+    }
+
+    #[test]
+    fn reset_repo_settings_single_category_reverts_only_that_category() {
+        let mut settings = RepoSettingsMap::default();
+        settings
+            .repos
+            .insert("/repo".to_string(), entry_with_overrides());
+        let defaults = RepoDefaultsConfig::default();
+
+        let eff = reset_repo_settings_in(
+            &mut settings,
+            &defaults,
+            "/repo",
+            Some(&["branch".to_string()]),
+        )
+        .unwrap();
+
+        assert_eq!(eff.base_branch, defaults.base_branch);
+        assert!(eff.copy_ignored_files);
+        assert_eq!(eff.setup_script, "pnpm install");
+        assert_eq!(eff.pr_merge_strategy, MergeStrategy::Rebase);
+        let entry = settings.repos.get("/repo").unwrap();
+        assert_eq!(entry.base_branch, None);
+        assert_eq!(entry.copy_ignored_files, Some(true));
+        assert_eq!(entry.color, "#ff0000");
+    }
+
+    #[test]
+    fn reset_repo_settings_all_clears_every_override() {
+        let mut settings = RepoSettingsMap::default();
+        settings
+            .repos
+            .insert("/repo".to_string(), entry_with_overrides());
+        let defaults = RepoDefaultsConfig {
+            setup_script: "npm install".to_string(),
+            ..RepoDefaultsConfig::default()
+        };
+
+        let eff = reset_repo_settings_in(&mut settings, &defaults, "/repo", None).unwrap();
+
+        assert_eq!(eff.base_branch, defaults.base_branch);
+        assert_eq!(eff.setup_script, "npm install");
+        assert_eq!(eff.pr_merge_strategy, MergeStrategy::default());
+        let entry = settings.repos.get("/repo").unwrap();
+        assert_eq!(
+            entry.branch_labels.len(),
+            1,
+            "labels are data, not overrides"
+        );
+        let mut without_labels = entry.clone();
+        without_labels.branch_labels.clear();
+        assert!(!without_labels.has_custom_settings());
+    }
+
+    #[test]
+    fn reset_repo_settings_env_clears_file_and_variables() {
+        let mut settings = RepoSettingsMap::default();
+        settings.repos.insert(
+            "/repo".to_string(),
+            RepoSettingsEntry {
+                env_file: Some(".env.local".to_string()),
+                env: HashMap::from([("API_URL".to_string(), "http://dev".to_string())]),
+                ..RepoSettingsEntry::default()
+            },
+        );
+
+        reset_repo_settings_in(
+            &mut settings,
+            &RepoDefaultsConfig::default(),
+            "/repo",
+            Some(&["env".to_string()]),
+        )
+        .unwrap();
+
+        let entry = settings.repos.get("/repo").unwrap();
+        assert_eq!(entry.env_file, None);
+        assert!(entry.env.is_empty());
+        assert!(!entry.has_custom_settings());
+    }
+
+    #[test]
+    fn reset_repo_settings_rejects_unknown_category() {
+        let mut settings = RepoSettingsMap::default();
+        settings
+            .repos
+            .insert("/repo".to_string(), entry_with_overrides());
+        let err = reset_repo_settings_in(
+            &mut settings,
+            &RepoDefaultsConfig::default(),
+            "/repo",
+            Some(&["branch".to_string(), "bogus".to_string()]),
+        )
+        .unwrap_err();
+        assert!(err.contains("bogus"));
+        // Validation happens before any mutation.
+        assert!(settings.repos["/repo"].base_branch.is_some());
+    }
+
     #[test]
     fn is_experimental_enabled_gates_on_parent() {
         let mut cfg = AppConfig::default();
@@ -3118,4 +4648,81 @@ mod tests {
         assert!(cfg.is_experimental_enabled(true));
         assert!(!cfg.is_experimental_enabled(false));
     }
+
+    fn repo_order(config: &serde_json::Value) -> Vec<&str> {
+        config["repoOrder"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p.as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn reorder_repositories_persists_order() {
+        let dir = TempDir::new().unwrap();
+        let mut config = serde_json::json!({
+            "repos": { "/a": {}, "/b": {}, "/c": {} },
+            "repoOrder": ["/a", "/b", "/c"],
+        });
+        apply_repo_order(&mut config, &["/c".to_string(), "/a".to_string()]);
+        let loaded: serde_json::Value = round_trip_in_dir(dir.path(), REPOSITORIES_FILE, &config);
+        assert_eq!(repo_order(&loaded), vec!["/c", "/a", "/b"]);
+    }
+
+    #[test]
+    fn reorder_repositories_emits_repositories_changed() {
+        let dir = TempDir::new().unwrap();
+        let _guard = set_config_dir_override(dir.path().to_path_buf());
+        save_json_config(
+            REPOSITORIES_FILE,
+            &serde_json::json!({ "repos": { "/a": {}, "/b": {} }, "repoOrder": ["/a", "/b"] }),
+        )
+        .unwrap();
+        let state = crate::state::tests_support::make_test_app_state();
+        let mut rx = state.event_bus.subscribe();
+
+        reorder_repositories_impl(&state, vec!["/b".to_string()]).unwrap();
+
+        match rx.try_recv() {
+            Ok(crate::state::AppEvent::RepositoriesChanged { repo_order }) => {
+                assert_eq!(repo_order, vec!["/b", "/a"]);
+            }
+            other => panic!("expected RepositoriesChanged, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn pinned_repos_sort_ahead_of_saved_order() {
+        let mut config = serde_json::json!({
+            "repos": { "/a": {}, "/b": { "pinned": true }, "/c": {}, "/g1": {}, "/g2": { "pinned": true } },
+            "repoOrder": ["/c", "/a", "/b", "/gone", "/a"],
+            "groups": { "grp": { "id": "grp", "repoOrder": ["/g1", "/g2"] } },
+        });
+        normalize_repo_order(&mut config);
+        assert_eq!(repo_order(&config), vec!["/b", "/c", "/a"]);
+        assert_eq!(
+            config["groups"]["grp"]["repoOrder"],
+            serde_json::json!(["/g2", "/g1"])
+        );
+
+        // Reordering cannot push a pinned repo behind unpinned ones.
+        apply_repo_order(&mut config, &["/a".to_string()]);
+        assert_eq!(repo_order(&config), vec!["/b", "/a", "/c"]);
+    }
+
+    #[test]
+    fn repositories_without_order_fields_default_sensibly() {
+        let mut config =
+            serde_json::json!({ "repos": { "/b": { "path": "/b" }, "/a": { "path": "/a" } } });
+        normalize_repo_order(&mut config);
+        assert_eq!(repo_order(&config), vec!["/a", "/b"]);
+        assert_eq!(config["repos"]["/a"]["pinned"], false);
+        assert_eq!(config["repos"]["/a"]["path"], "/a");
+
+        // A missing or empty file (Null) is left alone.
+        let mut empty = serde_json::Value::Null;
+        normalize_repo_order(&mut empty);
+        assert!(empty.is_null());
+    }
 }
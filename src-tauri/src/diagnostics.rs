@@ -0,0 +1,221 @@
+//! Support self-test: external tools, credentials and writable directories.
+//!
+//! `run_diagnostics` turns "claude not found" / "git missing" reports into a
+//! one-click list of checks instead of a trip through the logs. Every check is
+//! built on the same detection the rest of the app uses, so a failure here
+//! matches what the feature itself would hit.
+
+use std::process::Command;
+
+use serde::Serialize;
+
+/// Outcome of a single check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CheckStatus {
+    Ok,
+    /// Optional tool or credential missing — the related feature is unavailable.
+    Warn,
+    /// Something the app needs is broken.
+    Fail,
+}
+
+/// One row of the diagnostics report.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct DiagnosticCheck {
+    /// Stable id: `agent:<binary>`, `cli:<name>`, `github_token`, `config_dir`,
+    /// `dictation_models`.
+    pub(crate) check: String,
+    pub(crate) status: CheckStatus,
+    pub(crate) detail: String,
+}
+
+impl DiagnosticCheck {
+    fn new(check: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            check: check.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// CLIs resolved via `resolve_cli`. Only git is required.
+const CLI_TOOLS: &[(&str, bool)] = &[("git", true), ("gh", false), ("lazygit", false)];
+
+fn agent_checks() -> Vec<DiagnosticCheck> {
+    let binaries: Vec<String> = crate::agent::KNOWN_AGENTS
+        .iter()
+        .map(|a| a.binary.to_string())
+        .collect();
+    let detected = crate::agent::detect_all_agent_binaries(binaries);
+    crate::agent::KNOWN_AGENTS
+        .iter()
+        .map(|agent| agent.binary)
+        .map(|binary| {
+            let check = format!("agent:{binary}");
+            match detected.get(binary).and_then(|d| d.path.as_deref()) {
+                Some(path) => DiagnosticCheck::new(check, CheckStatus::Ok, path),
+                None => DiagnosticCheck::new(
+                    check,
+                    CheckStatus::Warn,
+                    "not found on PATH or in known install locations",
+                ),
+            }
+        })
+        .collect()
+}
+
+fn cli_check(name: &str, required: bool) -> DiagnosticCheck {
+    let check = format!("cli:{name}");
+    let missing = if required {
+        CheckStatus::Fail
+    } else {
+        CheckStatus::Warn
+    };
+    let resolved = crate::cli::resolve_cli(name);
+    let mut cmd = Command::new(&resolved);
+    cmd.arg("--version");
+    crate::cli::apply_no_window(&mut cmd);
+    match cmd.output() {
+        Ok(out) if out.status.success() => {
+            let version = String::from_utf8_lossy(&out.stdout);
+            let version = version.lines().next().unwrap_or("").trim();
+            DiagnosticCheck::new(check, CheckStatus::Ok, format!("{resolved} ({version})"))
+        }
+        Ok(out) => DiagnosticCheck::new(
+            check,
+            missing,
+            format!("{resolved} --version exited with {}", out.status),
+        ),
+        Err(e) => DiagnosticCheck::new(check, missing, format!("cannot run {resolved}: {e}")),
+    }
+}
+
+fn github_token_check() -> DiagnosticCheck {
+    match crate::github_auth::resolve_token_with_source() {
+        (Some(_), source) => DiagnosticCheck::new(
+            "github_token",
+            CheckStatus::Ok,
+            format!("found ({source:?})"),
+        ),
+        (None, _) => DiagnosticCheck::new(
+            "github_token",
+            CheckStatus::Warn,
+            "no token in GH_TOKEN/GITHUB_TOKEN, keyring or gh CLI — GitHub features are disabled",
+        ),
+    }
+}
+
+fn config_dir_check() -> DiagnosticCheck {
+    let dir = crate::config::config_dir();
+    let probe = dir.join(".tuic-diagnostics-probe");
+    let result = std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(&probe, b"ok"))
+        .and_then(|()| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => DiagnosticCheck::new("config_dir", CheckStatus::Ok, dir.to_string_lossy()),
+        Err(e) => DiagnosticCheck::new(
+            "config_dir",
+            CheckStatus::Fail,
+            format!("{} is not writable: {e}", dir.display()),
+        ),
+    }
+}
+
+#[cfg(feature = "desktop")]
+fn dictation_models_check() -> DiagnosticCheck {
+    use crate::dictation::model::{WhisperModel, model_exists, models_dir};
+    let dir = models_dir();
+    if !dir.is_dir() {
+        return DiagnosticCheck::new(
+            "dictation_models",
+            CheckStatus::Warn,
+            format!("{} does not exist — no model downloaded", dir.display()),
+        );
+    }
+    let installed: Vec<&str> = WhisperModel::ALL
+        .iter()
+        .filter(|m| model_exists(**m))
+        .map(|m| m.name())
+        .collect();
+    if installed.is_empty() {
+        DiagnosticCheck::new(
+            "dictation_models",
+            CheckStatus::Warn,
+            format!("no model downloaded in {}", dir.display()),
+        )
+    } else {
+        DiagnosticCheck::new(
+            "dictation_models",
+            CheckStatus::Ok,
+            format!("{} ({})", dir.display(), installed.join(", ")),
+        )
+    }
+}
+
+/// Run every check. Blocking: spawns `--version` probes and may shell out to
+/// `gh auth token`, so async callers should use `spawn_blocking`.
+pub(crate) fn run_diagnostics_impl() -> Vec<DiagnosticCheck> {
+    let mut checks = agent_checks();
+    checks.extend(
+        CLI_TOOLS
+            .iter()
+            .map(|(name, required)| cli_check(name, *required)),
+    );
+    checks.push(github_token_check());
+    checks.push(config_dir_check());
+    #[cfg(feature = "desktop")]
+    checks.push(dictation_models_check());
+    checks
+}
+
+/// Support self-test (Tauri command).
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn run_diagnostics() -> Result<Vec<DiagnosticCheck>, String> {
+    tokio::task::spawn_blocking(run_diagnostics_impl)
+        .await
+        .map_err(|e| format!("Task panic: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostics_include_every_known_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        let _guard = crate::config::set_config_dir_override(dir.path().to_path_buf());
+
+        let checks = run_diagnostics_impl();
+        for binary in crate::agent::KNOWN_AGENTS.iter().map(|a| a.binary) {
+            let id = format!("agent:{binary}");
+            let check = checks
+                .iter()
+                .find(|c| c.check == id)
+                .unwrap_or_else(|| panic!("missing diagnostics entry for {binary}"));
+            assert_ne!(check.status, CheckStatus::Fail, "agents are optional");
+        }
+        let config = checks.iter().find(|c| c.check == "config_dir").unwrap();
+        assert_eq!(config.status, CheckStatus::Ok, "{}", config.detail);
+        for (name, _) in CLI_TOOLS {
+            assert!(checks.iter().any(|c| c.check == format!("cli:{name}")));
+        }
+    }
+
+    #[test]
+    fn missing_optional_cli_is_a_warning() {
+        let check = cli_check("tuic-definitely-not-installed", false);
+        assert_eq!(check.status, CheckStatus::Warn);
+        let check = cli_check("tuic-definitely-not-installed", true);
+        assert_eq!(check.status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn check_status_serializes_lowercase() {
+        let json = serde_json::to_value(DiagnosticCheck::new("x", CheckStatus::Warn, "d")).unwrap();
+        assert_eq!(json["status"], "warn");
+        assert_eq!(json["check"], "x");
+    }
+}
@@ -270,13 +270,18 @@ pub fn start_dictation(app: AppHandle, dictation: State<'_, DictationState>) ->
     let audio_buffer = capture.buffer_handle();
     *dictation.audio.lock() = Some(capture);
 
+    // Reset accumulated partials for this session
+    dictation.accumulated_partials.lock().clear();
+
+    if !config.streaming {
+        // Batch mode: audio accumulates in the capture buffer until stop.
+        app_logger::log_via_handle(&app, "info", "dictation", "Recording started");
+        recording_guard.disarm();
+        return Ok(());
+    }
+
     // Start streaming session
-    let config = get_dictation_config();
-    let lang = if config.language == "auto" {
-        None
-    } else {
-        Some(config.language.clone())
-    };
+    let lang = resolve_language(&config.language, None);
     let (tx, rx) = mpsc::channel::<String>();
 
     let session = streaming::StreamingSession::start(
@@ -284,15 +289,13 @@ pub fn start_dictation(app: AppHandle, dictation: State<'_, DictationState>) ->
         audio_buffer,
         tx,
         lang,
+        dictation.processing.clone(),
     );
     *dictation.streaming.lock() = Some(session);
 
     // recording is already true (set by compare_exchange above)
     app_logger::log_via_handle(&app, "info", "dictation", "Streaming recording started");
 
-    // Reset accumulated partials for this session
-    dictation.accumulated_partials.lock().clear();
-
     // Spawn event forwarder: reads partials from channel, emits Tauri events,
     // and concatenates them for accuracy comparison at the end.
     let app_clone = app.clone();
@@ -320,8 +323,19 @@ pub fn start_dictation(app: AppHandle, dictation: State<'_, DictationState>) ->
     Ok(())
 }
 
+/// Stop recording and run the final transcription pass.
+///
+/// `language` overrides the configured dictation language for this call only
+/// (ISO 639-1 code or "auto").
 #[tauri::command]
-pub async fn stop_dictation_and_transcribe(app: AppHandle) -> Result<TranscribeResponse, String> {
+pub async fn stop_dictation_and_transcribe(
+    app: AppHandle,
+    language: Option<String>,
+) -> Result<TranscribeResponse, String> {
+    if let Some(lang) = language.as_deref() {
+        validate_language(lang)?;
+    }
+
     // Gather all data from DictationState synchronously (before any .await).
     // This block ensures no MutexGuard or State borrow lives across the await point.
     let prepare = {
@@ -349,11 +363,7 @@ pub async fn stop_dictation_and_transcribe(app: AppHandle) -> Result<TranscribeR
 
         // Read config while we still have sync context (avoids file I/O after .await)
         let config = get_dictation_config();
-        let lang_owned = if config.language == "auto" {
-            None
-        } else {
-            Some(config.language.clone())
-        };
+        let lang_owned = resolve_language(&config.language, language.as_deref());
 
         // Clone Arc-ed resources for the blocking task
         let transcriber = dictation.transcriber_arc.lock().clone();
@@ -386,10 +396,13 @@ pub async fn stop_dictation_and_transcribe(app: AppHandle) -> Result<TranscribeR
 
     // Run session join + whisper inference off the IPC thread
     let result = tokio::task::spawn_blocking(move || {
-        let _guard = ProcessingGuard(processing);
+        let _guard = ProcessingGuard(processing.clone());
 
-        // Join the streaming thread (may block while last partial window finishes)
+        // Join the streaming thread (may block while last partial window finishes).
+        // The thread clears `processing` after each window, so re-assert it for
+        // the final pass.
         let mut all_audio = session.map(|s| s.stop()).unwrap_or_default();
+        processing.store(true, Ordering::Release);
 
         // Drain anything left in the audio capture buffer (arrived after last poll).
         // Safe: streaming thread is joined above, no more concurrent readers.
@@ -404,7 +417,7 @@ pub async fn stop_dictation_and_transcribe(app: AppHandle) -> Result<TranscribeR
             "info",
             "dictation",
             &format!(
-                "Streaming stopped, {:.1}s total audio for final transcription",
+                "Recording stopped, {:.1}s total audio for final transcription",
                 total_duration_s
             ),
         );
@@ -419,55 +432,21 @@ pub async fn stop_dictation_and_transcribe(app: AppHandle) -> Result<TranscribeR
             };
         }
 
-        let mut final_text = String::new();
-
-        if let Some(ref transcriber) = transcriber {
-            let lang_ref = lang_owned.as_deref();
-            match transcriber.transcribe(&all_audio, lang_ref) {
-                Ok(result) if result.skip_reason.is_none() => {
-                    final_text = result.text;
-                }
-                Ok(result) => {
-                    if let Some(reason) = &result.skip_reason {
-                        app_logger::log_via_handle(
-                            &app_clone,
-                            "info",
-                            "dictation",
-                            &format!("Final transcription skipped: {reason}"),
-                        );
-                    }
-                }
-                Err(e) => {
-                    app_logger::log_via_handle(
-                        &app_clone,
-                        "warn",
-                        "dictation",
-                        &format!("Final transcription failed: {e}"),
-                    );
-                }
+        let final_text = match transcribe_final(
+            transcriber.as_deref(),
+            &all_audio,
+            lang_owned.as_deref(),
+            |level, msg| app_logger::log_via_handle(&app_clone, level, "dictation", msg),
+        ) {
+            Ok(text) => text,
+            Err(reason) => {
+                return TranscribeResponse {
+                    text: String::new(),
+                    skip_reason: Some(reason.to_string()),
+                    duration_s: total_duration_s,
+                };
             }
-        } else {
-            app_logger::log_via_handle(
-                &app_clone,
-                "warn",
-                "dictation",
-                "Transcriber not available — model not loaded",
-            );
-            return TranscribeResponse {
-                text: String::new(),
-                skip_reason: Some("model not loaded".to_string()),
-                duration_s: total_duration_s,
-            };
-        }
-
-        if final_text.is_empty() {
-            app_logger::log_via_handle(&app_clone, "info", "dictation", "No speech detected");
-            return TranscribeResponse {
-                text: String::new(),
-                skip_reason: Some("no speech detected".to_string()),
-                duration_s: total_duration_s,
-            };
-        }
+        };
 
         // Log accuracy comparison (lengths only — no verbatim text to avoid PII in logs)
         let composed = std::mem::take(&mut *accumulated_partials.lock());
@@ -540,7 +519,12 @@ pub fn list_audio_devices() -> Vec<audio::AudioDevice> {
 }
 
 /// Shell integration: inject text into active terminal.
-/// Currently only callable from within the app via Tauri IPC.
+/// Callable from within the app via Tauri IPC and over HTTP (`POST /dictation/inject`).
+///
+/// With `dictation_target = "active-session"` and a `session_id`, the corrected
+/// text is written straight into that session's PTY instead of being returned
+/// for the caller to type at the OS focus. `focus_window_on_inject` raises the
+/// main window first either way.
 ///
 /// Future external trigger mechanisms:
 /// 1. CLI: `tuicommander inject "text"` via IPC socket
@@ -549,13 +533,95 @@ pub fn list_audio_devices() -> Vec<audio::AudioDevice> {
 ///
 /// Security: Will require authentication token stored in env var.
 #[tauri::command]
-pub fn inject_text(dictation: State<'_, DictationState>, text: String) -> Result<String, String> {
+pub fn inject_text(
+    app: AppHandle,
+    state: State<'_, Arc<crate::state::AppState>>,
+    dictation: State<'_, DictationState>,
+    text: String,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    inject_text_core(&app, &state, &dictation, &text, session_id.as_deref())
+}
+
+/// Shared by the Tauri command and the HTTP route.
+pub(crate) fn inject_text_core(
+    app: &AppHandle,
+    state: &crate::state::AppState,
+    dictation: &DictationState,
+    text: &str,
+    session_id: Option<&str>,
+) -> Result<String, String> {
     // Apply corrections before injection
-    let corrected = dictation.corrections.lock().correct(&text);
+    let corrected = dictation.corrections.lock().correct(text);
     let final_text = corrected.replace('\n', " ");
+
+    let config = get_dictation_config();
+    if config.focus_window_on_inject
+        && let Some(window) = app.get_webview_window("main")
+    {
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+    deliver_injected_text(
+        config.dictation_target,
+        session_id,
+        &final_text,
+        |sid, data| write_session_text(state, sid, data),
+    )?;
     Ok(final_text)
 }
 
+/// Route corrected text to its target. Returns `true` when `write` delivered it
+/// to a session; `false` means the caller types it at the OS focus.
+fn deliver_injected_text(
+    target: DictationTarget,
+    session_id: Option<&str>,
+    text: &str,
+    write: impl FnOnce(&str, &str) -> Result<(), String>,
+) -> Result<bool, String> {
+    match target {
+        DictationTarget::OsFocus => Ok(false),
+        DictationTarget::ActiveSession => {
+            let session_id = session_id.ok_or("No active session to inject into")?;
+            write(session_id, text)?;
+            Ok(true)
+        }
+    }
+}
+
+/// Write dictated text to a session's PTY (same path as keyboard input).
+fn write_session_text(
+    state: &crate::state::AppState,
+    session_id: &str,
+    text: &str,
+) -> Result<(), String> {
+    use std::io::Write;
+    let entry = state
+        .sessions
+        .get(session_id)
+        .ok_or_else(|| format!("Session not found: {session_id}"))?;
+    let mut session = entry.lock();
+    session
+        .writer
+        .write_all(text.as_bytes())
+        .map_err(|e| format!("Write failed: {e}"))?;
+    let _ = session.writer.flush();
+    drop(session);
+    crate::pty::stamp_input_ms(state, session_id, text);
+    Ok(())
+}
+
+/// Where dictated text goes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DictationTarget {
+    /// Whatever has OS keyboard focus (the frontend inserts it).
+    #[default]
+    OsFocus,
+    /// The active terminal session's input, regardless of focus.
+    ActiveSession,
+}
+
 /// Dictation configuration persisted to <config_dir>/dictation-config.json
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DictationConfig {
@@ -575,6 +641,16 @@ pub struct DictationConfig {
     /// Automatically send (press Enter) after injecting transcribed text.
     #[serde(default)]
     pub auto_send: bool,
+    /// Where injected text goes ("os-focus" or "active-session").
+    #[serde(default)]
+    pub dictation_target: DictationTarget,
+    /// Raise and focus the main window before injecting.
+    #[serde(default)]
+    pub focus_window_on_inject: bool,
+    /// Emit `dictation-partial` events with interim text while recording.
+    /// Off = batch: a single transcription pass on stop.
+    #[serde(default)]
+    pub streaming: bool,
 }
 
 fn default_model() -> String {
@@ -595,6 +671,9 @@ impl Default for DictationConfig {
             device: None,
             long_press_ms: default_long_press_ms(),
             auto_send: false,
+            dictation_target: DictationTarget::OsFocus,
+            focus_window_on_inject: false,
+            streaming: false,
         }
     }
 }
@@ -607,10 +686,73 @@ pub fn get_dictation_config() -> DictationConfig {
 }
 
 #[tauri::command]
-pub fn set_dictation_config(config: DictationConfig) -> Result<(), String> {
+pub fn set_dictation_config(mut config: DictationConfig) -> Result<(), String> {
+    config.language = config.language.trim().to_ascii_lowercase();
+    if config.language.is_empty() {
+        config.language = "auto".to_string();
+    }
+    validate_language(&config.language)?;
     crate::config::save_json_config(DICTATION_CONFIG_FILE, &config)
 }
 
+/// Accept "auto" or a 2–3 letter ISO 639 language code (case-insensitive).
+fn validate_language(lang: &str) -> Result<(), String> {
+    let lang = lang.trim();
+    if lang.eq_ignore_ascii_case("auto")
+        || ((2..=3).contains(&lang.len()) && lang.bytes().all(|b| b.is_ascii_alphabetic()))
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid dictation language '{lang}': expected an ISO 639-1 code or \"auto\""
+        ))
+    }
+}
+
+/// Final transcription pass over the whole recording with the resolved
+/// language hint. `Err` is the skip reason reported to the frontend.
+fn transcribe_final(
+    transcriber: Option<&dyn transcribe::Transcriber>,
+    audio: &[f32],
+    language: Option<&str>,
+    log: impl Fn(&str, &str),
+) -> Result<String, &'static str> {
+    let Some(transcriber) = transcriber else {
+        log("warn", "Transcriber not available — model not loaded");
+        return Err("model not loaded");
+    };
+    let text = match transcriber.transcribe(audio, language) {
+        Ok(result) => {
+            if let Some(reason) = &result.skip_reason {
+                log("info", &format!("Final transcription skipped: {reason}"));
+                String::new()
+            } else {
+                result.text
+            }
+        }
+        Err(e) => {
+            log("warn", &format!("Final transcription failed: {e}"));
+            String::new()
+        }
+    };
+    if text.is_empty() {
+        log("info", "No speech detected");
+        return Err("no speech detected");
+    }
+    Ok(text)
+}
+
+/// Language hint passed to the transcriber. A per-call override wins over the
+/// configured language; "auto" (or empty) means let Whisper detect it.
+fn resolve_language(configured: &str, override_lang: Option<&str>) -> Option<String> {
+    let lang = override_lang.unwrap_or(configured).trim();
+    if lang.is_empty() || lang.eq_ignore_ascii_case("auto") {
+        None
+    } else {
+        Some(lang.to_ascii_lowercase())
+    }
+}
+
 /// Check microphone permission status (macOS TCC).
 /// Returns: "authorized", "denied", "restricted", or "not_determined".
 #[tauri::command]
@@ -623,3 +765,103 @@ pub fn check_microphone_permission() -> String {
 pub fn open_microphone_settings() {
     permission::open_settings();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictation::transcribe::TranscribeResult;
+    use parking_lot::Mutex;
+
+    /// Records the language hint of every transcribe call.
+    struct RecordingTranscriber {
+        languages: Mutex<Vec<Option<String>>>,
+    }
+
+    impl transcribe::Transcriber for RecordingTranscriber {
+        fn transcribe(
+            &self,
+            _audio: &[f32],
+            language: Option<&str>,
+        ) -> Result<TranscribeResult, String> {
+            self.languages.lock().push(language.map(str::to_string));
+            Ok(TranscribeResult {
+                text: "ok".to_string(),
+                skip_reason: None,
+            })
+        }
+    }
+
+    #[test]
+    fn language_override_wins_over_config() {
+        assert_eq!(resolve_language("auto", None), None);
+        assert_eq!(resolve_language("it", None), Some("it".to_string()));
+        assert_eq!(resolve_language("it", Some("DE")), Some("de".to_string()));
+        assert_eq!(resolve_language("it", Some("auto")), None);
+        assert_eq!(resolve_language("", None), None);
+
+        assert!(validate_language("auto").is_ok());
+        assert!(validate_language("en").is_ok());
+        assert!(validate_language("english!").is_err());
+        assert!(validate_language("e").is_err());
+    }
+
+    #[test]
+    fn language_hint_is_threaded_into_transcribe() {
+        let transcriber = RecordingTranscriber {
+            languages: Mutex::new(Vec::new()),
+        };
+        let audio = vec![0.0f32; 16];
+        for (configured, override_lang) in [("fr", None), ("fr", Some("ja")), ("auto", None)] {
+            let lang = resolve_language(configured, override_lang);
+            let text = transcribe_final(Some(&transcriber), &audio, lang.as_deref(), |_, _| {});
+            assert_eq!(text, Ok("ok".to_string()));
+        }
+        assert_eq!(
+            *transcriber.languages.lock(),
+            vec![Some("fr".to_string()), Some("ja".to_string()), None]
+        );
+
+        assert_eq!(
+            transcribe_final(None, &audio, Some("fr"), |_, _| {}),
+            Err("model not loaded")
+        );
+    }
+
+    #[test]
+    fn active_session_target_writes_to_session() {
+        let mut written = None;
+        let delivered = deliver_injected_text(
+            DictationTarget::ActiveSession,
+            Some("s1"),
+            "hello world",
+            |sid, data| {
+                written = Some((sid.to_string(), data.to_string()));
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert!(delivered);
+        assert_eq!(written, Some(("s1".to_string(), "hello world".to_string())));
+
+        let err = deliver_injected_text(DictationTarget::ActiveSession, None, "x", |_, _| {
+            panic!("no session to write to")
+        });
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn os_focus_target_leaves_text_to_caller() {
+        let delivered = deliver_injected_text(DictationTarget::OsFocus, Some("s1"), "x", |_, _| {
+            panic!("os-focus must not write to the session")
+        })
+        .unwrap();
+        assert!(!delivered);
+        let config: DictationConfig =
+            serde_json::from_str(r#"{"enabled":true,"hotkey":"F5","language":"auto"}"#).unwrap();
+        assert_eq!(config.dictation_target, DictationTarget::OsFocus);
+        assert!(!config.focus_window_on_inject);
+        assert!(!config.streaming, "batch transcription is the default");
+        let target: DictationTarget = serde_json::from_str(r#""active-session""#).unwrap();
+        assert_eq!(target, DictationTarget::ActiveSession);
+    }
+}
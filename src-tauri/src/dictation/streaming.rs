@@ -42,6 +42,11 @@ fn ms_to_samples(ms: u32) -> usize {
     (SAMPLE_RATE as usize * ms as usize) / 1000
 }
 
+/// Window size after `current_ms`: grows by `STEP_GROWTH_MS` up to `MAX_STEP_MS`.
+fn next_step_ms(current_ms: u32) -> u32 {
+    (current_ms + STEP_GROWTH_MS).min(MAX_STEP_MS)
+}
+
 /// Manages a streaming transcription session on a background thread.
 pub struct StreamingSession {
     handle: Option<std::thread::JoinHandle<Vec<f32>>>,
@@ -53,20 +58,30 @@ impl StreamingSession {
     ///
     /// The background thread polls `audio_buffer` for new samples, applies VAD,
     /// and feeds speech windows to the transcriber. Partial results are sent
-    /// via `tx`. On stop, the thread returns unconsumed audio for a final
-    /// transcription pass.
+    /// via `tx`. `processing` is held true while a window is being transcribed.
+    /// On stop, the thread returns unconsumed audio for a final transcription pass.
     pub fn start(
         transcriber: Arc<dyn Transcriber>,
         audio_buffer: Arc<Mutex<VecDeque<f32>>>,
         tx: mpsc::Sender<String>,
         language: Option<String>,
+        processing: Arc<AtomicBool>,
     ) -> Self {
         let stop = Arc::new(AtomicBool::new(false));
         let stop_clone = stop.clone();
 
         let handle = std::thread::Builder::new()
             .name("streaming-dictation".into())
-            .spawn(move || streaming_loop(transcriber, audio_buffer, tx, stop_clone, language))
+            .spawn(move || {
+                streaming_loop(
+                    transcriber,
+                    audio_buffer,
+                    tx,
+                    stop_clone,
+                    language,
+                    processing,
+                )
+            })
             .expect("Failed to spawn streaming thread");
 
         Self {
@@ -127,6 +142,7 @@ fn streaming_loop(
     tx: mpsc::Sender<String>,
     stop: Arc<AtomicBool>,
     language: Option<String>,
+    processing: Arc<AtomicBool>,
 ) -> Vec<f32> {
     let mut all_audio: Vec<f32> = Vec::new(); // complete recording for final pass
     let mut step_buf: Vec<f32> = Vec::new();
@@ -178,8 +194,10 @@ fn streaming_loop(
                 window_buf.extend_from_slice(&step_buf);
 
                 // Transcribe the window
-                if let Some(text) =
-                    transcribe_window(&*transcriber, &window_buf, language.as_deref())
+                processing.store(true, Ordering::Release);
+                let partial = transcribe_window(&*transcriber, &window_buf, language.as_deref());
+                processing.store(false, Ordering::Release);
+                if let Some(text) = partial
                     && !text.is_empty()
                     && tx.send(text).is_err()
                 {
@@ -202,9 +220,7 @@ fn streaming_loop(
             // Grow window toward MAX_STEP_MS (grows on both speech and silence
             // to improve quality as the session progresses; only the first window
             // uses the small INITIAL_STEP_MS for low-latency first partial).
-            if current_step_ms < MAX_STEP_MS {
-                current_step_ms = (current_step_ms + STEP_GROWTH_MS).min(MAX_STEP_MS);
-            }
+            current_step_ms = next_step_ms(current_step_ms);
         }
 
         std::thread::sleep(std::time::Duration::from_millis(POLL_INTERVAL_MS));
@@ -296,6 +312,70 @@ mod tests {
             .collect()
     }
 
+    fn idle_flag() -> Arc<AtomicBool> {
+        Arc::new(AtomicBool::new(false))
+    }
+
+    /// Sample ranges the loop would transcribe if `total` samples arrived at
+    /// once, fed window by window (trailing partial window left unprocessed).
+    fn chunk_boundaries(total: usize) -> Vec<(usize, usize)> {
+        let mut bounds = Vec::new();
+        let mut start = 0;
+        let mut step_ms = INITIAL_STEP_MS;
+        while start + ms_to_samples(step_ms) <= total {
+            let end = start + ms_to_samples(step_ms);
+            bounds.push((start, end));
+            start = end;
+            step_ms = next_step_ms(step_ms);
+        }
+        bounds
+    }
+
+    #[test]
+    fn test_chunk_boundaries_from_fixed_sample_count() {
+        // 10s of audio: 1.5s + 2s + 2.5s + 3s windows, 1s left for the final pass
+        assert_eq!(
+            chunk_boundaries(ms_to_samples(10_000)),
+            vec![
+                (0, 24_000),
+                (24_000, 56_000),
+                (56_000, 96_000),
+                (96_000, 144_000)
+            ]
+        );
+        // Shorter than the first window: nothing is streamed
+        assert!(chunk_boundaries(ms_to_samples(1_000)).is_empty());
+        // Windows stay capped at MAX_STEP_MS once reached
+        let long = chunk_boundaries(ms_to_samples(20_000));
+        assert!(
+            long[3..]
+                .iter()
+                .all(|(s, e)| e - s == ms_to_samples(MAX_STEP_MS))
+        );
+    }
+
+    #[test]
+    fn test_processing_flag_cleared_after_window() {
+        let transcriber: Arc<dyn Transcriber> = Arc::new(EchoTranscriber::new());
+        let buffer: Arc<Mutex<VecDeque<f32>>> = Arc::new(Mutex::new(VecDeque::new()));
+        buffer.lock().extend(speech_samples(2000));
+        let stop = Arc::new(AtomicBool::new(false));
+        let processing = idle_flag();
+        let (tx, rx) = mpsc::channel::<String>();
+
+        let (buf_clone, stop_clone, proc_clone) =
+            (buffer.clone(), stop.clone(), processing.clone());
+        let handle = std::thread::spawn(move || {
+            streaming_loop(transcriber, buf_clone, tx, stop_clone, None, proc_clone)
+        });
+
+        rx.recv_timeout(std::time::Duration::from_secs(2))
+            .expect("Should have received a partial");
+        stop.store(true, Ordering::Release);
+        handle.join().expect("Loop should not panic");
+        assert!(!processing.load(Ordering::Acquire));
+    }
+
     #[test]
     fn test_ms_to_samples() {
         assert_eq!(ms_to_samples(1000), 16_000);
@@ -361,7 +441,7 @@ mod tests {
         let stop_clone = stop.clone();
 
         let handle = std::thread::spawn(move || {
-            streaming_loop(transcriber, buf_clone, tx, stop_clone, None)
+            streaming_loop(transcriber, buf_clone, tx, stop_clone, None, idle_flag())
         });
 
         // Let it poll a few times with no data
@@ -394,7 +474,7 @@ mod tests {
         let stop_clone = stop.clone();
 
         let handle = std::thread::spawn(move || {
-            streaming_loop(transcriber, buf_clone, tx, stop_clone, None)
+            streaming_loop(transcriber, buf_clone, tx, stop_clone, None, idle_flag())
         });
 
         // Wait for the loop to process the speech window
@@ -428,7 +508,7 @@ mod tests {
         let stop_clone = stop.clone();
 
         let handle = std::thread::spawn(move || {
-            streaming_loop(transcriber, buf_clone, tx, stop_clone, None)
+            streaming_loop(transcriber, buf_clone, tx, stop_clone, None, idle_flag())
         });
 
         std::thread::sleep(std::time::Duration::from_millis(200));
@@ -460,7 +540,7 @@ mod tests {
         let stop_clone = stop.clone();
 
         let handle = std::thread::spawn(move || {
-            streaming_loop(transcriber, buf_clone, tx, stop_clone, None)
+            streaming_loop(transcriber, buf_clone, tx, stop_clone, None, idle_flag())
         });
 
         // Loop should terminate on its own due to channel disconnect
@@ -527,7 +607,7 @@ mod tests {
         let stop_clone = stop.clone();
 
         let handle = std::thread::spawn(move || {
-            streaming_loop(transcriber, buf_clone, tx, stop_clone, None)
+            streaming_loop(transcriber, buf_clone, tx, stop_clone, None, idle_flag())
         });
 
         // Let the loop drain the buffer
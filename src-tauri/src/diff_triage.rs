@@ -1245,7 +1245,7 @@ pub(crate) async fn run_diff_triage(
     repo_path: String,
     refresh: Option<bool>,
 ) -> Result<TriageResult, String> {
-    let changed_files = crate::git::get_changed_files(repo_path.clone(), None).await?;
+    let changed_files = crate::git::get_changed_files(repo_path.clone(), None, None, None).await?;
     if changed_files.is_empty() {
         if let Ok(mut sessions) = triage_sessions().lock() {
             sessions.remove(&repo_path);
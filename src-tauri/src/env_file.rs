@@ -0,0 +1,396 @@
+//! `.env`-style file loading for spawned session environments.
+//!
+//! A repo can point at an uncommitted env file (repo settings → `env_file`)
+//! whose `KEY=value` pairs are merged into the child environment of agents and
+//! worktree shells, followed by the repo's `env` map (repo settings → `env`).
+//! Values are secrets: they are never logged, and parse errors report only the
+//! line number and key.
+//!
+//! Full merge order for a spawn, later wins: the inherited app environment,
+//! the terminal variables every PTY gets (`TERM`, `LANG`, …), the repo's
+//! `env_file`, the repo's `env` map, then the caller's per-spawn `env`
+//! (`PtyConfig::env`, or `env` in the `/sessions` and `/sessions/agent` bodies),
+//! checked by [`validate_spawn_env`].
+
+use portable_pty::CommandBuilder;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::RepoSettingsEntry;
+
+/// Where a spawn environment variable comes from, lowest precedence first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum EnvSource {
+    /// The app's own environment, inherited by every child
+    Inherited,
+    /// The repo's `env_file`
+    EnvFile,
+    /// The repo's `env` map in repo settings
+    RepoSettings,
+}
+
+/// Parse `.env` content into ordered `(key, value)` pairs.
+///
+/// Supports blank lines, `#` comments, an optional `export ` prefix,
+/// double-quoted values (with `\n`, `\t`, `\"`, `\\` escapes), single-quoted
+/// literal values, and trailing ` # comment`s after unquoted values.
+pub(crate) fn parse_env_file(content: &str) -> Result<Vec<(String, String)>, String> {
+    let mut vars = Vec::new();
+    for (idx, raw) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        let Some((key, rest)) = line.split_once('=') else {
+            return Err(format!("line {line_no}: expected KEY=value"));
+        };
+        let key = key.trim();
+        if !is_valid_key(key) {
+            return Err(format!("line {line_no}: invalid variable name"));
+        }
+        let value =
+            parse_value(rest.trim_start()).map_err(|e| format!("line {line_no} ({key}): {e}"))?;
+        vars.push((key.to_string(), value));
+    }
+    Ok(vars)
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn parse_value(raw: &str) -> Result<String, String> {
+    if let Some(body) = raw.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = body.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return trailing_is_comment(chars.as_str()).map(|()| out),
+                '\\' => match chars.next() {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some('r') => out.push('\r'),
+                    Some(other) => out.push(other),
+                    None => break,
+                },
+                _ => out.push(c),
+            }
+        }
+        return Err("unterminated double-quoted value".to_string());
+    }
+    if let Some(body) = raw.strip_prefix('\'') {
+        let Some(end) = body.find('\'') else {
+            return Err("unterminated single-quoted value".to_string());
+        };
+        trailing_is_comment(&body[end + 1..])?;
+        return Ok(body[..end].to_string());
+    }
+    // Unquoted: a `#` preceded by whitespace starts an inline comment.
+    let value = match raw.find(" #").or_else(|| raw.find("\t#")) {
+        Some(pos) => &raw[..pos],
+        None => raw,
+    };
+    Ok(value.trim_end().to_string())
+}
+
+fn trailing_is_comment(rest: &str) -> Result<(), String> {
+    let rest = rest.trim_start();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err("unexpected characters after closing quote".to_string())
+    }
+}
+
+/// Resolve `relative` against `repo_path`, rejecting anything outside the repo
+/// (absolute paths, `..` traversal, symlinks pointing out).
+fn resolve_env_file_path(repo_path: &Path, relative: &str) -> Result<PathBuf, String> {
+    let canonical_repo = repo_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve repo path: {e}"))?;
+    let target = repo_path.join(relative);
+    let canonical_target = target
+        .canonicalize()
+        .map_err(|e| format!("Env file '{relative}' not readable: {e}"))?;
+    if !canonical_target.starts_with(&canonical_repo) {
+        return Err(format!(
+            "Access denied: env file '{relative}' is outside repository"
+        ));
+    }
+    Ok(canonical_target)
+}
+
+/// Load and parse a repo-relative env file.
+pub(crate) fn load_env_file(
+    repo_path: &Path,
+    relative: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let path = resolve_env_file_path(repo_path, relative)?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read env file '{relative}': {e}"))?;
+    parse_env_file(&content).map_err(|e| format!("Invalid env file '{relative}': {e}"))
+}
+
+/// The repo's spawn variables in application order: `env_file` pairs, then
+/// the `env` map (sorted by key). Later entries win.
+pub(crate) fn repo_env_layers(
+    repo_path: &Path,
+    entry: &RepoSettingsEntry,
+) -> Result<Vec<(EnvSource, String, String)>, String> {
+    let mut layers = Vec::new();
+    if let Some(rel) = entry.env_file.as_deref().map(str::trim)
+        && !rel.is_empty()
+    {
+        let vars = load_env_file(repo_path, rel)?;
+        tracing::debug!(
+            repo = %repo_path.display(),
+            count = vars.len(),
+            "Loaded env file for spawn"
+        );
+        layers.extend(vars.into_iter().map(|(k, v)| (EnvSource::EnvFile, k, v)));
+    }
+    let mut settings_vars: Vec<_> = entry.env.iter().collect();
+    settings_vars.sort();
+    layers.extend(
+        settings_vars
+            .into_iter()
+            .map(|(k, v)| (EnvSource::RepoSettings, k.clone(), v.clone())),
+    );
+    Ok(layers)
+}
+
+/// [`repo_env_layers`] for the repo that owns `cwd`, if any.
+///
+/// The owning repo is found by [`owning_repo`]. Returns an empty list when no
+/// repo matches or the repo has no `env_file` / `env` configured.
+pub(crate) fn repo_env_layers_for_cwd(
+    cwd: &str,
+) -> Result<Vec<(EnvSource, String, String)>, String> {
+    let settings = crate::config::load_repo_settings();
+    match owning_repo(&settings.repos, Path::new(cwd)) {
+        Some((repo_path, entry)) => repo_env_layers(Path::new(repo_path), entry),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// The repo in `repos` that owns `cwd`. A git checkout resolves to its main
+/// repo through the git common dir first, so a linked worktree outside the
+/// repo directory still gets the repo's env; otherwise the repo whose path
+/// equals or contains `cwd` wins (longest match).
+fn owning_repo<'a>(
+    repos: &'a HashMap<String, RepoSettingsEntry>,
+    cwd: &Path,
+) -> Option<(&'a String, &'a RepoSettingsEntry)> {
+    let registered: Vec<String> = repos.keys().cloned().collect();
+    if let Some(found) = crate::git::find_repo_for_path_in(cwd, &registered) {
+        return repos.get_key_value(&found.repo_path);
+    }
+    repos
+        .iter()
+        .filter(|(path, _)| cwd.starts_with(Path::new(path.as_str())))
+        .max_by_key(|(path, _)| path.len())
+}
+
+/// Load the env variables configured for the repo that owns `cwd`, in
+/// application order (see [`repo_env_layers_for_cwd`]).
+pub(crate) fn load_repo_env_for_cwd(cwd: &str) -> Result<Vec<(String, String)>, String> {
+    Ok(repo_env_layers_for_cwd(cwd)?
+        .into_iter()
+        .map(|(_, k, v)| (k, v))
+        .collect())
+}
+
+/// Variables a per-spawn `env` may not set: our own session binding, the
+/// terminal type the output parser relies on, and loader hooks that inject
+/// code into the child. `PATH` and everything else is allowed.
+const SPAWN_ENV_DENYLIST: &[&str] = &[
+    "TUIC_SESSION",
+    "TUIC_CONFIG_DIR",
+    "TERM",
+    "LD_PRELOAD",
+    "LD_LIBRARY_PATH",
+    "DYLD_INSERT_LIBRARIES",
+    "DYLD_LIBRARY_PATH",
+    "DYLD_FRAMEWORK_PATH",
+];
+
+/// Validate caller-supplied per-spawn env vars before they reach a command.
+/// Errors name the offending key, never the value.
+pub(crate) fn validate_spawn_env(env: &HashMap<String, String>) -> Result<(), String> {
+    let mut keys: Vec<&String> = env.keys().collect();
+    keys.sort();
+    for key in keys {
+        if !is_valid_key(key) {
+            return Err(format!("Invalid environment variable name '{key}'"));
+        }
+        let upper = key.to_ascii_uppercase();
+        if SPAWN_ENV_DENYLIST.contains(&upper.as_str()) {
+            return Err(format!("Environment variable '{key}' cannot be overridden"));
+        }
+        if env[key].contains('\0') {
+            return Err(format!("Environment variable '{key}' contains a NUL byte"));
+        }
+    }
+    Ok(())
+}
+
+/// Merge env-file variables into a spawn command.
+pub(crate) fn apply_env_vars(cmd: &mut CommandBuilder, vars: &[(String, String)]) {
+    for (key, value) in vars {
+        cmd.env(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_quotes_comments_and_export() {
+        let content = r#"
+# database settings
+export DB_HOST=localhost
+DB_PASS="p@ss # not a comment"
+TOKEN='literal $VALUE \n'
+EMPTY=
+MULTI="line1\nline2"
+PLAIN=value # trailing comment
+"#;
+        let vars = parse_env_file(content).unwrap();
+        assert_eq!(
+            vars,
+            vec![
+                ("DB_HOST".to_string(), "localhost".to_string()),
+                ("DB_PASS".to_string(), "p@ss # not a comment".to_string()),
+                ("TOKEN".to_string(), "literal $VALUE \\n".to_string()),
+                ("EMPTY".to_string(), String::new()),
+                ("MULTI".to_string(), "line1\nline2".to_string()),
+                ("PLAIN".to_string(), "value".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors_report_line_without_value() {
+        let err = parse_env_file("OK=1\nSECRET=\"hunter2").unwrap_err();
+        assert!(err.contains("line 2"), "{err}");
+        assert!(err.contains("SECRET"), "{err}");
+        assert!(!err.contains("hunter2"), "value leaked: {err}");
+
+        let err = parse_env_file("not a pair").unwrap_err();
+        assert!(err.contains("line 1"), "{err}");
+        let err = parse_env_file("1BAD=x").unwrap_err();
+        assert!(err.contains("invalid variable name"), "{err}");
+    }
+
+    #[test]
+    fn spawn_env_allows_path_and_rejects_denylisted_keys() {
+        let env = |k: &str| HashMap::from([(k.to_string(), "v".to_string())]);
+        assert!(validate_spawn_env(&env("PATH")).is_ok());
+        assert!(validate_spawn_env(&env("ANTHROPIC_MODEL")).is_ok());
+        for key in [
+            "TUIC_SESSION",
+            "TERM",
+            "LD_PRELOAD",
+            "dyld_insert_libraries",
+        ] {
+            let err = validate_spawn_env(&env(key)).unwrap_err();
+            assert!(err.contains("cannot be overridden"), "{key}: {err}");
+        }
+        let err = validate_spawn_env(&env("BAD-NAME")).unwrap_err();
+        assert!(err.contains("Invalid environment variable name"), "{err}");
+        let nul = HashMap::from([("A".to_string(), "x\0y".to_string())]);
+        assert!(validate_spawn_env(&nul).is_err());
+    }
+
+    #[test]
+    fn external_worktree_resolves_to_its_main_repo() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        let worktree = dir.path().join("elsewhere").join("feature");
+        std::fs::create_dir(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let out = std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo)
+                .output()
+                .unwrap();
+            assert!(
+                out.status.success(),
+                "{}",
+                String::from_utf8_lossy(&out.stderr)
+            );
+        };
+        git(&["init", "-q"]);
+        git(&[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "init",
+        ]);
+        git(&[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "feature",
+            &worktree.to_string_lossy(),
+        ]);
+
+        let repo_key = repo.to_string_lossy().to_string();
+        let repos: HashMap<String, RepoSettingsEntry> = [(
+            repo_key.clone(),
+            RepoSettingsEntry {
+                path: repo_key.clone(),
+                ..Default::default()
+            },
+        )]
+        .into_iter()
+        .collect();
+
+        let (owner, _) = owning_repo(&repos, &worktree.join("src")).unwrap();
+        assert_eq!(owner, &repo_key);
+        let (owner, _) = owning_repo(&repos, &repo).unwrap();
+        assert_eq!(owner, &repo_key);
+        assert!(owning_repo(&repos, dir.path()).is_none());
+    }
+
+    #[test]
+    fn rejects_env_file_outside_repo() {
+        let outer = tempfile::TempDir::new().unwrap();
+        let repo = outer.path().join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        std::fs::write(outer.path().join(".env"), "A=1\n").unwrap();
+        let err = load_env_file(&repo, "../.env").unwrap_err();
+        assert!(err.contains("outside repository"), "{err}");
+    }
+
+    #[test]
+    fn env_file_values_reach_child_command() {
+        let repo = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            repo.path().join(".env.local"),
+            "API_KEY=\"abc 123\"\n# comment\nREGION=eu\n",
+        )
+        .unwrap();
+        let vars = load_env_file(repo.path(), ".env.local").unwrap();
+        let mut cmd = CommandBuilder::new("sh");
+        apply_env_vars(&mut cmd, &vars);
+        assert_eq!(
+            cmd.get_env("API_KEY").and_then(|v| v.to_str()),
+            Some("abc 123")
+        );
+        assert_eq!(cmd.get_env("REGION").and_then(|v| v.to_str()), Some("eu"));
+    }
+}
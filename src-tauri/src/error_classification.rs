@@ -65,6 +65,33 @@ pub(crate) fn classify_error(message: &str) -> &'static str {
     "unknown"
 }
 
+/// Whether a PTY open/spawn error is worth retrying: resource exhaustion or
+/// an interrupted call (EAGAIN, EINTR, EMFILE, ENFILE, ENOSPC from a full pty
+/// table). Anything else — a missing shell, a bad cwd, permission denied — is
+/// permanent and retrying would only delay the error.
+pub(crate) fn is_transient_spawn_error(message: &str) -> bool {
+    // errno values differ per platform (EAGAIN is 11 on Linux, 35 on macOS).
+    #[cfg(unix)]
+    {
+        lazy_static::lazy_static! {
+            static ref OS_ERROR: regex::Regex = regex::Regex::new(r"os error (\d+)").unwrap();
+        }
+        if let Some(code) = OS_ERROR
+            .captures(message)
+            .and_then(|c| c[1].parse::<i32>().ok())
+        {
+            return matches!(
+                code,
+                libc::EINTR | libc::EAGAIN | libc::ENFILE | libc::EMFILE | libc::ENOSPC
+            );
+        }
+    }
+    let lower = message.to_lowercase();
+    lower.contains("resource temporarily unavailable")
+        || lower.contains("too many open files")
+        || lower.contains("interrupted system call")
+}
+
 /// Calculate delay with exponential backoff.
 ///
 /// Formula: min(base_delay_ms * multiplier^retry_count + jitter, max_delay_ms)
@@ -188,6 +215,32 @@ mod tests {
         assert!(delay <= 5000.0, "large retry should cap: got {delay}");
     }
 
+    #[test]
+    fn classifies_transient_spawn_errors() {
+        assert!(is_transient_spawn_error(
+            "Failed to open PTY: Too many open files (os error 24)"
+        ));
+        assert!(is_transient_spawn_error(&format!(
+            "Failed to spawn shell: Resource temporarily unavailable (os error {})",
+            libc::EAGAIN
+        )));
+        assert!(is_transient_spawn_error("openpty: too many open files"));
+        assert!(!is_transient_spawn_error(
+            "Failed to spawn shell: No such file or directory (os error 2)"
+        ));
+        #[cfg(target_os = "linux")]
+        assert!(
+            !is_transient_spawn_error("Failed to spawn shell: (os error 35)"),
+            "35 is EDEADLK on Linux, not EAGAIN"
+        );
+        assert!(!is_transient_spawn_error(
+            "Failed to spawn shell: Permission denied (os error 13)"
+        ));
+        assert!(!is_transient_spawn_error(
+            "Failed to spawn shell: unable to spawn /bin/nope"
+        ));
+    }
+
     #[test]
     fn backoff_delay_zero_retry() {
         let delay = calculate_backoff_delay(0, 1000.0, 30000.0, 2.0);
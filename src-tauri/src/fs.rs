@@ -1105,16 +1105,179 @@ pub fn fs_read_file(repo_path: String, file: String) -> Result<String, String> {
     crate::read_file_impl(repo_path, file)
 }
 
+/// File content plus what kind of file it is.
+#[derive(Serialize)]
+pub struct FileReadInfo {
+    pub content: String,
+    /// The file is a Git LFS pointer: `content` is the pointer text, not the object.
+    pub lfs: bool,
+    pub lfs_pointer: Option<crate::git::LfsPointer>,
+}
+
+/// Like [`fs_read_file`], but flags Git LFS pointer files so viewers can show
+/// the pointer metadata instead of presenting the pointer as the file.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub fn fs_read_file_info(repo_path: String, file: String) -> Result<FileReadInfo, String> {
+    let content = crate::read_file_impl(repo_path, file)?;
+    let lfs_pointer = crate::git::parse_lfs_pointer(content.as_bytes());
+    Ok(FileReadInfo {
+        lfs: lfs_pointer.is_some(),
+        lfs_pointer,
+        content,
+    })
+}
+
+/// One line of a [`FileDiffHunk`].
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FileDiffLine {
+    /// `"+"` (only in `file_b`), `"-"` (only in `file_a`) or `" "` (context).
+    pub kind: String,
+    /// Line text without its trailing newline.
+    pub content: String,
+    /// 1-based line number in `file_a` (absent for additions).
+    pub old_line: Option<u32>,
+    /// 1-based line number in `file_b` (absent for deletions).
+    pub new_line: Option<u32>,
+}
+
+/// A `@@` block of a file-to-file diff.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDiffHunk {
+    /// The `@@ -a,b +c,d @@` line.
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<FileDiffLine>,
+}
+
+/// Result of [`diff_files`]: the unified text (for the diff viewer) and the
+/// same hunks in structured form. No hunks means the files are identical.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDiff {
+    pub unified: String,
+    pub hunks: Vec<FileDiffHunk>,
+}
+
+/// Lines of context around each change, matching `git diff`.
+const FILE_DIFF_CONTEXT: usize = 3;
+
+/// Unified-diff start for a range: 1-based, or the preceding line when empty.
+fn hunk_start(range: &std::ops::Range<usize>) -> u32 {
+    if range.is_empty() {
+        range.start as u32
+    } else {
+        range.start as u32 + 1
+    }
+}
+
+/// Diff two texts line by line; `name_a`/`name_b` label the `---`/`+++` header.
+pub(crate) fn diff_texts(name_a: &str, name_b: &str, a: &str, b: &str) -> FileDiff {
+    let diff = similar::TextDiff::from_lines(a, b);
+    let mut hunks = Vec::new();
+    for group in diff.grouped_ops(FILE_DIFF_CONTEXT) {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let old = first.old_range().start..last.old_range().end;
+        let new = first.new_range().start..last.new_range().end;
+        let (old_start, new_start) = (hunk_start(&old), hunk_start(&new));
+        let (old_lines, new_lines) = (old.len() as u32, new.len() as u32);
+
+        let mut lines = Vec::new();
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let kind = match change.tag() {
+                    similar::ChangeTag::Equal => " ",
+                    similar::ChangeTag::Delete => "-",
+                    similar::ChangeTag::Insert => "+",
+                };
+                lines.push(FileDiffLine {
+                    kind: kind.to_string(),
+                    content: change.value().trim_end_matches(['\n', '\r']).to_string(),
+                    old_line: change.old_index().map(|i| i as u32 + 1),
+                    new_line: change.new_index().map(|i| i as u32 + 1),
+                });
+            }
+        }
+        hunks.push(FileDiffHunk {
+            header: format!("@@ -{old_start},{old_lines} +{new_start},{new_lines} @@"),
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+            lines,
+        });
+    }
+
+    let unified = if hunks.is_empty() {
+        String::new()
+    } else {
+        diff.unified_diff()
+            .context_radius(FILE_DIFF_CONTEXT)
+            .header(name_a, name_b)
+            .to_string()
+    };
+    FileDiff { unified, hunks }
+}
+
+/// Diff two working-tree files of a repository, independent of git (works on
+/// untracked and ignored files). Both paths must stay inside `repo_path`.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub fn diff_files(repo_path: String, file_a: String, file_b: String) -> Result<FileDiff, String> {
+    let a = crate::read_file_impl(repo_path.clone(), file_a.clone())?;
+    let b = crate::read_file_impl(repo_path, file_b.clone())?;
+    Ok(diff_texts(&file_a, &file_b, &a, &b))
+}
+
 /// Write content to a file within a repository.
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub fn write_file(repo_path: String, file: String, content: String) -> Result<(), String> {
-    let (_canonical_repo, canonical_target) = if PathBuf::from(&repo_path).join(&file).exists() {
-        validate_path(&repo_path, &file)?
+    write_file_atomic(&repo_path, &file, &content, false).map(|_| ())
+}
+
+/// Write a file within a repository via temp file + rename, so readers never
+/// see a partial write. An existing file keeps its permissions. With
+/// `create_parents`, missing parent directories are created (inside the repo
+/// only). Returns the number of bytes written.
+pub(crate) fn write_file_atomic(
+    repo_path: &str,
+    file: &str,
+    content: &str,
+    create_parents: bool,
+) -> Result<u64, String> {
+    if create_parents
+        && let Some(parent) = std::path::Path::new(file).parent()
+        && !parent.as_os_str().is_empty()
+    {
+        create_directory(repo_path.to_string(), parent.to_string_lossy().into_owned())?;
+    }
+    let (_canonical_repo, canonical_target) = if PathBuf::from(repo_path).join(file).exists() {
+        validate_path(repo_path, file)?
     } else {
-        validate_path_for_creation(&repo_path, &file)?
+        validate_path_for_creation(repo_path, file)?
     };
 
-    std::fs::write(&canonical_target, &content).map_err(|e| format!("Failed to write file: {e}"))
+    let parent = canonical_target
+        .parent()
+        .ok_or_else(|| "Failed to write file: target has no parent directory".to_string())?;
+    // The temp file gets a random name and is created with O_EXCL, so a
+    // symlink planted in the repo can't redirect the write and concurrent
+    // writes to one file don't share a temp path. Dropped (and removed) on error.
+    (|| -> std::io::Result<()> {
+        use std::io::Write;
+        let mut tmp = tempfile::NamedTempFile::new_in(parent)?;
+        tmp.write_all(content.as_bytes())?;
+        tmp.as_file().sync_all()?;
+        if let Ok(meta) = std::fs::metadata(&canonical_target) {
+            tmp.as_file().set_permissions(meta.permissions())?;
+        }
+        tmp.persist(&canonical_target).map_err(|e| e.error)?;
+        Ok(())
+    })()
+    .map_err(|e| format!("Failed to write file: {e}"))?;
+    Ok(content.len() as u64)
 }
 
 /// Create a directory (and parents) within a repository.
@@ -1816,6 +1979,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fs_read_file_info_flags_lfs_pointer() {
+        let dir = setup_test_repo();
+        let repo_path = dir.path().to_string_lossy().to_string();
+        fs::write(
+            dir.path().join("asset.psd"),
+            "version https://git-lfs.github.com/spec/v1\noid sha256:abc123\nsize 2048\n",
+        )
+        .unwrap();
+
+        let info = fs_read_file_info(repo_path.clone(), "asset.psd".to_string()).unwrap();
+        assert!(info.lfs);
+        let pointer = info.lfs_pointer.unwrap();
+        assert_eq!(pointer.oid, "sha256:abc123");
+        assert_eq!(pointer.size, 2048);
+
+        let info = fs_read_file_info(repo_path, "README.md".to_string()).unwrap();
+        assert!(!info.lfs);
+        assert!(info.lfs_pointer.is_none());
+        assert_eq!(info.content, "# Test");
+    }
+
+    #[test]
+    fn test_write_file_atomic_creates_parents_and_keeps_mode() {
+        let dir = setup_test_repo();
+        let repo_path = dir.path().to_string_lossy().to_string();
+
+        let written = write_file_atomic(&repo_path, "a/b/new.txt", "héllo", true).unwrap();
+        assert_eq!(written, "héllo".len() as u64);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a/b/new.txt")).unwrap(),
+            "héllo"
+        );
+        let leftovers: Vec<_> = fs::read_dir(dir.path().join("a/b"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(leftovers, vec!["new.txt"], "no temp file left behind");
+        assert!(write_file_atomic(&repo_path, "x/y.txt", "no parents", false).is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let script = dir.path().join("run.sh");
+            fs::write(&script, "#!/bin/sh\n").unwrap();
+            fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+            write_file_atomic(&repo_path, "run.sh", "#!/bin/sh\necho hi\n", false).unwrap();
+            let mode = fs::metadata(&script).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_atomic_ignores_planted_temp_symlink() {
+        let dir = setup_test_repo();
+        let outside = TempDir::new().unwrap();
+        let victim = outside.path().join("victim.txt");
+        fs::write(&victim, "untouched").unwrap();
+        // A repo can commit a symlink under the old fixed temp name.
+        std::os::unix::fs::symlink(&victim, dir.path().join("foo.tmp.tuic")).unwrap();
+        let repo_path = dir.path().to_string_lossy().to_string();
+
+        write_file_atomic(&repo_path, "foo", "new content", false).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("foo")).unwrap(),
+            "new content"
+        );
+        assert_eq!(fs::read_to_string(&victim).unwrap(), "untouched");
+    }
+
+    #[test]
+    fn test_write_file_atomic_parents_outside_repo_rejected() {
+        let dir = setup_test_repo();
+        let repo_path = dir.path().to_string_lossy().to_string();
+        assert!(write_file_atomic(&repo_path, "../outside/f.txt", "bad", true).is_err());
+        assert!(!dir.path().parent().unwrap().join("outside").exists());
+    }
+
     #[test]
     fn test_write_file_path_traversal_rejected() {
         let dir = setup_test_repo();
@@ -2934,4 +3176,59 @@ mod tests {
         assert!(result.matches.is_empty());
         assert!(!result.truncated);
     }
+
+    #[test]
+    fn diff_files_reports_small_change_in_untracked_files() {
+        let repo = setup_test_repo();
+        let path = repo.path().to_string_lossy().to_string();
+        let template: String = (1..=10).map(|i| format!("line {i}\n")).collect();
+        fs::write(repo.path().join("template.txt"), &template).unwrap();
+        fs::write(
+            repo.path().join("generated.txt"),
+            template.replace("line 5\n", "line five\n"),
+        )
+        .unwrap();
+
+        let diff = diff_files(path, "template.txt".into(), "generated.txt".into()).unwrap();
+
+        assert_eq!(diff.hunks.len(), 1);
+        let hunk = &diff.hunks[0];
+        assert_eq!(hunk.header, "@@ -2,7 +2,7 @@");
+        let changed: Vec<_> = hunk.lines.iter().filter(|l| l.kind != " ").collect();
+        assert_eq!(
+            changed,
+            vec![
+                &FileDiffLine {
+                    kind: "-".into(),
+                    content: "line 5".into(),
+                    old_line: Some(5),
+                    new_line: None,
+                },
+                &FileDiffLine {
+                    kind: "+".into(),
+                    content: "line five".into(),
+                    old_line: None,
+                    new_line: Some(5),
+                },
+            ]
+        );
+        assert!(
+            diff.unified
+                .starts_with("--- template.txt\n+++ generated.txt\n")
+        );
+        assert!(diff.unified.contains("-line 5\n+line five\n"));
+    }
+
+    #[test]
+    fn diff_files_identical_has_no_hunks_and_rejects_escapes() {
+        let repo = setup_test_repo();
+        let path = repo.path().to_string_lossy().to_string();
+        fs::write(repo.path().join("copy.md"), "# Test").unwrap();
+
+        let diff = diff_files(path.clone(), "README.md".into(), "copy.md".into()).unwrap();
+        assert!(diff.hunks.is_empty());
+        assert!(diff.unified.is_empty());
+
+        assert!(diff_files(path, "README.md".into(), "../outside.md".into()).is_err());
+    }
 }
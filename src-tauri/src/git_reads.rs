@@ -546,6 +546,7 @@ impl GitReads for GixGitReads {
                 (!s.is_empty()).then_some(s)
             };
 
+            let last_commit_sha = Some(commit.id.to_string());
             let is_merged = merged.contains(&name);
             branches.push(BranchDetail {
                 is_main: crate::git::is_main_branch(&name),
@@ -559,6 +560,8 @@ impl GitReads for GixGitReads {
                 last_commit_date,
                 last_commit_message,
                 last_commit_author,
+                last_commit_sha,
+                worktree_path: None,
                 base_ahead: None,
                 base_behind: None,
                 base_branch: None,
@@ -174,9 +174,19 @@ impl GitHubCircuitBreaker {
     }
 }
 
-/// Parse a git remote URL into (owner, repo) for GitHub repos.
-/// Supports HTTPS (github.com/owner/repo.git) and SSH (git@github.com:owner/repo.git).
-pub(crate) fn parse_remote_url(url: &str) -> Option<(String, String)> {
+/// Forge hosting a repo's `origin`, with the path components each API needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RemoteHost {
+    /// github.com — (owner, repo)
+    GitHub(String, String),
+    /// gitlab.com — (group, project). `group` may contain subgroups (`a/b`).
+    GitLab(String, String),
+}
+
+/// Parse a git remote URL into the hosting forge and its path components.
+/// Supports HTTPS (host/owner/repo.git) and SSH (git@host:owner/repo.git)
+/// for github.com and gitlab.com.
+pub(crate) fn parse_remote_host(url: &str) -> Option<RemoteHost> {
     let url = url.trim();
 
     // SSH: git@github.com:owner/repo.git
@@ -184,7 +194,10 @@ pub(crate) fn parse_remote_url(url: &str) -> Option<(String, String)> {
         let path = path.strip_suffix(".git").unwrap_or(path);
         let parts: Vec<&str> = path.splitn(2, '/').collect();
         if parts.len() == 2 && !parts[0].is_empty() && !parts[1].is_empty() {
-            return Some((parts[0].to_string(), parts[1].to_string()));
+            return Some(RemoteHost::GitHub(
+                parts[0].to_string(),
+                parts[1].to_string(),
+            ));
         }
     }
 
@@ -198,15 +211,46 @@ pub(crate) fn parse_remote_url(url: &str) -> Option<(String, String)> {
         let path = path.strip_suffix(".git").unwrap_or(path);
         let parts: Vec<&str> = path.splitn(3, '/').collect();
         if parts.len() >= 2 && !parts[0].is_empty() && !parts[1].is_empty() {
-            return Some((parts[0].to_string(), parts[1].to_string()));
+            return Some(RemoteHost::GitHub(
+                parts[0].to_string(),
+                parts[1].to_string(),
+            ));
+        }
+    }
+
+    // GitLab: git@gitlab.com:group/sub/project.git or https://gitlab.com/group/sub/project.git.
+    // Nested groups are allowed, so the project is the last segment.
+    let gitlab_path = url.strip_prefix("git@gitlab.com:").or_else(|| {
+        url.strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .and_then(|rest| rest.strip_prefix("gitlab.com/"))
+    });
+    if let Some(path) = gitlab_path {
+        let path = path.trim_end_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        if let Some((group, project)) = path.rsplit_once('/')
+            && !group.is_empty()
+            && !project.is_empty()
+            && !group.split('/').any(str::is_empty)
+        {
+            return Some(RemoteHost::GitLab(group.to_string(), project.to_string()));
         }
     }
 
     None
 }
 
+/// Parse a git remote URL into (owner, repo) for GitHub repos.
+/// GitHub-only view of [`parse_remote_host`] for the GitHub API callers.
+pub(crate) fn parse_remote_url(url: &str) -> Option<(String, String)> {
+    match parse_remote_host(url)? {
+        RemoteHost::GitHub(owner, repo) => Some((owner, repo)),
+        RemoteHost::GitLab(..) => None,
+    }
+}
+
 /// Parse a header value as a u64, returning None if missing or unparseable.
-fn header_as_u64(headers: &reqwest::header::HeaderMap, name: &str) -> Option<u64> {
+pub(crate) fn header_as_u64(headers: &reqwest::header::HeaderMap, name: &str) -> Option<u64> {
     headers.get(name)?.to_str().ok()?.parse().ok()
 }
 
@@ -340,7 +384,7 @@ pub(crate) async fn graphql_request(
 /// Calculate how long to wait for a rate limit, in seconds.
 /// Prefers `retry-after` (secondary limits), falls back to `reset_at - now + 1`,
 /// defaults to 60s if neither header is available.
-fn rate_limit_wait_secs(reset_at: Option<u64>, retry_after: Option<u64>) -> u64 {
+pub(crate) fn rate_limit_wait_secs(reset_at: Option<u64>, retry_after: Option<u64>) -> u64 {
     if let Some(secs) = retry_after {
         return secs;
     }
@@ -356,6 +400,78 @@ fn rate_limit_wait_secs(reset_at: Option<u64>, retry_after: Option<u64>) -> u64
     60 // Default: wait 60 seconds
 }
 
+/// Return the stored GitHub token, resolving it lazily on first use
+/// (boot skips the keychain so startup never blocks on a credential prompt).
+async fn current_github_token(state: &AppState) -> Result<String, String> {
+    let mut current_token = state.github_token.read().clone();
+    if current_token.is_none() {
+        let (t, s) = tokio::task::spawn_blocking(crate::github_auth::resolve_token_with_source)
+            .await
+            .map_err(|e| format!("token resolve task panicked: {e}"))?;
+        if t.is_some() {
+            *state.github_token.write() = t.clone();
+            *state.github_token_source.write() = s;
+        }
+        current_token = t;
+    }
+    current_token.ok_or_else(|| "No GitHub token available".to_string())
+}
+
+/// POST a JSON body to a GitHub REST endpoint with circuit breaker protection.
+/// Rate limits feed the breaker's rate-limit backoff, 5xx/network errors count
+/// as failures; 4xx responses mean the API is reachable and leave it untouched.
+async fn rest_post_with_breaker(
+    state: &AppState,
+    url: &str,
+    body: &serde_json::Value,
+    caller: &str,
+) -> Result<serde_json::Value, String> {
+    state.github_circuit_breaker.check()?;
+    let token = current_github_token(state).await?;
+    crate::github_debug::log_api("POST", url, caller);
+
+    let response = match state
+        .http_client
+        .post(url)
+        .header("Authorization", format!("Bearer {token}"))
+        .header("User-Agent", "tuicommander")
+        .header("Accept", "application/vnd.github+json")
+        .json(body)
+        .send()
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            state.github_circuit_breaker.record_failure();
+            return Err(format!("GitHub API request failed: {e}"));
+        }
+    };
+
+    let status = response.status();
+    let ratelimit_remaining = header_as_u64(response.headers(), "x-ratelimit-remaining");
+    let ratelimit_reset = header_as_u64(response.headers(), "x-ratelimit-reset");
+    let retry_after = header_as_u64(response.headers(), "retry-after");
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .unwrap_or_else(|_| serde_json::json!({}));
+
+    if status.is_success() {
+        state.github_circuit_breaker.record_success();
+        return Ok(json);
+    }
+    let msg = json["message"].as_str().unwrap_or("Unknown error");
+    if status.as_u16() == 429 || (status.as_u16() == 403 && ratelimit_remaining == Some(0)) {
+        let wait = rate_limit_wait_secs(ratelimit_reset, retry_after);
+        state.github_circuit_breaker.record_rate_limit(wait);
+        return Err(format!("rate-limit: {msg}"));
+    }
+    if status.is_server_error() {
+        state.github_circuit_breaker.record_failure();
+    }
+    Err(format!("GitHub API error ({status}): {msg}"))
+}
+
 /// Execute a GraphQL query with token fallback and circuit breaker protection.
 /// On 401, tries remaining token candidates and updates the stored token on success.
 /// Rate limits are handled separately from failures — they don't inflate the failure count.
@@ -378,22 +494,7 @@ pub(crate) async fn graphql_with_retry(
         );
     }
 
-    let mut current_token = state.github_token.read().clone();
-    // Lazy resolution: boot skips keychain, resolve on first use.
-    if current_token.is_none() {
-        let (t, s) = tokio::task::spawn_blocking(crate::github_auth::resolve_token_with_source)
-            .await
-            .map_err(|e| format!("token resolve task panicked: {e}"))?;
-        if t.is_some() {
-            *state.github_token.write() = t.clone();
-            *state.github_token_source.write() = s;
-        }
-        current_token = t;
-    }
-    let token = match current_token.as_deref() {
-        Some(t) => t.to_string(),
-        None => return Err("No GitHub token available".to_string()),
-    };
+    let token = current_github_token(state).await?;
 
     match graphql_request(&state.http_client, &token, query, &variables).await {
         Ok(response) => {
@@ -584,6 +685,29 @@ pub(crate) struct PrLabel {
     background_color: String,
 }
 
+impl PrLabel {
+    /// Build a label from its name and 6-char hex color (no `#`), pre-computing
+    /// display colors. Colors of any other shape get empty display colors.
+    pub(crate) fn new(name: String, color: String) -> Self {
+        let (text_color, background_color) = if color.len() == 6 {
+            let text = if is_light_color(&color) {
+                "#1e1e1e"
+            } else {
+                "#e5e5e5"
+            };
+            (text.to_string(), hex_to_rgba(&color, LABEL_BG_OPACITY))
+        } else {
+            (String::new(), String::new())
+        };
+        Self {
+            name,
+            color,
+            text_color,
+            background_color,
+        }
+    }
+}
+
 /// PR status for a branch, returned by batch endpoint
 #[derive(Clone, Debug, Serialize)]
 pub(crate) struct BranchPrStatus {
@@ -751,22 +875,7 @@ fn parse_pr_node(v: &serde_json::Value) -> Option<BranchPrStatus> {
             arr.iter()
                 .filter_map(|l| {
                     let color = l["color"].as_str().unwrap_or("").to_string();
-                    let (text_color, background_color) = if color.len() == 6 {
-                        let text = if is_light_color(&color) {
-                            "#1e1e1e"
-                        } else {
-                            "#e5e5e5"
-                        };
-                        (text.to_string(), hex_to_rgba(&color, LABEL_BG_OPACITY))
-                    } else {
-                        (String::new(), String::new())
-                    };
-                    Some(PrLabel {
-                        name: l["name"].as_str()?.to_string(),
-                        color,
-                        text_color,
-                        background_color,
-                    })
+                    Some(PrLabel::new(l["name"].as_str()?.to_string(), color))
                 })
                 .collect()
         })
@@ -882,22 +991,7 @@ fn parse_issue_node(v: &serde_json::Value) -> Option<GitHubIssue> {
             arr.iter()
                 .filter_map(|l| {
                     let color = l["color"].as_str().unwrap_or("").to_string();
-                    let (text_color, background_color) = if color.len() == 6 {
-                        let text = if is_light_color(&color) {
-                            "#1e1e1e"
-                        } else {
-                            "#e5e5e5"
-                        };
-                        (text.to_string(), hex_to_rgba(&color, LABEL_BG_OPACITY))
-                    } else {
-                        (String::new(), String::new())
-                    };
-                    Some(PrLabel {
-                        name: l["name"].as_str()?.to_string(),
-                        color,
-                        text_color,
-                        background_color,
-                    })
+                    Some(PrLabel::new(l["name"].as_str()?.to_string(), color))
                 })
                 .collect()
         })
@@ -942,17 +1036,19 @@ fn issues_filter_clause(filter_mode: &str, viewer: &str) -> String {
     }
 }
 
-/// The issues sub-selection for embedding inside a repository alias.
-fn issues_repo_section(filter_mode: &str, viewer: &str) -> String {
-    let filter = issues_filter_clause(filter_mode, viewer);
-    let node_fields = r#"number title state url createdAt updatedAt
+/// GraphQL selection for an issue node, shared by every issue query.
+const ISSUE_NODE_FIELDS: &str = r#"number title state url createdAt updatedAt
         author { login }
         labels(first: 10) { nodes { name color } }
         assignees(first: 5) { nodes { login } }
         milestone { title }
         comments { totalCount }"#;
+
+/// The issues sub-selection for embedding inside a repository alias.
+fn issues_repo_section(filter_mode: &str, viewer: &str) -> String {
+    let filter = issues_filter_clause(filter_mode, viewer);
     format!(
-        "    issues(first: 30, states: [OPEN]{filter}, orderBy: {{field: UPDATED_AT, direction: DESC}}) {{\n      nodes {{ {node_fields} }}\n    }}"
+        "    issues(first: 30, states: [OPEN]{filter}, orderBy: {{field: UPDATED_AT, direction: DESC}}) {{\n      nodes {{ {ISSUE_NODE_FIELDS} }}\n    }}"
     )
 }
 
@@ -1137,9 +1233,12 @@ pub(crate) async fn get_all_batch_impl(
     pr_hide_drafts: bool,
     state: &AppState,
 ) -> Result<BatchPollResult, String> {
+    // GitLab repos are not part of the GitHub batch query; fetch their MRs first.
+    let gitlab_prs = crate::gitlab::get_all_mr_statuses_impl(paths, include_merged, state).await;
+
     if state.github_token.read().is_none() {
         return Ok(BatchPollResult {
-            prs: Default::default(),
+            prs: gitlab_prs,
             issues: Default::default(),
         });
     }
@@ -1170,7 +1269,7 @@ pub(crate) async fn get_all_batch_impl(
 
     if repos.is_empty() {
         return Ok(BatchPollResult {
-            prs: Default::default(),
+            prs: gitlab_prs,
             issues: Default::default(),
         });
     }
@@ -1196,7 +1295,7 @@ pub(crate) async fn get_all_batch_impl(
         .map(|(i, (_path, owner, name))| (aliases[i].0.as_str(), (owner.as_str(), name.as_str())))
         .collect();
 
-    let mut pr_results: std::collections::HashMap<String, Vec<BranchPrStatus>> = Default::default();
+    let mut pr_results: std::collections::HashMap<String, Vec<BranchPrStatus>> = gitlab_prs;
     let mut issue_results: std::collections::HashMap<String, Vec<GitHubIssue>> = Default::default();
 
     for (alias, path) in &aliases {
@@ -1229,17 +1328,8 @@ pub(crate) async fn get_all_batch_impl(
                 nodes.iter().filter_map(parse_pr_node).collect();
             stamp_merge_policy(&mut statuses, repo_json);
 
-            if include_merged && statuses.iter().any(|s| s.state == "MERGED") {
-                let branch_tips = local_branch_tips(Path::new(path));
-                statuses.retain(|s| {
-                    if s.state != "MERGED" || s.head_ref_oid.is_empty() {
-                        return true;
-                    }
-                    match branch_tips.get(&s.branch) {
-                        Some(tip) => tip == &s.head_ref_oid,
-                        None => true,
-                    }
-                });
+            if include_merged {
+                retain_current_merged(&mut statuses, Path::new(path));
             }
 
             state
@@ -1412,6 +1502,182 @@ pub(crate) async fn reopen_issue(
     reopen_issue_impl(&repo_path, issue_number, &state).await
 }
 
+/// Map an issue state filter (`open`, `closed`, `all`) to GraphQL `IssueState` values.
+/// `None` means no state restriction.
+pub(crate) fn issue_states_for_filter(filter: &str) -> Result<Option<Vec<&'static str>>, String> {
+    match filter {
+        "open" => Ok(Some(vec!["OPEN"])),
+        "closed" => Ok(Some(vec!["CLOSED"])),
+        "all" => Ok(None),
+        other => Err(format!(
+            "Invalid issue state '{other}' (expected open, closed or all)"
+        )),
+    }
+}
+
+/// Parse a single-repository issues response (`data.repository.issues.nodes`).
+pub(crate) fn parse_graphql_issues(response: &serde_json::Value) -> Vec<GitHubIssue> {
+    match response["data"]["repository"]["issues"]["nodes"].as_array() {
+        Some(nodes) => nodes.iter().filter_map(parse_issue_node).collect(),
+        None => vec![],
+    }
+}
+
+/// List issues for one repository, filtered by state and (optionally) labels.
+pub(crate) async fn list_issues_impl(
+    repo_path: &str,
+    state_filter: &str,
+    labels: &[String],
+    state: &AppState,
+) -> Result<Vec<GitHubIssue>, String> {
+    let states = issue_states_for_filter(state_filter)?;
+    let (owner, repo) = resolve_owner_repo(repo_path)?;
+    let labels: Vec<&str> = labels
+        .iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect();
+    let query = format!(
+        "query RepoIssues($owner: String!, $repo: String!, $states: [IssueState!], $labels: [String!], $first: Int!) {{\n  repository(owner: $owner, name: $repo) {{\n    issues(first: $first, states: $states, labels: $labels, orderBy: {{field: UPDATED_AT, direction: DESC}}) {{\n      nodes {{ {ISSUE_NODE_FIELDS} }}\n    }}\n  }}\n}}"
+    );
+    let variables = serde_json::json!({
+        "owner": owner,
+        "repo": repo,
+        "states": states,
+        "labels": if labels.is_empty() { None } else { Some(labels) },
+        "first": 50,
+    });
+    let response = graphql_with_retry(state, &query, variables).await?;
+    Ok(parse_graphql_issues(&response))
+}
+
+/// List issues for a repository (Tauri command).
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn list_repo_issues(
+    path: String,
+    state_filter: Option<String>,
+    labels: Option<Vec<String>>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<GitHubIssue>, String> {
+    let state = state.inner().clone();
+    list_issues_impl(
+        &path,
+        state_filter.as_deref().unwrap_or("open"),
+        &labels.unwrap_or_default(),
+        &state,
+    )
+    .await
+}
+
+/// A single comment on a GitHub issue.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct IssueComment {
+    pub(crate) author: String,
+    pub(crate) body: String,
+    pub(crate) created_at: String,
+    pub(crate) url: String,
+}
+
+/// A GitHub issue with its body and most recent comments.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct GitHubIssueDetail {
+    #[serde(flatten)]
+    pub(crate) issue: GitHubIssue,
+    pub(crate) body: String,
+    pub(crate) comments: Vec<IssueComment>,
+}
+
+/// Parse a single-issue response (`data.repository.issue`).
+/// Returns `None` when the issue does not exist.
+pub(crate) fn parse_issue_detail(response: &serde_json::Value) -> Option<GitHubIssueDetail> {
+    let node = &response["data"]["repository"]["issue"];
+    let issue = parse_issue_node(node)?;
+    let body = node["body"].as_str().unwrap_or("").to_string();
+    let comments = node["commentList"]["nodes"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .map(|c| IssueComment {
+                    author: c["author"]["login"].as_str().unwrap_or("").to_string(),
+                    body: c["body"].as_str().unwrap_or("").to_string(),
+                    created_at: c["createdAt"].as_str().unwrap_or("").to_string(),
+                    url: c["url"].as_str().unwrap_or("").to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(GitHubIssueDetail {
+        issue,
+        body,
+        comments,
+    })
+}
+
+/// Fetch a single issue with its body and the last 50 comments.
+pub(crate) async fn get_issue_impl(
+    repo_path: &str,
+    issue_number: i64,
+    state: &AppState,
+) -> Result<GitHubIssueDetail, String> {
+    if issue_number <= 0 {
+        return Err("Invalid issue number".to_string());
+    }
+    let (owner, repo) = resolve_owner_repo(repo_path)?;
+    let query = format!(
+        "query RepoIssue($owner: String!, $repo: String!, $number: Int!) {{\n  repository(owner: $owner, name: $repo) {{\n    issue(number: $number) {{\n      {ISSUE_NODE_FIELDS}\n      body\n      commentList: comments(last: 50) {{ nodes {{ author {{ login }} body createdAt url }} }}\n    }}\n  }}\n}}"
+    );
+    let variables = serde_json::json!({ "owner": owner, "repo": repo, "number": issue_number });
+    let response = graphql_with_retry(state, &query, variables).await?;
+    parse_issue_detail(&response).ok_or_else(|| format!("Issue #{issue_number} not found"))
+}
+
+/// Fetch a single issue with body and comments (Tauri command).
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn get_issue(
+    repo_path: String,
+    issue_number: i64,
+    state: State<'_, Arc<AppState>>,
+) -> Result<GitHubIssueDetail, String> {
+    let state = state.inner().clone();
+    get_issue_impl(&repo_path, issue_number, &state).await
+}
+
+/// Leave a comment on an issue. Returns the `html_url` of the created comment.
+pub(crate) async fn add_issue_comment_impl(
+    repo_path: &str,
+    issue_number: i64,
+    body: &str,
+    state: &AppState,
+) -> Result<String, String> {
+    if issue_number <= 0 {
+        return Err("Invalid issue number".to_string());
+    }
+    validate_comment_body(body)?;
+    let (owner, repo) = resolve_owner_repo(repo_path)?;
+    let url = format!("https://api.github.com/repos/{owner}/{repo}/issues/{issue_number}/comments");
+    let payload = serde_json::json!({ "body": body });
+    let json = rest_post_with_breaker(state, &url, &payload, "add_issue_comment_impl").await?;
+    json["html_url"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "GitHub response missing comment URL".to_string())
+}
+
+/// Leave a comment on an issue (Tauri command).
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn add_issue_comment(
+    repo_path: String,
+    issue_number: i64,
+    body: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<String, String> {
+    let state = state.inner().clone();
+    add_issue_comment_impl(&repo_path, issue_number, &body, &state).await
+}
+
 // ── End GitHub Issues ────────────────────────────────────────────────────────
 
 /// Parse a GraphQL batch PR response into BranchPrStatus entries.
@@ -1482,6 +1748,20 @@ pub(crate) async fn get_repo_pr_statuses_impl(
 ) -> Result<Vec<BranchPrStatus>, String> {
     let repo_path = PathBuf::from(path);
 
+    // GitLab origins go to the merge request backend, mapped into the same shape.
+    if let Some(url) = crate::git::read_remote_url(&repo_path)
+        && let Some(RemoteHost::GitLab(group, project)) = parse_remote_host(&url)
+    {
+        return crate::gitlab::get_repo_mr_statuses_impl(
+            path,
+            &group,
+            &project,
+            include_merged,
+            state,
+        )
+        .await;
+    }
+
     if state.github_token.read().is_none() {
         return Ok(vec![]); // No token = no GitHub API access
     }
@@ -1512,17 +1792,8 @@ pub(crate) async fn get_repo_pr_statuses_impl(
             stamp_merge_policy(&mut nodes, repo_json);
 
             // Filter stale merged PRs (same logic as batch endpoint)
-            if include_merged && nodes.iter().any(|s| s.state == "MERGED") {
-                let branch_tips = local_branch_tips(&repo_path);
-                nodes.retain(|s| {
-                    if s.state != "MERGED" || s.head_ref_oid.is_empty() {
-                        return true;
-                    }
-                    match branch_tips.get(&s.branch) {
-                        Some(tip) => tip == &s.head_ref_oid,
-                        None => true,
-                    }
-                });
+            if include_merged {
+                retain_current_merged(&mut nodes, &repo_path);
             }
 
             Ok(nodes)
@@ -1560,6 +1831,24 @@ pub(crate) async fn get_repo_pr_statuses(
     Ok(statuses)
 }
 
+/// Drop MERGED entries whose head no longer matches the local branch tip —
+/// the branch name was reused after the merge, so the old PR is stale.
+pub(crate) fn retain_current_merged(nodes: &mut Vec<BranchPrStatus>, repo_path: &Path) {
+    if !nodes.iter().any(|s| s.state == "MERGED") {
+        return;
+    }
+    let branch_tips = local_branch_tips(repo_path);
+    nodes.retain(|s| {
+        if s.state != "MERGED" || s.head_ref_oid.is_empty() {
+            return true;
+        }
+        match branch_tips.get(&s.branch) {
+            Some(tip) => tip == &s.head_ref_oid,
+            None => true,
+        }
+    });
+}
+
 /// Read local branch tips (name → commit SHA) via `git for-each-ref`.
 /// Returns an empty map on any error (no git, not a repo, etc.).
 fn local_branch_tips(repo_path: &Path) -> std::collections::HashMap<String, String> {
@@ -1657,10 +1946,84 @@ pub(crate) async fn get_all_pr_statuses(
     get_all_pr_statuses_impl(&paths, include_merged, &state).await
 }
 
+// ---------------------------------------------------------------------------
+// Ahead/behind cache — skips `git rev-list` while neither ref has moved.
+// ---------------------------------------------------------------------------
+
+/// Last ahead/behind result for a repo and the ref SHAs it was computed from.
+/// Stored in `GitCacheState::ahead_behind`.
+pub(crate) struct AheadBehindEntry {
+    local_sha: String,
+    remote_sha: Option<String>,
+    counts: (i32, i32),
+}
+
+/// `(ahead, behind)` of HEAD vs `origin/<branch>` via `git rev-list`.
+fn rev_list_ahead_behind(repo_path: &Path, branch: &str) -> Option<(i32, i32)> {
+    let rev_range = format!("origin/{branch}...HEAD");
+    crate::git_cli::git_cmd(repo_path)
+        .args(["rev-list", "--left-right", "--count", &rev_range])
+        .run_silent()
+        .and_then(|o| {
+            let parts: Vec<&str> = o.stdout.split_whitespace().collect();
+            if parts.len() == 2 {
+                let behind = parts[0].parse::<i32>().unwrap_or(0);
+                let ahead = parts[1].parse::<i32>().unwrap_or(0);
+                Some((ahead, behind))
+            } else {
+                None
+            }
+        })
+}
+
+/// Ahead/behind keyed on `(repo_path, local_head_sha, remote_head_sha)`, both
+/// read from the ref files. `run` is only invoked when either SHA changed
+/// since the last call; unreadable local refs (detached HEAD, ref update in
+/// flight) always run it. Failed runs (no `origin/<branch>` yet, transient
+/// git error) report `(0, 0)` but are not cached, so the next poll retries.
+fn cached_ahead_behind(
+    cache: &dashmap::DashMap<String, AheadBehindEntry>,
+    repo_path: &Path,
+    branch: &str,
+    run: impl FnOnce(&Path, &str) -> Option<(i32, i32)>,
+) -> (i32, i32) {
+    let key = repo_path.to_string_lossy().to_string();
+    let local_sha = if branch.is_empty() {
+        None
+    } else {
+        crate::git::read_ref_sha(repo_path, &format!("refs/heads/{branch}"))
+    };
+    let Some(local_sha) = local_sha else {
+        return run(repo_path, branch).unwrap_or((0, 0));
+    };
+    let remote_sha = crate::git::read_ref_sha(repo_path, &format!("refs/remotes/origin/{branch}"));
+
+    if let Some(entry) = cache.get(&key)
+        && entry.local_sha == local_sha
+        && entry.remote_sha == remote_sha
+    {
+        return entry.counts;
+    }
+
+    let Some(counts) = run(repo_path, branch) else {
+        cache.remove(&key);
+        return (0, 0);
+    };
+    cache.insert(
+        key,
+        AheadBehindEntry {
+            local_sha,
+            remote_sha,
+            counts,
+        },
+    );
+    counts
+}
+
 /// Get git remote + branch status for a repository (implementation).
 /// PR and CI data now comes from the batch githubStore (GraphQL),
 /// so this only returns has_remote, current_branch, ahead, and behind.
-pub(crate) fn get_github_status_impl(path: &str) -> GitHubStatus {
+pub(crate) fn get_github_status_impl(state: &AppState, path: &str) -> GitHubStatus {
     let repo_path = PathBuf::from(path);
 
     let has_remote = get_github_remote_url(&repo_path).is_some();
@@ -1677,22 +2040,12 @@ pub(crate) fn get_github_status_impl(path: &str) -> GitHubStatus {
         };
     }
 
-    // Get ahead/behind counts
-    let rev_range = format!("origin/{current_branch}...HEAD");
-    let (ahead, behind) = crate::git_cli::git_cmd(&repo_path)
-        .args(["rev-list", "--left-right", "--count", &rev_range])
-        .run_silent()
-        .and_then(|o| {
-            let parts: Vec<&str> = o.stdout.split_whitespace().collect();
-            if parts.len() == 2 {
-                let behind = parts[0].parse::<i32>().unwrap_or(0);
-                let ahead = parts[1].parse::<i32>().unwrap_or(0);
-                Some((ahead, behind))
-            } else {
-                None
-            }
-        })
-        .unwrap_or((0, 0));
+    let (ahead, behind) = cached_ahead_behind(
+        &state.git_cache.ahead_behind,
+        &repo_path,
+        &current_branch,
+        rev_list_ahead_behind,
+    );
 
     GitHubStatus {
         has_remote,
@@ -1705,10 +2058,9 @@ pub(crate) fn get_github_status_impl(path: &str) -> GitHubStatus {
 /// Cached github status for synchronous callers (MCP handlers, etc.).
 pub(crate) fn get_github_status_cached(state: &AppState, path: &str) -> GitHubStatus {
     let p = path.to_string();
-    (*state
-        .git_cache
-        .git_status
-        .get_with(path.to_string(), || Arc::new(get_github_status_impl(&p))))
+    (*state.git_cache.git_status.get_with(path.to_string(), || {
+        Arc::new(get_github_status_impl(state, &p))
+    }))
     .clone()
 }
 
@@ -1725,7 +2077,7 @@ pub(crate) async fn get_github_status(
         (*state
             .git_cache
             .git_status
-            .get_with(path, || Arc::new(get_github_status_impl(&p))))
+            .get_with(path, || Arc::new(get_github_status_impl(&state, &p))))
         .clone()
     })
     .await
@@ -1769,7 +2121,13 @@ query PRChecks($owner: String!, $repo: String!, $number: Int!) {
 
 /// Parse GraphQL PR check contexts into frontend-compatible CiCheckDetail objects.
 fn parse_pr_check_contexts(data: &serde_json::Value) -> Vec<serde_json::Value> {
-    let nodes = &data["data"]["repository"]["pullRequest"]["commits"]["nodes"];
+    parse_pull_request_checks(&data["data"]["repository"]["pullRequest"])
+}
+
+/// Same as [`parse_pr_check_contexts`], starting from a `pullRequest` node
+/// (used for aliased multi-PR responses).
+fn parse_pull_request_checks(pull_request: &serde_json::Value) -> Vec<serde_json::Value> {
+    let nodes = &pull_request["commits"]["nodes"];
     let contexts = match nodes.as_array().and_then(|a| a.first()) {
         Some(node) => &node["commit"]["statusCheckRollup"]["contexts"],
         None => return vec![],
@@ -1811,50 +2169,404 @@ fn parse_pr_check_contexts(data: &serde_json::Value) -> Vec<serde_json::Value> {
         .collect()
 }
 
-/// Core logic for fetching CI check details via GitHub GraphQL API (no caching).
-pub(crate) async fn get_ci_checks_impl(
+/// Core logic for fetching CI check details via GitHub GraphQL API (no caching).
+pub(crate) async fn get_ci_checks_impl(
+    path: &str,
+    pr_number: i64,
+    state: &AppState,
+) -> Vec<serde_json::Value> {
+    let repo_path = PathBuf::from(path);
+
+    if state.github_token.read().is_none() {
+        return vec![];
+    }
+
+    let remote_url = match get_github_remote_url(&repo_path) {
+        Some(url) => url,
+        None => return vec![],
+    };
+
+    let (owner, repo) = match parse_remote_url(&remote_url) {
+        Some(pair) => pair,
+        None => return vec![],
+    };
+
+    let variables = serde_json::json!({
+        "owner": owner,
+        "repo": repo,
+        "number": pr_number,
+    });
+
+    match graphql_with_retry(state, PR_CHECKS_QUERY, variables).await {
+        Ok(data) => parse_pr_check_contexts(&data),
+        Err(e) => {
+            tracing::warn!(source = "github", "GraphQL PR checks query failed: {e}");
+            vec![]
+        }
+    }
+}
+
+/// Max PRs per aliased check-details query; larger requests are chunked so a
+/// single query stays well under GitHub's node limit (100 contexts per PR).
+const MAX_PRS_PER_CHECKS_QUERY: usize = 20;
+
+/// Build one aliased GraphQL query fetching check contexts for several PRs of
+/// the same repo. Each PR gets an alias `p{i}` under the single `repository`
+/// field. Returns (query_string, Vec<(alias, pr_number)>) for result extraction.
+fn build_multi_pr_checks_query(
+    owner: &str,
+    repo: &str,
+    pr_numbers: &[i64],
+) -> (String, Vec<(String, i64)>) {
+    let check_fields = r#"commits(last: 1) {
+        nodes {
+          commit {
+            statusCheckRollup {
+              contexts(first: 100) {
+                nodes {
+                  __typename
+                  ... on CheckRun { name status conclusion detailsUrl startedAt }
+                  ... on StatusContext { context state targetUrl createdAt }
+                }
+              }
+            }
+          }
+        }
+      }"#;
+
+    let mut aliases: Vec<(String, i64)> = Vec::new();
+    let mut parts = vec![
+        "query BatchPRChecks {".to_string(),
+        format!("  repository(owner: \"{owner}\", name: \"{repo}\") {{"),
+    ];
+    for (i, number) in pr_numbers.iter().enumerate() {
+        let alias = format!("p{i}");
+        parts.push(format!(
+            "    {alias}: pullRequest(number: {number}) {{\n      {check_fields}\n    }}"
+        ));
+        aliases.push((alias, *number));
+    }
+    parts.push("  }".to_string());
+    parts.push("  rateLimit { cost remaining resetAt }".to_string());
+    parts.push("}".to_string());
+
+    (parts.join("\n"), aliases)
+}
+
+/// Extract per-PR check details from a [`build_multi_pr_checks_query`] response.
+/// Aliases resolving to `null` (unknown PR numbers) are skipped.
+fn parse_multi_pr_checks_response(
+    response: &serde_json::Value,
+    aliases: &[(String, i64)],
+) -> std::collections::HashMap<i64, Vec<serde_json::Value>> {
+    let repo_json = &response["data"]["repository"];
+    aliases
+        .iter()
+        .filter(|(alias, _)| repo_json[alias].is_object())
+        .map(|(alias, number)| (*number, parse_pull_request_checks(&repo_json[alias])))
+        .collect()
+}
+
+/// Convert a parsed CiCheckDetail object into the compact `CheckDetail` stored on
+/// `BranchPrStatus`: completed checks report their conclusion, others their status.
+fn check_detail_from_json(check: &serde_json::Value) -> CheckDetail {
+    let conclusion = check["conclusion"].as_str().unwrap_or("");
+    let state = if conclusion.is_empty() {
+        check["status"].as_str().unwrap_or("")
+    } else {
+        conclusion
+    };
+    CheckDetail {
+        context: check["name"].as_str().unwrap_or("").to_string(),
+        state: state.to_string(),
+    }
+}
+
+/// Write hydrated check details into the cached PR statuses for `path`.
+/// PRs not in the cache are left alone; the cache entry is replaced (not
+/// mutated) so concurrent readers keep a consistent snapshot.
+fn merge_check_details_into_cache(
+    state: &AppState,
+    path: &str,
+    details: &std::collections::HashMap<i64, Vec<serde_json::Value>>,
+) {
+    let Some(cached) = state.git_cache.github_status.get(path) else {
+        return;
+    };
+    let mut statuses = (*cached).clone();
+    let mut changed = false;
+    for status in &mut statuses {
+        if let Some(checks) = details.get(&i64::from(status.number)) {
+            status.check_details = checks.iter().map(check_detail_from_json).collect();
+            changed = true;
+        }
+    }
+    if changed {
+        state
+            .git_cache
+            .github_status
+            .insert(path.to_string(), Arc::new(statuses));
+    }
+}
+
+/// Fetch check details for several PRs of one repo in batched GraphQL calls and
+/// merge them into the cached `BranchPrStatus` entries. Returns the details per
+/// PR number in the same shape as [`get_ci_checks_impl`]. PRs whose lookup
+/// fails (or that don't exist) are omitted.
+pub(crate) async fn hydrate_pr_check_details_impl(
+    path: &str,
+    pr_numbers: &[i64],
+    state: &AppState,
+) -> Result<std::collections::HashMap<i64, Vec<serde_json::Value>>, String> {
+    let mut details = std::collections::HashMap::new();
+    if state.github_token.read().is_none() {
+        return Ok(details);
+    }
+    let Some(remote_url) = get_github_remote_url(Path::new(path)) else {
+        return Ok(details);
+    };
+    let Some((owner, repo)) = parse_remote_url(&remote_url) else {
+        return Ok(details);
+    };
+
+    let mut numbers = pr_numbers.to_vec();
+    numbers.sort_unstable();
+    numbers.dedup();
+
+    for chunk in numbers.chunks(MAX_PRS_PER_CHECKS_QUERY) {
+        let (query, aliases) = build_multi_pr_checks_query(&owner, &repo, chunk);
+        match graphql_with_retry(state, &query, serde_json::Value::Null).await {
+            Ok(response) => details.extend(parse_multi_pr_checks_response(&response, &aliases)),
+            Err(e) if e.starts_with("rate-limit:") => return Err(e),
+            Err(e) => {
+                tracing::warn!(source = "github", %path, "GraphQL batch PR checks query failed: {e}");
+            }
+        }
+    }
+
+    merge_check_details_into_cache(state, path, &details);
+    Ok(details)
+}
+
+const PR_HEAD_QUERY: &str = r#"
+query PRHead($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      headRefName
+      isCrossRepository
+      headRepositoryOwner { login }
+      headRepository { name }
+    }
+  }
+}
+"#;
+
+/// Where a PR's head commit lives, as needed to check it out locally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PrHead {
+    pub(crate) number: i64,
+    pub(crate) head_ref: String,
+    /// True when the head branch lives in a fork.
+    pub(crate) is_cross_repository: bool,
+    /// Owner/name of the head repository. `None` when the fork was deleted.
+    pub(crate) head_owner: Option<String>,
+    pub(crate) head_repo: Option<String>,
+}
+
+fn parse_pr_head(data: &serde_json::Value, number: i64) -> Option<PrHead> {
+    let pr = &data["data"]["repository"]["pullRequest"];
+    Some(PrHead {
+        number,
+        head_ref: pr["headRefName"].as_str()?.to_string(),
+        is_cross_repository: pr["isCrossRepository"].as_bool().unwrap_or(false),
+        head_owner: pr["headRepositoryOwner"]["login"]
+            .as_str()
+            .map(str::to_string),
+        head_repo: pr["headRepository"]["name"].as_str().map(str::to_string),
+    })
+}
+
+/// Resolve a PR's head ref and repository via GraphQL. `remote` names the
+/// git remote of the repository the PR was opened against.
+pub(crate) async fn fetch_pr_head_impl(
     path: &str,
+    remote: &str,
     pr_number: i64,
     state: &AppState,
-) -> Vec<serde_json::Value> {
-    let repo_path = PathBuf::from(path);
+) -> Result<PrHead, String> {
+    let remote_url = crate::git::read_named_remote_url(Path::new(path), remote)
+        .filter(|url| url.contains("github.com"))
+        .ok_or_else(|| format!("Remote '{remote}' is not a GitHub remote"))?;
+    let (owner, repo) = parse_remote_url(&remote_url)
+        .ok_or_else(|| format!("Cannot parse GitHub remote URL: {remote_url}"))?;
+    let variables = serde_json::json!({
+        "owner": owner,
+        "repo": repo,
+        "number": pr_number,
+    });
+    let data = graphql_with_retry(state, PR_HEAD_QUERY, variables).await?;
+    parse_pr_head(&data, pr_number).ok_or_else(|| format!("PR #{pr_number} not found"))
+}
 
-    if state.github_token.read().is_none() {
-        return vec![];
+const PR_TIMELINE_QUERY: &str = r#"
+query PRTimeline($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      timelineItems(last: 100, itemTypes: [PULL_REQUEST_REVIEW, REVIEW_REQUESTED_EVENT, READY_FOR_REVIEW_EVENT, CONVERT_TO_DRAFT_EVENT, LABELED_EVENT, UNLABELED_EVENT, HEAD_REF_FORCE_PUSHED_EVENT, MERGED_EVENT, CLOSED_EVENT, REOPENED_EVENT]) {
+        nodes {
+          __typename
+          ... on PullRequestReview { author { login } createdAt state }
+          ... on ReviewRequestedEvent { actor { login } createdAt requestedReviewer { ... on User { login } ... on Team { name } } }
+          ... on ReadyForReviewEvent { actor { login } createdAt }
+          ... on ConvertToDraftEvent { actor { login } createdAt }
+          ... on LabeledEvent { actor { login } createdAt label { name } }
+          ... on UnlabeledEvent { actor { login } createdAt label { name } }
+          ... on HeadRefForcePushedEvent { actor { login } createdAt beforeCommit { abbreviatedOid } afterCommit { abbreviatedOid } }
+          ... on MergedEvent { actor { login } createdAt mergeRefName commit { abbreviatedOid } }
+          ... on ClosedEvent { actor { login } createdAt }
+          ... on ReopenedEvent { actor { login } createdAt }
+        }
+      }
     }
+  }
+}
+"#;
 
-    let remote_url = match get_github_remote_url(&repo_path) {
-        Some(url) => url,
-        None => return vec![],
-    };
+/// One entry in a PR's status history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct PrTimelineEvent {
+    /// `review`, `review_requested`, `ready_for_review`, `converted_to_draft`,
+    /// `labeled`, `unlabeled`, `head_ref_force_pushed`, `merged`, `closed`, `reopened`.
+    pub(crate) event_type: String,
+    pub(crate) actor: Option<String>,
+    pub(crate) created_at: String,
+    /// Event-specific summary (review state, label, commit range, …).
+    pub(crate) detail: Option<String>,
+}
 
-    let (owner, repo) = match parse_remote_url(&remote_url) {
-        Some(pair) => pair,
-        None => return vec![],
+fn parse_timeline_node(node: &serde_json::Value) -> Option<PrTimelineEvent> {
+    let login = |v: &serde_json::Value| v["login"].as_str().map(str::to_string);
+    let oid = |v: &serde_json::Value| v["abbreviatedOid"].as_str().unwrap_or("?").to_string();
+    let (event_type, actor, detail) = match node["__typename"].as_str()? {
+        "PullRequestReview" => (
+            "review",
+            login(&node["author"]),
+            node["state"].as_str().map(str::to_string),
+        ),
+        "ReviewRequestedEvent" => {
+            let reviewer = &node["requestedReviewer"];
+            let who = reviewer["login"].as_str().or(reviewer["name"].as_str());
+            (
+                "review_requested",
+                login(&node["actor"]),
+                who.map(str::to_string),
+            )
+        }
+        "ReadyForReviewEvent" => ("ready_for_review", login(&node["actor"]), None),
+        "ConvertToDraftEvent" => ("converted_to_draft", login(&node["actor"]), None),
+        "LabeledEvent" | "UnlabeledEvent" => (
+            if node["__typename"] == "LabeledEvent" {
+                "labeled"
+            } else {
+                "unlabeled"
+            },
+            login(&node["actor"]),
+            node["label"]["name"].as_str().map(str::to_string),
+        ),
+        "HeadRefForcePushedEvent" => (
+            "head_ref_force_pushed",
+            login(&node["actor"]),
+            Some(format!(
+                "{} → {}",
+                oid(&node["beforeCommit"]),
+                oid(&node["afterCommit"])
+            )),
+        ),
+        "MergedEvent" => (
+            "merged",
+            login(&node["actor"]),
+            Some(format!(
+                "{} into {}",
+                oid(&node["commit"]),
+                node["mergeRefName"].as_str().unwrap_or("?")
+            )),
+        ),
+        "ClosedEvent" => ("closed", login(&node["actor"]), None),
+        "ReopenedEvent" => ("reopened", login(&node["actor"]), None),
+        _ => return None,
     };
+    Some(PrTimelineEvent {
+        event_type: event_type.to_string(),
+        actor,
+        created_at: node["createdAt"].as_str()?.to_string(),
+        detail,
+    })
+}
+
+/// Extract timeline events oldest-first. `None` when the PR wasn't found.
+fn parse_pr_timeline(data: &serde_json::Value) -> Option<Vec<PrTimelineEvent>> {
+    let pr = &data["data"]["repository"]["pullRequest"];
+    if pr.is_null() {
+        return None;
+    }
+    let mut events: Vec<PrTimelineEvent> = pr["timelineItems"]["nodes"]
+        .as_array()
+        .map(|nodes| nodes.iter().filter_map(parse_timeline_node).collect())
+        .unwrap_or_default();
+    // RFC 3339 timestamps in UTC sort lexicographically; stable sort keeps
+    // GitHub's order for same-second events.
+    events.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Some(events)
+}
 
+/// Fetch a PR's review/status history (last 100 high-value timeline items).
+pub(crate) async fn get_pr_timeline_impl(
+    path: &str,
+    pr_number: i64,
+    state: &AppState,
+) -> Result<Vec<PrTimelineEvent>, String> {
+    let (owner, repo) = resolve_owner_repo(path)?;
     let variables = serde_json::json!({
         "owner": owner,
         "repo": repo,
         "number": pr_number,
     });
+    let data = graphql_with_retry(state, PR_TIMELINE_QUERY, variables).await?;
+    parse_pr_timeline(&data).ok_or_else(|| format!("PR #{pr_number} not found"))
+}
 
-    match graphql_with_retry(state, PR_CHECKS_QUERY, variables).await {
-        Ok(data) => parse_pr_check_contexts(&data),
-        Err(e) => {
-            tracing::warn!(source = "github", "GraphQL PR checks query failed: {e}");
-            vec![]
+/// Build the request body for `PUT /pulls/{n}/merge`.
+/// `commit_title`/`commit_message` are only sent when provided, so GitHub keeps
+/// its default message otherwise. A provided title must not be blank.
+fn build_merge_pr_body(
+    merge_method: &str,
+    commit_title: Option<&str>,
+    commit_message: Option<&str>,
+) -> Result<serde_json::Value, String> {
+    let mut body = serde_json::json!({ "merge_method": merge_method });
+    if let Some(title) = commit_title {
+        if title.trim().is_empty() {
+            return Err("Commit title must not be empty".to_string());
         }
+        body["commit_title"] = serde_json::json!(title);
+    }
+    if let Some(message) = commit_message {
+        body["commit_message"] = serde_json::json!(message);
     }
+    Ok(body)
 }
 
-/// Merge a PR via GitHub REST API using the specified merge method.
+/// Merge a PR via GitHub REST API using the specified merge method, optionally
+/// with a custom commit title/message (useful for squash merges).
 pub(crate) async fn merge_pr_github_impl(
     repo_path: &str,
     pr_number: i64,
     merge_method: &str,
+    commit_title: Option<&str>,
+    commit_message: Option<&str>,
     state: &AppState,
 ) -> Result<String, String> {
+    let body = build_merge_pr_body(merge_method, commit_title, commit_message)?;
     let token = state
         .github_token
         .read()
@@ -1869,7 +2581,6 @@ pub(crate) async fn merge_pr_github_impl(
 
     let url = format!("https://api.github.com/repos/{owner}/{repo}/pulls/{pr_number}/merge");
     crate::github_debug::log_api("PUT", &url, "merge_pr_github_impl");
-    let body = serde_json::json!({ "merge_method": merge_method });
 
     let response = state
         .http_client
@@ -1907,10 +2618,20 @@ pub(crate) async fn merge_pr_via_github(
     repo_path: String,
     pr_number: i64,
     merge_method: String,
+    commit_title: Option<String>,
+    commit_message: Option<String>,
     state: State<'_, Arc<AppState>>,
 ) -> Result<String, String> {
     let state = state.inner().clone();
-    merge_pr_github_impl(&repo_path, pr_number, &merge_method, &state).await
+    merge_pr_github_impl(
+        &repo_path,
+        pr_number,
+        &merge_method,
+        commit_title.as_deref(),
+        commit_message.as_deref(),
+        &state,
+    )
+    .await
 }
 
 /// Get CI check details for a PR via GitHub GraphQL API (Story 060).
@@ -1925,6 +2646,30 @@ pub(crate) async fn get_ci_checks(
     Ok(get_ci_checks_impl(&path, pr_number, &state).await)
 }
 
+/// Get a PR's status history (reviews, labels, force-pushes, merge) via GraphQL.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn get_pr_timeline(
+    path: String,
+    pr_number: i64,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<PrTimelineEvent>, String> {
+    let state = state.inner().clone();
+    get_pr_timeline_impl(&path, pr_number, &state).await
+}
+
+/// Hydrate CI check details for several PRs in one batched GraphQL call.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn hydrate_pr_check_details(
+    path: String,
+    pr_numbers: Vec<i64>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<std::collections::HashMap<i64, Vec<serde_json::Value>>, String> {
+    let state = state.inner().clone();
+    hydrate_pr_check_details_impl(&path, &pr_numbers, &state).await
+}
+
 /// Approve a PR via GitHub REST API.
 /// Creates a review with event=APPROVE.
 pub(crate) async fn approve_pr_impl(
@@ -2017,6 +2762,118 @@ pub(crate) async fn approve_pr(
     approve_pr_impl(&repo_path, pr_number, &state).await
 }
 
+/// Resolve the GitHub `(owner, repo)` for a local repository from its remote.
+fn resolve_owner_repo(repo_path: &str) -> Result<(String, String), String> {
+    let remote_url = get_github_remote_url(std::path::Path::new(repo_path))
+        .ok_or_else(|| "No GitHub remote URL found for this repository".to_string())?;
+    parse_remote_url(&remote_url)
+        .ok_or_else(|| format!("Failed to parse GitHub remote URL: {remote_url}"))
+}
+
+/// Reject non-positive PR numbers before any network call.
+pub(crate) fn validate_pr_number(pr_number: i64) -> Result<(), String> {
+    if pr_number <= 0 {
+        return Err("Invalid PR number".to_string());
+    }
+    Ok(())
+}
+
+/// Build the request body for `POST /pulls/{n}/requested_reviewers`.
+/// Blank logins/slugs are dropped; at least one reviewer or team is required.
+pub(crate) fn build_request_reviewers_body(
+    reviewers: &[String],
+    team_reviewers: &[String],
+) -> Result<serde_json::Value, String> {
+    let clean = |v: &[String]| -> Vec<String> {
+        v.iter()
+            .map(|s| s.trim().trim_start_matches('@').to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    };
+    let reviewers = clean(reviewers);
+    let team_reviewers = clean(team_reviewers);
+    if reviewers.is_empty() && team_reviewers.is_empty() {
+        return Err("At least one reviewer or team reviewer is required".to_string());
+    }
+    Ok(serde_json::json!({
+        "reviewers": reviewers,
+        "team_reviewers": team_reviewers,
+    }))
+}
+
+/// Validate a PR comment body — it must contain non-whitespace text.
+pub(crate) fn validate_comment_body(body: &str) -> Result<(), String> {
+    if body.trim().is_empty() {
+        return Err("Comment body must not be empty".to_string());
+    }
+    Ok(())
+}
+
+/// Request user and/or team reviewers on a PR via GitHub REST API.
+pub(crate) async fn request_reviewers_impl(
+    repo_path: &str,
+    pr_number: i64,
+    reviewers: &[String],
+    team_reviewers: &[String],
+    state: &AppState,
+) -> Result<(), String> {
+    validate_pr_number(pr_number)?;
+    let body = build_request_reviewers_body(reviewers, team_reviewers)?;
+    let (owner, repo) = resolve_owner_repo(repo_path)?;
+    let url = format!(
+        "https://api.github.com/repos/{owner}/{repo}/pulls/{pr_number}/requested_reviewers"
+    );
+    rest_post_with_breaker(state, &url, &body, "request_reviewers_impl").await?;
+    Ok(())
+}
+
+/// Request reviewers on a PR (Tauri command).
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn request_reviewers(
+    repo_path: String,
+    pr_number: i64,
+    reviewers: Vec<String>,
+    team_reviewers: Vec<String>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<(), String> {
+    let state = state.inner().clone();
+    request_reviewers_impl(&repo_path, pr_number, &reviewers, &team_reviewers, &state).await
+}
+
+/// Leave a conversation comment on a PR (REST issue-comment endpoint).
+/// Returns the `html_url` of the created comment.
+pub(crate) async fn add_pr_comment_impl(
+    repo_path: &str,
+    pr_number: i64,
+    body: &str,
+    state: &AppState,
+) -> Result<String, String> {
+    validate_pr_number(pr_number)?;
+    validate_comment_body(body)?;
+    let (owner, repo) = resolve_owner_repo(repo_path)?;
+    let url = format!("https://api.github.com/repos/{owner}/{repo}/issues/{pr_number}/comments");
+    let payload = serde_json::json!({ "body": body });
+    let json = rest_post_with_breaker(state, &url, &payload, "add_pr_comment_impl").await?;
+    json["html_url"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "GitHub response missing comment URL".to_string())
+}
+
+/// Leave a comment on a PR (Tauri command).
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn add_pr_comment(
+    repo_path: String,
+    pr_number: i64,
+    body: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<String, String> {
+    let state = state.inner().clone();
+    add_pr_comment_impl(&repo_path, pr_number, &body, &state).await
+}
+
 /// Fetch the unified diff for a PR via GitHub REST API.
 /// Uses Accept: application/vnd.github.diff to get raw diff text.
 pub(crate) async fn get_pr_diff_impl(
@@ -2747,6 +3604,144 @@ mod tests {
         assert!(!result[2].viewer_did_approve);
     }
 
+    #[test]
+    fn test_build_request_reviewers_body() {
+        let body = build_request_reviewers_body(
+            &["@alice".to_string(), " ".to_string(), "bob".to_string()],
+            &["core-team".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "reviewers": ["alice", "bob"],
+                "team_reviewers": ["core-team"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_request_reviewers_body_requires_someone() {
+        let err = build_request_reviewers_body(&[" ".to_string()], &[]).unwrap_err();
+        assert!(err.contains("At least one reviewer"));
+    }
+
+    #[test]
+    fn test_ahead_behind_cached_until_a_ref_moves() {
+        use std::sync::atomic::AtomicUsize;
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let git = |args: &[&str]| {
+            let out = Command::new("git")
+                .current_dir(repo)
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "T")
+                .env("GIT_AUTHOR_EMAIL", "t@t")
+                .env("GIT_COMMITTER_NAME", "T")
+                .env("GIT_COMMITTER_EMAIL", "t@t")
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "git {args:?}");
+        };
+        git(&["init", "-b", "main"]);
+        git(&["commit", "--allow-empty", "-m", "one", "--no-verify"]);
+        git(&["update-ref", "refs/remotes/origin/main", "HEAD"]);
+
+        let cache = crate::state::GitCacheState::new();
+        let calls = AtomicUsize::new(0);
+        let runner = |_: &Path, _: &str| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Some((1, 0))
+        };
+        assert_eq!(
+            cached_ahead_behind(&cache.ahead_behind, repo, "main", runner),
+            (1, 0)
+        );
+        assert_eq!(
+            cached_ahead_behind(&cache.ahead_behind, repo, "main", runner),
+            (1, 0)
+        );
+        assert_eq!(
+            calls.load(Ordering::Relaxed),
+            1,
+            "unchanged refs must hit the cache"
+        );
+
+        // A new local commit moves HEAD → recompute.
+        git(&["commit", "--allow-empty", "-m", "two", "--no-verify"]);
+        cached_ahead_behind(&cache.ahead_behind, repo, "main", runner);
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        // Packed remote refs are read too; moving origin/main recomputes.
+        git(&["pack-refs", "--all"]);
+        cached_ahead_behind(&cache.ahead_behind, repo, "main", runner);
+        assert_eq!(
+            calls.load(Ordering::Relaxed),
+            2,
+            "packing refs changes no SHA"
+        );
+        git(&["update-ref", "refs/remotes/origin/main", "HEAD"]);
+        cached_ahead_behind(&cache.ahead_behind, repo, "main", runner);
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        cache.invalidate_repo(&repo.to_string_lossy());
+        cached_ahead_behind(&cache.ahead_behind, repo, "main", runner);
+        assert_eq!(calls.load(Ordering::Relaxed), 4);
+
+        // A failed run (e.g. origin/main not fetched yet) is not cached.
+        git(&["commit", "--allow-empty", "-m", "three", "--no-verify"]);
+        let failing = |_: &Path, _: &str| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            None
+        };
+        assert_eq!(
+            cached_ahead_behind(&cache.ahead_behind, repo, "main", failing),
+            (0, 0)
+        );
+        assert!(
+            !cache.ahead_behind.contains_key(&*repo.to_string_lossy()),
+            "a failed run must not leave an entry behind"
+        );
+        assert_eq!(
+            cached_ahead_behind(&cache.ahead_behind, repo, "main", runner),
+            (1, 0)
+        );
+        assert_eq!(
+            calls.load(Ordering::Relaxed),
+            6,
+            "a failed run must be retried on the next call"
+        );
+    }
+
+    #[test]
+    fn test_build_merge_pr_body_includes_commit_fields_only_when_present() {
+        let body = build_merge_pr_body("squash", None, None).unwrap();
+        assert_eq!(body, serde_json::json!({ "merge_method": "squash" }));
+
+        let body =
+            build_merge_pr_body("squash", Some("feat: add X (#12)"), Some("Details")).unwrap();
+        assert_eq!(body["merge_method"], "squash");
+        assert_eq!(body["commit_title"], "feat: add X (#12)");
+        assert_eq!(body["commit_message"], "Details");
+
+        let body = build_merge_pr_body("merge", Some("Title only"), None).unwrap();
+        assert_eq!(body["commit_title"], "Title only");
+        assert!(body.get("commit_message").is_none());
+    }
+
+    #[test]
+    fn test_build_merge_pr_body_rejects_blank_title() {
+        let err = build_merge_pr_body("squash", Some("  "), Some("msg")).unwrap_err();
+        assert!(err.contains("title"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_comment_body_rejects_blank() {
+        assert!(validate_comment_body("").is_err());
+        assert!(validate_comment_body("  \n\t").is_err());
+        assert!(validate_comment_body("LGTM").is_ok());
+    }
+
     #[test]
     fn test_friendly_approve_error_self_approve() {
         let msg = friendly_approve_error(
@@ -3096,6 +4091,29 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_parse_remote_host_gitlab() {
+        assert_eq!(
+            parse_remote_host("git@gitlab.com:group/sub/project.git"),
+            Some(RemoteHost::GitLab(
+                "group/sub".to_string(),
+                "project".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_remote_host("https://gitlab.com/group/project\n"),
+            Some(RemoteHost::GitLab(
+                "group".to_string(),
+                "project".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_remote_host("https://github.com/owner/repo.git"),
+            Some(RemoteHost::GitHub("owner".to_string(), "repo".to_string()))
+        );
+        assert_eq!(parse_remote_host("https://gitlab.com/project"), None);
+    }
+
     #[test]
     fn test_parse_remote_url_empty() {
         assert_eq!(parse_remote_url(""), None);
@@ -3304,6 +4322,92 @@ mod tests {
         assert_eq!(parse_pr_check_contexts(&data).len(), 0);
     }
 
+    // --- hydrate_pr_check_details tests ---
+
+    #[test]
+    fn test_build_multi_pr_checks_query_aliases_each_pr() {
+        let (query, aliases) = build_multi_pr_checks_query("octocat", "hello", &[12, 7, 99]);
+        assert_eq!(
+            aliases,
+            vec![
+                ("p0".to_string(), 12),
+                ("p1".to_string(), 7),
+                ("p2".to_string(), 99)
+            ]
+        );
+        assert_eq!(query.matches("repository(").count(), 1);
+        assert!(query.contains("repository(owner: \"octocat\", name: \"hello\")"));
+        assert!(query.contains("p0: pullRequest(number: 12)"));
+        assert!(query.contains("p1: pullRequest(number: 7)"));
+        assert!(query.contains("p2: pullRequest(number: 99)"));
+        assert_eq!(query.matches("statusCheckRollup").count(), 3);
+        assert!(query.contains("rateLimit"));
+    }
+
+    #[test]
+    fn test_hydrated_checks_merge_into_matching_cache_entries() {
+        let state = crate::state::tests_support::make_test_app_state();
+        let pr = |number, branch| {
+            graphql_pr_node(
+                number,
+                "t",
+                "OPEN",
+                branch,
+                1,
+                1,
+                "alice",
+                1,
+                &[("SUCCESS", 1)],
+                &[],
+                "MERGEABLE",
+                "CLEAN",
+                None,
+                None,
+                false,
+                &[],
+                "main",
+            )
+        };
+        let cached = parse_graphql_prs(&graphql_response(vec![
+            pr(42, "feature/x"),
+            pr(43, "fix/y"),
+        ]));
+        state
+            .git_cache
+            .github_status
+            .insert("/repo".to_string(), Arc::new(cached));
+
+        let (_, aliases) = build_multi_pr_checks_query("o", "r", &[43, 404]);
+        let response = serde_json::json!({
+            "data": {
+                "repository": {
+                    "p0": {
+                        "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "contexts": { "nodes": [
+                            { "__typename": "CheckRun", "name": "build", "status": "COMPLETED", "conclusion": "FAILURE", "detailsUrl": "" },
+                            { "__typename": "StatusContext", "context": "ci/lint", "state": "PENDING", "targetUrl": "" }
+                        ] } } } }] }
+                    },
+                    "p1": null
+                }
+            }
+        });
+        let details = parse_multi_pr_checks_response(&response, &aliases);
+        assert_eq!(details.len(), 1, "unknown PR 404 must be skipped");
+        assert_eq!(details[&43].len(), 2);
+
+        merge_check_details_into_cache(&state, "/repo", &details);
+
+        let merged = state.git_cache.github_status.get("/repo").unwrap();
+        let by_number = |n: i32| merged.iter().find(|s| s.number == n).unwrap();
+        assert!(by_number(42).check_details.is_empty());
+        let hydrated = &by_number(43).check_details;
+        assert_eq!(hydrated.len(), 2);
+        assert_eq!(hydrated[0].context, "build");
+        assert_eq!(hydrated[0].state, "failure");
+        assert_eq!(hydrated[1].context, "ci/lint");
+        assert_eq!(hydrated[1].state, "in_progress");
+    }
+
     // --- Integration tests: GraphQL API vs gh CLI (requires network + token) ---
     // Run with: cargo test --lib -- --ignored --test-threads=1
 
@@ -3973,6 +5077,100 @@ mod tests {
         assert!(parse_issue_node(&json).is_none());
     }
 
+    #[test]
+    fn test_parse_graphql_issues_basic() {
+        let response = serde_json::json!({
+            "data": { "repository": { "issues": { "nodes": [
+                {
+                    "number": 7,
+                    "title": "Crash on start",
+                    "state": "OPEN",
+                    "url": "https://github.com/o/r/issues/7",
+                    "author": { "login": "alice" },
+                    "labels": { "nodes": [{ "name": "bug", "color": "d73a49" }] },
+                    "assignees": { "nodes": [] },
+                    "milestone": null,
+                    "comments": { "totalCount": 2 }
+                },
+                { "title": "malformed, no number" },
+                {
+                    "number": 3,
+                    "title": "Docs typo",
+                    "state": "CLOSED",
+                    "url": "https://github.com/o/r/issues/3",
+                    "author": { "login": "bob" },
+                    "labels": { "nodes": [] },
+                    "assignees": { "nodes": [{ "login": "carol" }] },
+                    "milestone": { "title": "v1" },
+                    "comments": { "totalCount": 0 }
+                }
+            ]}}}
+        });
+        let issues = parse_graphql_issues(&response);
+        assert_eq!(issues.len(), 2, "node without a number is skipped");
+        assert_eq!(issues[0].number, 7);
+        assert_eq!(issues[0].labels[0].name, "bug");
+        assert_eq!(
+            issues[0].labels[0].background_color,
+            "rgba(215, 58, 73, 0.7)"
+        );
+        assert_eq!(issues[1].state, "CLOSED");
+        assert_eq!(issues[1].assignees, vec!["carol"]);
+    }
+
+    #[test]
+    fn test_parse_graphql_issues_no_data() {
+        let response = serde_json::json!({ "errors": [{ "message": "Not found" }] });
+        assert!(parse_graphql_issues(&response).is_empty());
+    }
+
+    #[test]
+    fn test_parse_issue_detail_with_comments() {
+        let response = serde_json::json!({
+            "data": { "repository": { "issue": {
+                "number": 12,
+                "title": "Feature request",
+                "state": "OPEN",
+                "url": "https://github.com/o/r/issues/12",
+                "author": { "login": "alice" },
+                "labels": { "nodes": [] },
+                "assignees": { "nodes": [] },
+                "milestone": null,
+                "comments": { "totalCount": 1 },
+                "body": "Please add X",
+                "commentList": { "nodes": [{
+                    "author": { "login": "bob" },
+                    "body": "+1",
+                    "createdAt": "2026-05-01T00:00:00Z",
+                    "url": "https://github.com/o/r/issues/12#issuecomment-1"
+                }]}
+            }}}
+        });
+        let detail = parse_issue_detail(&response).expect("should parse");
+        assert_eq!(detail.issue.number, 12);
+        assert_eq!(detail.body, "Please add X");
+        assert_eq!(detail.comments.len(), 1);
+        assert_eq!(detail.comments[0].author, "bob");
+        assert_eq!(detail.comments[0].body, "+1");
+    }
+
+    #[test]
+    fn test_parse_issue_detail_missing_issue() {
+        let response = serde_json::json!({ "data": { "repository": { "issue": null } } });
+        assert!(parse_issue_detail(&response).is_none());
+    }
+
+    #[test]
+    fn test_issue_states_for_filter() {
+        assert_eq!(issue_states_for_filter("open").unwrap(), Some(vec!["OPEN"]));
+        assert_eq!(
+            issue_states_for_filter("closed").unwrap(),
+            Some(vec!["CLOSED"])
+        );
+        assert_eq!(issue_states_for_filter("all").unwrap(), None);
+        assert!(issue_states_for_filter("merged").is_err());
+    }
+
     #[test]
     fn test_build_multi_repo_issues_query_assigned() {
         let repos = vec![("path1".to_string(), "owner".to_string(), "repo".to_string())];
@@ -4085,4 +5283,48 @@ mod tests {
             "<inline>"
         );
     }
+
+    #[test]
+    fn parse_pr_timeline_orders_high_value_events() {
+        let data = serde_json::json!({"data": {"repository": {"pullRequest": {"timelineItems": {"nodes": [
+            {"__typename": "ReadyForReviewEvent", "actor": {"login": "alice"}, "createdAt": "2024-05-01T10:00:00Z"},
+            {"__typename": "LabeledEvent", "actor": {"login": "bob"}, "createdAt": "2024-05-01T09:00:00Z", "label": {"name": "needs-tests"}},
+            {"__typename": "PullRequestReview", "author": {"login": "carol"}, "createdAt": "2024-05-02T08:00:00Z", "state": "CHANGES_REQUESTED"},
+            {"__typename": "HeadRefForcePushedEvent", "actor": {"login": "alice"}, "createdAt": "2024-05-02T12:00:00Z",
+             "beforeCommit": {"abbreviatedOid": "abc1234"}, "afterCommit": {"abbreviatedOid": "def5678"}},
+            {"__typename": "SomethingNew", "createdAt": "2024-05-02T13:00:00Z"},
+            {"__typename": "MergedEvent", "actor": null, "createdAt": "2024-05-03T00:00:00Z",
+             "mergeRefName": "main", "commit": {"abbreviatedOid": "9999aaa"}}
+        ]}}}}});
+
+        let events = parse_pr_timeline(&data).unwrap();
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = events
+            .iter()
+            .map(|e| {
+                (
+                    e.event_type.as_str(),
+                    e.actor.as_deref(),
+                    e.detail.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("labeled", Some("bob"), Some("needs-tests")),
+                ("ready_for_review", Some("alice"), None),
+                ("review", Some("carol"), Some("CHANGES_REQUESTED")),
+                (
+                    "head_ref_force_pushed",
+                    Some("alice"),
+                    Some("abc1234 → def5678")
+                ),
+                ("merged", None, Some("9999aaa into main")),
+            ]
+        );
+        assert_eq!(events[0].created_at, "2024-05-01T09:00:00Z");
+
+        let missing = serde_json::json!({"data": {"repository": {"pullRequest": null}}});
+        assert!(parse_pr_timeline(&missing).is_none());
+    }
 }
@@ -211,8 +211,55 @@ const IDLE_POLL_DIVISOR: u32 = 5;
 /// Cold repos (no active terminals) are included every Nth poll cycle (~10min at 60s base).
 const DORMANT_POLL_DIVISOR: u32 = 10;
 
+/// Outcome of checking `PollActivity` before a background poll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PollGate {
+    Run,
+    Skip,
+}
+
+/// Client key of the desktop main window in [`PollActivity`].
+pub(crate) const DESKTOP_POLL_CLIENT: &str = "desktop";
+
+/// Whether background refreshes may run. Each client (the desktop window, or
+/// a remote browser by IP) reports its focus state through `set_poll_active`;
+/// polling pauses only once every client that reported is unfocused, so a
+/// blurred remote tab can't pause the desktop window. A poll that would fire
+/// while inactive is skipped without making a request, so a paused stretch
+/// records nothing against the circuit breaker or the poller's failure
+/// backoff. One catch-up poll runs on refocus.
+#[derive(Default)]
+pub(crate) struct PollActivity {
+    clients: parking_lot::Mutex<HashMap<String, bool>>,
+}
+
+impl PollActivity {
+    /// Record `client`'s focus state. Returns `true` when this call resumed
+    /// polling.
+    pub(crate) fn set_active(&self, client: &str, active: bool) -> bool {
+        let mut clients = self.clients.lock();
+        let was_active = any_client_active(&clients);
+        clients.insert(client.to_string(), active);
+        !was_active && any_client_active(&clients)
+    }
+
+    pub(crate) fn gate(&self) -> PollGate {
+        if any_client_active(&self.clients.lock()) {
+            PollGate::Run
+        } else {
+            PollGate::Skip
+        }
+    }
+}
+
+fn any_client_active(clients: &HashMap<String, bool>) -> bool {
+    clients.is_empty() || clients.values().any(|&active| active)
+}
+
 pub(crate) enum PollerCmd {
     SetVisibility(bool),
+    /// Polling resumed after an inactive stretch: catch up on all paths now.
+    Resume,
     PollRepo(String),
     UpdatePaths(Vec<String>),
     SetIssueFilter(String),
@@ -293,6 +340,11 @@ async fn poll_loop(state: Arc<AppState>, handle: AppHandle, mut rx: mpsc::Receiv
         tokio::select! {
             _ = pending_sleep => {
                 pending_poll_at = None;
+                // Explicit `PollRepo` refreshes still run; only full background
+                // polls pause. `Resume` re-arms one on refocus.
+                if pending_poll_paths.is_empty() && state.poll_activity.gate() == PollGate::Skip {
+                    continue;
+                }
                 let rate_budget = state.github_rate_limit_remaining.load(std::sync::atomic::Ordering::Relaxed);
                 let batch = if pending_poll_paths.is_empty() { &paths } else { &pending_poll_paths };
                 poll_batch(&state, &handle, batch, false, &issue_filter, pr_hide_drafts, &mut ps).await;
@@ -302,6 +354,9 @@ async fn poll_loop(state: Arc<AppState>, handle: AppHandle, mut rx: mpsc::Receiv
                 interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
             }
             _ = interval.tick() => {
+                if state.poll_activity.gate() == PollGate::Skip {
+                    continue;
+                }
                 let rate_budget = state.github_rate_limit_remaining.load(std::sync::atomic::Ordering::Relaxed);
                 let batch_paths = if startup {
                     paths.clone()
@@ -328,6 +383,10 @@ async fn poll_loop(state: Arc<AppState>, handle: AppHandle, mut rx: mpsc::Receiv
                             pending_poll_at = Some(tokio::time::Instant::now());
                         }
                     }
+                    Some(PollerCmd::Resume) => {
+                        pending_poll_paths.clear();
+                        pending_poll_at = Some(tokio::time::Instant::now());
+                    }
                     Some(PollerCmd::PollRepo(path)) => {
                         if !pending_poll_paths.contains(&path) {
                             pending_poll_paths.push(path);
@@ -734,6 +793,28 @@ pub(crate) async fn github_set_visibility(
     Ok(())
 }
 
+/// Gate background GitHub polling on window focus. Returns whether polling is
+/// now active: always `true` when `pause_polling_when_unfocused` is off.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn set_poll_active(
+    state: tauri::State<'_, Arc<AppState>>,
+    active: bool,
+) -> Result<bool, String> {
+    Ok(set_poll_active_impl(&state, DESKTOP_POLL_CLIENT, active))
+}
+
+pub(crate) fn set_poll_active_impl(state: &AppState, client: &str, active: bool) -> bool {
+    let active = active || !state.config.read().pause_polling_when_unfocused;
+    if state.poll_activity.set_active(client, active)
+        && let Some(poller) = state.github_poller.lock().as_ref()
+        && let Err(e) = poller.cmd_tx.try_send(PollerCmd::Resume)
+    {
+        tracing::warn!(source = "github", "Failed to send Resume: {e}");
+    }
+    state.poll_activity.gate() == PollGate::Run
+}
+
 #[cfg(feature = "desktop")]
 #[tauri::command]
 pub(crate) async fn github_poll_repo(
@@ -1128,4 +1209,60 @@ mod tests {
         assert!(!should_emit(Some(&prev), &cur, false));
         assert!(should_emit(Some(&prev), &cur, true));
     }
+
+    #[test]
+    fn poll_gate_skips_while_inactive_and_resumes() {
+        let activity = PollActivity::default();
+        assert_eq!(activity.gate(), PollGate::Run);
+
+        assert!(
+            !activity.set_active("desktop", false),
+            "pausing is not a resume"
+        );
+        assert_eq!(activity.gate(), PollGate::Skip);
+        assert!(!activity.set_active("desktop", false));
+        assert_eq!(activity.gate(), PollGate::Skip);
+
+        assert!(
+            activity.set_active("desktop", true),
+            "refocus resumes polling"
+        );
+        assert_eq!(activity.gate(), PollGate::Run);
+        assert!(!activity.set_active("desktop", true), "already active");
+    }
+
+    #[test]
+    fn poll_gate_pauses_only_when_every_client_is_inactive() {
+        let activity = PollActivity::default();
+        assert!(!activity.set_active("desktop", true));
+        assert!(!activity.set_active("http:10.0.0.2", false));
+        assert_eq!(
+            activity.gate(),
+            PollGate::Run,
+            "remote blur can't pause the desktop"
+        );
+
+        assert!(!activity.set_active("desktop", false));
+        assert_eq!(activity.gate(), PollGate::Skip);
+
+        assert!(activity.set_active("http:10.0.0.2", true));
+        assert_eq!(activity.gate(), PollGate::Run);
+    }
+
+    #[test]
+    fn set_poll_active_respects_config() {
+        let state = crate::state::tests_support::make_test_app_state();
+        state.config.write().pause_polling_when_unfocused = true;
+        assert!(!set_poll_active_impl(&state, DESKTOP_POLL_CLIENT, false));
+        assert_eq!(state.poll_activity.gate(), PollGate::Skip);
+        assert!(set_poll_active_impl(&state, DESKTOP_POLL_CLIENT, true));
+        assert_eq!(state.poll_activity.gate(), PollGate::Run);
+
+        state.config.write().pause_polling_when_unfocused = false;
+        assert!(
+            set_poll_active_impl(&state, DESKTOP_POLL_CLIENT, false),
+            "pausing disabled"
+        );
+        assert_eq!(state.poll_activity.gate(), PollGate::Run);
+    }
 }
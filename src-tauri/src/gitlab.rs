@@ -0,0 +1,496 @@
+//! GitLab merge request backend.
+//!
+//! Mirrors the GitHub PR path in `github.rs` for repos whose `origin` is on
+//! gitlab.com: merge requests are fetched from the GitLab GraphQL API and mapped
+//! into [`BranchPrStatus`] so the frontend renders them with the same shape and
+//! the same merge/review state labels.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::github::{
+    BranchPrStatus, CheckSummary, GqlError, PrLabel, RemoteHost, StateLabel, check_graphql_errors,
+    classify_merge_state, classify_review_state, header_as_u64, parse_remote_host,
+    rate_limit_wait_secs, retain_current_merged,
+};
+use crate::state::AppState;
+
+const GITLAB_GRAPHQL_URL: &str = "https://gitlab.com/api/graphql";
+
+/// Open MRs, plus merged ones when `$includeMerged` is set (two aliased
+/// connections so each state keeps its own page of results).
+const PROJECT_MRS_QUERY: &str = r#"
+query ProjectMergeRequests($fullPath: ID!, $includeMerged: Boolean!) {
+  currentUser { username }
+  project(fullPath: $fullPath) {
+    open: mergeRequests(state: opened, first: 20, sort: UPDATED_DESC) {
+      nodes { ...MrFields }
+    }
+    merged: mergeRequests(state: merged, first: 20, sort: UPDATED_DESC) @include(if: $includeMerged) {
+      nodes { ...MrFields }
+    }
+  }
+}
+
+fragment MrFields on MergeRequest {
+  iid title state webUrl sourceBranch targetBranch draft
+  diffHeadSha createdAt updatedAt commitCount
+  detailedMergeStatus conflicts approved
+  diffStatsSummary { additions deletions }
+  author { username }
+  approvedBy { nodes { username } }
+  labels(first: 10) { nodes { title color } }
+  headPipeline { status }
+}
+"#;
+
+/// Resolve a GitLab API token: `GITLAB_TOKEN` env, then `glab auth token`.
+pub(crate) fn resolve_gitlab_token() -> Option<String> {
+    if let Ok(token) = std::env::var("GITLAB_TOKEN")
+        && !token.is_empty()
+    {
+        return Some(token);
+    }
+    token_from_glab_cli()
+}
+
+/// Run `glab auth token` to read the token from glab's credential store.
+fn token_from_glab_cli() -> Option<String> {
+    let mut cmd = std::process::Command::new(crate::agent::resolve_cli("glab"));
+    cmd.args(["auth", "token"]);
+    crate::cli::apply_no_window(&mut cmd);
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let token = String::from_utf8(output.stdout).ok()?;
+    let token = token.trim().to_string();
+    if token.is_empty() { None } else { Some(token) }
+}
+
+/// Return the stored GitLab token, resolving it lazily on first use.
+async fn current_gitlab_token(state: &AppState) -> Option<String> {
+    if let Some(token) = state.gitlab_token.read().clone() {
+        return Some(token);
+    }
+    let token = tokio::task::spawn_blocking(resolve_gitlab_token)
+        .await
+        .ok()
+        .flatten()?;
+    *state.gitlab_token.write() = Some(token.clone());
+    Some(token)
+}
+
+/// Execute a GraphQL query against the GitLab API.
+/// Rate limits (429, or 403 with `ratelimit-remaining: 0`) map to `GqlError::RateLimit`.
+async fn graphql_request(
+    client: &reqwest::Client,
+    token: &str,
+    query: &str,
+    variables: &serde_json::Value,
+) -> Result<serde_json::Value, GqlError> {
+    let body = serde_json::json!({
+        "query": query,
+        "variables": variables,
+    });
+
+    let response = client
+        .post(GITLAB_GRAPHQL_URL)
+        .header("Authorization", format!("Bearer {token}"))
+        .header("User-Agent", "tuicommander")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| GqlError::Other(format!("GitLab GraphQL request failed: {e}")))?;
+
+    let status = response.status();
+    let ratelimit_remaining = header_as_u64(response.headers(), "ratelimit-remaining");
+    let ratelimit_reset = header_as_u64(response.headers(), "ratelimit-reset");
+    let retry_after = header_as_u64(response.headers(), "retry-after");
+
+    if status.as_u16() == 429 || (status.as_u16() == 403 && ratelimit_remaining == Some(0)) {
+        return Err(GqlError::RateLimit {
+            reset_at: ratelimit_reset,
+            retry_after,
+            message: format!("GitLab HTTP {status}"),
+        });
+    }
+
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| GqlError::Other(format!("Failed to parse GitLab response: {e}")))?;
+
+    if !status.is_success() {
+        let msg = json["message"]
+            .as_str()
+            .or_else(|| json["error"].as_str())
+            .unwrap_or("Unknown error");
+        let err_msg = format!("GitLab API error ({status}): {msg}");
+        if status.as_u16() == 401 {
+            return Err(GqlError::Auth(err_msg));
+        }
+        return Err(GqlError::Other(err_msg));
+    }
+
+    check_graphql_errors(&json, ratelimit_reset, retry_after)?;
+    Ok(json)
+}
+
+/// Execute a GitLab GraphQL query behind `gitlab_circuit_breaker`.
+/// Same policy as the GitHub path: rate limits back off without counting as
+/// failures; auth and other errors count toward opening the circuit.
+async fn graphql_with_breaker(
+    state: &AppState,
+    token: &str,
+    query: &str,
+    variables: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    state.gitlab_circuit_breaker.check()?;
+    match graphql_request(&state.http_client, token, query, &variables).await {
+        Ok(response) => {
+            state.gitlab_circuit_breaker.record_success();
+            Ok(response)
+        }
+        Err(GqlError::RateLimit {
+            reset_at,
+            retry_after,
+            message,
+        }) => {
+            let wait = rate_limit_wait_secs(reset_at, retry_after);
+            state.gitlab_circuit_breaker.record_rate_limit(wait);
+            Err(format!("rate-limit: {message}"))
+        }
+        Err(GqlError::Auth(msg)) => {
+            // Drop the cached token so the next call re-resolves (e.g. after `glab auth login`).
+            *state.gitlab_token.write() = None;
+            state.gitlab_circuit_breaker.record_failure();
+            Err(msg)
+        }
+        Err(GqlError::Other(msg)) => {
+            state.gitlab_circuit_breaker.record_failure();
+            Err(msg)
+        }
+    }
+}
+
+/// Translate GitLab's `detailedMergeStatus` into GitHub's
+/// (`mergeable`, `mergeStateStatus`) vocabulary.
+fn github_merge_fields(detailed: &str, conflicts: bool) -> (&'static str, &'static str) {
+    if conflicts {
+        return ("CONFLICTING", "DIRTY");
+    }
+    match detailed.to_uppercase().as_str() {
+        "MERGEABLE" => ("MERGEABLE", "CLEAN"),
+        "CONFLICT" | "BROKEN_STATUS" => ("CONFLICTING", "DIRTY"),
+        "NEED_REBASE" => ("MERGEABLE", "BEHIND"),
+        "DRAFT_STATUS" => ("MERGEABLE", "DRAFT"),
+        "CI_MUST_PASS"
+        | "CI_STILL_RUNNING"
+        | "NOT_APPROVED"
+        | "REQUESTED_CHANGES"
+        | "DISCUSSIONS_NOT_RESOLVED"
+        | "BLOCKED_STATUS"
+        | "EXTERNAL_STATUS_CHECKS"
+        | "JIRA_ASSOCIATION_MISSING"
+        | "SECURITY_POLICIES_VIOLATIONS"
+        | "MERGE_TIME"
+        | "COMMITS_STATUS"
+        | "LOCKED_PATHS"
+        | "LOCKED_LFS_FILES" => ("MERGEABLE", "BLOCKED"),
+        // CHECKING, UNCHECKED, APPROVALS_SYNCING, PREPARING, NOT_OPEN
+        _ => ("UNKNOWN", "UNKNOWN"),
+    }
+}
+
+/// Merge readiness label for a GitLab MR, using the GitHub CSS classes.
+pub(crate) fn classify_gitlab_merge_state(detailed: &str, conflicts: bool) -> Option<StateLabel> {
+    let (mergeable, status) = github_merge_fields(detailed, conflicts);
+    classify_merge_state(Some(mergeable), Some(status))
+}
+
+/// GitHub-style `reviewDecision` for a GitLab MR.
+fn github_review_decision(detailed: &str, approved: bool) -> &'static str {
+    match detailed.to_uppercase().as_str() {
+        "REQUESTED_CHANGES" => "CHANGES_REQUESTED",
+        _ if approved => "APPROVED",
+        "NOT_APPROVED" => "REVIEW_REQUIRED",
+        _ => "",
+    }
+}
+
+/// Review label for a GitLab MR, using the GitHub CSS classes.
+pub(crate) fn classify_gitlab_review_state(detailed: &str, approved: bool) -> Option<StateLabel> {
+    match github_review_decision(detailed, approved) {
+        "" => None,
+        decision => classify_review_state(Some(decision)),
+    }
+}
+
+/// Map a GitLab MR state (`opened`, `merged`, `closed`, `locked`) to GitHub's.
+fn github_pr_state(state: &str) -> &'static str {
+    match state {
+        "opened" => "OPEN",
+        "merged" => "MERGED",
+        _ => "CLOSED",
+    }
+}
+
+/// Summarize the head pipeline as a single check: GitLab has no per-job
+/// rollup on the MR node, so the pipeline status stands in for it.
+fn pipeline_checks(status: Option<&str>) -> CheckSummary {
+    let (passed, failed, pending) = match status.map(str::to_uppercase).as_deref() {
+        None => (0, 0, 0),
+        Some("SUCCESS" | "SKIPPED") => (1, 0, 0),
+        Some("FAILED" | "CANCELED") => (0, 1, 0),
+        Some(_) => (0, 0, 1),
+    };
+    CheckSummary {
+        passed,
+        failed,
+        pending,
+        total: passed + failed + pending,
+    }
+}
+
+/// Map a GitLab merge request node into a [`BranchPrStatus`].
+/// `viewer` is the authenticated username, used for `viewer_did_approve`.
+fn parse_mr_node(v: &serde_json::Value, viewer: &str) -> Option<BranchPrStatus> {
+    let branch = v["sourceBranch"].as_str()?.to_string();
+    let number = v["iid"].as_str()?.parse::<i32>().ok()?;
+    let detailed = v["detailedMergeStatus"].as_str().unwrap_or("");
+    let conflicts = v["conflicts"].as_bool().unwrap_or(false);
+    let approved = v["approved"].as_bool().unwrap_or(false);
+    let is_draft = v["draft"].as_bool().unwrap_or(false);
+
+    let (mergeable, merge_state_status) = github_merge_fields(detailed, conflicts);
+    let review_decision = github_review_decision(detailed, approved);
+    let viewer_did_approve = !viewer.is_empty()
+        && v["approvedBy"]["nodes"]
+            .as_array()
+            .is_some_and(|arr| arr.iter().any(|u| u["username"].as_str() == Some(viewer)));
+
+    let labels = v["labels"]["nodes"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|l| {
+                    // GitLab colors carry a leading '#'.
+                    let color = l["color"].as_str().unwrap_or("").trim_start_matches('#');
+                    Some(PrLabel::new(
+                        l["title"].as_str()?.to_string(),
+                        color.to_string(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(BranchPrStatus {
+        branch,
+        number,
+        title: v["title"].as_str().unwrap_or("").to_string(),
+        state: github_pr_state(v["state"].as_str().unwrap_or("")).to_string(),
+        url: v["webUrl"].as_str().unwrap_or("").to_string(),
+        additions: v["diffStatsSummary"]["additions"].as_i64().unwrap_or(0) as i32,
+        deletions: v["diffStatsSummary"]["deletions"].as_i64().unwrap_or(0) as i32,
+        checks: pipeline_checks(v["headPipeline"]["status"].as_str()),
+        check_details: vec![],
+        author: v["author"]["username"].as_str().unwrap_or("").to_string(),
+        commits: v["commitCount"].as_i64().unwrap_or(0) as i32,
+        mergeable: mergeable.to_string(),
+        merge_state_status: merge_state_status.to_string(),
+        review_decision: review_decision.to_string(),
+        viewer_did_approve,
+        labels,
+        is_draft,
+        base_ref_name: v["targetBranch"].as_str().unwrap_or("").to_string(),
+        head_ref_oid: v["diffHeadSha"].as_str().unwrap_or("").to_string(),
+        created_at: v["createdAt"].as_str().unwrap_or("").to_string(),
+        updated_at: v["updatedAt"].as_str().unwrap_or("").to_string(),
+        merge_state_label: classify_gitlab_merge_state(detailed, conflicts),
+        review_state_label: classify_gitlab_review_state(detailed, approved),
+        // GitLab's merge method is project-level and not exposed here; leave all enabled.
+        merge_commit_allowed: true,
+        squash_merge_allowed: true,
+        rebase_merge_allowed: true,
+    })
+}
+
+/// Extract MR statuses from a `ProjectMergeRequests` response.
+fn parse_project_mrs(response: &serde_json::Value) -> Vec<BranchPrStatus> {
+    let viewer = response["data"]["currentUser"]["username"]
+        .as_str()
+        .unwrap_or("");
+    let project = &response["data"]["project"];
+    ["open", "merged"]
+        .iter()
+        .filter_map(|alias| project[*alias]["nodes"].as_array())
+        .flatten()
+        .filter_map(|node| parse_mr_node(node, viewer))
+        .collect()
+}
+
+/// Core logic for fetching MR statuses of one GitLab project (no caching).
+/// Same contract as `github::get_repo_pr_statuses_impl`: no token or non-rate-limit
+/// errors yield an empty list; rate limits return Err prefixed with "rate-limit:".
+pub(crate) async fn get_repo_mr_statuses_impl(
+    path: &str,
+    group: &str,
+    project: &str,
+    include_merged: bool,
+    state: &AppState,
+) -> Result<Vec<BranchPrStatus>, String> {
+    let Some(token) = current_gitlab_token(state).await else {
+        return Ok(vec![]); // No token = no GitLab API access
+    };
+
+    let variables = serde_json::json!({
+        "fullPath": format!("{group}/{project}"),
+        "includeMerged": include_merged,
+    });
+    match graphql_with_breaker(state, &token, PROJECT_MRS_QUERY, variables).await {
+        Ok(response) => {
+            let mut nodes = parse_project_mrs(&response);
+            if include_merged {
+                retain_current_merged(&mut nodes, Path::new(path));
+            }
+            Ok(nodes)
+        }
+        Err(e) if e.starts_with("rate-limit:") => Err(e),
+        Err(e) => {
+            tracing::warn!(source = "gitlab", %path, "GraphQL MR query failed: {e}");
+            Ok(vec![])
+        }
+    }
+}
+
+/// Fetch MR statuses for every GitLab-hosted repo in `paths`, caching each
+/// result alongside the GitHub ones. Non-GitLab paths are skipped; failures
+/// are logged and leave that repo out of the map.
+pub(crate) async fn get_all_mr_statuses_impl(
+    paths: &[String],
+    include_merged: bool,
+    state: &AppState,
+) -> HashMap<String, Vec<BranchPrStatus>> {
+    let mut results = HashMap::new();
+    for path in paths {
+        let Some(url) = crate::git::read_remote_url(Path::new(path)) else {
+            continue;
+        };
+        let Some(RemoteHost::GitLab(group, project)) = parse_remote_host(&url) else {
+            continue;
+        };
+        match get_repo_mr_statuses_impl(path, &group, &project, include_merged, state).await {
+            Ok(statuses) => {
+                state
+                    .git_cache
+                    .github_status
+                    .insert(path.clone(), Arc::new(statuses.clone()));
+                results.insert(path.clone(), statuses);
+            }
+            Err(e) => tracing::warn!(source = "gitlab", %path, "MR poll skipped: {e}"),
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mr_node() -> serde_json::Value {
+        serde_json::json!({
+            "iid": "42",
+            "title": "Add widget",
+            "state": "opened",
+            "webUrl": "https://gitlab.com/g/p/-/merge_requests/42",
+            "sourceBranch": "feature/widget",
+            "targetBranch": "main",
+            "draft": false,
+            "diffHeadSha": "abc123",
+            "createdAt": "2026-01-01T00:00:00Z",
+            "updatedAt": "2026-01-02T00:00:00Z",
+            "commitCount": 3,
+            "detailedMergeStatus": "MERGEABLE",
+            "conflicts": false,
+            "approved": true,
+            "diffStatsSummary": { "additions": 10, "deletions": 2 },
+            "author": { "username": "alice" },
+            "approvedBy": { "nodes": [{ "username": "bob" }] },
+            "labels": { "nodes": [{ "title": "bug", "color": "#d73a4a" }] },
+            "headPipeline": { "status": "RUNNING" }
+        })
+    }
+
+    #[test]
+    fn parse_mr_node_maps_into_branch_pr_status() {
+        let pr = parse_mr_node(&mr_node(), "bob").unwrap();
+        assert_eq!(pr.branch, "feature/widget");
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.state, "OPEN");
+        assert_eq!(pr.base_ref_name, "main");
+        assert_eq!((pr.additions, pr.deletions, pr.commits), (10, 2, 3));
+        assert_eq!(pr.author, "alice");
+        assert_eq!(pr.checks.pending, 1);
+        assert_eq!(pr.checks.total, 1);
+        assert_eq!(pr.review_decision, "APPROVED");
+        assert!(pr.viewer_did_approve);
+        assert_eq!(pr.merge_state_label.unwrap().css_class, "clean");
+        assert_eq!(pr.review_state_label.unwrap().css_class, "approved");
+        assert_eq!(pr.labels.len(), 1);
+
+        assert!(
+            !parse_mr_node(&mr_node(), "carol")
+                .unwrap()
+                .viewer_did_approve
+        );
+    }
+
+    #[test]
+    fn detailed_merge_status_uses_github_css_classes() {
+        let css = |d: &str, c: bool| classify_gitlab_merge_state(d, c).map(|l| l.css_class);
+        assert_eq!(css("CONFLICT", false).as_deref(), Some("conflicting"));
+        assert_eq!(css("MERGEABLE", true).as_deref(), Some("conflicting"));
+        assert_eq!(css("need_rebase", false).as_deref(), Some("behind"));
+        assert_eq!(css("DRAFT_STATUS", false).as_deref(), Some("behind"));
+        assert_eq!(css("CI_STILL_RUNNING", false).as_deref(), Some("blocked"));
+        assert_eq!(css("CHECKING", false), None);
+
+        let review = |d: &str, a: bool| classify_gitlab_review_state(d, a).map(|l| l.css_class);
+        assert_eq!(
+            review("REQUESTED_CHANGES", true).as_deref(),
+            Some("changes-requested")
+        );
+        assert_eq!(
+            review("NOT_APPROVED", false).as_deref(),
+            Some("review-required")
+        );
+        assert_eq!(review("MERGEABLE", false), None);
+    }
+
+    #[test]
+    fn parse_project_mrs_reads_open_and_merged_aliases() {
+        let mut merged = mr_node();
+        merged["state"] = "merged".into();
+        merged["iid"] = "7".into();
+        let response = serde_json::json!({
+            "data": {
+                "currentUser": { "username": "bob" },
+                "project": {
+                    "open": { "nodes": [mr_node()] },
+                    "merged": { "nodes": [merged] }
+                }
+            }
+        });
+        let states: Vec<_> = parse_project_mrs(&response)
+            .into_iter()
+            .map(|p| (p.number, p.state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![(42, "OPEN".to_string()), (7, "MERGED".to_string())]
+        );
+    }
+}
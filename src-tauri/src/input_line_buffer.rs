@@ -18,6 +18,10 @@ pub(crate) struct InputLineBuffer {
     esc_state: EscState,
     /// Accumulator for CSI parameter bytes (digits and semicolons).
     csi_params: Vec<u8>,
+    /// Return submitted line content (`Line`). When false, Enter yields
+    /// `Submitted` instead — editing state is still tracked, content is not
+    /// surfaced (`reconstruct_user_input` config).
+    reconstruct: bool,
 }
 
 /// Escape sequence parser state.
@@ -40,6 +44,8 @@ pub(crate) enum InputAction {
     Line(String),
     /// Input was interrupted (Ctrl+C). Buffer was cleared.
     Interrupt,
+    /// A line was submitted with reconstruction disabled; content withheld.
+    Submitted { empty: bool },
 }
 
 impl InputLineBuffer {
@@ -49,9 +55,15 @@ impl InputLineBuffer {
             cursor: 0,
             esc_state: EscState::Normal,
             csi_params: Vec::new(),
+            reconstruct: true,
         }
     }
 
+    /// Enable or disable surfacing submitted line content.
+    pub(crate) fn set_reconstruct(&mut self, reconstruct: bool) {
+        self.reconstruct = reconstruct;
+    }
+
     /// Feed raw PTY write data into the buffer.
     /// Returns a list of actions (typically 0 or 1 Line actions per call).
     pub(crate) fn feed(&mut self, data: &str) -> Vec<InputAction> {
@@ -93,10 +105,16 @@ impl InputLineBuffer {
             }
             // Enter (CR or LF) — submit line
             '\r' | '\n' => {
-                let line: String = self.chars.iter().collect();
+                let action = if self.reconstruct {
+                    InputAction::Line(self.chars.iter().collect())
+                } else {
+                    InputAction::Submitted {
+                        empty: self.chars.is_empty(),
+                    }
+                };
                 self.chars.clear();
                 self.cursor = 0;
-                Some(InputAction::Line(line))
+                Some(action)
             }
             // Backspace (DEL) or Ctrl+H — delete char before cursor
             '\x7f' | '\x08' => {
@@ -445,6 +463,35 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_reconstruction_disabled_withholds_lines() {
+        let mut buf = InputLineBuffer::new();
+        buf.set_reconstruct(false);
+        buf.feed("helo");
+        buf.feed("\x1b[D"); // Left arrow
+        buf.feed("l");
+        // Editing still works internally (slash-mode tracking relies on it).
+        assert_eq!(buf.content(), "hello");
+        assert_eq!(buf.cursor_pos(), 4);
+
+        let actions = buf.feed("\r\r");
+        assert!(
+            actions.iter().all(|a| !matches!(a, InputAction::Line(_))),
+            "no line content may be surfaced: {actions:?}"
+        );
+        assert!(matches!(
+            actions[..],
+            [
+                InputAction::Submitted { empty: false },
+                InputAction::Submitted { empty: true }
+            ]
+        ));
+        assert_eq!(buf.content(), "");
+
+        buf.set_reconstruct(true);
+        assert_eq!(feed_and_get_line(&mut buf, "ok\r"), Some("ok".into()));
+    }
+
     #[test]
     fn test_simple_typing_and_submit() {
         let mut buf = InputLineBuffer::new();
@@ -14,6 +14,7 @@ pub(crate) mod agent_session;
 pub(crate) mod ai_agent;
 pub(crate) mod ai_chat;
 pub(crate) mod ai_chat_registry;
+pub(crate) mod ansi;
 pub(crate) mod app_logger;
 pub(crate) mod chrome;
 pub(crate) mod claude_usage;
@@ -22,10 +23,12 @@ pub(crate) mod config;
 pub(crate) mod content_index;
 pub(crate) mod cpu_watchdog;
 pub(crate) mod credentials;
+pub(crate) mod diagnostics;
 #[cfg(feature = "desktop")]
 mod dictation;
 pub(crate) mod diff_triage;
 pub(crate) mod dir_watcher;
+pub(crate) mod env_file;
 pub(crate) mod error_classification;
 pub(crate) mod fs;
 pub(crate) mod generators;
@@ -37,6 +40,7 @@ pub(crate) mod github;
 pub(crate) mod github_auth;
 pub(crate) mod github_debug;
 pub(crate) mod github_poller;
+pub(crate) mod gitlab;
 #[cfg(feature = "desktop")]
 mod global_hotkey;
 mod input_line_buffer;
@@ -58,6 +62,7 @@ mod menu;
 mod native_drag;
 #[cfg(feature = "desktop")]
 pub(crate) mod notification_sound;
+pub(crate) mod notification_throttle;
 mod output_parser;
 #[cfg(feature = "desktop")]
 mod panel_window;
@@ -74,15 +79,20 @@ pub(crate) mod prompt;
 pub(crate) mod provider_registry;
 pub(crate) mod pty;
 pub(crate) mod push;
+pub(crate) mod recording;
 pub(crate) mod registry;
 pub(crate) mod relay_client;
 #[allow(dead_code)] // Constructors used by remote binary and future tests
 pub(crate) mod remote_connection;
+pub(crate) mod remote_diagnosis;
 pub(crate) mod repo_watcher;
+pub(crate) mod resize_debounce;
+pub(crate) mod session_export;
 mod shell_integration;
 #[cfg(feature = "desktop")]
 pub(crate) mod sleep_prevention;
 pub(crate) mod smart_prompt;
+pub(crate) mod spawn_ledger;
 pub(crate) mod state;
 #[cfg(feature = "desktop")]
 mod tab_shortcut;
@@ -91,6 +101,7 @@ pub(crate) mod terminal_grid;
 pub(crate) mod text_rank;
 pub(crate) mod themes;
 pub(crate) mod tool_search;
+pub(crate) mod transcript;
 #[cfg(feature = "desktop")]
 mod tuic_cli;
 #[allow(dead_code)] // Many items used only by the remote binary (not(desktop) build)
@@ -105,7 +116,6 @@ use std::sync::Arc;
 use tauri::{Emitter, Manager, State, WebviewWindow};
 
 // Re-export shared types from state module
-pub(crate) use state::MAX_CONCURRENT_SESSIONS;
 #[cfg(test)]
 pub(crate) use state::SessionMetrics;
 pub(crate) use state::{AppState, OutputRingBuffer, PtySession};
@@ -238,12 +248,23 @@ fn load_config(state: State<'_, Arc<AppState>>) -> config::AppConfig {
 /// if MCP / Remote Access settings changed (no app restart required).
 #[tauri::command]
 fn save_config(state: State<'_, Arc<AppState>>, config: config::AppConfig) -> Result<(), String> {
+    save_config_impl(state.inner(), config)
+}
+
+/// Apply a named remote-access profile (`services.remote_profiles`) and save,
+/// live-restarting the server like `save_config`.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+fn switch_remote_profile(state: State<'_, Arc<AppState>>, name: String) -> Result<(), String> {
+    let mut config = state.config.read().clone();
+    config.services.apply_remote_profile(&name)?;
+    save_config_impl(state.inner(), config)
+}
+
+#[cfg(feature = "desktop")]
+fn save_config_impl(state: &Arc<AppState>, config: config::AppConfig) -> Result<(), String> {
     let old = state.config.read().clone();
-    let server_changed = old.services.server.enabled != config.services.server.enabled
-        || old.services.server.port != config.services.server.port
-        || old.services.auth.username != config.services.auth.username
-        || old.services.auth.password_hash != config.services.auth.password_hash
-        || old.services.server.ipv6_enabled != config.services.server.ipv6_enabled;
+    let server_changed = old.services.listener_changed(&config.services);
 
     let tools_changed = old.disabled_native_tools != config.disabled_native_tools
         || old.collapse_tools != config.collapse_tools;
@@ -256,7 +277,7 @@ fn save_config(state: State<'_, Arc<AppState>>, config: config::AppConfig) -> Re
     }
 
     if server_changed {
-        restart_server(state.inner());
+        restart_server(state);
     }
 
     Ok(())
@@ -301,6 +322,8 @@ fn screenshot_response(state: State<'_, Arc<AppState>>, request_id: String, data
 struct LocalIpEntry {
     ip: String,
     label: String,
+    /// Interface name (e.g. `en0`, `tailscale0`); empty on Windows.
+    interface: String,
 }
 
 /// Return all non-loopback IP addresses on this machine, with human-readable labels.
@@ -345,6 +368,7 @@ fn get_local_ips_with_config(ipv6_enabled: bool) -> Vec<LocalIpEntry> {
                 result.push(LocalIpEntry {
                     ip,
                     label: "Network".to_string(),
+                    interface: String::new(),
                 });
             }
         }
@@ -357,7 +381,11 @@ fn get_local_ips_with_config(ipv6_enabled: bool) -> Vec<LocalIpEntry> {
             let ip_str = addr.ip().to_string();
             if !ip_str.starts_with("::1") {
                 let label = classify_ipv6_addr(&addr.ip());
-                result.push(LocalIpEntry { ip: ip_str, label });
+                result.push(LocalIpEntry {
+                    ip: ip_str,
+                    label,
+                    interface: String::new(),
+                });
             }
         }
         result
@@ -403,6 +431,7 @@ fn enumerate_unix_ips(ipv6_enabled: bool) -> Vec<LocalIpEntry> {
                         result.push(LocalIpEntry {
                             ip: ip.to_string(),
                             label,
+                            interface: iface,
                         });
                     }
                 } else if ipv6_enabled && family == libc::AF_INET6 {
@@ -415,6 +444,7 @@ fn enumerate_unix_ips(ipv6_enabled: bool) -> Vec<LocalIpEntry> {
                         result.push(LocalIpEntry {
                             ip: ip.to_string(),
                             label,
+                            interface: iface,
                         });
                     }
                 }
@@ -487,6 +517,79 @@ pub(crate) fn pick_preferred_ip(ips: Vec<LocalIpEntry>) -> Option<String> {
     ips.into_iter().next().map(|e| e.ip)
 }
 
+/// Check a configured remote bind address against this machine's addresses.
+///
+/// Returns `Ok(None)` for the wildcard (empty, `0.0.0.0`, `::`) — bind every
+/// interface — and `Ok(Some(ip))` for loopback or an address in `local_ips`.
+/// Anything else is rejected so the server never silently binds elsewhere.
+pub(crate) fn validate_bind_address(
+    bind_address: &str,
+    local_ips: &[String],
+) -> Result<Option<std::net::IpAddr>, String> {
+    let trimmed = bind_address.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let ip: std::net::IpAddr = trimmed
+        .parse()
+        .map_err(|_| format!("Invalid bind address '{trimmed}': not an IP address"))?;
+    if ip.is_unspecified() {
+        return Ok(None);
+    }
+    if ip.is_loopback() || local_ips.iter().any(|l| l.parse().ok() == Some(ip)) {
+        Ok(Some(ip))
+    } else {
+        Err(format!(
+            "Bind address {ip} is not assigned to any network interface on this machine"
+        ))
+    }
+}
+
+/// Pick the address to bind for `services.server.bind_interface`: the
+/// interface's IPv4 address, else (with `ipv6_enabled`) its IPv6 one.
+fn resolve_bind_interface(
+    interface: &str,
+    local_ips: &[LocalIpEntry],
+    ipv6_enabled: bool,
+) -> Result<std::net::IpAddr, String> {
+    let addrs: Vec<std::net::IpAddr> = local_ips
+        .iter()
+        .filter(|e| e.interface == interface)
+        .filter_map(|e| e.ip.parse().ok())
+        .collect();
+    addrs
+        .iter()
+        .find(|ip| ip.is_ipv4())
+        .or_else(|| addrs.iter().find(|ip| ipv6_enabled && ip.is_ipv6()))
+        .copied()
+        .ok_or_else(|| {
+            let mut names: Vec<&str> = local_ips.iter().map(|e| e.interface.as_str()).collect();
+            names.sort_unstable();
+            names.dedup();
+            format!(
+                "Bind interface '{interface}' has no usable address (available: {})",
+                names.join(", ")
+            )
+        })
+}
+
+/// Resolve `services.server.bind_address` (then `bind_interface`) against the
+/// live interface list. IPv6 addresses are always enumerated so a v6 bind
+/// address can validate.
+pub(crate) fn configured_bind_ip(
+    server: &config::ServerConfig,
+) -> Result<Option<std::net::IpAddr>, String> {
+    let local = get_local_ips_with_config(true);
+    let local_ips: Vec<String> = local.iter().map(|e| e.ip.clone()).collect();
+    let bind_ip = validate_bind_address(&server.bind_address, &local_ips)?;
+    match server.bind_interface.as_deref().map(str::trim) {
+        Some(interface) if bind_ip.is_none() && !interface.is_empty() => {
+            resolve_bind_interface(interface, &local, server.ipv6_enabled).map(Some)
+        }
+        _ => Ok(bind_ip),
+    }
+}
+
 /// Legacy single-IP command kept for backwards compatibility.
 /// Returns the LAN/Tailscale IP preferred for remote access, or the default-route IP.
 #[cfg(feature = "desktop")]
@@ -507,6 +610,12 @@ pub(crate) struct MarkdownFileEntry {
     pub modified_at: u64,
 }
 
+/// Directory-walk skip rule shared by the markdown listing and `git::scan_for_repos`:
+/// hidden entries plus dependency/build output dirs.
+pub(crate) fn is_walk_skipped(name: &str) -> bool {
+    name.starts_with('.') || name == "node_modules" || name == "target"
+}
+
 /// List all markdown files in a repository recursively, with git status (shared logic)
 pub(crate) fn list_markdown_files_impl(path: String) -> Result<Vec<MarkdownFileEntry>, String> {
     let repo_path = PathBuf::from(&path);
@@ -525,7 +634,7 @@ pub(crate) fn list_markdown_files_impl(path: String) -> Result<Vec<MarkdownFileE
 
                 // Skip hidden directories and common ignore patterns
                 if let Some(name) = path.file_name().and_then(|n| n.to_str())
-                    && (name.starts_with('.') || name == "node_modules" || name == "target")
+                    && is_walk_skipped(name)
                 {
                     continue;
                 }
@@ -738,28 +847,30 @@ async fn get_mcp_status(state: State<'_, Arc<AppState>>) -> Result<serde_json::V
     #[cfg(not(unix))]
     let running = false;
 
-    // TCP reachability self-test for remote access
-    let remote_port = state.config.read().services.server.port;
+    // TCP reachability self-test for remote access — probe the address the
+    // listener is actually bound to when a specific bind address is configured.
+    let server_cfg = state.config.read().services.server.clone();
+    let remote_port = server_cfg.port;
     let reachable = if remote_enabled {
-        let preferred_ip = pick_preferred_ip(get_local_ips_with_config(
-            state.config.read().services.server.ipv6_enabled,
-        ));
+        let preferred_ip = match configured_bind_ip(&server_cfg) {
+            Ok(Some(ip)) => Some(ip),
+            Ok(None) => pick_preferred_ip(get_local_ips_with_config(server_cfg.ipv6_enabled))
+                .and_then(|ip| ip.parse().ok()),
+            Err(_) => Some(std::net::IpAddr::from([127, 0, 0, 1])),
+        };
+        // The listener may have moved to a neighbouring port if the configured one was busy.
+        let port = match state.remote_port.load(std::sync::atomic::Ordering::Relaxed) {
+            0 => remote_port,
+            bound => bound,
+        };
         if let Some(ip) = preferred_ip {
-            let port = remote_port;
-            let addr = if ip.contains(':') {
-                format!("[{ip}]:{port}")
-            } else {
-                format!("{ip}:{port}")
-            };
+            let sa = std::net::SocketAddr::new(ip, port);
             tokio::task::spawn_blocking(move || {
-                addr.parse::<std::net::SocketAddr>().ok().map(|sa| {
-                    std::net::TcpStream::connect_timeout(&sa, std::time::Duration::from_millis(200))
-                        .is_ok()
-                })
+                std::net::TcpStream::connect_timeout(&sa, std::time::Duration::from_millis(200))
+                    .is_ok()
             })
             .await
             .ok()
-            .flatten()
         } else {
             None
         }
@@ -773,7 +884,7 @@ async fn get_mcp_status(state: State<'_, Arc<AppState>>) -> Result<serde_json::V
         "remote_port": if remote_enabled { Some(remote_port) } else { None },
         "active_sessions": active_sessions,
         "mcp_clients": mcp_protocol_sessions,
-        "max_sessions": MAX_CONCURRENT_SESSIONS,
+        "max_sessions": state.max_sessions(),
         "reachable": reachable,
     }))
 }
@@ -812,6 +923,7 @@ async fn deep_link_mcp_call(
     let result = mcp_http::mcp_transport::handle_mcp_tool_call(
         &state.inner().clone(),
         addr,
+        false,
         &tool,
         &serde_json::Value::Object(args),
         None,
@@ -897,6 +1009,48 @@ async fn provision_tls_config(
     None
 }
 
+/// Load the cert/key of a manual `services.tls`. `Ok(None)` when TLS is off.
+async fn load_manual_tls(
+    tls: &config::TlsConfig,
+) -> Result<Option<axum_server::tls_rustls::RustlsConfig>, String> {
+    let config::TlsConfig::Manual {
+        cert_path,
+        key_path,
+    } = tls
+    else {
+        return Ok(None);
+    };
+    let cert_pem = std::fs::read(cert_path)
+        .map_err(|e| format!("Failed to read TLS cert at {cert_path}: {e}"))?;
+    let key_pem = std::fs::read(key_path)
+        .map_err(|e| format!("Failed to read TLS key at {key_path}: {e}"))?;
+    let tls = axum_server::tls_rustls::RustlsConfig::from_pem(cert_pem, key_pem)
+        .await
+        .map_err(|e| format!("Invalid TLS cert/key: {e}"))?;
+    tracing::info!(
+        source = "remote",
+        cert_path,
+        key_path,
+        "TLS loaded (manual mode)"
+    );
+    Ok(Some(tls))
+}
+
+#[cfg(feature = "desktop")]
+/// TLS for the remote-access listener: a manual `services.tls` cert wins,
+/// otherwise a Tailscale cert when HTTPS is available. A manual cert that
+/// fails to load is logged and falls back the same way.
+async fn server_tls_config(state: &Arc<AppState>) -> Option<axum_server::tls_rustls::RustlsConfig> {
+    let tls = state.config.read().services.tls.clone();
+    match load_manual_tls(&tls).await {
+        Ok(Some(tls)) => return Some(tls),
+        Ok(None) => {}
+        Err(e) => tracing::error!(source = "remote", "{e} — ignoring manual TLS"),
+    }
+    let ts = state.tailscale_state.read().clone();
+    provision_tls_config(&ts).await
+}
+
 #[cfg(feature = "desktop")]
 /// Restart the HTTP/MCP server with fresh TLS config (reuses the shutdown/spawn pattern from save_config).
 fn restart_server(state: &Arc<AppState>) {
@@ -909,8 +1063,7 @@ fn restart_server(state: &Arc<AppState>) {
     std::thread::spawn(move || {
         let rt = tokio::runtime::Runtime::new().expect("tokio runtime for HTTP server restart");
         rt.block_on(async move {
-            let ts = state_arc.tailscale_state.read().clone();
-            let tls_config = provision_tls_config(&ts).await;
+            let tls_config = server_tls_config(&state_arc).await;
             mcp_http::start_server(state_arc, true, remote_enabled, tls_config).await;
         });
     });
@@ -1087,6 +1240,8 @@ pub fn run() {
     let mut app_state = AppState::new(data_dir, worktrees_dir, config.clone(), log_buffer);
     *app_state.github_token.get_mut() = github_token;
     *app_state.github_token_source.get_mut() = github_token_source;
+    *app_state.active_selection.get_mut() = crate::config::load_ui_prefs().active_selection;
+    app_state.load_recoverable_sessions();
 
     let state = Arc::new(app_state);
     state.wire_event_bus();
@@ -1112,8 +1267,8 @@ pub fn run() {
                         ?ts_state,
                         "Tailscale detection result"
                     );
-                    *server_state.tailscale_state.write() = ts_state.clone();
-                    provision_tls_config(&ts_state).await
+                    *server_state.tailscale_state.write() = ts_state;
+                    server_tls_config(&server_state).await
                 } else {
                     None
                 };
@@ -1434,6 +1589,8 @@ pub fn run() {
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
+            ansi::ansi_to_html,
+            output_parser::parse_output_sample,
             generators::generate_value,
             native_drag::start_native_drag,
             remote_connection::list_remote_connections,
@@ -1448,6 +1605,7 @@ pub fn run() {
             pty::create_pty_with_worktree,
             pty::list_worktrees,
             pty::write_pty,
+            pty::broadcast_input,
             pty::get_input_buffer_content,
             pty::resize_pty,
             pty::set_ansi_colors,
@@ -1457,7 +1615,11 @@ pub fn run() {
             pty::get_last_prompt,
             pty::get_shell_state,
             pty::get_session_shell_family,
+            pty::get_session_context,
             pty::close_pty,
+            recording::start_session_recording,
+            recording::stop_session_recording,
+            session_export::export_session_transcript,
             worktree::get_worktrees_dir,
             git::get_repo_info,
             git::get_remote_url,
@@ -1466,6 +1628,7 @@ pub fn run() {
             git::get_changed_files,
             git::get_file_diff,
             git::get_gutter_changes,
+            git::parse_conflict_markers,
             diff_triage::run_diff_triage,
             git::get_recent_commits,
             list_markdown_files,
@@ -1479,7 +1642,11 @@ pub fn run() {
             pty::get_session_metrics,
             pty::can_spawn_session,
             pty::list_active_sessions,
+            pty::list_sessions_page,
             pty::get_process_stats,
+            pty::get_session_resources,
+            pty::list_orphaned_processes,
+            pty::kill_orphaned_process,
             pty::read_vt_log,
             pty::subscribe_terminal_grid,
             pty::unsubscribe_terminal_grid,
@@ -1504,12 +1671,14 @@ pub fn run() {
             pty::set_session_visible,
             pty::update_session_cwd,
             pty::set_session_name,
+            state::list_recoverable_sessions,
             pty::get_session_foreground_process,
             pty::get_session_leaf_pid,
             pty::has_foreground_process,
             pty::debug_agent_detection,
             load_config,
             save_config,
+            switch_remote_profile,
             themes::list_themes,
             mdkb_commands::mdkb_outline,
             mdkb_commands::mdkb_goto_definition,
@@ -1525,6 +1694,8 @@ pub fn run() {
             agent::detect_agent_binary,
             agent::detect_all_agent_binaries,
             agent::spawn_agent,
+            agent::dry_run_spawn,
+            agent::explain_env,
             agent_session::discover_agent_session,
             agent_session::verify_agent_session,
             agent_session::claude_project_dir,
@@ -1533,16 +1704,24 @@ pub fn run() {
             worktree::delete_local_branch,
             agent::detect_installed_ides,
             worktree::create_worktree,
+            worktree::create_worktree_from_pr,
             git::rename_branch,
             git::create_branch,
             git::get_branch_base,
             git::update_from_base,
             git::delete_branch,
+            git::rebase_branch,
+            git::cherry_pick,
+            git::abort_rebase,
+            git::abort_cherry_pick,
+            git::checkout_branch,
             worktree::get_worktree_paths,
             git::get_git_branches,
             git::get_branches_detail,
             git::get_recent_branches,
             git::get_merged_branches,
+            git::get_repo_stats,
+            git::scan_for_repos,
             git::get_repo_summary,
             git::get_repo_structure,
             git::get_repo_diff_stats,
@@ -1562,22 +1741,35 @@ pub fn run() {
             git::git_stash_pop,
             git::git_stash_drop,
             git::git_stash_show,
+            git::get_config_value,
+            git::set_config_value,
+            git::lfs_status,
             git::get_file_history,
             git::get_file_blame,
+            git::get_blame_cache_stats,
+            git::find_repo_for_path,
             github::get_github_viewer_login,
             github::get_ci_checks,
+            github::get_pr_timeline,
+            github::hydrate_pr_check_details,
             github::get_repo_pr_statuses,
             github::get_all_pr_statuses,
             github::merge_pr_via_github,
             github::get_pr_diff,
             github::approve_pr,
+            github::request_reviewers,
+            github::add_pr_comment,
             github::fetch_ci_failure_logs,
             github::get_all_issues,
             github::close_issue,
             github::reopen_issue,
+            github::list_repo_issues,
+            github::get_issue,
+            github::add_issue_comment,
             github_poller::github_start_polling,
             github_poller::github_stop_polling,
             github_poller::github_set_visibility,
+            github_poller::set_poll_active,
             github_poller::github_poll_repo,
             github_poller::github_update_paths,
             github_poller::github_set_issue_filter,
@@ -1598,6 +1790,7 @@ pub fn run() {
             worktree::checkout_remote_branch,
             worktree::detect_orphan_worktrees,
             worktree::remove_orphan_worktree,
+            worktree::prune_orphaned_worktrees,
             worktree::run_setup_script,
             clear_caches,
             clear_repo_caches,
@@ -1605,6 +1798,8 @@ pub fn run() {
             get_local_ips,
             updater::check_update_channel,
             get_mcp_status,
+            remote_diagnosis::diagnose_remote_access,
+            diagnostics::run_diagnostics,
             deep_link_mcp_call,
             get_connect_url,
             regenerate_session_token,
@@ -1629,10 +1824,14 @@ pub fn run() {
             global_hotkey::get_global_hotkey,
             config::load_app_config,
             config::save_app_config,
+            config::take_config_recovery_notices,
             config::load_notification_config,
             config::save_notification_config,
+            notification_throttle::notification_allowed,
             config::load_ui_prefs,
             config::save_ui_prefs,
+            config::get_recent_files,
+            config::record_recent_file,
             config::load_repo_settings,
             config::save_repo_settings,
             config::set_branch_label,
@@ -1649,10 +1848,14 @@ pub fn run() {
             mcp_oauth::commands::mcp_oauth_callback,
             mcp_oauth::commands::cancel_mcp_upstream_oauth,
             config::check_has_custom_settings,
+            transcript::list_agent_transcripts,
+            transcript::prune_agent_transcripts,
+            config::reset_repo_settings,
             config::load_repo_defaults,
             config::save_repo_defaults,
             config::load_repositories,
             config::save_repositories,
+            config::reorder_repositories,
             config::load_pane_layout,
             config::save_pane_layout,
             config::load_prompt_library,
@@ -1669,6 +1872,8 @@ pub fn run() {
             config::save_activity,
             config::load_keybindings,
             config::save_keybindings,
+            config::export_keybindings,
+            config::import_keybindings,
             config::load_agents_config,
             config::save_agents_config,
             agent_hook_commands::set_agent_hook_instrumentation,
@@ -1678,6 +1883,9 @@ pub fn run() {
             agent_mcp::remove_agent_mcp,
             agent_mcp::get_agent_config_path,
             agent_mcp::get_mcp_bridge_info,
+            agent_mcp::generate_mcp_config_snippet,
+            agent_mcp::preview_mcp_registration,
+            agent_mcp::list_mcp_servers,
             prompt::extract_prompt_variables,
             prompt::process_prompt_content,
             prompt::process_prompt_content_shell_safe,
@@ -1725,6 +1933,8 @@ pub fn run() {
             repo_watcher::start_repo_watcher,
             repo_watcher::stop_repo_watcher,
             repo_watcher::set_hot_repos,
+            repo_watcher::get_active_selection,
+            repo_watcher::set_active_selection,
             dir_watcher::start_dir_watcher,
             dir_watcher::stop_dir_watcher,
             sleep_prevention::block_sleep,
@@ -1737,6 +1947,8 @@ pub fn run() {
             fs::search_content,
             fs::search_content_all,
             fs::fs_read_file,
+            fs::fs_read_file_info,
+            fs::diff_files,
             fs::write_file,
             fs::create_directory,
             fs::delete_path,
@@ -1767,6 +1979,7 @@ pub fn run() {
             plugin_http::plugin_http_fetch,
             plugin_pty::plugin_read_session_output,
             plugin_exec::plugin_exec_cli,
+            plugins::plugin_emit_event,
             plugin_credentials::plugin_read_credential,
             registry::fetch_plugin_registry,
             claude_usage::get_claude_usage_api,
@@ -1777,6 +1990,7 @@ pub fn run() {
             app_logger::push_log,
             app_logger::get_logs,
             app_logger::clear_logs,
+            app_logger::get_log_path,
             notification_sound::play_notification_sound,
             notification_sound::list_audio_output_devices,
             git_graph::get_commit_graph,
@@ -1855,6 +2069,8 @@ pub fn run() {
                     if let Some(state) = app_handle.try_state::<Arc<AppState>>() {
                         state.tunnel_manager.shutdown_all();
                         crate::ai_agent::knowledge::flush_dirty(state.inner());
+                        // Changes since the last periodic flush would otherwise be lost.
+                        state.flush_session_registry();
                     }
                 }
                 _ => {}
@@ -1876,6 +2092,7 @@ fn build_connect_url(scheme: &str, host: &str, port: u16, token: &str) -> String
 /// Spawn background tasks shared by both desktop and headless modes.
 fn spawn_background_tasks(state: &Arc<AppState>) {
     AppState::spawn_session_state_accumulator(state.clone());
+    AppState::spawn_session_registry_persister(state.clone());
     mcp_http::mcp_transport::spawn_tool_search_index_updater(state.clone());
     pty::spawn_tombstone_sweeper(state.clone());
     content_index::spawn_content_index_updater(state.clone());
@@ -1986,6 +2203,8 @@ pub async fn run_headless(port: u16) -> anyhow::Result<()> {
     let mut app_state = AppState::new(data_dir, worktrees_dir, app_config.clone(), log_buffer);
     *app_state.github_token.get_mut() = github_token;
     *app_state.github_token_source.get_mut() = github_token_source;
+    *app_state.active_selection.get_mut() = crate::config::load_ui_prefs().active_selection;
+    app_state.load_recoverable_sessions();
 
     let state = Arc::new(app_state);
     state.wire_event_bus();
@@ -1994,28 +2213,9 @@ pub async fn run_headless(port: u16) -> anyhow::Result<()> {
 
     agent_mcp::ensure_mcp_configs(&app_config.disabled_mcp_agents);
 
-    let tls_config = match &app_config.services.tls {
-        config::TlsConfig::Manual {
-            cert_path,
-            key_path,
-        } => {
-            let cert_pem = std::fs::read(cert_path)
-                .map_err(|e| anyhow::anyhow!("Failed to read TLS cert at {cert_path}: {e}"))?;
-            let key_pem = std::fs::read(key_path)
-                .map_err(|e| anyhow::anyhow!("Failed to read TLS key at {key_path}: {e}"))?;
-            let tls = axum_server::tls_rustls::RustlsConfig::from_pem(cert_pem, key_pem)
-                .await
-                .map_err(|e| anyhow::anyhow!("Invalid TLS cert/key: {e}"))?;
-            tracing::info!(
-                source = "remote",
-                cert_path,
-                key_path,
-                "TLS loaded (manual mode)"
-            );
-            Some(tls)
-        }
-        config::TlsConfig::Off => None,
-    };
+    let tls_config = load_manual_tls(&app_config.services.tls)
+        .await
+        .map_err(anyhow::Error::msg)?;
 
     tracing::info!(
         source = "remote",
@@ -2053,13 +2253,16 @@ pub async fn run_headless(port: u16) -> anyhow::Result<()> {
             if !tcp_bound {
                 anyhow::bail!("Fatal: failed to bind TCP on port {port} — cannot serve in headless mode");
             }
+            // Only a shutdown ends start_server: remote access was auto-disabled
+            // and `restart_local_only` is serving the local listeners. Keep them up.
+            tracing::warn!(source = "remote", "Remote access disabled — serving local listeners only");
+            let _ = tokio::signal::ctrl_c().await;
         }
-        _ = tokio::signal::ctrl_c() => {
-            tracing::info!(source = "remote", "Received shutdown signal");
-            if let Some(tx) = state.server_shutdown.lock().take() {
-                let _ = tx.send(());
-            }
-        }
+        _ = tokio::signal::ctrl_c() => {}
+    }
+    tracing::info!(source = "remote", "Received shutdown signal");
+    if let Some(tx) = state.server_shutdown.lock().take() {
+        let _ = tx.send(());
     }
 
     Ok(())
@@ -2115,6 +2318,7 @@ pub async fn run_remote(port: u16) -> anyhow::Result<()> {
     let mut app_state = AppState::new(data_dir, worktrees_dir, app_config.clone(), log_buffer);
     *app_state.github_token.get_mut() = github_token;
     *app_state.github_token_source.get_mut() = github_token_source;
+    *app_state.active_selection.get_mut() = crate::config::load_ui_prefs().active_selection;
 
     let state = Arc::new(app_state);
     state.wire_event_bus();
@@ -2124,28 +2328,9 @@ pub async fn run_remote(port: u16) -> anyhow::Result<()> {
     AppState::spawn_session_state_accumulator(state.clone());
     pty::spawn_tombstone_sweeper(state.clone());
 
-    let tls_config = match &app_config.services.tls {
-        config::TlsConfig::Manual {
-            cert_path,
-            key_path,
-        } => {
-            let cert_pem = std::fs::read(cert_path)
-                .map_err(|e| anyhow::anyhow!("Failed to read TLS cert at {cert_path}: {e}"))?;
-            let key_pem = std::fs::read(key_path)
-                .map_err(|e| anyhow::anyhow!("Failed to read TLS key at {key_path}: {e}"))?;
-            let tls = axum_server::tls_rustls::RustlsConfig::from_pem(cert_pem, key_pem)
-                .await
-                .map_err(|e| anyhow::anyhow!("Invalid TLS cert/key: {e}"))?;
-            tracing::info!(
-                source = "remote",
-                cert_path,
-                key_path,
-                "TLS loaded (manual mode)"
-            );
-            Some(tls)
-        }
-        config::TlsConfig::Off => None,
-    };
+    let tls_config = load_manual_tls(&app_config.services.tls)
+        .await
+        .map_err(anyhow::Error::msg)?;
 
     const PROTOCOL_VERSION: u32 = 1;
     tracing::info!(
@@ -2199,6 +2384,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_bind_address_rejects_address_not_on_machine() {
+        let local = vec!["192.168.1.20".to_string(), "100.101.102.103".to_string()];
+        let err = validate_bind_address("10.255.255.1", &local).unwrap_err();
+        assert!(err.contains("not assigned"), "unexpected error: {err}");
+        assert!(validate_bind_address("not-an-ip", &local).is_err());
+    }
+
+    fn ip_entry(ip: &str, interface: &str) -> LocalIpEntry {
+        LocalIpEntry {
+            ip: ip.to_string(),
+            label: String::new(),
+            interface: interface.to_string(),
+        }
+    }
+
+    #[test]
+    fn resolve_bind_interface_prefers_ipv4_then_ipv6() {
+        let local = vec![
+            ip_entry("192.168.1.20", "en0"),
+            ip_entry("fd7a:115c:a1e0::1", "tailscale0"),
+            ip_entry("100.101.102.103", "tailscale0"),
+            ip_entry("fd7a:115c:a1e0::2", "utun4"),
+        ];
+        assert_eq!(
+            resolve_bind_interface("tailscale0", &local, true).unwrap(),
+            "100.101.102.103".parse::<std::net::IpAddr>().unwrap()
+        );
+        // IPv6-only interface: usable only with ipv6_enabled.
+        assert_eq!(
+            resolve_bind_interface("utun4", &local, true).unwrap(),
+            "fd7a:115c:a1e0::2".parse::<std::net::IpAddr>().unwrap()
+        );
+        assert!(resolve_bind_interface("utun4", &local, false).is_err());
+        let err = resolve_bind_interface("eth9", &local, true).unwrap_err();
+        assert!(
+            err.contains("en0, tailscale0, utun4"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn validate_bind_address_accepts_local_ip_and_wildcard() {
+        let local = vec!["192.168.1.20".to_string(), "100.101.102.103".to_string()];
+        assert_eq!(
+            validate_bind_address("100.101.102.103", &local).unwrap(),
+            Some("100.101.102.103".parse().unwrap())
+        );
+        assert_eq!(
+            validate_bind_address("127.0.0.1", &local).unwrap(),
+            Some("127.0.0.1".parse().unwrap())
+        );
+        assert_eq!(validate_bind_address("0.0.0.0", &local).unwrap(), None);
+        assert_eq!(validate_bind_address("", &local).unwrap(), None);
+    }
+
     #[test]
     fn read_text_file_guarded_reads_small_file() {
         let dir = tempfile::tempdir().unwrap();
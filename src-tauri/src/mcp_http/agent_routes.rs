@@ -1,6 +1,6 @@
 use crate::pty::spawn_reader_thread;
 use crate::state::{OUTPUT_RING_BUFFER_CAPACITY, VT_LOG_BUFFER_CAPACITY, VtLogBuffer};
-use crate::{AppState, MAX_CONCURRENT_SESSIONS, OutputRingBuffer, PtySession};
+use crate::{AppState, OutputRingBuffer, PtySession};
 use axum::extract::{ConnectInfo, Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
@@ -18,13 +18,13 @@ use super::guards::{Authenticated, require_local_or_auth};
 use super::types::*;
 
 pub(super) async fn detect_agents() -> impl IntoResponse {
-    let known_agents = ["claude", "codex", "aider", "goose"];
-    let results: Vec<serde_json::Value> = known_agents
+    let results: Vec<serde_json::Value> = crate::agent::KNOWN_AGENTS
         .iter()
-        .map(|name| {
-            let detection = crate::agent::detect_agent_binary(name.to_string());
+        .map(|agent| {
+            let detection = crate::agent::detect_agent_binary(agent.binary.to_string());
             serde_json::json!({
-                "name": name,
+                "name": agent.name,
+                "binary": agent.binary,
                 "path": detection.path,
                 "version": detection.version,
             })
@@ -34,8 +34,7 @@ pub(super) async fn detect_agents() -> impl IntoResponse {
 }
 
 pub(super) async fn detect_agent_binary_http(Query(q): Query<DetectBinaryQuery>) -> Response {
-    const KNOWN_AGENTS: &[&str] = &["claude", "codex", "aider", "goose"];
-    if !KNOWN_AGENTS.contains(&q.binary.as_str()) {
+    if crate::agent::known_agent_by_binary(&q.binary).is_none() {
         return Json(serde_json::json!({"error": "Unknown agent"})).into_response();
     }
     let detection = crate::agent::detect_agent_binary(q.binary);
@@ -50,13 +49,20 @@ pub(super) async fn detect_installed_ides_http() -> impl IntoResponse {
     Json(crate::agent::detect_installed_ides())
 }
 
-pub(super) async fn process_prompt_http(
-    Json(body): Json<ProcessPromptRequest>,
-) -> impl IntoResponse {
-    Json(crate::prompt::process_prompt_content(
+pub(super) async fn process_prompt_http(Json(body): Json<ProcessPromptRequest>) -> Response {
+    match crate::prompt::process_prompt_content(
         body.content,
         body.variables,
-    ))
+        body.prompt_id,
+        body.send,
+    ) {
+        Ok(text) => Json(text).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response(),
+    }
 }
 
 pub(super) async fn extract_prompt_variables_http(
@@ -234,10 +240,10 @@ pub(super) async fn spawn_agent_session(
     if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
         return resp.into_response();
     }
-    if state.sessions.len() >= MAX_CONCURRENT_SESSIONS {
+    if !state.can_spawn_session() {
         return (
             StatusCode::TOO_MANY_REQUESTS,
-            Json(serde_json::json!({"error": "Max concurrent sessions reached"})),
+            Json(serde_json::json!({"error": format!("Max concurrent sessions ({}) reached", state.max_sessions())})),
         )
             .into_response();
     }
@@ -263,15 +269,16 @@ pub(super) async fn spawn_agent_session(
         }
         path.clone()
     } else if let Some(ref agent_type) = body.agent_type {
-        let detection = crate::agent::detect_agent_binary(agent_type.clone());
+        let binary =
+            crate::agent::known_agent(agent_type).map_or(agent_type.as_str(), |a| a.binary);
+        let detection = crate::agent::detect_agent_binary(binary.to_string());
         match detection.path {
             Some(p) => p,
-            None => {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(serde_json::json!({"error": format!("Agent binary '{}' not found", agent_type)})),
-                ).into_response()
-            }
+            None => return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Agent binary '{}' not found", binary)})),
+            )
+                .into_response(),
         }
     } else {
         // Default to claude
@@ -296,6 +303,13 @@ pub(super) async fn spawn_agent_session(
         )
             .into_response();
     }
+    if let Err(msg) = crate::env_file::validate_spawn_env(&body.env) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": msg})),
+        )
+            .into_response();
+    }
     let session_id = Uuid::new_v4().to_string();
     let pty_system = native_pty_system();
 
@@ -322,24 +336,35 @@ pub(super) async fn spawn_agent_session(
             cmd.arg(arg);
         }
     } else {
-        if body.print_mode.unwrap_or(false) {
-            cmd.arg("--print");
-        }
-        if let Some(ref format) = body.output_format {
-            cmd.arg("--output-format");
-            cmd.arg(format);
-        }
-        if let Some(ref model) = body.model {
-            cmd.arg("--model");
-            cmd.arg(model);
-        }
-        cmd.arg(&body.prompt);
+        cmd.args(crate::agent::default_spawn_args(
+            body.agent_type.as_deref(),
+            &body.prompt,
+            body.model.as_deref(),
+            body.print_mode.unwrap_or(false),
+            body.output_format.as_deref(),
+        ));
     }
 
     if let Some(ref cwd) = body.cwd {
-        cmd.cwd(crate::cli::expand_tilde(cwd));
+        let cwd = crate::cli::expand_tilde(cwd);
+        match crate::env_file::load_repo_env_for_cwd(&cwd) {
+            Ok(vars) => crate::env_file::apply_env_vars(&mut cmd, &vars),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": e})),
+                )
+                    .into_response();
+            }
+        }
+        cmd.cwd(cwd);
+    }
+    // Per-spawn env wins over the repo env file
+    for (key, value) in &body.env {
+        cmd.env(key, value);
     }
 
+    let command_line = cmd.as_unix_command_line().ok();
     let child = match pair.slave.spawn_command(cmd) {
         Ok(c) => c,
         Err(e) => {
@@ -372,6 +397,15 @@ pub(super) async fn spawn_agent_session(
                 .into_response();
         }
     };
+    let transcript = crate::transcript::TranscriptSettings::from_config(&state.config.read());
+    let reader = crate::transcript::wrap_agent_reader(
+        reader,
+        transcript.as_ref(),
+        body.cwd.as_deref(),
+        &session_id,
+    );
+
+    crate::spawn_ledger::record_spawn(&state.data_dir, child.process_id(), &session_id);
 
     let paused = Arc::new(AtomicBool::new(false));
     state.sessions.insert(
@@ -385,9 +419,11 @@ pub(super) async fn spawn_agent_session(
             cwd: body.cwd.clone(),
             display_name: None,
             shell: binary_path.clone(),
+            token_usage: Default::default(),
         }),
     );
     state.assign_term_alias(&session_id);
+    state.record_session_opened(&session_id, command_line);
     state.metrics.total_spawned.fetch_add(1, Ordering::Relaxed);
     state
         .metrics
@@ -442,6 +478,40 @@ pub(super) async fn spawn_agent_session(
         .into_response()
 }
 
+/// List recorded agent transcripts. Transcripts hold full agent output, so
+/// remote callers must be authenticated.
+pub(super) async fn list_agent_transcripts_http(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    match tokio::task::spawn_blocking(crate::transcript::list_agent_transcripts).await {
+        Ok(r) => super::json_result(r),
+        Err(e) => super::err_500(&format!("Task failed: {e}")),
+    }
+}
+
+pub(super) async fn prune_agent_transcripts_http(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Json(body): Json<PruneAgentTranscriptsRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    let older_than_days = body.older_than_days;
+    match tokio::task::spawn_blocking(move || {
+        crate::transcript::prune_agent_transcripts(older_than_days)
+    })
+    .await
+    {
+        Ok(r) => super::json_result(r),
+        Err(e) => super::err_500(&format!("Task failed: {e}")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -517,4 +587,69 @@ mod tests {
                 .await;
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
+
+    fn spawn_request(env: serde_json::Value) -> SpawnAgentRequest {
+        serde_json::from_value(serde_json::json!({
+            "prompt": "",
+            "binary_path": "/bin/sh",
+            "args": ["-c", "echo \"value=$TUIC_TEST_INJECTED\""],
+            "env": env,
+        }))
+        .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn spawn_agent_session_injects_per_spawn_env() {
+        let state = super::super::tests::test_state();
+        let resp = spawn_agent_session(
+            State(state.clone()),
+            ConnectInfo(loopback()),
+            None,
+            Json(spawn_request(
+                serde_json::json!({ "TUIC_TEST_INJECTED": "from-request" }),
+            )),
+        )
+        .await;
+        if resp.status() != StatusCode::CREATED {
+            return; // PTY unavailable in CI — skip gracefully
+        }
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let sid = json["session_id"].as_str().unwrap().to_string();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let mut output = String::new();
+        while std::time::Instant::now() < deadline {
+            if let Some(ring) = state.output_buffers.get(&sid) {
+                let (bytes, _) = ring.lock().read_last(4096);
+                output = String::from_utf8_lossy(&bytes).to_string();
+            }
+            if output.contains("value=from-request") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert!(
+            output.contains("value=from-request"),
+            "injected var missing from output: {output:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn spawn_agent_session_rejects_denylisted_env() {
+        let state = super::super::tests::test_state();
+        let resp = spawn_agent_session(
+            State(state),
+            ConnectInfo(loopback()),
+            None,
+            Json(spawn_request(
+                serde_json::json!({ "LD_PRELOAD": "/tmp/x.so" }),
+            )),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
 }
@@ -6,6 +6,8 @@ use axum::response::{IntoResponse, Response};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::sync::Arc;
 
+use super::guards::AuthVia;
+
 /// Cookie name used to persist the session after successful Basic Auth.
 /// The browser sends cookies automatically in fetch() calls (unlike stored Basic Auth),
 /// which is why we need this: JS API calls would otherwise fail with 401 every time.
@@ -203,11 +205,13 @@ pub async fn basic_auth_middleware(
     // like any other address — otherwise any local process gets full access.
     #[cfg(feature = "desktop")]
     if addr.ip().is_loopback() {
+        req.extensions_mut().insert(AuthVia::Loopback);
         return next.run(req).await;
     }
 
     // LAN bypass: skip auth for private/RFC1918 addresses when configured
     if state.config.read().services.auth.lan_auth_bypass && is_private_ip(&addr.ip()) {
+        req.extensions_mut().insert(AuthVia::LanBypass);
         return next.run(req).await;
     }
 
@@ -227,6 +231,7 @@ pub async fn basic_auth_middleware(
 
     // Fast path: valid session cookie skips bcrypt entirely
     if has_valid_session_cookie(&req, &session_token) {
+        req.extensions_mut().insert(AuthVia::SessionCookie);
         return next.run(req).await;
     }
 
@@ -235,6 +240,7 @@ pub async fn basic_auth_middleware(
     // We set a session cookie so the SPA's subsequent fetch() calls are also authenticated.
     if has_valid_url_token(&req, &session_token) {
         state.auth_rate_limits.remove(&addr.ip());
+        req.extensions_mut().insert(AuthVia::UrlToken);
         let mut response = next.run(req).await;
         if let Ok(val) = session_cookie_value(&session_token, token_duration_secs, is_tls).parse() {
             response.headers_mut().insert(header::SET_COOKIE, val);
@@ -298,6 +304,7 @@ pub async fn basic_auth_middleware(
         AuthResult::Ok => {
             // Successful auth clears rate limit counter for this IP
             state.auth_rate_limits.remove(&client_ip);
+            req.extensions_mut().insert(AuthVia::BasicAuth);
             let mut response = next.run(req).await;
             if let Ok(val) =
                 session_cookie_value(&session_token, token_duration_secs, is_tls).parse()
@@ -315,6 +322,7 @@ pub async fn basic_auth_middleware(
         AuthResult::Invalid => {
             tracing::warn!(source = "auth", ip = %client_ip, "Failed auth attempt");
             record_auth_failure(&state.auth_rate_limits, client_ip, rate_window_secs);
+            check_remote_auto_disable(&state, client_ip, super::restart_local_only).await;
             (StatusCode::UNAUTHORIZED, "Invalid credentials").into_response()
         }
     }
@@ -341,10 +349,145 @@ fn record_auth_failure(
         .or_insert((1, now));
 }
 
+/// Track distinct failing IPs for `auto_disable_remote_after_failures`. When
+/// the threshold is reached within the window while remote access is on, turn
+/// it off in the config (persisted), emit `remote-auto-disabled` and call
+/// `restart` to bring the server back localhost-only. Returns `true` when
+/// remote access was disabled by this call.
+pub(super) async fn check_remote_auto_disable(
+    state: &Arc<AppState>,
+    ip: IpAddr,
+    restart: impl FnOnce(&Arc<AppState>),
+) -> bool {
+    let (threshold, window_secs) = {
+        let config = state.config.read();
+        (
+            config.services.auth.auto_disable_remote_after_failures,
+            config.services.auth.auto_disable_remote_window_secs,
+        )
+    };
+    if threshold == 0 {
+        return false;
+    }
+    let window = std::time::Duration::from_secs(window_secs);
+    let now = std::time::Instant::now();
+    state.auth_failure_ips.insert(ip, now);
+    state
+        .auth_failure_ips
+        .retain(|_, at| now.duration_since(*at) < window);
+    let distinct_ips = state.auth_failure_ips.len();
+    if distinct_ips < threshold as usize {
+        return false;
+    }
+
+    // Flip under the write lock so concurrent failures disable only once.
+    let snapshot = {
+        let mut config = state.config.write();
+        if !config.services.server.enabled {
+            return false;
+        }
+        config.services.server.enabled = false;
+        config.clone()
+    };
+    state.auth_failure_ips.clear();
+    tracing::error!(
+        source = "auth",
+        distinct_ips,
+        window_secs,
+        "Remote access auto-disabled after repeated auth failures"
+    );
+    let saved = tokio::task::spawn_blocking(move || crate::config::save_app_config(snapshot))
+        .await
+        .map_err(|e| format!("spawn_blocking error: {e}"))
+        .and_then(|r| r);
+    if let Err(e) = saved {
+        tracing::error!(source = "auth", "Failed to persist remote access off: {e}");
+    }
+
+    #[cfg(feature = "desktop")]
+    {
+        use tauri::Emitter;
+        if let Some(app) = state.app_handle.read().as_ref() {
+            let _ = app.emit(
+                "remote-auto-disabled",
+                serde_json::json!({ "distinct_ips": distinct_ips, "window_secs": window_secs }),
+            );
+        }
+    }
+    let _ = state
+        .event_bus
+        .send(crate::state::AppEvent::RemoteAutoDisabled {
+            distinct_ips,
+            window_secs,
+        });
+
+    restart(state);
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn auto_disable_remote_trips_at_distinct_ip_threshold() {
+        let tmp = tempfile::tempdir().unwrap();
+        let _guard = crate::config::set_config_dir_override(tmp.path().to_path_buf());
+        let state = Arc::new(crate::state::tests_support::make_test_app_state());
+        {
+            let mut config = state.config.write();
+            config.services.server.enabled = true;
+            config.services.auth.auto_disable_remote_after_failures = 3;
+        }
+        let mut rx = state.event_bus.subscribe();
+        let restarts = std::sync::atomic::AtomicUsize::new(0);
+        let restart = |_: &Arc<AppState>| {
+            restarts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        };
+        let ip = |n| IpAddr::V4(Ipv4Addr::new(203, 0, 113, n));
+
+        // Repeats from one IP count once
+        assert!(!check_remote_auto_disable(&state, ip(1), restart).await);
+        assert!(!check_remote_auto_disable(&state, ip(1), restart).await);
+        assert!(!check_remote_auto_disable(&state, ip(2), restart).await);
+        assert!(state.config.read().services.server.enabled);
+        assert_eq!(restarts.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        assert!(check_remote_auto_disable(&state, ip(3), restart).await);
+        assert!(!state.config.read().services.server.enabled);
+        assert_eq!(restarts.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(!crate::config::load_app_config().services.server.enabled);
+        match rx.try_recv() {
+            Ok(crate::state::AppEvent::RemoteAutoDisabled {
+                distinct_ips,
+                window_secs,
+            }) => {
+                assert_eq!(distinct_ips, 3);
+                assert_eq!(window_secs, 600);
+            }
+            other => panic!("expected RemoteAutoDisabled, got {other:?}"),
+        }
+
+        // Already off: further failures don't restart again
+        for n in 4..8 {
+            assert!(!check_remote_auto_disable(&state, ip(n), restart).await);
+        }
+        assert_eq!(restarts.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn auto_disable_remote_off_by_default() {
+        let state = Arc::new(crate::state::tests_support::make_test_app_state());
+        state.config.write().services.server.enabled = true;
+        for n in 0..50 {
+            let ip = IpAddr::V4(Ipv4Addr::new(203, 0, 113, n));
+            assert!(!check_remote_auto_disable(&state, ip, |_| panic!("must not restart")).await);
+        }
+        assert!(state.config.read().services.server.enabled);
+        assert!(state.auth_failure_ips.is_empty());
+    }
+
     // --- is_private_ip tests ---
 
     #[test]
@@ -1,4 +1,4 @@
-use crate::{AppState, MAX_CONCURRENT_SESSIONS};
+use crate::AppState;
 use axum::extract::{ConnectInfo, Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
@@ -128,12 +128,13 @@ pub(super) async fn get_notification_config() -> impl IntoResponse {
 pub(super) async fn put_notification_config(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     auth: Option<Extension<Authenticated>>,
+    State(state): State<Arc<AppState>>,
     Json(config): Json<crate::config::NotificationConfig>,
 ) -> impl IntoResponse {
     if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
         return resp;
     }
-    match crate::config::save_notification_config(config) {
+    match crate::config::save_notification_config_impl(&state, config) {
         Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -142,10 +143,55 @@ pub(super) async fn put_notification_config(
     }
 }
 
+/// Mirrors the `notification_allowed` command (desktop-channel throttle).
+pub(super) async fn notification_allowed_http(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<NotificationAllowedRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    let allowed = state.notification_throttle.allow_now(
+        crate::notification_throttle::NotificationChannel::Desktop,
+        body.session_id.as_deref().unwrap_or(""),
+        &body.sound,
+    );
+    Json(allowed).into_response()
+}
+
 pub(super) async fn get_ui_prefs() -> impl IntoResponse {
     Json(crate::config::load_ui_prefs())
 }
 
+pub(super) async fn get_active_selection(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    Json(state.active_selection.read().clone())
+}
+
+pub(super) async fn put_active_selection(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<SetActiveSelectionRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    if let Err(e) = validate_repo_path(&body.repo_path) {
+        return e.into_response();
+    }
+    match crate::repo_watcher::set_active_selection_impl(&state, body.repo_path, body.worktree_path)
+    {
+        Ok(()) => Json(serde_json::json!({"ok": true})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response(),
+    }
+}
+
 pub(super) async fn put_ui_prefs(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     auth: Option<Extension<Authenticated>>,
@@ -190,6 +236,39 @@ pub(super) async fn check_has_custom_settings_http(
     Json(crate::config::check_has_custom_settings(q.path))
 }
 
+pub(super) async fn reset_repo_settings_http(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Json(body): Json<ResetRepoSettingsRequest>,
+) -> axum::response::Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    if let Err(e) = validate_repo_path(&body.repo_path) {
+        return e.into_response();
+    }
+    json_result(crate::config::reset_repo_settings(
+        body.repo_path,
+        body.categories,
+    ))
+}
+
+/// Layered spawn environment for a repo; loopback or authenticated only,
+/// since it lists the host's inherited variables.
+pub(super) async fn explain_env_http(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Query(q): Query<PathQuery>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    if let Err(e) = validate_repo_path(&q.path) {
+        return e.into_response();
+    }
+    json_result(crate::agent::explain_env(q.path))
+}
+
 pub(super) async fn get_repositories() -> impl IntoResponse {
     Json(crate::config::load_repositories())
 }
@@ -211,6 +290,24 @@ pub(super) async fn put_repositories(
     }
 }
 
+pub(super) async fn reorder_repositories_http(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Json(body): Json<ReorderRepositoriesRequest>,
+) -> impl IntoResponse {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp;
+    }
+    match crate::config::reorder_repositories_impl(&state, body.order) {
+        Ok(config) => (StatusCode::OK, Json(config)),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e})),
+        ),
+    }
+}
+
 pub(super) async fn get_pane_layout() -> impl IntoResponse {
     Json(crate::config::load_pane_layout())
 }
@@ -265,8 +362,8 @@ pub(super) async fn get_repo_local_config(Query(q): Query<PathQuery>) -> impl In
     ))
 }
 
-pub(super) async fn get_prompt_library() -> impl IntoResponse {
-    Json(crate::config::load_prompt_library())
+pub(super) async fn get_prompt_library(Query(q): Query<PromptLibraryQuery>) -> impl IntoResponse {
+    Json(crate::config::load_prompt_library(q.sort))
 }
 
 pub(super) async fn put_prompt_library(
@@ -378,6 +475,30 @@ pub(super) async fn put_keybindings(
     }
 }
 
+pub(super) async fn export_keybindings_http(
+    Query(q): Query<KeybindingsExportQuery>,
+) -> impl IntoResponse {
+    json_result(crate::config::export_keybindings(q.include_ui_prefs))
+}
+
+pub(super) async fn import_keybindings_http(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Json(body): Json<KeybindingsImportRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    match crate::config::import_keybindings(body.json, body.known_actions) {
+        Ok(report) => (StatusCode::OK, Json(report)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response(),
+    }
+}
+
 // --- Agents Config ---
 
 pub(super) async fn get_agents_config() -> impl IntoResponse {
@@ -518,10 +639,16 @@ pub(super) async fn get_mcp_status_http(State(state): State<Arc<AppState>>) -> i
         "running": running,
         "active_sessions": state.sessions.len(),
         "mcp_clients": state.mcp_sessions.len(),
-        "max_sessions": MAX_CONCURRENT_SESSIONS,
+        "max_sessions": state.max_sessions(),
     }))
 }
 
+pub(super) async fn diagnose_remote_access_http(
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    json_result(crate::remote_diagnosis::diagnose_remote_access_impl(&state).await)
+}
+
 // ---------------------------------------------------------------------------
 // Remote connections
 // ---------------------------------------------------------------------------
@@ -75,14 +75,22 @@ pub(super) async fn start_dictation_http(
     }
 }
 
+#[derive(serde::Deserialize, Default)]
+pub(super) struct StopDictationRequest {
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
 pub(super) async fn stop_dictation_http(
     State(state): State<Arc<AppState>>,
+    body: Option<Json<StopDictationRequest>>,
 ) -> Response {
     let app_handle = state.app_handle.read().clone();
     let Some(app) = app_handle else {
         return (StatusCode::SERVICE_UNAVAILABLE, "App not initialized").into_response();
     };
-    match dictation::commands::stop_dictation_and_transcribe(app).await {
+    let language = body.map(|Json(b)| b).unwrap_or_default().language;
+    match dictation::commands::stop_dictation_and_transcribe(app, language).await {
         Ok(resp) => Json(resp).into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
     }
@@ -126,6 +134,8 @@ pub(super) async fn list_audio_devices_http() -> impl IntoResponse {
 #[derive(serde::Deserialize)]
 pub(super) struct InjectTextRequest {
     pub text: String,
+    #[serde(default)]
+    pub session_id: Option<String>,
 }
 
 pub(super) async fn inject_text_http(
@@ -137,7 +147,13 @@ pub(super) async fn inject_text_http(
         return (StatusCode::SERVICE_UNAVAILABLE, "App not initialized").into_response();
     };
     let dictation = app.state::<DictationState>();
-    match dictation::commands::inject_text(dictation, body.text) {
+    match dictation::commands::inject_text_core(
+        app,
+        &state,
+        &dictation,
+        &body.text,
+        body.session_id.as_deref(),
+    ) {
         Ok(text) => Json(serde_json::json!({"text": text})).into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
     }
@@ -113,6 +113,22 @@ pub(super) async fn fs_read_file_http(Query(q): Query<FsFileQuery>) -> Response
     json_result(crate::fs::fs_read_file(q.repo_path, q.file))
 }
 
+/// Repo file read that flags Git LFS pointer files.
+pub(super) async fn fs_read_file_info_http(Query(q): Query<FsFileQuery>) -> Response {
+    if let Err(e) = validate_repo_path(&q.repo_path) {
+        return e.into_response();
+    }
+    json_result(crate::fs::fs_read_file_info(q.repo_path, q.file))
+}
+
+/// Line diff of two repo files, independent of git.
+pub(super) async fn diff_files_http(Query(q): Query<FsDiffQuery>) -> Response {
+    if let Err(e) = validate_repo_path(&q.repo_path) {
+        return e.into_response();
+    }
+    json_result(crate::fs::diff_files(q.repo_path, q.file_a, q.file_b))
+}
+
 /// Repo file read for the code editor, at the larger `MAX_EDITOR_LARGE_FILE_SIZE` cap.
 pub(super) async fn read_editor_file_http(Query(q): Query<FsFileQuery>) -> Response {
     if let Err(e) = validate_repo_path(&q.repo_path) {
@@ -127,17 +143,13 @@ pub(super) async fn read_editor_file_http(Query(q): Query<FsFileQuery>) -> Respo
 
 /// Check if a path falls within any of the given repository roots.
 /// Uses Path::starts_with for component-level matching (not string prefix).
-fn is_within_repo_roots(path: &std::path::Path, roots: &[String]) -> bool {
+pub(super) fn is_within_repo_roots(path: &std::path::Path, roots: &[String]) -> bool {
     roots.iter().any(|root| path.starts_with(root))
 }
 
 /// Extract registered repository root paths from the opaque repos JSON.
 fn registered_repo_roots() -> Vec<String> {
-    crate::config::load_repositories()
-        .get("repositories")
-        .and_then(|r| r.as_object())
-        .map(|obj| obj.keys().cloned().collect())
-        .unwrap_or_default()
+    crate::config::registered_repo_paths()
 }
 
 pub(super) async fn read_external_file_http(Query(q): Query<FsExternalFileQuery>) -> Response {
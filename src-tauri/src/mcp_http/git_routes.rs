@@ -1,8 +1,10 @@
-use axum::Json;
-use axum::extract::Query;
+use axum::extract::{ConnectInfo, Query};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use std::net::SocketAddr;
 
+use super::guards::{Authenticated, require_local_or_auth};
 use super::types::*;
 use super::{err_500, json_result, validate_repo_path};
 
@@ -32,23 +34,21 @@ pub(super) async fn repo_diff(Query(q): Query<PathQuery>) -> Response {
     }
 }
 
-pub(super) async fn repo_diff_stats(Query(q): Query<PathQuery>) -> Response {
+pub(super) async fn repo_diff_stats(Query(q): Query<DiffStatsQuery>) -> Response {
     if let Err(e) = validate_repo_path(&q.path) {
         return e.into_response();
     }
-    let path = q.path;
-    match crate::git::get_diff_stats(path, None).await {
+    match crate::git::get_diff_stats(q.path, q.scope, q.per_file).await {
         Ok(stats) => Json(stats).into_response(),
         Err(e) => err_500(&e),
     }
 }
 
-pub(super) async fn repo_changed_files(Query(q): Query<PathQuery>) -> Response {
+pub(super) async fn repo_changed_files(Query(q): Query<ChangedFilesQuery>) -> Response {
     if let Err(e) = validate_repo_path(&q.path) {
         return e.into_response();
     }
-    let path = q.path;
-    json_result(crate::git::get_changed_files(path, None).await)
+    json_result(crate::git::get_changed_files(q.path, q.scope, q.base, q.head).await)
 }
 
 pub(super) async fn repo_branches(Query(q): Query<PathQuery>) -> Response {
@@ -88,13 +88,43 @@ pub(super) async fn read_file_http(Query(q): Query<FileQuery>) -> Response {
     }
     let path = q.path;
     let file = q.file;
-    match tokio::task::spawn_blocking(move || crate::read_file_impl(path, file)).await {
+    match tokio::task::spawn_blocking(move || crate::read_file(path, file)).await {
         Ok(Ok(content)) => (StatusCode::OK, Json(serde_json::json!(content))).into_response(),
         Ok(Err(e)) => err_500(&e),
         Err(e) => err_500(&format!("Task failed: {e}")),
     }
 }
 
+pub(super) async fn recent_files_http(Query(q): Query<PathQuery>) -> Response {
+    if let Err(e) = validate_repo_path(&q.path) {
+        return e.into_response();
+    }
+    let path = q.path;
+    match tokio::task::spawn_blocking(move || crate::config::get_recent_files(path)).await {
+        Ok(files) => (StatusCode::OK, Json(serde_json::json!(files))).into_response(),
+        Err(e) => err_500(&format!("Task failed: {e}")),
+    }
+}
+
+pub(super) async fn record_recent_file_http(Json(body): Json<RecordRecentFileRequest>) -> Response {
+    if let Err(e) = validate_repo_path(&body.path) {
+        return e.into_response();
+    }
+    match tokio::task::spawn_blocking(move || {
+        crate::config::record_recent_file(body.path, body.file)
+    })
+    .await
+    {
+        Ok(Ok(())) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))).into_response(),
+        Ok(Err(e)) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response(),
+        Err(e) => err_500(&format!("Task failed: {e}")),
+    }
+}
+
 pub(super) async fn rename_branch_http(Json(body): Json<RenameBranchRequest>) -> Response {
     if let Err(e) = validate_repo_path(&body.path) {
         return e.into_response();
@@ -184,6 +214,43 @@ pub(super) async fn repo_merged_branches(
     }
 }
 
+pub(super) async fn repo_stats(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
+    Query(q): Query<RepoStatsQuery>,
+) -> Response {
+    if let Err(e) = validate_repo_path(&q.path) {
+        return e.into_response();
+    }
+    let extra_ignores: Vec<String> = q
+        .extra_ignores
+        .as_deref()
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+    // Same cache as the Tauri command for the default ignore set.
+    match crate::git::load_repo_stats(
+        state.git_cache.repo_stats.clone(),
+        q.path,
+        extra_ignores,
+        q.count_lines,
+    )
+    .await
+    {
+        Ok(stats) => (StatusCode::OK, Json(serde_json::json!(stats))).into_response(),
+        Err(e) => err_500(&e),
+    }
+}
+
+pub(super) async fn scan_for_repos_http(Query(q): Query<ScanReposQuery>) -> Response {
+    if let Err(e) = validate_repo_path(&q.root_dir) {
+        return e.into_response();
+    }
+    json_result(crate::git::scan_for_repos(q.root_dir, q.max_depth).await)
+}
+
 pub(super) async fn get_local_ip_http(
     axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
 ) -> impl axum::response::IntoResponse {
@@ -282,41 +349,21 @@ pub(super) async fn run_git_command_http(
             .into_response();
     }
 
-    let path = body.path;
-    let args = body.args;
-    let state_clone = state.clone();
+    let RunGitCommandRequest {
+        path,
+        args,
+        autostash,
+    } = body;
     match tokio::task::spawn_blocking(move || {
-        let repo_path = std::path::PathBuf::from(&path);
-        let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        let mut builder = crate::git_cli::git_cmd(&repo_path).args(&args_str);
-        if let Some(ref askpass_path) = crate::git::ensure_askpass_script() {
-            let askpass_str = askpass_path.to_string_lossy();
-            builder = builder
-                .env("SSH_ASKPASS", &askpass_str)
-                .env("SSH_ASKPASS_REQUIRE", "prefer")
-                .env("DISPLAY", ":0");
-        }
-        match builder.run_raw() {
-            Ok(o) => {
-                let success = o.status.success();
-                let result = crate::git::GitCommandResult {
-                    success,
-                    stdout: String::from_utf8_lossy(&o.stdout).to_string(),
-                    stderr: String::from_utf8_lossy(&o.stderr).to_string(),
-                    exit_code: o.status.code().unwrap_or(-1),
-                };
-                if success {
-                    state_clone.invalidate_repo_caches(&path);
-                }
-                Ok(result)
-            }
-            Err(e) => Err(format!("git command failed: {e}")),
+        let result = crate::git::run_git_command_impl(&path, &args, autostash);
+        if result.success {
+            state.invalidate_repo_caches(&path);
         }
+        result
     })
     .await
     {
-        Ok(Ok(result)) => Json(result).into_response(),
-        Ok(Err(e)) => err_500(&e),
+        Ok(result) => Json(result).into_response(),
         Err(e) => err_500(&format!("Task failed: {e}")),
     }
 }
@@ -384,12 +431,31 @@ pub(super) async fn git_commit_http(Json(body): Json<CommitRequest>) -> Response
     if let Err(e) = validate_repo_path(&body.path) {
         return e.into_response();
     }
-    let path = body.path;
-    let message = body.message;
-    let amend = body.amend;
-    match crate::git::git_commit(path, message, amend).await {
-        Ok(hash) => Json(serde_json::json!(hash)).into_response(),
-        Err(e) => err_500(&e),
+    let result = tokio::task::spawn_blocking(move || {
+        crate::git::commit_impl(
+            std::path::Path::new(&body.path),
+            &body.message,
+            &body.files,
+            body.all,
+            body.amend.unwrap_or(false),
+        )
+    })
+    .await;
+    match result {
+        Ok(Ok(hash)) => Json(serde_json::json!(hash)).into_response(),
+        Ok(Err(e)) => {
+            use crate::git::CommitError;
+            let status = match e {
+                CommitError::EmptyMessage => StatusCode::BAD_REQUEST,
+                CommitError::NothingToCommit => StatusCode::CONFLICT,
+                CommitError::HookFailed { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                CommitError::Git { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            let mut body = serde_json::json!(e);
+            body["error"] = serde_json::json!(e.to_string());
+            (status, Json(body)).into_response()
+        }
+        Err(e) => err_500(&e.to_string()),
     }
 }
 
@@ -453,6 +519,53 @@ pub(super) async fn stash_drop_http(Json(body): Json<StashRefRequest>) -> Respon
     }
 }
 
+pub(super) async fn lfs_status_http(Query(q): Query<PathQuery>) -> Response {
+    if let Err(e) = validate_repo_path(&q.path) {
+        return e.into_response();
+    }
+    match crate::git::lfs_status(q.path).await {
+        Ok(status) => Json(status).into_response(),
+        Err(e) => err_500(&e),
+    }
+}
+
+pub(super) async fn get_config_value_http(Query(q): Query<GitConfigQuery>) -> Response {
+    if let Err(e) = validate_repo_path(&q.path) {
+        return e.into_response();
+    }
+    match crate::git::get_config_value(q.path, q.key).await {
+        Ok(value) => Json(serde_json::json!(value)).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response(),
+    }
+}
+
+/// Writes can change the identity commits are made with (or global config),
+/// so remote callers must be authenticated.
+pub(super) async fn set_config_value_http(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Json(body): Json<SetGitConfigRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    if let Err(e) = validate_repo_path(&body.path) {
+        return e.into_response();
+    }
+    match crate::git::set_config_value(body.path, body.key, body.value, body.scope).await {
+        Ok(()) => Json(serde_json::json!({"ok": true})).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response(),
+    }
+}
+
 pub(super) async fn stash_show_http(Query(q): Query<StashRefRequest>) -> Response {
     if let Err(e) = validate_repo_path(&q.path) {
         return e.into_response();
@@ -483,14 +596,23 @@ pub(super) async fn file_blame_http(Query(q): Query<FileBlameQuery>) -> Response
     if let Err(e) = validate_repo_path(&q.path) {
         return e.into_response();
     }
-    let path = q.path;
-    let file = q.file;
-    match crate::git::get_file_blame(path, file).await {
+    match crate::git::get_file_blame(q.path, q.file, q.start_line, q.end_line).await {
         Ok(lines) => Json(serde_json::json!(lines)).into_response(),
         Err(e) => err_500(&e),
     }
 }
 
+pub(super) async fn blame_cache_stats_http() -> Response {
+    Json(crate::git::get_blame_cache_stats()).into_response()
+}
+
+pub(super) async fn find_repo_for_path_http(Query(q): Query<PathQuery>) -> Response {
+    match tokio::task::spawn_blocking(move || crate::git::find_repo_for_path(q.path)).await {
+        Ok(found) => Json(found).into_response(),
+        Err(e) => err_500(&e.to_string()),
+    }
+}
+
 // --- Git panel (story 064; browser/remote parity) ---
 // Reads call the cfg_attr commands / *_impl fns directly; mutations call the
 // non-gated *_impl + invalidate_repo_caches (mirroring the desktop wrappers).
@@ -504,6 +626,12 @@ pub(super) async fn get_gutter_changes_http(Query(q): Query<GitGutterQuery>) ->
     json_result(crate::git::get_gutter_changes(q.path, q.file, q.scope).await)
 }
 
+pub(super) async fn parse_conflict_markers_http(
+    Json(body): Json<ConflictMarkersRequest>,
+) -> Response {
+    Json(crate::git::parse_conflict_markers(body.content)).into_response()
+}
+
 pub(super) async fn get_branches_detail_http(
     axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
     Query(q): Query<PathQuery>,
@@ -601,6 +729,73 @@ pub(super) async fn create_branch_http(
     }
 }
 
+/// Checkout moves HEAD and may autostash local changes, so remote callers
+/// must be authenticated.
+pub(super) async fn checkout_branch_http(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
+    Json(body): Json<GitCheckoutBranchRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    if let Err(e) = validate_repo_path(&body.path) {
+        return e.into_response();
+    }
+    let GitCheckoutBranchRequest {
+        path,
+        branch,
+        autostash,
+    } = body;
+    let res = tokio::task::spawn_blocking(move || {
+        let r = crate::git::checkout_branch_impl(&path, &branch, autostash)?;
+        state.invalidate_repo_caches(&path);
+        Ok::<_, String>(r)
+    })
+    .await;
+    match res {
+        Ok(r) => json_result(r),
+        Err(e) => err_500(&format!("Task failed: {e}")),
+    }
+}
+
+/// Stash subactions (`save`, `list`, `pop`, `drop`). Everything except `list`
+/// rewrites the working tree, so remote callers must be authenticated.
+pub(super) async fn stash_http(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
+    Json(body): Json<GitStashRequest>,
+) -> Response {
+    if body.action != "list"
+        && let Err(resp) = require_local_or_auth(&addr, auth.is_some())
+    {
+        return resp.into_response();
+    }
+    if let Err(e) = validate_repo_path(&body.path) {
+        return e.into_response();
+    }
+    let GitStashRequest {
+        path,
+        action,
+        message,
+        index,
+    } = body;
+    let res = tokio::task::spawn_blocking(move || {
+        let r = crate::git::stash_action_impl(&path, &action, message.as_deref(), index)?;
+        if action != "list" {
+            state.invalidate_repo_caches(&path);
+        }
+        Ok::<_, String>(r)
+    })
+    .await;
+    match res {
+        Ok(r) => json_result(r),
+        Err(e) => err_500(&format!("Task failed: {e}")),
+    }
+}
+
 pub(super) async fn delete_branch_http(
     axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
     Json(body): Json<GitDeleteBranchRequest>,
@@ -621,6 +816,69 @@ pub(super) async fn delete_branch_http(
     }
 }
 
+/// Run a blocking integration op for an HTTP handler, invalidating caches after.
+async fn integration_http<T: serde::Serialize + Send + 'static>(
+    state: std::sync::Arc<crate::AppState>,
+    path: String,
+    f: impl FnOnce(&str) -> Result<T, String> + Send + 'static,
+) -> Response {
+    if let Err(e) = validate_repo_path(&path) {
+        return e.into_response();
+    }
+    let res = tokio::task::spawn_blocking(move || {
+        let r = f(&path);
+        state.invalidate_repo_caches(&path);
+        r
+    })
+    .await;
+    match res {
+        Ok(r) => json_result(r),
+        Err(e) => err_500(&format!("Task failed: {e}")),
+    }
+}
+
+pub(super) async fn rebase_branch_http(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
+    Json(body): Json<GitRebaseRequest>,
+) -> Response {
+    let GitRebaseRequest { path, onto } = body;
+    integration_http(state, path, move |p| {
+        crate::git::rebase_branch_impl(p, &onto)
+    })
+    .await
+}
+
+pub(super) async fn abort_rebase_http(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
+    Json(body): Json<PathQuery>,
+) -> Response {
+    integration_http(state, body.path, |p| {
+        crate::git::abort_integration_impl(p, crate::git::IntegrationOp::Rebase)
+    })
+    .await
+}
+
+pub(super) async fn cherry_pick_http(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
+    Json(body): Json<GitCherryPickRequest>,
+) -> Response {
+    let GitCherryPickRequest { path, commit_sha } = body;
+    integration_http(state, path, move |p| {
+        crate::git::cherry_pick_impl(p, &commit_sha)
+    })
+    .await
+}
+
+pub(super) async fn abort_cherry_pick_http(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
+    Json(body): Json<PathQuery>,
+) -> Response {
+    integration_http(state, body.path, |p| {
+        crate::git::abort_integration_impl(p, crate::git::IntegrationOp::CherryPick)
+    })
+    .await
+}
+
 pub(super) async fn delete_local_branch_http(
     axum::extract::State(state): axum::extract::State<std::sync::Arc<crate::AppState>>,
     Json(body): Json<GitDeleteLocalBranchRequest>,
@@ -1,23 +1,39 @@
-use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::{ConnectInfo, Query, State};
 use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use std::net::SocketAddr;
 use std::sync::Arc;
 
+use super::guards::{Authenticated, require_local_or_auth};
 use super::types::{
-    CiChecksQuery, CiFailureLogsQuery, GithubPollLoginRequest, GithubSetHideDraftsRequest,
-    IssueActionRequest, IssuesQuery, PathQuery, PollRepoRequest, PrDiffQuery, SetVisibilityRequest,
-    StartPollingRequest, UpdatePathsRequest,
+    AddIssueCommentRequest, AddPrCommentRequest, CiChecksQuery, CiFailureLogsQuery,
+    GithubPollLoginRequest, GithubSetHideDraftsRequest, IssueActionRequest, IssueQuery,
+    IssuesQuery, ListIssuesQuery, PathQuery, PollRepoRequest, PrDiffQuery, RequestReviewersRequest,
+    SetVisibilityRequest, StartPollingRequest, UpdatePathsRequest,
 };
 use super::{err_500, json_result, validate_repo_path};
 use crate::github_poller::PollerCmd;
 use crate::state::AppState;
 
-pub(super) async fn repo_github_status(Query(q): Query<PathQuery>) -> Response {
+fn bad_request(msg: &str) -> Response {
+    (
+        axum::http::StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({"error": msg})),
+    )
+        .into_response()
+}
+
+pub(super) async fn repo_github_status(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<PathQuery>,
+) -> Response {
     if let Err(e) = validate_repo_path(&q.path) {
         return e.into_response();
     }
     let path = q.path;
-    match tokio::task::spawn_blocking(move || crate::github::get_github_status_impl(&path)).await {
+    match tokio::task::spawn_blocking(move || crate::github::get_github_status_impl(&state, &path))
+        .await
+    {
         Ok(status) => Json(status).into_response(),
         Err(e) => err_500(&format!("Task failed: {e}")),
     }
@@ -64,6 +80,32 @@ pub(super) async fn repo_ci_checks(
     Json(crate::github::get_ci_checks_impl(&path, pr_number, &state).await).into_response()
 }
 
+pub(super) async fn repo_pr_timeline(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<CiChecksQuery>,
+) -> Response {
+    if let Err(e) = validate_repo_path(&q.path) {
+        return e.into_response();
+    }
+    match crate::github::get_pr_timeline_impl(&q.path, q.pr_number, &state).await {
+        Ok(events) => Json(events).into_response(),
+        Err(e) => err_500(&e),
+    }
+}
+
+pub(super) async fn repo_hydrate_pr_checks(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<super::types::HydratePrCheckDetailsRequest>,
+) -> Response {
+    if let Err(e) = validate_repo_path(&body.path) {
+        return e.into_response();
+    }
+    match crate::github::hydrate_pr_check_details_impl(&body.path, &body.pr_numbers, &state).await {
+        Ok(details) => Json(details).into_response(),
+        Err(e) => err_500(&e),
+    }
+}
+
 pub(super) async fn repo_approve_pr(
     State(state): State<Arc<AppState>>,
     Json(body): Json<super::types::ApprovePrRequest>,
@@ -83,6 +125,71 @@ pub(super) async fn repo_approve_pr(
     }
 }
 
+pub(super) async fn repo_request_reviewers(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Json(body): Json<RequestReviewersRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    if let Err(e) = validate_repo_path(&body.repo_path) {
+        return e.into_response();
+    }
+    // Argument errors are the caller's fault (400); only GitHub failures are 502.
+    if let Err(e) = crate::github::validate_pr_number(body.pr_number).and_then(|()| {
+        crate::github::build_request_reviewers_body(&body.reviewers, &body.team_reviewers)
+    }) {
+        return bad_request(&e);
+    }
+    match crate::github::request_reviewers_impl(
+        &body.repo_path,
+        body.pr_number,
+        &body.reviewers,
+        &body.team_reviewers,
+        &state,
+    )
+    .await
+    {
+        Ok(()) => Json(serde_json::json!({"ok": true})).into_response(),
+        Err(e) => (
+            axum::http::StatusCode::BAD_GATEWAY,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response(),
+    }
+}
+
+pub(super) async fn repo_add_pr_comment(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Json(body): Json<AddPrCommentRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    if let Err(e) = validate_repo_path(&body.repo_path) {
+        return e.into_response();
+    }
+    if let Err(e) = crate::github::validate_pr_number(body.pr_number)
+        .and_then(|()| crate::github::validate_comment_body(&body.body))
+    {
+        return bad_request(&e);
+    }
+    match crate::github::add_pr_comment_impl(&body.repo_path, body.pr_number, &body.body, &state)
+        .await
+    {
+        Ok(url) => Json(serde_json::json!({"url": url})).into_response(),
+        Err(e) => (
+            axum::http::StatusCode::BAD_GATEWAY,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response(),
+    }
+}
+
 pub(super) async fn repo_pr_diff(
     State(state): State<Arc<AppState>>,
     Query(q): Query<PrDiffQuery>,
@@ -150,6 +257,82 @@ pub(super) async fn repo_reopen_issue(
     }
 }
 
+/// Map a GitHub helper error to a response: `rate-limit:` errors become 429
+/// so clients can back off, everything else is a 502.
+fn github_error_response(e: String) -> Response {
+    let status = if e.starts_with("rate-limit:") {
+        axum::http::StatusCode::TOO_MANY_REQUESTS
+    } else {
+        axum::http::StatusCode::BAD_GATEWAY
+    };
+    (status, Json(serde_json::json!({"error": e}))).into_response()
+}
+
+pub(super) async fn repo_list_issues(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<ListIssuesQuery>,
+) -> Response {
+    if let Err(e) = validate_repo_path(&q.path) {
+        return e.into_response();
+    }
+    if let Err(e) = crate::github::issue_states_for_filter(&q.state) {
+        return (
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+            .into_response();
+    }
+    let labels: Vec<String> = q
+        .labels
+        .as_deref()
+        .unwrap_or("")
+        .split(',')
+        .map(str::to_string)
+        .collect();
+    match crate::github::list_issues_impl(&q.path, &q.state, &labels, &state).await {
+        Ok(issues) => Json(issues).into_response(),
+        Err(e) => github_error_response(e),
+    }
+}
+
+pub(super) async fn repo_get_issue(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<IssueQuery>,
+) -> Response {
+    if let Err(e) = validate_repo_path(&q.path) {
+        return e.into_response();
+    }
+    match crate::github::get_issue_impl(&q.path, q.number, &state).await {
+        Ok(issue) => Json(issue).into_response(),
+        Err(e) => github_error_response(e),
+    }
+}
+
+pub(super) async fn repo_add_issue_comment(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Json(body): Json<AddIssueCommentRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    if let Err(e) = validate_repo_path(&body.repo_path) {
+        return e.into_response();
+    }
+    match crate::github::add_issue_comment_impl(
+        &body.repo_path,
+        body.issue_number,
+        &body.body,
+        &state,
+    )
+    .await
+    {
+        Ok(url) => Json(serde_json::json!({"url": url})).into_response(),
+        Err(e) => github_error_response(e),
+    }
+}
+
 // --- GitHub poller HTTP handlers ---
 
 pub(super) async fn poller_start(
@@ -186,6 +369,21 @@ pub(super) async fn poller_set_visibility(
     Json(serde_json::json!({"ok": true})).into_response()
 }
 
+pub(super) async fn poller_set_active(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<SetPollActiveRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    // Keyed per remote host so a blurred browser tab can't pause the desktop.
+    let client = format!("http:{}", addr.ip());
+    let active = crate::github_poller::set_poll_active_impl(&state, &client, body.active);
+    Json(serde_json::json!({"active": active})).into_response()
+}
+
 pub(super) async fn poller_poll_repo(
     State(state): State<Arc<AppState>>,
     Json(body): Json<PollRepoRequest>,
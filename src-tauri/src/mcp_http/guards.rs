@@ -25,6 +25,29 @@ use std::net::SocketAddr;
 #[derive(Clone, Copy)]
 pub(super) struct Authenticated;
 
+/// Which gate in `basic_auth_middleware` let the request through. Inserted
+/// next to [`Authenticated`]; `/whoami` reports it to the web client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum AuthVia {
+    /// Desktop loopback bypass
+    Loopback,
+    /// `lan_auth_bypass` for a private address
+    LanBypass,
+    /// Session cookie set by an earlier token/Basic Auth login
+    SessionCookie,
+    /// `?token=` from the QR code
+    UrlToken,
+    /// Basic Auth username + password
+    BasicAuth,
+}
+
+impl AuthVia {
+    /// True when the request carried the session token (URL or cookie).
+    pub(super) fn is_token_based(self) -> bool {
+        matches!(self, Self::SessionCookie | Self::UrlToken)
+    }
+}
+
 /// Reject requests that did not originate on the loopback interface.
 ///
 /// Used by strict RCE/diagnostics surfaces (e.g. `/debug/invoke_js`) that must
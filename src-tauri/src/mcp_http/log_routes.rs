@@ -81,6 +81,16 @@ pub(crate) async fn clear_logs(State(state): State<Arc<AppState>>) -> StatusCode
     StatusCode::NO_CONTENT
 }
 
+#[derive(Deserialize)]
+pub(crate) struct AnsiHtmlBody {
+    text: String,
+}
+
+/// POST /logs/ansi-html — render ANSI-colored text as HTML spans.
+pub(crate) async fn ansi_to_html(Json(body): Json<AnsiHtmlBody>) -> Json<String> {
+    Json(crate::ansi::to_html(body.text.as_bytes()))
+}
+
 // ---------------------------------------------------------------------------
 // Diagnostic mode toggle
 // ---------------------------------------------------------------------------
@@ -1,6 +1,6 @@
 use crate::pty::{resolve_shell, spawn_reader_thread};
 use crate::state::{OUTPUT_RING_BUFFER_CAPACITY, VT_LOG_BUFFER_CAPACITY, VtLogBuffer};
-use crate::{AppState, MAX_CONCURRENT_SESSIONS, OutputRingBuffer, PtySession};
+use crate::{AppState, OutputRingBuffer, PtySession};
 use axum::Json;
 use axum::extract::{ConnectInfo, State};
 use axum::http::{HeaderMap, StatusCode};
@@ -150,6 +150,7 @@ fn build_mcp_instructions(state: &Arc<AppState>, client_name: Option<&str>) -> S
     if show_intent {
         out.push_str("- `intent: <desc> (<title>)` on work-phase change. `<title>` ≤3 words, spaces not hyphens.\n");
     }
+    out.push_str("- `[[error: <what failed>]]` on its own line when blocked by a problem you cannot resolve (missing access, broken env). Not for errors you can fix.\n");
     if show_suggest {
         out.push_str("- `suggest:` — after task done: `suggest: [ A | B | C ]` — wrap the WHOLE list in one `[ … ]`, EXACTLY 3 items separated by `|`, each item ≤40 chars. The brackets bound the token (parsed even if it wraps); never emit 4+ items.\n");
     }
@@ -165,10 +166,14 @@ fn build_mcp_instructions(state: &Arc<AppState>, client_name: Option<&str>) -> S
         out.push_str("**Worktrees:** never `git worktree add/remove` — always use `repo action=worktree_create` / `worktree_remove` so TUIC tracks the worktree and can spawn a PTY inside.\n\n");
     } else {
         out.push_str("## Tools\n\n");
-        out.push_str("- `session` (PTY panes, tmux-equivalent): list, create, input, output, status, resize, close, kill, pause, resume, process_stats\n");
+        out.push_str("- `session` (PTY panes, tmux-equivalent): list, create, input, output, search, status, resize, close, kill, signal, pause, resume, process_stats\n");
         out.push_str("- `agent` (AI peers + messaging): spawn, detect, stats, metrics, register, list_peers, send, inbox\n");
-        out.push_str("- `repo` (repos, PRs, worktrees): list, active, prs, status, worktree_list, worktree_create, worktree_remove\n");
+        out.push_str("- `repo` (repos, PRs, worktrees, stash): list, active, prs, status, worktree_list, worktree_create, worktree_remove, stash, file_diff\n");
+        out.push_str("- `issues` (GitHub issues): list, get, comment\n");
         out.push_str("- `ui` (tabs, toasts, confirm dialogs): tab, toast, confirm\n");
+        out.push_str(
+            "- `fs` (repo files): read, write, list, mkdir — writes need `mcp_allow_file_writes`\n",
+        );
         out.push_str("- `plugin_dev_guide`: plugin authoring reference\n\n");
         out.push_str("**Worktrees:** always `repo action=worktree_create`/`worktree_remove` — never `git worktree add/remove` (TUIC must track them to spawn a PTY inside).\n\n");
         out.push_str("**UI feedback:** `ui action=toast` on task done/blocking error · `ui action=confirm` BEFORE destructive ops (rm -rf, git reset --hard, force push, DROP TABLE) · `ui action=tab` for structured output >20 lines · `ui action=screenshot id=<panel-id>` to see rendered output (Read the returned path).\n\n");
@@ -264,14 +269,15 @@ fn validate_mcp_repo_path(path: &str) -> Result<(), serde_json::Value> {
     super::validate_path_string(path).map_err(|msg| serde_json::json!({"error": msg}))
 }
 
-const SESSION_ACTIONS: &str =
-    "list, create, input, output, resize, close, kill, pause, resume, status, process_stats";
+const SESSION_ACTIONS: &str = "list, create, input, output, search, resize, close, kill, signal, pause, resume, status, process_stats";
 const AGENT_ACTIONS: &str = "spawn, detect, stats, metrics, register, list_peers, send, inbox";
-const REPO_ACTIONS: &str =
-    "list, active, prs, status, worktree_list, worktree_create, worktree_remove";
+const REPO_ACTIONS: &str = "list, active, prs, status, worktree_list, worktree_create, worktree_remove, stash, file_diff, commit";
+const STASH_SUBACTIONS: &str = "save, list, pop, drop";
+const ISSUES_ACTIONS: &str = "list, get, comment";
 const UI_ACTIONS: &str = "tab, toast, confirm, screenshot";
 const CONFIG_ACTIONS: &str = "get, save, list_ai_prompts, load_ai_prompt, save_ai_prompt, list_prompts, load_prompt, save_prompt";
 const DEBUG_ACTIONS: &str = "agent_detection, logs, sessions, invoke_js, help";
+const FS_ACTIONS: &str = "read, write, list, mkdir";
 
 // Legacy action constants — still referenced by handlers until dispatch refactor (story 1091).
 // Remove these when handle_mcp_tool_call dispatch is updated.
@@ -284,7 +290,7 @@ const LEGACY_NOTIFY_ACTIONS: &str = "toast, confirm";
 const LEGACY_MESSAGING_ACTIONS: &str = "register, list_peers, send, inbox";
 const LEGACY_DEBUG_ACTIONS: &str = "agent_detection, logs, sessions, invoke_js";
 
-/// Full MCP tool definitions — 7 base native tools + all `ai_terminal_*` tools.
+/// Full MCP tool definitions — 9 base native tools + all `ai_terminal_*` tools.
 ///
 /// This returns the unfiltered schema list. Public listing/search paths MUST
 /// route through [`filtered_native_tools`] to honour `disabled_native_tools`
@@ -294,10 +300,16 @@ fn native_tool_definitions() -> serde_json::Value {
     let mut defs = serde_json::json!([
         {
             "name": "session",
-            "description": "PTY multiplexer (replaces tmux). Create terminals, send input (send-keys), read output (capture-pane), manage lifecycle.\n\nActions:\n- list: Active sessions with cwd, process info. Call first to discover IDs.\n- create: New PTY. Returns {session_id}. Optional: cwd, shell, rows, cols.\n- input: Send text and/or special_key to a session.\n- output: Read terminal output. Returns {data, cursor, scrollback_lines, oldest_offset, exited, exit_code}. scrollback_lines = total lines in buffer (up to 10000); oldest_offset = first available line number. Patterns: (1) Snapshot: omit since_cursor, default limit=50 gives last 50 lines. (2) Delta poll: since_cursor=<previous cursor> returns only new lines — very cheap, use for monitoring. (3) Navigate backwards: from_line=oldest_offset reads from the beginning of the buffer. (4) Arbitrary window: from_line=N, limit=50 reads any 50-line slice.\n- status: Shell state for a session: {shell_state, idle_since_ms, busy_duration_ms, exit_code, agent_type}. Use to poll agent progress without streaming output.\n- resize: Change PTY dimensions.\n- close: Graceful shutdown (Ctrl+C, waits).\n- kill: Force SIGKILL (use when close fails).\n- pause: Pause output buffering. resume: Resume.\n- process_stats: CPU% and RSS memory for TUIC and all child process trees. Returns {processes: [{session_id, name, pid, rss_kb, cpu_pct}]}. Use to diagnose high CPU/memory.",
+            "description": "PTY multiplexer (replaces tmux). Create terminals, send input (send-keys), read output (capture-pane), manage lifecycle.\n\nActions:\n- list: Active sessions with cwd, process info. Call first to discover IDs.\n- create: New PTY. Returns {session_id}. Optional: cwd, shell, rows, cols.\n- input: Send text and/or special_key to a session.\n- output: Read terminal output. Returns {data, cursor, scrollback_lines, oldest_offset, exited, exit_code}. scrollback_lines = total lines in buffer (up to 10000); oldest_offset = first available line number. Patterns: (1) Snapshot: omit since_cursor, default limit=50 gives last 50 lines. (2) Delta poll: since_cursor=<previous cursor> returns only new lines — very cheap, use for monitoring. (3) Navigate backwards: from_line=oldest_offset reads from the beginning of the buffer. (4) Arbitrary window: from_line=N, limit=50 reads any 50-line slice.\n- search: Grep a session's scrollback. Requires session_id, query. Optional: regex, case_sensitive, max_matches (default 50, max 500), strip_ansi (default true). Returns {matches: [{offset, length, text, context}], truncated}.\n- status: Shell state for a session: {shell_state, idle_since_ms, busy_duration_ms, exit_code, agent_type}. Use to poll agent progress without streaming output.\n- resize: Change PTY dimensions.\n- close: Graceful shutdown (Ctrl+C, waits).\n- kill: Force SIGKILL (use when close fails).\n- signal: Send signal (SIGINT, SIGTERM or SIGKILL) to the session's foreground process group without closing the session. Returns {pid, signal}.\n- pause: Pause output buffering. resume: Resume.\n- process_stats: CPU% and RSS memory for TUIC and all child process trees. Returns {processes: [{session_id, name, pid, rss_kb, cpu_pct}]}. Use to diagnose high CPU/memory.",
             "inputSchema": { "type": "object", "properties": {
-                "action": { "type": "string", "description": "One of: list, create, input, output, status, resize, close, kill, pause, resume, process_stats" },
-                "session_id": { "type": "string", "description": "Session ID (required for input, output, resize, close, pause, resume)" },
+                "action": { "type": "string", "description": "One of: list, create, input, output, search, status, resize, close, kill, signal, pause, resume, process_stats" },
+                "session_id": { "type": "string", "description": "Session ID (required for input, output, search, resize, close, signal, pause, resume)" },
+                "signal": { "type": "string", "description": "SIGINT, SIGTERM or SIGKILL (action=signal)" },
+                "query": { "type": "string", "description": "Text or regex to find (action=search)" },
+                "regex": { "type": "boolean", "description": "Treat query as a regex (action=search, default false)" },
+                "case_sensitive": { "type": "boolean", "description": "Case-sensitive match (action=search, default false)" },
+                "max_matches": { "type": "integer", "description": "Max matches to return (action=search, default 50, capped at 500)" },
+                "strip_ansi": { "type": "boolean", "description": "Strip ANSI escapes before matching (action=search, default true)" },
                 "input": { "type": "string", "description": "Raw text to write (action=input)" },
                 "special_key": { "type": "string", "description": "Special key: enter, tab, ctrl+c, ctrl+d, ctrl+z, ctrl+l, ctrl+a, ctrl+e, ctrl+k, ctrl+u, ctrl+w, ctrl+r, up, down, left, right, home, end, backspace, delete, escape (action=input)" },
                 "rows": { "type": "integer", "description": "Terminal rows (action=create or resize)" },
@@ -335,15 +347,36 @@ fn native_tool_definitions() -> serde_json::Value {
         },
         {
             "name": "repo",
-            "description": "Repository and version control. Query workspace repos, GitHub PR/CI status, manage git worktrees.\n\nActions:\n- list: Open repos with branch, dirty status, worktrees.\n- active: Focused repo path, branch, group.\n- prs: Open PRs with CI, merge readiness, reviews. Requires path.\n- status: Cross-repo {path, branch, ahead, behind, open_prs, failing_ci}.\n- worktree_list: Worktrees for a repo. Requires path.\n- worktree_create: Create worktree. Requires path. Optional: branch, base_ref, spawn_session.\n- worktree_remove: Remove worktree. Requires path, branch.",
+            "description": "Repository and version control. Query workspace repos, GitHub PR/CI status, manage git worktrees.\n\nActions:\n- list: Open repos with branch, dirty status, worktrees.\n- active: Focused repo path, branch, group.\n- prs: Open PRs with CI, merge readiness, reviews. Requires path.\n- status: Cross-repo {path, branch, ahead, behind, open_prs, failing_ci}.\n- worktree_list: Worktrees for a repo. Requires path.\n- worktree_create: Create worktree. Requires path. Optional: branch, base_ref, spawn_session.\n- worktree_remove: Remove worktree. Requires path, branch. Refused when the worktree has uncommitted changes or untracked files unless force=true. dry_run=true removes nothing and returns {has_uncommitted_changes, unpushed_commits, untracked_files, would_remove_path}.\n- stash: git stash. Requires path, subaction (save/list/pop/drop). save takes optional message and fails on a clean tree; list returns [{index, message, branch}]; pop takes optional index (default 0); drop requires index.\n- file_diff: Unified diff of one file. Requires path, file. Optional: staged (default false = working tree vs index). Errors when the file has no changes.\n- commit: git add + git commit. Requires path, message. Optional: files (repo-relative paths to stage) or all=true (git add -A); with neither, commits what is already staged. Returns {sha}. Errors carry kind: empty_message, nothing_to_commit, hook_failed (with the hook's output), git.",
             "inputSchema": { "type": "object", "properties": {
-                "action": { "type": "string", "description": "One of: list, active, prs, status, worktree_list, worktree_create, worktree_remove" },
-                "path": { "type": "string", "description": "Absolute path to git repository (required for prs, worktree_list, worktree_create, worktree_remove)" },
+                "action": { "type": "string", "description": "One of: list, active, prs, status, worktree_list, worktree_create, worktree_remove, stash, file_diff, commit" },
+                "path": { "type": "string", "description": "Absolute path to git repository (required for prs, worktree_list, worktree_create, worktree_remove, stash, file_diff, commit)" },
+                "file": { "type": "string", "description": "Repo-relative file path (action=file_diff, required)" },
+                "staged": { "type": "boolean", "description": "Diff the index against HEAD instead of the working tree (action=file_diff, default false)" },
+                "subaction": { "type": "string", "description": "Stash operation: save, list, pop, drop (action=stash, required)" },
+                "message": { "type": "string", "description": "Stash message (action=stash subaction=save, optional) or commit message (action=commit, required)" },
+                "files": { "type": "array", "items": { "type": "string" }, "description": "Repo-relative files to stage before committing (action=commit)" },
+                "all": { "type": "boolean", "description": "Stage every change with git add -A before committing (action=commit, default false)" },
+                "index": { "type": "integer", "description": "N in stash@{N} (action=stash: subaction=pop optional, default 0; subaction=drop required)" },
                 "branch": { "type": "string", "description": "Branch name (action=worktree_create optional, action=worktree_remove required)" },
+                "dry_run": { "type": "boolean", "description": "Report what removal would lose instead of removing (action=worktree_remove, default false)" },
+                "force": { "type": "boolean", "description": "Remove even with uncommitted changes or untracked files (action=worktree_remove, default false)" },
                 "base_ref": { "type": "string", "description": "Base ref to branch from, default HEAD (action=worktree_create)" },
                 "spawn_session": { "type": "boolean", "description": "Auto-create a PTY session in the worktree (action=worktree_create, default false)" }
             }, "required": ["action"] }
         },
+        {
+            "name": "issues",
+            "description": "GitHub issues for a repository.\n\nActions:\n- list: Issues with labels, assignees, milestone, comment count. Requires path. Optional: state (open/closed/all, default open), labels.\n- get: One issue with body and the last 50 comments. Requires path, number.\n- comment: Post a comment (localhost only). Requires path, number, body. Returns {url}.\n\nErrors prefixed `rate-limit:` mean GitHub throttled the request — back off before retrying.",
+            "inputSchema": { "type": "object", "properties": {
+                "action": { "type": "string", "description": "One of: list, get, comment" },
+                "path": { "type": "string", "description": "Absolute path to git repository (required)" },
+                "state": { "type": "string", "description": "Issue state filter: open, closed, all (action=list, default open)" },
+                "labels": { "type": "array", "items": { "type": "string" }, "description": "Only issues carrying all of these labels (action=list)" },
+                "number": { "type": "integer", "description": "Issue number (action=get, comment)" },
+                "body": { "type": "string", "description": "Comment text, markdown (action=comment)" }
+            }, "required": ["action", "path"] }
+        },
         {
             "name": "ui",
             "description": "Control TUIC UI. Actions:\n- tab: open/update panel tab. Requires id, title, + html OR url.\n- toast: non-blocking notification. Requires title. Optional: message, level (info/warn/error), sound.\n- confirm: blocking dialog. Returns {confirmed}. Requires title.\n- screenshot: capture a panel as WebP. Requires id. Returns {path}. Read the path to view.\n\nURL schemes for tab:\n- http(s): loaded in sandboxed iframe.\n- file:///path: read via IPC and rendered as inline HTML (sandbox blocks direct file:// access).\n- tuic://edit/<path>?line=N: native code editor (no iframe). Prefix absolute paths with `//` (tuic://edit//Users/x/a.rs). Relative = active repo.\n- tuic://open/<path>: native markdown/preview tab.\n\nCustom schemes (vscode://) do NOT work in iframes.\n\nUse:\n- toast for done/error/long-job end; error=failure, warn=recoverable. Skip for micro-steps.\n- confirm BEFORE destructive ops (rm -rf, git reset --hard, force-push, DROP). Only proceed if confirmed.\n- tab http(s) for dashboards, reports, >20-line structured output.\n- tab tuic://edit to point user at source file+line (review, bug discussion) — beats pasting snippets.\n- screenshot to visually verify rendered HTML content in a panel you created.",
@@ -390,6 +423,18 @@ fn native_tool_definitions() -> serde_json::Value {
                 "script": { "type": "string", "description": "JavaScript to execute in the WebView (action=invoke_js). The ONLY global is window.__TUIC__ — call action=help for the full API list. Example: return window.__TUIC__.terminals()" },
                 "limit": { "type": "integer", "description": "Max entries (action=logs, default 50)" }
             }, "required": ["action"] }
+        },
+        {
+            "name": "fs",
+            "description": "Read, write and list files inside a registered repository (localhost or authenticated connections). All paths are relative to the repo root; absolute paths, '..' and symlink escapes are rejected.\n\nActions:\n- read: File content. Requires file.\n- write: Atomic write (temp file + rename). Requires file, content. Optional create_parents. Returns {path, bytes_written}. Localhost only, and only when `mcp_allow_file_writes` is enabled.\n- list: Directory entries with git status. Optional dir (default repo root).\n- mkdir: Create a directory and its parents. Requires dir. Same restrictions as write.",
+            "inputSchema": { "type": "object", "properties": {
+                "action": { "type": "string", "description": "One of: read, write, list, mkdir" },
+                "path": { "type": "string", "description": "Absolute repository root path" },
+                "file": { "type": "string", "description": "File path relative to the repo root (action=read, write)" },
+                "dir": { "type": "string", "description": "Directory relative to the repo root (action=list, mkdir)" },
+                "content": { "type": "string", "description": "File content (action=write)" },
+                "create_parents": { "type": "boolean", "description": "Create missing parent directories (action=write, default: false)" }
+            }, "required": ["action", "path"] }
         }
     ]);
 
@@ -556,36 +601,6 @@ fn merged_tool_definitions(
     serde_json::Value::Array(tools)
 }
 
-/// Translate special key names to terminal escape sequences
-fn translate_special_key(key: &str) -> Option<&'static str> {
-    match key {
-        "enter" | "return" => Some("\r"),
-        "tab" => Some("\t"),
-        "escape" | "esc" => Some("\x1b"),
-        "backspace" => Some("\x7f"),
-        "delete" => Some("\x1b[3~"),
-        "up" => Some("\x1b[A"),
-        "down" => Some("\x1b[B"),
-        "right" => Some("\x1b[C"),
-        "left" => Some("\x1b[D"),
-        "home" => Some("\x1b[H"),
-        "end" => Some("\x1b[F"),
-        "ctrl+c" => Some("\x03"),
-        "ctrl+d" => Some("\x04"),
-        "ctrl+z" => Some("\x1a"),
-        "ctrl+l" => Some("\x0c"),
-        "ctrl+a" => Some("\x01"),
-        "ctrl+e" => Some("\x05"),
-        "ctrl+k" => Some("\x0b"),
-        "ctrl+u" => Some("\x15"),
-        "ctrl+w" => Some("\x17"),
-        "ctrl+r" => Some("\x12"),
-        "ctrl+p" => Some("\x10"),
-        "ctrl+n" => Some("\x0e"),
-        _ => None,
-    }
-}
-
 /// Extract action from args, returning a guidance error if missing
 fn require_action<'a>(
     args: &'a serde_json::Value,
@@ -726,6 +741,7 @@ fn handle_get_tool_schema(state: &Arc<AppState>, args: &serde_json::Value) -> se
 async fn handle_call_tool(
     state: &Arc<AppState>,
     addr: SocketAddr,
+    authenticated: bool,
     args: &serde_json::Value,
     mcp_session_id: Option<&str>,
 ) -> serde_json::Value {
@@ -770,6 +786,7 @@ async fn handle_call_tool(
         Box::pin(handle_mcp_tool_call(
             state,
             addr,
+            authenticated,
             &tool_name,
             &tool_args,
             mcp_session_id,
@@ -780,9 +797,12 @@ async fn handle_call_tool(
 
 /// Handle an MCP tools/call request, executing against the app state directly (no HTTP round-trip).
 /// Also used by the `deep_link_mcp_call` Tauri command for the `tuic://cmd/` gateway.
+/// `authenticated` is true when the request passed the auth middleware (see
+/// `guards::require_local_or_auth`).
 pub(crate) async fn handle_mcp_tool_call(
     state: &Arc<AppState>,
     addr: SocketAddr,
+    authenticated: bool,
     name: &str,
     args: &serde_json::Value,
     mcp_session_id: Option<&str>,
@@ -808,8 +828,8 @@ pub(crate) async fn handle_mcp_tool_call(
             // Executing / destructive session actions carry the same loopback
             // restriction as `agent spawn`: `input` writes raw bytes to a PTY's stdin
             // (arbitrary command execution on a shell session, unfiltered context
-            // injection on an agent session), `create`/`kill`/`close` spawn or
-            // destroy sessions, and `pause`/`resume` halt/resume output buffering
+            // injection on an agent session), `create`/`kill`/`close`/`signal` spawn,
+            // destroy or interrupt sessions, and `pause`/`resume` halt/resume output buffering
             // (a remote `pause` on any session is a DoS). A non-loopback MCP client
             // (authenticated remote, or admitted via lan_auth_bypass) must not reach
             // them — remote terminal control is served separately by the auth-gated
@@ -818,7 +838,7 @@ pub(crate) async fn handle_mcp_tool_call(
             let action = args["action"].as_str().unwrap_or("");
             if matches!(
                 action,
-                "create" | "input" | "kill" | "close" | "pause" | "resume"
+                "create" | "input" | "kill" | "close" | "signal" | "pause" | "resume"
             ) && !addr.ip().is_loopback()
             {
                 serde_json::json!({
@@ -830,15 +850,24 @@ pub(crate) async fn handle_mcp_tool_call(
         }
         "agent" => handle_agent_unified(state, addr, args, mcp_session_id),
         "repo" => handle_repo(state, args, is_claude_code).await,
+        "issues" => handle_issues(state, addr, args).await,
         "ui" => handle_ui_unified(state, addr, args, mcp_session_id).await,
         "plugin_dev_guide" => {
             serde_json::json!({"content": super::plugin_docs::PLUGIN_DOCS})
         }
         "config" => handle_config(state, addr, args),
         "debug" => handle_debug_unified(state, addr, args),
+        "fs" => {
+            // Reads, listings and atomic writes all hit the disk.
+            let state = state.clone();
+            let args = args.clone();
+            tokio::task::spawn_blocking(move || handle_fs(&state, addr, authenticated, &args))
+                .await
+                .unwrap_or_else(|e| serde_json::json!({"error": format!("Task failed: {e}")}))
+        }
         "search_tools" => handle_search_tools(state, args),
         "get_tool_schema" => handle_get_tool_schema(state, args),
-        "call_tool" => handle_call_tool(state, addr, args, mcp_session_id).await,
+        "call_tool" => handle_call_tool(state, addr, authenticated, args, mcp_session_id).await,
         n if super::ai_terminal::is_ai_terminal_tool(n) => {
             if !state.config.read().ai_terminal_mcp_enabled {
                 return serde_json::json!({
@@ -850,7 +879,121 @@ pub(crate) async fn handle_mcp_tool_call(
             super::ai_terminal::handle(state, n, args).await
         }
         _ => serde_json::json!({"error": format!(
-            "Unknown tool '{}'. Available: session, agent, repo, ui, plugin_dev_guide, config, debug, search_tools, get_tool_schema, call_tool, ai_terminal_*", name
+            "Unknown tool '{}'. Available: session, agent, repo, issues, ui, plugin_dev_guide, config, debug, fs, search_tools, get_tool_schema, call_tool, ai_terminal_*", name
+        )}),
+    }
+}
+
+/// Reject repo-relative paths that are absolute, climb with `..` or contain
+/// NUL. The fs helpers also canonicalize against the repo root; this gives
+/// MCP clients a clear error before touching the filesystem.
+fn validate_mcp_relative_path(rel: &str) -> Result<(), serde_json::Value> {
+    let p = std::path::Path::new(rel);
+    if rel.contains('\0')
+        || p.is_absolute()
+        || p.components()
+            .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err(serde_json::json!({
+            "error": format!("Invalid path '{rel}': must be relative to the repo root without '..'")
+        }));
+    }
+    Ok(())
+}
+
+fn handle_fs(
+    state: &Arc<AppState>,
+    addr: SocketAddr,
+    authenticated: bool,
+    args: &serde_json::Value,
+) -> serde_json::Value {
+    let action = match require_action(args, "fs", FS_ACTIONS) {
+        Ok(a) => a,
+        Err(e) => return e,
+    };
+    if super::guards::require_local_or_auth(&addr, authenticated).is_err() {
+        return serde_json::json!({"error": "fs actions require a localhost or authenticated connection"});
+    }
+    let repo = match args["path"].as_str() {
+        Some(p) => p,
+        None => return serde_json::json!({"error": "path required (absolute repo root)"}),
+    };
+    if let Err(e) = validate_mcp_repo_path(repo) {
+        return e;
+    }
+    // validate_mcp_repo_path accepts any absolute path; fs access is limited
+    // to registered repositories.
+    if !super::fs_routes::is_within_repo_roots(
+        std::path::Path::new(repo),
+        &crate::config::registered_repo_paths(),
+    ) {
+        return serde_json::json!({
+            "error": format!("Access denied: '{repo}' is not inside a registered repository")
+        });
+    }
+    if matches!(action, "write" | "mkdir") {
+        if !addr.ip().is_loopback() {
+            return serde_json::json!({"error": "fs write actions are restricted to localhost connections"});
+        }
+        if !state.config.read().mcp_allow_file_writes {
+            return serde_json::json!({
+                "error": "File writes are disabled. Enable `mcp_allow_file_writes` in config to allow fs write/mkdir."
+            });
+        }
+    }
+    match action {
+        "read" | "write" => {
+            let file = match args["file"].as_str() {
+                Some(f) if !f.is_empty() => f,
+                _ => {
+                    return serde_json::json!({"error": format!("file required for action={action}")});
+                }
+            };
+            if let Err(e) = validate_mcp_relative_path(file) {
+                return e;
+            }
+            if action == "read" {
+                return match crate::read_file_impl(repo.to_string(), file.to_string()) {
+                    Ok(content) => serde_json::json!({"path": file, "content": content}),
+                    Err(e) => serde_json::json!({"error": e}),
+                };
+            }
+            let Some(content) = args["content"].as_str() else {
+                return serde_json::json!({"error": "content required for action=write"});
+            };
+            let create_parents = args["create_parents"].as_bool().unwrap_or(false);
+            match crate::fs::write_file_atomic(repo, file, content, create_parents) {
+                Ok(bytes_written) => {
+                    serde_json::json!({"path": file, "bytes_written": bytes_written})
+                }
+                Err(e) => serde_json::json!({"error": e}),
+            }
+        }
+        "list" => {
+            let dir = args["dir"].as_str().unwrap_or("");
+            if let Err(e) = validate_mcp_relative_path(dir) {
+                return e;
+            }
+            match crate::fs::list_directory_impl(repo.to_string(), dir.to_string()) {
+                Ok(entries) => serde_json::json!({"entries": entries}),
+                Err(e) => serde_json::json!({"error": e}),
+            }
+        }
+        "mkdir" => {
+            let dir = match args["dir"].as_str() {
+                Some(d) if !d.is_empty() => d,
+                _ => return serde_json::json!({"error": "dir required for action=mkdir"}),
+            };
+            if let Err(e) = validate_mcp_relative_path(dir) {
+                return e;
+            }
+            match crate::fs::create_directory(repo.to_string(), dir.to_string()) {
+                Ok(()) => serde_json::json!({"ok": true, "path": dir}),
+                Err(e) => serde_json::json!({"error": e}),
+            }
+        }
+        other => serde_json::json!({"error": format!(
+            "Unknown fs action '{other}'. Available: {FS_ACTIONS}"
         )}),
     }
 }
@@ -901,8 +1044,8 @@ fn handle_session(
             serde_json::json!(sessions)
         }
         "create" => {
-            if state.sessions.len() >= MAX_CONCURRENT_SESSIONS {
-                return serde_json::json!({"error": "Max concurrent sessions reached"});
+            if !state.can_spawn_session() {
+                return serde_json::json!({"error": format!("Max concurrent sessions ({}) reached", state.max_sessions())});
             }
             let rows = args["rows"].as_u64().unwrap_or(24) as u16;
             let cols = args["cols"].as_u64().unwrap_or(80) as u16;
@@ -920,6 +1063,7 @@ fn handle_session(
                 cols,
                 None,
                 None,
+                &Default::default(),
             ) {
                 Ok(session_id) => serde_json::json!({"session_id": session_id}),
                 Err((_, body)) => {
@@ -934,7 +1078,7 @@ fn handle_session(
             };
             let text = args["input"].as_str().unwrap_or("");
             let key_seq: Option<&str> = if let Some(key) = args["special_key"].as_str() {
-                match translate_special_key(key) {
+                match crate::pty::translate_special_key(key) {
                     Some(seq) => Some(seq),
                     None => {
                         return serde_json::json!({"error": format!("Unknown special key: {}", key)});
@@ -1180,6 +1324,32 @@ fn handle_session(
                 serde_json::json!({"error": "Session not found"})
             }
         }
+        "signal" => {
+            let session_id = match require_session_id(args, "signal") {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            let signal =
+                match crate::pty::ForegroundSignal::parse(args["signal"].as_str().unwrap_or("")) {
+                    Ok(s) => s,
+                    Err(e) => return serde_json::json!({"error": e}),
+                };
+            // Self-signal guard: an agent interrupting its own foreground process
+            // would interrupt itself.
+            if let Some(sid) = mcp_session_id
+                && let Some(own_pty) = state.mcp_to_session.get(sid)
+                && own_pty.value() == session_id
+            {
+                return serde_json::json!({"error": "Cannot signal own session"});
+            }
+            if !state.sessions.contains_key(session_id) {
+                return serde_json::json!({"error": "Session not found"});
+            }
+            match crate::pty::signal_foreground(state, session_id, signal) {
+                Ok(pid) => serde_json::json!({"pid": pid, "signal": signal.name()}),
+                Err(e) => serde_json::json!({"error": e}),
+            }
+        }
         "pause" => {
             let session_id = match require_session_id(args, "pause") {
                 Ok(id) => id,
@@ -1204,6 +1374,29 @@ fn handle_session(
             entry.lock().paused.store(false, Ordering::Relaxed);
             serde_json::json!({"ok": true})
         }
+        "search" => {
+            let session_id = match require_session_id(args, "search") {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            let Some(q) = args["query"].as_str() else {
+                return serde_json::json!({"error": "Action 'search' requires 'query'"});
+            };
+            let query = super::types::OutputSearchQuery {
+                q: q.to_string(),
+                regex: args["regex"].as_bool().unwrap_or(false),
+                case_sensitive: args["case_sensitive"].as_bool().unwrap_or(false),
+                max_matches: args["max_matches"].as_u64().map(|n| n as usize),
+                strip_ansi: args["strip_ansi"].as_bool().unwrap_or(true),
+            };
+            match super::session::search_session_output(state, session_id, &query) {
+                Ok(Some(v)) => v,
+                Ok(None) => {
+                    serde_json::json!({"error": format!("Session not found: {session_id}")})
+                }
+                Err(e) => serde_json::json!({"error": e}),
+            }
+        }
         "status" => {
             let session_id = match require_session_id(args, "status") {
                 Ok(id) => id,
@@ -1212,6 +1405,10 @@ fn handle_session(
             match state.session_state_with_shell(session_id) {
                 Some(ss) => {
                     let exit_code = state.exit_codes.get(session_id).map(|e| *e.value());
+                    let exit_info = state
+                        .exit_reasons
+                        .get(session_id)
+                        .map(|e| e.value().clone());
                     let now_ms = std::time::SystemTime::now()
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap_or_default()
@@ -1240,6 +1437,7 @@ fn handle_session(
                         "rate_limited": ss.rate_limited,
                         "last_activity_ms": ss.last_activity_ms,
                         "exit_code": exit_code,
+                        "exit_info": exit_info,
                         "idle_since_ms": if is_idle && elapsed > 0 { serde_json::json!(elapsed) } else { serde_json::Value::Null },
                         "busy_duration_ms": if is_busy && elapsed > 0 { serde_json::json!(elapsed) } else { serde_json::Value::Null },
                         "standby": standby,
@@ -1396,6 +1594,7 @@ fn create_session_in_dir(state: &Arc<AppState>, cwd: &str) -> Result<String, Str
         80,
         None,
         None,
+        &Default::default(),
     )
     .map_err(|(_, body)| {
         body.0
@@ -1450,7 +1649,7 @@ async fn handle_worktree(
                         vec![]
                     }
                 };
-                crate::worktree::generate_worktree_name(&existing)
+                crate::worktree::generate_worktree_name_for(&state, &existing, Some(&path))
             });
 
             let config = crate::worktree::WorktreeConfig {
@@ -1574,13 +1773,21 @@ async fn handle_worktree(
                     return serde_json::json!({"error": "Action 'remove' requires 'branch' parameter"});
                 }
             };
+            if args["dry_run"].as_bool().unwrap_or(false) {
+                return match crate::worktree::preview_worktree_removal(&path, &branch) {
+                    Ok(report) => serde_json::to_value(report)
+                        .unwrap_or_else(|e| serde_json::json!({"error": e.to_string()})),
+                    Err(e) => serde_json::json!({"error": e}),
+                };
+            }
+            let force = args["force"].as_bool().unwrap_or(false);
             let archive = crate::worktree::resolve_archive_script(&path);
             match crate::worktree::remove_worktree_by_branch(
                 &path,
                 &branch,
                 true,
                 archive.as_deref(),
-                false,
+                force,
             ) {
                 Ok(()) => {
                     state.invalidate_repo_caches(&path);
@@ -1629,12 +1836,16 @@ fn handle_agent(
     };
     match action {
         "detect" => {
-            let known = ["claude", "codex", "aider", "goose"];
-            let results: Vec<serde_json::Value> = known
+            let results: Vec<serde_json::Value> = crate::agent::KNOWN_AGENTS
                 .iter()
-                .map(|name| {
-                    let det = crate::agent::detect_agent_binary(name.to_string());
-                    serde_json::json!({"name": name, "path": det.path, "version": det.version})
+                .map(|agent| {
+                    let det = crate::agent::detect_agent_binary(agent.binary.to_string());
+                    serde_json::json!({
+                        "name": agent.name,
+                        "binary": agent.binary,
+                        "path": det.path,
+                        "version": det.version,
+                    })
                 })
                 .collect();
             serde_json::json!(results)
@@ -1648,8 +1859,8 @@ fn handle_agent(
                 Some(p) => p.to_string(),
                 None => return serde_json::json!({"error": "Action 'spawn' requires 'prompt'"}),
             };
-            if state.sessions.len() >= MAX_CONCURRENT_SESSIONS {
-                return serde_json::json!({"error": "Max concurrent sessions reached"});
+            if !state.can_spawn_session() {
+                return serde_json::json!({"error": format!("Max concurrent sessions ({}) reached", state.max_sessions())});
             }
 
             // Resolve agent binary — run config name takes priority, then literal agent type
@@ -1667,7 +1878,10 @@ fn handle_agent(
             } else {
                 let agent_type_raw = args["agent_type"].as_str().unwrap_or("claude");
                 let rc = resolve_run_config(agent_type_raw, &agents_cfg);
-                let bin_raw = rc.command.as_deref().unwrap_or(&rc.agent_type);
+                let bin_raw = rc.command.as_deref().unwrap_or_else(|| {
+                    crate::agent::known_agent(&rc.agent_type)
+                        .map_or(rc.agent_type.as_str(), |a| a.binary)
+                });
                 let bin = crate::cli::expand_tilde(bin_raw);
                 let detection = crate::agent::detect_agent_binary(bin.clone());
                 match detection.path {
@@ -1743,19 +1957,14 @@ fn handle_agent(
                         cmd.arg(arg);
                     }
                 } else {
-                    // Run config matched but no args override — use default MCP param logic
-                    if args["print_mode"].as_bool().unwrap_or(false) {
-                        cmd.arg("--print");
-                    }
-                    if let Some(format) = args["output_format"].as_str() {
-                        cmd.arg("--output-format");
-                        cmd.arg(format);
-                    }
-                    if let Some(model) = args["model"].as_str() {
-                        cmd.arg("--model");
-                        cmd.arg(model);
-                    }
-                    cmd.arg(&effective_prompt);
+                    // Run config matched but no args override — use the agent's default args
+                    cmd.args(crate::agent::default_spawn_args(
+                        Some(&rc.agent_type),
+                        &effective_prompt,
+                        args["model"].as_str(),
+                        args["print_mode"].as_bool().unwrap_or(false),
+                        args["output_format"].as_str(),
+                    ));
                 }
             } else {
                 // No run config, no explicit args — default MCP param logic
@@ -1776,6 +1985,7 @@ fn handle_agent(
                 cmd.cwd(crate::cli::expand_tilde(cwd));
             }
 
+            let command_line = cmd.as_unix_command_line().ok();
             let child = match pair.slave.spawn_command(cmd) {
                 Ok(c) => c,
                 Err(e) => {
@@ -1795,6 +2005,8 @@ fn handle_agent(
                 }
             };
 
+            crate::spawn_ledger::record_spawn(&state.data_dir, child.process_id(), &session_id);
+
             let paused = Arc::new(AtomicBool::new(false));
             state.sessions.insert(
                 session_id.clone(),
@@ -1807,9 +2019,11 @@ fn handle_agent(
                     cwd: args["cwd"].as_str().map(|s| s.to_string()),
                     display_name: None,
                     shell: binary_path.clone(),
+                    token_usage: Default::default(),
                 }),
             );
             state.assign_term_alias(&session_id);
+            state.record_session_opened(&session_id, command_line);
             state.metrics.total_spawned.fetch_add(1, Ordering::Relaxed);
             state
                 .metrics
@@ -2260,14 +2474,14 @@ fn handle_config(
                     Ok(c) => c,
                     Err(e) => return serde_json::json!({"error": format!("Invalid config: {}", e)}),
                 };
-            // Preserve server-managed secrets
+            // Security settings are never changed through MCP: keep remote
+            // access, auth, TLS, relay and push (including server-managed
+            // secrets) and the MCP capability toggles from the current config.
             {
                 let current = state.config.read();
-                config.services.auth.session_token = current.services.auth.session_token.clone();
-                config.services.push.vapid_private_key =
-                    current.services.push.vapid_private_key.clone();
-                config.services.push.vapid_public_key =
-                    current.services.push.vapid_public_key.clone();
+                config.services = current.services.clone();
+                config.mcp_allow_file_writes = current.mcp_allow_file_writes;
+                config.ai_terminal_mcp_enabled = current.ai_terminal_mcp_enabled;
             }
             match crate::config::save_app_config(config.clone()) {
                 Ok(()) => {
@@ -2341,10 +2555,11 @@ fn handle_config(
             }
         }
         "list_prompts" => {
-            let lib = crate::config::load_prompt_library();
+            let lib = crate::config::load_prompt_library(None);
             serde_json::json!({
                 "prompts": lib.prompts.iter().map(|p| serde_json::json!({
                     "id": p.id, "label": p.label, "pinned": p.pinned,
+                    "use_count": p.use_count, "last_used_at": p.last_used_at,
                 })).collect::<Vec<_>>()
             })
         }
@@ -2353,7 +2568,7 @@ fn handle_config(
                 Ok(s) => s,
                 Err(e) => return e,
             };
-            let lib = crate::config::load_prompt_library();
+            let lib = crate::config::load_prompt_library(None);
             match lib.prompts.iter().find(|p| p.id == id) {
                 Some(p) => to_json_or_error(p.clone()),
                 None => serde_json::json!({"error": format!("Prompt not found: {id}")}),
@@ -2379,7 +2594,7 @@ fn handle_config(
                 .get("pinned")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
-            let mut lib = crate::config::load_prompt_library();
+            let mut lib = crate::config::load_prompt_library(None);
             if let Some(existing) = lib.prompts.iter_mut().find(|p| p.id == id) {
                 existing.label = label;
                 existing.text = text;
@@ -2390,6 +2605,7 @@ fn handle_config(
                     label,
                     text,
                     pinned,
+                    ..Default::default()
                 });
             }
             match crate::config::save_prompt_library(lib) {
@@ -2841,11 +3057,88 @@ fn resolve_repo_for_path(path: &str, known: &[String]) -> String {
 pub(super) async fn mcp_post(
     State(state): State<Arc<AppState>>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<axum::Extension<super::guards::Authenticated>>,
     headers: HeaderMap,
     Json(body): Json<serde_json::Value>,
 ) -> impl IntoResponse {
-    let method = body["method"].as_str().unwrap_or("");
+    let authenticated = auth.is_some();
+    let (response, session_id) = match body {
+        // JSON-RPC 2.0 batch: process entries in order, reply with an array.
+        // Notifications (no `id`) get no entry; an all-notification batch is 202.
+        serde_json::Value::Array(items) => {
+            if items.is_empty() {
+                (Some(jsonrpc_invalid_request(serde_json::Value::Null)), None)
+            } else {
+                let mut responses = Vec::new();
+                let mut session_id = None;
+                for item in &items {
+                    let reply =
+                        handle_mcp_message(&state, addr, authenticated, &headers, item).await;
+                    let is_notification = item.get("method").is_some() && item.get("id").is_none();
+                    if !is_notification && let Some(resp) = reply.response {
+                        responses.push(resp);
+                    }
+                    if reply.session_id.is_some() {
+                        session_id = reply.session_id;
+                    }
+                }
+                let response =
+                    (!responses.is_empty()).then_some(serde_json::Value::Array(responses));
+                (response, session_id)
+            }
+        }
+        single => {
+            let reply = handle_mcp_message(&state, addr, authenticated, &headers, &single).await;
+            (reply.response, reply.session_id)
+        }
+    };
+
+    let Some(response) = response else {
+        return StatusCode::ACCEPTED.into_response();
+    };
+    let mut resp = Json(response).into_response();
+    if let Some(sid) = session_id
+        && let Ok(val) = sid.parse()
+    {
+        resp.headers_mut().insert(MCP_SESSION_HEADER, val);
+    }
+    resp
+}
+
+/// Outcome of a single JSON-RPC message: the response object (`None` when
+/// nothing is sent back) and the session id to echo in `Mcp-Session-Id`.
+struct McpReply {
+    response: Option<serde_json::Value>,
+    session_id: Option<String>,
+}
+
+fn jsonrpc_invalid_request(id: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": -32600, "message": "Invalid Request" }
+    })
+}
+
+async fn handle_mcp_message(
+    state: &Arc<AppState>,
+    addr: SocketAddr,
+    authenticated: bool,
+    headers: &HeaderMap,
+    body: &serde_json::Value,
+) -> McpReply {
     let id = body.get("id").cloned().unwrap_or(serde_json::Value::Null);
+    let Some(method) = body.get("method").and_then(|m| m.as_str()) else {
+        return McpReply {
+            response: Some(jsonrpc_invalid_request(id)),
+            session_id: None,
+        };
+    };
+    // Session id sent by the client, echoed back on list/call responses
+    let request_session_id = headers
+        .get(MCP_SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
 
     match method {
         "initialize" => {
@@ -2878,7 +3171,7 @@ pub(super) async fn mcp_post(
                     repo_path,
                 },
             );
-            let instructions = build_mcp_instructions(&state, client_name);
+            let instructions = build_mcp_instructions(state, client_name);
 
             let response = serde_json::json!({
                 "jsonrpc": "2.0",
@@ -2897,20 +3190,19 @@ pub(super) async fn mcp_post(
                 }
             });
 
-            (
-                StatusCode::OK,
-                [(MCP_SESSION_HEADER, session_id)],
-                Json(response),
-            )
-                .into_response()
+            McpReply {
+                response: Some(response),
+                session_id: Some(session_id),
+            }
         }
 
-        "notifications/initialized" => StatusCode::ACCEPTED.into_response(),
+        "notifications/initialized" => McpReply {
+            response: None,
+            session_id: None,
+        },
 
         "tools/list" => {
-            let list_session_id = headers
-                .get(MCP_SESSION_HEADER)
-                .and_then(|v| v.to_str().ok());
+            let list_session_id = request_session_id.as_deref();
             if let Some(sid) = list_session_id
                 && let Some(mut meta) = state.mcp_sessions.get_mut(sid)
             {
@@ -2925,31 +3217,25 @@ pub(super) async fn mcp_post(
                 .mcp_upstream_registry
                 .await_initial_settle(std::time::Duration::from_secs(3))
                 .await;
-            let tools = merged_tool_definitions(&state, list_session_id);
+            let tools = merged_tool_definitions(state, list_session_id);
             let response = serde_json::json!({
                 "jsonrpc": "2.0",
                 "id": id,
                 "result": { "tools": tools }
             });
-            let mut resp = Json(response).into_response();
-            if let Some(sid) = headers
-                .get(MCP_SESSION_HEADER)
-                .and_then(|v| v.to_str().ok())
-                && let Ok(val) = sid.parse()
-            {
-                resp.headers_mut().insert(MCP_SESSION_HEADER, val);
+            McpReply {
+                response: Some(response),
+                session_id: request_session_id,
             }
-            resp
         }
 
         "tools/call" => {
             // Validate MCP session. If the session ID is stale (e.g. app restarted, or
             // long-lived client like Claude Code lost its session), auto-recover by
             // re-registering the session instead of returning an error.
-            let is_cc_ua = detect_claude_code_from_headers(&headers);
-            let session_valid = headers
-                .get(MCP_SESSION_HEADER)
-                .and_then(|v| v.to_str().ok())
+            let is_cc_ua = detect_claude_code_from_headers(headers);
+            let session_valid = request_session_id
+                .as_deref()
                 .map(|sid| {
                     if let Some(mut meta) = state.mcp_sessions.get_mut(sid) {
                         meta.last_activity = std::time::Instant::now();
@@ -2984,7 +3270,10 @@ pub(super) async fn mcp_post(
                     "id": id,
                     "error": { "code": -32600, "message": "mcp-session-id header required. Call initialize first." }
                 });
-                return Json(response).into_response();
+                return McpReply {
+                    response: Some(response),
+                    session_id: None,
+                };
             }
 
             let params = body
@@ -2996,14 +3285,11 @@ pub(super) async fn mcp_post(
                 .get("arguments")
                 .cloned()
                 .unwrap_or(serde_json::json!({}));
-            let session_id_str = headers
-                .get(MCP_SESSION_HEADER)
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.to_string());
+            let session_id_str = request_session_id.clone();
 
             // Route upstream-prefixed tools ({upstream}__{tool}) via the proxy registry.
             // Native tools (no "__") go through the sync handler via spawn_blocking.
-            let allowed = resolve_allowed_upstreams(&state, session_id_str.as_deref());
+            let allowed = resolve_allowed_upstreams(state, session_id_str.as_deref());
             let (result, is_error) = if tool_name.contains("__") {
                 match state
                     .mcp_upstream_registry
@@ -3015,8 +3301,9 @@ pub(super) async fn mcp_post(
                 }
             } else {
                 let result = handle_mcp_tool_call(
-                    &state,
+                    state,
                     addr,
+                    authenticated,
                     &tool_name,
                     &args,
                     session_id_str.as_deref(),
@@ -3034,15 +3321,10 @@ pub(super) async fn mcp_post(
                     "isError": is_error
                 }
             });
-            let mut resp = Json(response).into_response();
-            if let Some(sid) = headers
-                .get(MCP_SESSION_HEADER)
-                .and_then(|v| v.to_str().ok())
-                && let Ok(val) = sid.parse()
-            {
-                resp.headers_mut().insert(MCP_SESSION_HEADER, val);
+            McpReply {
+                response: Some(response),
+                session_id: request_session_id,
             }
-            resp
         }
 
         other => {
@@ -3051,7 +3333,10 @@ pub(super) async fn mcp_post(
                 "id": id,
                 "error": { "code": -32601, "message": format!("Method not found: {}", other) }
             });
-            Json(response).into_response()
+            McpReply {
+                response: Some(response),
+                session_id: None,
+            }
         }
     }
 }
@@ -3198,6 +3483,72 @@ pub(super) async fn mcp_delete(
 
 // ── Unified handlers (merged tools) ──────────────────────────────────────
 
+/// Issues tool: list, get and comment on a repo's GitHub issues.
+async fn handle_issues(
+    state: &Arc<AppState>,
+    addr: SocketAddr,
+    args: &serde_json::Value,
+) -> serde_json::Value {
+    let action = match require_action(args, "issues", ISSUES_ACTIONS) {
+        Ok(a) => a,
+        Err(e) => return e,
+    };
+    let path = match require_path(args, action) {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    if let Err(e) = validate_mcp_repo_path(&path) {
+        return e;
+    }
+    match action {
+        "list" => {
+            let state_filter = args["state"].as_str().unwrap_or("open");
+            if let Err(e) = crate::github::issue_states_for_filter(state_filter) {
+                return serde_json::json!({"error": e});
+            }
+            let labels: Vec<String> = args["labels"]
+                .as_array()
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            match crate::github::list_issues_impl(&path, state_filter, &labels, state).await {
+                Ok(issues) => to_json_or_error(issues),
+                Err(e) => serde_json::json!({"error": e}),
+            }
+        }
+        "get" => {
+            let Some(number) = args["number"].as_i64() else {
+                return serde_json::json!({"error": "Missing required parameter: number"});
+            };
+            match crate::github::get_issue_impl(&path, number, state).await {
+                Ok(issue) => to_json_or_error(issue),
+                Err(e) => serde_json::json!({"error": e}),
+            }
+        }
+        "comment" => {
+            if !addr.ip().is_loopback() {
+                return serde_json::json!({
+                    "error": "Issue comments are restricted to localhost connections"
+                });
+            }
+            let Some(number) = args["number"].as_i64() else {
+                return serde_json::json!({"error": "Missing required parameter: number"});
+            };
+            let body = args["body"].as_str().unwrap_or("");
+            match crate::github::add_issue_comment_impl(&path, number, body, state).await {
+                Ok(url) => serde_json::json!({"url": url}),
+                Err(e) => serde_json::json!({"error": e}),
+            }
+        }
+        other => serde_json::json!({"error": format!(
+            "Unknown action '{}' for tool 'issues'. Available: {}", other, ISSUES_ACTIONS
+        )}),
+    }
+}
+
 /// Merged repo tool: dispatches to workspace, github, or worktree handlers.
 async fn handle_repo(
     state: &Arc<AppState>,
@@ -3222,12 +3573,134 @@ async fn handle_repo(
         "worktree_remove" => {
             handle_worktree(state, &remap_action(args, "remove"), is_claude_code).await
         }
+        "stash" => handle_stash(state, args).await,
+        "file_diff" => handle_file_diff(args).await,
+        "commit" => handle_commit(state, args).await,
         other => serde_json::json!({"error": format!(
             "Unknown action '{}' for tool 'repo'. Available: {}", other, REPO_ACTIONS
         )}),
     }
 }
 
+async fn handle_stash(state: &Arc<AppState>, args: &serde_json::Value) -> serde_json::Value {
+    let path = match require_path(args, "stash") {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    if let Err(e) = validate_mcp_repo_path(&path) {
+        return e;
+    }
+    let Some(subaction) = args["subaction"].as_str().map(String::from) else {
+        return serde_json::json!({"error": format!(
+            "Missing required parameter: subaction. Available: {STASH_SUBACTIONS}"
+        )});
+    };
+    let message = args["message"].as_str().map(String::from);
+    let index = match &args["index"] {
+        serde_json::Value::Null => None,
+        v => match v.as_u64().map(u32::try_from) {
+            Some(Ok(n)) => Some(n),
+            _ => {
+                return serde_json::json!({"error": format!(
+                    "Invalid index: {v} (expected N in stash@{{N}}, 0 to {})",
+                    u32::MAX
+                )});
+            }
+        },
+    };
+    let state = state.clone();
+    let res = tokio::task::spawn_blocking(move || {
+        let r = crate::git::stash_action_impl(&path, &subaction, message.as_deref(), index)?;
+        if subaction != "list" {
+            state.invalidate_repo_caches(&path);
+        }
+        Ok::<_, String>(r)
+    })
+    .await;
+    match res {
+        Ok(Ok(v)) => v,
+        Ok(Err(e)) => serde_json::json!({"error": e}),
+        Err(e) => serde_json::json!({"error": format!("Task failed: {e}")}),
+    }
+}
+
+/// Stage and commit, mirroring `POST /repo/commit`.
+async fn handle_commit(state: &Arc<AppState>, args: &serde_json::Value) -> serde_json::Value {
+    let path = match require_path(args, "commit") {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    if let Err(e) = validate_mcp_repo_path(&path) {
+        return e;
+    }
+    let message = args["message"].as_str().unwrap_or("").to_string();
+    let files: Vec<String> = args["files"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    let all = args["all"].as_bool().unwrap_or(false);
+    let state = state.clone();
+    let res = tokio::task::spawn_blocking(move || {
+        let r = crate::git::commit_impl(std::path::Path::new(&path), &message, &files, all, false);
+        if r.is_ok() {
+            state.invalidate_repo_caches(&path);
+        }
+        r
+    })
+    .await;
+    match res {
+        Ok(Ok(sha)) => serde_json::json!({"sha": sha}),
+        Ok(Err(e)) => {
+            let mut body = serde_json::json!(e);
+            body["error"] = serde_json::json!(e.to_string());
+            body
+        }
+        Err(e) => serde_json::json!({"error": format!("Task failed: {e}")}),
+    }
+}
+
+/// Unified diff of a single file, mirroring `GET /repo/file-diff`.
+async fn handle_file_diff(args: &serde_json::Value) -> serde_json::Value {
+    let path = match require_path(args, "file_diff") {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    if let Err(e) = validate_mcp_repo_path(&path) {
+        return e;
+    }
+    let Some(file) = args["file"].as_str().filter(|f| !f.is_empty()) else {
+        return serde_json::json!({"error": "Action 'file_diff' requires 'file' (path relative to the repo)"});
+    };
+    let rel = std::path::Path::new(file);
+    if rel.is_absolute()
+        || rel
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return serde_json::json!({"error": format!("Invalid file '{file}': must be relative to the repo without '..'")});
+    }
+    let staged = args["staged"].as_bool().unwrap_or(false);
+    let scope = staged.then(|| "staged".to_string());
+    match crate::git::get_file_diff(path.clone(), file.to_string(), scope, None).await {
+        Ok(diff) if diff.trim().is_empty() => {
+            let err = if !staged && !std::path::Path::new(&path).join(file).exists() {
+                format!("File '{file}' not found or not tracked")
+            } else if staged {
+                format!("No staged changes for '{file}'")
+            } else {
+                format!("No changes for '{file}'")
+            };
+            serde_json::json!({"error": err})
+        }
+        Ok(diff) => serde_json::json!({"file": file, "staged": staged, "diff": diff}),
+        Err(e) => serde_json::json!({"error": e}),
+    }
+}
+
 /// Merged agent tool: original agent actions + messaging actions.
 fn handle_agent_unified(
     state: &Arc<AppState>,
@@ -3546,7 +4019,7 @@ pub(crate) fn test_mcp_tool_definitions() -> serde_json::Value {
 }
 #[cfg(test)]
 pub(crate) fn test_translate_special_key(key: &str) -> Option<&'static str> {
-    translate_special_key(key)
+    crate::pty::translate_special_key(key)
 }
 #[cfg(test)]
 pub(crate) fn test_validate_mcp_repo_path(path: &str) -> Result<(), serde_json::Value> {
@@ -3580,6 +4053,8 @@ mod tests {
             github_token: parking_lot::RwLock::new(None),
             github_token_source: parking_lot::RwLock::new(Default::default()),
             github_circuit_breaker: crate::github::GitHubCircuitBreaker::new(),
+            gitlab_token: parking_lot::RwLock::new(None),
+            gitlab_circuit_breaker: crate::github::GitHubCircuitBreaker::new(),
             github_poller: parking_lot::Mutex::new(None),
             github_viewer_login: parking_lot::RwLock::new(None),
             github_rate_limit_remaining: std::sync::atomic::AtomicU32::new(u32::MAX),
@@ -3587,6 +4062,7 @@ mod tests {
             ipc_started: std::sync::atomic::AtomicBool::new(false),
             session_token: parking_lot::RwLock::new(uuid::Uuid::new_v4().to_string()),
             auth_rate_limits: dashmap::DashMap::new(),
+            auth_failure_ips: dashmap::DashMap::new(),
             #[cfg(feature = "desktop")]
             app_handle: parking_lot::RwLock::new(None),
             plugin_watchers: dashmap::DashMap::new(),
@@ -3632,6 +4108,7 @@ mod tests {
             shell_states: dashmap::DashMap::new(),
             terminal_rows: dashmap::DashMap::new(),
             exit_codes: dashmap::DashMap::new(),
+            recordings: dashmap::DashMap::new(),
             shell_state_since_ms: dashmap::DashMap::new(),
             loaded_plugins: dashmap::DashMap::new(),
             relay: crate::state::RelayState::new(),
@@ -3654,6 +4131,14 @@ mod tests {
                 crate::tailscale::TailscaleState::NotInstalled,
             ),
             push_store: crate::push::PushStore::load(&std::env::temp_dir()),
+            notification_throttle: Default::default(),
+            resize_debouncer: Default::default(),
+            poll_activity: Default::default(),
+            remote_port: std::sync::atomic::AtomicU16::new(0),
+            active_selection: parking_lot::RwLock::new(None),
+            session_records: dashmap::DashMap::new(),
+            session_records_dirty: std::sync::atomic::AtomicBool::new(false),
+            recoverable_sessions: parking_lot::RwLock::new(Vec::new()),
             desktop_window_focused: std::sync::atomic::AtomicBool::new(true),
             server_start_time: std::time::Instant::now(),
             term_aliases: dashmap::DashMap::new(),
@@ -3725,6 +4210,33 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn session_create_respects_configured_max_sessions() {
+        let state = test_state();
+        state.config.write().max_concurrent_sessions = 2;
+        let args = serde_json::json!({"action": "create"});
+
+        let mut created = Vec::new();
+        for _ in 0..2 {
+            let result = handle_session(&state, &args, None);
+            // Skip if PTY cannot be opened (sandbox/CI without /dev/ptmx access)
+            if result.get("error").is_some() {
+                eprintln!("Skipping: PTY not available in this environment");
+                return;
+            }
+            created.push(result["session_id"].as_str().unwrap().to_string());
+        }
+
+        let third = handle_session(&state, &args, None);
+        let err = third["error"].as_str().unwrap();
+        assert!(err.contains("Max concurrent sessions (2)"), "{err}");
+        assert_eq!(state.sessions.len(), 2);
+
+        for sid in created {
+            crate::pty::kill_pty_core(&state, &sid);
+        }
+    }
+
     #[tokio::test]
     async fn session_create_registers_vt_log_and_last_output() {
         let state = test_state();
@@ -4375,10 +4887,12 @@ mod tests {
                 "session",
                 "agent",
                 "repo",
+                "issues",
                 "ui",
                 "plugin_dev_guide",
                 "config",
                 "debug",
+                "fs",
                 "ai_terminal_read_screen",
                 "ai_terminal_send_input",
                 "ai_terminal_send_key",
@@ -4393,7 +4907,7 @@ mod tests {
                 "ai_terminal_run_command",
                 "ai_terminal_drive_agent",
             ],
-            "native_tool_definitions must return 7 base tools + 13 ai_terminal_* tools in order"
+            "native_tool_definitions must return 9 base tools + 13 ai_terminal_* tools in order"
         );
     }
 
@@ -4461,12 +4975,17 @@ mod tests {
             "worktree_list",
             "worktree_create",
             "worktree_remove",
+            "stash",
+            "file_diff",
         ] {
             assert!(
                 action_desc.contains(action),
                 "repo action description must include '{action}'"
             );
         }
+        let props = &repo["inputSchema"]["properties"];
+        assert_eq!(props["dry_run"]["type"], "boolean");
+        assert_eq!(props["force"]["type"], "boolean");
     }
 
     #[test]
@@ -4742,7 +5261,8 @@ mod tests {
     #[tokio::test]
     async fn call_tool_requires_tool_name() {
         let state = test_state();
-        let r = handle_call_tool(&state, loopback_addr(), &serde_json::json!({}), None).await;
+        let r =
+            handle_call_tool(&state, loopback_addr(), false, &serde_json::json!({}), None).await;
         assert!(r["error"].as_str().unwrap().contains("tool_name"));
     }
 
@@ -4753,6 +5273,7 @@ mod tests {
             let r = handle_call_tool(
                 &state,
                 loopback_addr(),
+                false,
                 &serde_json::json!({ "tool_name": meta, "arguments": { "query": "x" } }),
                 None,
             )
@@ -4772,6 +5293,7 @@ mod tests {
         let r = handle_call_tool(
             &state,
             loopback_addr(),
+            false,
             &serde_json::json!({ "tool_name": "workspace", "arguments": { "action": "active" } }),
             None,
         )
@@ -4785,6 +5307,7 @@ mod tests {
         let r = handle_call_tool(
             &state,
             loopback_addr(),
+            false,
             &serde_json::json!({ "tool_name": "nonsense_xyz", "arguments": {} }),
             None,
         )
@@ -4801,6 +5324,7 @@ mod tests {
         let r = handle_call_tool(
             &state,
             loopback_addr(),
+            false,
             &serde_json::json!({ "tool_name": "session", "arguments": {} }),
             None,
         )
@@ -4820,6 +5344,7 @@ mod tests {
         let r = handle_call_tool(
             &state,
             non_loopback_addr(),
+            false,
             &serde_json::json!({
                 "tool_name": "config",
                 "arguments": { "action": "save", "config": {} }
@@ -4842,6 +5367,7 @@ mod tests {
         let r = handle_call_tool(
             &state,
             loopback_addr(),
+            false,
             &serde_json::json!({ "tool_name": "session" }),
             None,
         )
@@ -4858,6 +5384,7 @@ mod tests {
         let r = handle_call_tool(
             &state,
             loopback_addr(),
+            false,
             &serde_json::json!({ "tool_name": "fake_upstream__some_tool", "arguments": {} }),
             None,
         )
@@ -4878,6 +5405,7 @@ mod tests {
         let r = handle_mcp_tool_call(
             &state,
             loopback_addr(),
+            false,
             "search_tools",
             &serde_json::json!({ "query": "terminal" }),
             None,
@@ -4892,6 +5420,7 @@ mod tests {
         let r = handle_mcp_tool_call(
             &state,
             loopback_addr(),
+            false,
             "get_tool_schema",
             &serde_json::json!({ "tool_name": "agent" }),
             None,
@@ -4906,6 +5435,7 @@ mod tests {
         let r = handle_mcp_tool_call(
             &state,
             loopback_addr(),
+            false,
             "call_tool",
             &serde_json::json!({ "tool_name": "session", "arguments": {} }),
             None,
@@ -4920,6 +5450,7 @@ mod tests {
         let r = handle_mcp_tool_call(
             &state,
             loopback_addr(),
+            false,
             "repo",
             &serde_json::json!({ "action": "list" }),
             None,
@@ -4932,6 +5463,148 @@ mod tests {
         );
     }
 
+    /// Point the config dir at `cfg` and register `repo` in repositories.json.
+    fn register_fs_repo(cfg: &std::path::Path, repo: &str) -> impl Drop {
+        let guard = crate::config::set_config_dir_override(cfg.to_path_buf());
+        std::fs::write(
+            cfg.join("repositories.json"),
+            serde_json::json!({"repositories": {repo: {}}}).to_string(),
+        )
+        .unwrap();
+        guard
+    }
+
+    #[tokio::test]
+    async fn handle_mcp_tool_call_fs_rejects_bad_paths() {
+        let state = test_state();
+        state.config.write().mcp_allow_file_writes = true;
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().to_string_lossy().to_string();
+        let cfg = tempfile::tempdir().unwrap();
+        let _guard = register_fs_repo(cfg.path(), &repo);
+        let cases = [
+            serde_json::json!({"action": "read", "path": "relative/repo", "file": "a.txt"}),
+            serde_json::json!({"action": "read", "path": repo, "file": "../escape.txt"}),
+            serde_json::json!({"action": "read", "path": repo, "file": "/etc/passwd"}),
+            serde_json::json!({"action": "write", "path": repo, "file": "a\0b", "content": "x"}),
+            serde_json::json!({"action": "list", "path": repo, "dir": "sub/../.."}),
+            serde_json::json!({"action": "mkdir", "path": repo, "dir": "../outside"}),
+        ];
+        for args in cases {
+            let r = handle_mcp_tool_call(&state, loopback_addr(), false, "fs", &args, None).await;
+            assert!(
+                r["error"].is_string(),
+                "expected rejection for {args}, got: {r}"
+            );
+        }
+        assert!(!dir.path().parent().unwrap().join("outside").exists());
+    }
+
+    #[tokio::test]
+    async fn handle_mcp_tool_call_fs_write_gated() {
+        let state = test_state();
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().to_string_lossy().to_string();
+        let cfg = tempfile::tempdir().unwrap();
+        let _guard = register_fs_repo(cfg.path(), &repo);
+        let args =
+            serde_json::json!({"action": "write", "path": repo, "file": "a.txt", "content": "x"});
+
+        let r = handle_mcp_tool_call(&state, loopback_addr(), false, "fs", &args, None).await;
+        assert!(
+            r["error"]
+                .as_str()
+                .unwrap()
+                .contains("mcp_allow_file_writes")
+        );
+
+        state.config.write().mcp_allow_file_writes = true;
+        let r = handle_mcp_tool_call(&state, remote_addr(), true, "fs", &args, None).await;
+        assert!(r["error"].as_str().unwrap().contains("localhost"));
+        assert!(!dir.path().join("a.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn handle_mcp_tool_call_fs_rejects_unregistered_root() {
+        let state = test_state();
+        let registered = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        std::fs::write(other.path().join("secret.txt"), "x").unwrap();
+        let cfg = tempfile::tempdir().unwrap();
+        let _guard = register_fs_repo(cfg.path(), &registered.path().to_string_lossy());
+
+        let other_root = other.path().to_string_lossy().to_string();
+        for args in [
+            serde_json::json!({"action": "read", "path": other_root, "file": "secret.txt"}),
+            serde_json::json!({"action": "list", "path": other_root}),
+            serde_json::json!({"action": "read", "path": "/", "file": "etc/passwd"}),
+        ] {
+            let r = handle_mcp_tool_call(&state, loopback_addr(), false, "fs", &args, None).await;
+            assert!(
+                r["error"]
+                    .as_str()
+                    .is_some_and(|e| e.contains("registered repository")),
+                "expected unregistered-root rejection for {args}, got: {r}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn handle_mcp_tool_call_fs_read_requires_local_or_auth() {
+        let state = test_state();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "hi").unwrap();
+        let repo = dir.path().to_string_lossy().to_string();
+        let cfg = tempfile::tempdir().unwrap();
+        let _guard = register_fs_repo(cfg.path(), &repo);
+        let args = serde_json::json!({"action": "read", "path": repo, "file": "a.txt"});
+
+        let r = handle_mcp_tool_call(&state, remote_addr(), false, "fs", &args, None).await;
+        assert!(r["error"].as_str().unwrap().contains("authenticated"));
+
+        let r = handle_mcp_tool_call(&state, remote_addr(), true, "fs", &args, None).await;
+        assert_eq!(r["content"], "hi", "authenticated remote read failed: {r}");
+    }
+
+    #[tokio::test]
+    async fn handle_mcp_tool_call_fs_round_trip() {
+        let state = test_state();
+        state.config.write().mcp_allow_file_writes = true;
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().to_string_lossy().to_string();
+        let cfg = tempfile::tempdir().unwrap();
+        let _guard = register_fs_repo(cfg.path(), &repo);
+        let call = |args: serde_json::Value| {
+            let state = state.clone();
+            async move { handle_mcp_tool_call(&state, loopback_addr(), false, "fs", &args, None).await }
+        };
+
+        let r = call(serde_json::json!({
+            "action": "write", "path": repo, "file": "notes/today.md",
+            "content": "# Hi\n", "create_parents": true
+        }))
+        .await;
+        assert_eq!(r["bytes_written"], 5, "write failed: {r}");
+
+        let r = call(serde_json::json!({"action": "read", "path": repo, "file": "notes/today.md"}))
+            .await;
+        assert_eq!(r["content"], "# Hi\n");
+
+        let r =
+            call(serde_json::json!({"action": "mkdir", "path": repo, "dir": "empty/nested"})).await;
+        assert_eq!(r["ok"], true, "mkdir failed: {r}");
+
+        let r = call(serde_json::json!({"action": "list", "path": repo, "dir": "notes"})).await;
+        let names: Vec<&str> = r["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|e| e["name"].as_str())
+            .collect();
+        assert_eq!(names, vec!["today.md"]);
+        assert!(dir.path().join("empty/nested").is_dir());
+    }
+
     #[tokio::test]
     async fn handle_mcp_tool_call_routes_agent_messaging() {
         let state = test_state();
@@ -4939,6 +5612,7 @@ mod tests {
         let r = handle_mcp_tool_call(
             &state,
             loopback_addr(),
+            false,
             "agent",
             &serde_json::json!({ "action": "register" }),
             None,
@@ -4956,6 +5630,7 @@ mod tests {
         let r = handle_mcp_tool_call(
             &state,
             loopback_addr(),
+            false,
             "ui",
             &serde_json::json!({ "action": "toast", "title": "test" }),
             None,
@@ -4973,6 +5648,7 @@ mod tests {
         let r = handle_mcp_tool_call(
             &state,
             loopback_addr(),
+            false,
             "debug",
             &serde_json::json!({ "action": "sessions" }),
             None,
@@ -4984,6 +5660,51 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn handle_mcp_tool_call_issues_requires_path() {
+        let state = test_state();
+        let r = handle_mcp_tool_call(
+            &state,
+            loopback_addr(),
+            false,
+            "issues",
+            &serde_json::json!({ "action": "list" }),
+            None,
+        )
+        .await;
+        assert!(
+            r["error"]
+                .as_str()
+                .unwrap_or("")
+                .contains("requires 'path'"),
+            "issues without path should error, got: {r}"
+        );
+    }
+
+    #[tokio::test]
+    async fn handle_mcp_tool_call_issues_comment_rejects_remote() {
+        let state = test_state();
+        let remote: SocketAddr = "192.168.1.50:40000".parse().unwrap();
+        let r = handle_mcp_tool_call(
+            &state,
+            remote,
+            false,
+            "issues",
+            &serde_json::json!({
+                "action": "comment",
+                "path": std::env::temp_dir().to_string_lossy(),
+                "number": 1,
+                "body": "hi"
+            }),
+            None,
+        )
+        .await;
+        assert!(
+            r["error"].as_str().unwrap_or("").contains("localhost"),
+            "remote issue comment must be rejected, got: {r}"
+        );
+    }
+
     #[tokio::test]
     async fn handle_mcp_tool_call_old_names_return_unknown() {
         let state = test_state();
@@ -4991,6 +5712,7 @@ mod tests {
             let r = handle_mcp_tool_call(
                 &state,
                 loopback_addr(),
+                false,
                 old_name,
                 &serde_json::json!({ "action": "list" }),
                 None,
@@ -5366,6 +6088,7 @@ mod tests {
                 cwd: Some("/Gits/personal/beta".to_string()),
                 display_name: None,
                 shell: "true".to_string(),
+                token_usage: Default::default(),
             }),
         );
 
@@ -6171,6 +6894,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn session_status_includes_exit_info_when_signaled() {
+        let state = test_state();
+        let sid = "s-exit-info-test";
+        state
+            .session_states
+            .insert(sid.to_string(), crate::state::SessionState::default());
+        state.shell_states.insert(
+            sid.to_string(),
+            std::sync::atomic::AtomicU8::new(crate::pty::SHELL_IDLE),
+        );
+        state.exit_codes.insert(sid.to_string(), 139);
+        state.exit_reasons.insert(
+            sid.to_string(),
+            crate::state::ExitInfo {
+                reason: crate::state::ExitReason::Signaled,
+                signal: Some(11),
+                core_dumped: true,
+            },
+        );
+
+        let result = handle_session(
+            &state,
+            &serde_json::json!({"action": "status", "session_id": sid}),
+            None,
+        );
+        assert_eq!(
+            result["exit_info"],
+            serde_json::json!({"reason": "signaled", "signal": 11, "core_dumped": true}),
+            "exit_info missing: {result}"
+        );
+    }
+
     #[test]
     fn session_status_includes_idle_since_ms_when_idle() {
         let state = test_state();
@@ -6966,6 +7722,35 @@ mod tests {
         assert_eq!(config.diff_triage_system_prompt.as_deref(), Some("Custom"));
     }
 
+    #[test]
+    fn config_save_keeps_security_settings() {
+        let state = test_state();
+        let dir = tempfile::tempdir().unwrap();
+        let _guard = crate::config::set_config_dir_override(dir.path().to_path_buf());
+        state.config.write().services.auth.password_hash = "$2b$12$hash".to_string();
+
+        let mut config = handle_config(&state, localhost(), &serde_json::json!({"action": "get"}));
+        config["font_size"] = serde_json::json!(20);
+        config["mcp_allow_file_writes"] = serde_json::json!(true);
+        config["ai_terminal_mcp_enabled"] = serde_json::json!(true);
+        config["services"]["server"]["enabled"] = serde_json::json!(true);
+        config["services"]["auth"]["lan_auth_bypass"] = serde_json::json!(true);
+        let r = handle_config(
+            &state,
+            localhost(),
+            &serde_json::json!({"action": "save", "config": config}),
+        );
+        assert_eq!(r["ok"], true, "save failed: {r}");
+
+        let current = state.config.read();
+        assert_eq!(current.font_size, 20);
+        assert!(!current.mcp_allow_file_writes);
+        assert!(!current.ai_terminal_mcp_enabled);
+        assert!(!current.services.server.enabled);
+        assert!(!current.services.auth.lan_auth_bypass);
+        assert_eq!(current.services.auth.password_hash, "$2b$12$hash");
+    }
+
     #[test]
     fn config_list_prompts_empty_library() {
         let state = test_state();
@@ -7080,6 +7865,7 @@ mod tests {
         let r = handle_mcp_tool_call(
             &state,
             loopback_addr(),
+            false,
             "ui",
             &serde_json::json!({ "action": "screenshot" }),
             None,
@@ -7177,6 +7963,7 @@ mod tests {
         let r = handle_mcp_tool_call(
             &state,
             remote_addr,
+            false,
             "ui",
             &serde_json::json!({ "action": "screenshot", "id": "x" }),
             None,
@@ -539,24 +539,36 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
     let mut routes = Router::new()
         // Health & version
         .route("/health", get(session::health))
+        .route("/health/diagnostics", get(session::diagnostics))
         .route("/api/version", get(session::app_version))
+        .route("/whoami", get(session::whoami))
         // Session lifecycle
         .route(
             "/sessions",
             get(session::list_sessions).post(session::create_session),
         )
         .route("/sessions/{id}/write", post(session::write_to_session))
+        .route("/sessions/broadcast", post(session::broadcast_input))
+        .route(
+            "/sessions/recoverable",
+            get(session::list_recoverable_sessions),
+        )
         .route("/sessions/{id}/name", put(session::set_session_name))
         .route("/sessions/{id}/resize", post(session::resize_session))
+        .route("/sessions/{id}/record", post(session::record_session))
+        .route("/sessions/{id}/export", get(session::export_session))
         .route("/sessions/{id}/output", get(session::get_output))
+        .route("/sessions/{id}/search", get(session::search_output))
         .route("/sessions/{id}/pause", post(session::pause_session))
         .route("/sessions/{id}/resume", post(session::resume_session))
+        .route("/sessions/{id}/signal", post(session::signal_session))
         .route("/sessions/{id}/kitty-flags", get(session::get_kitty_flags))
         .route(
             "/sessions/{id}/foreground",
             get(session::get_foreground_process),
         )
         .route("/sessions/{id}/shell-state", get(session::get_shell_state))
+        .route("/sessions/{id}/context", get(session::get_session_context))
         .route("/sessions/{id}/last-prompt", get(session::get_last_prompt))
         .route(
             "/sessions/{id}/input-buffer",
@@ -566,6 +578,10 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/sessions/{id}/leaf-pid",
             get(session::get_session_leaf_pid),
         )
+        .route(
+            "/sessions/{id}/resources",
+            get(session::get_session_resources),
+        )
         .route(
             "/sessions/{id}/has-foreground",
             get(session::has_foreground_process),
@@ -645,6 +661,11 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
         .route("/stats", get(session::get_stats))
         .route("/metrics", get(session::get_metrics))
         .route("/process/stats", get(session::get_process_stats))
+        .route("/process/orphans", get(session::list_orphaned_processes))
+        .route(
+            "/process/orphans/kill",
+            post(session::kill_orphaned_process),
+        )
         .route("/process/monitor", get(session::process_monitor_panel))
         // Git/GitHub
         .route("/repo/info", get(git_routes::repo_info))
@@ -656,13 +677,25 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
         .route("/repo/prs", get(github_routes::repo_pr_statuses))
         .route("/repo/branches", get(git_routes::repo_branches))
         .route("/repo/ci", get(github_routes::repo_ci_checks))
+        .route("/repo/pr-timeline", get(github_routes::repo_pr_timeline))
+        .route(
+            "/repo/ci/batch",
+            post(github_routes::repo_hydrate_pr_checks),
+        )
         .route("/repo/pr-diff", get(github_routes::repo_pr_diff))
         .route("/repo/approve-pr", post(github_routes::repo_approve_pr))
+        .route(
+            "/repo/pr-review-request",
+            post(github_routes::repo_request_reviewers),
+        )
+        .route("/repo/pr-comment", post(github_routes::repo_add_pr_comment))
         .route(
             "/repo/branches/merged",
             get(git_routes::repo_merged_branches),
         )
         .route("/repo/summary", get(git_routes::repo_summary))
+        .route("/repo/stats", get(git_routes::repo_stats))
+        .route("/repo/scan", get(git_routes::scan_for_repos_http))
         .route("/repo/structure", get(git_routes::repo_structure))
         .route(
             "/repo/diff-stats/batch",
@@ -675,6 +708,12 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/repo/issues/reopen",
             post(github_routes::repo_reopen_issue),
         )
+        .route("/repo/issues/list", get(github_routes::repo_list_issues))
+        .route("/repo/issues/get", get(github_routes::repo_get_issue))
+        .route(
+            "/repo/issues/comment",
+            post(github_routes::repo_add_issue_comment),
+        )
         // GitHub poller
         .route(
             "/repo/github-poller/start",
@@ -685,6 +724,10 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/repo/github-poller/visibility",
             post(github_routes::poller_set_visibility),
         )
+        .route(
+            "/repo/github-poller/active",
+            post(github_routes::poller_set_active),
+        )
         .route(
             "/repo/github-poller/poll-repo",
             post(github_routes::poller_poll_repo),
@@ -830,10 +873,18 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/config/notifications",
             get(config_routes::get_notification_config).put(config_routes::put_notification_config),
         )
+        .route(
+            "/config/notifications/allow",
+            post(config_routes::notification_allowed_http),
+        )
         .route(
             "/config/ui-prefs",
             get(config_routes::get_ui_prefs).put(config_routes::put_ui_prefs),
         )
+        .route(
+            "/config/active-selection",
+            get(config_routes::get_active_selection).put(config_routes::put_active_selection),
+        )
         .route(
             "/config/repo-settings",
             get(config_routes::get_repo_settings).put(config_routes::put_repo_settings),
@@ -842,6 +893,14 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/config/repo-settings/has-custom",
             get(config_routes::check_has_custom_settings_http),
         )
+        .route(
+            "/config/repo-settings/reset",
+            post(config_routes::reset_repo_settings_http),
+        )
+        .route(
+            "/config/repo-settings/explain-env",
+            get(config_routes::explain_env_http),
+        )
         .route(
             "/config/repo-defaults",
             get(config_routes::get_repo_defaults).put(config_routes::put_repo_defaults),
@@ -850,6 +909,10 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/config/repositories",
             get(config_routes::get_repositories).put(config_routes::put_repositories),
         )
+        .route(
+            "/config/repositories/reorder",
+            post(config_routes::reorder_repositories_http),
+        )
         .route(
             "/config/pane-layout",
             get(config_routes::get_pane_layout).put(config_routes::put_pane_layout),
@@ -930,6 +993,14 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/config/keybindings",
             get(config_routes::get_keybindings).put(config_routes::put_keybindings),
         )
+        .route(
+            "/config/keybindings/export",
+            get(config_routes::export_keybindings_http),
+        )
+        .route(
+            "/config/keybindings/import",
+            post(config_routes::import_keybindings_http),
+        )
         .route(
             "/config/agents",
             get(config_routes::get_agents_config).put(config_routes::put_agents_config),
@@ -976,6 +1047,7 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
                 .post(log_routes::push_log)
                 .delete(log_routes::clear_logs),
         )
+        .route("/logs/ansi-html", post(log_routes::ansi_to_html))
         // Diagnostics
         .route(
             "/diagnostics",
@@ -989,6 +1061,10 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/worktrees",
             get(worktree_routes::list_worktrees_http).post(worktree_routes::create_worktree_http),
         )
+        .route(
+            "/worktrees/from-pr",
+            post(worktree_routes::create_worktree_from_pr_http),
+        )
         .route(
             "/worktrees/dir",
             get(worktree_routes::get_worktrees_dir_http),
@@ -1005,12 +1081,20 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/worktrees/finalize",
             post(worktree_routes::finalize_merged_worktree_http),
         )
+        .route(
+            "/worktrees/prune",
+            post(worktree_routes::prune_orphaned_worktrees_http),
+        )
         .route(
             "/worktrees/{branch}",
             delete(worktree_routes::remove_worktree_http),
         )
         // File operations
         .route("/repo/file", get(git_routes::read_file_http))
+        .route(
+            "/repo/recent-files",
+            get(git_routes::recent_files_http).post(git_routes::record_recent_file_http),
+        )
         .route("/repo/file-diff", get(git_routes::get_file_diff_http))
         .route(
             "/repo/markdown-files",
@@ -1070,6 +1154,14 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/agents/verify-session",
             post(agent_routes::verify_agent_session_http),
         )
+        .route(
+            "/agents/transcripts",
+            get(agent_routes::list_agent_transcripts_http),
+        )
+        .route(
+            "/agents/transcripts/prune",
+            post(agent_routes::prune_agent_transcripts_http),
+        )
         .route("/agents", get(agent_routes::detect_agents))
         .route(
             "/agents/detect",
@@ -1088,6 +1180,8 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             get(fs_routes::search_content_all_http),
         )
         .route("/fs/read", get(fs_routes::fs_read_file_http))
+        .route("/fs/read-info", get(fs_routes::fs_read_file_info_http))
+        .route("/fs/diff", get(fs_routes::diff_files_http))
         .route("/fs/read-editor", get(fs_routes::read_editor_file_http))
         .route("/fs/read-external", get(fs_routes::read_external_file_http))
         .route(
@@ -1155,13 +1249,28 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
         .route("/repo/stash/pop", post(git_routes::stash_pop_http))
         .route("/repo/stash/drop", post(git_routes::stash_drop_http))
         .route("/repo/stash/show", get(git_routes::stash_show_http))
+        .route(
+            "/repo/config",
+            get(git_routes::get_config_value_http).post(git_routes::set_config_value_http),
+        )
+        .route("/repo/lfs-status", get(git_routes::lfs_status_http))
         .route("/repo/file-history", get(git_routes::file_history_http))
         .route("/repo/file-blame", get(git_routes::file_blame_http))
+        .route("/repo/blame", get(git_routes::file_blame_http))
+        .route(
+            "/repo/blame-cache-stats",
+            get(git_routes::blame_cache_stats_http),
+        )
+        .route("/repo/for-path", get(git_routes::find_repo_for_path_http))
         // Git panel (story 064)
         .route(
             "/repo/gutter-changes",
             get(git_routes::get_gutter_changes_http),
         )
+        .route(
+            "/repo/conflict-markers",
+            post(git_routes::parse_conflict_markers_http),
+        )
         .route(
             "/repo/branches-detail",
             get(git_routes::get_branches_detail_http),
@@ -1186,6 +1295,15 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
         .route("/repo/commit-graph", get(git_routes::get_commit_graph_http))
         .route("/repo/create-branch", post(git_routes::create_branch_http))
         .route("/repo/delete-branch", post(git_routes::delete_branch_http))
+        .route("/repo/rebase", post(git_routes::rebase_branch_http))
+        .route("/repo/rebase/abort", post(git_routes::abort_rebase_http))
+        .route("/repo/cherry-pick", post(git_routes::cherry_pick_http))
+        .route(
+            "/repo/cherry-pick/abort",
+            post(git_routes::abort_cherry_pick_http),
+        )
+        .route("/repo/checkout", post(git_routes::checkout_branch_http))
+        .route("/repo/stash", post(git_routes::stash_http))
         .route(
             "/repo/delete-local-branch",
             post(git_routes::delete_local_branch_http),
@@ -1211,6 +1329,10 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
         .route("/events", get(sse_routes::sse_events))
         // MCP status + instructions
         .route("/mcp/status", get(config_routes::get_mcp_status_http))
+        .route(
+            "/mcp/remote-diagnosis",
+            get(config_routes::diagnose_remote_access_http),
+        )
         .route("/mcp/upstream-status", get(upstream_status_handler))
         .route(
             "/mcp/upstreams",
@@ -1268,6 +1390,10 @@ pub fn build_router(state: Arc<AppState>, remote_auth: bool, mcp_enabled: bool)
             "/api/plugins/{plugin_id}/pty/output",
             get(plugin_routes::plugin_pty_output),
         )
+        .route(
+            "/api/plugins/{plugin_id}/events",
+            post(plugin_routes::plugin_emit_event),
+        )
         .route(
             "/api/plugins/{plugin_id}/register",
             post(plugin_routes::plugin_register),
@@ -1354,25 +1480,36 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
         .with_state(state.clone());
 
     let routes = Router::new()
-        // Version (authenticated)
+        // Version + identity (authenticated)
         .route("/api/version", get(session::app_version))
+        .route("/whoami", get(session::whoami))
         // Session lifecycle
         .route(
             "/sessions",
             get(session::list_sessions).post(session::create_session),
         )
         .route("/sessions/{id}/write", post(session::write_to_session))
+        .route("/sessions/broadcast", post(session::broadcast_input))
+        .route(
+            "/sessions/recoverable",
+            get(session::list_recoverable_sessions),
+        )
         .route("/sessions/{id}/name", put(session::set_session_name))
         .route("/sessions/{id}/resize", post(session::resize_session))
+        .route("/sessions/{id}/record", post(session::record_session))
+        .route("/sessions/{id}/export", get(session::export_session))
         .route("/sessions/{id}/output", get(session::get_output))
+        .route("/sessions/{id}/search", get(session::search_output))
         .route("/sessions/{id}/pause", post(session::pause_session))
         .route("/sessions/{id}/resume", post(session::resume_session))
+        .route("/sessions/{id}/signal", post(session::signal_session))
         .route("/sessions/{id}/kitty-flags", get(session::get_kitty_flags))
         .route(
             "/sessions/{id}/foreground",
             get(session::get_foreground_process),
         )
         .route("/sessions/{id}/shell-state", get(session::get_shell_state))
+        .route("/sessions/{id}/context", get(session::get_session_context))
         .route("/sessions/{id}/last-prompt", get(session::get_last_prompt))
         .route(
             "/sessions/{id}/input-buffer",
@@ -1382,6 +1519,10 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
             "/sessions/{id}/leaf-pid",
             get(session::get_session_leaf_pid),
         )
+        .route(
+            "/sessions/{id}/resources",
+            get(session::get_session_resources),
+        )
         .route(
             "/sessions/{id}/has-foreground",
             get(session::has_foreground_process),
@@ -1474,6 +1615,8 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
             get(git_routes::repo_merged_branches),
         )
         .route("/repo/summary", get(git_routes::repo_summary))
+        .route("/repo/stats", get(git_routes::repo_stats))
+        .route("/repo/scan", get(git_routes::scan_for_repos_http))
         .route("/repo/structure", get(git_routes::repo_structure))
         .route(
             "/repo/diff-stats/batch",
@@ -1501,6 +1644,7 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
                 .post(log_routes::push_log)
                 .delete(log_routes::clear_logs),
         )
+        .route("/logs/ansi-html", post(log_routes::ansi_to_html))
         // Diagnostics
         .route(
             "/diagnostics",
@@ -1511,6 +1655,10 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
             "/worktrees",
             get(worktree_routes::list_worktrees_http).post(worktree_routes::create_worktree_http),
         )
+        .route(
+            "/worktrees/from-pr",
+            post(worktree_routes::create_worktree_from_pr_http),
+        )
         .route(
             "/worktrees/dir",
             get(worktree_routes::get_worktrees_dir_http),
@@ -1527,12 +1675,20 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
             "/worktrees/finalize",
             post(worktree_routes::finalize_merged_worktree_http),
         )
+        .route(
+            "/worktrees/prune",
+            post(worktree_routes::prune_orphaned_worktrees_http),
+        )
         .route(
             "/worktrees/{branch}",
             delete(worktree_routes::remove_worktree_http),
         )
         // File operations
         .route("/repo/file", get(git_routes::read_file_http))
+        .route(
+            "/repo/recent-files",
+            get(git_routes::recent_files_http).post(git_routes::record_recent_file_http),
+        )
         .route("/repo/file-diff", get(git_routes::get_file_diff_http))
         .route(
             "/repo/markdown-files",
@@ -1584,6 +1740,8 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
             get(fs_routes::search_content_all_http),
         )
         .route("/fs/read", get(fs_routes::fs_read_file_http))
+        .route("/fs/read-info", get(fs_routes::fs_read_file_info_http))
+        .route("/fs/diff", get(fs_routes::diff_files_http))
         // SECURITY: remote clients get the standard (10 MB) cap, NOT the large
         // editor cap. The 250 MB editor read is a desktop-local feature; serving
         // it over a (possibly metered/slow) remote link risks OOM/latency since
@@ -1650,13 +1808,28 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
         .route("/repo/stash/pop", post(git_routes::stash_pop_http))
         .route("/repo/stash/drop", post(git_routes::stash_drop_http))
         .route("/repo/stash/show", get(git_routes::stash_show_http))
+        .route(
+            "/repo/config",
+            get(git_routes::get_config_value_http).post(git_routes::set_config_value_http),
+        )
+        .route("/repo/lfs-status", get(git_routes::lfs_status_http))
         .route("/repo/file-history", get(git_routes::file_history_http))
         .route("/repo/file-blame", get(git_routes::file_blame_http))
+        .route("/repo/blame", get(git_routes::file_blame_http))
+        .route(
+            "/repo/blame-cache-stats",
+            get(git_routes::blame_cache_stats_http),
+        )
+        .route("/repo/for-path", get(git_routes::find_repo_for_path_http))
         // Git panel (story 064)
         .route(
             "/repo/gutter-changes",
             get(git_routes::get_gutter_changes_http),
         )
+        .route(
+            "/repo/conflict-markers",
+            post(git_routes::parse_conflict_markers_http),
+        )
         .route(
             "/repo/branches-detail",
             get(git_routes::get_branches_detail_http),
@@ -1681,6 +1854,15 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
         .route("/repo/commit-graph", get(git_routes::get_commit_graph_http))
         .route("/repo/create-branch", post(git_routes::create_branch_http))
         .route("/repo/delete-branch", post(git_routes::delete_branch_http))
+        .route("/repo/rebase", post(git_routes::rebase_branch_http))
+        .route("/repo/rebase/abort", post(git_routes::abort_rebase_http))
+        .route("/repo/cherry-pick", post(git_routes::cherry_pick_http))
+        .route(
+            "/repo/cherry-pick/abort",
+            post(git_routes::abort_cherry_pick_http),
+        )
+        .route("/repo/checkout", post(git_routes::checkout_branch_http))
+        .route("/repo/stash", post(git_routes::stash_http))
         .route(
             "/repo/delete-local-branch",
             post(git_routes::delete_local_branch_http),
@@ -1718,13 +1900,47 @@ pub fn build_remote_router(state: Arc<AppState>) -> Router {
     public_routes.merge(authed)
 }
 
+/// Stop the TCP listener and bring the server back without it (IPC only), as
+/// `restart_server` in lib.rs does for config changes. `run_headless` keeps
+/// running on the local listeners after this.
+pub(crate) fn restart_local_only(state: &Arc<AppState>) {
+    if let Some(tx) = state.server_shutdown.lock().take() {
+        let _ = tx.send(());
+    }
+    let state_arc = state.clone();
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().expect("tokio runtime for HTTP server restart");
+        rt.block_on(start_server(state_arc, true, false, None));
+    });
+}
+
+/// Bind the remote-access TCP listener. The IPv6 wildcard is bound
+/// dual-stack (`IPV6_V6ONLY` off) so IPv4 clients still reach `[::]` on hosts
+/// whose default is v6-only (Windows, some BSDs).
+fn bind_remote_listener(addr: std::net::SocketAddr) -> std::io::Result<std::net::TcpListener> {
+    use socket2::{Domain, Protocol, Socket, Type};
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    if addr.is_ipv6() && addr.ip().is_unspecified() {
+        socket.set_only_v6(false)?;
+    }
+    // Matches std's TcpListener::bind: quick rebind after a server restart.
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(128)?;
+    Ok(socket.into())
+}
+
 /// Start the HTTP API server.
 ///
 /// **Unix socket** (macOS/Linux): always starts at `<config_dir>/mcp.sock`.
 /// No auth, MCP always enabled. Used by the local MCP bridge.
 ///
 /// **TCP listener** (optional): when `remote_enabled` is true, binds to
-/// `0.0.0.0:{remote_access_port}` with Basic Auth.
+/// `{bind_address}:{remote_access_port}` with Basic Auth. `bind_address`
+/// defaults to `0.0.0.0` (dual-stack `[::]` with `ipv6_enabled`);
+/// `bind_interface` narrows the wildcard to one interface's address. An
+/// address not on this machine falls back to `127.0.0.1`.
 ///
 /// Both listeners share a single shutdown signal so `save_config` can restart
 /// the server cleanly.
@@ -1932,10 +2148,19 @@ pub async fn start_server(
     // Supports dual-protocol (HTTP+HTTPS on same port) when TLS cert is available.
     let tcp_handle = if remote_enabled {
         let base_port = config.services.server.port;
-        let host = if config.services.server.ipv6_enabled {
-            "[::]"
-        } else {
-            "0.0.0.0"
+        let mut host: std::net::IpAddr = match crate::configured_bind_ip(&config.services.server) {
+            Ok(Some(ip)) => ip,
+            Ok(None) if config.services.server.ipv6_enabled => {
+                std::net::Ipv6Addr::UNSPECIFIED.into()
+            }
+            Ok(None) => std::net::Ipv4Addr::UNSPECIFIED.into(),
+            Err(e) => {
+                tracing::error!(
+                    source = "mcp_http",
+                    "{e} — binding remote access to 127.0.0.1 only"
+                );
+                std::net::Ipv4Addr::LOCALHOST.into()
+            }
         };
         const MAX_PORT_ATTEMPTS: u16 = 3;
 
@@ -1944,8 +2169,20 @@ pub async fn start_server(
         let attempts = if base_port == 0 { 1 } else { MAX_PORT_ATTEMPTS };
         for attempt in 0..attempts {
             let port = base_port + attempt;
-            let bind_addr = format!("{host}:{port}");
-            match std::net::TcpListener::bind(&bind_addr) {
+            let mut bound = bind_remote_listener(std::net::SocketAddr::new(host, port));
+            // Host without IPv6 support: keep remote access up on IPv4.
+            if let Err(e) = &bound
+                && host == std::net::IpAddr::from(std::net::Ipv6Addr::UNSPECIFIED)
+                && e.kind() != std::io::ErrorKind::AddrInUse
+            {
+                tracing::warn!(
+                    source = "mcp_http",
+                    "Dual-stack bind on [::] failed ({e}) — falling back to 0.0.0.0"
+                );
+                host = std::net::Ipv4Addr::UNSPECIFIED.into();
+                bound = bind_remote_listener(std::net::SocketAddr::new(host, port));
+            }
+            match bound {
                 Ok(listener) => {
                     listener.set_nonblocking(true).ok();
                     if attempt > 0 {
@@ -1969,7 +2206,11 @@ pub async fn start_server(
         if let Some(listener) = listener_result {
             let addr = listener
                 .local_addr()
-                .unwrap_or_else(|_| std::net::SocketAddr::from(([0, 0, 0, 0], 0)));
+                .unwrap_or_else(|_| std::net::SocketAddr::new(host, 0));
+
+            state
+                .remote_port
+                .store(addr.port(), std::sync::atomic::Ordering::Relaxed);
 
             let app = build_router(state.clone(), true, mcp_enabled);
             let svc = app.into_make_service_with_connect_info::<std::net::SocketAddr>();
@@ -2031,6 +2272,9 @@ pub async fn start_server(
     // files before binding.
     if let Some(h) = tcp_handle {
         h.abort();
+        state
+            .remote_port
+            .store(0, std::sync::atomic::Ordering::Relaxed);
     }
     if let Some(h) = renewal_handle {
         h.abort();
@@ -2042,13 +2286,32 @@ pub async fn start_server(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::MAX_CONCURRENT_SESSIONS;
+    use crate::state::MAX_CONCURRENT_SESSIONS;
     use axum::body::Body;
     use axum::extract::connect_info::ConnectInfo;
     use axum::http::{Request, StatusCode};
     use dashmap::DashMap;
     use tower::ServiceExt;
 
+    #[test]
+    fn remote_listener_ipv6_wildcard_is_dual_stack() {
+        let addr = std::net::SocketAddr::new(std::net::Ipv6Addr::UNSPECIFIED.into(), 0);
+        let Ok(listener) = bind_remote_listener(addr) else {
+            // No IPv6 on this host (start_server falls back to 0.0.0.0).
+            return;
+        };
+        let port = listener.local_addr().unwrap().port();
+        std::net::TcpStream::connect(("127.0.0.1", port)).expect("IPv4 client reaches [::]");
+    }
+
+    #[test]
+    fn remote_listener_binds_specific_ipv4() {
+        let listener = bind_remote_listener("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = listener.local_addr().unwrap();
+        assert!(addr.is_ipv4());
+        std::net::TcpStream::connect(addr).expect("connect");
+    }
+
     /// Build a POST request with ConnectInfo from the given address.
     fn mcp_post_from(
         url: &str,
@@ -2113,6 +2376,8 @@ mod tests {
             github_token: parking_lot::RwLock::new(None),
             github_token_source: parking_lot::RwLock::new(Default::default()),
             github_circuit_breaker: crate::github::GitHubCircuitBreaker::new(),
+            gitlab_token: parking_lot::RwLock::new(None),
+            gitlab_circuit_breaker: crate::github::GitHubCircuitBreaker::new(),
             github_poller: parking_lot::Mutex::new(None),
             github_viewer_login: parking_lot::RwLock::new(None),
             github_rate_limit_remaining: std::sync::atomic::AtomicU32::new(u32::MAX),
@@ -2120,6 +2385,7 @@ mod tests {
             ipc_started: std::sync::atomic::AtomicBool::new(false),
             session_token: parking_lot::RwLock::new(uuid::Uuid::new_v4().to_string()),
             auth_rate_limits: DashMap::new(),
+            auth_failure_ips: DashMap::new(),
             #[cfg(feature = "desktop")]
             app_handle: parking_lot::RwLock::new(None),
             plugin_watchers: DashMap::new(),
@@ -2165,6 +2431,8 @@ mod tests {
             shell_states: DashMap::new(),
             terminal_rows: DashMap::new(),
             exit_codes: DashMap::new(),
+            exit_reasons: DashMap::new(),
+            recordings: DashMap::new(),
             shell_state_since_ms: DashMap::new(),
             loaded_plugins: DashMap::new(),
             relay: crate::state::RelayState::new(),
@@ -2187,6 +2455,14 @@ mod tests {
                 crate::tailscale::TailscaleState::NotInstalled,
             ),
             push_store: crate::push::PushStore::load(&std::env::temp_dir()),
+            notification_throttle: Default::default(),
+            resize_debouncer: Default::default(),
+            poll_activity: Default::default(),
+            remote_port: std::sync::atomic::AtomicU16::new(0),
+            active_selection: parking_lot::RwLock::new(None),
+            session_records: dashmap::DashMap::new(),
+            session_records_dirty: std::sync::atomic::AtomicBool::new(false),
+            recoverable_sessions: parking_lot::RwLock::new(Vec::new()),
             desktop_window_focused: std::sync::atomic::AtomicBool::new(true),
             server_start_time: std::time::Instant::now(),
             term_aliases: dashmap::DashMap::new(),
@@ -2238,6 +2514,69 @@ mod tests {
         assert_eq!(json["ok"], true);
     }
 
+    #[tokio::test]
+    async fn test_whoami_without_auth_configured() {
+        let state = test_state();
+        let app = build_router(state, false, true);
+        let resp = app
+            .oneshot(Request::get("/whoami").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "authenticated": false,
+                "username": null,
+                "remote_access_enabled": false,
+                "token_based": false,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_whoami_authenticated_with_token() {
+        let state = test_state();
+        {
+            let mut config = state.config.write();
+            config.services.server.enabled = true;
+            config.services.auth.username = "admin".to_string();
+            config.services.auth.password_hash = "$2b$12$hash".to_string();
+        }
+        *state.session_token.write() = "tok-whoami".to_string();
+        let remote_addr = std::net::SocketAddr::from(([203, 0, 113, 7], 5000));
+        let get_whoami = |url: &str| {
+            let mut req = Request::get(url).body(Body::empty()).unwrap();
+            req.extensions_mut().insert(ConnectInfo(remote_addr));
+            req
+        };
+
+        // Same auth middleware as every other route.
+        let app = build_router(state.clone(), true, true);
+        let resp = app.oneshot(get_whoami("/whoami")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let app = build_router(state, true, true);
+        let resp = app
+            .oneshot(get_whoami("/whoami?token=tok-whoami"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["authenticated"], true);
+        assert_eq!(json["username"], "admin");
+        assert_eq!(json["remote_access_enabled"], true);
+        assert_eq!(json["token_based"], true);
+        assert!(json.get("password_hash").is_none());
+    }
+
     #[tokio::test]
     async fn test_list_sessions_empty() {
         let state = test_state();
@@ -2254,6 +2593,172 @@ mod tests {
         assert_eq!(json, serde_json::json!([]));
     }
 
+    #[tokio::test]
+    async fn test_list_sessions_with_filter_returns_page() {
+        let state = test_state();
+        let app = build_router(state, false, true);
+        let resp = app
+            .oneshot(
+                Request::get("/sessions?repo=/tmp/none&limit=10&offset=0")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json, serde_json::json!({"sessions": [], "total": 0}));
+    }
+
+    #[tokio::test]
+    async fn test_list_orphaned_processes_empty() {
+        let state = test_state();
+        let app = build_router(state, false, true);
+        let resp = app
+            .oneshot(get_localhost("/process/orphans"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json, serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_orphaned_process_routes_reject_non_loopback() {
+        let state = test_state();
+        let remote_addr = std::net::SocketAddr::from(([192, 168, 1, 100], 12345));
+        let mut list = Request::get("/process/orphans")
+            .body(Body::empty())
+            .unwrap();
+        list.extensions_mut().insert(ConnectInfo(remote_addr));
+        let resp = build_router(state.clone(), false, true)
+            .oneshot(list)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let body = serde_json::json!({"pid": 1, "confirm": true});
+        let resp = build_router(state, false, true)
+            .oneshot(mcp_post_from("/process/orphans/kill", &body, remote_addr))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_record_session_rejects_non_loopback() {
+        let state = test_state();
+        let remote_addr = std::net::SocketAddr::from(([192, 168, 1, 100], 12345));
+        let body = serde_json::json!({"action": "start", "path": "/tmp/demo.cast"});
+        let resp = build_router(state, false, true)
+            .oneshot(mcp_post_from("/sessions/any/record", &body, remote_addr))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_logs_ansi_html() {
+        let state = test_state();
+        let app = build_router(state, false, true);
+        let resp = app
+            .oneshot(
+                Request::post("/logs/ansi-html")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"text":"\u001b[31mred\u001b[0m"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html: String = serde_json::from_slice(&body).unwrap();
+        assert_eq!(html, "<span style=\"color:#cd0000\">red</span>");
+    }
+
+    #[tokio::test]
+    async fn test_repo_commit_returns_sha_then_nothing_to_commit() {
+        let repo = create_temp_git_repo();
+        std::fs::write(repo.path().join("new.txt"), "new").unwrap();
+        let state = test_state();
+        let commit = |body: serde_json::Value| {
+            let app = build_router(state.clone(), false, true);
+            async move {
+                let resp = app
+                    .oneshot(
+                        Request::post("/repo/commit")
+                            .header("content-type", "application/json")
+                            .body(Body::from(body.to_string()))
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                let status = resp.status();
+                let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+                )
+            }
+        };
+        let path = repo.path().to_string_lossy().to_string();
+
+        let (status, json) =
+            commit(serde_json::json!({"path": path, "message": "add new", "files": ["new.txt"]}))
+                .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json.as_str().unwrap().len(), 40);
+
+        let (status, json) =
+            commit(serde_json::json!({"path": path, "message": "again", "all": true})).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(json["kind"], "nothing_to_commit");
+        assert!(
+            json["error"]
+                .as_str()
+                .unwrap()
+                .contains("Nothing to commit")
+        );
+
+        let (status, json) = commit(serde_json::json!({"path": path, "message": ""})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["kind"], "empty_message");
+    }
+
+    #[tokio::test]
+    async fn test_pr_review_and_comment_validation_errors_are_400() {
+        let state = test_state();
+        let cases = [
+            (
+                "/repo/pr-review-request",
+                serde_json::json!({"repoPath": "/tmp/repo", "prNumber": 1, "reviewers": [" "]}),
+            ),
+            (
+                "/repo/pr-review-request",
+                serde_json::json!({"repoPath": "/tmp/repo", "prNumber": 0, "reviewers": ["alice"]}),
+            ),
+            (
+                "/repo/pr-comment",
+                serde_json::json!({"repoPath": "/tmp/repo", "prNumber": 1, "body": "  "}),
+            ),
+        ];
+        for (url, body) in cases {
+            let app = build_router(state.clone(), false, true);
+            let resp = app.oneshot(mcp_post(url, &body)).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{url} {body}");
+        }
+    }
+
     #[tokio::test]
     async fn test_stats_no_sessions() {
         let state = test_state();
@@ -2570,9 +3075,11 @@ mod tests {
             .unwrap();
         let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
         let agents = json.as_array().unwrap();
-        assert_eq!(agents.len(), 4);
+        assert_eq!(agents.len(), crate::agent::KNOWN_AGENTS.len());
         let names: Vec<&str> = agents.iter().map(|a| a["name"].as_str().unwrap()).collect();
-        assert!(names.contains(&"claude"));
+        for name in ["claude", "opencode", "amp", "cline"] {
+            assert!(names.contains(&name), "missing {name}");
+        }
     }
 
     #[tokio::test]
@@ -2620,6 +3127,38 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_signal_session_rejects_unknown_signal() {
+        let state = test_state();
+        let app = build_router(state, false, true);
+        let resp = app
+            .oneshot(
+                Request::post("/sessions/nonexistent/signal")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"signal":"SIGSTOP"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_signal_nonexistent_session() {
+        let state = test_state();
+        let app = build_router(state, false, true);
+        let resp = app
+            .oneshot(
+                Request::post("/sessions/nonexistent/signal")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"signal":"SIGINT"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_mcp_initialize() {
         let state = test_state();
@@ -2652,6 +3191,59 @@ mod tests {
         assert!(json["result"]["serverInfo"]["name"].as_str().is_some());
     }
 
+    #[tokio::test]
+    async fn test_mcp_batch_returns_response_per_request() {
+        let state = test_state();
+        let app = build_router(state, false, true);
+        let body = serde_json::json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} },
+            { "jsonrpc": "2.0", "id": 2, "method": "no/such-method" },
+            42
+        ]);
+        let resp = app.oneshot(mcp_post("/mcp", &body)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get("mcp-session-id").is_some());
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let responses = json.as_array().expect("batch must return an array");
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["id"], 1);
+        assert!(responses[0]["result"]["serverInfo"].is_object());
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(responses[1]["error"]["code"], -32601);
+        // Malformed entry gets its own error instead of failing the batch
+        assert_eq!(responses[2]["id"], serde_json::Value::Null);
+        assert_eq!(responses[2]["error"]["code"], -32600);
+    }
+
+    #[tokio::test]
+    async fn test_mcp_batch_omits_notification_responses() {
+        let state = test_state();
+        let app = build_router(state, false, true);
+        let body = serde_json::json!([
+            { "jsonrpc": "2.0", "method": "notifications/initialized" },
+            { "jsonrpc": "2.0", "id": 7, "method": "initialize", "params": {} }
+        ]);
+        let resp = app.oneshot(mcp_post("/mcp", &body)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let responses = json.as_array().unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0]["id"], 7);
+
+        let app = build_router(test_state(), false, true);
+        let only_notes = serde_json::json!([
+            { "jsonrpc": "2.0", "method": "notifications/initialized" }
+        ]);
+        let resp = app.oneshot(mcp_post("/mcp", &only_notes)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    }
+
     #[tokio::test]
     async fn test_mcp_initialize_stores_claude_code_identity() {
         let state = test_state();
@@ -3429,6 +4021,22 @@ mod tests {
         assert_eq!(result["error"], "Session not found");
     }
 
+    #[tokio::test]
+    async fn test_session_signal_rejects_unknown_signal() {
+        let state = test_state();
+        let result = call_mcp_tool(
+            &state,
+            "session",
+            serde_json::json!({
+                "action": "signal",
+                "session_id": "nonexistent",
+                "signal": "SIGHUP"
+            }),
+        )
+        .await;
+        assert!(result["error"].as_str().unwrap().contains("Allowed"));
+    }
+
     // --- Agent meta-command tests ---
 
     #[tokio::test]
@@ -3437,6 +4045,20 @@ mod tests {
         let result = call_mcp_tool(&state, "agent", serde_json::json!({"action": "stats"})).await;
         assert_eq!(result["active_sessions"], 0);
         assert_eq!(result["max_sessions"], MAX_CONCURRENT_SESSIONS);
+
+        state.config.write().max_concurrent_sessions = 3;
+        let result = call_mcp_tool(&state, "agent", serde_json::json!({"action": "stats"})).await;
+        assert_eq!(result["max_sessions"], 3);
+        assert_eq!(result["available_slots"], 3);
+
+        // Nonsensical values are clamped.
+        state.config.write().max_concurrent_sessions = 0;
+        assert_eq!(state.max_sessions(), 1);
+        state.config.write().max_concurrent_sessions = usize::MAX;
+        assert_eq!(
+            state.max_sessions(),
+            crate::state::MAX_CONCURRENT_SESSIONS_CEILING
+        );
     }
 
     #[tokio::test]
@@ -3452,9 +4074,14 @@ mod tests {
         let state = test_state();
         let result = call_mcp_tool(&state, "agent", serde_json::json!({"action": "detect"})).await;
         let agents = result.as_array().unwrap();
-        assert_eq!(agents.len(), 4, "Should detect 4 known agents");
+        assert_eq!(
+            agents.len(),
+            crate::agent::KNOWN_AGENTS.len(),
+            "Should detect every registered agent"
+        );
         let names: Vec<&str> = agents.iter().map(|a| a["name"].as_str().unwrap()).collect();
         assert!(names.contains(&"claude"));
+        assert!(names.contains(&"cline"));
     }
 
     #[tokio::test]
@@ -3576,6 +4203,167 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_list_issues_rejects_invalid_state() {
+        let state = test_state();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let url = format!("/repo/issues/list?path={path}&state=merged");
+        let resp = build_router(state.clone(), false, true)
+            .oneshot(get_localhost(&url))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let result = call_mcp_tool(
+            &state,
+            "issues",
+            serde_json::json!({"action": "list", "path": path, "state": "merged"}),
+        )
+        .await;
+        let err = result["error"].as_str().unwrap();
+        assert!(err.contains("Invalid issue state"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_repo_stash_missing_subaction() {
+        let state = test_state();
+        let dir = tempfile::tempdir().unwrap();
+        let result = call_mcp_tool(
+            &state,
+            "repo",
+            serde_json::json!({
+                "action": "stash",
+                "path": dir.path().to_string_lossy()
+            }),
+        )
+        .await;
+        let err = result["error"].as_str().unwrap();
+        assert!(err.contains("subaction"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_repo_stash_rejects_out_of_range_index() {
+        let state = test_state();
+        let dir = tempfile::tempdir().unwrap();
+        for index in [serde_json::json!(-1), serde_json::json!(4_294_967_296u64)] {
+            let result = call_mcp_tool(
+                &state,
+                "repo",
+                serde_json::json!({
+                    "action": "stash",
+                    "path": dir.path().to_string_lossy(),
+                    "subaction": "drop",
+                    "index": index
+                }),
+            )
+            .await;
+            let err = result["error"].as_str().unwrap();
+            assert!(err.contains("Invalid index"), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repo_file_diff() {
+        let repo = create_temp_git_repo();
+        let repo_path = repo.path().to_str().unwrap();
+        let state = test_state();
+
+        let result = call_mcp_tool(
+            &state,
+            "repo",
+            serde_json::json!({"action": "file_diff", "path": repo_path, "file": "README.md"}),
+        )
+        .await;
+        let err = result["error"].as_str().unwrap();
+        assert!(err.contains("No changes"), "{err}");
+
+        std::fs::write(repo.path().join("README.md"), "changed\n").unwrap();
+        let result = call_mcp_tool(
+            &state,
+            "repo",
+            serde_json::json!({"action": "file_diff", "path": repo_path, "file": "README.md"}),
+        )
+        .await;
+        let diff = result["diff"].as_str().unwrap();
+        assert!(diff.contains("+changed"), "{diff}");
+        assert_eq!(result["staged"], false);
+
+        let result = call_mcp_tool(
+            &state,
+            "repo",
+            serde_json::json!({"action": "file_diff", "path": repo_path, "file": "README.md", "staged": true}),
+        )
+        .await;
+        let err = result["error"].as_str().unwrap();
+        assert!(err.contains("No staged changes"), "{err}");
+
+        let result = call_mcp_tool(
+            &state,
+            "repo",
+            serde_json::json!({"action": "file_diff", "path": repo_path, "file": "missing.txt"}),
+        )
+        .await;
+        let err = result["error"].as_str().unwrap();
+        assert!(err.contains("not found or not tracked"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_repo_commit_action() {
+        let repo = create_temp_git_repo();
+        let repo_path = repo.path().to_str().unwrap();
+        let state = test_state();
+        std::fs::write(repo.path().join("README.md"), "changed\n").unwrap();
+
+        let result = call_mcp_tool(
+            &state,
+            "repo",
+            serde_json::json!({"action": "commit", "path": repo_path, "message": "update readme", "all": true}),
+        )
+        .await;
+        assert_eq!(result["sha"].as_str().unwrap().len(), 40, "{result}");
+
+        let result = call_mcp_tool(
+            &state,
+            "repo",
+            serde_json::json!({"action": "commit", "path": repo_path, "message": "again"}),
+        )
+        .await;
+        assert_eq!(result["kind"], "nothing_to_commit", "{result}");
+    }
+
+    #[tokio::test]
+    async fn test_repo_file_diff_rejects_traversal() {
+        let state = test_state();
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["../etc/passwd", "src/../../x", "/etc/passwd"] {
+            let result = call_mcp_tool(
+                &state,
+                "repo",
+                serde_json::json!({
+                    "action": "file_diff",
+                    "path": dir.path().to_string_lossy(),
+                    "file": file
+                }),
+            )
+            .await;
+            let err = result["error"].as_str().unwrap();
+            assert!(err.contains("without '..'"), "{file}: {err}");
+        }
+        let result = call_mcp_tool(
+            &state,
+            "repo",
+            serde_json::json!({"action": "file_diff", "path": dir.path().to_string_lossy()}),
+        )
+        .await;
+        assert!(
+            result["error"]
+                .as_str()
+                .unwrap()
+                .contains("requires 'file'")
+        );
+    }
+
     // --- Action routing error tests ---
 
     #[tokio::test]
@@ -4914,6 +5702,50 @@ mod tests {
         // 3xx redirect is also valid (mobile redirect)
     }
 
+    #[tokio::test]
+    async fn test_get_output_polls_raw_bytes_by_offset() {
+        use crate::state::OutputRingBuffer;
+
+        let state = test_state();
+        let sid = "test-raw-poll";
+        let mut ring = OutputRingBuffer::new(8);
+        ring.write(b"0123456789AB");
+        state
+            .output_buffers
+            .insert(sid.to_string(), parking_lot::Mutex::new(ring));
+
+        let get = |query: &str| {
+            let app = build_router(state.clone(), false, true);
+            let uri = format!("/sessions/{sid}/output?{query}");
+            async move {
+                let resp = app
+                    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(resp.status(), StatusCode::OK);
+                let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        // Offset 4 is still buffered: a normal page.
+        let json = get("from_offset=4&max_bytes=3").await;
+        assert_eq!(json["data"], "456");
+        assert_eq!(json["from_offset"], 4);
+        assert_eq!(json["next_offset"], 7);
+        assert_eq!(json["total_written"], 12);
+        assert_eq!(json["gap"], false);
+
+        // Offset 0 was overwritten: restart at the earliest byte and flag the gap.
+        let json = get("from_offset=0").await;
+        assert_eq!(json["data"], "456789AB");
+        assert_eq!(json["from_offset"], 4);
+        assert_eq!(json["next_offset"], 12);
+        assert_eq!(json["gap"], true);
+    }
+
     #[tokio::test]
     async fn test_get_output_format_log_unknown_session_404() {
         let state = test_state();
@@ -35,6 +35,7 @@ Constraints:
 - `minAppVersion` must be <= current app version (current: 0.3.x)
 - `capabilities`: subset of `pty:write`, `pty:read`, `ui:markdown`, `ui:sound`, `ui:panel`, `ui:ticker`, `ui:context-menu`, `ui:sidebar`, `ui:file-icons`, `net:http`, `credentials:read`, `invoke:read_file`, `invoke:list_markdown_files`, `fs:read`, `fs:list`, `fs:watch`, `fs:write`, `fs:rename`, `exec:cli`, `git:read`
 - `allowedUrls`: URL patterns for `net:http` (supports `*` wildcard for path prefix matching)
+- `allowedHosts`: optional hostnames `net:http` may reach, redirects included. Exact (`api.example.com`) or `*.example.com` for subdomains only
 - `agentTypes`: optional array of agent type strings. When set, output watchers and structured event handlers only fire for terminals running a matching agent. Omit or use `[]` for universal plugins. Valid values: `claude`, `gemini`, `opencode`, `aider`, `codex`, `amp`, `cursor`, `goose`, `droid`, `git`.
 - `binaries`: optional array of CLI binary names this plugin may execute via `exec:cli` (e.g. `["rtk", "mdkb"]`). The on-disk manifest is the source of truth — binaries not declared here are rejected.
 - Module default export must have `id`, `onload(host)`, `onunload()`
@@ -254,7 +255,7 @@ HttpResponse: `{ status: number, headers: Record<string, string>, body: string }
 
 **setTicker notes:** Shared ticker area rotates messages from all plugins. Priority tiers: <10 = popover only, 10-99 = auto-rotate (5s), >=100 = urgent pin. `label` is shown as source prefix (e.g. "Usage · 5h: 42%"). Counter badge (1/3 ▸) shown when multiple tickers active. Click badge to cycle, right-click for popover. Legacy aliases: `postTickerMessage`/`removeTickerMessage`.
 
-**httpFetch notes:** Localhost blocked unless declared in `allowedUrls`. Max 5 redirects. 30s timeout, 10 MB limit. Built-in plugins have no URL restrictions.
+**httpFetch notes:** Localhost blocked unless declared in `allowedUrls`. Max 5 redirects, each checked against `allowedHosts` when declared. 30s timeout and 10 MB limit by default; override per call with `timeoutMs` (max 300000) and `maxResponseBytes` (max 50 MB). The body is streamed and aborted once over the limit. Failures reject with `{ kind, message }`, kind one of `rejected`, `timeout`, `size_exceeded`, `connection`, `request`. Built-in plugins have no URL restrictions.
 
 ### Tier 3b: Filesystem Operations (capability-gated)
 
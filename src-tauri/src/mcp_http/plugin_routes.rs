@@ -6,7 +6,8 @@
 
 use axum::Json;
 use axum::extract::{Path as AxumPath, Query, State};
-use axum::response::Response;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -141,6 +142,8 @@ pub(super) struct HttpFetchBody {
     pub body: Option<String>,
     #[serde(rename = "allowedUrls")]
     pub allowed_urls: Vec<String>,
+    #[serde(default)]
+    pub limits: Option<crate::plugin_http::FetchLimits>,
 }
 
 pub(super) async fn plugin_http_fetch(
@@ -148,18 +151,35 @@ pub(super) async fn plugin_http_fetch(
     AxumPath(plugin_id): AxumPath<String>,
     Json(body): Json<HttpFetchBody>,
 ) -> Response {
-    json_result(
-        crate::plugin_http::plugin_http_fetch_impl(
-            &state,
-            body.url,
-            body.method,
-            body.headers,
-            body.body,
-            body.allowed_urls,
-            plugin_id,
-        )
-        .await,
+    use crate::plugin_http::PluginHttpErrorKind;
+    let result = crate::plugin_http::plugin_http_fetch_impl(
+        &state,
+        body.url,
+        body.method,
+        body.headers,
+        body.body,
+        body.allowed_urls,
+        plugin_id,
+        body.limits.unwrap_or_default(),
     )
+    .await;
+    match result {
+        Ok(resp) => (StatusCode::OK, Json(resp)).into_response(),
+        Err(e) => {
+            let status = match e.kind {
+                PluginHttpErrorKind::Rejected => StatusCode::FORBIDDEN,
+                PluginHttpErrorKind::Timeout => StatusCode::GATEWAY_TIMEOUT,
+                PluginHttpErrorKind::SizeExceeded
+                | PluginHttpErrorKind::Connection
+                | PluginHttpErrorKind::Request => StatusCode::BAD_GATEWAY,
+            };
+            (
+                status,
+                Json(serde_json::json!({"error": e.message, "kind": e.kind})),
+            )
+                .into_response()
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -216,6 +236,33 @@ pub(super) async fn plugin_unregister(
     json_result(Ok::<_, String>(()))
 }
 
+// ---------------------------------------------------------------------------
+// Structured events
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+pub(super) struct EmitEventBody {
+    #[serde(rename = "eventType")]
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    #[serde(rename = "sessionId")]
+    pub session_id: Option<String>,
+}
+
+pub(super) async fn plugin_emit_event(
+    State(state): State<Arc<AppState>>,
+    AxumPath(plugin_id): AxumPath<String>,
+    Json(body): Json<EmitEventBody>,
+) -> Response {
+    json_result(crate::plugins::plugin_emit_event_impl(
+        &state,
+        &plugin_id,
+        &body.event_type,
+        &body.payload,
+        body.session_id.as_deref(),
+    ))
+}
+
 // ---------------------------------------------------------------------------
 // README path
 // ---------------------------------------------------------------------------
@@ -1,21 +1,24 @@
 use crate::pty::{build_shell_command, resolve_shell, spawn_reader_thread};
 use crate::state::{OUTPUT_RING_BUFFER_CAPACITY, VT_LOG_BUFFER_CAPACITY, VtLogBuffer};
-use crate::{AppState, MAX_CONCURRENT_SESSIONS, OutputRingBuffer, PtySession};
-use axum::Json;
+use crate::{AppState, OutputRingBuffer, PtySession};
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::extract::{Path, Query, State};
+use axum::extract::{ConnectInfo, Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
 use futures_util::stream::StreamExt;
 use parking_lot::Mutex;
 use portable_pty::{PtySize, native_pty_system};
+use serde::Serialize;
 use std::io::Write;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 #[cfg(feature = "desktop")]
 use tauri::Emitter;
 use uuid::Uuid;
 
+use super::guards::{AuthVia, Authenticated, require_local_or_auth};
 use super::types::*;
 
 /// Standard 404 response for missing sessions.
@@ -49,6 +52,21 @@ pub(super) async fn health(State(state): State<Arc<AppState>>) -> Json<HealthRes
     })
 }
 
+/// `GET /health/diagnostics` — support self-test (see `crate::diagnostics`).
+/// Exposes local paths, so it requires a local caller or authentication.
+pub(super) async fn diagnostics(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    match tokio::task::spawn_blocking(crate::diagnostics::run_diagnostics_impl).await {
+        Ok(checks) => Json(checks).into_response(),
+        Err(e) => super::err_500(&format!("Task failed: {e}")),
+    }
+}
+
 pub(super) async fn app_version() -> Json<super::types::VersionResponse> {
     Json(super::types::VersionResponse {
         version: env!("CARGO_PKG_VERSION"),
@@ -56,28 +74,82 @@ pub(super) async fn app_version() -> Json<super::types::VersionResponse> {
     })
 }
 
-pub(super) async fn list_sessions(State(state): State<Arc<AppState>>) -> Json<Vec<SessionInfo>> {
-    let sessions: Vec<SessionInfo> = state
-        .sessions
-        .iter()
-        .map(|entry| {
-            let session_id = entry.key().clone();
-            let session = entry.value().lock();
-            let session_state = state.session_state_with_shell(&session_id);
-            SessionInfo {
-                session_id,
-                cwd: session.cwd.clone(),
-                worktree_path: session
-                    .worktree
-                    .as_ref()
-                    .map(|w| w.path.to_string_lossy().to_string()),
-                worktree_branch: session.worktree.as_ref().and_then(|w| w.branch.clone()),
-                display_name: session.display_name.clone(),
-                state: session_state,
-            }
-        })
-        .collect();
-    Json(sessions)
+/// `GET /whoami` — who the caller is authenticated as, so the web client can
+/// render its login state. Never includes the password hash.
+pub(super) async fn whoami(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<Authenticated>>,
+    via: Option<Extension<AuthVia>>,
+) -> Json<WhoamiResponse> {
+    let config = state.config.read();
+    let creds = &config.services.auth;
+    let auth_configured = !creds.username.is_empty() && !creds.password_hash.is_empty();
+    Json(WhoamiResponse {
+        authenticated: auth.is_some(),
+        username: auth_configured.then(|| creds.username.clone()),
+        remote_access_enabled: config.services.server.enabled,
+        token_based: via.is_some_and(|Extension(v)| v.is_token_based()),
+    })
+}
+
+/// Sessions alive when the app last quit (see `AppState::recoverable_sessions`).
+pub(super) async fn list_recoverable_sessions(State(state): State<Arc<AppState>>) -> Response {
+    Json(state.recoverable_sessions.read().clone()).into_response()
+}
+
+/// `GET /sessions` — without query params returns the bare array (legacy
+/// shape); with any of `repo`/`tag`/`paused`/`limit`/`offset` returns
+/// `{sessions, total}`.
+pub(super) async fn list_sessions(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<crate::pty::SessionFilter>,
+) -> Response {
+    let page = crate::pty::list_sessions_filtered(&state, &filter, |session_id, session| {
+        let session_state = state.session_state_with_shell(&session_id);
+        SessionInfo {
+            session_id,
+            cwd: session.cwd.clone(),
+            worktree_path: session
+                .worktree
+                .as_ref()
+                .map(|w| w.path.to_string_lossy().to_string()),
+            worktree_branch: session.worktree.as_ref().and_then(|w| w.branch.clone()),
+            display_name: session.display_name.clone(),
+            state: session_state,
+        }
+    });
+    if filter.is_empty() {
+        Json(page.sessions).into_response()
+    } else {
+        Json(page).into_response()
+    }
+}
+
+/// POST /sessions/broadcast — write the same input to several sessions.
+pub(super) async fn broadcast_input(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<BroadcastInputRequest>,
+) -> impl IntoResponse {
+    let result = tokio::task::spawn_blocking(move || {
+        crate::pty::broadcast_input_core(
+            &state,
+            &body.session_ids,
+            body.input.as_deref(),
+            body.special_key.as_deref(),
+        )
+    })
+    .await;
+    match result {
+        Ok(Ok(results)) => (StatusCode::OK, Json(serde_json::json!(results))),
+        Ok(Err(e)) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Task failed: {e}")})),
+        ),
+    }
 }
 
 pub(super) async fn write_to_session(
@@ -101,7 +173,7 @@ pub(super) async fn write_to_session(
     }
     // Stamp last-input time (same as desktop write_pty) so the grid ticker
     // throttles frames for remote/PWA typing under CPU saturation too.
-    crate::pty::stamp_input_ms(&state, &session_id);
+    crate::pty::stamp_input_ms(&state, &session_id, &body.data);
 
     // Feed input through InputLineBuffer FSM to track slash_mode accurately.
     // The old substring heuristic false-positived on pastes starting with '/'.
@@ -112,11 +184,13 @@ pub(super) async fn write_to_session(
             parking_lot::Mutex::new(crate::input_line_buffer::InputLineBuffer::new())
         });
     let mut buf = input_entry.lock();
+    buf.set_reconstruct(state.config.read().reconstruct_user_input);
     let actions = buf.feed(&body.data);
     let line_submitted = actions.iter().any(|a| {
         matches!(
             a,
             crate::input_line_buffer::InputAction::Line(_)
+                | crate::input_line_buffer::InputAction::Submitted { .. }
                 | crate::input_line_buffer::InputAction::Interrupt
         )
     });
@@ -165,10 +239,66 @@ pub(super) async fn set_session_name(
         Some(e) => e,
         None => return session_not_found(),
     };
-    entry.lock().display_name = body.name;
+    entry.lock().display_name = body.name.clone();
+    drop(entry);
+    state.record_session_title(&session_id, body.name);
     (StatusCode::OK, Json(serde_json::json!({"ok": true})))
 }
 
+pub(super) async fn export_session(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    match crate::session_export::export_session(&state, &session_id, query.format) {
+        Ok(data) => (StatusCode::OK, Json(serde_json::json!(data))),
+        Err(_) => session_not_found(),
+    }
+}
+
+/// Start/stop an asciinema recording. Writes a file on this machine, so
+/// remote callers must be authenticated.
+pub(super) async fn record_session(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Json(body): Json<RecordRequest>,
+) -> impl IntoResponse {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp;
+    }
+    if !state.sessions.contains_key(&session_id) {
+        return session_not_found();
+    }
+    let bad_request = |msg: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": msg})),
+        )
+    };
+    match body.action.as_str() {
+        "start" => {
+            let Some(path) = body.path else {
+                return bad_request("path is required to start recording".to_string());
+            };
+            match crate::recording::start_recording(&state, &session_id, &path) {
+                Ok(()) => (StatusCode::OK, Json(serde_json::json!({"ok": true}))),
+                Err(e) => bad_request(e),
+            }
+        }
+        "stop" => match crate::recording::stop_recording(&state, &session_id) {
+            Some(Ok(info)) => (StatusCode::OK, Json(serde_json::json!(info))),
+            Some(Err(e)) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e})),
+            ),
+            None => bad_request(format!("Session {session_id} is not being recorded")),
+        },
+        other => bad_request(format!("Unknown action '{other}' (expected start or stop)")),
+    }
+}
+
 pub(super) async fn resize_session(
     State(state): State<Arc<AppState>>,
     Path(session_id): Path<String>,
@@ -180,28 +310,47 @@ pub(super) async fn resize_session(
             Json(serde_json::json!({"error": msg})),
         );
     }
-    let entry = match state.sessions.get(&session_id) {
-        Some(e) => e,
-        None => return session_not_found(),
-    };
-    let session = entry.lock();
-    if let Err(e) = session.master.resize(PtySize {
-        rows: body.rows,
-        cols: body.cols,
-        pixel_width: 0,
-        pixel_height: 0,
-    }) {
+    if !state.sessions.contains_key(&session_id) {
+        return session_not_found();
+    }
+    // Coalesced per session: only the last size of a burst reaches the PTY.
+    if let Err(e) = crate::resize_debounce::debounce_resize(
+        &state,
+        &session_id,
+        body.rows,
+        body.cols,
+        apply_resize,
+    ) {
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Resize failed: {}", e)})),
+            Json(serde_json::json!({"error": e})),
         );
     }
-    drop(session);
+    (StatusCode::OK, Json(serde_json::json!({"ok": true})))
+}
+
+/// Apply a (debounced) resize to the PTY and its VT log buffer.
+fn apply_resize(state: &AppState, session_id: &str, rows: u16, cols: u16) -> Result<(), String> {
+    let entry = state
+        .sessions
+        .get(session_id)
+        .ok_or_else(|| format!("Session not found: {session_id}"))?;
+    entry
+        .lock()
+        .master
+        .resize(PtySize {
+            rows,
+            cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .map_err(|e| format!("Resize failed: {e}"))?;
+    drop(entry);
     // Resize VT log buffer to match new terminal dimensions.
-    if let Some(vt_log) = state.vt_log_buffers.get(&session_id) {
-        vt_log.lock().resize(body.rows, body.cols);
+    if let Some(vt_log) = state.vt_log_buffers.get(session_id) {
+        vt_log.lock().resize(rows, cols);
     }
-    (StatusCode::OK, Json(serde_json::json!({"ok": true})))
+    Ok(())
 }
 
 pub(super) async fn get_output(
@@ -275,7 +424,42 @@ pub(super) async fn get_output(
         Some(r) => r,
         None => return session_not_found(),
     };
-    let limit = query.limit.unwrap_or(8192);
+
+    // Incremental polling: page forward from a byte offset instead of the tail.
+    if let Some(from) = query.from_offset {
+        let max_bytes = query
+            .max_bytes
+            .unwrap_or(OUTPUT_PAGE_DEFAULT_BYTES)
+            .min(OUTPUT_PAGE_MAX_BYTES);
+        let buf = ring.lock();
+        let (mut bytes, start, gap) = buf.read_range(from, max_bytes);
+        let total_written = buf.total_written;
+        drop(buf);
+        // Don't split a UTF-8 character across pages: leave an incomplete
+        // trailing sequence for the next poll.
+        if start + (bytes.len() as u64) < total_written
+            && let Err(e) = std::str::from_utf8(&bytes)
+            && e.error_len().is_none()
+            && e.valid_up_to() > 0
+        {
+            bytes.truncate(e.valid_up_to());
+        }
+        let next_offset = start + bytes.len() as u64;
+        let data = String::from_utf8_lossy(&bytes).to_string();
+        return (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "data": data,
+                "data_length": data.len(),
+                "from_offset": start,
+                "next_offset": next_offset,
+                "total_written": total_written,
+                "gap": gap,
+            })),
+        );
+    }
+
+    let limit = query.max_bytes.or(query.limit).unwrap_or(8192);
     let (bytes, total_written) = ring.lock().read_last(limit);
     let raw = String::from_utf8_lossy(&bytes).to_string();
     let data = raw;
@@ -289,6 +473,217 @@ pub(super) async fn get_output(
     )
 }
 
+/// Default and server-side cap for `max_bytes` when polling raw output by offset.
+const OUTPUT_PAGE_DEFAULT_BYTES: usize = 64 * 1024;
+const OUTPUT_PAGE_MAX_BYTES: usize = 1024 * 1024;
+
+/// Default and server-side cap for `max_matches` on output search.
+const SEARCH_DEFAULT_MATCHES: usize = 50;
+const SEARCH_MAX_MATCHES: usize = 500;
+/// Bytes of surrounding text returned on each side of a match.
+const SEARCH_CONTEXT_BYTES: usize = 80;
+
+/// One hit in a session's output ring buffer.
+#[derive(Debug, Serialize)]
+pub(super) struct OutputMatch {
+    /// Absolute stream byte offset of the match (comparable with
+    /// `total_written`), also when matching ANSI-stripped text.
+    pub offset: u64,
+    /// Length in raw buffer bytes, escape sequences inside the match included.
+    pub length: usize,
+    pub text: String,
+    pub context: String,
+}
+
+fn floor_char_boundary(s: &str, mut i: usize) -> usize {
+    while i > 0 && !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+fn ceil_char_boundary(s: &str, mut i: usize) -> usize {
+    while i < s.len() && !s.is_char_boundary(i) {
+        i += 1;
+    }
+    i
+}
+
+/// Decode `raw` as lossy UTF-8, optionally dropping ANSI escape sequences
+/// (CSI, OSC, other ESC-prefixed) and C0 controls other than `\n`, `\r`, `\t`.
+/// Returns the text plus, for each of its bytes, the raw byte it came from,
+/// so matches can be reported as raw buffer offsets.
+fn searchable_text(raw: &[u8], strip_ansi: bool) -> (String, Vec<usize>) {
+    let mut kept = Vec::with_capacity(raw.len());
+    let mut origin = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        let b = raw[i];
+        if !strip_ansi {
+            kept.push(b);
+            origin.push(i);
+            i += 1;
+            continue;
+        }
+        match b {
+            0x1b => {
+                i += 1;
+                match raw.get(i) {
+                    // CSI: parameters/intermediates up to a final byte 0x40..=0x7e
+                    Some(b'[') => {
+                        i += 1;
+                        while i < raw.len() && !(0x40..=0x7e).contains(&raw[i]) {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                    // OSC/DCS/APC/PM/SOS: string terminated by BEL or ESC \
+                    Some(b']' | b'P' | b'_' | b'^' | b'X') => {
+                        i += 1;
+                        while i < raw.len() && raw[i] != 0x07 && raw[i] != 0x1b {
+                            i += 1;
+                        }
+                        if raw.get(i) == Some(&0x1b) && raw.get(i + 1) == Some(&b'\\') {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                    // Two-byte escape (charset selection takes one more byte)
+                    Some(b'(' | b')' | b'*' | b'+') => i += 2,
+                    Some(_) => i += 1,
+                    None => {}
+                }
+            }
+            b'\n' | b'\r' | b'\t' => {
+                kept.push(b);
+                origin.push(i);
+                i += 1;
+            }
+            0x00..=0x1f | 0x7f => i += 1,
+            _ => {
+                kept.push(b);
+                origin.push(i);
+                i += 1;
+            }
+        }
+    }
+
+    // Lossy decode, keeping `origin` aligned with the bytes of the result.
+    let mut text = String::with_capacity(kept.len());
+    let mut offsets = Vec::with_capacity(kept.len());
+    let mut rest = &kept[..];
+    let mut pos = 0;
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                offsets.extend_from_slice(&origin[pos..pos + valid.len()]);
+                break;
+            }
+            Err(e) => {
+                let valid_len = e.valid_up_to();
+                // The first `valid_up_to` bytes are valid UTF-8 by definition.
+                text.push_str(std::str::from_utf8(&rest[..valid_len]).unwrap_or_default());
+                offsets.extend_from_slice(&origin[pos..pos + valid_len]);
+                let bad_len = e.error_len().unwrap_or(rest.len() - valid_len);
+                text.push(char::REPLACEMENT_CHARACTER);
+                offsets.extend([origin[pos + valid_len]; 3]);
+                rest = &rest[valid_len + bad_len..];
+                pos += valid_len + bad_len;
+            }
+        }
+    }
+    (text, offsets)
+}
+
+/// Scan `haystack` for `q`, returning at most `max` matches plus whether more
+/// were available. `raw_offsets[i]` is the raw buffer position of haystack
+/// byte `i`; `base` (stream offset of raw byte 0) is added to every offset.
+pub(super) fn search_text(
+    haystack: &str,
+    raw_offsets: &[usize],
+    base: u64,
+    q: &OutputSearchQuery,
+) -> Result<(Vec<OutputMatch>, bool), String> {
+    if q.q.is_empty() {
+        return Err("q must not be empty".to_string());
+    }
+    let pattern = if q.regex {
+        q.q.clone()
+    } else {
+        regex::escape(&q.q)
+    };
+    let re = regex::RegexBuilder::new(&pattern)
+        .case_insensitive(!q.case_sensitive)
+        .build()
+        .map_err(|e| format!("Invalid regex: {e}"))?;
+    let max = q
+        .max_matches
+        .unwrap_or(SEARCH_DEFAULT_MATCHES)
+        .clamp(1, SEARCH_MAX_MATCHES);
+
+    let mut matches = Vec::new();
+    let mut truncated = false;
+    for m in re.find_iter(haystack).filter(|m| !m.is_empty()) {
+        if matches.len() == max {
+            truncated = true;
+            break;
+        }
+        let start = floor_char_boundary(haystack, m.start().saturating_sub(SEARCH_CONTEXT_BYTES));
+        let end = ceil_char_boundary(
+            haystack,
+            (m.end() + SEARCH_CONTEXT_BYTES).min(haystack.len()),
+        );
+        let raw_start = raw_offsets[m.start()];
+        let raw_end = raw_offsets[m.end() - 1] + 1;
+        matches.push(OutputMatch {
+            offset: base + raw_start as u64,
+            length: raw_end - raw_start,
+            text: m.as_str().to_string(),
+            context: haystack[start..end].to_string(),
+        });
+    }
+    Ok((matches, truncated))
+}
+
+/// Search a session's raw output ring buffer. `Ok(None)` = unknown session.
+pub(super) fn search_session_output(
+    state: &AppState,
+    session_id: &str,
+    q: &OutputSearchQuery,
+) -> Result<Option<serde_json::Value>, String> {
+    let Some(ring) = state.output_buffers.get(session_id) else {
+        return Ok(None);
+    };
+    let (bytes, total_written) = ring.lock().read_last(OUTPUT_RING_BUFFER_CAPACITY);
+    drop(ring);
+    let (haystack, raw_offsets) = searchable_text(&bytes, q.strip_ansi);
+    let base = total_written - bytes.len() as u64;
+    let (matches, truncated) = search_text(&haystack, &raw_offsets, base, q)?;
+    Ok(Some(serde_json::json!({
+        "matches": matches,
+        "truncated": truncated,
+        "total_written": total_written,
+        "stripped": q.strip_ansi,
+    })))
+}
+
+/// GET /sessions/{id}/search — grep the scrollback without fetching it.
+pub(super) async fn search_output(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Query(query): Query<OutputSearchQuery>,
+) -> impl IntoResponse {
+    match search_session_output(&state, &session_id, &query) {
+        Ok(Some(body)) => (StatusCode::OK, Json(body)),
+        Ok(None) => session_not_found(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        ),
+    }
+}
+
 pub(super) async fn close_session(
     State(state): State<Arc<AppState>>,
     Path(session_id): Path<String>,
@@ -329,6 +724,7 @@ pub(super) async fn close_session(
 ///
 /// Returns `(session_id, cwd_string)` on success. Both `create_session` and
 /// `create_session_with_worktree` delegate here after deriving the cwd and worktree.
+#[allow(clippy::too_many_arguments)]
 pub(super) fn spawn_pty_session(
     state: Arc<AppState>,
     shell: String,
@@ -337,7 +733,14 @@ pub(super) fn spawn_pty_session(
     cols: u16,
     worktree: Option<crate::state::WorktreeInfo>,
     requested_id: Option<String>,
+    env: &std::collections::HashMap<String, String>,
 ) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
+    crate::env_file::validate_spawn_env(env).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e})),
+        )
+    })?;
     // Honor a client-provided id when it is non-empty and not already taken
     // (browser duplicate-tab fix); otherwise mint a fresh one.
     let session_id = match requested_id {
@@ -365,6 +768,9 @@ pub(super) fn spawn_pty_session(
         let dir = crate::cli::expand_tilde(dir);
         cmd.cwd(dir);
     }
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
 
     let child = pair.slave.spawn_command(cmd).map_err(|e| {
         (
@@ -387,6 +793,8 @@ pub(super) fn spawn_pty_session(
         )
     })?;
 
+    crate::spawn_ledger::record_spawn(&state.data_dir, child.process_id(), &session_id);
+
     let paused = Arc::new(AtomicBool::new(false));
     state.sessions.insert(
         session_id.clone(),
@@ -399,9 +807,11 @@ pub(super) fn spawn_pty_session(
             cwd: cwd.clone(),
             display_name: None,
             shell: shell.clone(),
+            token_usage: Default::default(),
         }),
     );
     state.assign_term_alias(&session_id);
+    state.record_session_opened(&session_id, None);
     state.metrics.total_spawned.fetch_add(1, Ordering::Relaxed);
     state
         .metrics
@@ -457,10 +867,12 @@ pub(super) async fn create_session(
     State(state): State<Arc<AppState>>,
     Json(body): Json<CreateSessionRequest>,
 ) -> impl IntoResponse {
-    if state.sessions.len() >= MAX_CONCURRENT_SESSIONS {
+    if !state.can_spawn_session() {
         return (
             StatusCode::TOO_MANY_REQUESTS,
-            Json(serde_json::json!({"error": "Max concurrent sessions reached"})),
+            Json(
+                serde_json::json!({"error": format!("Max concurrent sessions ({}) reached", state.max_sessions())}),
+            ),
         );
     }
 
@@ -474,7 +886,16 @@ pub(super) async fn create_session(
     }
     let shell = resolve_shell(body.shell);
 
-    match spawn_pty_session(state, shell, body.cwd, rows, cols, None, body.session_id) {
+    match spawn_pty_session(
+        state,
+        shell,
+        body.cwd,
+        rows,
+        cols,
+        None,
+        body.session_id,
+        &body.env,
+    ) {
         Ok(session_id) => (
             StatusCode::CREATED,
             Json(serde_json::json!({"session_id": session_id})),
@@ -511,6 +932,30 @@ pub(super) async fn resume_session(
     (StatusCode::OK, Json(serde_json::json!({"ok": true})))
 }
 
+pub(super) async fn signal_session(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Json(body): Json<SignalSessionRequest>,
+) -> Response {
+    let signal = match crate::pty::ForegroundSignal::parse(&body.signal) {
+        Ok(s) => s,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e})),
+            )
+                .into_response();
+        }
+    };
+    if !state.sessions.contains_key(&session_id) {
+        return session_not_found().into_response();
+    }
+    match crate::pty::signal_foreground(&state, &session_id, signal) {
+        Ok(pid) => Json(serde_json::json!({"pid": pid, "signal": signal.name()})).into_response(),
+        Err(e) => (StatusCode::CONFLICT, Json(serde_json::json!({"error": e}))).into_response(),
+    }
+}
+
 pub(super) async fn get_kitty_flags(
     State(state): State<Arc<AppState>>,
     Path(session_id): Path<String>,
@@ -566,6 +1011,18 @@ pub(super) async fn get_shell_state(
     Json(serde_json::json!({ "state": value }))
 }
 
+/// Where the session runs ("local"/"docker"/"ssh"), or null for unknown sessions.
+pub(super) async fn get_session_context(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    let value = state
+        .sessions
+        .get(&session_id)
+        .map(|entry| crate::pty::classify_session_context(&entry.lock().shell));
+    Json(serde_json::json!({ "context": value }))
+}
+
 /// Last relevant user prompt (>= 10 words) for a session, or null.
 pub(super) async fn get_last_prompt(
     State(state): State<Arc<AppState>>,
@@ -685,6 +1142,63 @@ pub(super) async fn get_process_stats(State(state): State<Arc<AppState>>) -> imp
     Json(crate::pty::collect_process_stats(&state))
 }
 
+/// CPU/memory summed over a session's process tree. 404 for unknown sessions,
+/// 410 once the session's process has exited.
+pub(super) async fn get_session_resources(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> Response {
+    if !state.sessions.contains_key(&session_id) {
+        return session_not_found().into_response();
+    }
+    let result =
+        tokio::task::spawn_blocking(move || crate::pty::session_resources(&state, &session_id))
+            .await;
+    match result {
+        Ok(Ok(resources)) => Json(resources).into_response(),
+        Ok(Err(e)) => (StatusCode::GONE, Json(serde_json::json!({ "error": e }))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": format!("spawn_blocking join error: {e}") })),
+        )
+            .into_response(),
+    }
+}
+
+/// List orphaned processes. Remote callers must be authenticated since this
+/// exposes host PIDs and process names.
+pub(super) async fn list_orphaned_processes(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    Json(crate::pty::find_orphaned_processes(&state)).into_response()
+}
+
+/// Kill an orphaned process. Requires `confirm: true`; remote callers must be
+/// authenticated since this signals processes on the host.
+pub(super) async fn kill_orphaned_process(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    auth: Option<Extension<Authenticated>>,
+    Json(body): Json<KillOrphanedProcessRequest>,
+) -> Response {
+    if let Err(resp) = require_local_or_auth(&addr, auth.is_some()) {
+        return resp.into_response();
+    }
+    match crate::pty::kill_orphaned_process_core(&state, body.pid, body.confirm) {
+        Ok(()) => Json(serde_json::json!({ "ok": true })).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": e })),
+        )
+            .into_response(),
+    }
+}
+
 pub(super) async fn process_monitor_panel() -> impl IntoResponse {
     axum::response::Html(include_str!("process_monitor.html"))
 }
@@ -693,10 +1207,12 @@ pub(super) async fn create_session_with_worktree(
     State(state): State<Arc<AppState>>,
     Json(body): Json<CreateSessionWithWorktreeRequest>,
 ) -> impl IntoResponse {
-    if state.sessions.len() >= MAX_CONCURRENT_SESSIONS {
+    if !state.can_spawn_session() {
         return (
             StatusCode::TOO_MANY_REQUESTS,
-            Json(serde_json::json!({"error": "Max concurrent sessions reached"})),
+            Json(
+                serde_json::json!({"error": format!("Max concurrent sessions ({}) reached", state.max_sessions())}),
+            ),
         );
     }
 
@@ -774,6 +1290,7 @@ pub(super) async fn create_session_with_worktree(
         cols,
         Some(worktree),
         body.config.session_id,
+        &body.config.env,
     ) {
         Ok(session_id) => {
             let mut response = serde_json::json!({
@@ -837,7 +1354,10 @@ pub(super) async fn ws_stream(
     // format=text and format=log both serve clean VtLogBuffer rows (no strip_ansi).
     let log_mode = format == "log" || format == "text";
     let initial_offset = query.offset;
-    ws.on_upgrade(move |socket| handle_ws_session(socket, id, state, log_mode, initial_offset))
+    let since = query.since;
+    ws.on_upgrade(move |socket| {
+        handle_ws_session(socket, id, state, log_mode, initial_offset, since)
+    })
 }
 
 /// Handle a WebSocket connection for a PTY session.
@@ -850,6 +1370,12 @@ pub(super) async fn ws_stream(
 /// output the client receives VT100-extracted log lines:
 /// `{"type":"log","lines":[...],"offset":N}`
 ///
+/// Raw output frames carry `offset` (stream byte offset of their first byte)
+/// and `next_offset` (the offset right after them). A client that drops can
+/// reconnect with `?since=<last next_offset>` to replay only what it missed; the server
+/// then sends `{"type":"resume","offset":N,"gap":bool}` first, where `gap`
+/// means the requested bytes were evicted and replay starts at `offset`.
+///
 /// Client → server messages are written to the PTY as input.
 async fn handle_ws_session(
     socket: WebSocket,
@@ -857,6 +1383,7 @@ async fn handle_ws_session(
     state: Arc<AppState>,
     log_mode: bool,
     initial_offset: Option<usize>,
+    since: Option<u64>,
 ) {
     let (mut ws_sender, mut ws_receiver) = socket.split();
 
@@ -884,10 +1411,18 @@ async fn handle_ws_session(
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
     let snapshot = state.output_buffers.get(&session_id).map(|ring| {
         let r = ring.lock();
-        let snap = if let Some(off) = initial_offset {
-            r.read_since(off as u64)
+        let total = r.total_written;
+        let snap = if let Some(since) = since {
+            let (data, start, gap) = r.read_resume(since);
+            (data, start, total, Some(gap))
         } else {
-            r.read_last(OUTPUT_RING_BUFFER_CAPACITY)
+            let (data, total) = if let Some(off) = initial_offset {
+                r.read_since(off as u64)
+            } else {
+                r.read_last(OUTPUT_RING_BUFFER_CAPACITY)
+            };
+            let start = total - data.len() as u64;
+            (data, start, total, None)
         };
         state
             .ws_clients
@@ -900,14 +1435,23 @@ async fn handle_ws_session(
 
     // Send catch-up data in chunks (64 KB) so the client can render progressively.
     const CATCHUP_CHUNK_SIZE: usize = 64 * 1024;
-    if let Some((data, total)) = snapshot
-        && !data.is_empty()
-    {
-        for chunk in data.chunks(CATCHUP_CHUNK_SIZE) {
+    let (data, start, total, resume_gap) = snapshot.unwrap_or((Vec::new(), 0, 0, None));
+    if let Some(gap) = resume_gap {
+        let frame = serde_json::json!({"type": "resume", "offset": start, "gap": gap, "total_written": total});
+        if futures_util::SinkExt::send(&mut ws_sender, Message::Text(frame.to_string().into()))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+    if !data.is_empty() {
+        for (i, chunk) in data.chunks(CATCHUP_CHUNK_SIZE).enumerate() {
             let text = String::from_utf8_lossy(chunk);
             if !text.is_empty() {
-                let frame =
-                    serde_json::json!({"type": "output", "data": text, "total_written": total});
+                let offset = start + (i * CATCHUP_CHUNK_SIZE) as u64;
+                let next_offset = offset + chunk.len() as u64;
+                let frame = serde_json::json!({"type": "output", "data": text, "offset": offset, "next_offset": next_offset, "total_written": total});
                 if futures_util::SinkExt::send(
                     &mut ws_sender,
                     Message::Text(frame.to_string().into()),
@@ -924,12 +1468,17 @@ async fn handle_ws_session(
     // Spawn a task to forward PTY output + parsed events to the WebSocket
     let sid_for_events = session_id.clone();
     let send_task = tokio::spawn(async move {
+        // Live chunks are the exact bytes appended to the ring right after
+        // the snapshot, so the stream offset can be tracked locally.
+        let mut next_offset = total;
         loop {
             tokio::select! {
                 // Raw PTY output from mpsc channel
                 data = rx.recv() => {
                     let Some(data) = data else { break };
-                    let frame = serde_json::json!({"type": "output", "data": data});
+                    let offset = next_offset;
+                    next_offset += data.len() as u64;
+                    let frame = serde_json::json!({"type": "output", "data": data, "offset": offset, "next_offset": next_offset});
                     if futures_util::SinkExt::send(
                         &mut ws_sender,
                         Message::Text(frame.to_string().into()),
@@ -1646,6 +2195,111 @@ pub(super) async fn terminal_request_frame(
 mod tests {
     use super::*;
 
+    fn search_query(q: &str) -> OutputSearchQuery {
+        OutputSearchQuery {
+            q: q.to_string(),
+            regex: false,
+            case_sensitive: false,
+            max_matches: None,
+            strip_ansi: false,
+        }
+    }
+
+    fn search_plain(
+        hay: &str,
+        base: u64,
+        q: &OutputSearchQuery,
+    ) -> Result<(Vec<OutputMatch>, bool), String> {
+        let offsets: Vec<usize> = (0..hay.len()).collect();
+        search_text(hay, &offsets, base, q)
+    }
+
+    #[test]
+    fn search_text_literal_is_case_insensitive_with_offsets() {
+        let hay = "build ok\nERROR: boom\nerror again";
+        let (matches, truncated) = search_plain(hay, 1000, &search_query("error")).unwrap();
+        assert!(!truncated);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].offset, 1009);
+        assert_eq!(matches[0].text, "ERROR");
+        assert!(matches[0].context.contains("build ok"));
+
+        let mut q = search_query("error");
+        q.case_sensitive = true;
+        assert_eq!(search_plain(hay, 0, &q).unwrap().0.len(), 1);
+
+        // Literal mode escapes regex metacharacters.
+        assert!(
+            search_plain("a.b", 0, &search_query("a.b"))
+                .unwrap()
+                .0
+                .len()
+                == 1
+        );
+        assert!(
+            search_plain("axb", 0, &search_query("a.b"))
+                .unwrap()
+                .0
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn search_text_regex_caps_matches_and_rejects_bad_patterns() {
+        let hay = "x1 x2 x3 x4";
+        let mut q = search_query(r"x\d");
+        q.regex = true;
+        q.max_matches = Some(2);
+        let (matches, truncated) = search_plain(hay, 0, &q).unwrap();
+        assert_eq!(matches.len(), 2);
+        assert!(truncated);
+
+        q.max_matches = Some(100_000);
+        assert_eq!(search_plain(hay, 0, &q).unwrap().0.len(), 4);
+
+        q.q = "(".to_string();
+        assert!(
+            search_plain(hay, 0, &q)
+                .unwrap_err()
+                .contains("Invalid regex")
+        );
+    }
+
+    #[test]
+    fn search_session_output_unknown_session_is_none() {
+        let state = super::super::tests::test_state();
+        let q = search_query("x");
+        assert!(search_session_output(&state, "nope", &q).unwrap().is_none());
+    }
+
+    #[test]
+    fn stripped_search_reports_raw_buffer_offsets() {
+        let raw = b"ok\x1b[31mERR\x1b[0mOR \xff\x1b]0;title\x07error";
+        let (text, offsets) = searchable_text(raw, true);
+        assert_eq!(text, "okERROR \u{fffd}error");
+        let (matches, _) = search_text(&text, &offsets, 100, &search_query("error")).unwrap();
+        assert_eq!(matches.len(), 2);
+        // "ERR\x1b[0mOR" starts at raw byte 7 and spans the reset sequence.
+        assert_eq!(matches[0].offset, 107);
+        assert_eq!(matches[0].length, 9);
+        assert_eq!(matches[0].text, "ERROR");
+        // Past the invalid byte and the OSC title: raw byte 28.
+        assert_eq!(matches[1].offset, 128);
+        assert_eq!(matches[1].length, 5);
+
+        let (raw_text, raw_offsets) = searchable_text(raw, false);
+        let (raw_matches, _) =
+            search_text(&raw_text, &raw_offsets, 100, &search_query("error")).unwrap();
+        assert_eq!(raw_matches.len(), 1);
+        assert_eq!(raw_matches[0].offset, 128);
+    }
+
+    #[test]
+    fn search_strip_ansi_defaults_to_true() {
+        let q: OutputSearchQuery = serde_json::from_str(r#"{"q":"x"}"#).unwrap();
+        assert!(q.strip_ansi);
+    }
+
     // is_separator_line tests live in chrome.rs (canonical location)
 
     // --- trim_screen_chrome ---
@@ -1901,6 +2555,7 @@ mod tests {
             80,
             None,
             None,
+            &Default::default(),
         );
 
         let session_id = match result {
@@ -1935,6 +2590,7 @@ mod tests {
             80,
             None,
             Some("client-provided-id".to_string()),
+            &Default::default(),
         );
         match result {
             Ok(id) => assert_eq!(
@@ -1960,6 +2616,7 @@ mod tests {
             80,
             None,
             Some("dup-id".to_string()),
+            &Default::default(),
         ) {
             Ok(id) => id,
             Err(_) => return, // PTY unavailable in CI — skip gracefully
@@ -1973,6 +2630,7 @@ mod tests {
             80,
             None,
             Some("dup-id".to_string()),
+            &Default::default(),
         )
         .expect("second spawn should succeed with a fresh id");
         assert_ne!(
@@ -96,6 +96,7 @@ fn event_type_name(event: &AppEvent) -> &'static str {
         AppEvent::McpToast { .. } => "mcp-toast",
         AppEvent::DirChanged { .. } => "dir-changed",
         AppEvent::WorktreeCreated { .. } => "worktree-created",
+        AppEvent::WorktreesChanged { .. } => "worktrees-changed",
         AppEvent::PeerRegistered { .. } => "peer-registered",
         AppEvent::PeerUnregistered { .. } => "peer-unregistered",
         AppEvent::UiTab { .. } => "ui-tab",
@@ -105,6 +106,10 @@ fn event_type_name(event: &AppEvent) -> &'static str {
         AppEvent::CloseHtmlTabs { .. } => "close-html-tabs",
         AppEvent::ScheduledJobCompleted { .. } => "scheduled-job-completed",
         AppEvent::DiffTriageProgress { .. } => "triage-progress",
+        AppEvent::SessionPossiblyWedged { .. } => "session-possibly-wedged",
+        AppEvent::RemoteAutoDisabled { .. } => "remote-auto-disabled",
+        AppEvent::RepositoriesChanged { .. } => "repositories-changed",
+        AppEvent::SessionSpawnRetry { .. } => "session-spawn-retry",
     }
 }
 
@@ -112,8 +117,12 @@ fn event_type_name(event: &AppEvent) -> &'static str {
 /// The SSE `event:` field already carries the type, so we only need the inner data.
 fn event_payload(event: &AppEvent) -> serde_json::Value {
     match event {
-        AppEvent::HeadChanged { repo_path, branch } => {
-            serde_json::json!({ "repo_path": repo_path, "branch": branch })
+        AppEvent::HeadChanged {
+            repo_path,
+            branch,
+            activate,
+        } => {
+            serde_json::json!({ "repo_path": repo_path, "branch": branch, "activate": activate })
         }
         AppEvent::RepoChanged { repo_path } => {
             serde_json::json!({ "repo_path": repo_path })
@@ -164,6 +173,12 @@ fn event_payload(event: &AppEvent) -> serde_json::Value {
         } => {
             serde_json::json!({ "repo_path": repo_path, "branch": branch, "worktree_path": worktree_path })
         }
+        AppEvent::WorktreesChanged {
+            repo_path,
+            worktrees,
+        } => {
+            serde_json::json!({ "repo_path": repo_path, "worktrees": worktrees })
+        }
         AppEvent::PeerRegistered { tuic_session, name } => {
             serde_json::json!({ "tuic_session": tuic_session, "name": name })
         }
@@ -229,5 +244,42 @@ fn event_payload(event: &AppEvent) -> serde_json::Value {
                 "llm_model": llm_model,
             })
         }
+        AppEvent::SessionPossiblyWedged {
+            session_id,
+            foreground,
+            foreground_pid,
+            silent_secs,
+        } => {
+            serde_json::json!({
+                "session_id": session_id,
+                "foreground": foreground,
+                "foreground_pid": foreground_pid,
+                "silent_secs": silent_secs,
+            })
+        }
+        AppEvent::RemoteAutoDisabled {
+            distinct_ips,
+            window_secs,
+        } => {
+            serde_json::json!({ "distinct_ips": distinct_ips, "window_secs": window_secs })
+        }
+        AppEvent::RepositoriesChanged { repo_order } => {
+            serde_json::json!({ "repo_order": repo_order })
+        }
+        AppEvent::SessionSpawnRetry {
+            attempt,
+            max_retries,
+            delay_ms,
+            error,
+            tuic_session,
+        } => {
+            serde_json::json!({
+                "attempt": attempt,
+                "max_retries": max_retries,
+                "delay_ms": delay_ms,
+                "error": error,
+                "tuic_session": tuic_session,
+            })
+        }
     }
 }
@@ -18,6 +18,17 @@ pub(super) struct VersionResponse {
     pub git_hash: &'static str,
 }
 
+#[derive(Serialize)]
+pub(super) struct WhoamiResponse {
+    /// Request passed the remote auth middleware (false on the local socket/router)
+    pub authenticated: bool,
+    /// Configured Basic Auth username; `None` means open access (no credentials set)
+    pub username: Option<String>,
+    pub remote_access_enabled: bool,
+    /// Authenticated with the session token (QR `?token=` or its cookie)
+    pub token_based: bool,
+}
+
 #[derive(Serialize)]
 pub(super) struct SessionInfo {
     pub session_id: String,
@@ -43,6 +54,10 @@ pub(super) struct CreateSessionRequest {
     /// locally-created and does not spawn a duplicate "PTY:" tab. Honored only
     /// when non-empty and not already in use; otherwise the backend mints one.
     pub session_id: Option<String>,
+    /// Extra env vars for this session only, merged last over the inherited
+    /// environment (see `env_file::validate_spawn_env` for the denylist).
+    #[serde(default)]
+    pub env: std::collections::HashMap<String, String>,
 }
 
 #[derive(Deserialize)]
@@ -50,17 +65,58 @@ pub(super) struct WriteRequest {
     pub data: String,
 }
 
+#[derive(Deserialize)]
+pub(super) struct BroadcastInputRequest {
+    pub session_ids: Vec<String>,
+    #[serde(default)]
+    pub input: Option<String>,
+    #[serde(default)]
+    pub special_key: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct SetNameRequest {
     pub name: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct SignalSessionRequest {
+    /// `SIGINT`, `SIGTERM` or `SIGKILL` (case-insensitive, `SIG` optional).
+    pub signal: String,
+}
+
 #[derive(Deserialize)]
 pub(super) struct ResizeRequest {
     pub rows: u16,
     pub cols: u16,
 }
 
+#[derive(Deserialize)]
+pub(super) struct ExportQuery {
+    pub format: crate::session_export::ExportFormat,
+}
+
+/// Body of `POST /sessions/{id}/record`. `path` is required for `start`.
+#[derive(Deserialize)]
+pub(super) struct RecordRequest {
+    pub action: String,
+    pub path: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct OutputSearchQuery {
+    pub q: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    pub max_matches: Option<usize>,
+    /// Match against ANSI-stripped text (default, as on MCP). Offsets always
+    /// index the raw stream.
+    #[serde(default = "default_strip_ansi")]
+    pub strip_ansi: bool,
+}
+
 #[derive(Deserialize)]
 pub(super) struct OutputQuery {
     pub limit: Option<usize>,
@@ -68,6 +124,12 @@ pub(super) struct OutputQuery {
     pub format: Option<String>,
     /// Starting offset for log-mode WebSocket catch-up (skip lines already fetched via HTTP).
     pub offset: Option<usize>,
+    /// Raw-mode WebSocket resume token: byte offset to replay from after a drop.
+    pub since: Option<u64>,
+    /// Raw-mode HTTP polling: byte offset to read forward from (a previous `next_offset`).
+    pub from_offset: Option<u64>,
+    /// Raw-mode HTTP polling: page size cap in bytes.
+    pub max_bytes: Option<usize>,
 }
 
 #[derive(Deserialize)]
@@ -75,6 +137,22 @@ pub(super) struct PathQuery {
     pub path: String,
 }
 
+#[derive(Deserialize)]
+pub(super) struct RepoStatsQuery {
+    pub path: String,
+    /// Comma-separated directory names to skip in addition to the defaults.
+    pub extra_ignores: Option<String>,
+    /// Opt into per-file line counts (reads every tracked file; not cached).
+    #[serde(default)]
+    pub count_lines: bool,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ScanReposQuery {
+    pub root_dir: String,
+    pub max_depth: Option<usize>,
+}
+
 #[derive(Deserialize, Default)]
 pub(super) struct OptionalRepoQuery {
     #[serde(default, rename = "repoPath")]
@@ -101,6 +179,27 @@ pub(super) struct ApprovePrRequest {
     pub pr_number: i64,
 }
 
+#[derive(Deserialize)]
+pub(super) struct RequestReviewersRequest {
+    #[serde(rename = "repoPath")]
+    pub repo_path: String,
+    #[serde(rename = "prNumber")]
+    pub pr_number: i64,
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+    #[serde(default, rename = "teamReviewers")]
+    pub team_reviewers: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AddPrCommentRequest {
+    #[serde(rename = "repoPath")]
+    pub repo_path: String,
+    #[serde(rename = "prNumber")]
+    pub pr_number: i64,
+    pub body: String,
+}
+
 #[derive(Deserialize)]
 pub(super) struct SpawnAgentRequest {
     pub rows: Option<u16>,
@@ -113,6 +212,9 @@ pub(super) struct SpawnAgentRequest {
     pub agent_type: Option<String>,
     pub binary_path: Option<String>,
     pub args: Option<Vec<String>>,
+    /// Extra env vars for this agent only, merged over the repo env file.
+    #[serde(default)]
+    pub env: std::collections::HashMap<String, String>,
 }
 
 #[derive(Deserialize)]
@@ -128,6 +230,16 @@ pub(super) struct CreateWorktreeRequest {
     pub base_ref: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct CreateWorktreeFromPrRequest {
+    pub repo_path: String,
+    pub pr_number: i64,
+    /// Remote of the repository the PR belongs to; defaults to the current
+    /// branch's upstream remote, then `origin`.
+    #[serde(default)]
+    pub remote: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct RemoveWorktreeQuery {
     #[serde(rename = "repoPath")]
@@ -137,14 +249,27 @@ pub(super) struct RemoveWorktreeQuery {
     pub delete_branch: Option<bool>,
     /// When true, force-remove a locked worktree (mirrors the desktop
     /// confirmation dialog). Also switches the branch deletion from `git
-    /// branch -d` (safe) to `-D` (force). Defaults to false.
+    /// branch -d` (safe) to `-D` (force), and removes a worktree with
+    /// uncommitted changes. Defaults to false.
     #[serde(default)]
     pub force: Option<bool>,
+    /// When true, remove nothing and return a `WorktreeRemovalReport`.
+    #[serde(rename = "dryRun", default)]
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SetActiveSelectionRequest {
+    pub repo_path: String,
+    #[serde(default)]
+    pub worktree_path: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub(super) struct GenerateWorktreeNameRequest {
     pub existing_names: Vec<String>,
+    #[serde(default)]
+    pub repo_path: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -155,6 +280,12 @@ pub(super) struct FileQuery {
     pub untracked: Option<bool>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct RecordRecentFileRequest {
+    pub path: String,
+    pub file: String,
+}
+
 #[derive(Deserialize)]
 pub(super) struct RenameBranchRequest {
     pub path: String,
@@ -176,6 +307,30 @@ pub(super) struct BranchQuery {
 pub(super) struct ProcessPromptRequest {
     pub content: String,
     pub variables: std::collections::HashMap<String, String>,
+    /// Library entry being rendered; usage is only counted when `send` is true.
+    #[serde(default, rename = "promptId")]
+    pub prompt_id: Option<String>,
+    #[serde(default)]
+    pub send: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct PromptLibraryQuery {
+    #[serde(default)]
+    pub sort: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct KeybindingsExportQuery {
+    #[serde(default, rename = "includeUiPrefs")]
+    pub include_ui_prefs: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct KeybindingsImportRequest {
+    pub json: String,
+    #[serde(default, rename = "knownActions")]
+    pub known_actions: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
@@ -211,6 +366,16 @@ pub(super) struct FsFileQuery {
     pub file: String,
 }
 
+#[derive(Deserialize)]
+pub(super) struct FsDiffQuery {
+    #[serde(rename = "repoPath")]
+    pub repo_path: String,
+    #[serde(rename = "fileA")]
+    pub file_a: String,
+    #[serde(rename = "fileB")]
+    pub file_b: String,
+}
+
 #[derive(Deserialize)]
 pub(super) struct FsSearchQuery {
     #[serde(rename = "repoPath")]
@@ -345,6 +510,14 @@ pub(super) struct CheckoutRemoteRequest {
     pub branch_name: String,
 }
 
+#[derive(Deserialize)]
+pub(super) struct PruneOrphanedWorktreesRequest {
+    #[serde(rename = "repoPath")]
+    pub repo_path: String,
+    #[serde(default, rename = "dryRun")]
+    pub dry_run: bool,
+}
+
 #[derive(Deserialize)]
 pub(super) struct RemoveOrphanRequest {
     #[serde(rename = "repoPath")]
@@ -353,6 +526,19 @@ pub(super) struct RemoveOrphanRequest {
     pub worktree_path: String,
 }
 
+#[derive(Deserialize)]
+pub(super) struct PruneAgentTranscriptsRequest {
+    #[serde(rename = "olderThanDays", default)]
+    pub older_than_days: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct KillOrphanedProcessRequest {
+    pub pid: u32,
+    #[serde(default)]
+    pub confirm: bool,
+}
+
 #[derive(Deserialize)]
 pub(super) struct SwitchBranchRequest {
     #[serde(rename = "repoPath")]
@@ -361,6 +547,9 @@ pub(super) struct SwitchBranchRequest {
     pub branch_name: String,
     pub force: bool,
     pub stash: bool,
+    /// Stash before checkout and pop after (unlike `stash`, which never pops)
+    #[serde(default)]
+    pub autostash: bool,
 }
 
 #[derive(Deserialize)]
@@ -385,6 +574,12 @@ pub(super) struct MergePrRequest {
     /// "merge", "squash", or "rebase"
     #[serde(rename = "mergeMethod")]
     pub merge_method: String,
+    /// Custom merge commit title; GitHub's default when omitted.
+    #[serde(rename = "commitTitle", default)]
+    pub commit_title: Option<String>,
+    /// Custom merge commit body; GitHub's default when omitted.
+    #[serde(rename = "commitMessage", default)]
+    pub commit_message: Option<String>,
 }
 
 // --- Recent commits query ---
@@ -404,6 +599,12 @@ pub(super) struct GetAllPrStatusesRequest {
     pub include_merged: bool,
 }
 
+#[derive(Deserialize)]
+pub(super) struct HydratePrCheckDetailsRequest {
+    pub path: String,
+    pub pr_numbers: Vec<i64>,
+}
+
 // --- GitHub Issues ---
 
 #[derive(Deserialize)]
@@ -413,6 +614,10 @@ pub(super) struct IssuesQuery {
     pub filter: String,
 }
 
+fn default_strip_ansi() -> bool {
+    true
+}
+
 fn default_issue_filter() -> String {
     "assigned".to_string()
 }
@@ -425,6 +630,35 @@ pub(super) struct IssueActionRequest {
     pub issue_number: i64,
 }
 
+#[derive(Deserialize)]
+pub(super) struct ListIssuesQuery {
+    pub path: String,
+    #[serde(default = "default_issue_state")]
+    pub state: String,
+    /// Comma-separated label names.
+    #[serde(default)]
+    pub labels: Option<String>,
+}
+
+fn default_issue_state() -> String {
+    "open".to_string()
+}
+
+#[derive(Deserialize)]
+pub(super) struct IssueQuery {
+    pub path: String,
+    pub number: i64,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AddIssueCommentRequest {
+    #[serde(rename = "repoPath")]
+    pub repo_path: String,
+    #[serde(rename = "issueNumber")]
+    pub issue_number: i64,
+    pub body: String,
+}
+
 // --- GitHub auth / misc ---
 
 #[derive(Deserialize)]
@@ -462,6 +696,21 @@ pub(super) struct SetBranchLabelRequest {
     pub label: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct NotificationAllowedRequest {
+    pub sound: String,
+    #[serde(default, rename = "sessionId")]
+    pub session_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ResetRepoSettingsRequest {
+    #[serde(rename = "repoPath")]
+    pub repo_path: String,
+    #[serde(default)]
+    pub categories: Option<Vec<String>>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct SaveNoteImageRequest {
     #[serde(rename = "noteId")]
@@ -548,6 +797,36 @@ pub(super) struct StashRefRequest {
     pub stash_ref: String,
 }
 
+#[derive(Deserialize)]
+pub(super) struct DiffStatsQuery {
+    pub path: String,
+    pub scope: Option<String>,
+    pub per_file: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ChangedFilesQuery {
+    pub path: String,
+    pub scope: Option<String>,
+    pub base: Option<String>,
+    pub head: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct GitConfigQuery {
+    pub path: String,
+    pub key: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SetGitConfigRequest {
+    pub path: String,
+    pub key: String,
+    pub value: String,
+    /// "local", "global" or "worktree".
+    pub scope: String,
+}
+
 #[derive(Deserialize)]
 pub(super) struct FilePathQuery {
     pub path: String,
@@ -560,6 +839,9 @@ pub(super) struct FilePathQuery {
 pub(super) struct FileBlameQuery {
     pub path: String,
     pub file: String,
+    /// 1-based inclusive range; omit for the whole file.
+    pub start_line: Option<u32>,
+    pub end_line: Option<u32>,
 }
 
 #[derive(Deserialize)]
@@ -580,12 +862,21 @@ pub(super) struct CommitRequest {
     pub path: String,
     pub message: String,
     pub amend: Option<bool>,
+    /// Repo-relative files to stage before committing.
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Stage every change (`git add -A`) before committing.
+    #[serde(default)]
+    pub all: bool,
 }
 
 #[derive(Deserialize)]
 pub(super) struct RunGitCommandRequest {
     pub path: String,
     pub args: Vec<String>,
+    /// Stash local changes before the command and restore them after
+    #[serde(default)]
+    pub autostash: bool,
 }
 
 // --- GitHub poller ---
@@ -602,6 +893,11 @@ pub(super) struct SetVisibilityRequest {
     pub visible: bool,
 }
 
+#[derive(Deserialize)]
+pub(super) struct SetPollActiveRequest {
+    pub active: bool,
+}
+
 #[derive(Deserialize)]
 pub(super) struct PollRepoRequest {
     pub path: String,
@@ -697,6 +993,11 @@ pub(super) struct ClaudeStatsQuery {
 
 // --- Git panel (story 064) ---
 
+#[derive(Deserialize)]
+pub(super) struct ConflictMarkersRequest {
+    pub content: String,
+}
+
 #[derive(Deserialize)]
 pub(super) struct GitGutterQuery {
     pub path: String,
@@ -761,6 +1062,37 @@ pub(super) struct GitDeleteBranchRequest {
     pub force: bool,
 }
 
+#[derive(Deserialize)]
+pub(super) struct GitRebaseRequest {
+    pub path: String,
+    pub onto: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct GitCherryPickRequest {
+    pub path: String,
+    #[serde(rename = "commitSha")]
+    pub commit_sha: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct GitStashRequest {
+    pub path: String,
+    /// save, list, pop, drop
+    pub action: String,
+    pub message: Option<String>,
+    /// N in `stash@{N}` for pop/drop (default 0)
+    pub index: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct GitCheckoutBranchRequest {
+    pub path: String,
+    pub branch: String,
+    #[serde(default)]
+    pub autostash: bool,
+}
+
 #[derive(Deserialize)]
 pub(super) struct GitDeleteLocalBranchRequest {
     #[serde(rename = "repoPath")]
@@ -778,3 +1110,8 @@ pub(super) struct GitUpdateFromBaseRequest {
     pub branch_name: String,
     pub strategy: Option<String>,
 }
+
+#[derive(Deserialize)]
+pub(super) struct ReorderRepositoriesRequest {
+    pub order: Vec<String>,
+}
@@ -162,6 +162,57 @@ pub(super) async fn create_worktree_http(
     }
 }
 
+pub(super) async fn create_worktree_from_pr_http(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<CreateWorktreeFromPrRequest>,
+) -> Response {
+    if let Err(e) = validate_repo_path(&body.repo_path) {
+        return e.into_response();
+    }
+    match crate::worktree::create_worktree_from_pr_impl(
+        &state,
+        &body.repo_path,
+        body.pr_number,
+        body.remote.clone(),
+    )
+    .await
+    {
+        Ok(wt) => {
+            let wt_path = wt.path.to_string_lossy().to_string();
+            let branch_name = wt.branch.clone().unwrap_or_default();
+            let _ = state
+                .event_bus
+                .send(crate::state::AppEvent::WorktreeCreated {
+                    repo_path: body.repo_path.clone(),
+                    branch: branch_name.clone(),
+                    worktree_path: wt_path.clone(),
+                });
+            #[cfg(feature = "desktop")]
+            if let Some(handle) = state.app_handle.read().as_ref() {
+                let _ = handle.emit(
+                    "worktree-created",
+                    serde_json::json!({
+                        "repo_path": &body.repo_path,
+                        "branch": &branch_name,
+                        "worktree_path": &wt_path,
+                    }),
+                );
+            }
+            (
+                StatusCode::CREATED,
+                Json(serde_json::json!({
+                    "name": wt.name,
+                    "path": wt_path,
+                    "branch": wt.branch,
+                    "base_repo": wt.base_repo.to_string_lossy(),
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => err_500(&e),
+    }
+}
+
 pub(super) async fn remove_worktree_http(
     Path(branch): Path<String>,
     Query(q): Query<RemoveWorktreeQuery>,
@@ -170,6 +221,17 @@ pub(super) async fn remove_worktree_http(
         return e.into_response();
     }
     let repo_path = q.repo_path.clone();
+    if q.dry_run.unwrap_or(false) {
+        let result = tokio::task::spawn_blocking(move || {
+            crate::worktree::preview_worktree_removal(&repo_path, &branch)
+        })
+        .await;
+        return match result {
+            Ok(Ok(report)) => (StatusCode::OK, Json(report)).into_response(),
+            Ok(Err(e)) => err_500(&e),
+            Err(e) => err_500(&format!("task panic: {e}")),
+        };
+    }
     let delete_branch = q.delete_branch.unwrap_or(true);
     let force = q.force.unwrap_or(false);
     let result = tokio::task::spawn_blocking(move || {
@@ -239,12 +301,52 @@ pub(super) async fn remove_orphan_worktree_http(
     }
 }
 
+pub(super) async fn prune_orphaned_worktrees_http(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<PruneOrphanedWorktreesRequest>,
+) -> Response {
+    if let Err(e) = validate_repo_path(&body.repo_path) {
+        return e.into_response();
+    }
+    let repo_path = body.repo_path.clone();
+    let dry_run = body.dry_run;
+    let result = tokio::task::spawn_blocking(move || {
+        crate::worktree::prune_orphaned_worktrees_impl(&repo_path, dry_run)
+    })
+    .await;
+    match result {
+        Ok(Ok(report)) => {
+            if !dry_run {
+                state.invalidate_repo_caches(&body.repo_path);
+            }
+            (StatusCode::OK, Json(report)).into_response()
+        }
+        Ok(Err(e)) => err_500(&e),
+        Err(e) => err_500(&format!("task panic: {e}")),
+    }
+}
+
 pub(super) async fn generate_worktree_name_http(
+    State(state): State<Arc<AppState>>,
     Json(body): Json<GenerateWorktreeNameRequest>,
-) -> impl IntoResponse {
-    Json(crate::worktree::generate_worktree_name_cmd(
-        body.existing_names,
-    ))
+) -> Response {
+    if let Some(repo) = &body.repo_path
+        && let Err(e) = validate_repo_path(repo)
+    {
+        return e.into_response();
+    }
+    let result = tokio::task::spawn_blocking(move || {
+        crate::worktree::generate_worktree_name_for(
+            &state,
+            &body.existing_names,
+            body.repo_path.as_deref(),
+        )
+    })
+    .await;
+    match result {
+        Ok(name) => Json(name).into_response(),
+        Err(e) => err_500(&format!("task panic: {e}")),
+    }
 }
 
 pub(super) async fn list_local_branches_http(Query(q): Query<PathQuery>) -> Response {
@@ -298,6 +400,8 @@ pub(super) async fn merge_pr_via_github_http(
         &body.repo_path,
         body.pr_number,
         &body.merge_method,
+        body.commit_title.as_deref(),
+        body.commit_message.as_deref(),
         &state,
     )
     .await
@@ -365,9 +469,10 @@ pub(super) async fn switch_branch_http(
         branch_name,
         force,
         stash,
+        autostash,
     } = body;
     let res = tokio::task::spawn_blocking(move || {
-        crate::worktree::switch_branch_impl(&state, repo_path, branch_name, force, stash)
+        crate::worktree::switch_branch_impl(&state, repo_path, branch_name, force, stash, autostash)
     })
     .await;
     match res {
@@ -0,0 +1,254 @@
+//! Per-session notification throttling and quiet hours.
+//!
+//! With several agents running, every question/completion would otherwise
+//! notify immediately. The throttle remembers when each `(session, type)`
+//! pair last fired and drops repeats inside `min_interval_secs`; during the
+//! configured quiet hours only attention-needed types (question, error) get
+//! through. Desktop sounds (`notification_allowed`) and mobile push are
+//! throttled separately, so one never swallows the other.
+
+use crate::config::NotificationConfig;
+#[cfg(feature = "desktop")]
+use crate::state::AppState;
+use dashmap::DashMap;
+#[cfg(feature = "desktop")]
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+#[cfg(feature = "desktop")]
+use tauri::State;
+
+/// Notification types that still fire during quiet hours.
+const ATTENTION_TYPES: &[&str] = &["question", "error"];
+
+/// Where a notification is delivered; each channel has its own timestamps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum NotificationChannel {
+    Desktop,
+    Push,
+}
+
+#[derive(Default)]
+pub(crate) struct NotificationThrottle {
+    /// (channel, session_id, type) → when that triple last fired.
+    last_fired: DashMap<(NotificationChannel, String, String), Instant>,
+    /// Live notification config: read from disk on first use, then replaced
+    /// by `save_notification_config`.
+    config: parking_lot::RwLock<Option<NotificationConfig>>,
+}
+
+impl NotificationThrottle {
+    /// The live notification config.
+    pub(crate) fn config(&self) -> NotificationConfig {
+        if let Some(config) = self.config.read().as_ref() {
+            return config.clone();
+        }
+        self.config
+            .write()
+            .get_or_insert_with(crate::config::load_notification_config)
+            .clone()
+    }
+
+    pub(crate) fn set_config(&self, config: NotificationConfig) {
+        *self.config.write() = Some(config);
+    }
+
+    /// Decide whether a `kind` notification for `session_id` may fire, and
+    /// record it if so. `minute_of_day` is local time (0..1440), passed in so
+    /// tests don't depend on the clock.
+    pub(crate) fn allow(
+        &self,
+        config: &NotificationConfig,
+        channel: NotificationChannel,
+        session_id: &str,
+        kind: &str,
+        now: Instant,
+        minute_of_day: u32,
+    ) -> bool {
+        if let Some(quiet) = &config.quiet_hours
+            && quiet.contains(minute_of_day)
+            && !ATTENTION_TYPES.contains(&kind)
+        {
+            return false;
+        }
+        if config.min_interval_secs == 0 {
+            return true;
+        }
+        let min_interval = Duration::from_secs(config.min_interval_secs);
+        let key = (channel, session_id.to_string(), kind.to_string());
+        if let Some(last) = self.last_fired.get(&key)
+            && now.saturating_duration_since(*last) < min_interval
+        {
+            return false;
+        }
+        self.last_fired.insert(key, now);
+        true
+    }
+
+    /// [`allow`](Self::allow) with the live config at the current wall-clock time.
+    pub(crate) fn allow_now(
+        &self,
+        channel: NotificationChannel,
+        session_id: &str,
+        kind: &str,
+    ) -> bool {
+        use chrono::Timelike;
+        let local = chrono::Local::now();
+        self.allow(
+            &self.config(),
+            channel,
+            session_id,
+            kind,
+            Instant::now(),
+            local.hour() * 60 + local.minute(),
+        )
+    }
+
+    /// Forget a session's timestamps (called when it exits).
+    pub(crate) fn clear_session(&self, session_id: &str) {
+        self.last_fired.retain(|(_, sid, _), _| sid != session_id);
+    }
+}
+
+/// Gate a desktop notification `sound` through quiet hours and the
+/// per-(session, type) throttle, recording it when allowed. Notifications
+/// without a session share one bucket.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn notification_allowed(
+    state: State<'_, Arc<AppState>>,
+    sound: String,
+    session_id: Option<String>,
+) -> bool {
+    state.notification_throttle.allow_now(
+        NotificationChannel::Desktop,
+        session_id.as_deref().unwrap_or(""),
+        &sound,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::QuietHours;
+
+    /// `allow` on the desktop channel.
+    fn allow(
+        throttle: &NotificationThrottle,
+        config: &NotificationConfig,
+        session_id: &str,
+        kind: &str,
+        now: Instant,
+        minute_of_day: u32,
+    ) -> bool {
+        throttle.allow(
+            config,
+            NotificationChannel::Desktop,
+            session_id,
+            kind,
+            now,
+            minute_of_day,
+        )
+    }
+
+    fn throttled(secs: u64) -> NotificationConfig {
+        NotificationConfig {
+            min_interval_secs: secs,
+            ..NotificationConfig::default()
+        }
+    }
+
+    #[test]
+    fn rapid_same_type_events_fire_once() {
+        let throttle = NotificationThrottle::default();
+        let config = throttled(30);
+        let t0 = Instant::now();
+        assert!(allow(&throttle, &config, "s1", "completion", t0, 600));
+        assert!(!allow(
+            &throttle,
+            &config,
+            "s1",
+            "completion",
+            t0 + Duration::from_secs(1),
+            600
+        ));
+        assert!(allow(
+            &throttle,
+            &config,
+            "s1",
+            "completion",
+            t0 + Duration::from_secs(31),
+            600
+        ));
+    }
+
+    #[test]
+    fn different_type_or_session_is_not_throttled() {
+        let throttle = NotificationThrottle::default();
+        let config = throttled(30);
+        let t0 = Instant::now();
+        assert!(allow(&throttle, &config, "s1", "completion", t0, 600));
+        assert!(allow(&throttle, &config, "s1", "question", t0, 600));
+        assert!(allow(&throttle, &config, "s2", "completion", t0, 600));
+    }
+
+    #[test]
+    fn quiet_hours_only_let_attention_types_through() {
+        let throttle = NotificationThrottle::default();
+        let config = NotificationConfig {
+            quiet_hours: Some(QuietHours {
+                start: "22:00".into(),
+                end: "07:30".into(),
+            }),
+            ..NotificationConfig::default()
+        };
+        let t0 = Instant::now();
+        let late = 23 * 60;
+        let early = 7 * 60 + 15;
+        let day = 12 * 60;
+        assert!(!allow(&throttle, &config, "s1", "completion", t0, late));
+        assert!(!allow(&throttle, &config, "s1", "info", t0, early));
+        assert!(allow(&throttle, &config, "s1", "question", t0, late));
+        assert!(allow(&throttle, &config, "s1", "error", t0, early));
+        assert!(allow(&throttle, &config, "s1", "completion", t0, day));
+    }
+
+    #[test]
+    fn desktop_and_push_are_throttled_separately() {
+        let throttle = NotificationThrottle::default();
+        let config = throttled(30);
+        let t0 = Instant::now();
+        assert!(throttle.allow(
+            &config,
+            NotificationChannel::Desktop,
+            "s1",
+            "completion",
+            t0,
+            600
+        ));
+        assert!(throttle.allow(
+            &config,
+            NotificationChannel::Push,
+            "s1",
+            "completion",
+            t0,
+            600
+        ));
+        assert!(!throttle.allow(
+            &config,
+            NotificationChannel::Push,
+            "s1",
+            "completion",
+            t0,
+            600
+        ));
+    }
+
+    #[test]
+    fn set_config_replaces_the_live_config() {
+        let throttle = NotificationThrottle::default();
+        throttle.set_config(throttled(30));
+        assert_eq!(throttle.config().min_interval_secs, 30);
+        assert!(throttle.allow_now(NotificationChannel::Desktop, "s1", "completion"));
+        assert!(!throttle.allow_now(NotificationChannel::Desktop, "s1", "completion"));
+    }
+}
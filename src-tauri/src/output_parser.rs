@@ -1,4 +1,7 @@
 use serde::Serialize;
+use std::borrow::Cow;
+
+use crate::config::{CustomOutputPattern, CustomPatternKind};
 
 /// Structured events parsed from PTY output
 #[derive(Clone, Debug, Serialize)]
@@ -28,6 +31,9 @@ pub enum ParsedEvent {
         state: u8, // 0=remove, 1=normal, 2=error, 3=indeterminate
         value: u8, // 0-100
     },
+    /// Terminal title set via OSC 0 (icon + title) or OSC 2 (title)
+    #[serde(rename = "title")]
+    Title { title: String },
     /// Agent is waiting for user input (question, confirmation, menu)
     #[serde(rename = "question")]
     Question {
@@ -52,6 +58,11 @@ pub enum ParsedEvent {
     /// Plan file detected in terminal output (e.g. plans/foo.md, .claude/plans/bar.md)
     #[serde(rename = "plan-file")]
     PlanFile { path: String },
+    /// Agent entered or left plan mode (Claude Code `⏸ plan mode on` footer,
+    /// the plan-approval prompt, `Exited plan mode`). Complements PlanFile:
+    /// tracks the mode itself so the UI can badge "planning" vs executing.
+    #[serde(rename = "plan-mode")]
+    PlanMode { active: bool },
     /// User submitted a line of input via the PTY.
     ///
     /// `line` is the absolute prompt row (`history_size + cursor row`, the same
@@ -82,6 +93,10 @@ pub enum ParsedEvent {
         #[serde(skip_serializing_if = "Option::is_none")]
         title: Option<String>,
     },
+    /// Agent-reported error it can't resolve on its own.
+    /// Emitted via `[[error: <text>]]` or `⟦error: <text>⟧` at column 0.
+    #[serde(rename = "agent-error")]
+    AgentError { text: String },
     /// Suggested follow-up actions for the user to choose from.
     /// Emitted via `suggest: A | B | C` at column 0.
     #[serde(rename = "suggest")]
@@ -90,6 +105,15 @@ pub enum ParsedEvent {
     /// Fired when the user types / in an agent TUI and a menu appears.
     #[serde(rename = "slash-menu")]
     SlashMenu { items: Vec<SlashMenuItem> },
+    /// Slash command submitted at the agent prompt (`/compact`, `/model opus`).
+    /// Parsed from the echoed input line; paths like `/usr/bin/env` never match.
+    #[serde(rename = "slash-command")]
+    SlashCommand {
+        /// Command including the leading slash (e.g. "/model").
+        command: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        args: Option<String>,
+    },
     /// Numbered choice dialog rendered below the prompt line (edit-confirmation,
     /// bash-confirmation, apply-patch, etc.). Cross-agent: Claude Code, Codex,
     /// Aider, Gemini all follow the same "title? / N. option" layout.
@@ -140,6 +164,117 @@ pub enum ParsedEvent {
         /// "in-use" (session id already locked) or "not-found" (session id not resumable)
         kind: String,
     },
+    /// Agent is blocked on a tool permission prompt (Claude Code "Bash command …
+    /// Do you want to proceed?", edit/create-file confirms, `Allow X to run …? [y/n]`).
+    /// A high-stakes subclass of question: carries what would run so the UI can
+    /// show it verbatim. Emitted alongside (not instead of) the generic ChoicePrompt.
+    #[serde(rename = "tool-approval")]
+    ToolApproval {
+        /// Tool requesting permission ("Bash", "Edit", "Write", "WebFetch", …).
+        tool: String,
+        /// The command line or file path the tool would act on.
+        command: String,
+        options: Vec<ChoiceOption>,
+    },
+    /// Compiler or test-runner failure printed in the terminal: cargo
+    /// `error[E0308]` / `could not compile`, `npm ERR!`, pytest `=== N failed`
+    /// summaries, tsc `error TS2322`. Lines inside code or diffs are ignored.
+    #[serde(rename = "build-failure")]
+    BuildFailure {
+        /// Toolchain that failed: "cargo", "npm", "pytest" or "tsc".
+        tool: String,
+        /// The failing line, trimmed.
+        summary: String,
+    },
+    /// The agent finished its turn: a completion marker (`✓ Completed`,
+    /// `All tasks complete`, a lone `Done.`, Claude Code's `✻ Worked for 2m 5s`,
+    /// a custom `task_complete` pattern) or the prompt returning after sustained
+    /// output, followed by a silence window. Emitted by the silence timer in
+    /// pty.rs, never by `parse()`.
+    #[serde(rename = "task-complete")]
+    TaskComplete {
+        /// The completion line, trimmed ("Prompt returned" for the prompt case).
+        summary: String,
+    },
+    /// Token/cost usage footer: Claude Code `↑ 2.4k ↓ 18k tokens · $0.14`,
+    /// Codex `Token usage: total=… input=… output=…`, or a custom
+    /// `token_usage` pattern. The parser reports what the footer shows (a
+    /// missing field is `None`); the PTY reader feeds that through the
+    /// session's [`TokenUsageCounter`] and emits running session totals.
+    #[serde(rename = "token-usage")]
+    TokenUsage {
+        input_tokens: Option<u64>,
+        output_tokens: Option<u64>,
+        cost_usd: Option<f64>,
+    },
+}
+
+/// Per-session running totals for [`ParsedEvent::TokenUsage`].
+///
+/// Agent footers show a counter for the current turn that grows and then
+/// restarts. Each field is tracked on its own: a reading above the last one
+/// adds the difference, a lower reading means the counter restarted and adds
+/// the whole value, an equal one (a repaint) adds nothing.
+#[derive(Debug, Default)]
+pub(crate) struct TokenUsageCounter {
+    last_input: Option<u64>,
+    last_output: Option<u64>,
+    last_cost: Option<f64>,
+    input_tokens: u64,
+    output_tokens: u64,
+    cost_usd: Option<f64>,
+}
+
+impl TokenUsageCounter {
+    /// Feed one footer reading. Returns the running totals as a
+    /// `TokenUsage` event, or `None` when the reading added nothing.
+    pub(crate) fn record(
+        &mut self,
+        input: Option<u64>,
+        output: Option<u64>,
+        cost: Option<f64>,
+    ) -> Option<ParsedEvent> {
+        fn delta(last: &mut Option<u64>, now: u64) -> u64 {
+            let d = match *last {
+                Some(prev) if now >= prev => now - prev,
+                _ => now,
+            };
+            *last = Some(now);
+            d
+        }
+        let mut changed = false;
+        if let Some(now) = input {
+            let d = delta(&mut self.last_input, now);
+            self.input_tokens += d;
+            changed |= d > 0;
+        }
+        if let Some(now) = output {
+            let d = delta(&mut self.last_output, now);
+            self.output_tokens += d;
+            changed |= d > 0;
+        }
+        if let Some(now) = cost {
+            let d = match self.last_cost {
+                Some(prev) if now >= prev => now - prev,
+                _ => now,
+            };
+            self.last_cost = Some(now);
+            if d > 0.0 || self.cost_usd.is_none() {
+                self.cost_usd = Some(self.cost_usd.unwrap_or(0.0) + d);
+                changed = true;
+            }
+        }
+        changed.then(|| self.totals())
+    }
+
+    /// Current totals as a `TokenUsage` event.
+    pub(crate) fn totals(&self) -> ParsedEvent {
+        ParsedEvent::TokenUsage {
+            input_tokens: Some(self.input_tokens),
+            output_tokens: Some(self.output_tokens),
+            cost_usd: self.cost_usd,
+        }
+    }
 }
 
 /// Payload for ParsedEvent::ChoicePrompt. Separate struct so it can be reused
@@ -184,6 +319,8 @@ pub struct SlashMenuItem {
 pub struct OutputParser {
     rate_limit_patterns: &'static [RateLimitPattern],
     api_error_patterns: &'static [ApiErrorPattern],
+    /// User-defined patterns from `custom_output_patterns`, checked after the built-ins.
+    custom_patterns: CustomPatterns,
     /// Dedup: last emitted suggest items to suppress re-emission on scroll.
     last_suggest_items: Option<Vec<String>>,
     /// Dedup: last emitted api-error matched text to suppress re-emission
@@ -192,19 +329,139 @@ pub struct OutputParser {
     /// Dedup: true after first session conflict detected — suppresses re-emission
     /// when the same error text remains visible in the VT log buffer.
     session_conflict_fired: bool,
+    /// Dedup: last emitted plan-mode state — the footer banner stays on screen
+    /// for the whole planning phase, so only transitions are emitted.
+    last_plan_mode: Option<bool>,
+    /// Dedup: last emitted build-failure summary — compiler output stays on
+    /// screen and is re-parsed when rows repaint.
+    last_build_failure: Option<String>,
+    /// Detectors switched off via `disabled_parsers`; fixed at construction.
+    disabled: DisabledParsers,
+}
+
+/// Built-in detectors that `disabled_parsers` can switch off, by wire `type`
+/// tag: `"question"`, `"rate-limit"`, `"plan-file"`, `"intent"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct DisabledParsers {
+    question: bool,
+    rate_limit: bool,
+    plan_file: bool,
+    intent: bool,
+}
+
+impl DisabledParsers {
+    /// Unknown names are logged and ignored so a typo never breaks the reader.
+    pub(crate) fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut out = Self::default();
+        for name in names {
+            match name {
+                "question" => out.question = true,
+                "rate-limit" => out.rate_limit = true,
+                "plan-file" => out.plan_file = true,
+                "intent" => out.intent = true,
+                other => tracing::warn!(
+                    source = "output_parser",
+                    "Ignoring unknown disabled_parsers entry: {other}"
+                ),
+            }
+        }
+        out
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The `question` detector is off (also gates the silence-timer heuristic).
+    pub(crate) fn question(&self) -> bool {
+        self.question
+    }
 }
 
 struct RateLimitPattern {
-    name: &'static str,
+    name: Cow<'static, str>,
     regex: regex::Regex,
     retry_after_ms: Option<u64>,
     has_retry_capture: bool,
 }
 
 struct ApiErrorPattern {
-    name: &'static str,
+    name: Cow<'static, str>,
     regex: regex::Regex,
-    error_kind: &'static str, // "server", "auth", "unknown"
+    error_kind: Cow<'static, str>, // "server", "auth", "unknown"
+}
+
+/// Compiled `custom_output_patterns` — see [`compile_custom_patterns`].
+#[derive(Default)]
+pub(crate) struct CustomPatterns {
+    rate_limit: Vec<RateLimitPattern>,
+    api_error: Vec<ApiErrorPattern>,
+    task_complete: Vec<regex::Regex>,
+    /// Regexes with `input`, `output` and/or `cost` named groups.
+    token_usage: Vec<regex::Regex>,
+}
+
+impl CustomPatterns {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rate_limit.is_empty()
+            && self.api_error.is_empty()
+            && self.task_complete.is_empty()
+            && self.token_usage.is_empty()
+    }
+}
+
+/// Compile user-defined patterns. A malformed regex is logged and skipped so
+/// one bad config entry never takes down the PTY reader.
+pub(crate) fn compile_custom_patterns(defs: &[CustomOutputPattern]) -> CustomPatterns {
+    let mut out = CustomPatterns::default();
+    for def in defs {
+        let regex = match regex::Regex::new(&def.regex) {
+            Ok(r) => r,
+            Err(e) => {
+                tracing::warn!(
+                    source = "output_parser",
+                    name = %def.name,
+                    "Skipping custom output pattern with invalid regex: {e}"
+                );
+                continue;
+            }
+        };
+        let name = Cow::Owned(def.name.clone());
+        match def.kind {
+            CustomPatternKind::RateLimit => out.rate_limit.push(RateLimitPattern {
+                name,
+                has_retry_capture: regex.captures_len() > 1,
+                regex,
+                retry_after_ms: def.retry_after_ms,
+            }),
+            CustomPatternKind::ApiError => out.api_error.push(ApiErrorPattern {
+                name,
+                regex,
+                error_kind: Cow::Owned(
+                    def.error_kind
+                        .clone()
+                        .unwrap_or_else(|| "unknown".to_string()),
+                ),
+            }),
+            CustomPatternKind::TaskComplete => out.task_complete.push(regex),
+            CustomPatternKind::TokenUsage => {
+                let has_group = regex
+                    .capture_names()
+                    .flatten()
+                    .any(|n| matches!(n, "input" | "output" | "cost"));
+                if !has_group {
+                    tracing::warn!(
+                        source = "output_parser",
+                        name = %def.name,
+                        "Skipping token_usage pattern without an input, output or cost group"
+                    );
+                    continue;
+                }
+                out.token_usage.push(regex);
+            }
+        }
+    }
+    out
 }
 
 lazy_static::lazy_static! {
@@ -224,20 +481,36 @@ lazy_static::lazy_static! {
 
 impl OutputParser {
     pub fn new() -> Self {
+        Self::with_custom_patterns(CustomPatterns::default())
+    }
+
+    /// Parser with user-defined patterns appended after the built-in ones.
+    pub(crate) fn with_custom_patterns(custom_patterns: CustomPatterns) -> Self {
         Self {
             rate_limit_patterns: &RATE_LIMIT_PATTERNS,
             api_error_patterns: &API_ERROR_PATTERNS,
+            custom_patterns,
             last_suggest_items: None,
             last_api_error_match: None,
             session_conflict_fired: false,
+            last_plan_mode: None,
+            last_build_failure: None,
+            disabled: DisabledParsers::default(),
         }
     }
 
+    /// Skip the given detectors entirely (see [`DisabledParsers`]).
+    pub(crate) fn with_disabled_parsers(mut self, disabled: DisabledParsers) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
     /// Parse a chunk of PTY output and return any detected events.
     ///
     /// Strips ANSI escape sequences via the vt100 crate before parsing.
-    /// Only available in tests — the production pipeline uses [`parse_clean_lines`].
-    #[cfg(test)]
+    /// Only available in tests and debug builds ([`parse_output_sample`]) — the
+    /// production pipeline uses [`parse_clean_lines`].
+    #[cfg(any(test, debug_assertions))]
     pub fn parse(&mut self, text: &str) -> Vec<ParsedEvent> {
         let mut events = Vec::new();
 
@@ -246,6 +519,11 @@ impl OutputParser {
             events.push(evt);
         }
 
+        // OSC 0/2 terminal title (raw stream, invisible in clean rows)
+        if let Some(evt) = parse_osc_title(text) {
+            events.push(evt);
+        }
+
         // PR/MR URL detection (no stripping needed)
         if let Some(evt) = parse_pr_url(text) {
             events.push(evt);
@@ -261,13 +539,20 @@ impl OutputParser {
             events.push(evt);
         }
 
+        // Token/cost usage footer
+        if let Some(evt) = self.parse_token_usage(&clean) {
+            events.push(evt);
+        }
+
         // Active sub-task indicator (Claude Code ›› lines)
         if let Some(evt) = parse_active_subtasks(&clean) {
             events.push(evt);
         }
 
         // Rate limit detection (operates on clean text to avoid ANSI escapes bridging unrelated tokens)
-        if let Some(evt) = self.parse_rate_limit(&clean) {
+        if !self.disabled.rate_limit
+            && let Some(evt) = self.parse_rate_limit(&clean)
+        {
             events.push(evt);
         }
 
@@ -287,18 +572,34 @@ impl OutputParser {
         }
 
         // Question/attention detection
-        if let Some(evt) = parse_question(&clean) {
+        if !self.disabled.question
+            && let Some(evt) = parse_question(&clean)
+        {
             events.push(evt);
         }
 
         // Plan file detection
-        if let Some(evt) = parse_plan_file(&clean) {
+        if !self.disabled.plan_file
+            && let Some(evt) = parse_plan_file(&clean)
+        {
+            events.push(evt);
+        }
+
+        // Plan mode enter/exit
+        if let Some(evt) = self.plan_mode_transition(&clean) {
             events.push(evt);
         }
 
         // Intent declaration: `intent: text` at column 0
         // Test-only parse assumes agent context (agent_active=true)
-        if let Some(evt) = parse_intent(&clean, true) {
+        if !self.disabled.intent
+            && let Some(evt) = parse_intent(&clean, true)
+        {
+            events.push(evt);
+        }
+
+        // Agent-reported error: `[[error: text]]` / `⟦error: text⟧` at column 0
+        if let Some(evt) = parse_agent_error(&clean, true) {
             events.push(evt);
         }
 
@@ -307,6 +608,11 @@ impl OutputParser {
             events.push(evt);
         }
 
+        // Compiler / test-runner failures (cargo, npm, pytest, tsc)
+        if let Some(evt) = self.build_failure(&clean) {
+            events.push(evt);
+        }
+
         if !self.session_conflict_fired {
             if let Some(evt) = parse_agent_session_conflict(&clean) {
                 self.session_conflict_fired = true;
@@ -364,13 +670,20 @@ impl OutputParser {
             events.push(evt);
         }
 
+        // Token/cost usage footer (totals are accumulated by the PTY reader)
+        if let Some(evt) = self.parse_token_usage(&joined) {
+            events.push(evt);
+        }
+
         // Active sub-task indicator (Claude Code ›› lines)
         if let Some(evt) = parse_active_subtasks(&joined) {
             events.push(evt);
         }
 
         // Rate limit and API error — pattern-match on joined text
-        if let Some(evt) = self.parse_rate_limit(&joined) {
+        if !self.disabled.rate_limit
+            && let Some(evt) = self.parse_rate_limit(&joined)
+        {
             events.push(evt);
         }
         if let Some(evt) = self.parse_api_error(&joined) {
@@ -388,12 +701,16 @@ impl OutputParser {
         }
 
         // Question/attention — iterates lines internally
-        if let Some(evt) = parse_question(&joined) {
+        if !self.disabled.question
+            && let Some(evt) = parse_question(&joined)
+        {
             events.push(evt);
         }
 
         // Plan file
-        if let Some(evt) = parse_plan_file(&joined) {
+        if !self.disabled.plan_file
+            && let Some(evt) = parse_plan_file(&joined)
+        {
             events.push(evt);
         }
 
@@ -401,7 +718,12 @@ impl OutputParser {
         // Plain-prefix tokens (`intent:`, `suggest:` at column 0) are only parsed
         // when an agent is detected — this prevents false positives from regular
         // CLI tools that might output text starting with these keywords.
-        if let Some(evt) = parse_intent(&joined, agent_active) {
+        if !self.disabled.intent
+            && let Some(evt) = parse_intent(&joined, agent_active)
+        {
+            events.push(evt);
+        }
+        if let Some(evt) = parse_agent_error(&joined, agent_active) {
             events.push(evt);
         }
         // Suggest follow-up actions: `suggest: [ A | B | C ]` on a single row.
@@ -416,6 +738,11 @@ impl OutputParser {
             events.push(evt);
         }
 
+        // Compiler / test-runner failures (deduped: the output stays on screen)
+        if let Some(evt) = self.build_failure(&joined) {
+            events.push(evt);
+        }
+
         if !self.session_conflict_fired
             && let Some(evt) = parse_agent_session_conflict(&joined)
         {
@@ -432,24 +759,133 @@ impl OutputParser {
             .any(|e| matches!(e, ParsedEvent::UserInput { .. }))
         {
             self.last_api_error_match = None;
+            self.last_build_failure = None;
             self.session_conflict_fired = false;
         }
 
         events
     }
 
-    fn parse_rate_limit(&self, text: &str) -> Option<ParsedEvent> {
-        // Fast path: every rate-limit pattern requires at least one of these keywords.
-        if !text.contains("rate_limit") && !text.contains("overloaded")
-            && !text.contains("RateLimit") && !text.contains("429")
-            && !text.contains("RESOURCE_EXHAUSTED") && !text.contains("etry") // Retry/retry
-            && !text.contains("Rate Limit") && !text.contains("per minute")
-            // Claude Code friendly overload message ("temporarily limiting requests")
-            && !text.contains("limiting requests")
-        {
+    fn build_failure(&mut self, text: &str) -> Option<ParsedEvent> {
+        let evt = parse_build_failure(text)?;
+        if let ParsedEvent::BuildFailure { ref summary, .. } = evt {
+            if self.last_build_failure.as_ref() == Some(summary) {
+                return None;
+            }
+            self.last_build_failure = Some(summary.clone());
+        }
+        Some(evt)
+    }
+
+    /// Return the trimmed row if it is a task-completion marker (built-in or a
+    /// custom `task_complete` pattern). Code and diff lines never match. The
+    /// caller (pty.rs reader) only parks it as a candidate — the event fires
+    /// after a silence window, so a mid-stream marker followed by more output
+    /// is dropped.
+    pub(crate) fn task_complete_line(&self, line: &str) -> Option<String> {
+        if line_is_code_or_diff(line) {
+            return None;
+        }
+        let body = line
+            .trim()
+            .trim_start_matches(['⎿', '⏺', '●', '•'])
+            .trim_start();
+        if body.is_empty() {
             return None;
         }
-        for pattern in self.rate_limit_patterns {
+        if is_builtin_task_complete(body)
+            || self
+                .custom_patterns
+                .task_complete
+                .iter()
+                .any(|re| re.is_match(body))
+        {
+            return Some(body.to_string());
+        }
+        None
+    }
+
+    /// Last usage footer in `text`, built-in formats first, then custom
+    /// `token_usage` patterns.
+    fn parse_token_usage(&self, text: &str) -> Option<ParsedEvent> {
+        let mut found = None;
+        for line in text.lines() {
+            if line_is_code_or_diff(line) {
+                continue;
+            }
+            let reading = parse_builtin_token_usage(line).or_else(|| {
+                self.custom_patterns
+                    .token_usage
+                    .iter()
+                    .find_map(|re| custom_token_usage(re, line))
+            });
+            if reading.is_some() {
+                found = reading;
+            }
+        }
+        found
+    }
+
+    /// Plan-mode transition from a full screen snapshot (`screen_cache`).
+    ///
+    /// Claude Code renders the `⏸ plan mode on` footer below the input box,
+    /// inside the rows `find_chrome_cutoff` trims from `parse_clean_lines`, so
+    /// the footer is only visible here. Deduped: the footer persists on screen.
+    ///
+    /// Once the input box is on screen its footer is authoritative: a plan
+    /// banner left in the scrollback above doesn't enter plan mode, and while
+    /// plan mode is active an input box without the footer means the mode was
+    /// cycled to one with no footer text (default mode), i.e. an exit.
+    pub fn parse_plan_mode_screen(&mut self, screen_rows: &[String]) -> Option<ParsedEvent> {
+        let rows: Vec<&str> = screen_rows.iter().map(String::as_str).collect();
+        let Some(cutoff) = crate::chrome::find_chrome_cutoff(&rows) else {
+            let evt = parse_plan_mode_lines(rows.into_iter())?;
+            return self.record_plan_mode(evt);
+        };
+        let footer = parse_plan_mode_lines(rows[cutoff..].iter().copied());
+        let content = parse_plan_mode_lines(rows[..cutoff].iter().copied());
+        let evt = match (footer, content) {
+            (Some(evt), _) => evt,
+            (None, Some(ParsedEvent::PlanMode { active: false })) => {
+                ParsedEvent::PlanMode { active: false }
+            }
+            (None, _) if self.last_plan_mode == Some(true) => {
+                ParsedEvent::PlanMode { active: false }
+            }
+            (None, _) => return None,
+        };
+        self.record_plan_mode(evt)
+    }
+
+    fn plan_mode_transition(&mut self, text: &str) -> Option<ParsedEvent> {
+        let evt = parse_plan_mode(text)?;
+        self.record_plan_mode(evt)
+    }
+
+    fn record_plan_mode(&mut self, evt: ParsedEvent) -> Option<ParsedEvent> {
+        if let ParsedEvent::PlanMode { active } = evt {
+            if self.last_plan_mode == Some(active) {
+                return None;
+            }
+            self.last_plan_mode = Some(active);
+        }
+        Some(evt)
+    }
+
+    fn parse_rate_limit(&self, text: &str) -> Option<ParsedEvent> {
+        // Fast path: every built-in rate-limit pattern requires at least one of these keywords.
+        let builtin_candidate = text.contains("rate_limit") || text.contains("overloaded")
+            || text.contains("RateLimit") || text.contains("429")
+            || text.contains("RESOURCE_EXHAUSTED") || text.contains("etry") // Retry/retry
+            || text.contains("Rate Limit") || text.contains("per minute")
+            // Claude Code friendly overload message ("temporarily limiting requests")
+            || text.contains("limiting requests");
+        let builtin: &[RateLimitPattern] = if builtin_candidate {
+            self.rate_limit_patterns
+        } else {
+            &[]
+        };
+        for pattern in builtin.iter().chain(&self.custom_patterns.rate_limit) {
             // Use captures() uniformly — group 0 is the full match (subsumes find())
             if let Some(caps) = pattern.regex.captures(text) {
                 let m: regex::Match<'_> = caps.get(0).unwrap();
@@ -477,25 +913,27 @@ impl OutputParser {
     }
 
     fn parse_api_error(&mut self, text: &str) -> Option<ParsedEvent> {
-        // Fast path: every api-error pattern requires at least one of these keywords.
-        if !text.contains("api_error")
-            && !text.contains("authentication_error")
-            && !text.contains("server_error")
-            && !text.contains("UNAVAILABLE")
-            && !text.contains("INTERNAL")
-            && !text.contains("UNAUTHENTICATED")
-            && !text.contains("litellm")
-            && !text.contains("copilot")
-            && !text.contains("provider_name")
-            && !text.contains("base_resp")
-            && !text.contains("stream error")
-            && !text.contains("servers are down")
-            && !text.contains("not able to authenticate")
-            && !text.contains("request failed")
-        {
-            return None;
-        }
-        for pattern in self.api_error_patterns {
+        // Fast path: every built-in api-error pattern requires at least one of these keywords.
+        let builtin_candidate = text.contains("api_error")
+            || text.contains("authentication_error")
+            || text.contains("server_error")
+            || text.contains("UNAVAILABLE")
+            || text.contains("INTERNAL")
+            || text.contains("UNAUTHENTICATED")
+            || text.contains("litellm")
+            || text.contains("copilot")
+            || text.contains("provider_name")
+            || text.contains("base_resp")
+            || text.contains("stream error")
+            || text.contains("servers are down")
+            || text.contains("not able to authenticate")
+            || text.contains("request failed");
+        let builtin: &[ApiErrorPattern] = if builtin_candidate {
+            self.api_error_patterns
+        } else {
+            &[]
+        };
+        for pattern in builtin.iter().chain(&self.custom_patterns.api_error) {
             if let Some(m) = pattern.regex.find(text) {
                 // Guard: reject matches inside source code or documentation.
                 if line_is_code_or_diff(line_containing_match(text, m.start())) {
@@ -669,7 +1107,7 @@ fn rl(
     has_retry_capture: bool,
 ) -> RateLimitPattern {
     RateLimitPattern {
-        name,
+        name: Cow::Borrowed(name),
         regex: regex::Regex::new(pattern).unwrap(),
         retry_after_ms,
         has_retry_capture,
@@ -769,9 +1207,9 @@ fn build_api_error_patterns() -> Vec<ApiErrorPattern> {
 
 fn ae(name: &'static str, pattern: &str, error_kind: &'static str) -> ApiErrorPattern {
     ApiErrorPattern {
-        name,
+        name: Cow::Borrowed(name),
         regex: regex::Regex::new(pattern).unwrap(),
-        error_kind,
+        error_kind: Cow::Borrowed(error_kind),
     }
 }
 
@@ -792,6 +1230,33 @@ pub(crate) fn parse_osc94(text: &str) -> Option<ParsedEvent> {
     })
 }
 
+/// Maximum terminal title length kept (chars); longer titles are truncated.
+const MAX_TITLE_CHARS: usize = 256;
+
+/// Parse OSC 0/2 title sequences: \x1b]0;TITLE\x07 or \x1b]2;TITLE\x1b\\
+///
+/// Only the exact `0;` / `2;` selectors match, so OSC 9;4 progress and other
+/// OSC codes are never mistaken for a title. Returns the last non-empty title
+/// in the chunk.
+pub(crate) fn parse_osc_title(text: &str) -> Option<ParsedEvent> {
+    // Fast path: check for ESC ] 0; / ESC ] 2; before running regex
+    if !text.contains("\x1b]0;") && !text.contains("\x1b]2;") {
+        return None;
+    }
+    lazy_static::lazy_static! {
+        static ref OSC_TITLE_RE: regex::Regex =
+            regex::Regex::new(r"\x1b\][02];([^\x07\x1b]*)(?:\x07|\x1b\\)").unwrap();
+    }
+    OSC_TITLE_RE
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let title = caps[1].trim();
+            (!title.is_empty()).then(|| title.chars().take(MAX_TITLE_CHARS).collect::<String>())
+        })
+        .last()
+        .map(|title| ParsedEvent::Title { title })
+}
+
 /// Parse GitHub/GitLab PR/MR URLs
 fn parse_pr_url(text: &str) -> Option<ParsedEvent> {
     // Fast path
@@ -823,14 +1288,17 @@ fn parse_pr_url(text: &str) -> Option<ParsedEvent> {
     None
 }
 
-/// Strip ANSI escape sequences from raw PTY text.
+/// Remove all CSI/OSC/ESC sequences from raw PTY bytes, keeping line structure.
+pub(crate) fn strip_ansi(text: &[u8]) -> String {
+    String::from_utf8_lossy(&strip_ansi_escapes::strip(text)).into_owned()
+}
+
+/// Strip ANSI escape sequences from raw PTY text and drop blank lines.
 ///
-/// Uses `strip-ansi-escapes` to remove all CSI/OSC/ESC sequences.
-/// Only used by the test-only [`OutputParser::parse`] method.
-#[cfg(test)]
+/// Only used by the test/debug-only [`OutputParser::parse`] method.
+#[cfg(any(test, debug_assertions))]
 fn strip_ansi_via_vt100(text: &str) -> String {
-    let stripped = strip_ansi_escapes::strip(text);
-    String::from_utf8_lossy(&stripped)
+    strip_ansi(text.as_bytes())
         .lines()
         .filter(|l| !l.trim().is_empty())
         .collect::<Vec<_>>()
@@ -1115,6 +1583,171 @@ fn parse_agent_session_conflict(clean: &str) -> Option<ParsedEvent> {
         .or_else(|| try_match(&SESSION_NOT_FOUND_RE, "not-found"))
 }
 
+/// Detect compiler / test-runner failures from pre-stripped text.
+///
+/// Every pattern is anchored at the start of the line (after indentation and
+/// Claude Code's `⎿` tool-output gutter), so an agent mentioning `error[E0308]`
+/// or `npm ERR!` mid-sentence never matches. Code, diff and fenced lines are
+/// rejected by the shared guards.
+fn parse_build_failure(clean: &str) -> Option<ParsedEvent> {
+    // Fast path: every pattern needs one of these keywords.
+    if !clean.contains("error")
+        && !clean.contains("ERR!")
+        && !clean.contains("FAILED")
+        && !clean.contains("failed")
+    {
+        return None;
+    }
+    lazy_static::lazy_static! {
+        static ref BUILD_FAILURE_PATTERNS: Vec<(&'static str, regex::Regex)> = [
+            // cargo: rustc diagnostics, the crate-level summary, failing test run
+            ("cargo", r"^error\[E\d{4}\]: \S"),
+            ("cargo", r"^error: could not compile `?[\w.-]+`?"),
+            ("cargo", r"^test result: FAILED\."),
+            // npm (ERR! up to npm 8, `npm error` since 9)
+            ("npm", r"^npm (?:ERR!|error) \S"),
+            // pytest: `==== 2 failed, 5 passed in 0.31s ====` and short summary lines
+            ("pytest", r"^=+ (?:.*, )?\d+ failed\b.* =+$"),
+            ("pytest", r"^FAILED \S+::\S+"),
+            // tsc: `src/a.ts(3,5): error TS2322:`, `src/a.ts:3:5 - error TS2322:`, global errors
+            ("tsc", r"^(?:\S+(?:\(\d+,\d+\):|:\d+:\d+ -) )?error TS\d+: \S"),
+        ]
+        .into_iter()
+        .map(|(tool, re)| (tool, regex::Regex::new(re).unwrap()))
+        .collect();
+    }
+    let mut in_fence = false;
+    for line in clean.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || line_is_code_or_diff(line) {
+            continue;
+        }
+        let body = trimmed.trim_start_matches('⎿').trim_start();
+        for (tool, re) in BUILD_FAILURE_PATTERNS.iter() {
+            if re.is_match(body) {
+                return Some(ParsedEvent::BuildFailure {
+                    tool: tool.to_string(),
+                    summary: body.to_string(),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Parse a token count with an optional `k`/`m` suffix: "18k", "2.4k",
+/// "1.2M", "12,345". Commas are thousands separators.
+pub(crate) fn parse_token_count(s: &str) -> Option<u64> {
+    let s = s.trim().trim_end_matches(['.', ',']);
+    let (num, mult) = match s.chars().last()? {
+        'k' | 'K' => (&s[..s.len() - 1], 1_000.0),
+        'm' | 'M' => (&s[..s.len() - 1], 1_000_000.0),
+        _ => (s, 1.0),
+    };
+    let num: f64 = num.trim().replace(',', "").parse().ok()?;
+    if !num.is_finite() || num < 0.0 {
+        return None;
+    }
+    Some((num * mult).round() as u64)
+}
+
+fn parse_cost(s: &str) -> Option<f64> {
+    let v: f64 = s.trim().trim_start_matches('$').trim().parse().ok()?;
+    (v.is_finite() && v >= 0.0).then_some(v)
+}
+
+/// Built-in usage footers. Claude Code shows `↑` (sent) and/or `↓`
+/// (received) counts on a line mentioning tokens, optionally with a `$`
+/// cost; Codex prints `Token usage: total=… input=… output=…`.
+fn parse_builtin_token_usage(line: &str) -> Option<ParsedEvent> {
+    if !line.contains("oken") && !line.contains("OKEN") {
+        return None;
+    }
+    lazy_static::lazy_static! {
+        static ref TOKENS_UP_RE: regex::Regex =
+            regex::Regex::new(r"↑\s*(\d[\d.,]*\s*[kKmM]?)").unwrap();
+        static ref TOKENS_DOWN_RE: regex::Regex =
+            regex::Regex::new(r"↓\s*(\d[\d.,]*\s*[kKmM]?)").unwrap();
+        static ref COST_RE: regex::Regex =
+            regex::Regex::new(r"\$\s*(\d+(?:\.\d+)?)").unwrap();
+        static ref CODEX_USAGE_RE: regex::Regex =
+            regex::Regex::new(r"(?i)^\s*token usage:").unwrap();
+        static ref CODEX_INPUT_RE: regex::Regex =
+            regex::Regex::new(r"\binput=(\d[\d.,]*[kKmM]?)").unwrap();
+        static ref CODEX_OUTPUT_RE: regex::Regex =
+            regex::Regex::new(r"\boutput=(\d[\d.,]*[kKmM]?)").unwrap();
+    }
+    let count = |re: &regex::Regex| {
+        re.captures(line)
+            .and_then(|c| parse_token_count(c.get(1)?.as_str()))
+    };
+    let (input_tokens, output_tokens) = if CODEX_USAGE_RE.is_match(line) {
+        (count(&CODEX_INPUT_RE), count(&CODEX_OUTPUT_RE))
+    } else if line.to_ascii_lowercase().contains("tokens") {
+        (count(&TOKENS_UP_RE), count(&TOKENS_DOWN_RE))
+    } else {
+        (None, None)
+    };
+    if input_tokens.is_none() && output_tokens.is_none() {
+        return None;
+    }
+    let cost_usd = COST_RE
+        .captures(line)
+        .and_then(|c| parse_cost(c.get(1)?.as_str()));
+    Some(ParsedEvent::TokenUsage {
+        input_tokens,
+        output_tokens,
+        cost_usd,
+    })
+}
+
+/// Apply a custom `token_usage` pattern: named groups `input`, `output` and
+/// `cost` are each optional, but at least one must capture a number.
+fn custom_token_usage(re: &regex::Regex, line: &str) -> Option<ParsedEvent> {
+    let caps = re.captures(line)?;
+    let input_tokens = caps
+        .name("input")
+        .and_then(|m| parse_token_count(m.as_str()));
+    let output_tokens = caps
+        .name("output")
+        .and_then(|m| parse_token_count(m.as_str()));
+    let cost_usd = caps.name("cost").and_then(|m| parse_cost(m.as_str()));
+    if input_tokens.is_none() && output_tokens.is_none() && cost_usd.is_none() {
+        return None;
+    }
+    Some(ParsedEvent::TokenUsage {
+        input_tokens,
+        output_tokens,
+        cost_usd,
+    })
+}
+
+/// Built-in task-completion markers, matched against a whole visible row after
+/// stripping leading bullets/gutters. Anchoring to the full line is what keeps
+/// "done" mid-sentence ("I'm done with the parser, now the tests") from matching.
+fn is_builtin_task_complete(body: &str) -> bool {
+    lazy_static::lazy_static! {
+        static ref TASK_COMPLETE_RE: regex::Regex = regex::Regex::new(concat!(
+            r"^(?:",
+            // `✓ Completed`, `✔ Done: 3 files changed`
+            r"[✓✔]\s*(?:Completed|Done)\b.*",
+            // `All tasks complete.`, `All tasks are done!`
+            r"|All tasks (?:are )?(?:complete|completed|done)[.!]?",
+            // a lone `Done` / `Done.` / `Done!`
+            r"|Done[.!]?",
+            // Claude Code turn summary: `✻ Worked for 2m 5s`, `✻ Cooked for 41s`
+            r"|[✻✶✳✢✽]\s+[A-Z][a-z]+ed for (?:\d+h\s*)?(?:\d+m\s*)?\d+s",
+            r")\s*$"
+        ))
+        .unwrap();
+    }
+    TASK_COMPLETE_RE.is_match(body)
+}
+
 /// Question detection: most detection is handled by the silence-based detector
 /// in pty.rs (last line ending with `?` + 10s of silence = real question).
 ///
@@ -1242,6 +1875,46 @@ fn line_is_diff_or_code_context(line: &str) -> bool {
     false
 }
 
+/// Detect plan-mode banners in pre-stripped terminal output.
+/// Enter: `⏸ plan mode on (shift+tab to cycle)` footer, plan-approval prompt
+/// (`Ready to code?`, `Here is Claude's plan:`).
+/// Exit: `Exited plan mode`, `User approved Claude's plan`, or the footer
+/// cycling to another mode (`⏵⏵ accept edits on`, `bypass permissions on`).
+/// Banners must start the line (after an optional status glyph), so the word
+/// "plan" in prose never matches. When several banners are visible the last wins.
+fn parse_plan_mode(clean: &str) -> Option<ParsedEvent> {
+    parse_plan_mode_lines(clean.lines())
+}
+
+fn parse_plan_mode_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Option<ParsedEvent> {
+    lazy_static::lazy_static! {
+        static ref ENTER_RE: regex::Regex = regex::Regex::new(
+            r"(?i)^\s*(?:[⏸⏺●⎿]\s*)?(?:plan mode on\b|ready to code\?|here is claude's plan:)"
+        )
+        .unwrap();
+        static ref EXIT_RE: regex::Regex = regex::Regex::new(
+            r"(?i)^\s*(?:[⏸⏺●⎿]\s*)?(?:exit(?:ed|ing) plan mode\b|user approved (?:claude's )?plan\b|⏵⏵\s*(?:accept edits|bypass permissions) on\b)"
+        )
+        .unwrap();
+    }
+    let mut found = None;
+    for line in lines {
+        // Fast path: every banner contains "plan" or the mode-cycle footer text
+        if !line.contains("lan") && !line.contains("permissions on") && !line.contains("edits on") {
+            continue;
+        }
+        if line_is_code_or_diff(line) {
+            continue;
+        }
+        if ENTER_RE.is_match(line) {
+            found = Some(true);
+        } else if EXIT_RE.is_match(line) {
+            found = Some(false);
+        }
+    }
+    found.map(|active| ParsedEvent::PlanMode { active })
+}
+
 /// Detect plan file paths in pre-stripped terminal output.
 /// Matches paths like `plans/foo.md`, `.claude/plans/bar.md`, absolute paths ending in plans/*.md
 fn parse_plan_file(clean: &str) -> Option<ParsedEvent> {
@@ -1340,6 +2013,35 @@ fn build_intent_event(raw_match: Option<String>, title_re: &regex::Regex) -> Opt
     })
 }
 
+/// Detect agent-reported errors: `[[error: <text>]]` or `⟦error: <text>⟧`
+/// (U+27E6/U+27E7) at column 0. The double bracket is required, so prose and
+/// log lines such as `[error: …]` or `error: …` never match — API errors are
+/// left to the pattern-based `ApiError` detection. Only parsed when an agent
+/// is active.
+fn parse_agent_error(clean: &str, agent_active: bool) -> Option<ParsedEvent> {
+    if !agent_active || !clean.contains("error:") {
+        return None;
+    }
+    lazy_static::lazy_static! {
+        // Same column-0 anchor as INTENT_PLAIN_RE (optional horizontal
+        // whitespace and Ink bullet glyph). Group 1 = ASCII form, group 2 =
+        // unicode form; neither may span rows or contain its own brackets.
+        static ref AGENT_ERROR_RE: regex::Regex = regex::Regex::new(
+            r"(?m)^[\t ]*(?:[\x{25CF}\x{23FA}][\t ]+)?(?:\[\[error:[\t ]*([^\[\]\r\n]+)\]\]|\x{27E6}error:[\t ]*([^\x{27E6}\x{27E7}\r\n]+)\x{27E7})"
+        ).unwrap();
+    }
+
+    let caps = AGENT_ERROR_RE.captures(clean)?;
+    let raw = caps.get(1).or_else(|| caps.get(2))?.as_str().trim();
+    // Filter out meaningless errors: ellipsis, template placeholders
+    if raw == "..." || raw == "<text>" || raw.len() < 4 {
+        return None;
+    }
+    // Normalize whitespace (CUF cursor-forward renders as runs of spaces).
+    let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(ParsedEvent::AgentError { text })
+}
+
 lazy_static::lazy_static! {
     /// Bracketed suggest token, anchored at column 0 (optional leading
     /// whitespace and an Ink bullet glyph ● U+25CF / ⏺ U+23FA). Two constraints
@@ -1362,7 +2064,7 @@ lazy_static::lazy_static! {
 /// brackets, so stray pipes/brackets in surrounding output (mermaid, markdown
 /// tables, prose) can never be captured. Inner items are pipe-separated, 2–4
 /// per the TUIC protocol. Only parsed when an agent is active.
-#[cfg(test)]
+#[cfg(any(test, debug_assertions))]
 fn parse_suggest(clean: &str, agent_active: bool) -> Option<ParsedEvent> {
     parse_suggest_with_line(clean, agent_active).map(|(evt, _)| evt)
 }
@@ -1587,6 +2289,27 @@ fn dewrap_suggest_keyword(text: &str) -> std::borrow::Cow<'_, str> {
     current
 }
 
+/// Detect a submitted slash command from an input echo line.
+///
+/// Conservative by design: the line must start with `/` followed by a
+/// lowercase `[a-z-]` name, then end or continue with whitespace-separated
+/// args. Anything else — file paths (`/usr/bin/env`), leading whitespace,
+/// uppercase — is ordinary input.
+pub fn parse_slash_command(line: &str) -> Option<ParsedEvent> {
+    lazy_static::lazy_static! {
+        static ref SLASH_COMMAND_RE: regex::Regex =
+            regex::Regex::new(r"^(/[a-z][a-z-]*)(?:\s+(\S.*?))?\s*$").unwrap();
+    }
+    if !line.starts_with('/') {
+        return None;
+    }
+    let caps = SLASH_COMMAND_RE.captures(line)?;
+    Some(ParsedEvent::SlashCommand {
+        command: caps[1].to_string(),
+        args: caps.get(2).map(|m| m.as_str().to_string()),
+    })
+}
+
 /// Detect a slash command autocomplete menu from screen bottom rows.
 ///
 /// Called separately from `parse_clean_lines` because it needs the *full*
@@ -1799,6 +2522,171 @@ pub fn parse_choice_prompt(screen_rows: &[String]) -> Option<ParsedEvent> {
     })
 }
 
+/// Strip box-drawing borders (`│ … │`) some agent versions draw around
+/// permission dialogs; border-only rows (`╭───╮`) become empty.
+fn unbox_row(row: &str) -> String {
+    let trimmed = row.trim();
+    if trimmed.starts_with(['╭', '╰', '┌', '└']) {
+        return String::new();
+    }
+    let inner = trimmed
+        .strip_prefix('│')
+        .map(|r| r.strip_suffix('│').unwrap_or(r))
+        .unwrap_or(row);
+    inner.trim_end().to_string()
+}
+
+/// Detect a tool permission prompt and extract the tool and what it would run.
+///
+/// Shapes recognised (Claude Code; borders optional):
+/// ```text
+///  Bash command
+///
+///    rm -rf build/
+///    Remove build directory
+///
+///  Do you want to proceed?
+///  ❯ 1. Yes
+///    2. Yes, and don't ask again for rm commands in /repo
+///    3. No, and tell Claude what to do differently (esc)
+/// ```
+/// `Do you want to make this edit to <file>?` / `Do you want to create <file>?`
+/// (tool `Edit` / `Write`, command = the file), and the one-line form
+/// ``Allow Bash to run `rm -rf build/`? [y/n]``.
+///
+/// A numbered dialog without a recognised tool header (e.g. a plain menu)
+/// returns `None`; it is still reported as a ChoicePrompt.
+pub fn parse_tool_approval(screen_rows: &[String]) -> Option<ParsedEvent> {
+    lazy_static::lazy_static! {
+        // One-line form: Allow Bash to run `cmd`? [y/n]
+        static ref INLINE_RE: regex::Regex = regex::Regex::new(
+            r"^\s*Allow\s+([A-Za-z][\w-]*)\s+to\s+(?:run|use|execute|edit|write|create|read|fetch)\s+`?(.+?)`?\s*\?\s*(?:\[y/n\]|\(y/n\))\s*$"
+        ).unwrap();
+        // Dialog header naming the tool: "Bash command", "Edit file", "Fetch", …
+        static ref HEADER_RE: regex::Regex = regex::Regex::new(
+            r"^\s*(Bash command|Edit file|Create file|Write file|Read file|Fetch|Web fetch|Web search|Tool use)\s*$"
+        ).unwrap();
+        static ref EDIT_TITLE_RE: regex::Regex =
+            regex::Regex::new(r"^Do you want to make this edit to (.+?)\?$").unwrap();
+        static ref CREATE_TITLE_RE: regex::Regex =
+            regex::Regex::new(r"^Do you want to create (.+?)\?$").unwrap();
+    }
+
+    let rows: Vec<String> = screen_rows.iter().map(|r| unbox_row(r)).collect();
+
+    // One-line y/n form: the last non-empty row.
+    if let Some(last) = rows.iter().rev().find(|r| !r.trim().is_empty())
+        && let Some(caps) = INLINE_RE.captures(last)
+    {
+        let option = |key: &str, label: &str, destructive: bool| ChoiceOption {
+            key: key.to_string(),
+            label: label.to_string(),
+            highlighted: false,
+            destructive,
+            hint: None,
+        };
+        return Some(ParsedEvent::ToolApproval {
+            tool: caps[1].to_string(),
+            command: caps[2].trim().to_string(),
+            options: vec![option("y", "Yes", false), option("n", "No", true)],
+        });
+    }
+
+    let ParsedEvent::ChoicePrompt { title, options, .. } = parse_choice_prompt(&rows)? else {
+        return None;
+    };
+
+    if let Some(caps) = EDIT_TITLE_RE.captures(&title) {
+        return Some(ParsedEvent::ToolApproval {
+            tool: "Edit".to_string(),
+            command: caps[1].to_string(),
+            options,
+        });
+    }
+    if let Some(caps) = CREATE_TITLE_RE.captures(&title) {
+        return Some(ParsedEvent::ToolApproval {
+            tool: "Write".to_string(),
+            command: caps[1].to_string(),
+            options,
+        });
+    }
+
+    // Otherwise the dialog must sit under a tool header; the command is the
+    // first non-empty row after it.
+    let title_idx = rows.iter().rposition(|r| r.trim() == title)?;
+    let header_idx = rows[..title_idx]
+        .iter()
+        .rposition(|r| HEADER_RE.is_match(r))?;
+    let header = rows[header_idx].trim();
+    let tool = match header {
+        "Bash command" => "Bash",
+        "Edit file" => "Edit",
+        "Create file" | "Write file" => "Write",
+        "Read file" => "Read",
+        "Fetch" | "Web fetch" => "WebFetch",
+        "Web search" => "WebSearch",
+        other => other,
+    };
+    let command = rows[header_idx + 1..title_idx]
+        .iter()
+        .map(|r| r.trim())
+        .find(|r| !r.is_empty())?
+        .to_string();
+
+    Some(ParsedEvent::ToolApproval {
+        tool: tool.to_string(),
+        command,
+        options,
+    })
+}
+
+/// An event found by [`parse_output_sample`], labelled with what fired it.
+#[derive(Debug, Serialize)]
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+pub(crate) struct SampleDetection {
+    /// Wire `type` tag of the event (`"rate-limit"`, `"pr-url"`, …)
+    pub(crate) kind: String,
+    /// Named pattern for table-driven detectors (rate-limit, api-error)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) pattern_name: Option<String>,
+    pub(crate) event: ParsedEvent,
+}
+
+/// Dev-only: run [`OutputParser::parse`] over pasted output and return every
+/// detected event, to reproduce false-positive reports without writing Rust.
+/// Debug builds only; not mapped over HTTP.
+#[cfg(debug_assertions)]
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn parse_output_sample(text: String) -> Result<Vec<SampleDetection>, String> {
+    let events = OutputParser::new().parse(&text);
+    Ok(events
+        .into_iter()
+        .map(|event| {
+            let kind = serde_json::to_value(&event)
+                .ok()
+                .and_then(|v| v["type"].as_str().map(str::to_string))
+                .unwrap_or_default();
+            let pattern_name = match &event {
+                ParsedEvent::RateLimit { pattern_name, .. }
+                | ParsedEvent::ApiError { pattern_name, .. } => Some(pattern_name.clone()),
+                _ => None,
+            };
+            SampleDetection {
+                kind,
+                pattern_name,
+                event,
+            }
+        })
+        .collect())
+}
+
+/// Release builds: the parse harness is a development tool.
+#[cfg(not(debug_assertions))]
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn parse_output_sample(_text: String) -> Result<Vec<SampleDetection>, String> {
+    Err("parse_output_sample is only available in debug builds".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1816,6 +2704,121 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_disabled_question_parser_skips_only_questions() {
+        let prompt = "\u{25C6}  Do you allow this tool call?";
+        let is_question = |e: &ParsedEvent| matches!(e, ParsedEvent::Question { .. });
+        assert!(OutputParser::new().parse(prompt).iter().any(is_question));
+
+        let disabled = DisabledParsers::from_names(["question"]);
+        let mut parser = OutputParser::new().with_disabled_parsers(disabled);
+        assert!(!parser.parse(prompt).iter().any(is_question));
+
+        let events = parser.parse("intent: wiring the parser");
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, ParsedEvent::Intent { .. }))
+        );
+        let events = parser.parse("Error: rate_limit_error - please try again");
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, ParsedEvent::RateLimit { .. }))
+        );
+    }
+
+    #[test]
+    fn test_disabled_parsers_ignore_unknown_names() {
+        assert!(DisabledParsers::from_names(["bogus"]).is_empty());
+        let disabled = DisabledParsers::from_names(["rate-limit", "plan-file", "intent"]);
+        assert!(!disabled.is_empty());
+        let mut parser = OutputParser::new().with_disabled_parsers(disabled);
+        assert!(
+            parser
+                .parse("Error: rate_limit_error - please try again")
+                .is_empty()
+        );
+    }
+
+    fn custom_pattern(name: &str, regex: &str, kind: CustomPatternKind) -> CustomOutputPattern {
+        CustomOutputPattern {
+            name: name.to_string(),
+            regex: regex.to_string(),
+            kind,
+            error_kind: Some("server".to_string()),
+            retry_after_ms: Some(15000),
+        }
+    }
+
+    #[test]
+    fn test_custom_rate_limit_and_api_error_patterns_fire() {
+        let custom = compile_custom_patterns(&[
+            custom_pattern(
+                "acme-throttle",
+                r"ACME_THROTTLED",
+                CustomPatternKind::RateLimit,
+            ),
+            custom_pattern(
+                "acme-down",
+                r"ACME_UPSTREAM_FAIL",
+                CustomPatternKind::ApiError,
+            ),
+        ]);
+        let mut parser = OutputParser::with_custom_patterns(custom);
+
+        let events = parser.parse("proxy: ACME_THROTTLED, slow down");
+        match events.as_slice() {
+            [
+                ParsedEvent::RateLimit {
+                    pattern_name,
+                    retry_after_ms,
+                    ..
+                },
+            ] => {
+                assert_eq!(pattern_name, "acme-throttle");
+                assert_eq!(*retry_after_ms, Some(15000));
+            }
+            other => panic!("Expected one RateLimit event, got {other:?}"),
+        }
+
+        let events = parser.parse("proxy: ACME_UPSTREAM_FAIL");
+        match events.as_slice() {
+            [
+                ParsedEvent::ApiError {
+                    pattern_name,
+                    error_kind,
+                    ..
+                },
+            ] => {
+                assert_eq!(pattern_name, "acme-down");
+                assert_eq!(error_kind, "server");
+            }
+            other => panic!("Expected one ApiError event, got {other:?}"),
+        }
+
+        // Source-code guard still applies to custom patterns.
+        assert!(parser.parse("// matches ACME_THROTTLED").is_empty());
+        // Plain parser knows nothing about them.
+        assert!(
+            OutputParser::new()
+                .parse("proxy: ACME_THROTTLED")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_custom_pattern_with_bad_regex_is_dropped() {
+        let custom = compile_custom_patterns(&[
+            custom_pattern("broken", r"(unclosed", CustomPatternKind::RateLimit),
+            custom_pattern("ok", r"ACME_THROTTLED", CustomPatternKind::RateLimit),
+        ]);
+        assert_eq!(custom.rate_limit.len(), 1);
+        assert_eq!(custom.rate_limit[0].name, "ok");
+        let mut parser = OutputParser::with_custom_patterns(custom);
+        assert_eq!(parser.parse("ACME_THROTTLED").len(), 1);
+    }
+
     #[test]
     fn test_rate_limit_with_retry_after() {
         let mut parser = OutputParser::new();
@@ -2041,6 +3044,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_osc0_title() {
+        let mut parser = OutputParser::new();
+        let events = parser.parse("\x1b]0;vim README.md\x07");
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            ParsedEvent::Title { title } => assert_eq!(title, "vim README.md"),
+            _ => panic!("Expected Title event"),
+        }
+    }
+
+    #[test]
+    fn test_osc2_title_st_terminator_last_wins() {
+        let chunk = "\x1b]2;first\x1b\\output\x1b]2;~/src/app\x1b\\";
+        match parse_osc_title(chunk) {
+            Some(ParsedEvent::Title { title }) => {
+                assert_eq!(title, "~/src/app", "should return the LAST title in chunk");
+            }
+            _ => panic!("Expected Title event"),
+        }
+    }
+
+    #[test]
+    fn test_osc94_is_not_a_title() {
+        assert!(parse_osc_title("\x1b]9;4;1;50\x07").is_none());
+        let mut parser = OutputParser::new();
+        let events = parser.parse("\x1b]9;4;1;50\x07");
+        assert!(
+            !events
+                .iter()
+                .any(|e| matches!(e, ParsedEvent::Title { .. })),
+            "OSC 9;4 must not produce a Title: {events:?}"
+        );
+    }
+
     #[test]
     fn test_status_line_claude() {
         let mut parser = OutputParser::new();
@@ -3084,6 +4122,132 @@ Enter to select · ↑/↓ to navigate · Esc to cancel";
         );
     }
 
+    #[test]
+    fn test_plan_mode_enter_banner() {
+        assert!(matches!(
+            parse_plan_mode("  ⏸ plan mode on (shift+tab to cycle)"),
+            Some(ParsedEvent::PlanMode { active: true })
+        ));
+    }
+
+    #[test]
+    fn test_plan_mode_exit_banner() {
+        assert!(matches!(
+            parse_plan_mode("  ⎿  User approved Claude's plan"),
+            Some(ParsedEvent::PlanMode { active: false })
+        ));
+        assert!(matches!(
+            parse_plan_mode("⏺ Exited Plan Mode"),
+            Some(ParsedEvent::PlanMode { active: false })
+        ));
+    }
+
+    #[test]
+    fn test_plan_mode_ignores_plan_in_prose() {
+        assert!(
+            parse_plan_mode("Let me plan the migration, then switch plan mode on later.").is_none()
+        );
+        assert!(parse_plan_mode("// plan mode on").is_none());
+    }
+
+    #[test]
+    fn test_plan_mode_emitted_only_on_transition() {
+        let mut parser = OutputParser::new();
+        let banner = "⏸ plan mode on (shift+tab to cycle)";
+        let is_plan_mode = |e: &ParsedEvent| matches!(e, ParsedEvent::PlanMode { .. });
+        assert!(parser.parse(banner).iter().any(is_plan_mode));
+        assert!(!parser.parse(banner).iter().any(is_plan_mode));
+        let events = parser.parse("⏵⏵ accept edits on (shift+tab to cycle)");
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, ParsedEvent::PlanMode { active: false }))
+        );
+    }
+
+    #[test]
+    fn test_plan_mode_footer_read_from_full_screen() {
+        // Claude Code screen: the mode footer sits in the chrome below the input box
+        let screen: Vec<String> = [
+            "⏺ I'll look at the parser before proposing changes.",
+            "",
+            "────────────────────────────────────────────────────────────────────────",
+            "❯",
+            "────────────────────────────────────────────────────────────────────────",
+            "  [Opus 4.6 (1M context) | Max] │ tuicommander git:(main*)",
+            "  ⏸ plan mode on (shift+tab to cycle)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        // The footer is below the chrome cutoff, so changed-row parsing never sees it
+        let refs: Vec<&str> = screen.iter().map(String::as_str).collect();
+        let cutoff = crate::chrome::find_chrome_cutoff(&refs).unwrap();
+        assert!(cutoff < 6);
+        let content: Vec<crate::state::ChangedRow> = screen[..cutoff]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| crate::state::ChangedRow {
+                row_index: i,
+                text: text.clone(),
+            })
+            .collect();
+        let mut parser = OutputParser::new();
+        assert!(
+            !parser
+                .parse_clean_lines(&content, true)
+                .iter()
+                .any(|e| matches!(e, ParsedEvent::PlanMode { .. }))
+        );
+
+        assert!(matches!(
+            parser.parse_plan_mode_screen(&screen),
+            Some(ParsedEvent::PlanMode { active: true })
+        ));
+        assert!(parser.parse_plan_mode_screen(&screen).is_none());
+
+        let mut cycled = screen.clone();
+        cycled[6] = "  ⏵⏵ accept edits on (shift+tab to cycle)".to_string();
+        assert!(matches!(
+            parser.parse_plan_mode_screen(&cycled),
+            Some(ParsedEvent::PlanMode { active: false })
+        ));
+    }
+
+    #[test]
+    fn test_plan_mode_exits_when_footer_disappears() {
+        let screen = |footer: &str| -> Vec<String> {
+            [
+                "⏺ Here is Claude's plan:",
+                "",
+                "────────────────────────────────────────────────────────────────────────",
+                "❯",
+                "────────────────────────────────────────────────────────────────────────",
+                "  [Opus 4.6 (1M context) | Max] │ tuicommander git:(main*)",
+                footer,
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+        };
+        let mut parser = OutputParser::new();
+        assert!(matches!(
+            parser.parse_plan_mode_screen(&screen("  ⏸ plan mode on (shift+tab to cycle)")),
+            Some(ParsedEvent::PlanMode { active: true })
+        ));
+
+        // Cycled to default mode: no footer text, plan banner still in scrollback
+        assert!(matches!(
+            parser.parse_plan_mode_screen(&screen("")),
+            Some(ParsedEvent::PlanMode { active: false })
+        ));
+        assert!(parser.parse_plan_mode_screen(&screen("")).is_none());
+
+        // A plan banner in the scrollback alone never enters plan mode
+        let mut fresh = OutputParser::new();
+        assert!(fresh.parse_plan_mode_screen(&screen("")).is_none());
+    }
+
     // --- False positive prevention tests ---
 
     fn has_rate_limit(events: &[ParsedEvent]) -> bool {
@@ -3635,6 +4799,54 @@ Enter to select · ↑/↓ to navigate · Esc to cancel";
         ));
     }
 
+    // --- Agent error detection tests ---
+
+    fn agent_error_text(events: &[ParsedEvent]) -> Option<String> {
+        events.iter().find_map(|e| match e {
+            ParsedEvent::AgentError { text } => Some(text.clone()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_agent_error_ascii_double_bracket() {
+        let mut parser = OutputParser::new();
+        let events = parser.parse("[[error:   cannot reach   the database ]]\n");
+        assert_eq!(
+            agent_error_text(&events).as_deref(),
+            Some("cannot reach the database")
+        );
+    }
+
+    #[test]
+    fn test_agent_error_unicode_brackets() {
+        let mut parser = OutputParser::new();
+        let events = parser.parse("\u{25CF} \u{27E6}error: migrations are out of sync\u{27E7}\n");
+        assert_eq!(
+            agent_error_text(&events).as_deref(),
+            Some("migrations are out of sync")
+        );
+    }
+
+    #[test]
+    fn test_agent_error_single_brackets_do_not_match() {
+        let mut parser = OutputParser::new();
+        for line in [
+            "[error: cannot reach the database]\n",
+            "error: cannot reach the database\n",
+            "[[error: cannot reach the database]\n",
+            "see log: [[error: cannot reach the database]]\n",
+        ] {
+            assert_eq!(agent_error_text(&parser.parse(line)), None, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn test_agent_error_requires_agent() {
+        assert!(parse_agent_error("[[error: cannot reach the database]]", false).is_none());
+        assert!(parse_agent_error("[[error: ...]]", true).is_none());
+    }
+
     // --- Intent detection tests ---
 
     fn get_intent(events: &[ParsedEvent]) -> Option<String> {
@@ -4426,6 +5638,39 @@ Enter to select · ↑/↓ to navigate · Esc to cancel";
         assert!(has_status, "expected StatusLine event, got: {:?}", events);
     }
 
+    // --- parse_slash_command tests ---
+
+    #[test]
+    fn test_slash_command_without_args() {
+        match parse_slash_command("/compact") {
+            Some(ParsedEvent::SlashCommand { command, args }) => {
+                assert_eq!(command, "/compact");
+                assert_eq!(args, None);
+            }
+            other => panic!("expected SlashCommand, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_slash_command_with_args() {
+        match parse_slash_command("/model opus") {
+            Some(ParsedEvent::SlashCommand { command, args }) => {
+                assert_eq!(command, "/model");
+                assert_eq!(args.as_deref(), Some("opus"));
+            }
+            other => panic!("expected SlashCommand, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_slash_command_rejects_paths_and_prose() {
+        assert!(parse_slash_command("/usr/bin/env").is_none());
+        assert!(parse_slash_command(" /compact").is_none());
+        assert!(parse_slash_command("/Compact").is_none());
+        assert!(parse_slash_command("run /compact").is_none());
+        assert!(parse_slash_command("/").is_none());
+    }
+
     // --- parse_slash_menu tests ---
 
     fn make_screen(rows: &[&str], total: usize) -> Vec<String> {
@@ -5520,4 +6765,401 @@ Enter to select · ↑/↓ to navigate · Esc to cancel";
             "numbered list without question/verb title must not match"
         );
     }
+
+    fn rows(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tool_approval_bash_permission_prompt() {
+        let screen = rows(&[
+            "╭──────────────────────────────────────────────╮",
+            "│ Bash command                                 │",
+            "│                                              │",
+            "│   rm -rf build/                              │",
+            "│   Remove build directory                     │",
+            "│                                              │",
+            "│ Do you want to proceed?                      │",
+            "│ ❯ 1. Yes                                     │",
+            "│   2. Yes, and don't ask again for rm commands│",
+            "│   3. No, and tell Claude what to do (esc)    │",
+            "╰──────────────────────────────────────────────╯",
+        ]);
+        let Some(ParsedEvent::ToolApproval {
+            tool,
+            command,
+            options,
+        }) = parse_tool_approval(&screen)
+        else {
+            panic!("expected ToolApproval");
+        };
+        assert_eq!(tool, "Bash");
+        assert_eq!(command, "rm -rf build/");
+        assert_eq!(options.len(), 3);
+        assert!(options[0].highlighted);
+        assert!(options[2].destructive);
+
+        let inline = rows(&["", "Allow Bash to run `rm -rf build/`? [y/n]"]);
+        let Some(ParsedEvent::ToolApproval { tool, command, .. }) = parse_tool_approval(&inline)
+        else {
+            panic!("expected inline ToolApproval");
+        };
+        assert_eq!((tool.as_str(), command.as_str()), ("Bash", "rm -rf build/"));
+    }
+
+    #[test]
+    fn test_tool_approval_file_edit_prompt() {
+        let screen = rows(&[
+            " Edit file",
+            " src/main.rs",
+            "   12 -    let x = 1;",
+            "   12 +    let x = 2;",
+            "",
+            " Do you want to make this edit to src/main.rs?",
+            " ❯ 1. Yes",
+            "   2. Yes, allow all edits during this session (shift+tab)",
+            "   3. No",
+        ]);
+        let Some(ParsedEvent::ToolApproval {
+            tool,
+            command,
+            options,
+        }) = parse_tool_approval(&screen)
+        else {
+            panic!("expected ToolApproval");
+        };
+        assert_eq!(tool, "Edit");
+        assert_eq!(command, "src/main.rs");
+        assert_eq!(options[1].hint.as_deref(), Some("shift+tab"));
+    }
+
+    #[test]
+    fn test_tool_approval_ignores_plain_menu() {
+        // A generic choice dialog is a ChoicePrompt, not a tool approval.
+        let screen = rows(&[
+            "Which model should I use?",
+            "❯ 1. Sonnet",
+            "  2. Opus",
+            "  3. Haiku",
+        ]);
+        assert!(parse_choice_prompt(&screen).is_some());
+        assert!(parse_tool_approval(&screen).is_none());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn parse_output_sample_reports_pr_url_and_rate_limit() {
+        let sample = "Created PR: https://github.com/owner/repo/pull/42\n\
+                      Error: rate_limit_error - please try again\n";
+        let detections = parse_output_sample(sample.to_string()).unwrap();
+        let kinds: Vec<(&str, Option<&str>)> = detections
+            .iter()
+            .map(|d| (d.kind.as_str(), d.pattern_name.as_deref()))
+            .collect();
+        assert!(kinds.contains(&("pr-url", None)), "{kinds:?}");
+        assert!(
+            kinds.contains(&("rate-limit", Some("claude-http-429"))),
+            "{kinds:?}"
+        );
+    }
+
+    #[test]
+    fn task_complete_markers_match() {
+        let parser = OutputParser::new();
+        for line in [
+            "✓ Completed",
+            "  ✔ Done: 3 files changed",
+            "All tasks complete.",
+            "All tasks are done!",
+            "⏺ Done.",
+            "Done",
+            "✻ Worked for 2m 5s",
+            "✻ Cooked for 41s",
+        ] {
+            assert!(
+                parser.task_complete_line(line).is_some(),
+                "should match: {line:?}"
+            );
+        }
+        assert_eq!(
+            parser.task_complete_line("  ⏺ All tasks complete."),
+            Some("All tasks complete.".to_string())
+        );
+    }
+
+    #[test]
+    fn task_complete_ignores_done_mid_sentence() {
+        let parser = OutputParser::new();
+        for line in [
+            "I'm done with the parser, now the tests.",
+            "Done with step 1, moving on to step 2",
+            "Once that's done we can merge",
+            "Not all tasks complete yet",
+            "// Done",
+            "+ Done.",
+            "✻ Thinking… (12s · esc to interrupt)",
+        ] {
+            assert!(
+                parser.task_complete_line(line).is_none(),
+                "should not match: {line:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn task_complete_custom_pattern() {
+        let custom = compile_custom_patterns(&[custom_pattern(
+            "aider-done",
+            r"^Applied edit to \S+$",
+            CustomPatternKind::TaskComplete,
+        )]);
+        assert!(!custom.is_empty());
+        let parser = OutputParser::with_custom_patterns(custom);
+        assert_eq!(
+            parser.task_complete_line("Applied edit to src/main.rs"),
+            Some("Applied edit to src/main.rs".to_string())
+        );
+        assert!(
+            OutputParser::new()
+                .task_complete_line("Applied edit to src/main.rs")
+                .is_none()
+        );
+    }
+
+    fn build_failure(text: &str) -> Option<(String, String)> {
+        match parse_build_failure(text)? {
+            ParsedEvent::BuildFailure { tool, summary } => Some((tool, summary)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn build_failure_cargo() {
+        let out = "   Compiling app v0.1.0\nerror[E0308]: mismatched types\n --> src/main.rs:3:5";
+        assert_eq!(
+            build_failure(out),
+            Some(("cargo".into(), "error[E0308]: mismatched types".into()))
+        );
+        let out = "error: could not compile `app` (bin \"app\") due to 1 previous error";
+        assert_eq!(build_failure(out).unwrap().0, "cargo");
+        let out = "test result: FAILED. 3 passed; 1 failed; 0 ignored";
+        assert_eq!(build_failure(out).unwrap().0, "cargo");
+        // Claude Code tool-output gutter
+        let out = "  ⎿  error[E0425]: cannot find value `x` in this scope";
+        assert_eq!(
+            build_failure(out),
+            Some((
+                "cargo".into(),
+                "error[E0425]: cannot find value `x` in this scope".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn build_failure_npm() {
+        let out = "npm ERR! code ELIFECYCLE\nnpm ERR! errno 1";
+        assert_eq!(
+            build_failure(out),
+            Some(("npm".into(), "npm ERR! code ELIFECYCLE".into()))
+        );
+        assert_eq!(
+            build_failure("npm error Missing script: \"bild\"")
+                .unwrap()
+                .0,
+            "npm"
+        );
+    }
+
+    #[test]
+    fn build_failure_pytest() {
+        let out = "========= 2 failed, 5 passed in 0.31s =========";
+        assert_eq!(build_failure(out), Some(("pytest".into(), out.into())));
+        let out = "FAILED tests/test_api.py::test_login - AssertionError: 401";
+        assert_eq!(build_failure(out).unwrap().0, "pytest");
+        assert_eq!(build_failure("========= 7 passed in 0.20s ========="), None);
+    }
+
+    #[test]
+    fn build_failure_tsc() {
+        let out =
+            "src/app.ts(12,5): error TS2322: Type 'string' is not assignable to type 'number'.";
+        assert_eq!(build_failure(out).unwrap().0, "tsc");
+        let out = "src/app.ts:12:5 - error TS2304: Cannot find name 'foo'.";
+        assert_eq!(build_failure(out).unwrap().0, "tsc");
+        assert_eq!(
+            build_failure("error TS5083: Cannot read file 'tsconfig.json'.")
+                .unwrap()
+                .0,
+            "tsc"
+        );
+    }
+
+    #[test]
+    fn build_failure_ignores_prose_and_code() {
+        for text in [
+            "⏺ The build failed with error[E0308]: mismatched types in main.rs.",
+            "I saw npm ERR! code ELIFECYCLE earlier, it's fixed now.",
+            "Previously 2 failed tests were flaky; now all pass.",
+            "The compiler reports error TS2322: Type 'string' is not assignable.",
+            "- error[E0308]: mismatched types",
+            "// error: could not compile `app`",
+            "+npm ERR! code ELIFECYCLE",
+            "    assert!(out.contains(\"error[E0308]: mismatched types\"));",
+            "```\nerror[E0308]: mismatched types\n```",
+        ] {
+            assert_eq!(build_failure(text), None, "{text}");
+        }
+    }
+
+    fn token_usage(line: &str) -> Option<(Option<u64>, Option<u64>, Option<f64>)> {
+        match OutputParser::new().parse_token_usage(line)? {
+            ParsedEvent::TokenUsage {
+                input_tokens,
+                output_tokens,
+                cost_usd,
+            } => Some((input_tokens, output_tokens, cost_usd)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn token_count_suffixes() {
+        assert_eq!(parse_token_count("18k"), Some(18_000));
+        assert_eq!(parse_token_count("2.4k"), Some(2_400));
+        assert_eq!(parse_token_count("1.25M"), Some(1_250_000));
+        assert_eq!(parse_token_count("3m"), Some(3_000_000));
+        assert_eq!(parse_token_count("12,345"), Some(12_345));
+        assert_eq!(parse_token_count("512"), Some(512));
+        assert_eq!(parse_token_count(" 7 k "), Some(7_000));
+        assert_eq!(parse_token_count("18k,"), Some(18_000));
+        assert_eq!(parse_token_count("k"), None);
+        assert_eq!(parse_token_count(""), None);
+    }
+
+    #[test]
+    fn token_usage_claude_footer() {
+        assert_eq!(
+            token_usage("↑ 2.4k ↓ 18k tokens · $0.14"),
+            Some((Some(2_400), Some(18_000), Some(0.14)))
+        );
+        // Spinner line with a single arrow and no cost
+        assert_eq!(
+            token_usage("✻ Thinking… (12s · ↓ 1.2k tokens · esc to interrupt)"),
+            Some((None, Some(1_200), None))
+        );
+        assert_eq!(token_usage("Scroll ↑ to see more"), None);
+        assert_eq!(token_usage("The tokens cost $3 in total"), None);
+    }
+
+    #[test]
+    fn token_usage_codex_footer() {
+        assert_eq!(
+            token_usage("Token usage: total=14,230 input=12,000 (+ 4,096 cached) output=2,230"),
+            Some((Some(12_000), Some(2_230), None))
+        );
+    }
+
+    #[test]
+    fn token_usage_custom_pattern() {
+        let custom = compile_custom_patterns(&[
+            custom_pattern(
+                "acme-usage",
+                r"in=(?P<input>\S+) out=(?P<output>\S+) spent=(?P<cost>\$[\d.]+)",
+                CustomPatternKind::TokenUsage,
+            ),
+            custom_pattern("no-groups", r"usage", CustomPatternKind::TokenUsage),
+        ]);
+        assert_eq!(custom.token_usage.len(), 1);
+        let parser = OutputParser::with_custom_patterns(custom);
+        match parser.parse_token_usage("acme: in=3k out=1.5k spent=$0.02") {
+            Some(ParsedEvent::TokenUsage {
+                input_tokens,
+                output_tokens,
+                cost_usd,
+            }) => {
+                assert_eq!(input_tokens, Some(3_000));
+                assert_eq!(output_tokens, Some(1_500));
+                assert_eq!(cost_usd, Some(0.02));
+            }
+            other => panic!("expected TokenUsage, got {other:?}"),
+        }
+    }
+
+    fn totals(evt: Option<ParsedEvent>) -> Option<(u64, u64, Option<f64>)> {
+        match evt? {
+            ParsedEvent::TokenUsage {
+                input_tokens,
+                output_tokens,
+                cost_usd,
+            } => Some((
+                input_tokens?,
+                output_tokens?,
+                // Float deltas: compare to the micro-dollar
+                cost_usd.map(|c| (c * 1e6).round() / 1e6),
+            )),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn token_usage_counter_accumulates_deltas() {
+        let mut c = TokenUsageCounter::default();
+        // First turn: counter grows, repaints add nothing
+        assert_eq!(
+            totals(c.record(Some(1_000), None, None)),
+            Some((1_000, 0, None))
+        );
+        assert_eq!(totals(c.record(Some(1_000), None, None)), None);
+        assert_eq!(
+            totals(c.record(Some(2_400), Some(500), Some(0.05))),
+            Some((2_400, 500, Some(0.05)))
+        );
+        assert_eq!(
+            totals(c.record(None, Some(18_000), Some(0.14))),
+            Some((2_400, 18_000, Some(0.14)))
+        );
+        // Second turn: the footer restarts from a lower value
+        assert_eq!(
+            totals(c.record(Some(300), Some(1_000), Some(0.01))),
+            Some((2_700, 19_000, Some(0.15)))
+        );
+        assert_eq!(totals(c.record(Some(300), Some(1_000), Some(0.01))), None);
+    }
+
+    #[test]
+    fn token_usage_counter_without_cost() {
+        let mut c = TokenUsageCounter::default();
+        assert_eq!(
+            totals(c.record(None, Some(2_000), None)),
+            Some((0, 2_000, None))
+        );
+        assert_eq!(
+            totals(c.record(None, Some(5_000), None)),
+            Some((0, 5_000, None))
+        );
+        // A zero-cost reading still reports a known cost
+        assert_eq!(
+            totals(c.record(None, None, Some(0.0))),
+            Some((0, 5_000, Some(0.0)))
+        );
+        assert_eq!(totals(c.record(None, None, Some(0.0))), None);
+    }
+
+    #[test]
+    fn build_failure_dedups_until_user_input() {
+        let mut parser = OutputParser::new();
+        let rows = vec![row(0, "error[E0308]: mismatched types")];
+        let first = parser.parse_clean_lines(&rows, true);
+        assert!(
+            first
+                .iter()
+                .any(|e| matches!(e, ParsedEvent::BuildFailure { tool, .. } if tool == "cargo"))
+        );
+        let again = parser.parse_clean_lines(&rows, true);
+        assert!(
+            !again
+                .iter()
+                .any(|e| matches!(e, ParsedEvent::BuildFailure { .. }))
+        );
+    }
 }
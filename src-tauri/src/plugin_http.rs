@@ -1,24 +1,36 @@
 //! HTTP fetch API for plugins.
 //!
 //! Plugins declaring the `net:http` capability can make outbound HTTP requests
-//! to URLs matching their declared `allowedUrls` patterns. Provides SSRF
-//! protection by blocking unsafe schemes and validating URLs against patterns.
+//! to URLs matching their declared `allowedUrls` patterns (and, optionally,
+//! `allowedHosts`). Provides SSRF protection by blocking unsafe schemes and
+//! validating URLs against patterns. Each request is bounded by a timeout and a
+//! response size cap; the body is streamed and aborted once the cap is passed.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
-/// Maximum response body size (10 MB).
-const MAX_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
+/// Default maximum response body size (10 MB).
+const DEFAULT_MAX_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Ceiling for a per-request `max_response_bytes` (50 MB).
+const MAX_RESPONSE_BYTES_CEILING: usize = 50 * 1024 * 1024;
 
 /// Default request timeout in seconds.
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
+/// Ceiling for a per-request `timeout_ms` (5 minutes).
+const MAX_TIMEOUT_MS: u64 = 5 * 60 * 1000;
+
+/// Maximum redirects followed per request.
+const MAX_REDIRECTS: usize = 5;
+
 /// Shared HTTP client — connection pooling across all plugin/tab fetches.
 fn shared_client() -> &'static reqwest::Client {
     static CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
     CLIENT.get_or_init(|| {
         reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .redirect(reqwest::redirect::Policy::limited(5))
+            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS))
             .build()
             .expect("Failed to build shared HTTP client")
     })
@@ -32,6 +44,99 @@ pub struct HttpResponse {
     pub body: String,
 }
 
+/// What went wrong with a plugin fetch. Serialized as `{kind, message}` so
+/// plugins can branch on `kind` and still read `message` like an `Error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginHttpErrorKind {
+    /// Refused before sending: capability, scheme, URL/host allowlist, bad method.
+    Rejected,
+    /// No response (or no complete body) within the timeout.
+    Timeout,
+    /// The response body is larger than the size cap.
+    SizeExceeded,
+    /// DNS resolution or TCP/TLS connection failed.
+    Connection,
+    /// Any other transport failure (redirect loop, broken body, …).
+    Request,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PluginHttpError {
+    pub kind: PluginHttpErrorKind,
+    pub message: String,
+}
+
+impl PluginHttpError {
+    fn new(kind: PluginHttpErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    fn rejected(message: impl Into<String>) -> Self {
+        Self::new(PluginHttpErrorKind::Rejected, message)
+    }
+
+    fn size_exceeded(limit: usize) -> Self {
+        Self::new(
+            PluginHttpErrorKind::SizeExceeded,
+            format!("Response body exceeds maximum size ({limit} bytes)"),
+        )
+    }
+
+    /// Classify a reqwest failure; `timeout` is the effective limit for the message.
+    fn from_reqwest(e: &reqwest::Error, timeout: Duration) -> Self {
+        if e.is_timeout() {
+            Self::new(
+                PluginHttpErrorKind::Timeout,
+                format!("HTTP request timed out after {}ms", timeout.as_millis()),
+            )
+        } else if e.is_connect() {
+            Self::new(
+                PluginHttpErrorKind::Connection,
+                format!("Could not connect (DNS or connection failure): {e}"),
+            )
+        } else {
+            Self::new(
+                PluginHttpErrorKind::Request,
+                format!("HTTP request failed: {e}"),
+            )
+        }
+    }
+}
+
+impl std::fmt::Display for PluginHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Accumulates response body chunks, failing as soon as the total passes
+/// `limit` so an oversized body is never fully buffered.
+struct CappedBody {
+    buf: Vec<u8>,
+    limit: usize,
+}
+
+impl CappedBody {
+    fn new(limit: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            limit,
+        }
+    }
+
+    fn push(&mut self, chunk: &[u8]) -> Result<(), PluginHttpError> {
+        if self.buf.len() + chunk.len() > self.limit {
+            return Err(PluginHttpError::size_exceeded(self.limit));
+        }
+        self.buf.extend_from_slice(chunk);
+        Ok(())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // URL validation
 // ---------------------------------------------------------------------------
@@ -102,6 +207,33 @@ fn validate_url(url: &str, allowed_urls: &[String]) -> Result<(), String> {
     ))
 }
 
+/// Check a URL's host against a plugin's `allowedHosts`. Entries are exact
+/// hostnames (`api.example.com`) or `*.example.com`, which matches subdomains
+/// but not `example.com` itself. An empty list allows every host.
+fn validate_host(url: &url::Url, allowed_hosts: &[String]) -> Result<(), String> {
+    if allowed_hosts.is_empty() {
+        return Ok(());
+    }
+    let host = url.host_str().unwrap_or("");
+    if allowed_hosts.iter().any(|h| host_matches(host, h)) {
+        return Ok(());
+    }
+    Err(format!(
+        "Host \"{host}\" is not in the plugin's allowedHosts ({})",
+        allowed_hosts.join(", ")
+    ))
+}
+
+fn host_matches(host: &str, pattern: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .is_some_and(|sub| sub.len() > 1 && sub.ends_with('.')),
+        None => host.eq_ignore_ascii_case(pattern),
+    }
+}
+
 /// Check if a URL matches a pattern.
 /// Pattern format: a URL prefix, optionally ending with `*` for wildcard suffix.
 /// Examples:
@@ -119,6 +251,56 @@ fn url_matches_pattern(url: &str, pattern: &str) -> bool {
 // Tauri command
 // ---------------------------------------------------------------------------
 
+/// Per-request limits and the optional host allowlist for `plugin_http_fetch`.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchLimits {
+    /// Total request timeout (default 30s, capped at 5 minutes).
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Response body size cap (default 10 MB, capped at 50 MB).
+    #[serde(default)]
+    pub max_response_bytes: Option<usize>,
+    /// Hosts from the plugin's manifest `allowedHosts` (empty = any host the
+    /// URL patterns allow). Redirects are checked against it too.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+}
+
+impl FetchLimits {
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(
+            self.timeout_ms
+                .unwrap_or(DEFAULT_TIMEOUT_SECS * 1000)
+                .clamp(1, MAX_TIMEOUT_MS),
+        )
+    }
+
+    fn max_response_bytes(&self) -> usize {
+        self.max_response_bytes
+            .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES)
+            .min(MAX_RESPONSE_BYTES_CEILING)
+    }
+}
+
+/// Client for a plugin with an `allowedHosts` list: like the shared client, but
+/// redirects to a host outside the list are refused instead of followed.
+fn host_restricted_client(allowed_hosts: &[String]) -> Result<reqwest::Client, PluginHttpError> {
+    let allowed = allowed_hosts.to_vec();
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error("too many redirects");
+            }
+            match validate_host(attempt.url(), &allowed) {
+                Ok(()) => attempt.follow(),
+                Err(e) => attempt.error(format!("Redirect refused: {e}")),
+            }
+        }))
+        .build()
+        .map_err(|e| PluginHttpError::new(PluginHttpErrorKind::Request, e.to_string()))
+}
+
 /// Make an HTTP request on behalf of a plugin.
 ///
 /// Parameters:
@@ -128,8 +310,10 @@ fn url_matches_pattern(url: &str, pattern: &str) -> bool {
 /// - `body` — Optional request body
 /// - `allowed_urls` — URL patterns from the plugin's manifest (empty = unrestricted)
 /// - `plugin_id` — The requesting plugin's ID (for logging)
+/// - `limits` — Optional timeout, size cap and `allowedHosts` (see `FetchLimits`)
 #[cfg(feature = "desktop")]
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn plugin_http_fetch(
     url: String,
     method: Option<String>,
@@ -137,11 +321,23 @@ pub async fn plugin_http_fetch(
     body: Option<String>,
     allowed_urls: Vec<String>,
     plugin_id: String,
+    limits: Option<FetchLimits>,
     state: tauri::State<'_, std::sync::Arc<crate::AppState>>,
-) -> Result<HttpResponse, String> {
-    plugin_http_fetch_impl(&state, url, method, headers, body, allowed_urls, plugin_id).await
+) -> Result<HttpResponse, PluginHttpError> {
+    plugin_http_fetch_impl(
+        &state,
+        url,
+        method,
+        headers,
+        body,
+        allowed_urls,
+        plugin_id,
+        limits.unwrap_or_default(),
+    )
+    .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn plugin_http_fetch_impl(
     state: &std::sync::Arc<crate::AppState>,
     url: String,
@@ -150,16 +346,29 @@ pub(crate) async fn plugin_http_fetch_impl(
     body: Option<String>,
     allowed_urls: Vec<String>,
     plugin_id: String,
-) -> Result<HttpResponse, String> {
-    crate::plugins::check_plugin_capability(state, &plugin_id, "net:http")?;
-    validate_url(&url, &allowed_urls)?;
+    limits: FetchLimits,
+) -> Result<HttpResponse, PluginHttpError> {
+    crate::plugins::check_plugin_capability(state, &plugin_id, "net:http")
+        .map_err(PluginHttpError::rejected)?;
+    validate_url(&url, &allowed_urls).map_err(PluginHttpError::rejected)?;
+    let parsed = url::Url::parse(&url).map_err(|e| PluginHttpError::rejected(e.to_string()))?;
+    validate_host(&parsed, &limits.allowed_hosts).map_err(PluginHttpError::rejected)?;
 
     let method_str = method.as_deref().unwrap_or("GET");
     let http_method: reqwest::Method = method_str
         .parse()
-        .map_err(|_| format!("Invalid HTTP method: {method_str}"))?;
+        .map_err(|_| PluginHttpError::rejected(format!("Invalid HTTP method: {method_str}")))?;
 
-    let mut request = shared_client().request(http_method, &url);
+    let timeout = limits.timeout();
+    let max_bytes = limits.max_response_bytes();
+    let restricted;
+    let client = if limits.allowed_hosts.is_empty() {
+        shared_client()
+    } else {
+        restricted = host_restricted_client(&limits.allowed_hosts)?;
+        &restricted
+    };
+    let mut request = client.request(http_method, &url).timeout(timeout);
 
     if let Some(ref hdrs) = headers {
         for (key, value) in hdrs {
@@ -171,20 +380,20 @@ pub(crate) async fn plugin_http_fetch_impl(
         request = request.body(b.clone());
     }
 
-    let response = request
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {e}"))?;
+    let mut response = request.send().await.map_err(|e| {
+        let err = PluginHttpError::from_reqwest(&e, timeout);
+        tracing::warn!(source = "plugin_http", plugin_id = %plugin_id, kind = ?err.kind, "{err}");
+        err
+    })?;
 
     let status = response.status().as_u16();
 
     // Reject early if Content-Length advertises an oversized body
-    if let Some(cl) = response.content_length()
-        && cl as usize > MAX_RESPONSE_BYTES
+    if response
+        .content_length()
+        .is_some_and(|cl| cl > max_bytes as u64)
     {
-        return Err(format!(
-            "Response body exceeds maximum size ({cl} bytes > {MAX_RESPONSE_BYTES} bytes)"
-        ));
+        return Err(PluginHttpError::size_exceeded(max_bytes));
     }
 
     let resp_headers: HashMap<String, String> = response
@@ -193,19 +402,21 @@ pub(crate) async fn plugin_http_fetch_impl(
         .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
         .collect();
 
-    let body_bytes = response
-        .bytes()
+    // Stream the body so a missing or lying Content-Length can't make us
+    // buffer more than the cap; dropping `response` aborts the transfer.
+    let mut body_buf = CappedBody::new(max_bytes);
+    while let Some(chunk) = response
+        .chunk()
         .await
-        .map_err(|e| format!("Failed to read response body: {e}"))?;
-
-    if body_bytes.len() > MAX_RESPONSE_BYTES {
-        return Err(format!(
-            "Response body exceeds maximum size ({} bytes > {MAX_RESPONSE_BYTES} bytes)",
-            body_bytes.len(),
-        ));
+        .map_err(|e| PluginHttpError::from_reqwest(&e, timeout))?
+    {
+        if let Err(e) = body_buf.push(&chunk) {
+            tracing::warn!(source = "plugin_http", plugin_id = %plugin_id, url = %url, "{e}");
+            return Err(e);
+        }
     }
 
-    let body_str = String::from_utf8_lossy(&body_bytes).to_string();
+    let body_str = String::from_utf8_lossy(&body_buf.buf).to_string();
 
     Ok(HttpResponse {
         status,
@@ -396,4 +607,89 @@ mod tests {
         // Empty allowed_urls = built-in plugin, no restrictions
         assert!(validate_url("http://10.0.0.1/api", &[]).is_ok());
     }
+
+    // -- Host allowlist --
+
+    fn host_check(url: &str, hosts: &[&str]) -> Result<(), String> {
+        let hosts: Vec<String> = hosts.iter().map(|h| h.to_string()).collect();
+        validate_host(&url::Url::parse(url).unwrap(), &hosts)
+    }
+
+    #[test]
+    fn host_allowlist_empty_allows_any_host() {
+        assert!(host_check("https://anything.example/x", &[]).is_ok());
+    }
+
+    #[test]
+    fn host_allowlist_exact_and_wildcard() {
+        let hosts = ["api.github.com", "*.example.com"];
+        assert!(host_check("https://api.github.com/repos", &hosts).is_ok());
+        assert!(host_check("https://API.GitHub.com/repos", &hosts).is_ok());
+        assert!(host_check("https://cdn.example.com/a", &hosts).is_ok());
+        assert!(host_check("https://a.b.example.com/a", &hosts).is_ok());
+        // The wildcard covers subdomains only, and not look-alike suffixes.
+        assert!(host_check("https://example.com/a", &hosts).is_err());
+        assert!(host_check("https://evilexample.com/a", &hosts).is_err());
+        let err = host_check("https://github.com/x", &hosts).unwrap_err();
+        assert!(err.contains("\"github.com\" is not in the plugin's allowedHosts"));
+    }
+
+    // -- Limits --
+
+    #[test]
+    fn fetch_limits_defaults_and_ceilings() {
+        let defaults = FetchLimits::default();
+        assert_eq!(defaults.timeout(), Duration::from_secs(30));
+        assert_eq!(defaults.max_response_bytes(), 10 * 1024 * 1024);
+        let oversized = FetchLimits {
+            timeout_ms: Some(u64::MAX),
+            max_response_bytes: Some(usize::MAX),
+            allowed_hosts: vec![],
+        };
+        assert_eq!(oversized.timeout(), Duration::from_millis(MAX_TIMEOUT_MS));
+        assert_eq!(oversized.max_response_bytes(), MAX_RESPONSE_BYTES_CEILING);
+        let limits: FetchLimits =
+            serde_json::from_str(r#"{"timeoutMs": 500, "maxResponseBytes": 1024}"#).unwrap();
+        assert_eq!(limits.timeout(), Duration::from_millis(500));
+        assert_eq!(limits.max_response_bytes(), 1024);
+    }
+
+    #[test]
+    fn capped_body_accepts_body_at_limit() {
+        let mut body = CappedBody::new(8);
+        body.push(b"abcd").unwrap();
+        body.push(b"efgh").unwrap();
+        assert_eq!(body.buf, b"abcdefgh");
+    }
+
+    #[test]
+    fn capped_body_aborts_on_first_chunk_over_limit() {
+        // Mock a streamed 3 MB body against a 1 MB cap, in 64 KB chunks.
+        let limit = 1024 * 1024;
+        let chunk = vec![b'x'; 64 * 1024];
+        let mut body = CappedBody::new(limit);
+        let mut pushed = 0;
+        let err = loop {
+            match body.push(&chunk) {
+                Ok(()) => pushed += 1,
+                Err(e) => break e,
+            }
+            assert!(pushed < 48, "cap never triggered");
+        };
+        assert_eq!(err.kind, PluginHttpErrorKind::SizeExceeded);
+        assert_eq!(pushed, 16, "stops at the chunk that crosses the cap");
+        assert!(body.buf.len() <= limit);
+    }
+
+    #[test]
+    fn error_serializes_kind_and_message() {
+        let err = PluginHttpError::size_exceeded(1024);
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({
+                "kind": "size_exceeded",
+                "message": "Response body exceeds maximum size (1024 bytes)",
+            })
+        );
+    }
 }
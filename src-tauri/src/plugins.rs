@@ -99,6 +99,10 @@ pub struct PluginManifest {
     /// URL patterns allowed for net:http requests (e.g. "https://api.example.com/*")
     #[serde(default, rename = "allowedUrls")]
     pub allowed_urls: Vec<String>,
+    /// Hostnames net:http requests (and their redirects) may reach. Exact
+    /// names or `*.example.com` for subdomains; empty means no host filter.
+    #[serde(default, rename = "allowedHosts")]
+    pub allowed_hosts: Vec<String>,
     /// Agent types this plugin is specific to (e.g. ["claude", "gemini"]).
     /// Empty means the plugin is universal and active for all terminals.
     #[serde(default, rename = "agentTypes")]
@@ -147,6 +151,7 @@ const KNOWN_CAPABILITIES: &[&str] = &[
     "ui:sidebar",
     "ui:file-icons",
     "ui:file-preview",
+    "ui:events",
 ];
 
 /// Validate a parsed manifest for required fields and sanity.
@@ -473,6 +478,147 @@ pub(crate) fn unregister_loaded_plugin_impl(state: &crate::AppState, plugin_id:
     state.loaded_plugins.remove(plugin_id);
 }
 
+// ---------------------------------------------------------------------------
+// Plugin-emitted structured events
+// ---------------------------------------------------------------------------
+
+/// Parsed-event types a plugin may emit. Everything else stays parser-only.
+const PLUGIN_EVENT_TYPES: &[&str] = &["status-line", "progress", "intent"];
+
+/// Maximum serialized payload size for `plugin_emit_event`.
+const MAX_PLUGIN_EVENT_BYTES: usize = 4 * 1024;
+
+fn payload_str(payload: &serde_json::Value, key: &str) -> Result<String, String> {
+    payload
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .ok_or_else(|| format!("payload.{key} must be a string"))
+}
+
+fn payload_opt_str(payload: &serde_json::Value, key: &str) -> Result<Option<String>, String> {
+    match payload.get(key) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_str()
+            .map(|s| Some(s.to_string()))
+            .ok_or_else(|| format!("payload.{key} must be a string")),
+    }
+}
+
+fn payload_u8(payload: &serde_json::Value, key: &str, max: u64) -> Result<u8, String> {
+    payload
+        .get(key)
+        .and_then(|v| v.as_u64())
+        .filter(|n| *n <= max)
+        .map(|n| n as u8)
+        .ok_or_else(|| format!("payload.{key} must be an integer 0-{max}"))
+}
+
+/// Validate a plugin event and build the `ParsedEvent` JSON tagged with
+/// `plugin_id`. Building through `ParsedEvent` keeps the shape identical to
+/// what the output parser emits, so existing frontend handlers apply as-is.
+pub(crate) fn build_plugin_event(
+    plugin_id: &str,
+    event_type: &str,
+    payload: &serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    use crate::output_parser::ParsedEvent;
+
+    if !PLUGIN_EVENT_TYPES.contains(&event_type) {
+        return Err(format!(
+            "Event type \"{event_type}\" is not allowed (allowed: {})",
+            PLUGIN_EVENT_TYPES.join(", ")
+        ));
+    }
+    let size = serde_json::to_vec(payload).map(|v| v.len()).unwrap_or(0);
+    if size > MAX_PLUGIN_EVENT_BYTES {
+        return Err(format!(
+            "Event payload is {size} bytes (max {MAX_PLUGIN_EVENT_BYTES})"
+        ));
+    }
+
+    let event = match event_type {
+        "status-line" => ParsedEvent::StatusLine {
+            task_name: payload_str(payload, "task_name")?,
+            full_line: payload_str(payload, "full_line")?,
+            time_info: payload_opt_str(payload, "time_info")?,
+            token_info: payload_opt_str(payload, "token_info")?,
+        },
+        "progress" => ParsedEvent::Progress {
+            state: payload_u8(payload, "state", 3)?,
+            value: payload_u8(payload, "value", 100)?,
+        },
+        _ => ParsedEvent::Intent {
+            text: payload_str(payload, "text")?,
+            title: payload_opt_str(payload, "title")?,
+        },
+    };
+
+    let mut json = serde_json::to_value(&event).map_err(|e| e.to_string())?;
+    if let Some(obj) = json.as_object_mut() {
+        obj.insert(
+            "plugin_id".into(),
+            serde_json::Value::String(plugin_id.into()),
+        );
+    }
+    Ok(json)
+}
+
+pub(crate) fn plugin_emit_event_impl(
+    state: &crate::AppState,
+    plugin_id: &str,
+    event_type: &str,
+    payload: &serde_json::Value,
+    session_id: Option<&str>,
+) -> Result<(), String> {
+    check_plugin_capability(state, plugin_id, "ui:events")?;
+    if let Some(sid) = session_id
+        && !state.sessions.contains_key(sid)
+    {
+        return Err(format!("Session not found: {sid}"));
+    }
+    let json = build_plugin_event(plugin_id, event_type, payload)?;
+
+    #[cfg(feature = "desktop")]
+    if let Some(app) = state.app_handle.read().as_ref() {
+        let channel = match session_id {
+            Some(sid) => format!("pty-parsed-{sid}"),
+            None => "plugin-parsed".to_string(),
+        };
+        let _ = app.emit(&channel, &json);
+    }
+    if let Some(sid) = session_id {
+        let _ = state.event_bus.send(crate::state::AppEvent::PtyParsed {
+            session_id: sid.to_string(),
+            parsed: json,
+        });
+    }
+    Ok(())
+}
+
+/// Emit a structured event (`status-line`, `progress`, `intent`) on behalf of
+/// a plugin. With a `session_id` it joins that session's `pty-parsed-*`
+/// stream; without one it goes to the global `plugin-parsed` channel, which the
+/// frontend shows in the status bar ticker. Requires the `ui:events` capability.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub fn plugin_emit_event(
+    plugin_id: String,
+    event_type: String,
+    payload: serde_json::Value,
+    session_id: Option<String>,
+    state: tauri::State<'_, std::sync::Arc<crate::AppState>>,
+) -> Result<(), String> {
+    plugin_emit_event_impl(
+        &state,
+        &plugin_id,
+        &event_type,
+        &payload,
+        session_id.as_deref(),
+    )
+}
+
 // ---------------------------------------------------------------------------
 // Plugin README
 // ---------------------------------------------------------------------------
@@ -1033,6 +1179,58 @@ pub fn start_plugin_watcher(app_handle: &AppHandle) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn plugin_event_matches_parsed_event_shape() {
+        use crate::output_parser::ParsedEvent;
+
+        let json = build_plugin_event(
+            "ci-watcher",
+            "progress",
+            &serde_json::json!({ "state": 1, "value": 42 }),
+        )
+        .unwrap();
+        let mut expected = serde_json::to_value(ParsedEvent::Progress {
+            state: 1,
+            value: 42,
+        })
+        .unwrap();
+        expected["plugin_id"] = serde_json::json!("ci-watcher");
+        assert_eq!(json, expected);
+
+        let json = build_plugin_event(
+            "ci-watcher",
+            "intent",
+            &serde_json::json!({ "text": "Waiting for CI" }),
+        )
+        .unwrap();
+        let mut expected = serde_json::to_value(ParsedEvent::Intent {
+            text: "Waiting for CI".into(),
+            title: None,
+        })
+        .unwrap();
+        expected["plugin_id"] = serde_json::json!("ci-watcher");
+        assert_eq!(json, expected);
+    }
+
+    #[test]
+    fn plugin_event_rejects_unknown_type_and_oversized_payload() {
+        let err = build_plugin_event("p", "pr-url", &serde_json::json!({})).unwrap_err();
+        assert!(err.contains("not allowed"), "{err}");
+
+        let big = "x".repeat(MAX_PLUGIN_EVENT_BYTES + 1);
+        let err =
+            build_plugin_event("p", "intent", &serde_json::json!({ "text": big })).unwrap_err();
+        assert!(err.contains("bytes"), "{err}");
+
+        let err = build_plugin_event(
+            "p",
+            "progress",
+            &serde_json::json!({ "state": 9, "value": 1 }),
+        )
+        .unwrap_err();
+        assert!(err.contains("payload.state"), "{err}");
+    }
+
     // -- Path safety --
 
     #[test]
@@ -1103,6 +1301,7 @@ mod tests {
             author: None,
             capabilities: vec![],
             allowed_urls: vec![],
+            allowed_hosts: vec![],
             agent_types: vec![],
             binaries: vec![],
         }
@@ -8,9 +8,35 @@ use std::time::{Duration, Instant};
 /// Finds `{varname}` patterns and returns unique variable names
 /// in order of first appearance. Matches greedily from the first
 /// `{` to the first `}`, so `{{nested}}` yields `{nested`.
+/// Block tags contribute their variable (`{{#if issue}}` → `issue`);
+/// the loop-scoped `{this}` / `{@index}` are not reported.
 pub(crate) fn extract_variables(content: &str) -> Vec<String> {
     let mut vars = Vec::new();
     let mut seen = HashSet::new();
+
+    if !has_block_tags(content) {
+        collect_variables(content, &mut vars, &mut seen);
+        return vars;
+    }
+
+    seen.insert("this".to_string());
+    seen.insert("@index".to_string());
+    let mut pos = 0;
+    // Malformed markup is reported by render_template; here we just stop at it.
+    while let Ok(Some((start, end, tag))) = next_tag(content, pos) {
+        collect_variables(&content[pos..start], &mut vars, &mut seen);
+        if let Tag::If(name) | Tag::Each(name) = tag
+            && seen.insert(name.to_string())
+        {
+            vars.push(name.to_string());
+        }
+        pos = end;
+    }
+    collect_variables(&content[pos..], &mut vars, &mut seen);
+    vars
+}
+
+fn collect_variables(content: &str, vars: &mut Vec<String>, seen: &mut HashSet<String>) {
     let bytes = content.as_bytes();
     let len = bytes.len();
     let mut i = 0;
@@ -31,8 +57,6 @@ pub(crate) fn extract_variables(content: &str) -> Vec<String> {
             i += 1;
         }
     }
-
-    vars
 }
 
 /// Replace `{name}` placeholders with values from the variables map.
@@ -102,6 +126,277 @@ fn process_content_inner(
     result
 }
 
+// ---------------------------------------------------------------------------
+// Block templating — a small Handlebars subset layered over `{var}` substitution.
+//
+//   {{#if var}} ... {{else}} ... {{/if}}   var set and not blank → first branch
+//   {{#each var}} ... {this} ... {{/each}} once per non-empty line of var;
+//                                          {this} is the line, {@index} its 0-based index
+//
+// Undefined variables are falsy / iterate zero times. A tag alone on its line
+// consumes that whole line, so block markup doesn't leave blank lines behind.
+// Any other `{{...}}` is literal text, as before.
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+enum Node<'a> {
+    Text(&'a str),
+    If {
+        var: &'a str,
+        then: Vec<Node<'a>>,
+        otherwise: Vec<Node<'a>>,
+    },
+    Each {
+        var: &'a str,
+        body: Vec<Node<'a>>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tag<'a> {
+    If(&'a str),
+    Each(&'a str),
+    Else,
+    CloseIf,
+    CloseEach,
+}
+
+impl Tag<'_> {
+    fn describe(self) -> String {
+        match self {
+            Tag::If(v) => format!("{{{{#if {v}}}}}"),
+            Tag::Each(v) => format!("{{{{#each {v}}}}}"),
+            Tag::Else => "{{else}}".to_string(),
+            Tag::CloseIf => "{{/if}}".to_string(),
+            Tag::CloseEach => "{{/each}}".to_string(),
+        }
+    }
+}
+
+/// 1-based line number of byte offset `pos`.
+fn line_of(content: &str, pos: usize) -> usize {
+    content[..pos].matches('\n').count() + 1
+}
+
+/// True if `content` contains any block tag, i.e. needs the template parser.
+fn has_block_tags(content: &str) -> bool {
+    content.contains("{{#") || content.contains("{{/") || content.contains("{{else}}")
+}
+
+/// Find the next block tag at or after `from`.
+///
+/// Returns `(start, end, tag)` where `start..end` is the span to drop from the
+/// output — widened to the whole line when the tag stands alone on it.
+fn next_tag(content: &str, from: usize) -> Result<Option<(usize, usize, Tag<'_>)>, String> {
+    let mut search = from;
+    while let Some(rel) = content[search..].find("{{") {
+        let start = search + rel;
+        let rest = &content[start + 2..];
+        if !(rest.starts_with('#') || rest.starts_with('/') || rest.starts_with("else}}")) {
+            search = start + 2;
+            continue;
+        }
+        let Some(close) = rest.find("}}") else {
+            return Err(format!(
+                "Unterminated tag at line {}",
+                line_of(content, start)
+            ));
+        };
+        let inner = rest[..close].trim();
+        let end = start + 2 + close + 2;
+        let tag = if inner == "else" {
+            Tag::Else
+        } else if inner == "/if" {
+            Tag::CloseIf
+        } else if inner == "/each" {
+            Tag::CloseEach
+        } else {
+            let (helper, var) = inner[1..]
+                .split_once(char::is_whitespace)
+                .unwrap_or((&inner[1..], ""));
+            let var = var.trim();
+            let line = line_of(content, start);
+            if inner.starts_with('/') {
+                return Err(format!(
+                    "Unknown closing tag {{{{{inner}}}}} at line {line}"
+                ));
+            }
+            if var.is_empty() || var.contains(char::is_whitespace) {
+                return Err(format!(
+                    "{{{{#{helper}}}}} at line {line} needs exactly one variable name"
+                ));
+            }
+            match helper {
+                "if" => Tag::If(var),
+                "each" => Tag::Each(var),
+                _ => return Err(format!("Unknown block helper '#{helper}' at line {line}")),
+            }
+        };
+
+        // Standalone tag: only whitespace around it on its line → drop the line.
+        let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1).max(from);
+        let line_end = content[end..]
+            .find('\n')
+            .map_or(content.len(), |i| end + i + 1);
+        let standalone = content[line_start..start].trim().is_empty()
+            && content[end..line_end].trim().is_empty();
+        return Ok(Some(if standalone {
+            (line_start, line_end, tag)
+        } else {
+            (start, end, tag)
+        }));
+    }
+    Ok(None)
+}
+
+/// Parse nodes until EOF or a closing/else tag, which is returned to the caller.
+/// `open` describes the enclosing block (tag + line) for error messages.
+fn parse_nodes<'a>(
+    content: &'a str,
+    pos: &mut usize,
+    open: Option<(Tag<'a>, usize)>,
+) -> Result<(Vec<Node<'a>>, Option<Tag<'a>>), String> {
+    let mut nodes = Vec::new();
+    loop {
+        let Some((start, end, tag)) = next_tag(content, *pos)? else {
+            if *pos < content.len() {
+                nodes.push(Node::Text(&content[*pos..]));
+            }
+            *pos = content.len();
+            return match open {
+                Some((tag, line)) => {
+                    Err(format!("Unclosed {} opened at line {line}", tag.describe()))
+                }
+                None => Ok((nodes, None)),
+            };
+        };
+        if start > *pos {
+            nodes.push(Node::Text(&content[*pos..start]));
+        }
+        let line = line_of(content, start);
+        *pos = end;
+        match tag {
+            Tag::If(var) => {
+                let (then, term) = parse_nodes(content, pos, Some((tag, line)))?;
+                let otherwise = match term {
+                    Some(Tag::CloseIf) => Vec::new(),
+                    Some(Tag::Else) => {
+                        let (otherwise, term) = parse_nodes(content, pos, Some((tag, line)))?;
+                        expect_closer(tag, line, term, Tag::CloseIf)?;
+                        otherwise
+                    }
+                    _ => return Err(mismatch(tag, line, term)),
+                };
+                nodes.push(Node::If {
+                    var,
+                    then,
+                    otherwise,
+                });
+            }
+            Tag::Each(var) => {
+                let (body, term) = parse_nodes(content, pos, Some((tag, line)))?;
+                expect_closer(tag, line, term, Tag::CloseEach)?;
+                nodes.push(Node::Each { var, body });
+            }
+            Tag::Else | Tag::CloseIf | Tag::CloseEach => {
+                if open.is_none() {
+                    return Err(format!("Unexpected {} at line {line}", tag.describe()));
+                }
+                return Ok((nodes, Some(tag)));
+            }
+        }
+    }
+}
+
+fn mismatch(open: Tag<'_>, line: usize, found: Option<Tag<'_>>) -> String {
+    match found {
+        Some(found) => format!(
+            "{} opened at line {line} cannot be closed by {}",
+            open.describe(),
+            found.describe()
+        ),
+        None => format!("Unclosed {} opened at line {line}", open.describe()),
+    }
+}
+
+fn expect_closer(
+    open: Tag<'_>,
+    line: usize,
+    found: Option<Tag<'_>>,
+    expected: Tag<'_>,
+) -> Result<(), String> {
+    if found == Some(expected) {
+        Ok(())
+    } else {
+        Err(mismatch(open, line, found))
+    }
+}
+
+fn is_truthy(value: Option<&String>) -> bool {
+    value.is_some_and(|v| !v.trim().is_empty())
+}
+
+fn render_nodes(
+    nodes: &[Node<'_>],
+    variables: &HashMap<String, String>,
+    shell_safe: bool,
+    out: &mut String,
+) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(&process_content_inner(text, variables, shell_safe)),
+            Node::If {
+                var,
+                then,
+                otherwise,
+            } => {
+                let branch = if is_truthy(variables.get(*var)) {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, variables, shell_safe, out);
+            }
+            Node::Each { var, body } => {
+                let Some(list) = variables.get(*var) else {
+                    continue;
+                };
+                let items = list.lines().map(str::trim).filter(|l| !l.is_empty());
+                for (index, item) in items.enumerate() {
+                    let mut scoped = variables.clone();
+                    scoped.insert("this".to_string(), item.to_string());
+                    scoped.insert("@index".to_string(), index.to_string());
+                    render_nodes(body, &scoped, shell_safe, out);
+                }
+            }
+        }
+    }
+}
+
+/// Render a prompt template: block tags first, then `{var}` substitution.
+///
+/// Content without block tags goes straight to `{var}` substitution, so
+/// existing prompts render exactly as before. Malformed block markup
+/// (unclosed or mismatched blocks, unknown helpers) is an error.
+pub(crate) fn render_template(
+    content: &str,
+    variables: &HashMap<String, String>,
+    shell_safe: bool,
+) -> Result<String, String> {
+    if !has_block_tags(content) {
+        return Ok(if shell_safe {
+            process_content_shell_safe(content, variables)
+        } else {
+            process_content(content, variables)
+        });
+    }
+    let mut pos = 0;
+    let (nodes, _) = parse_nodes(content, &mut pos, None)?;
+    let mut out = String::with_capacity(content.len());
+    render_nodes(&nodes, variables, shell_safe, &mut out);
+    Ok(out)
+}
+
 /// Platform-appropriate shell quoting for a single argument.
 ///
 /// On POSIX (`sh -c`) we use single-quote wrapping: `'` → `'\''` and wrap in
@@ -159,15 +454,34 @@ pub(crate) fn extract_prompt_variables(content: String) -> Vec<String> {
     extract_variables(&content)
 }
 
+/// Count a real send of library prompt `prompt_id` (previews pass `send: false`).
+/// Tracking failures are logged, never surfaced — the render already succeeded.
+fn record_send(prompt_id: Option<&str>, send: Option<bool>) {
+    if send != Some(true) {
+        return;
+    }
+    let Some(id) = prompt_id else { return };
+    if let Err(e) = crate::config::record_prompt_usage(id) {
+        tracing::warn!(source = "prompt", prompt_id = %id, "Failed to record prompt usage: {e}");
+    }
+}
+
+/// Render prompt content: `{var}` substitution plus `{{#if}}` / `{{#each}}`
+/// blocks (see [`render_template`]). Errors on malformed block markup.
+/// With `send: true` and a `prompt_id`, also counts the send in the library.
 #[cfg_attr(feature = "desktop", tauri::command)]
 pub(crate) fn process_prompt_content(
     content: String,
     variables: HashMap<String, String>,
-) -> String {
-    process_content(&content, &variables)
+    prompt_id: Option<String>,
+    send: Option<bool>,
+) -> Result<String, String> {
+    let rendered = render_template(&content, &variables, false)?;
+    record_send(prompt_id.as_deref(), send);
+    Ok(rendered)
 }
 
-/// Tauri-exposed wrapper around [`process_content_shell_safe`] for use by the
+/// Shell-safe Tauri wrapper around [`render_template`] for use by the
 /// Smart Prompts shell-execution path. Callers must prefer this over
 /// `process_prompt_content` whenever the resulting string is going to be
 /// handed to `sh -c` / `cmd /C`, otherwise repo-controlled variables like
@@ -176,8 +490,12 @@ pub(crate) fn process_prompt_content(
 pub(crate) fn process_prompt_content_shell_safe(
     content: String,
     variables: HashMap<String, String>,
-) -> String {
-    process_content_shell_safe(&content, &variables)
+    prompt_id: Option<String>,
+    send: Option<bool>,
+) -> Result<String, String> {
+    let rendered = render_template(&content, &variables, true)?;
+    record_send(prompt_id.as_deref(), send);
+    Ok(rendered)
 }
 
 const MAX_VARIABLE_LEN: usize = 50_000;
@@ -632,6 +950,101 @@ mod tests {
         assert!(vars.get("branch").is_none() || !vars.get("branch").unwrap().is_empty());
     }
 
+    // --- render_template tests ---
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn template_if_block_included_when_var_set() {
+        let tpl = "Fix the bug.{{#if issue}} See {issue}.{{/if}}";
+        let out = render_template(tpl, &vars(&[("issue", "#42")]), false).unwrap();
+        assert_eq!(out, "Fix the bug. See #42.");
+    }
+
+    #[test]
+    fn template_if_block_excluded_when_var_missing_or_blank() {
+        let tpl = "Fix the bug.{{#if issue}} See {issue}.{{else}} No issue.{{/if}}";
+        assert_eq!(
+            render_template(tpl, &HashMap::new(), false).unwrap(),
+            "Fix the bug. No issue."
+        );
+        assert_eq!(
+            render_template(tpl, &vars(&[("issue", "  ")]), false).unwrap(),
+            "Fix the bug. No issue."
+        );
+    }
+
+    #[test]
+    fn template_nested_if_and_standalone_lines() {
+        let tpl = "Review:\n{{#if diff}}\nDiff present.\n{{#if pr_title}}\nPR: {pr_title}\n{{/if}}\n{{/if}}\nDone.";
+        let out = render_template(tpl, &vars(&[("diff", "x")]), false).unwrap();
+        assert_eq!(out, "Review:\nDiff present.\nDone.");
+        let out =
+            render_template(tpl, &vars(&[("diff", "x"), ("pr_title", "Add y")]), false).unwrap();
+        assert_eq!(out, "Review:\nDiff present.\nPR: Add y\nDone.");
+    }
+
+    #[test]
+    fn template_each_iterates_list_lines() {
+        let tpl = "Files:\n{{#each changed_files}}\n{@index}. {this} ({branch})\n{{/each}}\nEnd";
+        let out = render_template(
+            tpl,
+            &vars(&[
+                ("changed_files", "src/a.rs\n\nsrc/b.rs\n"),
+                ("branch", "main"),
+            ]),
+            false,
+        )
+        .unwrap();
+        assert_eq!(out, "Files:\n0. src/a.rs (main)\n1. src/b.rs (main)\nEnd");
+        // Undefined list iterates zero times
+        assert_eq!(
+            render_template(tpl, &HashMap::new(), false).unwrap(),
+            "Files:\nEnd"
+        );
+    }
+
+    #[test]
+    fn template_malformed_blocks_are_errors() {
+        let err = render_template("a\n{{#if x}}\nb", &HashMap::new(), false).unwrap_err();
+        assert!(
+            err.contains("Unclosed {{#if x}}") && err.contains("line 2"),
+            "{err}"
+        );
+        let err = render_template("a {{/if}}", &HashMap::new(), false).unwrap_err();
+        assert!(err.contains("Unexpected {{/if}}"), "{err}");
+        let err = render_template("{{#each xs}}{this}{{/if}}", &HashMap::new(), false).unwrap_err();
+        assert!(err.contains("cannot be closed by {{/if}}"), "{err}");
+        let err = render_template("{{#unless x}}y{{/unless}}", &HashMap::new(), false).unwrap_err();
+        assert!(err.contains("Unknown block helper"), "{err}");
+        let err = render_template("{{#if}}y{{/if}}", &HashMap::new(), false).unwrap_err();
+        assert!(err.contains("needs exactly one variable name"), "{err}");
+    }
+
+    #[test]
+    fn template_without_blocks_matches_plain_substitution() {
+        let v = vars(&[("name", "World")]);
+        for tpl in ["Hello {name}!", "{{nested}} {name}", "{unknown} {name"] {
+            assert_eq!(
+                render_template(tpl, &v, false).unwrap(),
+                process_content(tpl, &v)
+            );
+        }
+    }
+
+    #[test]
+    fn extract_variables_includes_block_vars_not_loop_scope() {
+        let vars = extract_variables(
+            "{{#if issue}}{issue}{{/if}}{{#each files}}{this}{@index}{{/each}}{branch}",
+        );
+        assert_eq!(vars, vec!["issue", "files", "branch"]);
+    }
+
     // --- process_content_shell_safe tests ---
 
     #[test]
@@ -771,4 +1184,58 @@ mod tests {
         // Other repo's entries untouched.
         assert!(cache.contains_key(&(repo_b.to_string(), "diff".to_string())));
     }
+
+    #[test]
+    fn process_prompt_content_counts_sends_but_not_previews() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let _guard = crate::config::set_config_dir_override(dir.path().to_path_buf());
+        crate::config::save_prompt_library(crate::config::PromptLibraryConfig {
+            prompts: vec![crate::config::PromptEntry {
+                id: "p1".to_string(),
+                label: "Review".to_string(),
+                text: "Review {branch}".to_string(),
+                ..Default::default()
+            }],
+        })
+        .unwrap();
+        let vars = HashMap::from([("branch".to_string(), "main".to_string())]);
+
+        // Preview: rendered, not counted.
+        let out = process_prompt_content(
+            "Review {branch}".into(),
+            vars.clone(),
+            Some("p1".into()),
+            Some(false),
+        )
+        .unwrap();
+        assert_eq!(out, "Review main");
+        let lib = crate::config::load_prompt_library(None);
+        assert_eq!(lib.prompts[0].use_count, 0);
+        assert_eq!(lib.prompts[0].last_used_at, None);
+
+        // Send: counted and timestamped.
+        process_prompt_content(
+            "Review {branch}".into(),
+            vars,
+            Some("p1".into()),
+            Some(true),
+        )
+        .unwrap();
+        let lib = crate::config::load_prompt_library(None);
+        assert_eq!(lib.prompts[0].use_count, 1);
+        assert!(lib.prompts[0].last_used_at.is_some_and(|t| t > 0));
+
+        // A frontend save without usage fields must not reset the counter.
+        crate::config::save_prompt_library(crate::config::PromptLibraryConfig {
+            prompts: vec![crate::config::PromptEntry {
+                id: "p1".to_string(),
+                label: "Review (renamed)".to_string(),
+                ..Default::default()
+            }],
+        })
+        .unwrap();
+        let lib = crate::config::load_prompt_library(Some("most_used".into()));
+        assert_eq!(lib.prompts[0].use_count, 1);
+        assert_eq!(lib.prompts[0].label, "Review (renamed)");
+    }
 }
@@ -12,9 +12,8 @@ use crate::input_line_buffer::{InputAction, InputLineBuffer};
 use crate::output_parser::{OutputParser, ParsedEvent};
 use crate::state::{
     AppState, ChangedRow, EscapeAwareBuffer, KittyAction, KittyKeyboardState,
-    MAX_CONCURRENT_SESSIONS, OUTPUT_RING_BUFFER_CAPACITY, OrchestratorStats, OutputRingBuffer,
-    PtyConfig, PtyOutput, PtySession, Utf8ReadBuffer, VT_LOG_BUFFER_CAPACITY, VtLogBuffer,
-    strip_kitty_sequences,
+    OUTPUT_RING_BUFFER_CAPACITY, OrchestratorStats, OutputRingBuffer, PtyConfig, PtyOutput,
+    PtySession, Utf8ReadBuffer, VT_LOG_BUFFER_CAPACITY, VtLogBuffer, strip_kitty_sequences,
 };
 use crate::worktree::{
     WorktreeConfig, WorktreeResult, create_worktree_with_stale_recovery, remove_worktree_internal,
@@ -356,6 +355,46 @@ pub(crate) fn classify_shell(cmd: &str) -> ShellFamily {
     }
 }
 
+/// Where a PTY session's shell actually runs.
+///
+/// A session spawned through `docker exec` or `ssh` shows a remote filesystem,
+/// so local file and git operations against its cwd are meaningless. The
+/// frontend uses this to disable them for that session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum SessionContext {
+    Local,
+    /// Inside a container (`docker`/`podman` `exec`/`run`/`attach`)
+    Docker,
+    /// On a remote host (`ssh`, `mosh`, `autossh`)
+    Ssh,
+}
+
+/// Classify the spawn command of a session into a [`SessionContext`].
+/// Pure function, same parsing rules as [`classify_shell`]: the leading
+/// binary's basename decides, with `.exe` stripped.
+pub(crate) fn classify_session_context(cmd: &str) -> SessionContext {
+    let mut words = cmd.trim().split_whitespace();
+    let exe = words.next().unwrap_or("").trim_matches('"');
+    let filename = exe.rsplit(['/', '\\']).next().unwrap_or(exe);
+    let lower = filename.to_ascii_lowercase();
+    let stem = lower.strip_suffix(".exe").unwrap_or(&lower);
+
+    match stem {
+        "ssh" | "mosh" | "autossh" => SessionContext::Ssh,
+        "docker" | "podman" => {
+            // Skip global flags (`docker --context prod exec ...` is rare
+            // enough that the first non-flag word is a good-enough subcommand).
+            let subcommand = words.find(|w| !w.starts_with('-'));
+            match subcommand {
+                Some("exec" | "run" | "attach") => SessionContext::Docker,
+                _ => SessionContext::Local,
+            }
+        }
+        _ => SessionContext::Local,
+    }
+}
+
 /// How long the agent must be silent after printing a `?`-ending line before
 /// we treat it as a question waiting for input. 10s is long enough to avoid
 /// false positives from AI agents that pause while thinking between API calls.
@@ -372,6 +411,15 @@ const STALE_QUESTION_CHUNKS: u32 = 10;
 /// turn end (no retry) — 5s is enough to rule out a same-chunk recovery.
 const SILENCE_TOOL_ERROR_THRESHOLD: std::time::Duration = std::time::Duration::from_secs(5);
 
+/// How long the agent must be silent after a task-completion candidate (marker
+/// line or prompt return) before `TaskComplete` fires. Matches the tool-error
+/// window: long enough that a mid-stream "Done." followed by more work is dropped.
+const SILENCE_TASK_COMPLETE_THRESHOLD: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Real-output chunks since the last user input before a bare prompt return
+/// counts as "finished a task" — filters out quick replies and prompt redraws.
+const TASK_COMPLETE_MIN_OUTPUT_CHUNKS: u32 = 20;
+
 /// Detect a turn-ending tool-failure line like Claude Code's
 /// `⎿  Error: Exit code 1`. Anchored to line-start with only non-letter,
 /// non-quote prefix characters (whitespace, box-drawing glyphs) so source
@@ -618,6 +666,90 @@ pub(crate) fn find_last_chat_question(screen_rows: &[String]) -> Option<String>
     None
 }
 
+/// Wedge detection: input was written but nothing came back.
+///
+/// Armed by submitted input (`stamp_input_ms` with a `\r` or `\n`), disarmed by
+/// output that is more than an echo of that input (a blocked cooked-mode
+/// process still echoes keystrokes). Partial typing never arms: an idle prompt
+/// waiting for the rest of the line is not wedged.
+/// The silence timer samples the foreground process while armed; once
+/// `pty_wedge_timeout_secs` passes with the same foreground, `check` fires once.
+#[derive(Default)]
+pub(crate) struct WedgeWatch {
+    /// When the first unanswered input was written.
+    input_at: Option<std::time::Instant>,
+    /// Bytes written since arming — output up to ~2x this is treated as echo.
+    input_bytes: usize,
+    /// Bytes read since arming.
+    output_bytes: usize,
+    /// Foreground pid sampled on the first timer tick after arming.
+    foreground: Option<u32>,
+    /// Already reported for the current arming.
+    reported: bool,
+}
+
+impl WedgeWatch {
+    /// Record input. Arms on the first input that submits a line (`\r`/`\n`)
+    /// and keeps that as the start time; later input only adds to the echo budget.
+    pub(crate) fn on_input(&mut self, now: std::time::Instant, data: &str) {
+        if self.input_at.is_none() {
+            if !data.contains(['\r', '\n']) {
+                return;
+            }
+            self.input_at = Some(now);
+            self.output_bytes = 0;
+            self.foreground = None;
+            self.reported = false;
+        }
+        self.input_bytes += data.len();
+    }
+
+    /// Record output. Anything beyond an echo of the input counts as a response.
+    pub(crate) fn on_output(&mut self, len: usize) {
+        if self.input_at.is_none() {
+            return;
+        }
+        self.output_bytes += len;
+        if self.output_bytes > self.input_bytes.saturating_mul(2) {
+            self.reset();
+        }
+    }
+
+    pub(crate) fn is_armed(&self) -> bool {
+        self.input_at.is_some()
+    }
+
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Called by the silence timer with the current foreground pid. Returns the
+    /// silent duration the first time the session counts as wedged: armed for
+    /// at least `timeout` with the foreground unchanged since arming. A changed
+    /// foreground means the input started or ended a process — not a wedge.
+    pub(crate) fn check(
+        &mut self,
+        now: std::time::Instant,
+        timeout: std::time::Duration,
+        foreground: Option<u32>,
+    ) -> Option<std::time::Duration> {
+        let input_at = self.input_at?;
+        if self.foreground.is_none() {
+            self.foreground = foreground;
+        }
+        let silent = now.saturating_duration_since(input_at);
+        if self.reported || silent < timeout {
+            return None;
+        }
+        if foreground.is_none() || foreground != self.foreground {
+            self.reset();
+            return None;
+        }
+        self.reported = true;
+        Some(silent)
+    }
+}
+
 /// Shared state between the PTY reader thread and the silence-detection timer thread.
 pub(crate) struct SilenceState {
     /// When the last chunk of output was received from the PTY.
@@ -678,6 +810,20 @@ pub(crate) struct SilenceState {
     /// diagnostics only — the flush decision is driven by `last_output_at`,
     /// not the park time.
     pending_suggest_at: Option<std::time::Instant>,
+    /// Input-without-response tracking (see `WedgeWatch`).
+    pub(crate) wedge: WedgeWatch,
+    /// Task-completion line (or "Prompt returned") awaiting silence
+    /// verification. Cleared by any later real output.
+    pending_task_complete: Option<String>,
+    /// True once `TaskComplete` fired in the current input epoch — one
+    /// notification per turn, reset on user input.
+    task_complete_surfaced: bool,
+    /// Real-output chunks since the last user input (see
+    /// `TASK_COMPLETE_MIN_OUTPUT_CHUNKS`).
+    output_chunks_since_input: u32,
+    /// `disabled_parsers` contains `"question"`: the silence timer never
+    /// reports a question, from the screen or from `pending_question_line`.
+    question_disabled: bool,
 }
 
 impl SilenceState {
@@ -698,9 +844,23 @@ impl SilenceState {
             surfaced_tool_errors: std::collections::HashSet::new(),
             pending_suggest_items: None,
             pending_suggest_at: None,
+            wedge: WedgeWatch::default(),
+            pending_task_complete: None,
+            task_complete_surfaced: false,
+            output_chunks_since_input: 0,
+            question_disabled: false,
         }
     }
 
+    /// Apply the session's `disabled_parsers` to the silence-based detectors.
+    pub(crate) fn with_disabled_parsers(
+        mut self,
+        disabled: crate::output_parser::DisabledParsers,
+    ) -> Self {
+        self.question_disabled = disabled.question();
+        self
+    }
+
     /// Called by resize_pty when the terminal is resized.
     /// Marks the start of a grace period during which parsed events are suppressed.
     pub(crate) fn on_resize(&mut self) {
@@ -730,17 +890,17 @@ impl SilenceState {
             return;
         }
         // Safety cap: always settle after STARTUP_GRACE_MAX
-        if self.created_at.elapsed() >= STARTUP_GRACE_MAX {
-            self.startup_settled = true;
-            self.pending_suggest_items = None;
-            self.pending_suggest_at = None;
-            return;
-        }
-        // Settle after STARTUP_SETTLE_SILENCE without output
-        if self.last_output_at.elapsed() >= STARTUP_SETTLE_SILENCE {
+        if self.created_at.elapsed() >= STARTUP_GRACE_MAX
+            || self.last_output_at.elapsed() >= STARTUP_SETTLE_SILENCE
+        {
+            // Settle after STARTUP_SETTLE_SILENCE without output. Replayed
+            // history (`--continue`) must not surface as a suggest or a
+            // finished task.
             self.startup_settled = true;
             self.pending_suggest_items = None;
             self.pending_suggest_at = None;
+            self.pending_task_complete = None;
+            self.output_chunks_since_input = 0;
         }
     }
 
@@ -770,6 +930,7 @@ impl SilenceState {
 
         if !insignificant {
             self.last_output_at = std::time::Instant::now();
+            self.output_chunks_since_input = self.output_chunks_since_input.saturating_add(1);
         }
 
         // Only mark spinner active when the status line accompanies real output.
@@ -856,7 +1017,7 @@ impl SilenceState {
     /// Called by the timer thread. Returns the question text if the silence
     /// threshold has been reached and we haven't emitted yet.
     pub(crate) fn check_silence(&mut self) -> Option<String> {
-        if self.question_already_emitted {
+        if self.question_disabled || self.question_already_emitted {
             return None;
         }
         // Spinner active means the agent is working — not waiting for input.
@@ -938,9 +1099,54 @@ impl SilenceState {
         }
         let line = self.pending_tool_error.take()?;
         self.surfaced_tool_errors.insert(line.clone());
+        // A turn that ended on an error did not complete.
+        self.pending_task_complete = None;
         Some(line)
     }
 
+    /// Park a task-completion candidate. Ignored once `TaskComplete` already
+    /// fired in this input epoch (the marker stays on screen and re-appears in
+    /// `changed_rows` on every repaint).
+    pub(crate) fn mark_task_complete_candidate(&mut self, summary: String) {
+        if !self.task_complete_surfaced {
+            self.pending_task_complete = Some(summary);
+        }
+    }
+
+    /// Real output after the candidate: the agent kept working, so the marker
+    /// was mid-stream.
+    pub(crate) fn clear_task_complete_on_output(&mut self) {
+        self.pending_task_complete = None;
+    }
+
+    /// True when enough real output arrived since the last user input for a
+    /// bare prompt return to count as a finished task.
+    pub(crate) fn has_sustained_output(&self) -> bool {
+        self.output_chunks_since_input >= TASK_COMPLETE_MIN_OUTPUT_CHUNKS
+    }
+
+    /// Called by the timer thread. Returns the candidate once the session has
+    /// been silent for `SILENCE_TASK_COMPLETE_THRESHOLD` with no spinner and no
+    /// tool error pending.
+    pub(crate) fn check_task_complete(&mut self) -> Option<String> {
+        if self.is_spinner_active() || self.pending_tool_error.is_some() {
+            return None;
+        }
+        if self.last_output_at.elapsed() < SILENCE_TASK_COMPLETE_THRESHOLD {
+            return None;
+        }
+        let summary = self.pending_task_complete.take()?;
+        self.task_complete_surfaced = true;
+        Some(summary)
+    }
+
+    /// New input epoch: allow the next turn to notify again.
+    pub(crate) fn reset_task_complete_memory(&mut self) {
+        self.pending_task_complete = None;
+        self.task_complete_surfaced = false;
+        self.output_chunks_since_input = 0;
+    }
+
     /// Park `suggest:` items parsed from output. The silence timer will flush
     /// them to the frontend once the shell state transitions to idle — this
     /// is the single source of truth for "turn ended". A newer set overwrites
@@ -979,6 +1185,12 @@ impl SilenceState {
             && self.last_output_at.elapsed() >= SILENCE_QUESTION_THRESHOLD
     }
 
+    /// Whether the silence timer should look for a question this tick: the
+    /// `question` parser is enabled and [`Self::is_silent`] holds.
+    pub(crate) fn question_check_due(&self) -> bool {
+        !self.question_disabled && self.is_silent()
+    }
+
     /// Mark that a question has been emitted (prevents re-emission).
     /// Stores the emitted text so that scroll-induced reappearances of the same
     /// `?` line in changed_rows are recognized as duplicates, not new questions.
@@ -1180,6 +1392,15 @@ fn transition_shell_state(
     }
 }
 
+/// Whether a serialized parsed event passes the global `enabled_output_events`
+/// filter. Only detector output is filtered; state-machine events
+/// (shell-state, active-subtasks, user-input) are always emitted.
+fn output_event_enabled(state: &crate::state::AppState, json: &serde_json::Value) -> bool {
+    json.get("type")
+        .and_then(|t| t.as_str())
+        .is_none_or(|kind| state.config.read().output_event_enabled(kind))
+}
+
 /// Emit an ActiveSubtasks parsed event via both event bus and Tauri IPC.
 /// Used by the stale-subtasks recovery path to keep the frontend store in
 /// sync after `should_transition_idle` force-clears the in-memory counter.
@@ -1209,12 +1430,287 @@ fn emit_active_subtasks(
 }
 
 /// Extract a signal number from portable_pty's signal string.
-/// Format is typically "Killed: 9", "Interrupt: 2", or "Signal 15".
+/// Format is typically "Killed: 9", "Interrupt: 2", or "Signal 15" (macOS
+/// `strsignal`); Linux reports the bare name ("Killed", "Segmentation fault").
 pub(crate) fn parse_signal_number(sig: &str) -> i32 {
     sig.rsplit(|c: char| !c.is_ascii_digit())
         .find(|s| !s.is_empty())
         .and_then(|s| s.parse::<i32>().ok())
-        .unwrap_or(0)
+        .unwrap_or_else(|| signal_number_from_name(sig))
+}
+
+/// Linux `strsignal` names for the signals that end agent processes.
+fn signal_number_from_name(name: &str) -> i32 {
+    match name.trim() {
+        "Hangup" => 1,
+        "Interrupt" => 2,
+        "Quit" => 3,
+        "Illegal instruction" => 4,
+        "Trace/breakpoint trap" => 5,
+        "Aborted" => 6,
+        "Bus error" => 7,
+        "Floating point exception" => 8,
+        "Killed" => 9,
+        "Segmentation fault" => 11,
+        "Broken pipe" => 13,
+        "Alarm clock" => 14,
+        "Terminated" => 15,
+        _ => 0,
+    }
+}
+
+/// Exit code for a finished child; signal deaths are reported as 128 + signum
+/// (shell convention) so SIGKILL (137) is distinguishable from exit(1).
+fn exit_status_code(status: &portable_pty::ExitStatus) -> i32 {
+    match status.signal() {
+        Some(sig) => 128 + parse_signal_number(sig),
+        None => status.exit_code() as i32,
+    }
+}
+
+const SIGKILL_NUMBER: i32 = 9;
+
+/// Everything `classify_exit` needs besides the portable-pty exit status.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ExitContext {
+    /// The session's own child (usually the shell).
+    pub(crate) pid: Option<u32>,
+    /// `WCOREDUMP` of the child's raw wait status (see `peek_core_dumped`).
+    pub(crate) core_dumped: bool,
+    /// Foreground job the shell was running (`SessionState::foreground_pid`).
+    pub(crate) foreground_pid: Option<u32>,
+    /// We closed or killed the session.
+    pub(crate) killed_by_us: bool,
+}
+
+/// Classify how a session's process ended. `killed_by_us` wins (close/kill
+/// paths). A shell that exits with 128 + N while it had a foreground job (the
+/// agent) is reported as that job dying from signal N. An unexplained SIGKILL
+/// is checked against the kernel OOM log via `oom_probe` with the dead
+/// process's pid, which only runs in that case.
+pub(crate) fn classify_exit(
+    status: &portable_pty::ExitStatus,
+    ctx: ExitContext,
+    oom_probe: impl FnOnce(u32) -> bool,
+) -> crate::state::ExitInfo {
+    use crate::state::ExitReason;
+    let job_signal = match (status.signal(), ctx.foreground_pid) {
+        (None, Some(job)) if (129..=192).contains(&status.exit_code()) => {
+            Some((status.exit_code() as i32 - 128, job))
+        }
+        _ => None,
+    };
+    let (signaled, signal, subject, core_dumped) = match job_signal {
+        // The shell only reports the job's signal, not whether it dumped core.
+        Some((sig, job)) => (true, Some(sig), Some(job), false),
+        None => {
+            let sig = status.signal().map(parse_signal_number).filter(|&n| n > 0);
+            let signaled = status.signal().is_some();
+            (signaled, sig, ctx.pid, signaled && ctx.core_dumped)
+        }
+    };
+    let reason = if ctx.killed_by_us {
+        ExitReason::KilledByUs
+    } else if !signaled {
+        ExitReason::Exited
+    } else if signal == Some(SIGKILL_NUMBER) && subject.is_some_and(oom_probe) {
+        ExitReason::Oom
+    } else {
+        ExitReason::Signaled
+    };
+    crate::state::ExitInfo {
+        reason,
+        signal,
+        core_dumped,
+    }
+}
+
+/// Whether an exited-but-unreaped child dumped core. portable-pty's
+/// `ExitStatus` drops the `WCOREDUMP` bit, so peek at the raw status with
+/// `waitid(WNOWAIT)`, which leaves the child for `try_wait` to reap. False
+/// while the child is still running.
+#[cfg(unix)]
+fn peek_core_dumped(pid: u32) -> bool {
+    // CLD_DUMPED has the same value on Linux and macOS; libc only exports it
+    // for Linux.
+    const CLD_DUMPED: libc::c_int = 3;
+    let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+    let rc = unsafe {
+        libc::waitid(
+            libc::P_PID,
+            pid as libc::id_t,
+            &mut info,
+            libc::WEXITED | libc::WNOHANG | libc::WNOWAIT,
+        )
+    };
+    rc == 0 && info.si_code == CLD_DUMPED
+}
+
+#[cfg(not(unix))]
+fn peek_core_dumped(_pid: u32) -> bool {
+    false
+}
+
+/// Best-effort: did the kernel OOM killer take `pid`? Scans the recent kernel
+/// log (`journalctl -k`, then `dmesg`); false when neither is readable.
+#[cfg(target_os = "linux")]
+fn oom_killed(pid: u32) -> bool {
+    let needle = format!("Killed process {pid} ");
+    let sources: [(&str, &[&str]); 2] = [
+        (
+            "journalctl",
+            &["-k", "-q", "--no-pager", "--since", "-5min"],
+        ),
+        ("dmesg", &[]),
+    ];
+    sources.iter().any(|(cmd, args)| {
+        std::process::Command::new(cmd)
+            .args(*args)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .is_some_and(|out| String::from_utf8_lossy(&out.stdout).contains(&needle))
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn oom_killed(_pid: u32) -> bool {
+    false
+}
+
+/// Classification for a session we closed or killed ourselves; the signal is
+/// recovered from a 128 + signum exit code.
+fn killed_by_us_info(exit_code: Option<i32>) -> crate::state::ExitInfo {
+    let signal = exit_code.filter(|&c| c > 128).map(|c| c - 128);
+    crate::state::ExitInfo {
+        reason: crate::state::ExitReason::KilledByUs,
+        signal,
+        core_dumped: false,
+    }
+}
+
+/// Reap the session's child if it has exited, with its core-dump bit and pid.
+fn try_reap_child(session: &mut PtySession) -> Option<(portable_pty::ExitStatus, ExitContext)> {
+    let pid = session._child.process_id();
+    // Peek before try_wait reaps the child and the raw status is gone.
+    let core_dumped = pid.is_some_and(peek_core_dumped);
+    let status = session._child.try_wait().ok()??;
+    Some((
+        status,
+        ExitContext {
+            pid,
+            core_dumped,
+            ..Default::default()
+        },
+    ))
+}
+
+/// Classify a child that exited on its own and record `exit_codes` /
+/// `exit_reasons`.
+fn record_natural_exit(
+    state: &AppState,
+    session_id: &str,
+    status: &portable_pty::ExitStatus,
+    ctx: ExitContext,
+) {
+    let ctx = ExitContext {
+        foreground_pid: state
+            .session_states
+            .get(session_id)
+            .and_then(|s| s.foreground_pid),
+        ..ctx
+    };
+    let info = classify_exit(status, ctx, oom_killed);
+    if info.reason != crate::state::ExitReason::Exited {
+        tracing::warn!(
+            source = "pty",
+            session_id = %session_id,
+            reason = ?info.reason,
+            signal = ?info.signal,
+            core_dumped = info.core_dumped,
+            "Session process terminated abnormally"
+        );
+    }
+    state
+        .exit_codes
+        .insert(session_id.to_string(), exit_status_code(status));
+    state.exit_reasons.insert(session_id.to_string(), info);
+}
+
+/// Record `exit_codes` / `exit_reasons` for a session whose child has exited
+/// on its own. No-op when already recorded (close/kill paths) or when the
+/// child is not reaped within a short grace window.
+fn capture_exit_status(session_id: &str, state: &AppState) {
+    if state.exit_reasons.contains_key(session_id) {
+        return;
+    }
+    let Some(entry) = state.sessions.get(session_id) else {
+        return;
+    };
+    // The reader sees EOF slightly before the kernel reaps the child.
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(100);
+    let (status, ctx) = loop {
+        let mut session = entry.value().lock();
+        if let Some(reaped) = try_reap_child(&mut session) {
+            break reaped;
+        }
+        if std::time::Instant::now() >= deadline {
+            return;
+        }
+        drop(session);
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    };
+    drop(entry);
+    record_natural_exit(state, session_id, &status, ctx);
+}
+
+/// Stamp a session's exit classification before close/kill signal it, while
+/// it is still in `sessions`. A child that already died on its own keeps its
+/// real classification; otherwise `KilledByUs` is recorded up front, so the
+/// reader's exit path (which reports `exit_reasons` as soon as it sees EOF)
+/// never mistakes our signal for a crash. Returns whether the child had
+/// already exited; `None` when the session doesn't exist.
+fn stamp_exit_before_close(state: &AppState, session_id: &str) -> Option<bool> {
+    let reaped = {
+        let entry = state.sessions.get(session_id)?;
+        let mut session = entry.value().lock();
+        try_reap_child(&mut session)
+    };
+    match reaped {
+        Some((status, ctx)) => {
+            record_natural_exit(state, session_id, &status, ctx);
+            Some(true)
+        }
+        None => {
+            state
+                .exit_reasons
+                .insert(session_id.to_string(), killed_by_us_info(None));
+            Some(false)
+        }
+    }
+}
+
+/// Remember the foreground job (agent or command) a session's shell is
+/// running, for `classify_exit`. Cleared while the shell itself is in the
+/// foreground.
+#[cfg(unix)]
+fn sample_foreground_job(state: &AppState, session_id: &str) {
+    let job = {
+        let Some(entry) = state.sessions.get(session_id) else {
+            return;
+        };
+        let session = entry.value().lock();
+        let child = session._child.process_id();
+        session
+            .master
+            .process_group_leader()
+            .filter(|&pgid| pgid > 1)
+            .map(|pgid| pgid as u32)
+            .filter(|&pgid| Some(pgid) != child)
+    };
+    if let Some(mut ss) = state.session_states.get_mut(session_id) {
+        ss.foreground_pid = job;
+    }
 }
 
 fn parse_osc7_cwd(url: &str) -> Result<String, ()> {
@@ -1357,6 +1853,70 @@ fn record_inferred_outcome_if_no_osc133(state: &AppState, session_id: &str) {
 /// question several rows above the prompt box.
 const SCREEN_VERIFY_ROWS: usize = 20;
 
+/// Emit `session-possibly-wedged` once when input has gone unanswered for
+/// `pty_wedge_timeout_secs` with the same foreground process. No-op when the
+/// timeout is 0 (disabled) or no input is pending.
+fn check_wedged(state: &AppState, silence: &Mutex<SilenceState>, session_id: &str) {
+    let timeout_secs = state.config.read().pty_wedge_timeout_secs;
+    if timeout_secs == 0 || !silence.lock().wedge.is_armed() {
+        return;
+    }
+    let foreground = foreground_pid(state, session_id);
+    let Some(silent) = silence.lock().wedge.check(
+        std::time::Instant::now(),
+        std::time::Duration::from_secs(timeout_secs),
+        foreground,
+    ) else {
+        return;
+    };
+    let foreground_name = foreground.and_then(process_name_from_pid);
+    tracing::warn!(
+        source = "pty",
+        session_id = %session_id,
+        silent_secs = silent.as_secs(),
+        foreground = foreground_name.as_deref().unwrap_or(""),
+        "Session possibly wedged — input without output"
+    );
+    #[cfg(feature = "desktop")]
+    if let Some(app) = state.app_handle.read().as_ref() {
+        let _ = app.emit(
+            "session-possibly-wedged",
+            serde_json::json!({
+                "session_id": session_id,
+                "foreground": foreground_name,
+                "foreground_pid": foreground,
+                "silent_secs": silent.as_secs(),
+            }),
+        );
+    }
+    let _ = state
+        .event_bus
+        .send(crate::state::AppEvent::SessionPossiblyWedged {
+            session_id: session_id.to_string(),
+            foreground: foreground_name,
+            foreground_pid: foreground,
+            silent_secs: silent.as_secs(),
+        });
+}
+
+/// Foreground process of a session: the PTY's foreground process group leader
+/// (deepest descendant of the shell on Windows).
+fn foreground_pid(state: &AppState, session_id: &str) -> Option<u32> {
+    let entry = state.sessions.get(session_id)?;
+    let session = entry.value().lock();
+    #[cfg(not(windows))]
+    {
+        session
+            .master
+            .process_group_leader()
+            .map(|pgid| pgid as u32)
+    }
+    #[cfg(windows)]
+    {
+        deepest_descendant_pid(session._child.process_id()?)
+    }
+}
+
 /// Spawn the silence-detection timer thread. Shared by desktop and headless readers.
 ///
 /// Two strategies run in priority order:
@@ -1409,10 +1969,15 @@ fn spawn_silence_timer(
                     let now = std::time::Instant::now();
                     sl.last_output_at = now;
                     sl.last_chunk_at = now;
+                    sl.wedge.reset();
                 }
                 continue;
             }
 
+            check_wedged(&state, &silence, &session_id);
+            #[cfg(unix)]
+            sample_foreground_job(&state, &session_id);
+
             // Sole idle path: the silence timer is the only code that transitions
             // busy → idle. The reader thread only does → busy on real output.
             // `should_transition_idle` checks elapsed time vs threshold (500ms shell /
@@ -1457,7 +2022,27 @@ fn spawn_silence_timer(
             // Checked before question detection — a tool error is not a question.
             if let Some(text) = silence.lock().check_tool_error() {
                 let parsed = ParsedEvent::ToolError { matched_text: text };
-                if let Ok(json) = serde_json::to_value(&parsed) {
+                if let Ok(json) = serde_json::to_value(&parsed)
+                    && output_event_enabled(&state, &json)
+                {
+                    #[cfg(feature = "desktop")]
+                    if let Some(app) = state.app_handle.read().as_ref() {
+                        let _ = app.emit(&format!("pty-parsed-{session_id}"), &json);
+                    }
+                    let _ = event_bus.send(crate::state::AppEvent::PtyParsed {
+                        session_id: session_id.clone(),
+                        parsed: json,
+                    });
+                }
+            }
+
+            // Task-complete turn-end: completion marker / prompt return + silence.
+            // After the tool-error check, which drops the candidate when the
+            // turn ended on an error.
+            if let Some(parsed) = take_task_complete(&state, &silence, &session_id) {
+                if let Ok(json) = serde_json::to_value(&parsed)
+                    && output_event_enabled(&state, &json)
+                {
                     #[cfg(feature = "desktop")]
                     if let Some(app) = state.app_handle.read().as_ref() {
                         let _ = app.emit(&format!("pty-parsed-{session_id}"), &json);
@@ -1481,7 +2066,9 @@ fn spawn_silence_timer(
                 .unwrap_or(false);
             if shell_is_idle && let Some(items) = silence.lock().drain_pending_suggest() {
                 let parsed = ParsedEvent::Suggest { items };
-                if let Ok(json) = serde_json::to_value(&parsed) {
+                if let Ok(json) = serde_json::to_value(&parsed)
+                    && output_event_enabled(&state, &json)
+                {
                     #[cfg(feature = "desktop")]
                     if let Some(app) = state.app_handle.read().as_ref() {
                         let _ = app.emit(&format!("pty-parsed-{session_id}"), &json);
@@ -1494,8 +2081,8 @@ fn spawn_silence_timer(
             }
 
             // Check temporal conditions first (shared by both strategies).
-            let is_silent = silence.lock().is_silent();
-            if !is_silent {
+            // A disabled `question` parser skips both strategies.
+            if !silence.lock().question_check_due() {
                 continue;
             }
 
@@ -1573,7 +2160,9 @@ fn spawn_silence_timer(
                 prompt_text: prompt_text.clone(),
                 confident: false,
             };
-            if let Ok(json) = serde_json::to_value(&parsed) {
+            if let Ok(json) = serde_json::to_value(&parsed)
+                && output_event_enabled(&state, &json)
+            {
                 #[cfg(feature = "desktop")]
                 if let Some(app) = state.app_handle.read().as_ref() {
                     let _ = app.emit(&format!("pty-parsed-{session_id}"), &json);
@@ -1666,6 +2255,9 @@ struct ChunkProcessor {
     /// Dedup: last emitted ChoicePrompt signature (title + option keys).
     /// Prevents re-emit on repaint while the dialog stays on screen.
     last_choice_prompt_sig: Option<String>,
+    /// Dedup: last emitted ToolApproval signature (tool + command). Cleared
+    /// once the prompt leaves the screen.
+    last_tool_approval_sig: Option<String>,
     /// Session CWD for resolving relative plan-file paths
     session_cwd: Option<String>,
     /// Plan files awaiting creation on disk (agent announces before writing).
@@ -1728,6 +2320,7 @@ impl ChunkProcessor {
             last_status_task: None,
             last_question_text: None,
             last_choice_prompt_sig: None,
+            last_tool_approval_sig: None,
             session_cwd,
             pending_planfiles: Vec::new(),
             emitted_planfiles: std::collections::HashSet::new(),
@@ -1777,9 +2370,21 @@ impl ChunkProcessor {
         match command {
             'A' => {
                 transition_shell_state(state, session_id, SHELL_IDLE, "idle");
+                // Prompt is back: no foreground job left to blame for an exit.
+                if let Some(mut ss) = state.session_states.get_mut(session_id) {
+                    ss.foreground_pid = None;
+                }
             }
             'C' => {
                 transition_shell_state(state, session_id, SHELL_BUSY, "busy");
+                // Command history is built from reconstructed input; with
+                // `reconstruct_user_input` off no outcome is recorded (the
+                // 'D' branch skips when nothing is pending).
+                if !state.config.read().reconstruct_user_input {
+                    self.pending_command = None;
+                    self.pending_command_started = None;
+                    return;
+                }
                 let cmd = state
                     .input_buffers
                     .get(session_id)
@@ -2269,6 +2874,14 @@ impl ChunkProcessor {
                             };
                             tuic_events.push(ParsedEvent::Intent { text, title });
                         }
+                        "error" => {
+                            let text = payload.trim();
+                            if !text.is_empty() {
+                                tuic_events.push(ParsedEvent::AgentError {
+                                    text: text.to_string(),
+                                });
+                            }
+                        }
                         "block" => {
                             let (action, exit_code) =
                                 if let Some(rest) = payload.strip_prefix("end;") {
@@ -2308,7 +2921,7 @@ impl ChunkProcessor {
             drop(ring_guard);
         }
 
-        // Parse events: OSC 9;4 progress from raw stream, others from clean rows.
+        // Parse events: OSC 9;4 progress and OSC 0/2 title from raw stream, others from clean rows.
         let (in_resize_grace, in_startup_grace) = {
             let sl = silence.lock();
             (sl.is_resize_grace(), sl.is_startup_grace())
@@ -2325,6 +2938,19 @@ impl ChunkProcessor {
         if let Some(evt) = crate::output_parser::parse_osc94(data) {
             events.push(evt);
         }
+        // OSC 0/2 title: only emit when it differs from the stored title, so
+        // shells that re-set the title on every prompt don't flood listeners.
+        if let Some(crate::output_parser::ParsedEvent::Title { title }) =
+            crate::output_parser::parse_osc_title(data)
+        {
+            let changed = state
+                .session_states
+                .get(session_id)
+                .is_none_or(|s| s.terminal_title.as_deref() != Some(title.as_str()));
+            if changed {
+                events.push(crate::output_parser::ParsedEvent::Title { title });
+            }
+        }
         let agent_active_for_parse = state
             .session_states
             .get(session_id)
@@ -2424,6 +3050,16 @@ impl ChunkProcessor {
             }
         }
 
+        // Plan mode — the `⏸ plan mode on` footer is chrome, trimmed from
+        // changed_rows, so read it from the full screen. Agent sessions only:
+        // a shell printing the banner text must not flip plan mode.
+        if agent_active_for_parse
+            && let Some(screen) = &screen_cache
+            && let Some(evt) = self.parser.parse_plan_mode_screen(screen)
+        {
+            events.push(evt);
+        }
+
         // ChoicePrompt detection — numbered confirmation dialogs rendered below
         // the prompt line (edit-confirm, bash-confirm, apply-patch). Runs on
         // every chunk (unlike slash_menu which is gated by slash_mode) because
@@ -2436,6 +3072,16 @@ impl ChunkProcessor {
         {
             events.push(evt);
         }
+        // Tool permission prompts additionally surface what would run
+        // (Bash command, edited file) as a distinct ToolApproval event.
+        if let Some(screen) = &screen_cache {
+            match crate::output_parser::parse_tool_approval(screen) {
+                Some(evt) => events.push(evt),
+                // Prompt answered and gone: asking for the same command again
+                // is a new prompt, not a repaint.
+                None => self.last_tool_approval_sig = None,
+            }
+        }
 
         let regex_found_question = if suppress_notifications {
             false
@@ -2517,6 +3163,34 @@ impl ChunkProcessor {
                 }
                 self.last_choice_prompt_sig = Some(sig);
             }
+            if let ParsedEvent::ToolApproval { tool, command, .. } = event {
+                let sig = format!("{tool}|{command}");
+                if self.last_tool_approval_sig.as_deref() == Some(sig.as_str()) {
+                    continue;
+                }
+                self.last_tool_approval_sig = Some(sig);
+            }
+
+            // Token usage: the parser reports what the footer shows; emit the
+            // session's running totals instead. Repaints of an unchanged footer
+            // add nothing and are dropped.
+            let totals = if let ParsedEvent::TokenUsage {
+                input_tokens,
+                output_tokens,
+                cost_usd,
+            } = event
+            {
+                let Some(totals) = state.sessions.get(session_id).and_then(|s| {
+                    s.lock()
+                        .token_usage
+                        .record(*input_tokens, *output_tokens, *cost_usd)
+                }) else {
+                    continue;
+                };
+                Some(totals)
+            } else {
+                None
+            };
 
             // Resolve relative plan-file paths to absolute using session CWD.
             // If the file doesn't exist yet (agent announces before writing),
@@ -2561,10 +3235,12 @@ impl ChunkProcessor {
                 None
             };
 
-            let emit_event = resolved.as_ref().unwrap_or(event);
+            let emit_event = resolved.as_ref().or(totals.as_ref()).unwrap_or(event);
 
             // Serialize once, reuse for both broadcast and Tauri IPC
-            if let Ok(json) = serde_json::to_value(emit_event) {
+            if let Ok(json) = serde_json::to_value(emit_event)
+                && output_event_enabled(state, &json)
+            {
                 #[cfg(feature = "desktop")]
                 if let Some(app) = state.app_handle.read().as_ref() {
                     let _ = app.emit(&format!("pty-parsed-{session_id}"), &json);
@@ -2625,6 +3301,7 @@ impl ChunkProcessor {
                 chrome_only,
                 suggest_only,
             );
+            sl.wedge.on_output(data.len());
 
             // Tool-error detection: scan visible rows for `Error: Exit code N`
             // emitted by Claude Code / Codex at the end of a failing tool call.
@@ -2642,6 +3319,40 @@ impl ChunkProcessor {
                 // Real output without an error line → agent recovered/continued.
                 sl.clear_tool_error_on_recovery();
             }
+
+            // Task-completion: the last completion marker in the chunk, provided
+            // no real output follows it; or the prompt coming back after
+            // sustained output. Fired by silence_timer after
+            // SILENCE_TASK_COMPLETE_THRESHOLD, so "Done." mid-stream is dropped
+            // as soon as more output arrives.
+            let is_real_row = |text: &str| {
+                !(is_chrome_row(text)
+                    || text.trim().is_empty()
+                    || crate::chrome::is_separator_line(text)
+                    || crate::chrome::is_prompt_line(text))
+            };
+            let marker = changed_rows.iter().enumerate().rev().find_map(|(i, r)| {
+                self.parser
+                    .task_complete_line(&r.text)
+                    .map(|summary| (i, summary))
+            });
+            match marker {
+                Some((i, summary))
+                    if !changed_rows[i + 1..].iter().any(|r| is_real_row(&r.text)) =>
+                {
+                    sl.mark_task_complete_candidate(summary);
+                }
+                _ if no_real_output
+                    && sl.has_sustained_output()
+                    && changed_rows
+                        .iter()
+                        .any(|r| crate::chrome::is_prompt_line(&r.text)) =>
+                {
+                    sl.mark_task_complete_candidate("Prompt returned".to_string());
+                }
+                _ if !chrome_only => sl.clear_task_complete_on_output(),
+                _ => {}
+            }
         }
 
         // Stamp last_output_ms for real output and for active spinner repaints.
@@ -2803,6 +3514,7 @@ pub(crate) fn cleanup_session(session_id: &str, state: &AppState) {
             .active_sessions
             .fetch_sub(1, Ordering::Relaxed);
     }
+    state.record_session_closed(session_id);
     state.output_buffers.remove(session_id);
     state.vt_log_buffers.remove(session_id);
     #[cfg(feature = "desktop")]
@@ -2818,7 +3530,9 @@ pub(crate) fn cleanup_session(session_id: &str, state: &AppState) {
     state.last_output_ms.remove(session_id);
     state.last_prompts.remove(session_id);
     state.terminal_rows.remove(session_id);
+    state.resize_debouncer.clear_session(session_id);
     state.exit_codes.remove(session_id);
+    state.exit_reasons.remove(session_id);
     state.term_aliases.remove(session_id);
 }
 
@@ -2836,6 +3550,7 @@ fn tombstone_transient_cleanup(session_id: &str, state: &AppState) {
         .or_insert_with(|| AtomicU64::new(0))
         .store(now_ms, Ordering::Relaxed);
     state.ws_clients.remove(session_id);
+    crate::recording::finish_for_session(state, session_id);
     #[cfg(feature = "desktop")]
     state.grid_channels.remove(session_id);
     state.grid_watch.remove(session_id);
@@ -2847,6 +3562,7 @@ fn tombstone_transient_cleanup(session_id: &str, state: &AppState) {
     state.shell_states.remove(session_id);
     state.last_prompts.remove(session_id);
     state.terminal_rows.remove(session_id);
+    state.resize_debouncer.clear_session(session_id);
     // Swarm maps — inserted at spawn/register time, must be cleaned on exit.
     state.shell_state_since_ms.remove(session_id);
     #[cfg(unix)]
@@ -2897,30 +3613,23 @@ fn push_state_change_to_parent(state: &AppState, session_id: &str, payload: serd
 /// alive so MCP consumers can read final output + exit status post-mortem.
 /// Tombstones are reaped by `spawn_tombstone_sweeper` after `TOMBSTONE_TTL_MS`.
 pub(crate) fn mark_session_exited(session_id: &str, state: &AppState) {
-    // Capture exit code before dropping the session entry.
-    // portable_pty::ExitStatus carries both exit_code() and signal().
-    // Signal-killed processes get 128+signum (shell convention) so the
-    // caller can distinguish SIGKILL (137) from normal exit(1).
-    if let Some(entry) = state.sessions.get(session_id)
-        && let Ok(Some(status)) = entry.value().lock()._child.try_wait()
-    {
-        let code = if let Some(sig) = status.signal() {
-            let signum = parse_signal_number(sig);
-            128 + signum
-        } else {
-            status.exit_code() as i32
-        };
-        state.exit_codes.insert(session_id.to_string(), code);
-    }
+    // Capture exit code + classification before dropping the session entry
+    // (already done by the reader's exit path unless it panicked).
+    capture_exit_status(session_id, state);
     if state.sessions.remove(session_id).is_some() {
         state
             .metrics
             .active_sessions
             .fetch_sub(1, Ordering::Relaxed);
     }
+    state.record_session_closed(session_id);
 
     // Notify orchestrator (if any) that this agent has exited.
     let exit_code = state.exit_codes.get(session_id).map(|e| *e.value());
+    let exit_info = state
+        .exit_reasons
+        .get(session_id)
+        .map(|e| e.value().clone());
     push_state_change_to_parent(
         state,
         session_id,
@@ -2929,6 +3638,7 @@ pub(crate) fn mark_session_exited(session_id: &str, state: &AppState) {
             "state": "exited",
             "session_id": session_id,
             "exit_code": exit_code,
+            "exit_info": exit_info,
         }),
     );
 
@@ -2978,6 +3688,7 @@ pub(crate) fn spawn_tombstone_sweeper(state: Arc<AppState>) {
                 state.vt_log_buffers.remove(&id);
                 state.last_output_ms.remove(&id);
                 state.exit_codes.remove(&id);
+                state.exit_reasons.remove(&id);
                 tracing::debug!(source = "pty", session_id = %id, "Tombstone reaped");
             }
         }
@@ -3303,7 +4014,7 @@ fn grid_send_min_interval_ms(input_recent: bool, dirty_run: u32) -> u64 {
 /// keeping the WebView/browser main thread free for keystroke dispatch + echo.
 /// Called from every interactive input entry point (desktop `write_pty` +
 /// HTTP/PWA `write_to_session`).
-pub(crate) fn stamp_input_ms(state: &AppState, session_id: &str) {
+pub(crate) fn stamp_input_ms(state: &AppState, session_id: &str, input: &str) {
     let now_ms = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
@@ -3313,35 +4024,181 @@ pub(crate) fn stamp_input_ms(state: &AppState, session_id: &str) {
         .entry(session_id.to_string())
         .or_insert_with(|| std::sync::atomic::AtomicU64::new(0))
         .store(now_ms, std::sync::atomic::Ordering::Relaxed);
+    // Arm wedge detection only when enabled — keeps the keystroke path lock-free otherwise.
+    if state.config.read().pty_wedge_timeout_secs > 0
+        && let Some(ss) = state.silence_states.get(session_id)
+    {
+        ss.lock().wedge.on_input(std::time::Instant::now(), input);
+    }
+}
+
+/// Translate special key names to terminal escape sequences
+pub(crate) fn translate_special_key(key: &str) -> Option<&'static str> {
+    match key {
+        "enter" | "return" => Some("\r"),
+        "tab" => Some("\t"),
+        "escape" | "esc" => Some("\x1b"),
+        "backspace" => Some("\x7f"),
+        "delete" => Some("\x1b[3~"),
+        "up" => Some("\x1b[A"),
+        "down" => Some("\x1b[B"),
+        "right" => Some("\x1b[C"),
+        "left" => Some("\x1b[D"),
+        "home" => Some("\x1b[H"),
+        "end" => Some("\x1b[F"),
+        "ctrl+c" => Some("\x03"),
+        "ctrl+d" => Some("\x04"),
+        "ctrl+z" => Some("\x1a"),
+        "ctrl+l" => Some("\x0c"),
+        "ctrl+a" => Some("\x01"),
+        "ctrl+e" => Some("\x05"),
+        "ctrl+k" => Some("\x0b"),
+        "ctrl+u" => Some("\x15"),
+        "ctrl+w" => Some("\x17"),
+        "ctrl+r" => Some("\x12"),
+        "ctrl+p" => Some("\x10"),
+        "ctrl+n" => Some("\x0e"),
+        _ => None,
+    }
 }
 
-pub(crate) fn spawn_reader_thread(
-    mut reader: Box<dyn Read + Send>,
-    paused: Arc<AtomicBool>,
-    session_id: String,
-    state: Arc<AppState>,
-    tuic_session: Option<String>,
-) {
-    let silence = Arc::new(Mutex::new(SilenceState::new()));
-    let running = Arc::new(AtomicBool::new(true));
-
-    state
-        .silence_states
-        .insert(session_id.clone(), silence.clone());
-    state.shell_states.insert(
-        session_id.clone(),
-        std::sync::atomic::AtomicU8::new(SHELL_NULL),
-    );
+/// Per-session outcome of `broadcast_input`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub(crate) struct BroadcastResult {
+    pub session_id: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
 
-    spawn_silence_timer(
-        silence.clone(),
-        running.clone(),
-        session_id.clone(),
-        state.clone(),
-    );
+/// Write the same text and/or special key to several sessions.
+///
+/// The key is validated up front (an unknown key fails the whole call); a
+/// missing session or failed write only fails that session's entry. Text and
+/// key go out as separate writes, as for MCP `session input`: raw-mode agents
+/// can miss an Enter concatenated onto the text.
+pub(crate) fn broadcast_input_core(
+    state: &AppState,
+    session_ids: &[String],
+    input: Option<&str>,
+    special_key: Option<&str>,
+) -> Result<Vec<BroadcastResult>, String> {
+    let key_seq = match special_key {
+        Some(key) => {
+            Some(translate_special_key(key).ok_or_else(|| format!("Unknown special key: {key}"))?)
+        }
+        None => None,
+    };
+    let text = input.unwrap_or("");
+    if text.is_empty() && key_seq.is_none() {
+        return Err("Broadcast requires 'input' and/or 'special_key'".to_string());
+    }
+    if session_ids.is_empty() {
+        return Err("Broadcast requires at least one session id".to_string());
+    }
 
-    // Frame ticker: decouples PTY read() from frame serialization.
-    // Reader sets dirty flag; ticker serializes+sends at fixed interval.
+    let mut seen = std::collections::HashSet::new();
+    let results = session_ids
+        .iter()
+        .filter(|id| seen.insert(id.as_str()))
+        .map(|session_id| {
+            let outcome = (|| {
+                let entry = state
+                    .sessions
+                    .get(session_id)
+                    .ok_or_else(|| "Session not found".to_string())?;
+                let mut session = entry.lock();
+                for chunk in [text, key_seq.unwrap_or("")] {
+                    if chunk.is_empty() {
+                        continue;
+                    }
+                    session
+                        .writer
+                        .write_all(chunk.as_bytes())
+                        .map_err(|e| format!("Write failed: {e}"))?;
+                    if let Err(e) = session.writer.flush() {
+                        tracing::warn!(session_id = %session_id, "PTY flush failed: {e}");
+                    }
+                }
+                Ok::<(), String>(())
+            })();
+            if outcome.is_ok() {
+                for chunk in [text, key_seq.unwrap_or("")] {
+                    if !chunk.is_empty() {
+                        stamp_input_ms(state, session_id, chunk);
+                    }
+                }
+            }
+            BroadcastResult {
+                session_id: session_id.clone(),
+                ok: outcome.is_ok(),
+                error: outcome.err(),
+            }
+        })
+        .collect();
+    Ok(results)
+}
+
+/// Type the same input into several sessions at once.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn broadcast_input(
+    state: State<'_, Arc<AppState>>,
+    session_ids: Vec<String>,
+    input: Option<String>,
+    special_key: Option<String>,
+) -> Result<Vec<BroadcastResult>, String> {
+    let state = Arc::clone(&state);
+    tokio::task::spawn_blocking(move || {
+        broadcast_input_core(
+            &state,
+            &session_ids,
+            input.as_deref(),
+            special_key.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking error: {e}"))?
+}
+
+pub(crate) fn spawn_reader_thread(
+    mut reader: Box<dyn Read + Send>,
+    paused: Arc<AtomicBool>,
+    session_id: String,
+    state: Arc<AppState>,
+    tuic_session: Option<String>,
+) {
+    let (custom_patterns, disabled_parsers) = {
+        let config = state.config.read();
+        (
+            crate::output_parser::compile_custom_patterns(&config.custom_output_patterns),
+            crate::output_parser::DisabledParsers::from_names(
+                config.disabled_parsers.iter().map(String::as_str),
+            ),
+        )
+    };
+    let silence = Arc::new(Mutex::new(
+        SilenceState::new().with_disabled_parsers(disabled_parsers),
+    ));
+    let running = Arc::new(AtomicBool::new(true));
+
+    state
+        .silence_states
+        .insert(session_id.clone(), silence.clone());
+    state.shell_states.insert(
+        session_id.clone(),
+        std::sync::atomic::AtomicU8::new(SHELL_NULL),
+    );
+
+    spawn_silence_timer(
+        silence.clone(),
+        running.clone(),
+        session_id.clone(),
+        state.clone(),
+    );
+
+    // Frame ticker: decouples PTY read() from frame serialization.
+    // Reader sets dirty flag; ticker serializes+sends at fixed interval.
     // Coalesces rapid writes (spinner erase+rewrite) into a single frame.
     let frame_dirty = Arc::new(AtomicBool::new(false));
     state
@@ -3507,6 +4364,10 @@ pub(crate) fn spawn_reader_thread(
                 .get(&session_id)
                 .and_then(|s| s.lock().cwd.clone());
             let mut processor = ChunkProcessor::new(session_cwd, tuic_session);
+            if !custom_patterns.is_empty() || !disabled_parsers.is_empty() {
+                processor.parser = OutputParser::with_custom_patterns(custom_patterns)
+                    .with_disabled_parsers(disabled_parsers);
+            }
             // pty-output is emitted only for frontend activity detection (the canvas
             // renders from grid frames and discards the text). Emitting it per-chunk
             // flooded the WebView main thread under output storms (`yes`), starving
@@ -3526,8 +4387,10 @@ pub(crate) fn spawn_reader_thread(
                 match reader.read(&mut buf) {
                     Ok(0) => break,
                     Ok(n) => {
+                        let read_at = std::time::Instant::now();
                         state.metrics.bytes_emitted.fetch_add(n, Ordering::Relaxed);
                         let utf8_data = utf8_buf.push(&buf[..n]);
+                        crate::recording::record_output(&state, &session_id, read_at, &utf8_data);
                         let esc_data = esc_buf.push(&utf8_data);
                         let (kitty_clean, kitty_actions) = strip_kitty_sequences(&esc_data);
                         if kitty_clean.contains("1049l") && !kitty_clean.contains("\x1b[?1049l") {
@@ -3634,6 +4497,11 @@ pub(crate) fn spawn_reader_thread(
                 );
             }
 
+            capture_exit_status(&session_id, &state);
+            let exit_info = state
+                .exit_reasons
+                .get(&session_id)
+                .map(|e| e.value().clone());
             let _ = state.event_bus.send(crate::state::AppEvent::PtyExit {
                 session_id: session_id.clone(),
             });
@@ -3641,7 +4509,7 @@ pub(crate) fn spawn_reader_thread(
             if let Some(app) = state.app_handle.read().as_ref() {
                 let _ = app.emit(
                     &format!("pty-exit-{session_id}"),
-                    serde_json::json!({ "session_id": session_id }),
+                    serde_json::json!({ "session_id": session_id, "exit_info": exit_info }),
                 );
             }
             tracing::info!(source = "pty", session_id = %session_id, "Session closed: process exited");
@@ -3661,6 +4529,7 @@ pub(crate) fn spawn_reader_thread(
                         "session_id": session_id,
                         "reason": "process_exit",
                         "agent_type": agent_type,
+                        "exit_info": exit_info,
                     }),
                 );
             }
@@ -3681,16 +4550,54 @@ pub(crate) fn spawn_reader_thread(
     });
 }
 
+/// Backoff between PTY spawn retries (see `spawn_with_retry`).
+const SPAWN_RETRY_BASE_MS: f64 = 100.0;
+const SPAWN_RETRY_MAX_MS: f64 = 2000.0;
+const SPAWN_RETRY_MULTIPLIER: f64 = 2.0;
+
+/// Run `spawn`, retrying up to `retries` more times with exponential backoff
+/// while it fails with a transient error. Permanent errors (missing shell,
+/// bad cwd) return immediately. `on_retry(attempt, delay_ms, error)` runs
+/// before each backoff sleep.
+pub(crate) async fn spawn_with_retry<T>(
+    retries: u32,
+    mut spawn: impl FnMut() -> Result<T, String>,
+    mut on_retry: impl FnMut(u32, u64, &str),
+) -> Result<T, String> {
+    let mut attempt = 0;
+    loop {
+        match spawn() {
+            Ok(spawned) => return Ok(spawned),
+            Err(e)
+                if attempt < retries
+                    && crate::error_classification::is_transient_spawn_error(&e) =>
+            {
+                let delay_ms = crate::error_classification::calculate_backoff_delay(
+                    attempt,
+                    SPAWN_RETRY_BASE_MS,
+                    SPAWN_RETRY_MAX_MS,
+                    SPAWN_RETRY_MULTIPLIER,
+                ) as u64;
+                attempt += 1;
+                on_retry(attempt, delay_ms, &e);
+                tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 /// Create a new PTY session with optional worktree
 #[cfg(feature = "desktop")]
 #[tauri::command]
 pub(crate) async fn create_pty(
-    _app: AppHandle,
+    app: AppHandle,
     state: State<'_, Arc<AppState>>,
     config: PtyConfig,
 ) -> Result<String, String> {
+    ensure_session_slot(&state)?;
     let session_id = Uuid::new_v4().to_string();
-    let pty_system = native_pty_system();
+    crate::env_file::validate_spawn_env(&config.env)?;
 
     let shell = resolve_shell(config.shell);
 
@@ -3698,30 +4605,17 @@ pub(crate) async fn create_pty(
     let rows = config.rows.max(24);
     let cols = config.cols.max(80);
 
-    // Retry PTY spawn up to 3 times with increasing delay (Story 059)
-    let max_retries = 3;
-    let mut last_err = String::new();
-    let mut pair_and_child = None;
-
-    for attempt in 0..max_retries {
-        let pair = match pty_system.openpty(PtySize {
-            rows,
-            cols,
-            pixel_width: 0,
-            pixel_height: 0,
-        }) {
-            Ok(p) => p,
-            Err(e) => {
-                last_err = format!("Failed to open PTY (attempt {}): {}", attempt + 1, e);
-                if attempt < max_retries - 1 {
-                    tokio::time::sleep(std::time::Duration::from_millis(
-                        100 * (attempt as u64 + 1),
-                    ))
-                    .await;
-                }
-                continue;
-            }
-        };
+    // Retry transient PTY spawn failures with backoff (Story 059)
+    let retries = state.config.read().pty_spawn_retries;
+    let spawn = || -> Result<_, String> {
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows,
+                cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| format!("Failed to open PTY: {e}"))?;
 
         let mut cmd = build_shell_command(&shell);
 
@@ -3752,24 +4646,40 @@ pub(crate) async fn create_pty(
             cmd.env(key, value);
         }
 
-        match pair.slave.spawn_command(cmd) {
-            Ok(child) => {
-                pair_and_child = Some((pair, child));
-                break;
-            }
-            Err(e) => {
-                last_err = format!("Failed to spawn shell (attempt {}): {}", attempt + 1, e);
-                if attempt < max_retries - 1 {
-                    tokio::time::sleep(std::time::Duration::from_millis(
-                        100 * (attempt as u64 + 1),
-                    ))
-                    .await;
-                }
-            }
-        }
-    }
-
-    let (pair, child) = pair_and_child.ok_or(last_err)?;
+        let child = pair
+            .slave
+            .spawn_command(cmd)
+            .map_err(|e| format!("Failed to spawn shell: {e}"))?;
+        Ok((pair, child))
+    };
+    let on_retry = |attempt: u32, delay_ms: u64, error: &str| {
+        tracing::warn!(
+            source = "pty",
+            attempt,
+            delay_ms,
+            "Retrying PTY spawn: {error}"
+        );
+        let _ = app.emit(
+            "session-spawn-retry",
+            serde_json::json!({
+                "attempt": attempt,
+                "max_retries": retries,
+                "delay_ms": delay_ms,
+                "error": error,
+                "tuic_session": config.tuic_session,
+            }),
+        );
+        let _ = state
+            .event_bus
+            .send(crate::state::AppEvent::SessionSpawnRetry {
+                attempt,
+                max_retries: retries,
+                delay_ms,
+                error: error.to_string(),
+                tuic_session: config.tuic_session.clone(),
+            });
+    };
+    let (pair, child) = spawn_with_retry(retries, spawn, on_retry).await?;
     lower_pty_child_priority(child.process_id());
 
     let tuic_session = config.tuic_session.clone();
@@ -3784,6 +4694,8 @@ pub(crate) async fn create_pty(
         .try_clone_reader()
         .map_err(|e| format!("Failed to get PTY reader: {e}"))?;
 
+    crate::spawn_ledger::record_spawn(&state.data_dir, child.process_id(), &session_id);
+
     // Store session (master handle kept for resize support)
     let paused = Arc::new(AtomicBool::new(false));
     state.sessions.insert(
@@ -3797,9 +4709,11 @@ pub(crate) async fn create_pty(
             cwd: config.cwd,
             display_name: None,
             shell: shell.clone(),
+            token_usage: Default::default(),
         }),
     );
     state.assign_term_alias(&session_id);
+    state.record_session_opened(&session_id, None);
     state.metrics.total_spawned.fetch_add(1, Ordering::Relaxed);
     state
         .metrics
@@ -3854,6 +4768,7 @@ pub(crate) async fn spawn_session_for_agent(
     cwd: Option<String>,
     display_name: Option<String>,
 ) -> Result<String, String> {
+    ensure_session_slot(state)?;
     let session_id = Uuid::new_v4().to_string();
     let pty_system = native_pty_system();
     let rows: u16 = 24;
@@ -3894,6 +4809,8 @@ pub(crate) async fn spawn_session_for_agent(
         .try_clone_reader()
         .map_err(|e| format!("Failed to get PTY reader: {e}"))?;
 
+    crate::spawn_ledger::record_spawn(&state.data_dir, child.process_id(), &session_id);
+
     let paused = Arc::new(AtomicBool::new(false));
     state.sessions.insert(
         session_id.clone(),
@@ -3906,9 +4823,11 @@ pub(crate) async fn spawn_session_for_agent(
             cwd,
             display_name,
             shell: shell.clone(),
+            token_usage: Default::default(),
         }),
     );
     state.assign_term_alias(&session_id);
+    state.record_session_opened(&session_id, None);
     state.metrics.total_spawned.fetch_add(1, Ordering::Relaxed);
     state
         .metrics
@@ -3972,8 +4891,11 @@ pub(crate) async fn create_pty_with_worktree(
     pty_config: PtyConfig,
     worktree_config: WorktreeConfig,
 ) -> Result<WorktreeResult, String> {
+    // Before the worktree is created, so a refused spawn leaves nothing behind.
+    ensure_session_slot(&state)?;
     let pty_rows = pty_config.rows.max(24);
     let _pty_cols = pty_config.cols.max(80);
+    crate::env_file::validate_spawn_env(&pty_config.env)?;
     // Create the worktree first
     let worktrees_dir = crate::worktree::resolve_worktree_dir_for_repo(
         std::path::Path::new(&worktree_config.base_repo),
@@ -3995,6 +4917,10 @@ pub(crate) async fn create_pty_with_worktree(
 
     // Wrap PTY creation so we can clean up the worktree on failure
     let pty_result = (|| -> Result<_, String> {
+        // The env file is uncommitted, so it lives in the base repo, not the
+        // fresh worktree.
+        let env_vars = crate::env_file::load_repo_env_for_cwd(&worktree_config.base_repo)?;
+
         let session_id = Uuid::new_v4().to_string();
         let pty_system = native_pty_system();
 
@@ -4019,6 +4945,9 @@ pub(crate) async fn create_pty_with_worktree(
         // Inject OSC 133 shell integration (command block markers)
         crate::shell_integration::inject(&state.data_dir, &shell, &mut cmd);
 
+        // Repo env file first so explicit env flags below take precedence
+        crate::env_file::apply_env_vars(&mut cmd, &env_vars);
+
         // Inject env flags (feature flags configured in Settings → Agents)
         for (key, value) in &pty_config.env {
             cmd.env(key, value);
@@ -4057,6 +4986,8 @@ pub(crate) async fn create_pty_with_worktree(
     let branch = worktree.branch.clone();
     let worktree_cwd = Some(worktree.path.to_string_lossy().to_string());
 
+    crate::spawn_ledger::record_spawn(&state.data_dir, child.process_id(), &session_id);
+
     // Store session with worktree info (master handle kept for resize support)
     let paused = Arc::new(AtomicBool::new(false));
     state.sessions.insert(
@@ -4070,9 +5001,11 @@ pub(crate) async fn create_pty_with_worktree(
             cwd: worktree_cwd,
             display_name: None,
             shell,
+            token_usage: Default::default(),
         }),
     );
     state.assign_term_alias(&session_id);
+    state.record_session_opened(&session_id, None);
     state.metrics.total_spawned.fetch_add(1, Ordering::Relaxed);
     state
         .metrics
@@ -4206,7 +5139,7 @@ pub(crate) async fn write_pty(
         // Stamp last-input time so the grid ticker can throttle frame sends while
         // the user types under CPU saturation (keeps the WebView thread free for
         // keystroke dispatch + echo).
-        stamp_input_ms(&state, &session_id);
+        stamp_input_ms(&state, &session_id, &data);
 
         // Feed input through the line buffer to reconstruct user-typed lines
         let input_entry = state
@@ -4214,6 +5147,7 @@ pub(crate) async fn write_pty(
             .entry(session_id.clone())
             .or_insert_with(|| parking_lot::Mutex::new(InputLineBuffer::new()));
         let mut buf = input_entry.lock();
+        buf.set_reconstruct(state.config.read().reconstruct_user_input);
         let actions = buf.feed(&data);
         let mut line_submitted = false;
         for action in actions {
@@ -4226,6 +5160,7 @@ pub(crate) async fn write_pty(
                         if word_count >= 10 {
                             state.last_prompts.insert(session_id.clone(), content.clone());
                         }
+                        let slash_command = agent_slash_command(&state, &session_id, &content);
                         // Keystroke-reconstructed: no grid context, so no prompt
                         // row (line = -1). The scrollbar marker uses the OSC 7770
                         // state=busy path's absolute line instead.
@@ -4242,6 +5177,16 @@ pub(crate) async fn write_pty(
                             &format!("pty-parsed-{session_id}"),
                             &parsed,
                         );
+                        if let Some(slash) = slash_command
+                            && let Ok(json) = serde_json::to_value(&slash)
+                            && output_event_enabled(&state, &json)
+                        {
+                            let _ = state.event_bus.send(crate::state::AppEvent::PtyParsed {
+                                session_id: session_id.clone(),
+                                parsed: json,
+                            });
+                            let _ = app.emit(&format!("pty-parsed-{session_id}"), &slash);
+                        }
 
                         // Suppress silence-based question detection for user-typed lines.
                         // The PTY will echo this input back — without suppression, a line
@@ -4251,6 +5196,15 @@ pub(crate) async fn write_pty(
                         }
                     }
                 }
+                // `reconstruct_user_input` off: no UserInput / slash-command /
+                // last-prompt capture, but the echo must still not be read as
+                // an agent question.
+                InputAction::Submitted { empty } => {
+                    line_submitted = true;
+                    if !empty && let Some(ss) = state.silence_states.get(&session_id) {
+                        ss.lock().suppress_user_input();
+                    }
+                }
                 InputAction::Interrupt => {
                     line_submitted = true;
                 }
@@ -4267,6 +5221,7 @@ pub(crate) async fn write_pty(
             let mut sl = ss.lock();
             sl.reset_tool_error_memory();
             sl.reset_suggest_memory();
+            sl.reset_task_complete_memory();
         }
 
         // Track slash command mode: true when the input buffer starts with /
@@ -4349,8 +5304,23 @@ pub(crate) fn get_session_shell_family(
         .map(|entry| classify_shell(&entry.lock().shell))
 }
 
+/// Return where a PTY session runs (local, container, or remote host),
+/// classified from its spawn command.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn get_session_context(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+) -> Option<SessionContext> {
+    state
+        .sessions
+        .get(&session_id)
+        .map(|entry| classify_session_context(&entry.lock().shell))
+}
+
 /// Enable or disable VT100 diff rendering for a PTY session.
-/// Resize a PTY session
+/// Resize a PTY session. Rapid resizes (window drags) are coalesced per session
+/// and only the last size is applied once `resize_debounce_ms` passes quietly.
 #[cfg(feature = "desktop")]
 #[tauri::command]
 pub(crate) fn resize_pty(
@@ -4362,9 +5332,23 @@ pub(crate) fn resize_pty(
     if rows == 0 || cols == 0 {
         return Err("Invalid dimensions: rows and cols must be > 0".to_string());
     }
+    if !state.sessions.contains_key(&session_id) {
+        return Err(format!("Session not found: {session_id}"));
+    }
+    crate::resize_debounce::debounce_resize(&state, &session_id, rows, cols, apply_pty_resize)
+}
+
+/// Apply a (debounced) resize: PTY, VT grid reflow, and a fresh frame.
+#[cfg(feature = "desktop")]
+fn apply_pty_resize(
+    state: &AppState,
+    session_id: &str,
+    rows: u16,
+    cols: u16,
+) -> Result<(), String> {
     let entry = state
         .sessions
-        .get(&session_id)
+        .get(session_id)
         .ok_or_else(|| format!("Session not found: {session_id}"))?;
     let session = entry.lock();
     session
@@ -4380,7 +5364,7 @@ pub(crate) fn resize_pty(
     // Pass shell_state so reflow can be smarter: idle → All, busy → HistoryOnly.
     let shell_state = state
         .shell_states
-        .get(&session_id)
+        .get(session_id)
         .map(|a| a.load(std::sync::atomic::Ordering::Relaxed))
         .unwrap_or(SHELL_NULL);
     // Resize the grid and capture a fresh full frame. `resize_with_mode` marks the
@@ -4389,24 +5373,24 @@ pub(crate) fn resize_pty(
     // so a resize/zoom over idle or static content (e.g. an agent's printed output)
     // would otherwise leave the viewport blank until a scroll forces
     // `terminal_request_frame`. Emitting here makes zoom repaint immediately.
-    let resize_frame = state.vt_log_buffers.get(&session_id).map(|vt_log| {
+    let resize_frame = state.vt_log_buffers.get(session_id).map(|vt_log| {
         let mut vt = vt_log.lock();
         vt.resize_with_shell_state(rows, cols, shell_state);
         vt.serialize_dirty_rows()
     });
     // Update terminal rows for cursor-up clamping in the reader thread.
-    if let Some(r) = state.terminal_rows.get(&session_id) {
+    if let Some(r) = state.terminal_rows.get(session_id) {
         r.store(rows, Ordering::Relaxed);
     }
     // Mark resize in silence state so the reader thread suppresses re-parsed events
     // from the shell's prompt redraw triggered by SIGWINCH.
-    if let Some(ss) = state.silence_states.get(&session_id) {
+    if let Some(ss) = state.silence_states.get(session_id) {
         ss.lock().on_resize();
     }
     // Flush the post-resize frame so the viewport repaints without waiting for the
     // next PTY data event (fixes blank screen after zoom on static content).
     if let Some(frame) = resize_frame {
-        send_grid_frame(&state, &session_id, frame);
+        send_grid_frame(state, session_id, frame);
     }
     Ok(())
 }
@@ -4652,93 +5636,257 @@ pub(crate) fn get_kitty_flags(state: State<'_, Arc<AppState>>, session_id: Strin
 /// process group) is reaped separately by the caller's `_child.kill()`.
 #[cfg(unix)]
 fn kill_foreground_process_group(session: &PtySession, session_id: &str) {
+    signal_foreground_process_group(session, session_id, libc::SIGKILL);
+}
+
+/// Send `sig` to the PTY's foreground process group (see
+/// [`kill_foreground_process_group`] for why the group, not just the shell).
+#[cfg(unix)]
+fn signal_foreground_process_group(session: &PtySession, session_id: &str, sig: libc::c_int) {
     let Some(pgid) = session.master.process_group_leader() else {
         return;
     };
-    // Never signal pid <= 1 or our own group — that would take down TUIC itself.
-    if pgid <= 1 || pgid == unsafe { libc::getpgid(0) } {
-        tracing::warn!(session_id, pgid, "Refusing killpg on unsafe pgid");
-        return;
-    }
-    if unsafe { libc::kill(-pgid, libc::SIGKILL) } != 0 {
-        let err = std::io::Error::last_os_error();
+    if let Err(err) = killpg_checked(pgid, sig) {
         // ESRCH just means the group already exited — not worth a warning.
         if err.raw_os_error() != Some(libc::ESRCH) {
-            tracing::warn!(session_id, pgid, "killpg(SIGKILL) failed: {err}");
+            tracing::warn!(session_id, pgid, sig, "killpg failed: {err}");
+        }
+    }
+}
+
+/// `kill(-pgid, sig)`, refusing pid <= 1 and our own group — signalling either
+/// would take down TUIC itself.
+#[cfg(unix)]
+fn killpg_checked(pgid: libc::pid_t, sig: libc::c_int) -> std::io::Result<()> {
+    if pgid <= 1 || pgid == unsafe { libc::getpgid(0) } {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("unsafe pgid {pgid}, refusing to signal it"),
+        ));
+    }
+    if unsafe { libc::kill(-pgid, sig) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Signals the `signal` session action may send to a foreground process group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ForegroundSignal {
+    Int,
+    Term,
+    Kill,
+}
+
+impl ForegroundSignal {
+    /// Parse `SIGINT` / `SIGTERM` / `SIGKILL`; case-insensitive, `SIG` optional.
+    pub(crate) fn parse(name: &str) -> Result<Self, String> {
+        let upper = name.trim().to_ascii_uppercase();
+        match upper.strip_prefix("SIG").unwrap_or(&upper) {
+            "INT" => Ok(Self::Int),
+            "TERM" => Ok(Self::Term),
+            "KILL" => Ok(Self::Kill),
+            _ => Err(format!(
+                "Unsupported signal '{name}'. Allowed: SIGINT, SIGTERM, SIGKILL"
+            )),
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Int => "SIGINT",
+            Self::Term => "SIGTERM",
+            Self::Kill => "SIGKILL",
+        }
+    }
+}
+
+/// Send `signal` to the session's foreground process group — the runaway
+/// command — without closing the session. Returns the signalled pgid.
+///
+/// Refused when the session's own root process (the shell, or the agent it was
+/// spawned as) is in the foreground: signalling it would end the session,
+/// which is what `close` / `kill` are for.
+#[cfg(unix)]
+pub(crate) fn signal_foreground(
+    state: &AppState,
+    session_id: &str,
+    signal: ForegroundSignal,
+) -> Result<u32, String> {
+    let entry = state
+        .sessions
+        .get(session_id)
+        .ok_or_else(|| format!("Session not found: {session_id}"))?;
+    let session = entry.value().lock();
+    let pgid = session
+        .master
+        .process_group_leader()
+        .ok_or_else(|| "No foreground process group".to_string())?;
+    if session._child.process_id() == Some(pgid as u32) {
+        return Err(
+            "Nothing is running in the foreground; use close or kill to end the session"
+                .to_string(),
+        );
+    }
+    let sig = match signal {
+        ForegroundSignal::Int => libc::SIGINT,
+        ForegroundSignal::Term => libc::SIGTERM,
+        ForegroundSignal::Kill => libc::SIGKILL,
+    };
+    killpg_checked(pgid, sig).map_err(|e| format!("{} failed: {e}", signal.name()))?;
+    tracing::info!(
+        source = "session",
+        session_id,
+        pgid,
+        signal = signal.name(),
+        "Signalled foreground process group"
+    );
+    Ok(pgid as u32)
+}
+
+#[cfg(windows)]
+pub(crate) fn signal_foreground(
+    _state: &AppState,
+    _session_id: &str,
+    signal: ForegroundSignal,
+) -> Result<u32, String> {
+    Err(format!(
+        "Sending {} to a foreground process is not supported on Windows",
+        signal.name()
+    ))
+}
+
+/// Grace period after Ctrl-C before escalating to SIGTERM.
+const CLOSE_INTERRUPT_GRACE: std::time::Duration = std::time::Duration::from_millis(100);
+/// Grace period after SIGTERM before escalating to SIGKILL.
+const CLOSE_TERM_GRACE: std::time::Duration = std::time::Duration::from_millis(400);
+/// How long to wait for the kernel to confirm a SIGKILLed child is gone.
+const CLOSE_KILL_CONFIRM: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Final outcome of closing a PTY session (`close_pty`).
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub(crate) struct CloseOutcome {
+    /// The child was confirmed exited before `close_pty` returned.
+    pub(crate) terminated: bool,
+    /// Escalation reached SIGKILL (the child ignored Ctrl-C and SIGTERM).
+    pub(crate) killed: bool,
+    /// Exit code; signal deaths are reported as 128 + signum.
+    pub(crate) exit_code: Option<i32>,
+}
+
+/// Poll the child until it exits or `timeout` elapses. Returns the exit code
+/// (signal deaths as 128 + signum, like `mark_session_exited`).
+fn wait_child_exit(session: &mut PtySession, timeout: std::time::Duration) -> Option<i32> {
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        match session._child.try_wait() {
+            Ok(Some(status)) => return Some(exit_status_code(&status)),
+            Ok(None) if std::time::Instant::now() < deadline => {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Escalate Ctrl-C → SIGTERM → SIGKILL until the child is confirmed gone.
+///
+/// Each step targets the foreground process group as well as the shell, so an
+/// agent running under the shell is signalled too.
+fn terminate_session_child(session: &mut PtySession, session_id: &str) -> CloseOutcome {
+    // Send Ctrl-C (0x03) to give the process a chance to clean up
+    let _ = session.writer.write_all(&[0x03]);
+    let _ = session.writer.flush();
+    if let Some(code) = wait_child_exit(session, CLOSE_INTERRUPT_GRACE) {
+        return CloseOutcome {
+            terminated: true,
+            killed: false,
+            exit_code: Some(code),
+        };
+    }
+
+    #[cfg(unix)]
+    {
+        signal_foreground_process_group(session, session_id, libc::SIGTERM);
+        if let Some(pid) = session._child.process_id()
+            && pid > 1
+        {
+            unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) };
+        }
+        if let Some(code) = wait_child_exit(session, CLOSE_TERM_GRACE) {
+            return CloseOutcome {
+                terminated: true,
+                killed: false,
+                exit_code: Some(code),
+            };
         }
     }
+
+    // Still alive: force-kill. Without this, agents that ignore Ctrl-C (e.g.
+    // claude) become orphans — the cloned reader fd keeps the pty master
+    // alive, the slave never sees EOF, and the reader thread spins forever.
+    #[cfg(unix)]
+    kill_foreground_process_group(session, session_id);
+    if let Err(e) = session._child.kill() {
+        tracing::warn!(session_id = %session_id, "close_pty_core SIGKILL fallback failed: {e}");
+    }
+    let exit_code = wait_child_exit(session, CLOSE_KILL_CONFIRM);
+    CloseOutcome {
+        terminated: exit_code.is_some(),
+        killed: true,
+        exit_code,
+    }
 }
 
-/// Close a PTY session core: sends Ctrl-C, waits briefly for graceful exit,
-/// captures the exit code for the tombstone, and leaves `output_buffers` +
-/// `vt_log_buffers` + `last_output_ms` + `exit_codes` alive so post-mortem
-/// MCP reads can still return final output and exit status.
+/// Close a PTY session core: escalates Ctrl-C → SIGTERM → SIGKILL until the
+/// child is confirmed exited, captures the exit code for the tombstone, and
+/// leaves `output_buffers` + `vt_log_buffers` + `last_output_ms` + `exit_codes`
+/// alive so post-mortem MCP reads can still return final output and exit status.
 ///
 /// Shared between the Tauri `close_pty` command and the MCP `close` action —
 /// both paths must tombstone identically, or post-mortem reads break.
-/// Returns the worktree path when `cleanup_worktree` is true and the session
-/// had one, so the caller can run `remove_worktree_internal` outside this fn.
+/// Returns `None` if the session doesn't exist; otherwise the close outcome and,
+/// when `cleanup_worktree` is true and the session had one, the worktree so the
+/// caller can run `remove_worktree_internal` outside this fn.
 pub(crate) fn close_pty_core(
     state: &AppState,
     session_id: &str,
     cleanup_worktree: bool,
-) -> Option<crate::state::WorktreeInfo> {
+) -> Option<(CloseOutcome, Option<crate::state::WorktreeInfo>)> {
+    let exited_on_its_own = stamp_exit_before_close(state, session_id)?;
     let (_, session_mutex) = state.sessions.remove(session_id)?;
     state
         .metrics
         .active_sessions
         .fetch_sub(1, Ordering::Relaxed);
+    state.record_session_closed(session_id);
     let mut session = session_mutex.into_inner();
 
-    // Send Ctrl-C (0x03) to give the process a chance to clean up
-    let _ = session.writer.write_all(&[0x03]);
-    let _ = session.writer.flush();
-
-    // Wait up to 100ms for process to exit gracefully
-    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(100);
-    loop {
-        match session._child.try_wait() {
-            Ok(Some(_)) => break, // Process exited cleanly
-            Ok(None) if std::time::Instant::now() >= deadline => break,
-            _ => std::thread::sleep(std::time::Duration::from_millis(10)),
-        }
-    }
-
-    // If the child is still alive after the grace window, force-kill it.
-    // Without this, agents that ignore Ctrl-C (e.g. claude) become orphans —
-    // the cloned reader fd keeps the pty master alive, the slave never sees
-    // EOF, and the reader thread spins forever.
-    if matches!(session._child.try_wait(), Ok(None)) {
-        // Nuke the agent's foreground process group first; SIGKILL on the shell
-        // alone leaves the agent (a grandchild) orphaned. See
-        // kill_foreground_process_group.
-        #[cfg(unix)]
-        kill_foreground_process_group(&session, session_id);
+    let outcome = terminate_session_child(&mut session, session_id);
+    crate::recording::finish_for_session(state, session_id);
 
-        if let Err(e) = session._child.kill() {
-            tracing::warn!(session_id = %session_id, "close_pty_core SIGKILL fallback failed: {e}");
-        }
-        // Brief wait so try_wait can observe the termination and record the code.
-        let kill_deadline = std::time::Instant::now() + std::time::Duration::from_millis(100);
-        loop {
-            match session._child.try_wait() {
-                Ok(Some(_)) => break,
-                Ok(None) if std::time::Instant::now() >= kill_deadline => break,
-                _ => std::thread::sleep(std::time::Duration::from_millis(10)),
-            }
-        }
+    if let Some(code) = outcome.exit_code {
+        state.exit_codes.insert(session_id.to_string(), code);
     }
-
-    // Capture exit code for the tombstone before dropping the child handle.
-    if let Ok(Some(status)) = session._child.try_wait() {
+    if !exited_on_its_own {
         state
-            .exit_codes
-            .insert(session_id.to_string(), status.exit_code() as i32);
+            .exit_reasons
+            .insert(session_id.to_string(), killed_by_us_info(outcome.exit_code));
     }
 
     // Preserve output_buffers, vt_log_buffers, last_output_ms, exit_codes.
-    // Tombstone sweeper reaps them after TOMBSTONE_TTL_MS.
-    tombstone_transient_cleanup(session_id, state);
+    // Tombstone sweeper reaps them after TOMBSTONE_TTL_MS. Transient state
+    // (ws_clients, watchers) is only torn down once the child is confirmed
+    // gone; otherwise the reader thread's exit path does it when it dies.
+    if outcome.terminated {
+        tombstone_transient_cleanup(session_id, state);
+    } else {
+        tracing::warn!(
+            source = "pty",
+            session_id = %session_id,
+            "Child survived SIGKILL on close; deferring cleanup to reader exit"
+        );
+    }
 
     let worktree_to_cleanup = if cleanup_worktree {
         session.worktree.clone()
@@ -4746,10 +5894,10 @@ pub(crate) fn close_pty_core(
         None
     };
 
-    // Drop session to release file handles (forcibly kills if still running)
+    // Drop session to release file handles
     drop(session);
 
-    worktree_to_cleanup
+    Some((outcome, worktree_to_cleanup))
 }
 
 /// Force-kill a PTY session and tombstone it. Used by the MCP `kill` action.
@@ -4757,6 +5905,9 @@ pub(crate) fn close_pty_core(
 /// immediately. The child exits near-instantly so `try_wait` captures the
 /// exit code before the tombstone is stamped.
 pub(crate) fn kill_pty_core(state: &AppState, session_id: &str) -> bool {
+    let Some(exited_on_its_own) = stamp_exit_before_close(state, session_id) else {
+        return false;
+    };
     let Some((_, session_mutex)) = state.sessions.remove(session_id) else {
         return false;
     };
@@ -4764,6 +5915,7 @@ pub(crate) fn kill_pty_core(state: &AppState, session_id: &str) -> bool {
         .metrics
         .active_sessions
         .fetch_sub(1, Ordering::Relaxed);
+    state.record_session_closed(session_id);
     let mut session = session_mutex.into_inner();
 
     // Nuke the agent's foreground process group first; SIGKILL on the shell
@@ -4786,10 +5938,17 @@ pub(crate) fn kill_pty_core(state: &AppState, session_id: &str) -> bool {
         }
     }
 
-    if let Ok(Some(status)) = session._child.try_wait() {
+    let exit_code = match session._child.try_wait() {
+        Ok(Some(status)) => Some(exit_status_code(&status)),
+        _ => None,
+    };
+    if let Some(code) = exit_code {
+        state.exit_codes.insert(session_id.to_string(), code);
+    }
+    if !exited_on_its_own {
         state
-            .exit_codes
-            .insert(session_id.to_string(), status.exit_code() as i32);
+            .exit_reasons
+            .insert(session_id.to_string(), killed_by_us_info(exit_code));
     }
 
     tombstone_transient_cleanup(session_id, state);
@@ -4798,21 +5957,36 @@ pub(crate) fn kill_pty_core(state: &AppState, session_id: &str) -> bool {
 }
 
 /// Close a PTY session with graceful shutdown and optional worktree cleanup.
-/// Sends Ctrl-C (0x03) and waits briefly for the process to exit cleanly
-/// before forcibly dropping handles.
+/// Escalates Ctrl-C → SIGTERM → SIGKILL and reports whether the child was
+/// confirmed exited. Closing an already-closed session reports its tombstoned
+/// exit code. The escalation sleeps between signals, so it runs on the
+/// blocking pool instead of an IPC worker.
 #[cfg(feature = "desktop")]
 #[tauri::command]
-pub(crate) fn close_pty(
+pub(crate) async fn close_pty(
     state: State<'_, Arc<AppState>>,
     session_id: String,
     cleanup_worktree: bool,
-) -> Result<(), String> {
-    if let Some(worktree) = close_pty_core(&state, &session_id, cleanup_worktree)
-        && let Err(e) = remove_worktree_internal(&worktree, false)
-    {
-        tracing::warn!("Failed to cleanup worktree: {e}");
-    }
-    Ok(())
+) -> Result<CloseOutcome, String> {
+    let state = Arc::clone(&state);
+    tokio::task::spawn_blocking(move || {
+        let Some((outcome, worktree)) = close_pty_core(&state, &session_id, cleanup_worktree)
+        else {
+            return Ok(CloseOutcome {
+                terminated: true,
+                killed: false,
+                exit_code: state.exit_codes.get(&session_id).map(|e| *e.value()),
+            });
+        };
+        if let Some(worktree) = worktree
+            && let Err(e) = remove_worktree_internal(&worktree, false)
+        {
+            tracing::warn!("Failed to cleanup worktree: {e}");
+        }
+        Ok(outcome)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking error: {e}"))?
 }
 
 /// Look up the process name for a given PID using OS-native syscalls.
@@ -5004,18 +6178,7 @@ pub(crate) fn deepest_descendant_pid(root_pid: u32) -> Option<u32> {
 
 /// Map a process name to a known agent type, or None for non-agent processes.
 pub(crate) fn classify_agent(process_name: &str) -> Option<&'static str> {
-    match process_name {
-        "claude" => Some("claude"),
-        "gemini" => Some("gemini"),
-        "opencode" => Some("opencode"),
-        "aider" => Some("aider"),
-        "codex" => Some("codex"),
-        "amp" => Some("amp"),
-        "cursor-agent" => Some("cursor"),
-        "goose" => Some("goose"),
-        "grok" => Some("grok"),
-        _ => None,
-    }
+    crate::agent::known_agent_by_binary(process_name).map(|a| a.name)
 }
 
 /// Get the foreground process of a PTY session and classify it as a known agent.
@@ -5048,7 +6211,7 @@ pub(crate) fn get_session_foreground_process(
         "cmd",
     ];
 
-    let (detected, fg_is_shell) = {
+    let (detected, fg_is_shell, fg_pid) = {
         let entry = state.sessions.get(&session_id)?;
         let session = entry.value().lock();
         #[cfg(not(windows))]
@@ -5056,7 +6219,11 @@ pub(crate) fn get_session_foreground_process(
             let pgid = session.master.process_group_leader()?;
             let name = process_name_from_pid(pgid as u32)?;
             let is_shell = SHELLS.contains(&name.as_str());
-            (classify_agent(&name).map(|s| s.to_string()), is_shell)
+            (
+                classify_agent(&name).map(|s| s.to_string()),
+                is_shell,
+                pgid as u32,
+            )
         }
         #[cfg(windows)]
         {
@@ -5064,10 +6231,16 @@ pub(crate) fn get_session_foreground_process(
             let leaf = deepest_descendant_pid(child_pid)?;
             let name = process_name_from_pid(leaf)?;
             let is_shell = SHELLS.contains(&name.as_str());
-            (classify_agent(&name).map(|s| s.to_string()), is_shell)
+            (classify_agent(&name).map(|s| s.to_string()), is_shell, leaf)
         }
     };
 
+    // Agents run as grandchildren in their own process group and are what
+    // actually survives a crash, so record them alongside the shell.
+    if detected.is_some() {
+        crate::spawn_ledger::record_spawn_in_background(&state.data_dir, fg_pid, &session_id);
+    }
+
     // Fallback: unrecognised non-shell foreground + pre-set agent type → use preset.
     // Covers custom commands (aliases, symlinks, wrappers) from run configs.
     let effective = detected.clone().or_else(|| {
@@ -5250,7 +6423,148 @@ pub(crate) fn get_session_metrics(state: State<'_, Arc<AppState>>) -> serde_json
 #[cfg(feature = "desktop")]
 #[tauri::command]
 pub(crate) fn can_spawn_session(state: State<'_, Arc<AppState>>) -> bool {
-    state.sessions.len() < MAX_CONCURRENT_SESSIONS
+    state.can_spawn_session()
+}
+
+/// Slash command in a submitted input line, only while an agent owns the
+/// session: at a plain shell prompt `/usr/bin/env` is a path, not a command.
+fn agent_slash_command(state: &AppState, session_id: &str, line: &str) -> Option<ParsedEvent> {
+    if !agent_active(state, session_id) {
+        return None;
+    }
+    crate::output_parser::parse_slash_command(line)
+}
+
+/// True while an agent (spawned or detected) owns the session.
+fn agent_active(state: &AppState, session_id: &str) -> bool {
+    state
+        .session_states
+        .get(session_id)
+        .is_some_and(|s| s.agent_type.is_some())
+}
+
+/// Take a silence-confirmed `TaskComplete` for the silence timer. Only agent
+/// sessions report it: a plain shell printing "Done." or returning to its
+/// prompt is not a finished agent turn, so the candidate is dropped there.
+fn take_task_complete(
+    state: &AppState,
+    silence: &Mutex<SilenceState>,
+    session_id: &str,
+) -> Option<ParsedEvent> {
+    let summary = silence.lock().check_task_complete()?;
+    agent_active(state, session_id).then_some(ParsedEvent::TaskComplete { summary })
+}
+
+/// `Err` once the configured session cap (`max_concurrent_sessions`) is
+/// reached. Every spawn path checks it first, before any PTY or worktree exists.
+pub(crate) fn ensure_session_slot(state: &AppState) -> Result<(), String> {
+    if state.can_spawn_session() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Max concurrent sessions ({}) reached",
+            state.max_sessions()
+        ))
+    }
+}
+
+/// Server-side filter + pagination for session listings (`GET /sessions`,
+/// `list_sessions_page`). All fields are optional; an empty filter matches all.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub(crate) struct SessionFilter {
+    /// Repo path: matches sessions whose cwd or worktree lives under it
+    pub(crate) repo: Option<String>,
+    /// Agent type tag (e.g. `claude`, `codex`), case-insensitive
+    pub(crate) tag: Option<String>,
+    /// Match only paused (`true`) or running (`false`) sessions
+    pub(crate) paused: Option<bool>,
+    pub(crate) limit: Option<usize>,
+    pub(crate) offset: Option<usize>,
+}
+
+/// One page of sessions plus the total number matching the filter.
+#[derive(Clone, Serialize)]
+pub(crate) struct SessionPage<T> {
+    pub(crate) sessions: Vec<T>,
+    pub(crate) total: usize,
+}
+
+impl SessionFilter {
+    /// True when no filter or pagination parameter is set.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.repo.is_none()
+            && self.tag.is_none()
+            && self.paused.is_none()
+            && self.limit.is_none()
+            && self.offset.is_none()
+    }
+
+    fn matches(
+        &self,
+        cwd: Option<&str>,
+        worktree: Option<&crate::state::WorktreeInfo>,
+        agent_type: Option<&str>,
+        paused: bool,
+    ) -> bool {
+        if let Some(repo) = self.repo.as_deref() {
+            let repo = std::path::Path::new(repo);
+            let in_repo = cwd.is_some_and(|c| std::path::Path::new(c).starts_with(repo))
+                || worktree.is_some_and(|w| w.path.starts_with(repo) || w.base_repo == repo);
+            if !in_repo {
+                return false;
+            }
+        }
+        if let Some(tag) = self.tag.as_deref()
+            && !agent_type.is_some_and(|a| a.eq_ignore_ascii_case(tag))
+        {
+            return false;
+        }
+        self.paused.is_none_or(|p| p == paused)
+    }
+
+    /// Apply offset/limit to already-filtered items, returning the page and total.
+    fn paginate<T>(&self, items: Vec<T>) -> SessionPage<T> {
+        let total = items.len();
+        let sessions = items
+            .into_iter()
+            .skip(self.offset.unwrap_or(0))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+        SessionPage { sessions, total }
+    }
+}
+
+/// Filter and paginate live sessions, mapping each match with `build`.
+///
+/// Sessions are ordered by id so offsets are stable across calls (DashMap
+/// iteration order is not).
+pub(crate) fn list_sessions_filtered<T>(
+    state: &AppState,
+    filter: &SessionFilter,
+    build: impl Fn(String, &PtySession) -> T,
+) -> SessionPage<T> {
+    let mut matched: Vec<(String, T)> = state
+        .sessions
+        .iter()
+        .filter_map(|entry| {
+            let session_id = entry.key().clone();
+            let session = entry.value().lock();
+            let agent_type = state
+                .session_states
+                .get(&session_id)
+                .and_then(|s| s.agent_type.clone());
+            filter
+                .matches(
+                    session.cwd.as_deref(),
+                    session.worktree.as_ref(),
+                    agent_type.as_deref(),
+                    session.paused.load(Ordering::Relaxed),
+                )
+                .then(|| (session_id.clone(), build(session_id, &session)))
+        })
+        .collect();
+    matched.sort_by(|a, b| a.0.cmp(&b.0));
+    filter.paginate(matched.into_iter().map(|(_, item)| item).collect())
 }
 
 /// Info about an active PTY session for frontend reconnection
@@ -5302,10 +6616,25 @@ pub(crate) fn set_session_name(
         .sessions
         .get(&session_id)
         .ok_or_else(|| format!("Session not found: {session_id}"))?;
-    entry.lock().display_name = name;
+    entry.lock().display_name = name.clone();
+    drop(entry);
+    state.record_session_title(&session_id, name);
     Ok(())
 }
 
+fn active_session_info(session_id: String, session: &PtySession) -> ActiveSessionInfo {
+    ActiveSessionInfo {
+        session_id,
+        cwd: session.cwd.clone(),
+        worktree_path: session
+            .worktree
+            .as_ref()
+            .map(|w| w.path.to_string_lossy().to_string()),
+        worktree_branch: session.worktree.as_ref().and_then(|w| w.branch.clone()),
+        display_name: session.display_name.clone(),
+    }
+}
+
 /// List all active PTY sessions for reconnection after frontend reload
 #[cfg(feature = "desktop")]
 #[tauri::command]
@@ -5313,23 +6642,31 @@ pub(crate) fn list_active_sessions(state: State<'_, Arc<AppState>>) -> Vec<Activ
     state
         .sessions
         .iter()
-        .map(|entry| {
-            let session_id = entry.key().clone();
-            let session = entry.value().lock();
-            ActiveSessionInfo {
-                session_id,
-                cwd: session.cwd.clone(),
-                worktree_path: session
-                    .worktree
-                    .as_ref()
-                    .map(|w| w.path.to_string_lossy().to_string()),
-                worktree_branch: session.worktree.as_ref().and_then(|w| w.branch.clone()),
-                display_name: session.display_name.clone(),
-            }
-        })
+        .map(|entry| active_session_info(entry.key().clone(), &entry.value().lock()))
         .collect()
 }
 
+/// List sessions filtered by repo / agent tag / paused state, one page at a time.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn list_sessions_page(
+    state: State<'_, Arc<AppState>>,
+    repo: Option<String>,
+    tag: Option<String>,
+    paused: Option<bool>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> SessionPage<ActiveSessionInfo> {
+    let filter = SessionFilter {
+        repo,
+        tag,
+        paused,
+        limit,
+        offset,
+    };
+    list_sessions_filtered(&state, &filter, active_session_info)
+}
+
 /// Per-process resource usage for the process manager modal.
 #[derive(Clone, Serialize)]
 pub(crate) struct ProcessStats {
@@ -5401,6 +6738,180 @@ pub(crate) fn get_process_stats(state: State<'_, Arc<AppState>>) -> Vec<ProcessS
     collect_process_stats(&state)
 }
 
+/// Aggregate resource usage of one session's process tree (child + descendants).
+#[derive(Clone, Debug, Default, Serialize)]
+pub(crate) struct SessionResources {
+    pub(crate) cpu_percent: f32,
+    pub(crate) memory_bytes: u64,
+    pub(crate) process_count: usize,
+}
+
+/// Sum current CPU% and RSS over `root` and every descendant.
+/// Returns `None` when `root` itself has already exited.
+///
+/// CPU is measured between two samples `MINIMUM_CPU_UPDATE_INTERVAL` apart
+/// (not `ps %cpu`, which averages over the process lifetime), so this blocks
+/// for that long — call it off the async runtime.
+pub(crate) fn process_tree_resources(root: u32) -> Option<SessionResources> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let mut pids = vec![Pid::from_u32(root)];
+    pids.extend(
+        collect_descendant_pids(root)
+            .unwrap_or_default()
+            .into_iter()
+            .map(Pid::from_u32),
+    );
+    let refresh = ProcessRefreshKind::nothing().with_cpu().with_memory();
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, refresh);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, refresh);
+    system.process(Pid::from_u32(root))?;
+    Some(pids.iter().filter_map(|pid| system.process(*pid)).fold(
+        SessionResources::default(),
+        |mut acc, process| {
+            acc.cpu_percent += process.cpu_usage();
+            acc.memory_bytes += process.memory();
+            acc.process_count += 1;
+            acc
+        },
+    ))
+}
+
+/// Resource usage for a session's whole process tree, rooted at the PTY child
+/// (the shell) so agents running in their own process group are included.
+pub(crate) fn session_resources(
+    state: &AppState,
+    session_id: &str,
+) -> Result<SessionResources, String> {
+    let child_pid = {
+        let entry = state
+            .sessions
+            .get(session_id)
+            .ok_or_else(|| format!("Session not found: {session_id}"))?;
+        let session = entry.value().lock();
+        session._child.process_id()
+    };
+    child_pid
+        .and_then(process_tree_resources)
+        .ok_or_else(|| format!("Session process has exited: {session_id}"))
+}
+
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn get_session_resources(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+) -> Result<SessionResources, String> {
+    let state = Arc::clone(&state);
+    tokio::task::spawn_blocking(move || session_resources(&state, &session_id))
+        .await
+        .map_err(|e| format!("spawn_blocking join error: {e}"))?
+}
+
+/// A process recorded in the spawn ledger whose session no longer exists.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct OrphanedProcess {
+    pub(crate) pid: u32,
+    pub(crate) name: Option<String>,
+    /// Session the process was spawned for (gone by now).
+    pub(crate) session_id: String,
+    /// Unix seconds when the process was recorded.
+    pub(crate) recorded_at: u64,
+}
+
+/// Processes spawned by TUICommander that are still running without a live
+/// session — typically agents left behind by a crash. See `spawn_ledger`.
+pub(crate) fn find_orphaned_processes(state: &AppState) -> Vec<OrphanedProcess> {
+    let live: std::collections::HashSet<String> =
+        state.sessions.iter().map(|e| e.key().clone()).collect();
+    crate::spawn_ledger::find_orphans(&state.data_dir, &live)
+        .into_iter()
+        .map(|r| OrphanedProcess {
+            pid: r.pid,
+            name: r.name,
+            session_id: r.session_id,
+            recorded_at: r.recorded_at,
+        })
+        .collect()
+}
+
+/// Kill a process reported by [`find_orphaned_processes`].
+///
+/// Refuses without `confirm`, and refuses any PID that isn't currently a
+/// recorded orphan so this can't be used to signal arbitrary processes.
+/// On Unix a process-group leader is killed with its whole group.
+pub(crate) fn kill_orphaned_process_core(
+    state: &AppState,
+    pid: u32,
+    confirm: bool,
+) -> Result<(), String> {
+    if !confirm {
+        return Err(format!(
+            "Refusing to kill process {pid} without confirmation"
+        ));
+    }
+    if pid == std::process::id() {
+        return Err("Refusing to kill TUICommander itself".to_string());
+    }
+    if !find_orphaned_processes(state).iter().any(|o| o.pid == pid) {
+        return Err(format!("Process {pid} is not a known orphaned process"));
+    }
+    #[cfg(unix)]
+    {
+        let Ok(pid) = libc::pid_t::try_from(pid) else {
+            return Err(format!("Invalid process id {pid}"));
+        };
+        if pid <= 1 {
+            return Err(format!("Refusing to kill process {pid}"));
+        }
+        // SAFETY: getpgid/kill take plain integers; we never signal pid <= 1
+        // or TUIC's own process group.
+        let own_pgid = unsafe { libc::getpgid(0) };
+        let target = if unsafe { libc::getpgid(pid) } == pid && pid != own_pgid {
+            -pid
+        } else {
+            pid
+        };
+        if unsafe { libc::kill(target, libc::SIGKILL) } != 0 {
+            let err = std::io::Error::last_os_error();
+            if err.raw_os_error() != Some(libc::ESRCH) {
+                return Err(format!("Failed to kill process {pid}: {err}"));
+            }
+        }
+    }
+    #[cfg(windows)]
+    {
+        let status = std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .status()
+            .map_err(|e| format!("Failed to run taskkill: {e}"))?;
+        if !status.success() {
+            return Err(format!("taskkill failed for process {pid}"));
+        }
+    }
+    crate::spawn_ledger::forget(&state.data_dir, pid);
+    tracing::info!(pid, "Killed orphaned process");
+    Ok(())
+}
+
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn list_orphaned_processes(state: State<'_, Arc<AppState>>) -> Vec<OrphanedProcess> {
+    find_orphaned_processes(&state)
+}
+
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn kill_orphaned_process(
+    state: State<'_, Arc<AppState>>,
+    pid: u32,
+    confirm: bool,
+) -> Result<(), String> {
+    kill_orphaned_process_core(&state, pid, confirm)
+}
+
 /// Collect all descendant PIDs of a process (excluding the root itself).
 fn collect_descendant_pids(root: u32) -> Option<Vec<u32>> {
     #[cfg(not(windows))]
@@ -5973,6 +7484,100 @@ pub(crate) async fn set_session_visible(
 mod tests {
     use super::*;
 
+    #[cfg(unix)]
+    #[test]
+    fn process_tree_resources_enumerates_descendants() {
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "sleep 5 & sleep 5 & wait"])
+            .spawn()
+            .expect("spawn sh");
+        let pid = child.id();
+        // Give the shell a moment to fork its children
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        let res = process_tree_resources(pid);
+        let _ = child.kill();
+        let _ = child.wait();
+
+        let res = res.expect("live process must report resources");
+        assert!(
+            res.process_count >= 3,
+            "expected sh + 2 sleeps, got {res:?}"
+        );
+        assert!(res.memory_bytes > 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn process_tree_resources_none_for_exited_process() {
+        let mut child = std::process::Command::new("true").spawn().expect("spawn");
+        let pid = child.id();
+        child.wait().unwrap();
+        assert!(process_tree_resources(pid).is_none());
+    }
+
+    #[test]
+    fn session_filter_by_repo_matches_only_sessions_in_repo() {
+        let filter = SessionFilter {
+            repo: Some("/repos/app".to_string()),
+            ..SessionFilter::default()
+        };
+        let worktree = crate::state::WorktreeInfo {
+            name: "feat".to_string(),
+            path: "/worktrees/app--feat".into(),
+            branch: Some("feat".to_string()),
+            base_repo: "/repos/app".into(),
+        };
+        assert!(filter.matches(Some("/repos/app"), None, None, false));
+        assert!(filter.matches(Some("/repos/app/src"), None, None, false));
+        assert!(filter.matches(None, Some(&worktree), None, false));
+        assert!(!filter.matches(Some("/repos/app-other"), None, None, false));
+        assert!(!filter.matches(Some("/repos/lib"), None, None, false));
+        assert!(!filter.matches(None, None, None, false));
+    }
+
+    #[test]
+    fn session_filter_by_tag_and_paused() {
+        let filter = SessionFilter {
+            tag: Some("Claude".to_string()),
+            paused: Some(true),
+            ..SessionFilter::default()
+        };
+        assert!(filter.matches(None, None, Some("claude"), true));
+        assert!(!filter.matches(None, None, Some("claude"), false));
+        assert!(!filter.matches(None, None, Some("codex"), true));
+        assert!(!filter.matches(None, None, None, true));
+        assert!(SessionFilter::default().matches(None, None, None, true));
+    }
+
+    #[test]
+    fn session_filter_paginates_with_limit_and_offset() {
+        let items: Vec<u32> = (0..5).collect();
+        let page = SessionFilter {
+            limit: Some(2),
+            offset: Some(1),
+            ..SessionFilter::default()
+        }
+        .paginate(items.clone());
+        assert_eq!(page.sessions, vec![1, 2]);
+        assert_eq!(page.total, 5);
+
+        let tail = SessionFilter {
+            limit: Some(2),
+            offset: Some(4),
+            ..SessionFilter::default()
+        }
+        .paginate(items.clone());
+        assert_eq!(tail.sessions, vec![4]);
+
+        let past_end = SessionFilter {
+            offset: Some(10),
+            ..SessionFilter::default()
+        }
+        .paginate(items);
+        assert!(past_end.sessions.is_empty());
+        assert_eq!(past_end.total, 5);
+    }
+
     /// The interactive-path threads raise their QoS to USER_INTERACTIVE. Verify
     /// the syscall actually takes effect by reading the class back on the same
     /// thread (default QoS for a fresh test thread is *not* USER_INTERACTIVE).
@@ -6069,6 +7674,157 @@ mod tests {
         assert_eq!(parse_signal_number("unknown signal"), 0);
     }
 
+    #[test]
+    fn test_parse_signal_number_linux_names() {
+        assert_eq!(parse_signal_number("Killed"), 9);
+        assert_eq!(parse_signal_number("Segmentation fault"), 11);
+        assert_eq!(parse_signal_number("Terminated"), 15);
+    }
+
+    /// Spawn `script` under /bin/sh in a real PTY and wait for it to exit.
+    #[cfg(unix)]
+    fn run_to_exit(script: &str) -> portable_pty::ExitStatus {
+        let pty = native_pty_system()
+            .openpty(PtySize {
+                rows: 24,
+                cols: 80,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .expect("openpty");
+        let mut cmd = CommandBuilder::new("/bin/sh");
+        cmd.args(["-c", script]);
+        let mut child = pty.slave.spawn_command(cmd).expect("spawn");
+        drop(pty.slave);
+        child.wait().expect("wait")
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn classify_exit_sigkill_is_signaled() {
+        let status = run_to_exit("kill -9 $$");
+        let info = classify_exit(&status, ExitContext::default(), |_| false);
+        assert_eq!(info.reason, crate::state::ExitReason::Signaled);
+        assert_eq!(info.signal, Some(9));
+        assert!(!info.core_dumped);
+        assert_eq!(exit_status_code(&status), 137);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn classify_exit_clean_exit_is_exited() {
+        let status = run_to_exit("exit 0");
+        let info = classify_exit(&status, ExitContext::default(), |_| {
+            panic!("OOM probe on clean exit")
+        });
+        assert_eq!(info.reason, crate::state::ExitReason::Exited);
+        assert_eq!(info.signal, None);
+        assert_eq!(exit_status_code(&status), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn classify_exit_core_dumped_follows_wait_status() {
+        let status = portable_pty::ExitStatus::with_signal("Segmentation fault");
+        let ctx = ExitContext {
+            core_dumped: true,
+            ..Default::default()
+        };
+        let info = classify_exit(&status, ctx, |_| false);
+        assert_eq!(info.reason, crate::state::ExitReason::Signaled);
+        assert_eq!(info.signal, Some(11));
+        assert!(info.core_dumped);
+        // A crash signal alone does not claim a core dump.
+        assert!(!classify_exit(&status, ExitContext::default(), |_| false).core_dumped);
+    }
+
+    /// `peek_core_dumped` must not reap: `try_wait` still sees the status.
+    #[cfg(unix)]
+    #[test]
+    fn peek_core_dumped_leaves_child_reapable() {
+        let pty = native_pty_system()
+            .openpty(PtySize {
+                rows: 24,
+                cols: 80,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .expect("openpty");
+        let mut cmd = CommandBuilder::new("/bin/sh");
+        cmd.args(["-c", "ulimit -c 0; kill -SEGV $$"]);
+        let mut child = pty.slave.spawn_command(cmd).expect("spawn");
+        drop(pty.slave);
+        let pid = child.process_id().expect("pid");
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        loop {
+            let dumped = peek_core_dumped(pid);
+            if let Some(status) = child.try_wait().expect("child still reapable") {
+                assert!(!dumped, "core dumps disabled by ulimit");
+                assert_eq!(parse_signal_number(status.signal().unwrap()), 11);
+                break;
+            }
+            assert!(std::time::Instant::now() < deadline, "child never exited");
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn classify_exit_reports_foreground_job_signal() {
+        // sh waiting on an agent that was SIGKILLed exits 137.
+        let status = portable_pty::ExitStatus::with_exit_code(137);
+        let ctx = ExitContext {
+            pid: Some(100),
+            foreground_pid: Some(4242),
+            ..Default::default()
+        };
+        let info = classify_exit(&status, ctx, |pid| {
+            assert_eq!(pid, 4242, "OOM probe must check the job, not the shell");
+            true
+        });
+        assert_eq!(info.reason, crate::state::ExitReason::Oom);
+        assert_eq!(info.signal, Some(9));
+
+        let segv = portable_pty::ExitStatus::with_exit_code(139);
+        let info = classify_exit(&segv, ctx, |_| false);
+        assert_eq!(info.reason, crate::state::ExitReason::Signaled);
+        assert_eq!(info.signal, Some(11));
+
+        // Without a foreground job, 137 is just the shell's exit code.
+        let info = classify_exit(&status, ExitContext::default(), |_| true);
+        assert_eq!(info.reason, crate::state::ExitReason::Exited);
+        assert_eq!(info.signal, None);
+    }
+
+    #[test]
+    fn classify_exit_prefers_killed_by_us_and_oom() {
+        let killed = portable_pty::ExitStatus::with_signal("Killed");
+        let ctx = ExitContext {
+            pid: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(
+            classify_exit(
+                &killed,
+                ExitContext {
+                    killed_by_us: true,
+                    ..ctx
+                },
+                |_| true
+            )
+            .reason,
+            crate::state::ExitReason::KilledByUs
+        );
+        assert_eq!(
+            classify_exit(&killed, ctx, |_| true).reason,
+            crate::state::ExitReason::Oom
+        );
+        assert_eq!(
+            killed_by_us_info(Some(137)).signal,
+            Some(9),
+            "signal recovered from 128 + signum"
+        );
+    }
+
     #[test]
     fn test_parse_osc133_exit_code() {
         assert_eq!(parse_osc133_exit_code('D', "0"), Some(0));
@@ -6107,6 +7863,35 @@ mod tests {
         assert_eq!(classify_agent("goose"), Some("goose"));
     }
 
+    #[test]
+    fn test_classify_agent_amp_and_cline() {
+        assert_eq!(classify_agent("amp"), Some("amp"));
+        assert_eq!(classify_agent("cline"), Some("cline"));
+    }
+
+    #[test]
+    fn test_classify_agent_maps_binary_to_agent_name() {
+        assert_eq!(classify_agent("cursor-agent"), Some("cursor"));
+        assert_eq!(classify_agent("cursor"), None);
+    }
+
+    #[test]
+    fn test_foreground_signal_parse_allowlist() {
+        assert_eq!(ForegroundSignal::parse("SIGINT"), Ok(ForegroundSignal::Int));
+        assert_eq!(ForegroundSignal::parse("term"), Ok(ForegroundSignal::Term));
+        assert_eq!(
+            ForegroundSignal::parse(" SigKill "),
+            Ok(ForegroundSignal::Kill)
+        );
+        for bad in ["SIGSTOP", "SIGHUP", "9", "", "SIG"] {
+            let err = ForegroundSignal::parse(bad).unwrap_err();
+            assert!(
+                err.contains("Allowed: SIGINT, SIGTERM, SIGKILL"),
+                "{bad}: {err}"
+            );
+        }
+    }
+
     #[test]
     fn test_classify_agent_unknown_returns_none() {
         assert_eq!(classify_agent("bash"), None);
@@ -6277,6 +8062,48 @@ mod tests {
         }
     }
 
+    // --- classify_session_context tests ---
+
+    #[test]
+    fn classify_session_context_docker_exec_is_docker() {
+        for s in [
+            "docker exec -it web bash",
+            "/usr/local/bin/docker exec -it web sh",
+            "docker.exe exec -it web bash",
+            "podman exec -it db bash",
+            "docker run --rm -it alpine sh",
+            "docker attach web",
+        ] {
+            assert_eq!(classify_session_context(s), SessionContext::Docker, "{s}");
+        }
+    }
+
+    #[test]
+    fn classify_session_context_ssh() {
+        for s in [
+            "ssh dev@box",
+            "/usr/bin/ssh -p 2222 box",
+            "mosh box",
+            "ssh.exe box",
+        ] {
+            assert_eq!(classify_session_context(s), SessionContext::Ssh, "{s}");
+        }
+    }
+
+    #[test]
+    fn classify_session_context_local_shells() {
+        for s in [
+            "/bin/zsh",
+            "bash --login",
+            "wsl.exe -d Ubuntu",
+            "docker ps",
+            "docker",
+            "",
+        ] {
+            assert_eq!(classify_session_context(s), SessionContext::Local, "{s:?}");
+        }
+    }
+
     // --- SilenceState tests ---
 
     #[test]
@@ -6286,12 +8113,100 @@ mod tests {
     }
 
     #[test]
-    fn test_tool_error_no_candidate_returns_none() {
-        let mut s = SilenceState::new();
-        assert!(s.check_tool_error().is_none());
-    }
+    fn test_disabled_question_parser_silences_the_timer() {
+        let silent_with_question = |s: SilenceState| {
+            let mut s = s;
+            s.on_chunk(false, Some("Proceed?".to_string()), false, false, false);
+            s.last_output_at = std::time::Instant::now()
+                - SILENCE_QUESTION_THRESHOLD
+                - std::time::Duration::from_millis(100);
+            s
+        };
 
-    #[test]
+        let mut enabled = silent_with_question(SilenceState::new());
+        assert!(enabled.question_check_due());
+        assert_eq!(enabled.check_silence(), Some("Proceed?".to_string()));
+
+        let disabled = crate::output_parser::DisabledParsers::from_names(["question"]);
+        let mut s = silent_with_question(SilenceState::new().with_disabled_parsers(disabled));
+        assert!(s.is_silent(), "other silence-based checks are unaffected");
+        assert!(!s.question_check_due());
+        assert!(s.check_silence().is_none());
+
+        let other = crate::output_parser::DisabledParsers::from_names(["intent"]);
+        let s = silent_with_question(SilenceState::new().with_disabled_parsers(other));
+        assert!(s.question_check_due());
+    }
+
+    #[test]
+    fn test_tool_error_no_candidate_returns_none() {
+        let mut s = SilenceState::new();
+        assert!(s.check_tool_error().is_none());
+    }
+
+    fn past_task_complete_threshold() -> std::time::Instant {
+        std::time::Instant::now()
+            - SILENCE_TASK_COMPLETE_THRESHOLD
+            - std::time::Duration::from_millis(100)
+    }
+
+    #[test]
+    fn test_task_complete_fires_once_after_silence() {
+        let mut s = SilenceState::new();
+        s.mark_task_complete_candidate("All tasks complete.".to_string());
+        // Not silent yet.
+        assert!(s.check_task_complete().is_none());
+        s.last_output_at = past_task_complete_threshold();
+        assert_eq!(
+            s.check_task_complete(),
+            Some("All tasks complete.".to_string())
+        );
+        // The marker re-appears on repaint: no second notification this turn.
+        s.mark_task_complete_candidate("All tasks complete.".to_string());
+        assert!(s.check_task_complete().is_none());
+        // Next turn notifies again.
+        s.reset_task_complete_memory();
+        s.mark_task_complete_candidate("Done.".to_string());
+        assert_eq!(s.check_task_complete(), Some("Done.".to_string()));
+    }
+
+    #[test]
+    fn test_task_complete_dropped_when_output_continues() {
+        let mut s = SilenceState::new();
+        s.mark_task_complete_candidate("Done.".to_string());
+        s.clear_task_complete_on_output();
+        s.last_output_at = past_task_complete_threshold();
+        assert!(s.check_task_complete().is_none());
+    }
+
+    #[test]
+    fn test_task_complete_suppressed_by_tool_error() {
+        let mut s = SilenceState::new();
+        s.mark_task_complete_candidate("Done.".to_string());
+        s.mark_tool_error_candidate("Error: Exit code 1".to_string());
+        s.last_output_at = past_task_complete_threshold();
+        assert!(s.check_task_complete().is_none());
+        assert!(s.check_tool_error().is_some());
+        // The error consumed the turn: no completion afterwards either.
+        assert!(s.check_task_complete().is_none());
+    }
+
+    #[test]
+    fn test_sustained_output_counts_real_chunks_only() {
+        let mut s = SilenceState::new();
+        for _ in 0..TASK_COMPLETE_MIN_OUTPUT_CHUNKS {
+            s.on_chunk(false, None, false, true, false); // chrome-only tick
+        }
+        assert!(!s.has_sustained_output());
+        for _ in 0..TASK_COMPLETE_MIN_OUTPUT_CHUNKS {
+            s.on_chunk(false, None, false, false, false);
+        }
+        assert!(s.has_sustained_output());
+        s.reset_task_complete_memory();
+        assert!(!s.has_sustained_output());
+    }
+
+    #[test]
     fn test_tool_error_fires_after_silence_threshold() {
         let mut s = SilenceState::new();
         s.mark_tool_error_candidate("Error: Exit code 128".to_string());
@@ -6644,6 +8559,79 @@ mod tests {
         assert_eq!(s.check_silence(), Some("Second question?".to_string()));
     }
 
+    #[test]
+    fn test_wedge_watch_input_then_silence_with_same_foreground() {
+        let timeout = std::time::Duration::from_secs(30);
+        let t0 = std::time::Instant::now();
+        let mut w = WedgeWatch::default();
+        assert!(w.check(t0, timeout, Some(42)).is_none(), "not armed");
+
+        w.on_input(t0, "ls\r");
+        // Echo of the typed bytes is not a response.
+        w.on_output(4);
+        assert!(
+            w.check(t0 + std::time::Duration::from_secs(1), timeout, Some(42))
+                .is_none()
+        );
+        let fired = w.check(t0 + std::time::Duration::from_secs(31), timeout, Some(42));
+        assert_eq!(fired, Some(std::time::Duration::from_secs(31)));
+        // Reported once per arming.
+        assert!(
+            w.check(t0 + std::time::Duration::from_secs(60), timeout, Some(42))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_wedge_watch_partial_typing_does_not_arm() {
+        let timeout = std::time::Duration::from_secs(30);
+        let t0 = std::time::Instant::now();
+        let mut w = WedgeWatch::default();
+        // Keystrokes of a half-typed command, with nothing but their echo back.
+        for key in ["g", "i", "t", " ", "s"] {
+            w.on_input(t0, key);
+            w.on_output(1);
+        }
+        assert!(!w.is_armed());
+        assert!(
+            w.check(t0 + std::time::Duration::from_secs(60), timeout, Some(42))
+                .is_none()
+        );
+
+        // Submitting the line arms it, from the time of the Enter.
+        let enter_at = t0 + std::time::Duration::from_secs(60);
+        w.on_input(enter_at, "\r");
+        assert!(w.is_armed());
+        assert!(w.check(enter_at, timeout, Some(42)).is_none());
+        assert_eq!(
+            w.check(
+                enter_at + std::time::Duration::from_secs(31),
+                timeout,
+                Some(42)
+            ),
+            Some(std::time::Duration::from_secs(31))
+        );
+    }
+
+    #[test]
+    fn test_wedge_watch_output_or_foreground_change_disarms() {
+        let timeout = std::time::Duration::from_secs(30);
+        let t0 = std::time::Instant::now();
+        let late = t0 + std::time::Duration::from_secs(31);
+
+        let mut w = WedgeWatch::default();
+        w.on_input(t0, "ls\r");
+        w.on_output(200);
+        assert!(!w.is_armed());
+        assert!(w.check(late, timeout, Some(42)).is_none());
+
+        let mut w = WedgeWatch::default();
+        w.on_input(t0, "ls\r");
+        assert!(w.check(t0, timeout, Some(42)).is_none());
+        assert!(w.check(late, timeout, Some(77)).is_none(), "new foreground");
+        assert!(!w.is_armed());
+    }
+
     #[test]
     fn test_silence_state_suppress_user_input() {
         let mut s = SilenceState::new();
@@ -9073,6 +11061,130 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_chunk_processor_re_emits_repeated_tool_approval() {
+        use crate::state::VtLogBuffer;
+        use std::sync::atomic::AtomicU64;
+
+        let state = Arc::new(crate::state::tests_support::make_test_app_state());
+        let sid = "test-cp-tool-approval";
+        let silence = Arc::new(Mutex::new(SilenceState::new()));
+        state
+            .silence_states
+            .insert(sid.to_string(), silence.clone());
+        state.shell_states.insert(
+            sid.to_string(),
+            std::sync::atomic::AtomicU8::new(SHELL_NULL),
+        );
+        state
+            .vt_log_buffers
+            .insert(sid.to_string(), Mutex::new(VtLogBuffer::new(24, 80, 1000)));
+        state
+            .output_buffers
+            .insert(sid.to_string(), Mutex::new(OutputRingBuffer::new(4096)));
+        state
+            .last_output_ms
+            .insert(sid.to_string(), AtomicU64::new(0));
+
+        let mut cp = ChunkProcessor::new(None, None);
+        let mut utf8_buf = Utf8ReadBuffer::new();
+        let mut esc_buf = EscapeAwareBuffer::new();
+        let mut rx = state.event_bus.subscribe();
+        let mut feed = |cp: &mut ChunkProcessor, bytes: &[u8]| {
+            let utf8_data = utf8_buf.push(bytes);
+            let esc_data = esc_buf.push(&utf8_data);
+            let _ = cp.process_chunk(&esc_data, &silence, sid, &state);
+        };
+        let mut approvals = || {
+            let mut count = 0;
+            while let Ok(evt) = rx.try_recv() {
+                if let crate::state::AppEvent::PtyParsed { parsed, .. } = evt
+                    && parsed.get("type").and_then(|t| t.as_str()) == Some("tool-approval")
+                {
+                    count += 1;
+                }
+            }
+            count
+        };
+
+        let prompt = b"\x1b[2J\x1b[HAllow Bash to run `rm -rf build/`? [y/n]";
+        feed(&mut cp, prompt);
+        assert_eq!(approvals(), 1);
+
+        // Repaint while the prompt stays on screen: deduped.
+        feed(&mut cp, prompt);
+        assert_eq!(approvals(), 0, "repaint must not re-emit");
+
+        // Answered: the prompt leaves the screen.
+        feed(&mut cp, b"\x1b[2J\x1b[HRemoved build/");
+        assert!(cp.last_tool_approval_sig.is_none());
+        assert_eq!(approvals(), 0);
+
+        // The same command asked again is a new prompt.
+        feed(&mut cp, prompt);
+        assert_eq!(approvals(), 1, "repeated prompt must be emitted again");
+    }
+
+    #[test]
+    fn test_chunk_processor_drops_disabled_output_events() {
+        use crate::state::VtLogBuffer;
+        use std::sync::atomic::AtomicU64;
+
+        let state = Arc::new(crate::state::tests_support::make_test_app_state());
+        let sid = "test-cp-disabled-events";
+        let silence = Arc::new(Mutex::new(SilenceState::new()));
+        state
+            .silence_states
+            .insert(sid.to_string(), silence.clone());
+        state.shell_states.insert(
+            sid.to_string(),
+            std::sync::atomic::AtomicU8::new(SHELL_NULL),
+        );
+        state
+            .vt_log_buffers
+            .insert(sid.to_string(), Mutex::new(VtLogBuffer::new(24, 80, 1000)));
+        state
+            .output_buffers
+            .insert(sid.to_string(), Mutex::new(OutputRingBuffer::new(4096)));
+        state
+            .last_output_ms
+            .insert(sid.to_string(), AtomicU64::new(0));
+        state.config.write().enabled_output_events = Some(vec!["question".to_string()]);
+
+        let mut cp = ChunkProcessor::new(None, None);
+        let mut utf8_buf = Utf8ReadBuffer::new();
+        let mut esc_buf = EscapeAwareBuffer::new();
+        let mut rx = state.event_bus.subscribe();
+
+        // A status line and a confident (cliclack) question in the same chunk.
+        let raw = "* Reading files...\r\n\u{25C6}  Do you allow this tool call?\r\n";
+        let utf8_data = utf8_buf.push(raw.as_bytes());
+        let esc_data = esc_buf.push(&utf8_data);
+        let _ = cp.process_chunk(&esc_data, &silence, sid, &state);
+
+        let mut kinds = Vec::new();
+        while let Ok(evt) = rx.try_recv() {
+            if let crate::state::AppEvent::PtyParsed { parsed, .. } = evt
+                && let Some(kind) = parsed.get("type").and_then(|t| t.as_str())
+            {
+                kinds.push(kind.to_string());
+            }
+        }
+        // The detector still ran (dedup state recorded), only emission was dropped.
+        assert!(
+            cp.last_status_task.is_some(),
+            "status line must still be parsed"
+        );
+        assert!(
+            !kinds.iter().any(|k| k == "status-line"),
+            "disabled status-line must not be emitted, got: {kinds:?}"
+        );
+        assert!(
+            kinds.iter().any(|k| k == "question"),
+            "enabled question must still fire, got: {kinds:?}"
+        );
+    }
+
     #[test]
     fn test_chunk_processor_planfile_resolution() {
         let cp = ChunkProcessor::new(Some("/home/user/repo".to_string()), None);
@@ -10290,6 +12402,46 @@ mod tests {
         assert_eq!(recorded, 1, "C→D should record exactly one outcome");
     }
 
+    #[test]
+    fn osc133_records_no_outcome_without_input_reconstruction() {
+        let state = crate::state::tests_support::make_test_app_state();
+        state.config.write().reconstruct_user_input = false;
+        let session_id = "test-osc133-no-reconstruct";
+        state.shell_states.insert(
+            session_id.to_string(),
+            std::sync::atomic::AtomicU8::new(SHELL_IDLE),
+        );
+        state
+            .shell_state_since_ms
+            .insert(session_id.to_string(), std::sync::atomic::AtomicU64::new(0));
+        state
+            .has_osc133_integration
+            .insert(session_id.to_string(), ());
+
+        let mut proc = ChunkProcessor::new(None, None);
+        proc.handle_osc133_event('C', "", session_id, &state);
+        proc.handle_osc133_event('D', "0", session_id, &state);
+
+        let recorded = state
+            .session_knowledge
+            .get(session_id)
+            .map(|k| k.lock().commands.len())
+            .unwrap_or(0);
+        assert_eq!(
+            recorded, 0,
+            "no command history when input reconstruction is off"
+        );
+        assert_eq!(
+            state
+                .shell_states
+                .get(session_id)
+                .unwrap()
+                .load(Ordering::Relaxed),
+            SHELL_BUSY,
+            "C still drives the shell state"
+        );
+    }
+
     // --- is_cc_tool_call_header tests ---
 
     #[test]
@@ -10359,6 +12511,257 @@ mod tests {
         assert!(!is_cc_tool_call_header("plain text"));
     }
 
+    /// Session create and close round-trip through `sessions.json`, and the
+    /// next startup sees what was alive as recoverable.
+    #[cfg(unix)]
+    #[test]
+    fn session_registry_round_trips_create_and_close() {
+        let dir = tempfile::tempdir().unwrap();
+        let _guard = crate::config::set_config_dir_override(dir.path().to_path_buf());
+
+        let pty = native_pty_system()
+            .openpty(PtySize {
+                rows: 24,
+                cols: 80,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .expect("openpty");
+        let mut cmd = CommandBuilder::new("/bin/sh");
+        cmd.args(["-c", "sleep 30"]);
+        let command_line = cmd.as_unix_command_line().unwrap();
+        let child = pty.slave.spawn_command(cmd).expect("spawn shell");
+        let master = pty.master;
+        let writer = master.take_writer().expect("writer");
+
+        let state = crate::state::tests_support::make_test_app_state();
+        let sid = "test-registry";
+        state
+            .metrics
+            .active_sessions
+            .fetch_add(1, Ordering::Relaxed);
+        state.sessions.insert(
+            sid.to_string(),
+            Mutex::new(PtySession {
+                writer,
+                master,
+                _child: child,
+                paused: Arc::new(AtomicBool::new(false)),
+                worktree: None,
+                cwd: Some("/tmp/project".to_string()),
+                display_name: None,
+                shell: "/bin/sh".to_string(),
+                token_usage: Default::default(),
+            }),
+        );
+        state.record_session_opened(sid, Some(command_line.clone()));
+        state.record_session_title(sid, Some("agent".to_string()));
+        state.flush_session_registry();
+
+        let restarted = crate::state::tests_support::make_test_app_state();
+        restarted.load_recoverable_sessions();
+        let recoverable = restarted.recoverable_sessions.read().clone();
+        assert_eq!(recoverable.len(), 1);
+        assert_eq!(recoverable[0].session_id, sid);
+        // The full command line, not just the shell it ran under.
+        assert_eq!(recoverable[0].command, command_line);
+        assert!(command_line.contains("sleep 30"), "{command_line}");
+        assert_eq!(recoverable[0].cwd.as_deref(), Some("/tmp/project"));
+        assert_eq!(recoverable[0].title.as_deref(), Some("agent"));
+        assert!(recoverable[0].created_at > 0);
+
+        assert!(kill_pty_core(&state, sid));
+        state.flush_session_registry();
+        restarted.load_recoverable_sessions();
+        assert!(restarted.recoverable_sessions.read().is_empty());
+    }
+
+    #[test]
+    fn slash_commands_only_parsed_while_agent_active() {
+        let state = crate::state::tests_support::make_test_app_state();
+        let sid = "test-slash";
+        assert!(agent_slash_command(&state, sid, "/compact").is_none());
+
+        state
+            .session_states
+            .insert(sid.to_string(), crate::state::SessionState::default());
+        assert!(agent_slash_command(&state, sid, "/compact").is_none());
+
+        state.session_states.insert(
+            sid.to_string(),
+            crate::state::SessionState {
+                agent_type: Some("claude".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            agent_slash_command(&state, sid, "/compact"),
+            Some(ParsedEvent::SlashCommand { .. })
+        ));
+    }
+
+    #[test]
+    fn task_complete_only_reported_for_agent_sessions() {
+        let state = crate::state::tests_support::make_test_app_state();
+        let sid = "test-task-complete";
+        let silence = Mutex::new(SilenceState::new());
+        let arm = |silence: &Mutex<SilenceState>| {
+            let mut s = silence.lock();
+            s.reset_task_complete_memory();
+            s.mark_task_complete_candidate("Done.".to_string());
+            s.last_output_at = past_task_complete_threshold();
+        };
+
+        // Plain shell: the candidate is consumed but never reported.
+        state
+            .session_states
+            .insert(sid.to_string(), crate::state::SessionState::default());
+        arm(&silence);
+        assert!(take_task_complete(&state, &silence, sid).is_none());
+
+        state.session_states.insert(
+            sid.to_string(),
+            crate::state::SessionState {
+                agent_type: Some("claude".to_string()),
+                ..Default::default()
+            },
+        );
+        arm(&silence);
+        assert!(matches!(
+            take_task_complete(&state, &silence, sid),
+            Some(ParsedEvent::TaskComplete { ref summary }) if summary == "Done."
+        ));
+    }
+
+    /// The Tauri spawn paths share the cap the HTTP/MCP paths enforce.
+    #[cfg(unix)]
+    #[test]
+    fn ensure_session_slot_refuses_at_configured_cap() {
+        let pty = native_pty_system()
+            .openpty(PtySize {
+                rows: 24,
+                cols: 80,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .expect("openpty");
+        let mut cmd = CommandBuilder::new("/bin/sh");
+        cmd.args(["-c", "sleep 30"]);
+        let child = pty.slave.spawn_command(cmd).expect("spawn shell");
+        let master = pty.master;
+        let writer = master.take_writer().expect("writer");
+
+        let state = crate::state::tests_support::make_test_app_state();
+        state.config.write().max_concurrent_sessions = 1;
+        assert!(ensure_session_slot(&state).is_ok());
+
+        let sid = "test-cap";
+        state
+            .metrics
+            .active_sessions
+            .fetch_add(1, Ordering::Relaxed);
+        state.sessions.insert(
+            sid.to_string(),
+            Mutex::new(PtySession {
+                writer,
+                master,
+                _child: child,
+                paused: Arc::new(AtomicBool::new(false)),
+                worktree: None,
+                cwd: None,
+                display_name: None,
+                shell: "/bin/sh".to_string(),
+                token_usage: Default::default(),
+            }),
+        );
+        let err = ensure_session_slot(&state).unwrap_err();
+        assert_eq!(err, "Max concurrent sessions (1) reached");
+
+        state.config.write().max_concurrent_sessions = 2;
+        assert!(ensure_session_slot(&state).is_ok());
+        assert!(kill_pty_core(&state, sid));
+    }
+
+    /// Insert a real `/bin/sh -c script` session for the close/kill tests.
+    #[cfg(unix)]
+    fn insert_script_session(state: &AppState, sid: &str, script: &str) {
+        let pty = native_pty_system()
+            .openpty(PtySize {
+                rows: 24,
+                cols: 80,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .expect("openpty");
+        let mut cmd = CommandBuilder::new("/bin/sh");
+        cmd.args(["-c", script]);
+        let child = pty.slave.spawn_command(cmd).expect("spawn shell");
+        let master = pty.master;
+        let writer = master.take_writer().expect("writer");
+        state
+            .metrics
+            .active_sessions
+            .fetch_add(1, Ordering::Relaxed);
+        state.sessions.insert(
+            sid.to_string(),
+            Mutex::new(PtySession {
+                writer,
+                master,
+                _child: child,
+                paused: Arc::new(AtomicBool::new(false)),
+                worktree: None,
+                cwd: None,
+                display_name: None,
+                shell: "/bin/sh".to_string(),
+                token_usage: Default::default(),
+            }),
+        );
+    }
+
+    /// Close/kill stamp `KilledByUs` before signalling, so a reader that sees
+    /// EOF mid-close already finds the classification.
+    #[cfg(unix)]
+    #[test]
+    fn kill_stamps_killed_by_us_before_signalling() {
+        let state = crate::state::tests_support::make_test_app_state();
+        let sid = "test-kill-stamp";
+        insert_script_session(&state, sid, "sleep 30");
+
+        assert_eq!(stamp_exit_before_close(&state, sid), Some(false));
+        assert_eq!(
+            state.exit_reasons.get(sid).map(|e| e.reason),
+            Some(crate::state::ExitReason::KilledByUs)
+        );
+        assert!(kill_pty_core(&state, sid));
+        let info = state.exit_reasons.get(sid).map(|e| e.value().clone());
+        assert_eq!(
+            info.map(|i| (i.reason, i.signal)),
+            Some((crate::state::ExitReason::KilledByUs, Some(9)))
+        );
+        assert_eq!(stamp_exit_before_close(&state, "missing"), None);
+    }
+
+    /// Closing a tab whose process already crashed keeps the crash, not
+    /// `KilledByUs`.
+    #[cfg(unix)]
+    #[test]
+    fn close_keeps_classification_of_child_that_already_died() {
+        let state = crate::state::tests_support::make_test_app_state();
+        let sid = "test-close-dead";
+        insert_script_session(&state, sid, "ulimit -c 0; kill -SEGV $$");
+        std::thread::sleep(std::time::Duration::from_millis(300));
+
+        let (outcome, _) = close_pty_core(&state, sid, false).expect("session exists");
+        assert!(outcome.terminated);
+        assert!(!outcome.killed);
+        let info = state.exit_reasons.get(sid).map(|e| e.value().clone());
+        assert_eq!(
+            info.map(|i| (i.reason, i.signal)),
+            Some((crate::state::ExitReason::Signaled, Some(11)))
+        );
+        assert_eq!(state.exit_codes.get(sid).map(|e| *e.value()), Some(139));
+    }
+
     /// Closing a tab must kill the agent grandchild, not just the shell.
     ///
     /// Mirrors `claude` launched inside the PTY's shell: shell → grandchild,
@@ -10412,6 +12815,7 @@ mod tests {
                 cwd: None,
                 display_name: None,
                 shell: "/bin/sh".to_string(),
+                token_usage: Default::default(),
             }),
         );
 
@@ -10438,7 +12842,15 @@ mod tests {
             "grandchild should be alive before close"
         );
 
-        close_pty_core(&state, sid, false);
+        let (outcome, _) = close_pty_core(&state, sid, false).expect("session exists");
+        assert!(outcome.killed, "trapped shell must escalate to SIGKILL");
+        assert!(outcome.terminated, "SIGKILL must be confirmed: {outcome:?}");
+        // strsignal() text differs per libc, so only require a code was captured.
+        assert!(outcome.exit_code.is_some(), "{outcome:?}");
+        assert_eq!(
+            state.exit_codes.get(sid).map(|e| *e.value()),
+            outcome.exit_code
+        );
 
         // killpg(SIGKILL) is untrappable: the grandchild must be gone shortly.
         let mut dead = false;
@@ -10456,4 +12868,171 @@ mod tests {
             "grandchild {grandchild} survived tab close — orphaned process tree"
         );
     }
+
+    /// A child that ignores Ctrl-C but not SIGTERM stops at the SIGTERM step.
+    #[cfg(unix)]
+    #[test]
+    fn close_pty_core_escalates_to_sigterm_before_kill() {
+        let pty = native_pty_system()
+            .openpty(PtySize {
+                rows: 24,
+                cols: 80,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .expect("openpty");
+        let mut cmd = CommandBuilder::new("/bin/sh");
+        cmd.args(["-c", "trap '' INT; sleep 30"]);
+        let child = pty.slave.spawn_command(cmd).expect("spawn shell");
+        let master = pty.master;
+        let writer = master.take_writer().expect("writer");
+
+        let state = crate::state::tests_support::make_test_app_state();
+        let sid = "test-sigterm";
+        state
+            .metrics
+            .active_sessions
+            .fetch_add(1, Ordering::Relaxed);
+        state.sessions.insert(
+            sid.to_string(),
+            Mutex::new(PtySession {
+                writer,
+                master,
+                _child: child,
+                paused: Arc::new(AtomicBool::new(false)),
+                worktree: None,
+                cwd: None,
+                display_name: None,
+                shell: "/bin/sh".to_string(),
+                token_usage: Default::default(),
+            }),
+        );
+        // Let the shell install its trap before the close races it.
+        std::thread::sleep(std::time::Duration::from_millis(200));
+
+        let (outcome, _) = close_pty_core(&state, sid, false).expect("session exists");
+        assert!(outcome.terminated, "{outcome:?}");
+        assert!(!outcome.killed, "SIGTERM should suffice: {outcome:?}");
+        assert!(outcome.exit_code.is_some(), "{outcome:?}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn broadcast_input_writes_to_each_session_and_reports_missing() {
+        use std::time::{Duration, Instant};
+
+        let state = crate::state::tests_support::make_test_app_state();
+        let dir = tempfile::tempdir().unwrap();
+        let mut outputs = Vec::new();
+        for sid in ["bc-1", "bc-2"] {
+            let pty = native_pty_system()
+                .openpty(PtySize {
+                    rows: 24,
+                    cols: 80,
+                    pixel_width: 0,
+                    pixel_height: 0,
+                })
+                .expect("openpty");
+            let out = dir.path().join(sid);
+            let mut cmd = CommandBuilder::new("/bin/sh");
+            cmd.args(["-c", &format!("head -n 1 > {}", out.display())]);
+            let child = pty.slave.spawn_command(cmd).expect("spawn shell");
+            let master = pty.master;
+            let writer = master.take_writer().expect("writer");
+            state.sessions.insert(
+                sid.to_string(),
+                Mutex::new(PtySession {
+                    writer,
+                    master,
+                    _child: child,
+                    paused: Arc::new(AtomicBool::new(false)),
+                    worktree: None,
+                    cwd: None,
+                    display_name: None,
+                    shell: "/bin/sh".to_string(),
+                    token_usage: Default::default(),
+                }),
+            );
+            outputs.push(out);
+        }
+
+        let ids = ["bc-1", "bc-2", "bc-missing"].map(String::from);
+        let results = broadcast_input_core(&state, &ids, Some("continue"), Some("enter")).unwrap();
+        assert_eq!(
+            results.iter().map(|r| r.ok).collect::<Vec<_>>(),
+            vec![true, true, false]
+        );
+        assert_eq!(results[2].error.as_deref(), Some("Session not found"));
+
+        for out in &outputs {
+            let deadline = Instant::now() + Duration::from_secs(3);
+            let mut content = String::new();
+            while Instant::now() < deadline {
+                content = std::fs::read_to_string(out).unwrap_or_default();
+                if content.ends_with('\n') {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            assert_eq!(
+                content,
+                "continue\n",
+                "{} should receive the input",
+                out.display()
+            );
+        }
+
+        assert!(broadcast_input_core(&state, &ids, Some("x"), Some("hyper+q")).is_err());
+    }
+
+    #[tokio::test]
+    async fn spawn_retry_retries_transient_errors_but_not_permanent_ones() {
+        // Transient: fails twice with fd exhaustion, then succeeds.
+        let mut calls = 0;
+        let mut retries_seen = Vec::new();
+        let result = spawn_with_retry(
+            3,
+            || {
+                calls += 1;
+                if calls < 3 {
+                    Err("Failed to open PTY: Too many open files (os error 24)".to_string())
+                } else {
+                    Ok("spawned")
+                }
+            },
+            |attempt, _, _| retries_seen.push(attempt),
+        )
+        .await;
+        assert_eq!(result, Ok("spawned"));
+        assert_eq!(calls, 3);
+        assert_eq!(retries_seen, vec![1, 2]);
+
+        // Permanent: a missing shell fails on the first attempt.
+        let mut calls = 0;
+        let result: Result<(), String> = spawn_with_retry(
+            3,
+            || {
+                calls += 1;
+                Err("Failed to spawn shell: No such file or directory (os error 2)".to_string())
+            },
+            |_, _, _| panic!("permanent errors must not retry"),
+        )
+        .await;
+        assert!(result.unwrap_err().contains("No such file"));
+        assert_eq!(calls, 1);
+
+        // Out of retries: the last transient error is returned.
+        let mut calls = 0;
+        let result: Result<(), String> = spawn_with_retry(
+            1,
+            || {
+                calls += 1;
+                Err("Resource temporarily unavailable (os error 11)".to_string())
+            },
+            |_, _, _| {},
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 2);
+    }
 }
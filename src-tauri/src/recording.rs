@@ -0,0 +1,305 @@
+//! Asciinema session recordings (`start_session_recording`).
+//!
+//! While a recording is active, the PTY reader thread appends every output
+//! chunk to an asciinema v2 `.cast` file: a JSON header line
+//! (`{version, width, height, timestamp}`) followed by one `[time, "o", data]`
+//! line per chunk. `time` is seconds since the recording started, stamped when
+//! the reader loop received the chunk, so playback reproduces the real pacing.
+//! Recordings stop on request or when the session closes. Write failures stop
+//! the recording — they never interrupt the session.
+//!
+//! Targets must be new files inside a registered repository or the app's
+//! `recordings/` directory, so a recording can never overwrite an existing
+//! file or land anywhere else on disk.
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "desktop")]
+use std::sync::Arc;
+use std::time::Instant;
+#[cfg(feature = "desktop")]
+use tauri::State;
+
+use crate::AppState;
+
+/// Summary of a finished recording.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub(crate) struct RecordingInfo {
+    pub(crate) path: String,
+    /// Output frames written after the header
+    pub(crate) frames: u64,
+    /// Seconds from start to the last frame
+    pub(crate) duration_secs: f64,
+}
+
+/// An open `.cast` file for one session.
+pub(crate) struct CastRecorder {
+    out: BufWriter<File>,
+    path: PathBuf,
+    started: Instant,
+    frames: u64,
+    last_secs: f64,
+}
+
+impl CastRecorder {
+    /// Create `path` (which must not exist yet) and write the asciinema v2 header.
+    pub(crate) fn create(path: &Path, width: u16, height: u16) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .map_err(|e| format!("Failed to create recording: {e}"))?;
+        let mut out = BufWriter::new(file);
+        let header = serde_json::json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "timestamp": chrono::Utc::now().timestamp(),
+        });
+        writeln!(out, "{header}").map_err(|e| format!("Failed to write recording: {e}"))?;
+        Ok(Self {
+            out,
+            path: path.to_path_buf(),
+            started: Instant::now(),
+            frames: 0,
+            last_secs: 0.0,
+        })
+    }
+
+    /// Append an output frame received at `at`.
+    pub(crate) fn record(&mut self, at: Instant, data: &str) -> std::io::Result<()> {
+        let secs = at.saturating_duration_since(self.started).as_secs_f64();
+        // Round to µs like asciinema itself; keeps the file compact.
+        let secs = (secs * 1_000_000.0).round() / 1_000_000.0;
+        let frame = serde_json::json!([secs, "o", data]);
+        writeln!(self.out, "{frame}")?;
+        self.frames += 1;
+        self.last_secs = secs;
+        Ok(())
+    }
+
+    /// Flush and close the file.
+    pub(crate) fn finish(mut self) -> Result<RecordingInfo, String> {
+        self.out
+            .flush()
+            .map_err(|e| format!("Failed to flush recording: {e}"))?;
+        Ok(RecordingInfo {
+            path: self.path.to_string_lossy().to_string(),
+            frames: self.frames,
+            duration_secs: self.last_secs,
+        })
+    }
+}
+
+/// Default home for recordings that don't belong to a repository.
+pub(crate) fn recordings_dir() -> PathBuf {
+    crate::config::config_dir().join("recordings")
+}
+
+/// `target` must pass the `write_external_file` checks and its parent must
+/// resolve inside one of `allowed_roots` (symlinks included).
+fn validate_recording_target(target: &Path, allowed_roots: &[PathBuf]) -> Result<(), String> {
+    let home =
+        dirs::home_dir().ok_or_else(|| "Could not resolve user home directory".to_string())?;
+    crate::fs::validate_external_write_path(target, &home)?;
+    let parent = target
+        .parent()
+        .and_then(|p| p.canonicalize().ok())
+        .ok_or_else(|| "Failed to resolve recording directory".to_string())?;
+    let allowed = allowed_roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| parent.starts_with(root));
+    if allowed {
+        Ok(())
+    } else {
+        Err(format!(
+            "Access denied: recordings must be saved inside a registered repository or {}",
+            recordings_dir().display()
+        ))
+    }
+}
+
+/// Start recording `session_id` to `path`: an absolute path to a new file in a
+/// registered repository or [`recordings_dir`]. Fails if the session is already
+/// being recorded.
+pub(crate) fn start_recording(
+    state: &AppState,
+    session_id: &str,
+    path: &str,
+) -> Result<(), String> {
+    let target = Path::new(path);
+    let recordings = recordings_dir();
+    if let Err(e) = std::fs::create_dir_all(&recordings) {
+        tracing::warn!(source = "recording", "Failed to create recordings dir: {e}");
+    }
+    let mut allowed_roots: Vec<PathBuf> = crate::config::registered_repo_paths()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    allowed_roots.push(recordings);
+    validate_recording_target(target, &allowed_roots)?;
+
+    let size = {
+        let entry = state
+            .sessions
+            .get(session_id)
+            .ok_or_else(|| format!("Session {session_id} not found"))?;
+        entry
+            .lock()
+            .master
+            .get_size()
+            .map_err(|e| format!("Failed to read terminal size: {e}"))?
+    };
+
+    match state.recordings.entry(session_id.to_string()) {
+        dashmap::mapref::entry::Entry::Occupied(_) => {
+            Err(format!("Session {session_id} is already being recorded"))
+        }
+        dashmap::mapref::entry::Entry::Vacant(slot) => {
+            let recorder = CastRecorder::create(target, size.cols, size.rows)?;
+            tracing::info!(
+                source = "recording",
+                session_id,
+                path = %target.display(),
+                "Recording session"
+            );
+            slot.insert(Mutex::new(recorder));
+            Ok(())
+        }
+    }
+}
+
+/// Stop recording `session_id` and flush the file. `None` when not recording.
+pub(crate) fn stop_recording(
+    state: &AppState,
+    session_id: &str,
+) -> Option<Result<RecordingInfo, String>> {
+    let (_, recorder) = state.recordings.remove(session_id)?;
+    Some(recorder.into_inner().finish())
+}
+
+/// Stop and flush any recording for a closing session, logging failures.
+pub(crate) fn finish_for_session(state: &AppState, session_id: &str) {
+    if let Some(Err(e)) = stop_recording(state, session_id) {
+        tracing::warn!(source = "recording", session_id, "{e}");
+    }
+}
+
+/// Append a reader-loop chunk when `session_id` is being recorded.
+/// A write failure stops the recording.
+pub(crate) fn record_output(state: &AppState, session_id: &str, at: Instant, data: &str) {
+    let failed = match state.recordings.get(session_id) {
+        Some(recorder) => recorder.lock().record(at, data).err(),
+        None => return,
+    };
+    if let Some(e) = failed {
+        tracing::warn!(
+            source = "recording",
+            session_id,
+            "Recording write failed, stopping: {e}"
+        );
+        finish_for_session(state, session_id);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Start an asciinema recording of a session's output to `path`.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn start_session_recording(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    path: String,
+) -> Result<(), String> {
+    start_recording(&state, &session_id, &path)
+}
+
+/// Stop a session's recording and flush the `.cast` file.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn stop_session_recording(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+) -> Result<RecordingInfo, String> {
+    stop_recording(&state, &session_id)
+        .unwrap_or_else(|| Err(format!("Session {session_id} is not being recorded")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn cast_file_has_header_and_timed_output_frames() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        let mut rec = CastRecorder::create(&path, 120, 40).unwrap();
+        let t0 = rec.started;
+        rec.record(t0 + Duration::from_millis(250), "$ ls\r\n")
+            .unwrap();
+        rec.record(
+            t0 + Duration::from_millis(1500),
+            "\u{1b}[32mok\u{1b}[0m\r\n",
+        )
+        .unwrap();
+        let info = rec.finish().unwrap();
+        assert_eq!(info.frames, 2);
+        assert_eq!(info.duration_secs, 1.5);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 120);
+        assert_eq!(lines[0]["height"], 40);
+        assert!(lines[0]["timestamp"].as_i64().unwrap() > 0);
+        assert_eq!(lines[1], serde_json::json!([0.25, "o", "$ ls\r\n"]));
+        assert_eq!(
+            lines[2],
+            serde_json::json!([1.5, "o", "\u{1b}[32mok\u{1b}[0m\r\n"])
+        );
+    }
+
+    #[test]
+    fn record_output_without_recording_is_noop_and_stop_reports_none() {
+        let state = crate::state::tests_support::make_test_app_state();
+        record_output(&state, "nope", Instant::now(), "data");
+        assert!(stop_recording(&state, "nope").is_none());
+        assert!(start_recording(&state, "nope", "relative.cast").is_err());
+    }
+
+    #[test]
+    fn recording_target_must_be_inside_allowed_roots() {
+        let repo = tempfile::tempdir().unwrap();
+        let elsewhere = tempfile::tempdir().unwrap();
+        let roots = vec![repo.path().to_path_buf()];
+
+        assert!(validate_recording_target(&repo.path().join("demo.cast"), &roots).is_ok());
+        let err =
+            validate_recording_target(&elsewhere.path().join("demo.cast"), &roots).unwrap_err();
+        assert!(err.contains("Access denied"), "{err}");
+        let escape = repo.path().join("..").join("demo.cast");
+        assert!(validate_recording_target(&escape, &roots).is_err());
+    }
+
+    #[test]
+    fn recorder_never_overwrites_an_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "keep me").unwrap();
+        let err = CastRecorder::create(&path, 80, 24).err().unwrap();
+        assert!(err.contains("Failed to create recording"), "{err}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+    }
+}
@@ -0,0 +1,321 @@
+//! Remote-access reachability diagnosis.
+//!
+//! `get_mcp_status` reports a bare `reachable` flag; this module explains *why*
+//! remote access does or doesn't work: whether the TCP listener is bound, on
+//! which address/port, whether it answers on loopback and on the preferred
+//! external IP, whether a failed probe looks like a firewall (timeout) or a
+//! closed port (refused), and whether auth is configured.
+
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::config::{AuthConfig, ServerConfig};
+use crate::state::AppState;
+
+/// Connect timeout for each self-test probe. Same budget as `get_mcp_status`.
+const PROBE_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Result of a single TCP connect probe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProbeOutcome {
+    /// Connection accepted.
+    Ok,
+    /// Actively refused — nothing listening, or the bind address excludes it.
+    Refused,
+    /// No answer within the timeout — typical of a firewall dropping packets.
+    Timeout,
+    /// Any other socket error.
+    Error,
+    /// Probe not attempted (server not bound, no external IP, ...).
+    Skipped,
+}
+
+/// Overall conclusion of the diagnosis, most actionable condition first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RemoteVerdict {
+    Disabled,
+    NotBound,
+    LoopbackUnreachable,
+    NoExternalIp,
+    FirewallLikely,
+    ExternalRefused,
+    AuthMissing,
+    Ok,
+}
+
+/// Structured remote-access report returned by `diagnose_remote_access`.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct RemoteAccessDiagnosis {
+    pub(crate) enabled: bool,
+    pub(crate) bound: bool,
+    /// Host the listener binds to (`0.0.0.0`, `[::]`, a specific IP).
+    pub(crate) bind_address: String,
+    pub(crate) configured_port: u16,
+    /// Port actually bound; may differ from `configured_port` when it was busy.
+    pub(crate) bound_port: Option<u16>,
+    pub(crate) loopback: ProbeOutcome,
+    pub(crate) external_ip: Option<String>,
+    pub(crate) external: ProbeOutcome,
+    pub(crate) auth_configured: bool,
+    pub(crate) lan_auth_bypass: bool,
+    pub(crate) verdict: RemoteVerdict,
+    /// Human-readable next step for the verdict.
+    pub(crate) advice: String,
+}
+
+/// Classify a connect attempt to `addr`.
+fn probe(addr: SocketAddr) -> ProbeOutcome {
+    match TcpStream::connect_timeout(&addr, PROBE_TIMEOUT) {
+        Ok(_) => ProbeOutcome::Ok,
+        Err(e) => match e.kind() {
+            std::io::ErrorKind::ConnectionRefused => ProbeOutcome::Refused,
+            std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => ProbeOutcome::Timeout,
+            _ => ProbeOutcome::Error,
+        },
+    }
+}
+
+fn socket_addr(ip: &str, port: u16) -> Option<SocketAddr> {
+    let s = if ip.contains(':') {
+        format!("[{ip}]:{port}")
+    } else {
+        format!("{ip}:{port}")
+    };
+    s.parse().ok()
+}
+
+/// Build the report from already-resolved inputs. `probe_fn` is only called
+/// when the listener is bound, so an unbound server can never be mistaken for
+/// a firewall problem.
+pub(crate) fn build_diagnosis(
+    server: &ServerConfig,
+    auth: &AuthConfig,
+    bound_port: u16,
+    bind_ip: Result<Option<std::net::IpAddr>, String>,
+    preferred_ip: Option<String>,
+    probe_fn: impl Fn(SocketAddr) -> ProbeOutcome,
+) -> RemoteAccessDiagnosis {
+    let bound = server.enabled && bound_port != 0;
+    let bind_address = match &bind_ip {
+        Ok(Some(ip)) => ip.to_string(),
+        Ok(None) if server.ipv6_enabled => "::".to_string(),
+        Ok(None) => "0.0.0.0".to_string(),
+        Err(_) => "127.0.0.1".to_string(),
+    };
+    // A specific non-loopback bind address never answers on 127.0.0.1.
+    let listens_on_loopback = match &bind_ip {
+        Ok(Some(ip)) => ip.is_loopback(),
+        Ok(None) | Err(_) => true,
+    };
+    // The external probe targets the bind IP itself when one is configured.
+    let external_ip = match &bind_ip {
+        Ok(Some(ip)) if !ip.is_loopback() => Some(ip.to_string()),
+        Ok(Some(_)) | Err(_) => None,
+        Ok(None) => preferred_ip,
+    };
+
+    let (loopback, external) = if bound {
+        let loopback = if listens_on_loopback {
+            socket_addr("127.0.0.1", bound_port).map_or(ProbeOutcome::Error, &probe_fn)
+        } else {
+            ProbeOutcome::Skipped
+        };
+        let external = match &external_ip {
+            Some(ip) => socket_addr(ip, bound_port).map_or(ProbeOutcome::Error, &probe_fn),
+            None => ProbeOutcome::Skipped,
+        };
+        (loopback, external)
+    } else {
+        (ProbeOutcome::Skipped, ProbeOutcome::Skipped)
+    };
+
+    let auth_configured = !auth.password_hash.is_empty();
+    let port_note = if bound && bound_port != server.port {
+        format!(
+            " (port {} was busy, bound {bound_port} instead)",
+            server.port
+        )
+    } else {
+        String::new()
+    };
+
+    let (verdict, advice) = if !server.enabled {
+        (
+            RemoteVerdict::Disabled,
+            "Remote access is turned off. Enable it in Settings → Services.".to_string(),
+        )
+    } else if !bound {
+        (
+            RemoteVerdict::NotBound,
+            format!(
+                "Remote access is enabled but the server is not listening on {bind_address}:{}. \
+                 Another program may hold the port — pick a different port and save.",
+                server.port
+            ),
+        )
+    } else if loopback != ProbeOutcome::Ok && loopback != ProbeOutcome::Skipped {
+        (
+            RemoteVerdict::LoopbackUnreachable,
+            format!(
+                "The server reports port {bound_port} bound but does not answer locally. \
+                 Restart remote access."
+            ),
+        )
+    } else if external_ip.is_none() {
+        (
+            RemoteVerdict::NoExternalIp,
+            "No non-loopback network address found — connect to Wi-Fi/LAN or Tailscale."
+                .to_string(),
+        )
+    } else if external == ProbeOutcome::Timeout {
+        (
+            RemoteVerdict::FirewallLikely,
+            format!(
+                "Connections to {}:{bound_port} time out — a firewall is likely dropping them. \
+                 Allow incoming connections for TUICommander.{port_note}",
+                external_ip.as_deref().unwrap_or_default()
+            ),
+        )
+    } else if external != ProbeOutcome::Ok {
+        (
+            RemoteVerdict::ExternalRefused,
+            format!(
+                "Connections to {}:{bound_port} are refused — check the bind address \
+                 ({bind_address}).{port_note}",
+                external_ip.as_deref().unwrap_or_default()
+            ),
+        )
+    } else if !auth_configured && !auth.lan_auth_bypass {
+        (
+            RemoteVerdict::AuthMissing,
+            "The server is reachable but no password is set — remote clients cannot log in."
+                .to_string(),
+        )
+    } else {
+        (
+            RemoteVerdict::Ok,
+            format!(
+                "Reachable at {}:{bound_port}.{port_note}",
+                external_ip.as_deref().unwrap_or_default()
+            ),
+        )
+    };
+
+    RemoteAccessDiagnosis {
+        enabled: server.enabled,
+        bound,
+        bind_address,
+        configured_port: server.port,
+        bound_port: bound.then_some(bound_port),
+        loopback,
+        external_ip,
+        external,
+        auth_configured,
+        lan_auth_bypass: auth.lan_auth_bypass,
+        verdict,
+        advice,
+    }
+}
+
+/// Implementation shared between the Tauri command and HTTP handler.
+/// Probes run on a blocking thread so the async runtime is never stalled.
+pub(crate) async fn diagnose_remote_access_impl(
+    state: &Arc<AppState>,
+) -> Result<RemoteAccessDiagnosis, String> {
+    let (server, auth) = {
+        let cfg = state.config.read();
+        (cfg.services.server.clone(), cfg.services.auth.clone())
+    };
+    let bound_port = state.remote_port.load(std::sync::atomic::Ordering::Relaxed);
+    tokio::task::spawn_blocking(move || {
+        let bind_ip = crate::configured_bind_ip(&server);
+        let preferred_ip =
+            crate::pick_preferred_ip(crate::get_local_ips_with_config(server.ipv6_enabled));
+        build_diagnosis(&server, &auth, bound_port, bind_ip, preferred_ip, probe)
+    })
+    .await
+    .map_err(|e| format!("diagnosis task failed: {e}"))
+}
+
+/// Explain remote-access reachability (Tauri command).
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn diagnose_remote_access(
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Result<RemoteAccessDiagnosis, String> {
+    diagnose_remote_access_impl(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_server() -> ServerConfig {
+        ServerConfig {
+            enabled: true,
+            port: 9877,
+            ..ServerConfig::default()
+        }
+    }
+
+    #[test]
+    fn not_bound_reports_not_bound_without_probing() {
+        let diag = build_diagnosis(
+            &enabled_server(),
+            &AuthConfig::default(),
+            0,
+            Ok(None),
+            Some("192.168.1.10".to_string()),
+            |_| panic!("must not probe an unbound server"),
+        );
+        assert!(!diag.bound);
+        assert_eq!(diag.verdict, RemoteVerdict::NotBound);
+        assert_eq!(diag.loopback, ProbeOutcome::Skipped);
+        assert_eq!(diag.external, ProbeOutcome::Skipped);
+        assert_eq!(diag.bound_port, None);
+    }
+
+    #[test]
+    fn external_timeout_is_reported_as_firewall() {
+        let auth = AuthConfig {
+            password_hash: "hash".to_string(),
+            ..AuthConfig::default()
+        };
+        let diag = build_diagnosis(
+            &enabled_server(),
+            &auth,
+            9877,
+            Ok(None),
+            Some("192.168.1.10".to_string()),
+            |addr| {
+                if addr.ip().is_loopback() {
+                    ProbeOutcome::Ok
+                } else {
+                    ProbeOutcome::Timeout
+                }
+            },
+        );
+        assert_eq!(diag.loopback, ProbeOutcome::Ok);
+        assert_eq!(diag.external, ProbeOutcome::Timeout);
+        assert_eq!(diag.verdict, RemoteVerdict::FirewallLikely);
+        assert!(diag.auth_configured);
+    }
+
+    #[test]
+    fn disabled_server_short_circuits() {
+        let diag = build_diagnosis(
+            &ServerConfig::default(),
+            &AuthConfig::default(),
+            0,
+            Ok(None),
+            None,
+            |_| panic!("must not probe when disabled"),
+        );
+        assert_eq!(diag.verdict, RemoteVerdict::Disabled);
+    }
+}
@@ -3,6 +3,7 @@ use crate::state::AppEvent;
 use ignore::gitignore::Gitignore;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -18,6 +19,10 @@ pub(crate) enum EventCategory {
     GitState,
     /// Non-.git, non-gitignored files — 1500ms debounce
     WorkingTree,
+    /// A worktree added or removed (see `is_worktree_list_change`) — 500ms
+    /// debounce. Never returned by `classify_path`: the same event also counts
+    /// as `GitState`, so it is detected separately.
+    Worktrees,
     /// `.git/objects`, `.git/config`, gitignored files — skip entirely
     Noise,
 }
@@ -115,7 +120,7 @@ impl EventCategory {
     fn delay(&self) -> Duration {
         match self {
             Self::Head => HEAD_DEBOUNCE,
-            Self::GitState => GIT_STATE_DEBOUNCE,
+            Self::GitState | Self::Worktrees => GIT_STATE_DEBOUNCE,
             Self::WorkingTree => WORKING_TREE_DEBOUNCE,
             Self::Noise => Duration::ZERO,
         }
@@ -132,6 +137,7 @@ pub(crate) struct CategoryEmitter {
     head: Mutex<Option<tokio::task::AbortHandle>>,
     git_state: Mutex<Option<tokio::task::AbortHandle>>,
     working_tree: Mutex<Option<tokio::task::AbortHandle>>,
+    worktrees: Mutex<Option<tokio::task::AbortHandle>>,
 }
 
 impl CategoryEmitter {
@@ -141,6 +147,7 @@ impl CategoryEmitter {
             head: Mutex::new(None),
             git_state: Mutex::new(None),
             working_tree: Mutex::new(None),
+            worktrees: Mutex::new(None),
         }
     }
 
@@ -166,6 +173,7 @@ impl CategoryEmitter {
             EventCategory::Head => &self.head,
             EventCategory::GitState => &self.git_state,
             EventCategory::WorkingTree => &self.working_tree,
+            EventCategory::Worktrees => &self.worktrees,
             EventCategory::Noise => return,
         };
         let mut guard = slot.lock();
@@ -191,6 +199,40 @@ pub(crate) struct RepoChangedPayload {
 pub(crate) struct HeadChangedPayload {
     pub repo_path: String,
     pub branch: String,
+    /// Whether the frontend should make `branch` the repo's active branch
+    /// (see `head_change_activates`).
+    pub activate: bool,
+}
+
+/// Payload emitted when a worktree is added or removed, carrying the current
+/// branch → worktree path map (same shape as `get_worktree_paths`).
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct WorktreesChangedPayload {
+    pub repo_path: String,
+    pub worktrees: HashMap<String, String>,
+}
+
+/// Whether an event path denotes a worktree being added or removed: a direct
+/// child of `.git/worktrees/` (git's per-worktree admin dir), that directory
+/// itself (created by the first `worktree add`, removed by the last prune), or
+/// a direct child of the repo's external worktrees directory.
+pub(crate) fn is_worktree_list_change(
+    path: &Path,
+    git_dir: &Path,
+    worktrees_dir: Option<&Path>,
+) -> bool {
+    let admin_dir = git_dir.join("worktrees");
+    path == admin_dir
+        || path.parent() == Some(admin_dir.as_path())
+        || worktrees_dir.is_some_and(|d| path.parent() == Some(d))
+}
+
+/// The repo's configured worktrees directory when it lives outside the repo
+/// (`Sibling`, `AppDir`, or a `worktrees_dir` override) and already exists.
+/// Inside-repo storage is covered by the working-tree watch.
+fn external_worktrees_dir(repo: &Path, app_worktrees_dir: &Path) -> Option<PathBuf> {
+    let dir = crate::worktree::resolve_worktree_dir_for_repo(repo, app_worktrees_dir);
+    (dir.is_dir() && !dir.starts_with(repo)).then_some(dir)
 }
 
 /// Build a `Gitignore` matcher from the repo's `.gitignore` file.
@@ -285,6 +327,24 @@ fn head_target_changed(
     true
 }
 
+/// Whether a HEAD move in `repo_path`'s main checkout should make its branch
+/// the repo's active one, per the server-side active selection. `false` only
+/// when this repo is selected with one of its linked worktrees: the main
+/// checkout switching branches (a `git checkout` in another terminal) must not
+/// yank the sidebar away from the worktree the user is looking at.
+pub(crate) fn head_change_activates(
+    selection: Option<&crate::config::ActiveSelection>,
+    repo_path: &str,
+) -> bool {
+    match selection {
+        Some(sel) if sel.repo_path == repo_path => sel
+            .worktree_path
+            .as_deref()
+            .is_none_or(|wt| wt == repo_path),
+        _ => true,
+    }
+}
+
 /// Collect every working-tree directory that should receive a watch, pruning
 /// the always-excluded dirs (`.git`, `node_modules`, `target`, …) and any
 /// gitignored paths via `ignore::WalkBuilder`. Used by the Linux watch path to
@@ -395,6 +455,7 @@ pub(crate) fn start_watching(repo_path: &str, state: &Arc<AppState>) -> Result<(
     // sub-watches below are skipped until it actually exists.
     let git_dir = crate::git::resolve_git_dir(&repo).unwrap_or_else(|| repo.join(".git"));
     let gitignore = Arc::new(parking_lot::RwLock::new(build_gitignore(&repo)));
+    let worktrees_dir = external_worktrees_dir(&repo, &state.worktrees_dir);
 
     let repo_path_owned = repo_path.to_string();
     #[cfg(feature = "desktop")]
@@ -421,6 +482,7 @@ pub(crate) fn start_watching(repo_path: &str, state: &Arc<AppState>) -> Result<(
     let repo_for_cb = repo.clone();
     let git_dir_for_cb = git_dir.clone();
     let gitignore_cb = Arc::clone(&gitignore);
+    let worktrees_dir_for_cb = worktrees_dir.clone();
 
     let mut watcher = notify::recommended_watcher(
         move |result: Result<notify::Event, notify::Error>| {
@@ -452,6 +514,7 @@ pub(crate) fn start_watching(repo_path: &str, state: &Arc<AppState>) -> Result<(
             let mut has_head = false;
             let mut has_git_state = false;
             let mut has_working_tree = false;
+            let mut has_worktrees = false;
 
             for path in &event.paths {
                 let category = classify_path(path, &repo_for_cb, &git_dir_for_cb, &gi);
@@ -459,7 +522,37 @@ pub(crate) fn start_watching(repo_path: &str, state: &Arc<AppState>) -> Result<(
                     EventCategory::Head => has_head = true,
                     EventCategory::GitState => has_git_state = true,
                     EventCategory::WorkingTree => has_working_tree = true,
-                    EventCategory::Noise => {}
+                    EventCategory::Worktrees | EventCategory::Noise => {}
+                }
+                if is_worktree_list_change(
+                    path,
+                    &git_dir_for_cb,
+                    worktrees_dir_for_cb.as_deref(),
+                ) {
+                    has_worktrees = true;
+                }
+
+                // `.git/worktrees` is only sub-watched if it existed at startup;
+                // the first `git worktree add` creates it, so watch it now or
+                // later adds/removes go unseen until the watcher restarts.
+                #[cfg(target_os = "linux")]
+                if matches!(
+                    event.kind,
+                    notify::EventKind::Create(notify::event::CreateKind::Folder)
+                ) && *path == git_dir_for_cb.join("worktrees")
+                {
+                    let st = Arc::clone(&state_cb);
+                    let rp = repo_path_owned.clone();
+                    let admin_dir = path.clone();
+                    rt_for_cb.spawn_blocking(move || {
+                        let Some(h) = st.repo_watchers.get(&rp).map(|r| r.value().clone()) else {
+                            return;
+                        };
+                        if let Err(e) = h.watcher.lock().watch(&admin_dir, RecursiveMode::Recursive)
+                        {
+                            tracing::warn!(source = "repo_watcher", path = %admin_dir.display(), "Failed to watch .git/worktrees: {e}");
+                        }
+                    });
                 }
 
                 // Linux watches each working-tree dir non-recursively (issue #82),
@@ -528,16 +621,23 @@ pub(crate) fn start_watching(repo_path: &str, state: &Arc<AppState>) -> Result<(
                         Some(_) => {}
                     }
                     if let Some(branch) = crate::git::read_branch_from_head(&repo) {
-                        tracing::debug!(source = "repo_watcher", path = %repo_path, "Emit head-changed");
+                        let activate =
+                            head_change_activates(st.active_selection.read().as_ref(), &repo_path);
+                        tracing::debug!(source = "repo_watcher", path = %repo_path, activate, "Emit head-changed");
                         let _ = bus.send(AppEvent::HeadChanged {
                             repo_path: repo_path.clone(),
                             branch: branch.clone(),
+                            activate,
                         });
                         #[cfg(feature = "desktop")]
                         if let Some(ref handle) = h {
                             let _ = handle.emit(
                                 "head-changed",
-                                HeadChangedPayload { repo_path, branch },
+                                HeadChangedPayload {
+                                    repo_path,
+                                    branch,
+                                    activate,
+                                },
                             );
                         }
                     }
@@ -578,6 +678,43 @@ pub(crate) fn start_watching(repo_path: &str, state: &Arc<AppState>) -> Result<(
                 });
             }
 
+            if has_worktrees {
+                let repo_path = repo_path_owned.clone();
+                let bus = event_bus.clone();
+                #[cfg(feature = "desktop")]
+                let h = handle.clone();
+                let st = Arc::clone(&state_cb);
+                emitter.trigger(&EventCategory::Worktrees, move || {
+                    // `get_worktree_paths` runs `git worktree list`; keep the
+                    // subprocess off the runtime worker driving the debounce.
+                    tokio::task::spawn_blocking(move || {
+                        st.invalidate_repo_caches(&repo_path);
+                        let worktrees = match crate::worktree::get_worktree_paths(repo_path.clone()) {
+                            Ok(w) => w,
+                            Err(e) => {
+                                tracing::debug!(source = "repo_watcher", path = %repo_path, "Skip worktrees-changed: {e}");
+                                return;
+                            }
+                        };
+                        tracing::debug!(source = "repo_watcher", path = %repo_path, count = worktrees.len(), "Emit worktrees-changed");
+                        let _ = bus.send(AppEvent::WorktreesChanged {
+                            repo_path: repo_path.clone(),
+                            worktrees: worktrees.clone(),
+                        });
+                        #[cfg(feature = "desktop")]
+                        if let Some(ref handle) = h {
+                            let _ = handle.emit(
+                                "worktrees-changed",
+                                WorktreesChangedPayload {
+                                    repo_path,
+                                    worktrees,
+                                },
+                            );
+                        }
+                    });
+                });
+            }
+
             if has_working_tree && !has_git_state {
                 let repo_path = repo_path_owned.clone();
                 let bus = event_bus.clone();
@@ -616,6 +753,14 @@ pub(crate) fn start_watching(repo_path: &str, state: &Arc<AppState>) -> Result<(
         .watch(repo.as_path(), RecursiveMode::Recursive)
         .map_err(|e| format!("Failed to watch repo: {e}"))?;
 
+    // An external worktrees directory sits outside the repo root, so no watch
+    // above covers it. Only its direct children (one dir per worktree) matter.
+    if let Some(dir) = &worktrees_dir
+        && let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive)
+    {
+        tracing::warn!(source = "repo_watcher", path = %dir.display(), "Failed to watch worktrees dir: {e}");
+    }
+
     // Linux (inotify): a recursive watch makes `notify` walk the entire tree and
     // add a watch per directory — including `node_modules`, `target`, and
     // `.git/objects` — so every churn in those subtrees floods our callback and
@@ -711,6 +856,45 @@ pub(crate) fn stop_repo_watcher(repo_path: String, app_handle: AppHandle) {
     stop_watching(&repo_path, &state);
 }
 
+/// The server's active repo + checkout, if one was set.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn get_active_selection(
+    state: tauri::State<'_, std::sync::Arc<AppState>>,
+) -> Option<crate::config::ActiveSelection> {
+    state.active_selection.read().clone()
+}
+
+/// Record which repo and checkout the UI shows; persisted in UI prefs.
+/// `worktree_path` equal to `repo_path` (or absent) means the main checkout.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn set_active_selection(
+    state: tauri::State<'_, std::sync::Arc<AppState>>,
+    repo_path: String,
+    worktree_path: Option<String>,
+) -> Result<(), String> {
+    set_active_selection_impl(&state, repo_path, worktree_path)
+}
+
+pub(crate) fn set_active_selection_impl(
+    state: &AppState,
+    repo_path: String,
+    worktree_path: Option<String>,
+) -> Result<(), String> {
+    let worktree_path = worktree_path.filter(|wt| *wt != repo_path);
+    let selection = Some(crate::config::ActiveSelection {
+        repo_path,
+        worktree_path,
+    });
+    if *state.active_selection.read() == selection {
+        return Ok(());
+    }
+    crate::config::save_active_selection(selection.clone())?;
+    *state.active_selection.write() = selection;
+    Ok(())
+}
+
 #[cfg(feature = "desktop")]
 #[tauri::command]
 pub(crate) fn set_hot_repos(paths: Vec<String>, state: tauri::State<'_, std::sync::Arc<AppState>>) {
@@ -908,6 +1092,7 @@ mod tests {
     fn test_category_delays() {
         assert_eq!(EventCategory::Head.delay(), Duration::from_millis(200));
         assert_eq!(EventCategory::GitState.delay(), Duration::from_millis(500));
+        assert_eq!(EventCategory::Worktrees.delay(), Duration::from_millis(500));
         assert_eq!(
             EventCategory::WorkingTree.delay(),
             Duration::from_millis(1500)
@@ -998,6 +1183,171 @@ mod tests {
         assert_eq!(git_count.load(Ordering::Relaxed), 1);
     }
 
+    #[test]
+    fn test_is_worktree_list_change() {
+        let git = Path::new("/repo/.git");
+        let ext = Path::new("/repo__wt");
+        assert!(is_worktree_list_change(
+            Path::new("/repo/.git/worktrees"),
+            git,
+            None
+        ));
+        assert!(is_worktree_list_change(
+            Path::new("/repo/.git/worktrees/feat"),
+            git,
+            None
+        ));
+        assert!(is_worktree_list_change(
+            Path::new("/repo__wt/feat"),
+            git,
+            Some(ext)
+        ));
+        // Per-worktree admin files and worktree contents are not add/remove.
+        assert!(!is_worktree_list_change(
+            Path::new("/repo/.git/worktrees/feat/HEAD"),
+            git,
+            None
+        ));
+        assert!(!is_worktree_list_change(
+            Path::new("/repo__wt/feat/src"),
+            git,
+            Some(ext)
+        ));
+        assert!(!is_worktree_list_change(
+            Path::new("/repo__wt/feat"),
+            git,
+            None
+        ));
+        assert!(!is_worktree_list_change(
+            Path::new("/repo/.git/refs/heads/main"),
+            git,
+            Some(ext)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_worktree_dir_creation_triggers_debounced_callback() {
+        let tmp = tempfile::tempdir().unwrap();
+        let git_dir = tmp.path().join(".git");
+        let admin_dir = git_dir.join("worktrees");
+        std::fs::create_dir_all(&admin_dir).unwrap();
+
+        let emitter = Arc::new(CategoryEmitter::new(tokio::runtime::Handle::current()));
+        let counter = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let (em, c, gd) = (Arc::clone(&emitter), Arc::clone(&counter), git_dir.clone());
+        let mut watcher =
+            notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
+                let Ok(event) = res else { return };
+                if event
+                    .paths
+                    .iter()
+                    .any(|p| is_worktree_list_change(p, &gd, None))
+                {
+                    let c = Arc::clone(&c);
+                    em.trigger(&EventCategory::Worktrees, move || {
+                        c.fetch_add(1, Ordering::Relaxed);
+                    });
+                }
+            })
+            .unwrap();
+        watcher
+            .watch(&admin_dir, RecursiveMode::NonRecursive)
+            .unwrap();
+
+        // A burst of adds (as `git worktree add` writes several entries)
+        // collapses into a single emit.
+        std::fs::create_dir(admin_dir.join("feat-a")).unwrap();
+        std::fs::create_dir(admin_dir.join("feat-b")).unwrap();
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(counter.load(Ordering::Relaxed), 0, "must wait for debounce");
+        tokio::time::sleep(Duration::from_millis(1300)).await;
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+    }
+
+    /// Wait until a `WorktreesChanged` for `repo` satisfies `done`, skipping
+    /// other events (and earlier emits of the same burst). False on timeout.
+    async fn wait_for_worktrees_changed(
+        rx: &mut tokio::sync::broadcast::Receiver<AppEvent>,
+        repo: &str,
+        done: impl Fn(&HashMap<String, String>) -> bool,
+    ) -> bool {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                match rx.recv().await {
+                    Ok(AppEvent::WorktreesChanged {
+                        repo_path,
+                        worktrees,
+                    }) if repo_path == repo && done(&worktrees) => return true,
+                    Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(_) => return false,
+                }
+            }
+        })
+        .await
+        .unwrap_or(false)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_start_watching_emits_worktrees_changed() {
+        let cfg = tempfile::tempdir().unwrap();
+        let _guard = crate::config::set_config_dir_override(cfg.path().to_path_buf());
+        let tmp = tempfile::tempdir().unwrap();
+        // Canonical so event paths (e.g. macOS /private/var) match the git dir.
+        let root = tmp.path().canonicalize().unwrap();
+        let repo = root.join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        let git = |args: &[&str]| {
+            crate::git_cli::git_cmd(&repo)
+                .args(args)
+                .run()
+                .unwrap_or_else(|e| panic!("git {args:?}: {e}"));
+        };
+        git(&["init", "-q"]);
+        git(&[
+            "-c",
+            "user.name=T",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "init",
+        ]);
+        assert!(!repo.join(".git/worktrees").exists());
+
+        let state = Arc::new(crate::state::tests_support::make_test_app_state());
+        let mut rx = state.event_bus.subscribe();
+        let repo_path = repo.to_string_lossy().to_string();
+        start_watching(&repo_path, &state).unwrap();
+
+        // The first add creates `.git/worktrees`, which was not watched at start.
+        let wt_a = root.join("wt-a").to_string_lossy().to_string();
+        git(&["worktree", "add", "-q", "-b", "feat-a", &wt_a]);
+        assert!(
+            wait_for_worktrees_changed(&mut rx, &repo_path, |w| w.contains_key("feat-a")).await,
+            "worktrees-changed after the first add"
+        );
+
+        // A later add only touches `.git/worktrees/<name>`: seen only if the
+        // watcher picked up the new admin dir (Linux late watch).
+        let wt_b = root.join("wt-b").to_string_lossy().to_string();
+        git(&["worktree", "add", "-q", "-b", "feat-b", &wt_b]);
+        assert!(
+            wait_for_worktrees_changed(&mut rx, &repo_path, |w| w.contains_key("feat-b")).await,
+            "worktrees-changed after the second add"
+        );
+
+        git(&["worktree", "remove", &wt_a]);
+        assert!(
+            wait_for_worktrees_changed(&mut rx, &repo_path, |w| !w.contains_key("feat-a")).await,
+            "worktrees-changed after a remove"
+        );
+
+        stop_watching(&repo_path, &state);
+    }
+
     #[test]
     fn test_cold_debounce_constant() {
         assert_eq!(COLD_WORKING_TREE_DEBOUNCE, Duration::from_secs(15));
@@ -1085,6 +1435,44 @@ mod tests {
         assert_ne!(on_main, on_feat);
     }
 
+    #[test]
+    fn active_selection_persists_and_gates_head_change_activation() {
+        let dir = tempfile::tempdir().unwrap();
+        let _guard = crate::config::set_config_dir_override(dir.path().to_path_buf());
+        let state = crate::state::tests_support::make_test_app_state();
+
+        // Nothing selected: HEAD moves activate as before.
+        assert!(head_change_activates(
+            state.active_selection.read().as_ref(),
+            "/repo"
+        ));
+
+        set_active_selection_impl(&state, "/repo".into(), Some("/wt/feat".into())).unwrap();
+        let persisted = crate::config::load_ui_prefs().active_selection;
+        assert_eq!(
+            persisted,
+            Some(crate::config::ActiveSelection {
+                repo_path: "/repo".into(),
+                worktree_path: Some("/wt/feat".into()),
+            })
+        );
+        // A layout save from the frontend (no selection) must not clear it.
+        crate::config::save_ui_prefs(crate::config::UIPrefsConfig::default()).unwrap();
+        assert_eq!(crate::config::load_ui_prefs().active_selection, persisted);
+
+        // Worktree selected: the main checkout's HEAD move must not re-activate.
+        let sel = state.active_selection.read().clone();
+        assert!(!head_change_activates(sel.as_ref(), "/repo"));
+        // Other repos are unaffected.
+        assert!(head_change_activates(sel.as_ref(), "/other"));
+
+        // Selecting the main checkout (worktree == repo) re-enables activation.
+        set_active_selection_impl(&state, "/repo".into(), Some("/repo".into())).unwrap();
+        let sel = state.active_selection.read().clone();
+        assert_eq!(sel.as_ref().unwrap().worktree_path, None);
+        assert!(head_change_activates(sel.as_ref(), "/repo"));
+    }
+
     #[test]
     fn test_resolve_head_target_packed_ref_falls_back_to_ref_path() {
         // Loose ref absent (packed) → fall back to "ref: <path>", still distinguishes branches.
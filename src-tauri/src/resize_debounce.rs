@@ -0,0 +1,167 @@
+//! Per-session PTY resize debouncing.
+//!
+//! Dragging a window edge fires a resize per animation frame; applying each
+//! one makes the child reflow (and full-screen TUIs redraw) dozens of times a
+//! second. Requests only record the latest size; a single flush thread per
+//! burst waits until no newer size arrived for the quiet period, then applies
+//! the last one. Because a request that lands after the flush took its size
+//! starts a new flush, the final size is always applied.
+
+use crate::state::AppState;
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+#[derive(Clone, Copy)]
+struct PendingResize {
+    rows: u16,
+    cols: u16,
+    generation: u64,
+}
+
+/// Result of checking a session's pending resize after a quiet period.
+#[derive(Debug, PartialEq, Eq)]
+enum Settle {
+    /// No newer request arrived: apply this size.
+    Apply(u16, u16),
+    /// A newer request arrived (its generation); keep waiting.
+    Moved(u64),
+    /// Nothing pending (session cleared).
+    Gone,
+}
+
+#[derive(Default)]
+pub(crate) struct ResizeDebouncer {
+    pending: DashMap<String, PendingResize>,
+    next_generation: AtomicU64,
+}
+
+impl ResizeDebouncer {
+    /// Record the latest requested size. Returns the request's generation and
+    /// whether the caller must start a flush (none was in flight).
+    fn request(&self, session_id: &str, rows: u16, cols: u16) -> (u64, bool) {
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+        let size = PendingResize {
+            rows,
+            cols,
+            generation,
+        };
+        let start_flush = self.pending.insert(session_id.to_string(), size).is_none();
+        (generation, start_flush)
+    }
+
+    /// After a quiet period: take the pending size if it is still the one
+    /// with generation `seen`.
+    fn settle(&self, session_id: &str, seen: u64) -> Settle {
+        let settled = self
+            .pending
+            .remove_if(session_id, |_, p| p.generation == seen);
+        if let Some((_, p)) = settled {
+            return Settle::Apply(p.rows, p.cols);
+        }
+        match self.pending.get(session_id) {
+            Some(p) => Settle::Moved(p.generation),
+            None => Settle::Gone,
+        }
+    }
+
+    /// Wait out the burst started by generation `first`, then apply the last size.
+    fn flush(&self, session_id: &str, first: u64, quiet: Duration, apply: impl FnOnce(u16, u16)) {
+        let mut seen = first;
+        loop {
+            std::thread::sleep(quiet);
+            match self.settle(session_id, seen) {
+                Settle::Apply(rows, cols) => return apply(rows, cols),
+                Settle::Moved(generation) => seen = generation,
+                Settle::Gone => return,
+            }
+        }
+    }
+
+    /// Drop a session's pending resize (called when it exits).
+    pub(crate) fn clear_session(&self, session_id: &str) {
+        self.pending.remove(session_id);
+    }
+}
+
+/// Apply `rows`×`cols` to `session_id` through `apply`, coalescing bursts per
+/// `resize_debounce_ms` (0 = apply immediately). Returns without waiting;
+/// errors from a deferred apply are logged.
+pub(crate) fn debounce_resize(
+    state: &Arc<AppState>,
+    session_id: &str,
+    rows: u16,
+    cols: u16,
+    apply: fn(&AppState, &str, u16, u16) -> Result<(), String>,
+) -> Result<(), String> {
+    let quiet_ms = state.config.read().resize_debounce_ms;
+    if quiet_ms == 0 {
+        return apply(state, session_id, rows, cols);
+    }
+    let (generation, start_flush) = state.resize_debouncer.request(session_id, rows, cols);
+    if start_flush {
+        let state = state.clone();
+        let session_id = session_id.to_string();
+        std::thread::spawn(move || {
+            state.resize_debouncer.flush(
+                &session_id,
+                generation,
+                Duration::from_millis(quiet_ms),
+                |rows, cols| {
+                    if let Err(e) = apply(&state, &session_id, rows, cols) {
+                        tracing::warn!(source = "pty", session_id = %session_id, "Debounced resize failed: {e}");
+                    }
+                },
+            );
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rapid_resizes_apply_only_the_last_size() {
+        let debouncer = Arc::new(ResizeDebouncer::default());
+        let applied = Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        let (first, start) = debouncer.request("s", 24, 80);
+        assert!(start);
+        let flusher = {
+            let debouncer = debouncer.clone();
+            let applied = applied.clone();
+            std::thread::spawn(move || {
+                debouncer.flush("s", first, Duration::from_millis(50), |r, c| {
+                    applied.lock().push((r, c));
+                });
+            })
+        };
+        // Drag: more sizes inside the quiet period only update the pending one.
+        for (rows, cols) in [(25, 90), (30, 100), (40, 120)] {
+            let (_, start) = debouncer.request("s", rows, cols);
+            assert!(!start, "a flush is already in flight");
+        }
+        flusher.join().unwrap();
+
+        assert_eq!(*applied.lock(), vec![(40, 120)]);
+        // The burst is over: the next request starts a new flush.
+        assert!(debouncer.request("s", 50, 50).1);
+    }
+
+    #[test]
+    fn settle_waits_for_the_latest_generation() {
+        let debouncer = ResizeDebouncer::default();
+        let (g1, _) = debouncer.request("s", 24, 80);
+        let (g2, _) = debouncer.request("s", 30, 100);
+        assert_eq!(debouncer.settle("s", g1), Settle::Moved(g2));
+        assert_eq!(debouncer.settle("s", g2), Settle::Apply(30, 100));
+        assert_eq!(debouncer.settle("s", g2), Settle::Gone);
+
+        debouncer.request("t", 10, 10);
+        debouncer.clear_session("t");
+        assert_eq!(debouncer.settle("t", 0), Settle::Gone);
+    }
+}
@@ -0,0 +1,156 @@
+//! Session transcript export (`export_session_transcript`).
+//!
+//! `txt` exports the clean VT100 log lines plus the visible screen (the same
+//! text `GET /sessions/{id}/output?format=text` serves); `html` renders the raw
+//! output ring buffer through [`crate::ansi::to_html`] so colors survive.
+//! Both honor `max_export_bytes` (oldest output dropped, with a marker) and,
+//! when `redact_secrets` is on, the same secret redaction transcripts use.
+
+use serde::Deserialize;
+#[cfg(feature = "desktop")]
+use std::sync::Arc;
+#[cfg(feature = "desktop")]
+use tauri::State;
+
+use crate::AppState;
+
+/// Export file format.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ExportFormat {
+    Txt,
+    Html,
+}
+
+/// Drop the oldest bytes so `text` fits in `max_bytes`, starting the kept part
+/// at a line boundary and prefixing a marker. `0` means no limit.
+pub(crate) fn truncate_oldest(text: &str, max_bytes: usize) -> String {
+    if max_bytes == 0 || text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut cut = text.len() - max_bytes;
+    while !text.is_char_boundary(cut) {
+        cut += 1;
+    }
+    // Don't start mid-line (or mid escape sequence for html): skip to the next
+    // newline when there is one in the kept part.
+    if let Some(nl) = text[cut..].find('\n') {
+        cut += nl + 1;
+    }
+    format!("[… {cut} earlier bytes truncated …]\n{}", &text[cut..])
+}
+
+/// Apply the size limit, redaction and format conversion to captured output.
+pub(crate) fn render_export(
+    raw: &str,
+    format: ExportFormat,
+    max_bytes: usize,
+    redact: bool,
+) -> String {
+    let text = truncate_oldest(raw, max_bytes);
+    let text = if redact {
+        crate::ai_agent::tools::redact_secrets(&text)
+    } else {
+        text
+    };
+    match format {
+        ExportFormat::Txt => text,
+        ExportFormat::Html => crate::ansi::to_html(text.as_bytes()),
+    }
+}
+
+/// Captured output for `session_id` in the form `format` starts from.
+fn captured_output(
+    state: &AppState,
+    session_id: &str,
+    format: ExportFormat,
+) -> Result<String, String> {
+    let not_found = || format!("Session {session_id} not found");
+    match format {
+        ExportFormat::Txt => {
+            let vt_log = state.vt_log_buffers.get(session_id).ok_or_else(not_found)?;
+            let buf = vt_log.lock();
+            let (log_lines, _) = buf.lines_since_owned(buf.oldest_offset(), usize::MAX);
+            let mut lines: Vec<String> = log_lines.iter().map(|ll| ll.text()).collect();
+            lines.extend(buf.screen_rows().into_iter().filter(|r| !r.is_empty()));
+            Ok(lines.join("\n"))
+        }
+        ExportFormat::Html => {
+            let ring = state.output_buffers.get(session_id).ok_or_else(not_found)?;
+            let (bytes, _) = ring.lock().read_last(usize::MAX);
+            Ok(String::from_utf8_lossy(&bytes).into_owned())
+        }
+    }
+}
+
+/// Export a session's transcript, applying `max_export_bytes` and `redact_secrets`.
+/// Works on tombstoned sessions too, while their buffers are still kept.
+pub(crate) fn export_session(
+    state: &AppState,
+    session_id: &str,
+    format: ExportFormat,
+) -> Result<String, String> {
+    let raw = captured_output(state, session_id, format)?;
+    let (max_bytes, redact) = {
+        let cfg = state.config.read();
+        (cfg.max_export_bytes as usize, cfg.redact_secrets)
+    };
+    Ok(render_export(&raw, format, max_bytes, redact))
+}
+
+/// Export a session transcript as `txt` or `html` (returned as a string).
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn export_session_transcript(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    format: ExportFormat,
+) -> Result<String, String> {
+    export_session(&state, &session_id, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversized_export_keeps_newest_lines_with_marker() {
+        let raw: String = (0..1000).map(|i| format!("line {i}\n")).collect();
+        let out = render_export(&raw, ExportFormat::Txt, 100, false);
+        let (marker, rest) = out.split_once('\n').unwrap();
+        assert!(marker.starts_with("[… ") && marker.ends_with(" earlier bytes truncated …]"));
+        assert!(rest.len() <= 100, "kept {} bytes", rest.len());
+        assert!(
+            rest.starts_with("line "),
+            "kept part must start on a line: {rest:?}"
+        );
+        assert!(rest.ends_with("line 999\n"));
+        assert!(!out.contains("line 0\n"));
+
+        assert_eq!(
+            render_export("short\n", ExportFormat::Txt, 100, false),
+            "short\n"
+        );
+        assert_eq!(render_export(&raw, ExportFormat::Txt, 0, false), raw);
+    }
+
+    #[test]
+    fn export_redacts_secrets_in_txt_and_html() {
+        let raw = "$ env\r\nOPENAI_API_KEY=sk-abc123def456ghi789jkl012mno345\r\n";
+        let txt = render_export(raw, ExportFormat::Txt, 0, true);
+        assert!(txt.contains("[REDACTED]"), "{txt}");
+        assert!(!txt.contains("sk-abc123"));
+
+        let html = render_export(
+            &format!("\x1b[31m{raw}\x1b[0m"),
+            ExportFormat::Html,
+            0,
+            true,
+        );
+        assert!(html.contains("[REDACTED]"), "{html}");
+        assert!(!html.contains("sk-abc123"));
+
+        let plain = render_export(raw, ExportFormat::Txt, 0, false);
+        assert!(plain.contains("sk-abc123"));
+    }
+}
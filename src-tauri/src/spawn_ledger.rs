@@ -0,0 +1,427 @@
+//! Persistent ledger of processes spawned into PTY sessions.
+//!
+//! A crash skips `close_pty_core`, so agents can keep running with no session
+//! left to own them. Scanning the process table can't tell those apart from
+//! the user's own shells, so instead every spawn is recorded here (PID, process
+//! name, start time, owning session and TUIC instance) in `spawned_pids.json`
+//! under the data dir. Reconciling that ledger against live sessions and the
+//! process table yields the orphans; the name + start time check keeps a
+//! recycled PID from being mistaken for one of ours, and the owner's start
+//! time does the same for a recycled instance PID.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const LEDGER_FILE: &str = "spawned_pids.json";
+
+/// Serializes read-modify-write cycles on the ledger file.
+static LEDGER_LOCK: Mutex<()> = Mutex::new(());
+
+/// PIDs already recorded (or being recorded) by this process, with their
+/// start time — lets the foreground-agent poller call
+/// [`record_spawn_in_background`] every tick without touching the disk. A
+/// PID whose start time changed was recycled and gets recorded again.
+static RECORDED: Mutex<Option<HashMap<u32, Option<u64>>>> = Mutex::new(None);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SpawnRecord {
+    pub(crate) pid: u32,
+    pub(crate) session_id: String,
+    pub(crate) name: Option<String>,
+    /// Opaque process start time (clock ticks on Linux, µs on macOS).
+    pub(crate) started: Option<u64>,
+    /// PID of the TUICommander instance that spawned it.
+    pub(crate) owner_pid: u32,
+    /// Start time of that instance, so a recycled `owner_pid` doesn't count
+    /// as a live owner. `None` in ledgers written before it was recorded.
+    #[serde(default)]
+    pub(crate) owner_started: Option<u64>,
+    /// Unix seconds when the record was written.
+    pub(crate) recorded_at: u64,
+}
+
+/// What the OS currently reports for a PID.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ProcessIdentity {
+    pub(crate) name: Option<String>,
+    pub(crate) started: Option<u64>,
+}
+
+impl ProcessIdentity {
+    /// Look up a running process. `None` means it has exited.
+    pub(crate) fn probe(pid: u32) -> Option<Self> {
+        let identity = Self {
+            name: crate::pty::process_name_from_pid(pid),
+            started: process_start_time(pid),
+        };
+        (identity.name.is_some() || identity.started.is_some()).then_some(identity)
+    }
+
+    /// True if this is still the process `record` was written for. A record
+    /// with neither a name nor a start time can't be verified and never matches.
+    fn is_same_process(&self, record: &SpawnRecord) -> bool {
+        match (self.started, record.started) {
+            (Some(now), Some(then)) => now == then,
+            _ => match (&self.name, &record.name) {
+                (Some(now), Some(then)) => now == then,
+                _ => false,
+            },
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn process_start_time(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // comm (field 2) may contain spaces; starttime is field 22, i.e. the
+    // 20th field after the closing paren.
+    let after_comm = &stat[stat.rfind(')')? + 1..];
+    after_comm.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(target_os = "macos")]
+fn process_start_time(pid: u32) -> Option<u64> {
+    // SAFETY: proc_bsdinfo is plain data; proc_pidinfo writes at most `size`
+    // bytes into it and returns the number written.
+    let mut info: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_bsdinfo>() as i32;
+    let ret = unsafe {
+        libc::proc_pidinfo(
+            pid as i32,
+            libc::PROC_PIDTBSDINFO,
+            0,
+            (&raw mut info).cast(),
+            size,
+        )
+    };
+    (ret == size).then(|| info.pbi_start_tvsec * 1_000_000 + info.pbi_start_tvusec)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn process_start_time(_pid: u32) -> Option<u64> {
+    None
+}
+
+fn ledger_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(LEDGER_FILE)
+}
+
+fn load(data_dir: &Path) -> Vec<SpawnRecord> {
+    std::fs::read_to_string(ledger_path(data_dir))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(data_dir: &Path, records: &[SpawnRecord]) {
+    let result = serde_json::to_string_pretty(records)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(ledger_path(data_dir), json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        tracing::warn!(source = "spawn_ledger", "Failed to write spawn ledger: {e}");
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Start time of this TUIC instance.
+fn own_start_time() -> Option<u64> {
+    static OWN: OnceLock<Option<u64>> = OnceLock::new();
+    *OWN.get_or_init(|| process_start_time(std::process::id()))
+}
+
+/// Mark `pid` as recorded. False if the same process already was.
+fn claim(pid: u32) -> bool {
+    let started = process_start_time(pid);
+    let mut recorded = RECORDED.lock();
+    let recorded = recorded.get_or_insert_with(HashMap::new);
+    if recorded.get(&pid) == Some(&started) {
+        return false;
+    }
+    recorded.insert(pid, started);
+    true
+}
+
+/// Let a later process reusing `pid` be recorded.
+fn unclaim(pid: u32) {
+    if let Some(recorded) = RECORDED.lock().as_mut() {
+        recorded.remove(&pid);
+    }
+}
+
+/// Record a process spawned for `session_id`. No-op for unknown or
+/// already-recorded PIDs; dead entries are pruned on the way.
+pub(crate) fn record_spawn(data_dir: &Path, pid: Option<u32>, session_id: &str) {
+    let Some(pid) = pid else { return };
+    if claim(pid) {
+        write_record(data_dir, pid, session_id);
+    }
+}
+
+/// [`record_spawn`] for polled paths (the foreground-process getter): the
+/// ledger read-modify-write runs on a background thread so the poll never
+/// waits on disk.
+pub(crate) fn record_spawn_in_background(data_dir: &Path, pid: u32, session_id: &str) {
+    if !claim(pid) {
+        return;
+    }
+    let data_dir = data_dir.to_path_buf();
+    let session_id = session_id.to_string();
+    let spawned = std::thread::Builder::new()
+        .name("spawn-ledger".to_string())
+        .spawn(move || write_record(&data_dir, pid, &session_id));
+    if let Err(e) = spawned {
+        tracing::warn!(
+            source = "spawn_ledger",
+            "Failed to start ledger writer: {e}"
+        );
+        unclaim(pid);
+    }
+}
+
+fn write_record(data_dir: &Path, pid: u32, session_id: &str) {
+    let Some(identity) = ProcessIdentity::probe(pid) else {
+        // Already gone — let a later spawn reusing the PID be recorded.
+        unclaim(pid);
+        return;
+    };
+
+    let _guard = LEDGER_LOCK.lock();
+    let mut records = load(data_dir);
+    records.retain(|r| {
+        if r.pid == pid {
+            return false;
+        }
+        let alive = ProcessIdentity::probe(r.pid).is_some();
+        if !alive {
+            unclaim(r.pid);
+        }
+        alive
+    });
+    records.push(SpawnRecord {
+        pid,
+        session_id: session_id.to_string(),
+        name: identity.name,
+        started: identity.started,
+        owner_pid: std::process::id(),
+        owner_started: own_start_time(),
+        recorded_at: now_secs(),
+    });
+    save(data_dir, &records);
+}
+
+/// Result of matching the ledger against the process table.
+#[derive(Debug, Default)]
+pub(crate) struct Reconciled {
+    /// Records to keep in the ledger (still-running processes).
+    pub(crate) keep: Vec<SpawnRecord>,
+    /// Subset of `keep` with no live owner.
+    pub(crate) orphans: Vec<SpawnRecord>,
+}
+
+/// Split `records` into live-and-owned, orphaned, and gone.
+///
+/// A record is dropped when its process exited or the PID now belongs to a
+/// different process. A running process is owned when its session is still
+/// live in this instance, or when another TUIC instance that recorded it is
+/// still running (same PID and start time); otherwise it is an orphan.
+pub(crate) fn reconcile(
+    records: Vec<SpawnRecord>,
+    live_sessions: &HashSet<String>,
+    self_pid: u32,
+    probe: impl Fn(u32) -> Option<ProcessIdentity>,
+) -> Reconciled {
+    let mut out = Reconciled::default();
+    for record in records {
+        let Some(identity) = probe(record.pid) else {
+            continue;
+        };
+        if !identity.is_same_process(&record) {
+            continue;
+        }
+        let owned = if record.owner_pid == self_pid {
+            live_sessions.contains(&record.session_id)
+        } else {
+            probe(record.owner_pid).is_some_and(|owner| {
+                match (owner.started, record.owner_started) {
+                    (Some(now), Some(then)) => now == then,
+                    _ => true,
+                }
+            })
+        };
+        if !owned {
+            out.orphans.push(record.clone());
+        }
+        out.keep.push(record);
+    }
+    out
+}
+
+/// Reconcile the on-disk ledger, persist the pruned result, and return orphans.
+pub(crate) fn find_orphans(data_dir: &Path, live_sessions: &HashSet<String>) -> Vec<SpawnRecord> {
+    let _guard = LEDGER_LOCK.lock();
+    let records = load(data_dir);
+    let before: Vec<u32> = records.iter().map(|r| r.pid).collect();
+    let reconciled = reconcile(
+        records,
+        live_sessions,
+        std::process::id(),
+        ProcessIdentity::probe,
+    );
+    if reconciled.keep.len() != before.len() {
+        // Dropped records exited or had their PID recycled.
+        for pid in before {
+            if !reconciled.keep.iter().any(|r| r.pid == pid) {
+                unclaim(pid);
+            }
+        }
+        save(data_dir, &reconciled.keep);
+    }
+    reconciled.orphans
+}
+
+/// Drop a PID from the ledger (after it has been killed).
+pub(crate) fn forget(data_dir: &Path, pid: u32) {
+    let _guard = LEDGER_LOCK.lock();
+    let mut records = load(data_dir);
+    let before = records.len();
+    records.retain(|r| r.pid != pid);
+    if records.len() != before {
+        save(data_dir, &records);
+    }
+    unclaim(pid);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const SELF_PID: u32 = 100;
+
+    fn record(pid: u32, session: &str, name: &str, started: u64, owner: u32) -> SpawnRecord {
+        SpawnRecord {
+            pid,
+            session_id: session.to_string(),
+            name: Some(name.to_string()),
+            started: Some(started),
+            owner_pid: owner,
+            owner_started: None,
+            recorded_at: 0,
+        }
+    }
+
+    fn identity(name: &str, started: u64) -> ProcessIdentity {
+        ProcessIdentity {
+            name: Some(name.to_string()),
+            started: Some(started),
+        }
+    }
+
+    #[test]
+    fn reconcile_classifies_recorded_pids() {
+        let records = vec![
+            record(10, "live", "zsh", 1, SELF_PID), // owned by live session
+            record(11, "closed", "claude", 2, SELF_PID), // session gone → orphan
+            record(12, "closed", "zsh", 3, SELF_PID), // exited → dropped
+            record(13, "closed", "zsh", 4, SELF_PID), // PID recycled → dropped
+            record(14, "old", "codex", 5, 200),     // crashed instance → orphan
+            record(15, "other", "zsh", 6, 300),     // other live instance → kept
+            SpawnRecord {
+                owner_started: Some(7),
+                ..record(16, "other", "zsh", 8, 300) // same live instance → kept
+            },
+            SpawnRecord {
+                owner_started: Some(1),
+                ..record(17, "gone", "claude", 9, 300) // owner PID recycled → orphan
+            },
+        ];
+        let table: HashMap<u32, ProcessIdentity> = [
+            (10, identity("zsh", 1)),
+            (11, identity("claude", 2)),
+            (13, identity("zsh", 99)),
+            (14, identity("codex", 5)),
+            (15, identity("zsh", 6)),
+            (16, identity("zsh", 8)),
+            (17, identity("claude", 9)),
+            (300, identity("tuicommander", 7)),
+        ]
+        .into_iter()
+        .collect();
+        let live: HashSet<String> = ["live".to_string()].into_iter().collect();
+
+        let out = reconcile(records, &live, SELF_PID, |pid| table.get(&pid).cloned());
+
+        let pids = |v: &[SpawnRecord]| v.iter().map(|r| r.pid).collect::<Vec<_>>();
+        assert_eq!(pids(&out.keep), vec![10, 11, 14, 15, 16, 17]);
+        assert_eq!(pids(&out.orphans), vec![11, 14, 17]);
+    }
+
+    #[test]
+    fn identity_falls_back_to_name_without_start_time() {
+        let mut rec = record(1, "s", "claude", 0, SELF_PID);
+        rec.started = None;
+        let same = ProcessIdentity {
+            name: Some("claude".into()),
+            started: None,
+        };
+        let other = ProcessIdentity {
+            name: Some("vim".into()),
+            started: None,
+        };
+        assert!(same.is_same_process(&rec));
+        assert!(!other.is_same_process(&rec));
+        rec.name = None;
+        assert!(
+            !same.is_same_process(&rec),
+            "unverifiable record must not match"
+        );
+    }
+
+    #[test]
+    fn ledger_round_trips_and_forgets() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let own = std::process::id();
+        record_spawn(dir.path(), Some(own), "sess-a");
+        let records = load(dir.path());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].pid, own);
+        assert_eq!(records[0].session_id, "sess-a");
+
+        // Our own process with its session gone is reported as an orphan.
+        let orphans = find_orphans(dir.path(), &HashSet::new());
+        assert_eq!(orphans.len(), 1);
+        let live: HashSet<String> = ["sess-a".to_string()].into_iter().collect();
+        assert!(find_orphans(dir.path(), &live).is_empty());
+
+        forget(dir.path(), own);
+        assert!(load(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn claim_is_released_for_exited_and_recycled_pids() {
+        // Not a real process, so its probed start time is `None`.
+        let pid = u32::MAX - 7;
+        assert!(claim(pid));
+        assert!(!claim(pid), "same process is recorded once");
+
+        // A different start time means the PID now belongs to another process.
+        RECORDED
+            .lock()
+            .get_or_insert_with(HashMap::new)
+            .insert(pid, Some(1));
+        assert!(claim(pid));
+
+        unclaim(pid);
+        assert!(claim(pid));
+        unclaim(pid);
+    }
+}
@@ -24,7 +24,12 @@ use tauri::{AppHandle, Emitter};
 #[serde(tag = "event", content = "payload")]
 pub enum AppEvent {
     #[serde(rename = "head-changed")]
-    HeadChanged { repo_path: String, branch: String },
+    HeadChanged {
+        repo_path: String,
+        branch: String,
+        /// See `repo_watcher::head_change_activates`.
+        activate: bool,
+    },
     #[serde(rename = "repo-changed")]
     RepoChanged { repo_path: String },
     #[serde(rename = "session-created")]
@@ -72,6 +77,14 @@ pub enum AppEvent {
         branch: String,
         worktree_path: String,
     },
+    /// A worktree was added or removed (including externally, e.g.
+    /// `git worktree remove` in a terminal) — carries the current branch →
+    /// path map so the frontend can reconcile its sidebar.
+    #[serde(rename = "worktrees-changed")]
+    WorktreesChanged {
+        repo_path: String,
+        worktrees: HashMap<String, String>,
+    },
     /// A peer agent registered for inter-agent messaging
     #[serde(rename = "peer-registered")]
     PeerRegistered { tuic_session: String, name: String },
@@ -133,6 +146,34 @@ pub enum AppEvent {
         llm_used: bool,
         llm_model: Option<String>,
     },
+    /// Input was written but no output followed within `pty_wedge_timeout_secs`
+    /// while the foreground process stayed the same.
+    #[serde(rename = "session-possibly-wedged")]
+    SessionPossiblyWedged {
+        session_id: String,
+        foreground: Option<String>,
+        foreground_pid: Option<u32>,
+        silent_secs: u64,
+    },
+    /// Remote access was turned off after `distinct_ips` failed auth within
+    /// `window_secs` (`auto_disable_remote_after_failures`).
+    #[serde(rename = "remote-auto-disabled")]
+    RemoteAutoDisabled {
+        distinct_ips: usize,
+        window_secs: u64,
+    },
+    /// The saved repository order changed (`reorder_repositories`).
+    #[serde(rename = "repositories-changed")]
+    RepositoriesChanged { repo_order: Vec<String> },
+    /// A PTY spawn failed transiently and will be retried after `delay_ms`.
+    #[serde(rename = "session-spawn-retry")]
+    SessionSpawnRetry {
+        attempt: u32,
+        max_retries: u32,
+        delay_ms: u64,
+        error: String,
+        tuic_session: Option<String>,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -179,6 +220,11 @@ pub(crate) struct SessionState {
     /// Detected agent type, if known
     #[serde(skip_serializing_if = "Option::is_none")]
     pub agent_type: Option<String>,
+    /// Foreground job (agent or command) the session's shell is running,
+    /// sampled by the silence timer; `pty::classify_exit` reports a shell that
+    /// exits with 128 + N after it as that job's death. Internal, not serialized.
+    #[serde(skip)]
+    pub foreground_pid: Option<u32>,
     /// True when this agent has native-hook instrumentation enabled, so heuristic
     /// question-detection is suppressed (awaiting comes from OSC 7770 instead).
     /// Resolved from config when `agent_type` is set; internal, not serialized.
@@ -199,6 +245,9 @@ pub(crate) struct SessionState {
     /// Current progress value (0-100); None when no active progress bar
     #[serde(skip_serializing_if = "Option::is_none")]
     pub progress: Option<u8>,
+    /// Latest terminal title set by the program via OSC 0/2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terminal_title: Option<String>,
     /// Number of active sub-tasks (local agents, bash, background tasks) from ›› mode line
     #[serde(skip_serializing_if = "is_zero")]
     pub active_sub_tasks: u32,
@@ -238,6 +287,7 @@ impl PartialEq for SessionState {
             && self.active_sub_tasks == other.active_sub_tasks
             && self.last_prompt == other.last_prompt
             && self.progress == other.progress
+            && self.terminal_title == other.terminal_title
             && self.suggested_actions == other.suggested_actions
             && self.slash_menu_items == other.slash_menu_items
             && self.choice_prompt == other.choice_prompt
@@ -245,6 +295,31 @@ impl PartialEq for SessionState {
     }
 }
 
+/// Why a session's process ended (see `pty::classify_exit`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ExitReason {
+    /// Normal exit with a status code.
+    Exited,
+    /// Terminated by a signal we did not send (crash, external kill).
+    Signaled,
+    /// SIGKILL matched to a kernel OOM-killer entry (Linux, best-effort).
+    Oom,
+    /// We closed or killed the session (`close_pty_core` / `kill_pty_core`).
+    KilledByUs,
+}
+
+/// Exit classification kept for tombstoned sessions next to `exit_codes`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct ExitInfo {
+    pub(crate) reason: ExitReason,
+    /// Terminating signal number, when the process died from one.
+    pub(crate) signal: Option<i32>,
+    /// The process dumped core (`WCOREDUMP` of its wait status), i.e. it
+    /// crashed. False when core dumps are disabled (`ulimit -c 0`).
+    pub(crate) core_dumped: bool,
+}
+
 /// TTL for git operations (local disk): 60s fallback.
 /// Primary invalidation is event-driven via repo_watcher FSEvents.
 /// This TTL is a safety net for cases where the watcher misses an event.
@@ -582,6 +657,53 @@ impl OutputRingBuffer {
         self.read_last(to_read)
     }
 
+    /// Offset of the oldest byte still held in the buffer.
+    pub fn earliest_offset(&self) -> u64 {
+        self.total_written - std::cmp::min(self.total_written, self.capacity as u64)
+    }
+
+    /// Resume a stream from byte offset `since`.
+    /// Returns (bytes, offset of the first returned byte, gap). `gap` is true when
+    /// bytes after `since` were already evicted (or `since` is ahead of the
+    /// stream, e.g. a token from before a restart) — the replay then starts at
+    /// the earliest available offset instead.
+    pub fn read_resume(&self, since: u64) -> (Vec<u8>, u64, bool) {
+        let earliest = self.earliest_offset();
+        let (start, gap) = if since < earliest || since > self.total_written {
+            (earliest, true)
+        } else {
+            (since, false)
+        };
+        let (data, _) = self.read_last((self.total_written - start) as usize);
+        (data, start, gap)
+    }
+
+    /// Page forward from byte offset `from`, returning at most `max_bytes`.
+    /// Returns (bytes, offset of the first returned byte, gap) with the same gap
+    /// semantics as `read_resume`; the next page starts at `start + bytes.len()`.
+    pub fn read_range(&self, from: u64, max_bytes: usize) -> (Vec<u8>, u64, bool) {
+        let earliest = self.earliest_offset();
+        let (start, gap) = if from < earliest || from > self.total_written {
+            (earliest, true)
+        } else {
+            (from, false)
+        };
+        let behind = (self.total_written - start) as usize;
+        let to_read = behind.min(max_bytes);
+        if to_read == 0 {
+            return (Vec::new(), start, gap);
+        }
+        // `behind <= capacity`, so the start index is at most one wrap back.
+        let begin = (self.write_pos + self.capacity - behind) % self.capacity;
+        let mut result = Vec::with_capacity(to_read);
+        let first_chunk = (self.capacity - begin).min(to_read);
+        result.extend_from_slice(&self.buf[begin..begin + first_chunk]);
+        if first_chunk < to_read {
+            result.extend_from_slice(&self.buf[..to_read - first_chunk]);
+        }
+        (result, start, gap)
+    }
+
     /// Current total_written counter (bytes ever written, monotonically increasing).
     #[cfg(test)]
     pub fn total_written(&self) -> u64 {
@@ -756,10 +878,14 @@ pub struct PtySession {
     /// "C:\\Program Files\\Git\\bin\\bash.exe", "wsl.exe -d Ubuntu").
     /// Kept so `get_session_shell_family` can classify without re-resolving.
     pub shell: String,
+    /// Running token/cost totals fed by `TokenUsage` events from the reader.
+    pub(crate) token_usage: crate::output_parser::TokenUsageCounter,
 }
 
-/// Configuration for agent orchestration
+/// Default cap on concurrent PTY sessions (`max_concurrent_sessions`).
 pub(crate) const MAX_CONCURRENT_SESSIONS: usize = 50;
+/// Upper clamp for a configured `max_concurrent_sessions`.
+pub(crate) const MAX_CONCURRENT_SESSIONS_CEILING: usize = 1000;
 
 /// PTY subsystem metrics for observability.
 /// All counters use AtomicUsize for lock-free, zero-overhead-when-idle tracking.
@@ -893,6 +1019,10 @@ pub struct AppState {
     pub(crate) github_token_source: parking_lot::RwLock<crate::github_auth::TokenSource>,
     /// Circuit breaker for GitHub API calls
     pub(crate) github_circuit_breaker: crate::github::GitHubCircuitBreaker,
+    /// GitLab API token (GITLAB_TOKEN or `glab auth token`), resolved lazily
+    pub(crate) gitlab_token: parking_lot::RwLock<Option<String>>,
+    /// Circuit breaker for GitLab API calls
+    pub(crate) gitlab_circuit_breaker: crate::github::GitHubCircuitBreaker,
     /// Background GitHub poller task handle
     pub(crate) github_poller: parking_lot::Mutex<Option<crate::github_poller::GitHubPoller>>,
     /// Cached GitHub viewer login (authenticated user) for issue filtering.
@@ -917,6 +1047,8 @@ pub struct AppState {
     /// Behind RwLock so it can be regenerated at runtime (invalidating all sessions).
     pub(crate) session_token: parking_lot::RwLock<String>,
     pub(crate) auth_rate_limits: DashMap<std::net::IpAddr, (u32, Instant)>,
+    /// Last failed auth per client IP, for `auto_disable_remote_after_failures`.
+    pub(crate) auth_failure_ips: DashMap<std::net::IpAddr, Instant>,
     #[cfg(feature = "desktop")]
     pub(crate) app_handle: parking_lot::RwLock<Option<AppHandle>>,
     /// Plugin filesystem watchers: watch_id → (plugin_id, watcher)
@@ -1033,6 +1165,12 @@ pub struct AppState {
     /// post-mortem `session action=output` reads can return the real code.
     /// Reaped by `pty::spawn_tombstone_sweeper` alongside the output buffers.
     pub(crate) exit_codes: DashMap<String, i32>,
+    /// Exit classification for tombstoned sessions (session_id → info), set
+    /// alongside `exit_codes` and reaped with it.
+    pub(crate) exit_reasons: DashMap<String, ExitInfo>,
+    /// Active asciinema recordings (session_id → open `.cast` file).
+    /// Appended by the reader thread; see `recording`.
+    pub(crate) recordings: DashMap<String, Mutex<crate::recording::CastRecorder>>,
     /// Epoch-ms timestamp of last shell_state transition per session.
     /// Updated by `pty::try_shell_transition` on every successful CAS.
     /// Used by `session(status)` to compute idle_since_ms / busy_duration_ms.
@@ -1075,6 +1213,27 @@ pub struct AppState {
     pub(crate) tailscale_state: parking_lot::RwLock<crate::tailscale::TailscaleState>,
     /// Push notification subscription store
     pub(crate) push_store: crate::push::PushStore,
+    /// Per-(session, type) throttle + quiet hours for mobile push
+    pub(crate) notification_throttle: crate::notification_throttle::NotificationThrottle,
+    /// Per-session PTY resize coalescing (see `resize_debounce`).
+    pub(crate) resize_debouncer: crate::resize_debounce::ResizeDebouncer,
+    /// Window-focus gate for background GitHub polling (`set_poll_active`).
+    pub(crate) poll_activity: crate::github_poller::PollActivity,
+    /// Port the remote-access TCP listener actually bound (0 = not listening).
+    /// May differ from `services.server.port` when that port was busy.
+    pub(crate) remote_port: AtomicU16,
+    /// Authoritative active repo + checkout (`set_active_selection`), hydrated
+    /// from UI prefs at startup. The repo watcher consults it so a HEAD move
+    /// in a repo's main checkout doesn't re-activate that branch while one of
+    /// its worktrees is selected.
+    pub(crate) active_selection: parking_lot::RwLock<Option<crate::config::ActiveSelection>>,
+    /// Live PTY sessions mirrored to `sessions.json` (see `record_session_opened`).
+    pub(crate) session_records: DashMap<String, SessionRecord>,
+    /// Set when `session_records` changed since the last flush to disk.
+    pub(crate) session_records_dirty: AtomicBool,
+    /// Sessions that were alive when the app last quit, loaded at startup.
+    /// PTYs can't be reattached; the frontend offers to re-spawn them.
+    pub(crate) recoverable_sessions: parking_lot::RwLock<Vec<SessionRecord>>,
     /// When true, the desktop window is currently focused and the user is at
     /// their machine — suppress mobile push notifications to avoid duplicate
     /// alerts. Set to true on focus and at startup; set to false on blur or
@@ -1188,6 +1347,8 @@ impl AppState {
             github_token: parking_lot::RwLock::new(None),
             github_token_source: parking_lot::RwLock::new(Default::default()),
             github_circuit_breaker: crate::github::GitHubCircuitBreaker::new(),
+            gitlab_token: parking_lot::RwLock::new(None),
+            gitlab_circuit_breaker: crate::github::GitHubCircuitBreaker::new(),
             github_poller: parking_lot::Mutex::new(None),
             github_viewer_login: parking_lot::RwLock::new(None),
             github_rate_limit_remaining: std::sync::atomic::AtomicU32::new(u32::MAX),
@@ -1195,6 +1356,7 @@ impl AppState {
             ipc_started: std::sync::atomic::AtomicBool::new(false),
             session_token: parking_lot::RwLock::new(session_token),
             auth_rate_limits: DashMap::new(),
+            auth_failure_ips: DashMap::new(),
             #[cfg(feature = "desktop")]
             app_handle: parking_lot::RwLock::new(None),
             plugin_watchers: DashMap::new(),
@@ -1235,6 +1397,8 @@ impl AppState {
             shell_states: DashMap::new(),
             terminal_rows: DashMap::new(),
             exit_codes: DashMap::new(),
+            exit_reasons: DashMap::new(),
+            recordings: DashMap::new(),
             shell_state_since_ms: DashMap::new(),
             loaded_plugins: DashMap::new(),
             relay: RelayState::new(),
@@ -1257,6 +1421,14 @@ impl AppState {
                 crate::tailscale::TailscaleState::NotInstalled,
             ),
             push_store,
+            notification_throttle: Default::default(),
+            resize_debouncer: Default::default(),
+            poll_activity: Default::default(),
+            remote_port: std::sync::atomic::AtomicU16::new(0),
+            active_selection: parking_lot::RwLock::new(None),
+            session_records: DashMap::new(),
+            session_records_dirty: AtomicBool::new(false),
+            recoverable_sessions: parking_lot::RwLock::new(Vec::new()),
             desktop_window_focused: std::sync::atomic::AtomicBool::new(true),
             server_start_time: std::time::Instant::now(),
             term_aliases: DashMap::new(),
@@ -1566,11 +1738,15 @@ pub(crate) struct GitCacheState {
     pub(crate) git_status: GitCache<crate::github::GitHubStatus>,
     pub(crate) git_panel_context: GitCache<crate::git::GitPanelContext>,
     pub(crate) worktree_paths: GitCache<std::collections::HashMap<String, String>>,
+    pub(crate) repo_stats: GitCache<crate::git::RepoStats>,
     /// Repos that returned null from GitHub GraphQL (not found / no access).
     /// Keyed by "owner/name", value is the cooldown expiry time.
     /// Excluded from batch queries until the cooldown expires (1 hour).
     /// NOT a TTL value cache — kept as a plain `DashMap` set with custom expiry.
     pub(crate) github_repo_cooldown: DashMap<String, Instant>,
+    /// Ahead/behind counts per repo, validated against the local and remote
+    /// ref SHAs on every read (see `github::cached_ahead_behind`), so no TTL.
+    pub(crate) ahead_behind: DashMap<String, crate::github::AheadBehindEntry>,
     /// Count of entries evicted by TTL expiry (watcher-miss observability).
     /// Shared across all git caches; surfaced in the cpu_watchdog snapshot.
     pub(crate) ttl_fallbacks: Arc<AtomicU64>,
@@ -1587,7 +1763,9 @@ impl GitCacheState {
             git_status: build_git_cache(GIT_CACHE_TTL, Arc::clone(&ttl_fallbacks)),
             git_panel_context: build_git_cache(GIT_CACHE_TTL, Arc::clone(&ttl_fallbacks)),
             worktree_paths: build_git_cache(GIT_CACHE_TTL, Arc::clone(&ttl_fallbacks)),
+            repo_stats: build_git_cache(GIT_CACHE_TTL, Arc::clone(&ttl_fallbacks)),
             github_repo_cooldown: DashMap::new(),
+            ahead_behind: DashMap::new(),
             ttl_fallbacks,
         }
     }
@@ -1605,6 +1783,8 @@ impl GitCacheState {
         self.git_status.invalidate_all();
         self.git_panel_context.invalidate_all();
         self.worktree_paths.invalidate_all();
+        self.repo_stats.invalidate_all();
+        self.ahead_behind.clear();
     }
 
     /// Invalidate caches for a specific repo path.
@@ -1618,9 +1798,144 @@ impl GitCacheState {
         self.git_status.invalidate(path);
         self.git_panel_context.invalidate(path);
         self.worktree_paths.invalidate(path);
+        self.repo_stats.invalidate(path);
+        self.ahead_behind.remove(path);
     }
 }
 
+// ---------------------------------------------------------------------------
+// Session registry — lightweight session metadata persisted across restarts
+// ---------------------------------------------------------------------------
+
+/// File in the config dir holding the live-session registry.
+pub(crate) const SESSIONS_FILE: &str = "sessions.json";
+
+/// How often a dirty session registry is flushed to disk. Coalesces bursts of
+/// creates/closes (e.g. restoring a workspace) into a single write.
+const SESSION_REGISTRY_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// What's needed to re-spawn a session after a restart. The PTY itself is gone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SessionRecord {
+    pub(crate) session_id: String,
+    /// Command line the PTY was spawned with: the agent binary and its args
+    /// for agent spawns, the shell otherwise.
+    pub(crate) command: String,
+    pub(crate) cwd: Option<String>,
+    pub(crate) worktree_branch: Option<String>,
+    /// Unix millis.
+    pub(crate) created_at: u64,
+    pub(crate) title: Option<String>,
+}
+
+impl AppState {
+    /// Add a freshly spawned session to the registry. Call after inserting
+    /// into `sessions`; no-op if the session is already gone. `command` is the
+    /// agent command line for agent spawns; `None` records the session's shell.
+    pub(crate) fn record_session_opened(&self, session_id: &str, command: Option<String>) {
+        let Some(entry) = self.sessions.get(session_id) else {
+            return;
+        };
+        let record = {
+            let session = entry.lock();
+            SessionRecord {
+                session_id: session_id.to_string(),
+                command: command.unwrap_or_else(|| session.shell.clone()),
+                cwd: session.cwd.clone(),
+                worktree_branch: session.worktree.as_ref().and_then(|w| w.branch.clone()),
+                created_at: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64,
+                title: session.display_name.clone(),
+            }
+        };
+        drop(entry);
+        self.session_records.insert(session_id.to_string(), record);
+        self.session_records_dirty
+            .store(true, std::sync::atomic::Ordering::Release);
+    }
+
+    /// Keep the registry title in sync with a session rename.
+    pub(crate) fn record_session_title(&self, session_id: &str, title: Option<String>) {
+        if let Some(mut record) = self.session_records.get_mut(session_id) {
+            record.title = title;
+            self.session_records_dirty
+                .store(true, std::sync::atomic::Ordering::Release);
+        }
+    }
+
+    /// Drop a closed session from the registry.
+    pub(crate) fn record_session_closed(&self, session_id: &str) {
+        if self.session_records.remove(session_id).is_some() {
+            self.session_records_dirty
+                .store(true, std::sync::atomic::Ordering::Release);
+        }
+    }
+
+    /// Write the registry if it changed since the last flush. The dirty flag is
+    /// cleared before the snapshot so a concurrent change is picked up by the
+    /// next flush; a failed write re-arms it. Writes are atomic (temp + rename).
+    pub(crate) fn flush_session_registry(&self) {
+        if !self
+            .session_records_dirty
+            .swap(false, std::sync::atomic::Ordering::AcqRel)
+        {
+            return;
+        }
+        let mut records: Vec<SessionRecord> = self
+            .session_records
+            .iter()
+            .map(|e| e.value().clone())
+            .collect();
+        records.sort_by_key(|r| r.created_at);
+        if let Err(e) = crate::config::save_json_config(SESSIONS_FILE, &records) {
+            tracing::warn!(
+                source = "session_registry",
+                "Failed to persist sessions: {e}"
+            );
+            self.session_records_dirty
+                .store(true, std::sync::atomic::Ordering::Release);
+        }
+    }
+
+    /// Load the registry written by the previous run into `recoverable_sessions`.
+    pub(crate) fn load_recoverable_sessions(&self) {
+        let records: Vec<SessionRecord> = crate::config::load_json_config(SESSIONS_FILE);
+        *self.recoverable_sessions.write() = records;
+    }
+
+    /// Spawn the background task that flushes the session registry.
+    pub(crate) fn spawn_session_registry_persister(state: Arc<AppState>) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(SESSION_REGISTRY_FLUSH_INTERVAL);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let s = state.clone();
+                if let Err(e) =
+                    tokio::task::spawn_blocking(move || s.flush_session_registry()).await
+                {
+                    tracing::warn!(
+                        source = "session_registry",
+                        "Registry flush task failed: {e}"
+                    );
+                }
+            }
+        });
+    }
+}
+
+/// Sessions that were alive when the app last quit (command, cwd, worktree,
+/// title), so the user can re-spawn them.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn list_recoverable_sessions(
+    state: tauri::State<'_, Arc<AppState>>,
+) -> Vec<SessionRecord> {
+    state.recoverable_sessions.read().clone()
+}
+
 /// Remove dead (closed-receiver) WebSocket senders for a session.
 ///
 /// Called on WS close so that disconnected clients don't accumulate
@@ -1698,8 +2013,17 @@ impl AppState {
         });
     }
 
-    /// Send a push notification to all mobile subscribers.
-    fn send_mobile_push(state: &Arc<AppState>, session_id: &str, body: &str) {
+    /// Send a push notification to all mobile subscribers, subject to the
+    /// notification throttle and quiet hours for `kind` (question/completion).
+    fn send_mobile_push(state: &Arc<AppState>, session_id: &str, kind: &str, body: &str) {
+        if !state.notification_throttle.allow_now(
+            crate::notification_throttle::NotificationChannel::Push,
+            session_id,
+            kind,
+        ) {
+            tracing::debug!(source = "push", session_id, kind, "Mobile push throttled");
+            return;
+        }
         let url = format!("/mobile/session/{session_id}");
         let config = state.config.read().clone();
         let subs = state.push_store.list();
@@ -1907,6 +2231,15 @@ impl AppState {
                                         .map(|v| v as u8);
                                 }
                             }
+                            "title" => {
+                                let title = parsed
+                                    .get("title")
+                                    .and_then(|v| v.as_str())
+                                    .map(|t| t.to_string());
+                                if s.terminal_title != title {
+                                    s.terminal_title = title;
+                                }
+                            }
                             _ => {}
                         }
                     })
@@ -1931,7 +2264,7 @@ impl AppState {
                     } else {
                         format!("{session_name}: {prompt}")
                     };
-                    Self::send_mobile_push(state, &sid, &body);
+                    Self::send_mobile_push(state, &sid, "question", &body);
                 }
             }
             AppEvent::PtyExit { session_id } => {
@@ -1958,9 +2291,11 @@ impl AppState {
                     Self::send_mobile_push(
                         state,
                         session_id,
+                        "completion",
                         &format!("{session_name}: completed"),
                     );
                 }
+                state.notification_throttle.clear_session(session_id);
             }
             // Global events don't affect per-session state
             AppEvent::HeadChanged { .. }
@@ -1971,6 +2306,7 @@ impl AppState {
             | AppEvent::McpToast { .. }
             | AppEvent::DirChanged { .. }
             | AppEvent::WorktreeCreated { .. }
+            | AppEvent::WorktreesChanged { .. }
             | AppEvent::PeerRegistered { .. }
             | AppEvent::PeerUnregistered { .. }
             | AppEvent::UiTab { .. }
@@ -1979,17 +2315,36 @@ impl AppState {
             | AppEvent::GitHubIssuesUpdate { .. }
             | AppEvent::CloseHtmlTabs { .. }
             | AppEvent::ScheduledJobCompleted { .. }
-            | AppEvent::DiffTriageProgress { .. } => {}
+            | AppEvent::DiffTriageProgress { .. }
+            | AppEvent::SessionPossiblyWedged { .. }
+            | AppEvent::RemoteAutoDisabled { .. }
+            | AppEvent::RepositoriesChanged { .. }
+            | AppEvent::SessionSpawnRetry { .. } => {}
         }
     }
 
+    /// Effective session cap: `max_concurrent_sessions` clamped to
+    /// `1..=MAX_CONCURRENT_SESSIONS_CEILING`. Read per call so config edits apply live.
+    pub(crate) fn max_sessions(&self) -> usize {
+        self.config
+            .read()
+            .max_concurrent_sessions
+            .clamp(1, MAX_CONCURRENT_SESSIONS_CEILING)
+    }
+
+    /// Whether another PTY session may be spawned under the configured cap.
+    pub(crate) fn can_spawn_session(&self) -> bool {
+        self.sessions.len() < self.max_sessions()
+    }
+
     /// Build orchestrator stats snapshot from current state.
     pub(crate) fn orchestrator_stats(&self) -> OrchestratorStats {
         let active = self.sessions.len();
+        let max_sessions = self.max_sessions();
         OrchestratorStats {
             active_sessions: active,
-            max_sessions: MAX_CONCURRENT_SESSIONS,
-            available_slots: MAX_CONCURRENT_SESSIONS.saturating_sub(active),
+            max_sessions,
+            available_slots: max_sessions.saturating_sub(active),
         }
     }
 
@@ -2976,6 +3331,75 @@ mod tests {
         assert_eq!(total, 0);
     }
 
+    #[test]
+    fn test_ring_buffer_resume_replays_only_newer_bytes() {
+        let mut rb = OutputRingBuffer::new(16);
+        rb.write(b"before ");
+        let token = rb.total_written;
+        rb.write(b"after");
+        let (data, start, gap) = rb.read_resume(token);
+        assert_eq!(&data, b"after");
+        assert_eq!(start, token);
+        assert!(!gap);
+        // Fully caught up: nothing to replay.
+        let (data, start, gap) = rb.read_resume(rb.total_written);
+        assert!(data.is_empty());
+        assert_eq!(start, 12);
+        assert!(!gap);
+    }
+
+    #[test]
+    fn test_ring_buffer_resume_reports_gap_when_evicted() {
+        let mut rb = OutputRingBuffer::new(8);
+        rb.write(b"0123456789AB");
+        assert_eq!(rb.earliest_offset(), 4);
+        let (data, start, gap) = rb.read_resume(2);
+        assert_eq!(&data, b"456789AB");
+        assert_eq!(start, 4);
+        assert!(gap);
+        // A token from a previous (longer) stream is also a gap.
+        let (_, start, gap) = rb.read_resume(100);
+        assert_eq!(start, 4);
+        assert!(gap);
+    }
+
+    #[test]
+    fn test_ring_buffer_read_range_pages_incrementally() {
+        let mut rb = OutputRingBuffer::new(16);
+        rb.write(b"0123456789");
+        let (data, start, gap) = rb.read_range(0, 4);
+        assert_eq!(&data, b"0123");
+        assert_eq!((start, gap), (0, false));
+        let next = start + data.len() as u64;
+        let (data, start, gap) = rb.read_range(next, 4);
+        assert_eq!(&data, b"4567");
+        assert_eq!((start, gap), (4, false));
+        // New output after the last poll is picked up from the next offset,
+        // including across the wrap point.
+        rb.write(b"ABCDEFGH");
+        let (data, start, gap) = rb.read_range(8, 100);
+        assert_eq!(&data, b"89ABCDEFGH");
+        assert_eq!((start, gap), (8, false));
+        // Caught up: empty page at the current offset.
+        let (data, start, gap) = rb.read_range(rb.total_written, 100);
+        assert!(data.is_empty());
+        assert_eq!((start, gap), (18, false));
+    }
+
+    #[test]
+    fn test_ring_buffer_read_range_reports_gap_when_evicted() {
+        let mut rb = OutputRingBuffer::new(8);
+        rb.write(b"0123456789AB");
+        let (data, start, gap) = rb.read_range(1, 3);
+        assert_eq!(&data, b"456");
+        assert_eq!(start, 4);
+        assert!(gap);
+        let (data, start, gap) = rb.read_range(500, 3);
+        assert_eq!(&data, b"456");
+        assert_eq!(start, 4);
+        assert!(gap);
+    }
+
     #[test]
     fn test_ring_buffer_large_write() {
         let mut rb = OutputRingBuffer::new(4);
@@ -3251,6 +3675,8 @@ mod tests {
             github_token: parking_lot::RwLock::new(None),
             github_token_source: parking_lot::RwLock::new(Default::default()),
             github_circuit_breaker: crate::github::GitHubCircuitBreaker::new(),
+            gitlab_token: parking_lot::RwLock::new(None),
+            gitlab_circuit_breaker: crate::github::GitHubCircuitBreaker::new(),
             github_poller: parking_lot::Mutex::new(None),
             github_viewer_login: parking_lot::RwLock::new(None),
             github_rate_limit_remaining: std::sync::atomic::AtomicU32::new(u32::MAX),
@@ -3258,6 +3684,7 @@ mod tests {
             ipc_started: std::sync::atomic::AtomicBool::new(false),
             session_token: parking_lot::RwLock::new(String::from("test-token")),
             auth_rate_limits: dashmap::DashMap::new(),
+            auth_failure_ips: dashmap::DashMap::new(),
             #[cfg(feature = "desktop")]
             app_handle: parking_lot::RwLock::new(None),
             plugin_watchers: dashmap::DashMap::new(),
@@ -3303,6 +3730,8 @@ mod tests {
             shell_states: DashMap::new(),
             terminal_rows: DashMap::new(),
             exit_codes: DashMap::new(),
+            exit_reasons: DashMap::new(),
+            recordings: DashMap::new(),
             shell_state_since_ms: DashMap::new(),
             loaded_plugins: DashMap::new(),
             relay: RelayState::new(),
@@ -3325,6 +3754,14 @@ mod tests {
                 crate::tailscale::TailscaleState::NotInstalled,
             ),
             push_store: crate::push::PushStore::load(&std::env::temp_dir()),
+            notification_throttle: Default::default(),
+            resize_debouncer: Default::default(),
+            poll_activity: Default::default(),
+            remote_port: std::sync::atomic::AtomicU16::new(0),
+            active_selection: parking_lot::RwLock::new(None),
+            session_records: DashMap::new(),
+            session_records_dirty: AtomicBool::new(false),
+            recoverable_sessions: parking_lot::RwLock::new(Vec::new()),
             desktop_window_focused: std::sync::atomic::AtomicBool::new(true),
             server_start_time: std::time::Instant::now(),
             term_aliases: DashMap::new(),
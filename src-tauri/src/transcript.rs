@@ -0,0 +1,693 @@
+//! Agent session transcripts (`agent_transcript_dir`).
+//!
+//! When configured, every agent-spawned PTY's output is appended, from spawn to
+//! exit, to `<dir>/<repo-slug>/<timestamp>-<session>.log`. Unlike the in-memory
+//! ring buffer this captures everything, so it works as an audit trail. The
+//! capture sits between the PTY and the reader thread, so it never depends on
+//! what the UI keeps. Write failures only disable the transcript — they never
+//! interrupt the session.
+
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, mpsc};
+use std::time::{Duration, SystemTime};
+
+/// Redaction holds back partial lines so a secret split across two reads is
+/// still matched. Past this size the pending bytes are written regardless.
+const MAX_PENDING_BYTES: usize = 64 * 1024;
+
+/// How often (in written bytes) a live transcript re-checks the size cap.
+/// Smaller caps are checked proportionally more often.
+const CAP_CHECK_INTERVAL_BYTES: u64 = 1024 * 1024;
+
+/// Resolved transcript settings from [`crate::config::AppConfig`].
+#[derive(Clone, Debug)]
+pub(crate) struct TranscriptSettings {
+    pub(crate) dir: PathBuf,
+    pub(crate) max_total_bytes: u64,
+    pub(crate) redact: bool,
+}
+
+impl TranscriptSettings {
+    /// `None` when `agent_transcript_dir` is unset or blank.
+    pub(crate) fn from_config(config: &crate::config::AppConfig) -> Option<Self> {
+        let dir = config.agent_transcript_dir.as_deref()?.trim();
+        if dir.is_empty() {
+            return None;
+        }
+        Some(Self {
+            dir: PathBuf::from(crate::cli::expand_tilde(dir)),
+            max_total_bytes: config.agent_transcript_max_mb.saturating_mul(1024 * 1024),
+            redact: config.agent_transcript_redact,
+        })
+    }
+}
+
+/// Filesystem-safe slug for the repo (or cwd) a session runs in.
+fn repo_slug(cwd: Option<&str>) -> String {
+    let name = cwd
+        .map(Path::new)
+        .and_then(Path::file_name)
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let slug: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches('.');
+    if slug.is_empty() {
+        "no-repo".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// `<dir>/<repo-slug>/<timestamp>-<session>.log`
+fn transcript_path(dir: &Path, cwd: Option<&str>, session_id: &str) -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    dir.join(repo_slug(cwd))
+        .join(format!("{timestamp}-{session_id}.log"))
+}
+
+/// `<timestamp>-<session>.<part>.log` — continuation of a rotated transcript.
+fn rotated_path(base: &Path, part: u32) -> PathBuf {
+    base.with_extension(format!("{part}.log"))
+}
+
+/// Whether `name` looks like a file [`transcript_path`] or [`rotated_path`]
+/// produced (`YYYYmmdd-HHMMSS-<session>[.<part>].log`). Listing and pruning
+/// skip anything else, so pointing the setting at a shared logs folder never
+/// touches unrelated files.
+fn is_transcript_file_name(name: &str) -> bool {
+    let Some(stem) = name.strip_suffix(".log") else {
+        return false;
+    };
+    let b = stem.as_bytes();
+    b.len() > 16
+        && b[..8].iter().all(u8::is_ascii_digit)
+        && b[8] == b'-'
+        && b[9..15].iter().all(u8::is_ascii_digit)
+        && b[15] == b'-'
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// A PTY reader that tees everything it reads into a transcript file.
+///
+/// The size cap is enforced while the session runs, not just at spawn: every
+/// so often other transcripts are pruned oldest-first, and once this file
+/// grows past half the cap it rotates to a new part so the older part can be
+/// pruned too.
+pub(crate) struct TranscriptReader<R> {
+    inner: R,
+    file: Option<File>,
+    /// Path of the first part; rotated parts derive from it.
+    base_path: PathBuf,
+    path: PathBuf,
+    part: u32,
+    dir: PathBuf,
+    max_total_bytes: u64,
+    file_bytes: u64,
+    since_cap_check: u64,
+    redact: bool,
+    pending: Vec<u8>,
+}
+
+impl<R: Read> TranscriptReader<R> {
+    pub(crate) fn new(inner: R, file: File, path: PathBuf, settings: &TranscriptSettings) -> Self {
+        Self {
+            inner,
+            file: Some(file),
+            base_path: path.clone(),
+            path,
+            part: 0,
+            dir: settings.dir.clone(),
+            max_total_bytes: settings.max_total_bytes,
+            file_bytes: 0,
+            since_cap_check: 0,
+            redact: settings.redact,
+            pending: Vec::new(),
+        }
+    }
+
+    fn write_out(&mut self, bytes: &[u8]) {
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+        let result = if self.redact {
+            let text = crate::ai_agent::tools::redact_secrets(&String::from_utf8_lossy(bytes));
+            file.write_all(text.as_bytes()).map(|()| text.len())
+        } else {
+            file.write_all(bytes).map(|()| bytes.len())
+        };
+        match result {
+            Ok(written) => {
+                self.file_bytes += written as u64;
+                self.since_cap_check += written as u64;
+                if self.max_total_bytes > 0
+                    && self.since_cap_check
+                        >= CAP_CHECK_INTERVAL_BYTES
+                            .min(self.max_total_bytes / 4)
+                            .max(1)
+                {
+                    self.enforce_cap();
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    source = "transcript",
+                    path = %self.path.display(),
+                    "Transcript write failed, disabling: {e}"
+                );
+                self.file = None;
+            }
+        }
+    }
+
+    /// Rotate past half the cap, then prune everything but the live part.
+    fn enforce_cap(&mut self) {
+        self.since_cap_check = 0;
+        if self.file_bytes > self.max_total_bytes / 2 {
+            self.rotate();
+        }
+        self.prune_others();
+    }
+
+    fn prune_others(&self) {
+        prune_in_background(PruneRequest {
+            dir: self.dir.clone(),
+            max_total_bytes: self.max_total_bytes,
+            keep: self.path.clone(),
+        });
+    }
+
+    fn rotate(&mut self) {
+        if let Some(file) = self.file.as_mut() {
+            let _ = file.flush();
+        }
+        let next = rotated_path(&self.base_path, self.part + 1);
+        match open_append(&next) {
+            Ok(file) => {
+                self.file = Some(file);
+                self.path = next;
+                self.part += 1;
+                self.file_bytes = 0;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    source = "transcript",
+                    path = %next.display(),
+                    "Transcript rotation failed, disabling: {e}"
+                );
+                self.file = None;
+            }
+        }
+    }
+
+    fn record(&mut self, bytes: &[u8]) {
+        if !self.redact {
+            self.write_out(bytes);
+            return;
+        }
+        self.pending.extend_from_slice(bytes);
+        let cut = match self.pending.iter().rposition(|&b| b == b'\n') {
+            Some(pos) => pos + 1,
+            None if self.pending.len() > MAX_PENDING_BYTES => self.pending.len(),
+            None => return,
+        };
+        let complete: Vec<u8> = self.pending.drain(..cut).collect();
+        self.write_out(&complete);
+    }
+
+    fn flush_pending(&mut self) {
+        if !self.pending.is_empty() {
+            let rest = std::mem::take(&mut self.pending);
+            self.write_out(&rest);
+        }
+        if let Some(file) = self.file.as_mut() {
+            let _ = file.flush();
+        }
+        if self.max_total_bytes > 0 && self.file.is_some() {
+            self.prune_others();
+        }
+    }
+}
+
+impl<R: Read> Read for TranscriptReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n == 0 {
+            self.flush_pending();
+        } else {
+            self.record(&buf[..n]);
+        }
+        Ok(n)
+    }
+}
+
+impl<R> Drop for TranscriptReader<R> {
+    fn drop(&mut self) {
+        if let Some(mut file) = self.file.take() {
+            if !self.pending.is_empty() {
+                let text = if self.redact {
+                    crate::ai_agent::tools::redact_secrets(&String::from_utf8_lossy(&self.pending))
+                        .into_bytes()
+                } else {
+                    std::mem::take(&mut self.pending)
+                };
+                let _ = file.write_all(&text);
+            }
+            let _ = file.flush();
+        }
+    }
+}
+
+/// A size-cap prune of `dir` that leaves `keep` (a live transcript) alone.
+struct PruneRequest {
+    dir: PathBuf,
+    max_total_bytes: u64,
+    keep: PathBuf,
+}
+
+/// Queue a prune on the shared background thread, so PTY readers never wait
+/// on directory walks or deletes. Requests for one dir that pile up while a
+/// prune runs collapse into the newest.
+fn prune_in_background(request: PruneRequest) {
+    static PRUNER: OnceLock<Option<mpsc::Sender<PruneRequest>>> = OnceLock::new();
+    let sender = PRUNER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<PruneRequest>();
+        let spawned = std::thread::Builder::new()
+            .name("transcript-prune".into())
+            .spawn(move || {
+                while let Ok(first) = rx.recv() {
+                    let mut batch = vec![first];
+                    for next in rx.try_iter() {
+                        batch.retain(|r| r.dir != next.dir);
+                        batch.push(next);
+                    }
+                    for r in batch {
+                        if let Err(e) =
+                            prune_transcripts_except(&r.dir, r.max_total_bytes, None, Some(&r.keep))
+                        {
+                            tracing::warn!(source = "transcript", "Transcript prune failed: {e}");
+                        }
+                    }
+                }
+            });
+        match spawned {
+            Ok(_) => Some(tx),
+            Err(e) => {
+                tracing::warn!(
+                    source = "transcript",
+                    "Failed to start transcript pruner: {e}"
+                );
+                None
+            }
+        }
+    });
+    if let Some(tx) = sender {
+        let _ = tx.send(request);
+    }
+}
+
+/// Open a transcript for `session_id` and wrap `reader` with it.
+///
+/// Queues a prune of the transcript directory down to the size cap. Returns
+/// the reader unchanged if the file can't be created.
+pub(crate) fn wrap_reader(
+    reader: Box<dyn Read + Send>,
+    settings: &TranscriptSettings,
+    cwd: Option<&str>,
+    session_id: &str,
+) -> Box<dyn Read + Send> {
+    let path = transcript_path(&settings.dir, cwd, session_id);
+    let opened = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| open_append(&path));
+    match opened {
+        Ok(file) => {
+            tracing::info!(
+                source = "transcript",
+                session_id,
+                path = %path.display(),
+                "Recording agent transcript"
+            );
+            let reader = TranscriptReader::new(reader, file, path, settings);
+            if settings.max_total_bytes > 0 {
+                reader.prune_others();
+            }
+            Box::new(reader)
+        }
+        Err(e) => {
+            tracing::warn!(
+                source = "transcript",
+                path = %path.display(),
+                "Failed to open transcript: {e}"
+            );
+            reader
+        }
+    }
+}
+
+/// Wrap an agent PTY reader when `agent_transcript_dir` is configured
+/// (`settings` from [`TranscriptSettings::from_config`] on the live config).
+pub(crate) fn wrap_agent_reader(
+    reader: Box<dyn Read + Send>,
+    settings: Option<&TranscriptSettings>,
+    cwd: Option<&str>,
+    session_id: &str,
+) -> Box<dyn Read + Send> {
+    match settings {
+        Some(settings) => wrap_reader(reader, settings, cwd, session_id),
+        None => reader,
+    }
+}
+
+/// A transcript file on disk.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct TranscriptInfo {
+    pub(crate) path: String,
+    /// Repo slug (the transcript's parent directory name)
+    pub(crate) repo: String,
+    pub(crate) file_name: String,
+    pub(crate) size_bytes: u64,
+    /// Last-modified time, epoch ms
+    pub(crate) modified_ms: u64,
+}
+
+/// Result of [`prune_transcripts`].
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub(crate) struct PruneResult {
+    pub(crate) removed: usize,
+    pub(crate) freed_bytes: u64,
+}
+
+fn epoch_ms(t: SystemTime) -> u64 {
+    t.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// All transcripts under `dir` (see [`is_transcript_file_name`]), newest
+/// first. Missing dir → empty.
+pub(crate) fn list_transcripts(dir: &Path) -> Result<Vec<TranscriptInfo>, String> {
+    let mut out = Vec::new();
+    let repos = match fs::read_dir(dir) {
+        Ok(r) => r,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(out),
+        Err(e) => return Err(format!("Failed to read transcript dir: {e}")),
+    };
+    for repo in repos.flatten() {
+        if !repo.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let repo_name = repo.file_name().to_string_lossy().to_string();
+        let Ok(files) = fs::read_dir(repo.path()) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let file_name = file.file_name().to_string_lossy().to_string();
+            if !is_transcript_file_name(&file_name) {
+                continue;
+            }
+            let Ok(meta) = file.metadata() else {
+                continue;
+            };
+            if !meta.is_file() {
+                continue;
+            }
+            out.push(TranscriptInfo {
+                path: file.path().to_string_lossy().to_string(),
+                repo: repo_name.clone(),
+                file_name,
+                size_bytes: meta.len(),
+                modified_ms: meta.modified().map_or(0, epoch_ms),
+            });
+        }
+    }
+    out.sort_by(|a, b| b.modified_ms.cmp(&a.modified_ms));
+    Ok(out)
+}
+
+/// Delete transcripts older than `older_than`, then the oldest remaining ones
+/// until the total size is within `max_total_bytes` (0 = no size cap).
+pub(crate) fn prune_transcripts(
+    dir: &Path,
+    max_total_bytes: u64,
+    older_than: Option<Duration>,
+) -> Result<PruneResult, String> {
+    prune_transcripts_except(dir, max_total_bytes, older_than, None)
+}
+
+/// [`prune_transcripts`], never deleting `keep` (a transcript still being
+/// written) although its size counts towards the cap.
+fn prune_transcripts_except(
+    dir: &Path,
+    max_total_bytes: u64,
+    older_than: Option<Duration>,
+    keep: Option<&Path>,
+) -> Result<PruneResult, String> {
+    let mut transcripts = list_transcripts(dir)?;
+    let mut result = PruneResult::default();
+    let cutoff = older_than.map(|age| {
+        epoch_ms(
+            SystemTime::now()
+                .checked_sub(age)
+                .unwrap_or(SystemTime::UNIX_EPOCH),
+        )
+    });
+    let mut total: u64 = transcripts.iter().map(|t| t.size_bytes).sum();
+
+    // Oldest first
+    transcripts.reverse();
+    for t in transcripts {
+        if keep.is_some_and(|k| Path::new(&t.path) == k) {
+            continue;
+        }
+        let expired = cutoff.is_some_and(|c| t.modified_ms < c);
+        let over_cap = max_total_bytes > 0 && total > max_total_bytes;
+        if !expired && !over_cap {
+            continue;
+        }
+        match fs::remove_file(&t.path) {
+            Ok(()) => {
+                total = total.saturating_sub(t.size_bytes);
+                result.removed += 1;
+                result.freed_bytes += t.size_bytes;
+            }
+            Err(e) => {
+                tracing::warn!(source = "transcript", path = %t.path, "Failed to prune transcript: {e}")
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn configured_dir() -> Result<TranscriptSettings, String> {
+    TranscriptSettings::from_config(&crate::config::load_app_config())
+        .ok_or_else(|| "agent_transcript_dir is not configured".to_string())
+}
+
+/// List recorded agent transcripts, newest first.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn list_agent_transcripts() -> Result<Vec<TranscriptInfo>, String> {
+    list_transcripts(&configured_dir()?.dir)
+}
+
+/// Prune agent transcripts: everything older than `older_than_days` (if set),
+/// then oldest-first down to the configured size cap.
+#[cfg_attr(feature = "desktop", tauri::command)]
+pub(crate) fn prune_agent_transcripts(older_than_days: Option<u64>) -> Result<PruneResult, String> {
+    let settings = configured_dir()?;
+    let older_than = older_than_days.map(|d| Duration::from_secs(d.saturating_mul(86_400)));
+    prune_transcripts(&settings.dir, settings.max_total_bytes, older_than)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(dir: &Path, redact: bool) -> TranscriptSettings {
+        TranscriptSettings {
+            dir: dir.to_path_buf(),
+            max_total_bytes: 0,
+            redact,
+        }
+    }
+
+    fn drain(mut reader: Box<dyn Read + Send>) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut buf = [0u8; 7]; // small buffer: output arrives in several reads
+        loop {
+            let n = reader.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        out
+    }
+
+    #[test]
+    fn spawned_session_output_is_appended_to_transcript() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let output = b"\x1b[32mBuilding\x1b[0m...\nDone in 2s\npartial".to_vec();
+        let source: Box<dyn Read + Send> = Box::new(std::io::Cursor::new(output.clone()));
+
+        let reader = wrap_reader(
+            source,
+            &settings(dir.path(), false),
+            Some("/home/me/repos/my app"),
+            "sess-1",
+        );
+        // The session still sees every byte unchanged
+        assert_eq!(drain(reader), output);
+
+        let transcripts = list_transcripts(dir.path()).unwrap();
+        assert_eq!(transcripts.len(), 1);
+        assert_eq!(transcripts[0].repo, "my_app");
+        assert!(transcripts[0].file_name.ends_with("-sess-1.log"));
+        assert_eq!(fs::read(&transcripts[0].path).unwrap(), output);
+    }
+
+    #[test]
+    fn transcript_redacts_secrets_split_across_reads() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let key = "sk-abcdefghijklmnopqrstuvwxyz0123";
+        let output = format!("export KEY={key}\nok\n");
+        let source: Box<dyn Read + Send> = Box::new(std::io::Cursor::new(output.into_bytes()));
+
+        drain(wrap_reader(source, &settings(dir.path(), true), None, "s"));
+
+        let t = &list_transcripts(dir.path()).unwrap()[0];
+        assert_eq!(t.repo, "no-repo");
+        let written = fs::read_to_string(&t.path).unwrap();
+        assert!(!written.contains(key), "secret leaked: {written}");
+        assert!(written.contains("[REDACTED]"));
+        assert!(written.ends_with("ok\n"));
+    }
+
+    #[test]
+    fn long_session_rotates_and_stays_under_cap() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path().join("app");
+        fs::create_dir(&repo).unwrap();
+        let old = repo.join("20240101-000000-old.log");
+        fs::write(&old, vec![b'o'; 400]).unwrap();
+        File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+
+        let cap = 1000;
+        let output = "line of session output\n".repeat(200); // ~4.6 KB, well past the cap
+        let source: Box<dyn Read + Send> = Box::new(std::io::Cursor::new(output.into_bytes()));
+        let live = TranscriptSettings {
+            dir: dir.path().to_path_buf(),
+            max_total_bytes: cap,
+            redact: false,
+        };
+        drain(wrap_reader(source, &live, Some("/repos/app"), "long"));
+
+        // Pruning runs on a background thread.
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        let transcripts = loop {
+            let transcripts = list_transcripts(dir.path()).unwrap();
+            let total: u64 = transcripts.iter().map(|t| t.size_bytes).sum();
+            if total <= cap {
+                break transcripts;
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "transcripts total {total} bytes"
+            );
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        assert!(!old.exists(), "older transcript should be pruned first");
+        assert!(
+            transcripts.iter().any(|t| t.file_name.contains("-long.")),
+            "latest rotated part is kept: {transcripts:?}"
+        );
+    }
+
+    #[test]
+    fn prune_removes_oldest_until_under_cap() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path().join("app");
+        fs::create_dir(&repo).unwrap();
+        let names = [
+            "20240101-000000-a.log",
+            "20240101-000100-b.log",
+            "20240101-000200-c.log",
+        ];
+        for (i, name) in names.iter().enumerate() {
+            let path = repo.join(name);
+            fs::write(&path, vec![b'x'; 100]).unwrap();
+            let mtime = SystemTime::now() - Duration::from_secs(1000 - i as u64 * 100);
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(mtime)
+                .unwrap();
+        }
+
+        let result = prune_transcripts(dir.path(), 150, None).unwrap();
+        assert_eq!(
+            result,
+            PruneResult {
+                removed: 2,
+                freed_bytes: 200
+            }
+        );
+        let left: Vec<_> = list_transcripts(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|t| t.file_name)
+            .collect();
+        assert_eq!(left, vec!["20240101-000200-c.log"]);
+    }
+
+    #[test]
+    fn prune_leaves_files_that_are_not_transcripts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let app = dir.path().join("app");
+        fs::create_dir(&app).unwrap();
+        let unrelated = ["server.log", "2024-01-01.log", "20240101-000000.log"];
+        for name in unrelated {
+            fs::write(app.join(name), vec![b'x'; 100]).unwrap();
+        }
+        let transcript = app.join("20240101-000000-sess.1.log");
+        fs::write(&transcript, vec![b'x'; 100]).unwrap();
+        for entry in fs::read_dir(&app).unwrap() {
+            File::options()
+                .write(true)
+                .open(entry.unwrap().path())
+                .unwrap()
+                .set_modified(SystemTime::now() - Duration::from_secs(3600))
+                .unwrap();
+        }
+
+        let listed = list_transcripts(dir.path()).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].file_name, "20240101-000000-sess.1.log");
+
+        prune_transcripts(dir.path(), 0, Some(Duration::from_secs(60))).unwrap();
+        assert!(!transcript.exists());
+        for name in unrelated {
+            assert!(app.join(name).exists(), "{name} must not be pruned");
+        }
+    }
+}
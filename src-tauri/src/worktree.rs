@@ -73,7 +73,7 @@ fn classify_worktree_add_failure(stderr: &str) -> WorktreeAddFailure {
 
 /// Parse `git worktree list --porcelain` output and return the worktree path
 /// for the given branch name, if any.
-fn find_worktree_path_for_branch(stdout: &str, branch_name: &str) -> Option<PathBuf> {
+pub(crate) fn find_worktree_path_for_branch(stdout: &str, branch_name: &str) -> Option<PathBuf> {
     let mut current_path: Option<PathBuf> = None;
     for line in stdout.lines() {
         if line.starts_with("worktree ") {
@@ -136,18 +136,59 @@ pub(crate) fn resolve_worktree_dir(
 }
 
 /// Resolve the effective worktree directory for a repo by loading config from disk.
-/// Per-repo `worktree_storage` overrides the global default from repo-defaults.
+/// A valid per-repo `worktrees_dir` wins; otherwise per-repo `worktree_storage`
+/// overrides the global default from repo-defaults.
 pub(crate) fn resolve_worktree_dir_for_repo(repo_path: &Path, app_worktrees_dir: &Path) -> PathBuf {
     let repo_path_str = repo_path.to_string_lossy();
     let repo_settings = crate::config::load_repo_settings();
-    let strategy = repo_settings
-        .repos
-        .get(repo_path_str.as_ref())
+    let entry = repo_settings.repos.get(repo_path_str.as_ref());
+    if let Some(dir) = entry.and_then(|e| e.worktrees_dir.as_deref()) {
+        match validate_worktrees_dir_override(repo_path, dir) {
+            Ok(path) => return path,
+            Err(e) => tracing::warn!(
+                source = "worktree",
+                repo = %repo_path_str,
+                "Ignoring worktrees_dir override: {e}"
+            ),
+        }
+    }
+    let strategy = entry
         .and_then(|entry| entry.worktree_storage.clone())
         .unwrap_or_else(|| crate::config::load_repo_defaults().worktree_storage);
     resolve_worktree_dir(repo_path, &strategy, app_worktrees_dir)
 }
 
+/// Check a per-repo `worktrees_dir` override: it must be absolute, outside the
+/// repo, and writable. The directory is created if missing.
+pub(crate) fn validate_worktrees_dir_override(
+    repo_path: &Path,
+    dir: &str,
+) -> Result<PathBuf, String> {
+    let dir = crate::cli::expand_tilde(dir.trim());
+    let path = PathBuf::from(&dir);
+    if dir.is_empty() || !path.is_absolute() {
+        return Err(format!("'{dir}' is not an absolute path"));
+    }
+    let repo = repo_path
+        .canonicalize()
+        .unwrap_or_else(|_| repo_path.to_path_buf());
+    // Reject lexically before creating anything inside the repo.
+    if path.starts_with(repo_path) || path.starts_with(&repo) {
+        return Err(format!("'{dir}' is inside the repository"));
+    }
+    std::fs::create_dir_all(&path).map_err(|e| format!("cannot create '{dir}': {e}"))?;
+    let resolved = path
+        .canonicalize()
+        .map_err(|e| format!("cannot resolve '{dir}': {e}"))?;
+    if resolved.starts_with(&repo) {
+        return Err(format!("'{dir}' resolves inside the repository"));
+    }
+    let probe = resolved.join(".tuic-write-probe");
+    std::fs::write(&probe, b"").map_err(|e| format!("'{dir}' is not writable: {e}"))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(path)
+}
+
 /// Sanitize task name for use as directory name
 pub(crate) fn sanitize_name(name: &str) -> String {
     name.chars()
@@ -329,6 +370,11 @@ pub(crate) fn create_worktree_internal(
 /// The JS layer checks for this prefix to show a confirmation dialog before retrying.
 pub(crate) const LOCKED_WORKTREE_PREFIX: &str = "worktree_locked:";
 
+/// Error prefix returned when a worktree has uncommitted changes or untracked
+/// files and `force` is false. The JS layer checks for this prefix to confirm
+/// with the user before retrying with force=true.
+pub(crate) const DIRTY_WORKTREE_PREFIX: &str = "worktree_dirty:";
+
 /// Error prefix returned when trying to `git worktree remove` the main working tree.
 /// The JS layer treats this as a non-fatal condition and does NOT remove the branch
 /// from the store (to avoid resurrection on the next refresh).
@@ -605,6 +651,77 @@ pub(crate) fn generate_clone_branch_name(source_branch: &str, existing: &[String
     format!("{sanitized}--wt-{}", ts % 100000)
 }
 
+/// Names a new worktree cannot take in `repo_path`: local branches, remote
+/// branches (without the `origin/` prefix) and existing worktree directories.
+pub(crate) fn taken_worktree_names(
+    repo_path: &Path,
+    worktrees_dir: &Path,
+) -> std::collections::HashSet<String> {
+    let mut taken: std::collections::HashSet<String> =
+        list_local_branches(repo_path.to_string_lossy().to_string())
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+    if let Some(out) = git_cmd(repo_path)
+        .args(["for-each-ref", "--format=%(refname:short)", "refs/remotes/"])
+        .run_silent()
+    {
+        for line in out.stdout.lines() {
+            // "origin/feature" → "feature"; "origin" alone is the remote HEAD
+            if let Some((_, branch)) = line.trim().split_once('/')
+                && branch != "HEAD"
+            {
+                taken.insert(branch.to_string());
+            }
+        }
+    }
+
+    if let Ok(entries) = std::fs::read_dir(worktrees_dir) {
+        taken.extend(
+            entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string()),
+        );
+    }
+
+    taken
+}
+
+/// Increment `name`'s numeric suffix (`brave-neo-042` → `brave-neo-043`;
+/// names without one get `-2`, `-3`, …) until it is not in `taken`.
+pub(crate) fn bump_until_unique(name: &str, taken: &std::collections::HashSet<String>) -> String {
+    if !taken.contains(name) {
+        return name.to_string();
+    }
+    let (stem, width, mut n) = match name.rsplit_once('-') {
+        Some((stem, num)) if !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()) => {
+            (stem, num.len(), num.parse::<u64>().unwrap_or(0))
+        }
+        _ => (name, 0, 1),
+    };
+    loop {
+        n += 1;
+        let candidate = format!("{stem}-{n:0width$}");
+        if !taken.contains(&candidate) {
+            return candidate;
+        }
+    }
+}
+
+/// Generate a worktree name that collides with no local branch, remote
+/// branch or worktree directory of `repo_path`, nor with `existing`.
+pub(crate) fn generate_unique_worktree_name(
+    repo_path: &Path,
+    worktrees_dir: &Path,
+    existing: &[String],
+) -> String {
+    let mut taken = taken_worktree_names(repo_path, worktrees_dir);
+    taken.extend(existing.iter().cloned());
+    let taken_list: Vec<String> = taken.iter().cloned().collect();
+    bump_until_unique(&generate_worktree_name(&taken_list), &taken)
+}
+
 /// Create a worktree without a PTY session
 #[cfg(feature = "desktop")]
 #[tauri::command]
@@ -783,6 +900,204 @@ pub(crate) async fn create_worktree(
     }
 }
 
+/// How to fetch a PR's head and which local branch to review it on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PrCheckoutPlan {
+    pub(crate) remote: String,
+    pub(crate) refspec: String,
+    /// Remote-tracking ref the refspec writes, used as the branch start point.
+    pub(crate) start_point: String,
+    pub(crate) branch: String,
+}
+
+/// Local review branch for a PR: `pr/<n>-<head ref>`.
+pub(crate) fn pr_branch_name(pr_number: i64, head_ref: &str) -> String {
+    let head = sanitize_name(head_ref);
+    if head.is_empty() {
+        format!("pr/{pr_number}")
+    } else {
+        format!("pr/{pr_number}-{head}")
+    }
+}
+
+/// Plan the fetch for a PR head.
+///
+/// Fork PRs whose head repository is already configured as a remote are
+/// fetched from that remote by branch name, so the review branch can later be
+/// pushed back. Everything else (same-repo PRs, unknown or deleted forks) goes
+/// through `base_remote`'s `pull/<n>/head`, which GitHub serves for any PR.
+/// Refs land under `refs/remotes/` so re-fetching never touches a checked-out
+/// local branch.
+pub(crate) fn plan_pr_checkout(
+    head: &crate::github::PrHead,
+    remotes: &[(String, String)],
+    base_remote: &str,
+) -> PrCheckoutPlan {
+    let branch = pr_branch_name(head.number, &head.head_ref);
+    let fork_remote = match (&head.head_owner, &head.head_repo) {
+        (Some(owner), Some(repo)) if head.is_cross_repository => {
+            remotes.iter().find_map(|(name, url)| {
+                let (o, r) = crate::github::parse_remote_url(url)?;
+                (o.eq_ignore_ascii_case(owner) && r.eq_ignore_ascii_case(repo)).then_some(name)
+            })
+        }
+        _ => None,
+    };
+    match fork_remote {
+        Some(remote) => PrCheckoutPlan {
+            remote: remote.clone(),
+            refspec: format!("+refs/heads/{0}:refs/remotes/{remote}/{0}", head.head_ref),
+            start_point: format!("{remote}/{}", head.head_ref),
+            branch,
+        },
+        None => PrCheckoutPlan {
+            remote: base_remote.to_string(),
+            refspec: format!(
+                "+pull/{0}/head:refs/remotes/{base_remote}/pr/{0}",
+                head.number
+            ),
+            start_point: format!("{base_remote}/pr/{}", head.number),
+            branch,
+        },
+    }
+}
+
+/// Parse `git config --get-regexp '^remote\..*\.url$'` into (name, url) pairs.
+fn parse_remote_urls(stdout: &str) -> Vec<(String, String)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (key, url) = line.split_once(char::is_whitespace)?;
+            let name = key.strip_prefix("remote.")?.strip_suffix(".url")?;
+            Some((name.to_string(), url.trim().to_string()))
+        })
+        .collect()
+}
+
+fn list_remote_urls(repo: &Path) -> Vec<(String, String)> {
+    git_cmd(repo)
+        .args(["config", "--get-regexp", r"^remote\..*\.url$"])
+        .run_silent()
+        .map(|out| parse_remote_urls(&out.stdout))
+        .unwrap_or_default()
+}
+
+/// Remote tracked by the currently checked-out branch (`branch.<name>.remote`).
+fn current_upstream_remote(repo: &Path) -> Option<String> {
+    let branch = git_cmd(repo)
+        .args(["symbolic-ref", "--short", "-q", "HEAD"])
+        .run_silent()?
+        .stdout
+        .trim()
+        .to_string();
+    let remote = git_cmd(repo)
+        .args(["config", "--get", &format!("branch.{branch}.remote")])
+        .run_silent()?
+        .stdout
+        .trim()
+        .to_string();
+    // "." means the branch tracks another local branch.
+    (!remote.is_empty() && remote != ".").then_some(remote)
+}
+
+/// Remote of the repository PRs are opened against: `remote` when given,
+/// else the current branch's upstream remote, else `origin`. It must be a
+/// configured remote.
+pub(crate) fn resolve_pr_remote(repo: &Path, remote: Option<&str>) -> Result<String, String> {
+    let name = match remote.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(r) => r.to_string(),
+        None => current_upstream_remote(repo).unwrap_or_else(|| "origin".to_string()),
+    };
+    if list_remote_urls(repo).iter().any(|(n, _)| *n == name) {
+        Ok(name)
+    } else {
+        Err(format!("Unknown remote: {name}"))
+    }
+}
+
+/// Fetch a PR head per [`plan_pr_checkout`] and create a worktree on its review
+/// branch. An existing review branch is reused as-is so local commits survive.
+fn checkout_pr_worktree(
+    worktrees_dir: &Path,
+    repo_path: &str,
+    head: &crate::github::PrHead,
+    base_remote: &str,
+) -> Result<WorktreeInfo, String> {
+    let repo = Path::new(repo_path);
+    let remotes = list_remote_urls(repo);
+    let plan = plan_pr_checkout(head, &remotes, base_remote);
+
+    git_cmd(repo)
+        .args(["fetch", &plan.remote, &plan.refspec])
+        .run()
+        .map_err(|e| format!("Failed to fetch PR #{}: {e}", head.number))?;
+
+    let branch_ref = format!("refs/heads/{}", plan.branch);
+    let branch_exists = git_cmd(repo)
+        .args(["rev-parse", "--verify", "--quiet", &branch_ref])
+        .run_silent()
+        .is_some();
+    if !branch_exists {
+        git_cmd(repo)
+            .args(["branch", &plan.branch, &plan.start_point])
+            .run()
+            .map_err(|e| format!("Failed to create branch {}: {e}", plan.branch))?;
+    }
+
+    let config = WorktreeConfig {
+        task_name: plan.branch.clone(),
+        base_repo: repo_path.to_string(),
+        branch: Some(plan.branch),
+        create_branch: false,
+    };
+    create_worktree_with_stale_recovery(worktrees_dir, &config, None)
+}
+
+/// Resolve a PR's head via the GitHub API and check it out into a new worktree.
+/// `remote` overrides the remote the PR's repository is read from (see
+/// [`resolve_pr_remote`]).
+pub(crate) async fn create_worktree_from_pr_impl(
+    state: &Arc<AppState>,
+    repo_path: &str,
+    pr_number: i64,
+    remote: Option<String>,
+) -> Result<WorktreeInfo, String> {
+    let repo = repo_path.to_string();
+    let base_remote =
+        tokio::task::spawn_blocking(move || resolve_pr_remote(Path::new(&repo), remote.as_deref()))
+            .await
+            .map_err(|e| format!("Task panic: {e}"))??;
+    let head = crate::github::fetch_pr_head_impl(repo_path, &base_remote, pr_number, state).await?;
+    let worktrees_dir = resolve_worktree_dir_for_repo(Path::new(repo_path), &state.worktrees_dir);
+    let repo = repo_path.to_string();
+    let worktree = tokio::task::spawn_blocking(move || {
+        checkout_pr_worktree(&worktrees_dir, &repo, &head, &base_remote)
+    })
+    .await
+    .map_err(|e| format!("Task panic: {e}"))??;
+    state.invalidate_repo_caches(repo_path);
+    Ok(worktree)
+}
+
+/// Create a worktree for reviewing a PR (fork PRs included)
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn create_worktree_from_pr(
+    state: State<'_, Arc<AppState>>,
+    repo_path: String,
+    pr_number: i64,
+    remote: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let worktree = create_worktree_from_pr_impl(&state, &repo_path, pr_number, remote).await?;
+    Ok(serde_json::json!({
+        "status": "ok",
+        "name": worktree.name,
+        "path": worktree.path.to_string_lossy(),
+        "branch": worktree.branch,
+        "base_repo": worktree.base_repo.to_string_lossy(),
+    }))
+}
+
 /// Get worktrees directory path.
 /// When `repo_path` is provided, resolves the effective storage strategy for the repo.
 #[cfg(feature = "desktop")]
@@ -799,28 +1114,84 @@ pub(crate) fn get_worktrees_dir(
     }
 }
 
-/// Core logic for removing a git worktree by branch name.
-///
-/// When `delete_branch` is true, also deletes the local branch after removing
-/// the worktree directory. When false, the branch is preserved.
-pub(crate) fn remove_worktree_by_branch(
-    repo_path: &str,
-    branch_name: &str,
-    delete_branch: bool,
-    archive_script: Option<&str>,
-    force: bool,
-) -> Result<(), String> {
-    let base_repo = PathBuf::from(repo_path);
+/// What removing a worktree would destroy. Returned by `remove_worktree`
+/// with `dry_run` instead of removing anything.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub(crate) struct WorktreeRemovalReport {
+    /// Staged or unstaged changes to tracked files.
+    pub(crate) has_uncommitted_changes: bool,
+    /// Commits `git cherry` finds missing from the branch's upstream (the
+    /// default branch when it has none).
+    pub(crate) unpushed_commits: u32,
+    /// Untracked, non-ignored files relative to the worktree root.
+    pub(crate) untracked_files: Vec<String>,
+    pub(crate) would_remove_path: String,
+}
 
-    tracing::info!(
-        source = "worktree",
-        branch = %branch_name,
-        delete_branch = %delete_branch,
-        "remove_worktree_by_branch: start"
-    );
+impl WorktreeRemovalReport {
+    /// Removing the directory would lose work that exists nowhere else.
+    /// Unpushed commits survive on the branch ref, so they don't count.
+    fn loses_work(&self) -> bool {
+        self.has_uncommitted_changes || !self.untracked_files.is_empty()
+    }
+}
 
-    // List worktrees to find the path for this branch
-    let out = git_cmd(&base_repo)
+/// Inspect a worktree via `git status --porcelain` and `git cherry`.
+pub(crate) fn worktree_removal_report(
+    worktree_path: &Path,
+) -> Result<WorktreeRemovalReport, String> {
+    let status = git_cmd(worktree_path)
+        .args(["status", "--porcelain", "--untracked-files=all"])
+        .run()
+        .map_err(|e| format!("git status failed: {e}"))?;
+    let mut has_uncommitted_changes = false;
+    let mut untracked_files = Vec::new();
+    for line in status.stdout.lines() {
+        if let Some(path) = line.strip_prefix("?? ") {
+            untracked_files.push(path.to_string());
+        } else if !line.trim().is_empty() {
+            has_uncommitted_changes = true;
+        }
+    }
+    Ok(WorktreeRemovalReport {
+        has_uncommitted_changes,
+        unpushed_commits: count_unpushed_commits(worktree_path),
+        untracked_files,
+        would_remove_path: worktree_path.to_string_lossy().to_string(),
+    })
+}
+
+/// `git cherry <upstream> HEAD`, counting the `+` lines. Falls back to the
+/// default branch when HEAD has no upstream; 0 when neither resolves.
+fn count_unpushed_commits(worktree_path: &Path) -> u32 {
+    let upstream = git_cmd(worktree_path)
+        .args([
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ])
+        .run_silent()
+        .map(|o| o.stdout.trim().to_string())
+        .filter(|s| !s.is_empty());
+    let Some(base) =
+        upstream.or_else(|| get_remote_default_branch(&worktree_path.to_string_lossy()).ok())
+    else {
+        return 0;
+    };
+    git_cmd(worktree_path)
+        .args(["cherry", &base, "HEAD"])
+        .run_silent()
+        .map(|o| o.stdout.lines().filter(|l| l.starts_with('+')).count() as u32)
+        .unwrap_or(0)
+}
+
+/// Find the checkout path of `branch_name` via `git worktree list --porcelain`.
+fn resolve_worktree_path_for_branch(
+    base_repo: &Path,
+    branch_name: &str,
+) -> Result<PathBuf, String> {
+    let out = git_cmd(base_repo)
         .args(["worktree", "list", "--porcelain"])
         .run()
         .map_err(|e| format!("git worktree list failed: {e}"))?;
@@ -830,7 +1201,7 @@ pub(crate) fn remove_worktree_by_branch(
             tracing::error!(
                 source = "worktree",
                 branch = %branch_name,
-                "remove_worktree_by_branch: no worktree found for branch"
+                "resolve_worktree_path_for_branch: no worktree found for branch"
             );
             format!("No worktree found for branch '{branch_name}'")
         })?;
@@ -839,9 +1210,92 @@ pub(crate) fn remove_worktree_by_branch(
         source = "worktree",
         branch = %branch_name,
         path = %worktree_path.display(),
-        "remove_worktree_by_branch: worktree path resolved"
+        "resolve_worktree_path_for_branch: worktree path resolved"
+    );
+    Ok(worktree_path)
+}
+
+/// Dry run of [`remove_worktree_by_branch`]: report what would be lost.
+pub(crate) fn preview_worktree_removal(
+    repo_path: &str,
+    branch_name: &str,
+) -> Result<WorktreeRemovalReport, String> {
+    let worktree_path = resolve_worktree_path_for_branch(Path::new(repo_path), branch_name)?;
+    worktree_removal_report(&worktree_path)
+}
+
+/// Fail with a [`DIRTY_WORKTREE_PREFIX`] error when removing `worktree_path`
+/// would lose uncommitted changes or untracked files. The main checkout is
+/// refused by `git worktree remove` with its own error, and a directory that
+/// is already gone has nothing to lose.
+fn refuse_dirty_worktree(
+    base_repo: &Path,
+    worktree_path: &Path,
+    branch_name: &str,
+) -> Result<(), String> {
+    let is_main = worktree_path.canonicalize().ok() == base_repo.canonicalize().ok();
+    if is_main || !worktree_path.exists() {
+        return Ok(());
+    }
+    match worktree_removal_report(worktree_path) {
+        Ok(report) if report.loses_work() => {
+            tracing::warn!(
+                source = "worktree",
+                branch = %branch_name,
+                untracked = report.untracked_files.len(),
+                "refuse_dirty_worktree: refusing, worktree has uncommitted work"
+            );
+            Err(format!(
+                "{DIRTY_WORKTREE_PREFIX}{} has {}{} untracked file(s)",
+                branch_name,
+                if report.has_uncommitted_changes {
+                    "uncommitted changes and "
+                } else {
+                    ""
+                },
+                report.untracked_files.len()
+            ))
+        }
+        Ok(_) => Ok(()),
+        Err(e) => {
+            tracing::warn!(
+                source = "worktree",
+                branch = %branch_name,
+                "refuse_dirty_worktree: could not inspect worktree, removing anyway: {e}"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Core logic for removing a git worktree by branch name.
+///
+/// When `delete_branch` is true, also deletes the local branch after removing
+/// the worktree directory. When false, the branch is preserved. Without
+/// `force`, a worktree with uncommitted changes or untracked files is refused
+/// with a [`DIRTY_WORKTREE_PREFIX`] error.
+pub(crate) fn remove_worktree_by_branch(
+    repo_path: &str,
+    branch_name: &str,
+    delete_branch: bool,
+    archive_script: Option<&str>,
+    force: bool,
+) -> Result<(), String> {
+    let base_repo = PathBuf::from(repo_path);
+
+    tracing::info!(
+        source = "worktree",
+        branch = %branch_name,
+        delete_branch = %delete_branch,
+        "remove_worktree_by_branch: start"
     );
 
+    let worktree_path = resolve_worktree_path_for_branch(&base_repo, branch_name)?;
+
+    if !force {
+        refuse_dirty_worktree(&base_repo, &worktree_path, branch_name)?;
+    }
+
     // Run archive/cleanup script before deletion (if configured)
     if let Some(script) = archive_script
         && !script.is_empty()
@@ -894,6 +1348,8 @@ pub(crate) fn remove_worktree_by_branch(
 /// Remove a git worktree by branch name (Tauri command with cache invalidation)
 ///
 /// `delete_branch` defaults to `true` when omitted (preserving existing behavior).
+/// With `dry_run`, nothing is removed and the [`WorktreeRemovalReport`] is
+/// returned instead.
 #[cfg(feature = "desktop")]
 #[tauri::command]
 pub(crate) async fn remove_worktree(
@@ -902,7 +1358,16 @@ pub(crate) async fn remove_worktree(
     branch_name: String,
     delete_branch: Option<bool>,
     force: Option<bool>,
-) -> Result<(), String> {
+    dry_run: Option<bool>,
+) -> Result<Option<WorktreeRemovalReport>, String> {
+    if dry_run.unwrap_or(false) {
+        return tokio::task::spawn_blocking(move || {
+            preview_worktree_removal(&repo_path, &branch_name)
+        })
+        .await
+        .map_err(|e| format!("Task panic: {e}"))?
+        .map(Some);
+    }
     let delete_branch = delete_branch.unwrap_or(true);
     let force = force.unwrap_or(false);
     tracing::info!(
@@ -933,7 +1398,7 @@ pub(crate) async fn remove_worktree(
             tracing::info!(source = "worktree", branch = %branch_name, "remove_worktree command: SUCCESS — invalidating caches");
             crate::config::remove_branch_label(&repo_path, &branch_name);
             state.invalidate_repo_caches(&repo_path);
-            Ok(())
+            Ok(None)
         }
         Err(e) => {
             tracing::error!(source = "worktree", branch = %branch_name, "remove_worktree command: FAILED — {e}");
@@ -1184,6 +1649,220 @@ pub(crate) fn remove_orphan_worktree(
     Ok(())
 }
 
+/// A linked (non-main) worktree entry from `git worktree list --porcelain`.
+#[derive(Debug, Default, PartialEq)]
+struct LinkedWorktreeEntry {
+    path: String,
+    /// Short branch name from the `branch refs/heads/…` line. Present even when
+    /// the ref itself has been deleted (HEAD then reads as the zero SHA).
+    branch: Option<String>,
+    detached: bool,
+    locked: bool,
+    /// Git would drop the admin entry on `git worktree prune` (directory gone).
+    prunable: bool,
+}
+
+/// Parse `git worktree list --porcelain` into linked worktree entries, skipping
+/// the main worktree (first block).
+fn parse_linked_worktrees(porcelain: &str) -> Vec<LinkedWorktreeEntry> {
+    porcelain
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .skip(1)
+        .filter_map(|block| {
+            let mut entry = LinkedWorktreeEntry::default();
+            for line in block.lines() {
+                if let Some(path) = line.strip_prefix("worktree ") {
+                    entry.path = path.to_string();
+                } else if let Some(branch) = line.strip_prefix("branch refs/heads/") {
+                    entry.branch = Some(branch.to_string());
+                } else if line == "detached" {
+                    entry.detached = true;
+                } else if line == "locked" || line.starts_with("locked ") {
+                    entry.locked = true;
+                } else if line == "prunable" || line.starts_with("prunable ") {
+                    entry.prunable = true;
+                }
+            }
+            (!entry.path.is_empty()).then_some(entry)
+        })
+        .collect()
+}
+
+/// An orphaned worktree left in place by `prune_orphaned_worktrees`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub(crate) struct KeptWorktree {
+    pub(crate) path: String,
+    pub(crate) reason: String,
+}
+
+/// Outcome of `prune_orphaned_worktrees`. With `dry_run` nothing is touched and
+/// `removed`/`stale` list what would go.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub(crate) struct OrphanPruneReport {
+    pub(crate) dry_run: bool,
+    /// Orphaned worktree directories (detached HEAD or deleted branch) removed.
+    pub(crate) removed: Vec<String>,
+    /// Admin entries whose directory is already gone, cleared by `git worktree prune`.
+    pub(crate) stale: Vec<String>,
+    /// Orphans skipped because removing them would lose work, or they're locked.
+    pub(crate) kept: Vec<KeptWorktree>,
+}
+
+/// Why an orphaned worktree must be kept, or `None` if it is safe to remove.
+///
+/// When the branch ref was deleted, HEAD is unborn and every indexed file shows
+/// as staged-added, so the index is compared against the last commit in the
+/// worktree's HEAD reflog instead. Staged changes are kept when it differs or
+/// that commit can't be found.
+fn orphan_keep_reason(worktree_path: &Path, branch_missing: bool) -> Option<String> {
+    let status = match git_cmd(worktree_path).args(["status", "--porcelain"]).run() {
+        Ok(o) => o.stdout,
+        Err(e) => return Some(format!("cannot read status: {e}")),
+    };
+    let mut untracked = false;
+    let mut staged_only = false;
+    for line in status.lines() {
+        if line.starts_with("?? ") {
+            untracked = true;
+        } else if branch_missing && line.as_bytes().get(1) == Some(&b' ') {
+            staged_only = true;
+        } else {
+            return Some("uncommitted changes".to_string());
+        }
+    }
+    if staged_only {
+        let index_matches_last_head = last_reflog_head(worktree_path).is_some_and(|sha| {
+            git_cmd(worktree_path)
+                .args(["diff-index", "--cached", "--quiet", &sha])
+                .run_silent()
+                .is_some()
+        });
+        if !index_matches_last_head {
+            return Some("staged changes".to_string());
+        }
+    }
+    untracked.then(|| "untracked files".to_string())
+}
+
+/// The commit HEAD last pointed at, from the worktree's `logs/HEAD` (file
+/// I/O). Survives deleting the branch ref, unlike `HEAD@{0}`.
+fn last_reflog_head(worktree_path: &Path) -> Option<String> {
+    let git_dir = crate::git::resolve_git_dir(worktree_path)?;
+    let log = std::fs::read_to_string(git_dir.join("logs").join("HEAD")).ok()?;
+    let sha = log.lines().last()?.split(' ').nth(1)?;
+    (!sha.is_empty() && sha.bytes().any(|b| b != b'0')).then(|| sha.to_string())
+}
+
+/// Remove every orphaned linked worktree in one pass: those in detached HEAD
+/// with no branch (see `detect_orphan_worktrees`) and those whose branch ref no
+/// longer exists. Worktrees with uncommitted changes, untracked files, or a lock
+/// are kept and reported with a reason. Also runs `git worktree prune` to clear
+/// admin entries whose directory is already gone.
+pub(crate) fn prune_orphaned_worktrees_impl(
+    repo_path: &str,
+    dry_run: bool,
+) -> Result<OrphanPruneReport, String> {
+    let base_repo = PathBuf::from(repo_path);
+    let out = git_cmd(&base_repo)
+        .args(["worktree", "list", "--porcelain"])
+        .run()
+        .map_err(|e| format!("git worktree list failed: {e}"))?;
+
+    let mut report = OrphanPruneReport {
+        dry_run,
+        ..Default::default()
+    };
+    for entry in parse_linked_worktrees(&out.stdout) {
+        // Git never prunes a locked entry, whatever state it is in.
+        if entry.prunable && !entry.locked {
+            report.stale.push(entry.path);
+            continue;
+        }
+        let branch_missing = entry.branch.as_deref().is_some_and(|b| {
+            git_cmd(&base_repo)
+                .args([
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("refs/heads/{b}"),
+                ])
+                .run_silent()
+                .is_none()
+        });
+        if !entry.prunable && !branch_missing && !(entry.detached && entry.branch.is_none()) {
+            continue;
+        }
+        if entry.locked {
+            report.kept.push(KeptWorktree {
+                path: entry.path,
+                reason: "locked".to_string(),
+            });
+            continue;
+        }
+        let path = PathBuf::from(&entry.path);
+        if let Some(reason) = orphan_keep_reason(&path, branch_missing) {
+            report.kept.push(KeptWorktree {
+                path: entry.path,
+                reason,
+            });
+            continue;
+        }
+        if !dry_run {
+            let worktree = WorktreeInfo {
+                name: path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| entry.path.clone()),
+                path,
+                branch: None,
+                base_repo: base_repo.clone(),
+            };
+            if let Err(e) = remove_worktree_internal(&worktree, false) {
+                report.kept.push(KeptWorktree {
+                    path: entry.path,
+                    reason: e,
+                });
+                continue;
+            }
+        }
+        report.removed.push(entry.path);
+    }
+
+    if !dry_run {
+        git_cmd(&base_repo)
+            .args(["worktree", "prune"])
+            .run()
+            .map_err(|e| format!("git worktree prune failed: {e}"))?;
+    }
+    tracing::info!(
+        source = "worktree",
+        repo = %repo_path,
+        dry_run,
+        removed = report.removed.len(),
+        stale = report.stale.len(),
+        kept = report.kept.len(),
+        "prune_orphaned_worktrees"
+    );
+    Ok(report)
+}
+
+/// Bulk-remove orphaned worktrees (see `prune_orphaned_worktrees_impl`).
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) fn prune_orphaned_worktrees(
+    state: State<'_, Arc<AppState>>,
+    repo_path: String,
+    dry_run: bool,
+) -> Result<OrphanPruneReport, String> {
+    let report = prune_orphaned_worktrees_impl(&repo_path, dry_run)?;
+    if !dry_run {
+        state.invalidate_repo_caches(&repo_path);
+    }
+    Ok(report)
+}
+
 /// Validate that `worktree_path` is a known worktree of the given repo by checking it against
 /// `git worktree list --porcelain` output. Prevents arbitrary directory deletion.
 pub(crate) fn validate_worktree_path(repo_path: &str, worktree_path: &str) -> Result<(), String> {
@@ -1213,10 +1892,37 @@ pub(crate) fn validate_worktree_path(repo_path: &str, worktree_path: &str) -> Re
     Ok(())
 }
 
-/// Generate a worktree name (Story 063)
-#[cfg_attr(feature = "desktop", tauri::command)]
-pub(crate) fn generate_worktree_name_cmd(existing_names: Vec<String>) -> String {
-    generate_worktree_name(&existing_names)
+/// Generate a worktree name (Story 063). With `repo_path`, the name also
+/// avoids the repo's local/remote branches and worktree directories.
+#[cfg(feature = "desktop")]
+#[tauri::command]
+pub(crate) async fn generate_worktree_name_cmd(
+    state: State<'_, Arc<AppState>>,
+    existing_names: Vec<String>,
+    repo_path: Option<String>,
+) -> Result<String, String> {
+    // Listing branches and worktree dirs runs git, so keep it off the runtime.
+    let state = Arc::clone(&state);
+    tokio::task::spawn_blocking(move || {
+        generate_worktree_name_for(&state, &existing_names, repo_path.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Task panic: {e}"))
+}
+
+pub(crate) fn generate_worktree_name_for(
+    state: &AppState,
+    existing_names: &[String],
+    repo_path: Option<&str>,
+) -> String {
+    match repo_path {
+        Some(repo) => {
+            let repo = Path::new(repo);
+            let worktrees_dir = resolve_worktree_dir_for_repo(repo, &state.worktrees_dir);
+            generate_unique_worktree_name(repo, &worktrees_dir, existing_names)
+        }
+        None => generate_worktree_name(existing_names),
+    }
 }
 
 /// Generate a hybrid clone branch name: `{sanitized_source}--{random_name}`
@@ -1415,6 +2121,11 @@ pub(crate) struct SwitchBranchResult {
     pub(crate) success: bool,
     /// True if changes were auto-stashed before checkout
     pub(crate) stashed: bool,
+    /// True if autostashed changes were popped back after checkout
+    pub(crate) restored: bool,
+    /// Autostash pop failure; the stash entry is left intact when set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) stash_conflict: Option<String>,
     pub(crate) previous_branch: String,
     pub(crate) new_branch: String,
 }
@@ -1427,6 +2138,9 @@ pub(crate) struct SwitchBranchResult {
 ///
 /// When `stash` is true, performs `git stash push` before checkout and
 /// does NOT auto-pop (the user can pop manually).
+/// When `autostash` is true, stashes before checkout and pops after via
+/// [`crate::git::with_autostash`]; a conflicting pop is reported in
+/// `stash_conflict` and leaves the stash in place.
 /// When `force` is true, passes `--force` to discard uncommitted changes.
 /// Core logic for switching the checked-out branch. Blocking — callers wrap in
 /// `spawn_blocking` when on an async runtime.
@@ -1436,6 +2150,7 @@ pub(crate) fn switch_branch_impl(
     branch_name: String,
     force: bool,
     stash: bool,
+    autostash: bool,
 ) -> Result<SwitchBranchResult, String> {
     let base_repo = PathBuf::from(&repo_path);
 
@@ -1446,13 +2161,15 @@ pub(crate) fn switch_branch_impl(
         return Ok(SwitchBranchResult {
             success: true,
             stashed: false,
+            restored: false,
+            stash_conflict: None,
             previous_branch: previous_branch.clone(),
             new_branch: previous_branch,
         });
     }
 
-    // Check for uncommitted changes (unless force or stash)
-    if !force && !stash {
+    // Check for uncommitted changes (unless force, stash or autostash)
+    if !force && !stash && !autostash {
         let status_out = git_cmd(&base_repo)
             .args(["status", "--porcelain"])
             .run()
@@ -1482,17 +2199,29 @@ pub(crate) fn switch_branch_impl(
         args.push("--force");
     }
     args.push(&branch_name);
+    let checkout = || {
+        git_cmd(&base_repo)
+            .args(&args)
+            .run()
+            .map(|_| ())
+            .map_err(|e| format!("Checkout failed: {e}"))
+    };
 
-    git_cmd(&base_repo)
-        .args(&args)
-        .run()
-        .map_err(|e| format!("Checkout failed: {e}"))?;
+    let outcome = if autostash {
+        let reason = format!("switching to {branch_name}");
+        crate::git::with_autostash(&base_repo, &reason, checkout)?.1
+    } else {
+        checkout()?;
+        crate::git::AutostashOutcome::default()
+    };
 
     state.invalidate_repo_caches(&repo_path);
 
     Ok(SwitchBranchResult {
         success: true,
-        stashed: did_stash,
+        stashed: did_stash || outcome.stashed,
+        restored: outcome.restored,
+        stash_conflict: outcome.conflict,
         previous_branch,
         new_branch: branch_name,
     })
@@ -1507,8 +2236,16 @@ pub(crate) fn switch_branch(
     branch_name: String,
     force: bool,
     stash: bool,
+    autostash: Option<bool>,
 ) -> Result<SwitchBranchResult, String> {
-    switch_branch_impl(state.inner(), repo_path, branch_name, force, stash)
+    switch_branch_impl(
+        state.inner(),
+        repo_path,
+        branch_name,
+        force,
+        stash,
+        autostash.unwrap_or(false),
+    )
 }
 
 /// Create a local branch tracking a remote branch and switch to it.
@@ -1582,6 +2319,39 @@ pub(crate) fn finalize_merged_worktree(
     }
 }
 
+/// Steps 1 and 2 of [`merge_and_archive_worktree_impl`]. When the worktree is
+/// going to be deleted, a dirty worktree is refused before anything is merged
+/// so the caller never ends up with a merged branch and an undeletable worktree.
+fn merge_worktree_branch(
+    base_repo: &Path,
+    branch_name: &str,
+    target_branch: &str,
+    after_merge: &str,
+) -> Result<(), String> {
+    if after_merge == "delete"
+        && let Ok(worktree_path) = resolve_worktree_path_for_branch(base_repo, branch_name)
+    {
+        refuse_dirty_worktree(base_repo, &worktree_path, branch_name)?;
+    }
+
+    // 1. Ensure we're on the target branch in the base repo
+    git_cmd(base_repo)
+        .args(["checkout", target_branch])
+        .run()
+        .map_err(|e| format!("Failed to checkout {target_branch}: {e}"))?;
+
+    // 2. Merge the source branch
+    if let Err(e) = git_cmd(base_repo)
+        .args(["merge", branch_name, "--no-edit"])
+        .run()
+    {
+        // Abort the merge to leave a clean state
+        let _ = git_cmd(base_repo).args(["merge", "--abort"]).run();
+        return Err(format!("Merge failed (conflicts?): {e}"));
+    }
+    Ok(())
+}
+
 /// Merge a worktree branch into a target branch, then archive or delete the worktree.
 ///
 /// Steps:
@@ -1600,21 +2370,7 @@ pub(crate) fn merge_and_archive_worktree_impl(
     let script = resolve_archive_script(&repo_path);
     let base_repo = PathBuf::from(&repo_path);
 
-    // 1. Ensure we're on the target branch in the base repo
-    git_cmd(&base_repo)
-        .args(["checkout", &target_branch])
-        .run()
-        .map_err(|e| format!("Failed to checkout {target_branch}: {e}"))?;
-
-    // 2. Merge the source branch
-    if let Err(e) = git_cmd(&base_repo)
-        .args(["merge", &branch_name, "--no-edit"])
-        .run()
-    {
-        // Abort the merge to leave a clean state
-        let _ = git_cmd(&base_repo).args(["merge", "--abort"]).run();
-        return Err(format!("Merge failed (conflicts?): {e}"));
-    }
+    merge_worktree_branch(&base_repo, &branch_name, &target_branch, &after_merge)?;
 
     // 3. Handle the worktree based on after_merge setting
     match after_merge.as_str() {
@@ -2422,6 +3178,36 @@ mod tests {
         assert_ne!(first, second, "Should generate unique names");
     }
 
+    #[test]
+    fn worktree_name_collision_with_branches_and_dirs_bumps_suffix() {
+        let repo = setup_test_repo();
+        let worktrees_dir = repo.path().join("worktrees");
+        git_cmd(repo.path())
+            .args(["branch", "brave-neo-042"])
+            .run()
+            .unwrap();
+        git_cmd(repo.path())
+            .args(["update-ref", "refs/remotes/origin/brave-neo-043", "HEAD"])
+            .run()
+            .unwrap();
+        fs::create_dir_all(worktrees_dir.join("brave-neo-044")).unwrap();
+
+        let taken = taken_worktree_names(repo.path(), &worktrees_dir);
+        assert!(taken.contains("brave-neo-042"), "local branch: {taken:?}");
+        assert!(taken.contains("brave-neo-043"), "remote branch: {taken:?}");
+        assert!(taken.contains("brave-neo-044"), "worktree dir: {taken:?}");
+
+        assert_eq!(bump_until_unique("brave-neo-042", &taken), "brave-neo-045");
+        assert_eq!(bump_until_unique("calm-hal-001", &taken), "calm-hal-001");
+
+        git_cmd(repo.path()).args(["branch", "feat"]).run().unwrap();
+        let taken = taken_worktree_names(repo.path(), &worktrees_dir);
+        assert_eq!(bump_until_unique("feat", &taken), "feat-2");
+
+        let generated = generate_unique_worktree_name(repo.path(), &worktrees_dir, &[]);
+        assert!(!taken.contains(&generated), "{generated} collides");
+    }
+
     #[test]
     fn get_remote_default_branch_from_test_repo() {
         let repo = setup_test_repo();
@@ -2568,6 +3354,124 @@ mod tests {
         );
     }
 
+    #[test]
+    fn preview_worktree_removal_reports_dirty_worktree() {
+        let repo = setup_test_repo();
+        let repo_path = repo.path().to_string_lossy().to_string();
+        let worktrees_dir = repo.path().join("worktrees");
+        let config = WorktreeConfig {
+            task_name: "feat-dirty".to_string(),
+            base_repo: repo_path.clone(),
+            branch: Some("feat-dirty".to_string()),
+            create_branch: true,
+        };
+        let wt = create_worktree_internal(&worktrees_dir, &config, None)
+            .expect("Failed to create worktree");
+
+        let clean = preview_worktree_removal(&repo_path, "feat-dirty").unwrap();
+        assert!(!clean.has_uncommitted_changes);
+        assert!(clean.untracked_files.is_empty());
+        assert_eq!(clean.unpushed_commits, 0);
+
+        // One commit ahead of the default branch, a modified tracked file
+        // and an untracked one.
+        fs::write(wt.path.join("feature.txt"), "work").unwrap();
+        git_cmd(&wt.path)
+            .args(["add", "feature.txt"])
+            .run()
+            .unwrap();
+        git_cmd(&wt.path)
+            .args(["commit", "-m", "feature work"])
+            .run()
+            .unwrap();
+        fs::write(wt.path.join("README.md"), "# Changed").unwrap();
+        fs::write(wt.path.join("notes.txt"), "scratch").unwrap();
+
+        let report = preview_worktree_removal(&repo_path, "feat-dirty").unwrap();
+        assert!(report.has_uncommitted_changes);
+        assert_eq!(report.untracked_files, vec!["notes.txt".to_string()]);
+        assert_eq!(report.unpushed_commits, 1);
+        assert_eq!(
+            PathBuf::from(&report.would_remove_path)
+                .canonicalize()
+                .unwrap(),
+            wt.path.canonicalize().unwrap()
+        );
+        assert!(wt.path.exists(), "dry run must not remove anything");
+    }
+
+    #[test]
+    fn remove_worktree_by_branch_refuses_dirty_without_force() {
+        let repo = setup_test_repo();
+        let repo_path = repo.path().to_string_lossy().to_string();
+        let worktrees_dir = repo.path().join("worktrees");
+        let config = WorktreeConfig {
+            task_name: "feat-unsaved".to_string(),
+            base_repo: repo_path.clone(),
+            branch: Some("feat-unsaved".to_string()),
+            create_branch: true,
+        };
+        let wt = create_worktree_internal(&worktrees_dir, &config, None)
+            .expect("Failed to create worktree");
+        fs::write(wt.path.join("untracked.txt"), "keep me").unwrap();
+
+        let err = remove_worktree_by_branch(&repo_path, "feat-unsaved", false, None, false)
+            .expect_err("dirty worktree must be refused");
+        assert!(err.starts_with(DIRTY_WORKTREE_PREFIX), "{err}");
+        assert!(wt.path.join("untracked.txt").exists());
+
+        remove_worktree_by_branch(&repo_path, "feat-unsaved", false, None, true)
+            .expect("force removes a dirty worktree");
+        assert!(!wt.path.exists());
+    }
+
+    #[test]
+    fn merge_for_delete_refuses_dirty_worktree_before_merging() {
+        let repo = setup_test_repo();
+        let repo_path = repo.path().to_string_lossy().to_string();
+        let target = git_cmd(repo.path())
+            .args(["rev-parse", "--abbrev-ref", "HEAD"])
+            .run()
+            .unwrap()
+            .stdout
+            .trim()
+            .to_string();
+        let config = WorktreeConfig {
+            task_name: "feat-merge-dirty".to_string(),
+            base_repo: repo_path.clone(),
+            branch: Some("feat-merge-dirty".to_string()),
+            create_branch: true,
+        };
+        let wt = create_worktree_internal(&repo.path().join("worktrees"), &config, None)
+            .expect("Failed to create worktree");
+        fs::write(wt.path.join("feature.txt"), "done").unwrap();
+        git_cmd(&wt.path).args(["add", "."]).run().unwrap();
+        git_cmd(&wt.path)
+            .args(["commit", "-m", "feature"])
+            .run()
+            .unwrap();
+        fs::write(wt.path.join("feature.txt"), "half-finished edit").unwrap();
+        let head_before = git_cmd(repo.path())
+            .args(["rev-parse", "HEAD"])
+            .run()
+            .unwrap()
+            .stdout;
+
+        let err = merge_worktree_branch(repo.path(), "feat-merge-dirty", &target, "delete")
+            .expect_err("dirty worktree must be refused before the merge");
+        assert!(err.starts_with(DIRTY_WORKTREE_PREFIX), "{err}");
+        let head_after = git_cmd(repo.path())
+            .args(["rev-parse", "HEAD"])
+            .run()
+            .unwrap()
+            .stdout;
+        assert_eq!(head_before, head_after, "nothing may be merged");
+
+        merge_worktree_branch(repo.path(), "feat-merge-dirty", &target, "archive")
+            .expect("archiving keeps the worktree files, so the merge goes ahead");
+        assert!(repo.path().join("feature.txt").exists());
+    }
+
     #[test]
     fn resolve_worktree_dir_sibling_strategy() {
         use crate::config::WorktreeStorage;
@@ -2612,6 +3516,66 @@ mod tests {
         );
     }
 
+    fn save_worktrees_dir_override(repo: &Path, dir: &str) {
+        let key = repo.to_string_lossy().to_string();
+        let mut map = crate::config::RepoSettingsMap::default();
+        map.repos.insert(
+            key.clone(),
+            crate::config::RepoSettingsEntry {
+                path: key,
+                worktrees_dir: Some(dir.to_string()),
+                worktree_storage: Some(WorktreeStorage::InsideRepo),
+                ..Default::default()
+            },
+        );
+        crate::config::save_repo_settings(map).unwrap();
+    }
+
+    #[test]
+    fn worktrees_dir_override_is_used_for_creation() {
+        let config_dir = TempDir::new().unwrap();
+        let _guard = crate::config::set_config_dir_override(config_dir.path().to_path_buf());
+        let repo = setup_test_repo();
+        let target = TempDir::new().unwrap();
+        let override_dir = target.path().join("custom-worktrees");
+        save_worktrees_dir_override(repo.path(), override_dir.to_str().unwrap());
+
+        let worktrees_dir = resolve_worktree_dir_for_repo(repo.path(), Path::new("/unused"));
+        assert_eq!(worktrees_dir, override_dir);
+
+        let config = WorktreeConfig {
+            task_name: "override-task".to_string(),
+            base_repo: repo.path().to_string_lossy().to_string(),
+            branch: None,
+            create_branch: false,
+        };
+        let worktree = create_worktree_internal(&worktrees_dir, &config, None).unwrap();
+        assert!(worktree.path.starts_with(&override_dir));
+        assert!(worktree.path.exists());
+    }
+
+    #[test]
+    fn invalid_worktrees_dir_override_falls_back() {
+        let config_dir = TempDir::new().unwrap();
+        let _guard = crate::config::set_config_dir_override(config_dir.path().to_path_buf());
+        let repo = setup_test_repo();
+        let app_dir = PathBuf::from("/unused");
+
+        let inside = repo.path().join("nested-worktrees");
+        save_worktrees_dir_override(repo.path(), inside.to_str().unwrap());
+        assert_eq!(
+            resolve_worktree_dir_for_repo(repo.path(), &app_dir),
+            repo.path().join(".worktrees")
+        );
+        assert!(!inside.exists(), "rejected override must not be created");
+
+        save_worktrees_dir_override(repo.path(), "relative/worktrees");
+        assert_eq!(
+            resolve_worktree_dir_for_repo(repo.path(), &app_dir),
+            repo.path().join(".worktrees")
+        );
+    }
+
     #[test]
     fn parse_orphan_worktrees_detects_detached_linked_worktrees() {
         let porcelain = "\
@@ -2665,6 +3629,132 @@ branch refs/heads/feat
         assert!(orphans.is_empty());
     }
 
+    #[test]
+    fn parse_linked_worktrees_reads_flags_and_skips_main() {
+        let porcelain = "\
+worktree /repo/main
+HEAD abc123
+branch refs/heads/main
+
+worktree /wt/gone
+HEAD 0000000000000000000000000000000000000000
+branch refs/heads/gone
+
+worktree /wt/locked
+HEAD def456
+detached
+locked in use
+
+worktree /wt/moved
+HEAD def456
+branch refs/heads/moved
+prunable gitdir file points to non-existent location
+
+";
+        let entries = super::parse_linked_worktrees(porcelain);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].path, "/wt/gone");
+        assert_eq!(entries[0].branch.as_deref(), Some("gone"));
+        assert!(entries[1].detached && entries[1].locked && entries[1].branch.is_none());
+        assert!(entries[2].prunable && !entries[2].locked);
+    }
+
+    #[test]
+    fn prune_orphaned_worktrees_removes_clean_orphans_and_keeps_dirty() {
+        let repo = setup_test_repo();
+        let wt_root = TempDir::new().unwrap();
+        let add = |name: &str| {
+            let path = wt_root.path().join(name);
+            git_cmd(repo.path())
+                .args(["worktree", "add", "-b", name, path.to_str().unwrap()])
+                .run()
+                .expect("worktree add");
+            path
+        };
+        let gone = add("wt-gone");
+        let dirty = add("wt-dirty");
+        let live = add("wt-live");
+        fs::write(dirty.join("README.md"), "# edited").unwrap();
+        // Delete the branches out from under their worktrees (`git branch -D`
+        // refuses while they're checked out).
+        for branch in ["wt-gone", "wt-dirty"] {
+            git_cmd(repo.path())
+                .args(["update-ref", "-d", &format!("refs/heads/{branch}")])
+                .run()
+                .expect("update-ref -d");
+        }
+        let repo_path = repo.path().to_str().unwrap();
+        let ends_with = |paths: &[String], name: &str| paths.iter().any(|p| p.ends_with(name));
+
+        let preview = prune_orphaned_worktrees_impl(repo_path, true).unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.removed.len(), 1);
+        assert!(ends_with(&preview.removed, "wt-gone"));
+        assert_eq!(preview.kept.len(), 1);
+        assert!(preview.kept[0].path.ends_with("wt-dirty"));
+        assert_eq!(preview.kept[0].reason, "uncommitted changes");
+        assert!(gone.exists(), "dry run must not remove anything");
+
+        let report = prune_orphaned_worktrees_impl(repo_path, false).unwrap();
+        assert_eq!(report.removed, preview.removed);
+        assert_eq!(report.kept, preview.kept);
+        assert!(!gone.exists());
+        assert!(dirty.exists());
+        assert!(live.exists());
+    }
+
+    #[test]
+    fn prune_orphaned_worktrees_keeps_staged_edits_of_deleted_branch() {
+        let repo = setup_test_repo();
+        let wt_root = TempDir::new().unwrap();
+        let path = wt_root.path().join("wt-staged");
+        git_cmd(repo.path())
+            .args(["worktree", "add", "-b", "wt-staged", path.to_str().unwrap()])
+            .run()
+            .expect("worktree add");
+        fs::write(path.join("README.md"), "# staged edit").unwrap();
+        git_cmd(&path)
+            .args(["add", "README.md"])
+            .run()
+            .expect("git add");
+        git_cmd(repo.path())
+            .args(["update-ref", "-d", "refs/heads/wt-staged"])
+            .run()
+            .expect("update-ref -d");
+
+        let report = prune_orphaned_worktrees_impl(repo.path().to_str().unwrap(), false).unwrap();
+        assert!(report.removed.is_empty(), "{:?}", report.removed);
+        assert_eq!(report.kept.len(), 1);
+        assert_eq!(report.kept[0].reason, "staged changes");
+        assert_eq!(
+            fs::read_to_string(path.join("README.md")).unwrap(),
+            "# staged edit"
+        );
+    }
+
+    #[test]
+    fn prune_orphaned_worktrees_clears_stale_entries() {
+        let repo = setup_test_repo();
+        let wt_root = TempDir::new().unwrap();
+        let path = wt_root.path().join("wt-moved");
+        git_cmd(repo.path())
+            .args(["worktree", "add", "-b", "wt-moved", path.to_str().unwrap()])
+            .run()
+            .expect("worktree add");
+        fs::remove_dir_all(&path).unwrap();
+        let repo_path = repo.path().to_str().unwrap();
+
+        let report = prune_orphaned_worktrees_impl(repo_path, false).unwrap();
+        assert_eq!(report.stale.len(), 1);
+        assert!(report.stale[0].ends_with("wt-moved"));
+        assert!(report.removed.is_empty());
+        let listed = git_cmd(repo.path())
+            .args(["worktree", "list", "--porcelain"])
+            .run()
+            .unwrap();
+        assert!(!listed.stdout.contains("wt-moved"));
+    }
+
     #[test]
     fn delete_local_branch_removes_bare_branch() {
         let repo = setup_test_repo();
@@ -3213,4 +4303,77 @@ branch refs/heads/feat
             names
         );
     }
+
+    #[test]
+    fn test_plan_pr_checkout_refspec_and_branch() {
+        let same_repo = crate::github::PrHead {
+            number: 42,
+            head_ref: "feature/login".to_string(),
+            is_cross_repository: false,
+            head_owner: Some("acme".to_string()),
+            head_repo: Some("app".to_string()),
+        };
+        let remotes = parse_remote_urls(
+            "remote.origin.url git@github.com:acme/app.git\n\
+             remote.alice.url https://github.com/Alice/app-fork.git\n",
+        );
+        assert_eq!(remotes.len(), 2);
+
+        let plan = plan_pr_checkout(&same_repo, &remotes, "origin");
+        assert_eq!(plan.remote, "origin");
+        assert_eq!(plan.refspec, "+pull/42/head:refs/remotes/origin/pr/42");
+        assert_eq!(plan.start_point, "origin/pr/42");
+        assert_eq!(plan.branch, "pr/42-feature-login");
+
+        // Fork configured as a remote: fetch the branch from it.
+        let fork = crate::github::PrHead {
+            number: 7,
+            head_ref: "fix-typo".to_string(),
+            is_cross_repository: true,
+            head_owner: Some("alice".to_string()),
+            head_repo: Some("app-fork".to_string()),
+        };
+        let plan = plan_pr_checkout(&fork, &remotes, "origin");
+        assert_eq!(plan.remote, "alice");
+        assert_eq!(
+            plan.refspec,
+            "+refs/heads/fix-typo:refs/remotes/alice/fix-typo"
+        );
+        assert_eq!(plan.start_point, "alice/fix-typo");
+        assert_eq!(plan.branch, "pr/7-fix-typo");
+
+        // Unknown fork: fall back to the base remote's pull ref.
+        let plan = plan_pr_checkout(&fork, &remotes[..1], "origin");
+        assert_eq!(plan.remote, "origin");
+        assert_eq!(plan.refspec, "+pull/7/head:refs/remotes/origin/pr/7");
+        let plan = plan_pr_checkout(&fork, &remotes[..1], "upstream");
+        assert_eq!(plan.remote, "upstream");
+        assert_eq!(plan.refspec, "+pull/7/head:refs/remotes/upstream/pr/7");
+        assert_eq!(plan.start_point, "upstream/pr/7");
+    }
+
+    #[test]
+    fn test_resolve_pr_remote_prefers_explicit_then_upstream() {
+        let temp = setup_test_repo();
+        let repo = temp.path();
+        let git = |args: &[&str]| git_cmd(repo).args(args).run().expect("git");
+        git(&["remote", "add", "origin", "git@github.com:me/app.git"]);
+        git(&["remote", "add", "upstream", "git@github.com:acme/app.git"]);
+
+        // No upstream configured for the current branch → origin.
+        assert_eq!(resolve_pr_remote(repo, None).unwrap(), "origin");
+
+        let branch = git(&["symbolic-ref", "--short", "HEAD"]).stdout;
+        git(&[
+            "config",
+            &format!("branch.{}.remote", branch.trim()),
+            "upstream",
+        ]);
+        assert_eq!(resolve_pr_remote(repo, None).unwrap(), "upstream");
+        assert_eq!(resolve_pr_remote(repo, Some("origin")).unwrap(), "origin");
+
+        // Unknown names (including option-looking ones) never reach `git fetch`.
+        assert!(resolve_pr_remote(repo, Some("fork")).is_err());
+        assert!(resolve_pr_remote(repo, Some("--upload-pack=x")).is_err());
+    }
 }
@@ -354,6 +354,7 @@ const App: Component = () => {
 			promptRepoPath,
 			confirmOrphanCleanup: dialogs.confirmOrphanCleanup,
 			confirmRemoveLockedWorktree: dialogs.confirmRemoveLockedWorktree,
+			confirmRemoveDirtyWorktree: dialogs.confirmRemoveDirtyWorktree,
 		},
 		closeTerminal: terminalLifecycle.closeTerminal,
 		createNewTerminal: terminalLifecycle.createNewTerminal,
@@ -940,6 +941,8 @@ const App: Component = () => {
 	const WAKE_GRACE_MS = 15_000;
 	let lastWakeAt = 0;
 	const deferredCompletionTimers = new Map<string, ReturnType<typeof setTimeout>>();
+	// Terminals that already got a completion this busy cycle (cleared on idle→busy).
+	const completedThisCycle = new Set<string>();
 	// OSC 133 "C" timestamp captured at idle→busy, used to tell whether a real
 	// command ran during the busy window (vs prompt-redraw / sleep-wake false-busy).
 	const busyStartExecAt = new Map<string, number | null | undefined>();
@@ -955,6 +958,68 @@ const App: Component = () => {
 		onCleanup(() => unlistenWake?.());
 	}
 
+	// Fire (or suppress) the completion notification for a terminal. Shared by
+	// the busy→idle path and the agent task-complete event; at most one
+	// completion fires per busy cycle.
+	const fireCompletion = (id: string, durationMs: number) => {
+		deferredCompletionTimers.delete(id);
+		if (completedThisCycle.has(id)) {
+			appLogger.debug("terminal", `[Notify] ${id} completion SUPPRESSED — already notified this cycle`);
+			return;
+		}
+		// Re-check: terminal may have been removed during deferral.
+		const terminal = terminalsStore.get(id);
+		if (!terminal) return;
+
+		const startExec = busyStartExecAt.get(id);
+		const reason = getCompletionSuppression({
+			isActiveTerminal: terminalsStore.state.activeId === id,
+			isDebouncedBusy: !!terminalsStore.state.debouncedBusy[id],
+			activeSubTasks: terminal.activeSubTasks,
+			awaitingInput: terminal.awaitingInput,
+			durationMs,
+			thresholdMs: BUSY_COMPLETION_THRESHOLD_MS,
+			// Gate ONLY plain shells: agent TUIs (claude, …) don't run shell commands
+			// during their lifetime, so their OSC 133 "C" never advances — gating them
+			// would suppress every legitimate agent completion. Agents keep legacy behaviour.
+			usesShellIntegration: !terminal.agentType && terminal.lastCommandExecAt != null,
+			// Unknown busy-start (snapshot missing) → don't gate; otherwise a real
+			// command ran iff the OSC 133 "C" timestamp advanced during the window.
+			ranCommandDuringBusy: startExec === undefined || terminal.lastCommandExecAt !== startExec,
+		});
+		if (reason) {
+			appLogger.debug("terminal", `[Notify] ${id} completion SUPPRESSED — ${reason}`);
+			return;
+		}
+		completedThisCycle.add(id);
+		appLogger.info("terminal", `[Notify] ${id} completion — busy for ${Math.round(durationMs / 1000)}s then idle`);
+		terminalsStore.update(id, { activity: true, unseen: true });
+		notificationsStore.playCompletion(id);
+		const agentLabel = terminal.agentType ? terminal.agentType[0].toUpperCase() + terminal.agentType.slice(1) : null;
+		const repoPath = repositoriesStore.getRepoPathForTerminal(id);
+		const repoName = repoPath ? pathBasename(repoPath) : null;
+		const durationStr = `ran for ${Math.round(durationMs / 1000)}s`;
+		const subtitleParts: string[] = [];
+		if (repoName) subtitleParts.push(repoName);
+		subtitleParts.push(durationStr);
+		if (terminal.agentIntent) subtitleParts.push(terminal.agentIntent);
+
+		const defaultIcon =
+			'<svg viewBox="0 0 16 16" width="14" height="14" fill="currentColor"><path d="M0 2.75C0 1.784.784 1 1.75 1h12.5c.966 0 1.75.784 1.75 1.75v10.5A1.75 1.75 0 0 1 14.25 15H1.75A1.75 1.75 0 0 1 0 13.25Zm1.75-.25a.25.25 0 0 0-.25.25v10.5c0 .138.112.25.25.25h12.5a.25.25 0 0 0 .25-.25V2.75a.25.25 0 0 0-.25-.25ZM7.25 8a.749.749 0 0 1-.22.53l-2.25 2.25a.749.749 0 1 1-1.06-1.06L5.44 8 3.72 6.28a.749.749 0 1 1 1.06-1.06l2.25 2.25c.141.14.22.331.22.53Zm1.5 1.5h3a.75.75 0 0 1 0 1.5h-3a.75.75 0 0 1 0-1.5Z"/></svg>';
+
+		activityStore.addItem({
+			id: `terminal-done-${id}`,
+			pluginId: "core",
+			sectionId: "terminals",
+			title: `${agentLabel ?? terminal.name} finished`,
+			subtitle: subtitleParts.join(" · "),
+			icon: (terminal.agentType && getAgentIconSvg(terminal.agentType, 14)) || defaultIcon,
+			repoPath: repoPath ?? undefined,
+			dismissible: true,
+			onClick: () => navigateToTerminal(id),
+		});
+	};
+
 	const unsubBusyToIdle = terminalsStore.onBusyToIdle((id, durationMs) => {
 		if (durationMs < BUSY_COMPLETION_THRESHOLD_MS) return;
 		if (terminalsStore.state.activeId === id) return;
@@ -965,60 +1030,6 @@ const App: Component = () => {
 			return;
 		}
 
-		const fireCompletion = () => {
-			deferredCompletionTimers.delete(id);
-			// Re-check: terminal may have been removed during deferral.
-			const terminal = terminalsStore.get(id);
-			if (!terminal) return;
-
-			const startExec = busyStartExecAt.get(id);
-			const reason = getCompletionSuppression({
-				isActiveTerminal: terminalsStore.state.activeId === id,
-				isDebouncedBusy: !!terminalsStore.state.debouncedBusy[id],
-				activeSubTasks: terminal.activeSubTasks,
-				awaitingInput: terminal.awaitingInput,
-				durationMs,
-				thresholdMs: BUSY_COMPLETION_THRESHOLD_MS,
-				// Gate ONLY plain shells: agent TUIs (claude, …) don't run shell commands
-				// during their lifetime, so their OSC 133 "C" never advances — gating them
-				// would suppress every legitimate agent completion. Agents keep legacy behaviour.
-				usesShellIntegration: !terminal.agentType && terminal.lastCommandExecAt != null,
-				// Unknown busy-start (snapshot missing) → don't gate; otherwise a real
-				// command ran iff the OSC 133 "C" timestamp advanced during the window.
-				ranCommandDuringBusy: startExec === undefined || terminal.lastCommandExecAt !== startExec,
-			});
-			if (reason) {
-				appLogger.debug("terminal", `[Notify] ${id} completion SUPPRESSED — ${reason}`);
-				return;
-			}
-			appLogger.info("terminal", `[Notify] ${id} completion — busy for ${Math.round(durationMs / 1000)}s then idle`);
-			terminalsStore.update(id, { activity: true, unseen: true });
-			notificationsStore.playCompletion(id);
-			const agentLabel = terminal.agentType ? terminal.agentType[0].toUpperCase() + terminal.agentType.slice(1) : null;
-			const repoPath = repositoriesStore.getRepoPathForTerminal(id);
-			const repoName = repoPath ? pathBasename(repoPath) : null;
-			const durationStr = `ran for ${Math.round(durationMs / 1000)}s`;
-			const subtitleParts: string[] = [];
-			if (repoName) subtitleParts.push(repoName);
-			subtitleParts.push(durationStr);
-			if (terminal.agentIntent) subtitleParts.push(terminal.agentIntent);
-
-			const defaultIcon =
-				'<svg viewBox="0 0 16 16" width="14" height="14" fill="currentColor"><path d="M0 2.75C0 1.784.784 1 1.75 1h12.5c.966 0 1.75.784 1.75 1.75v10.5A1.75 1.75 0 0 1 14.25 15H1.75A1.75 1.75 0 0 1 0 13.25Zm1.75-.25a.25.25 0 0 0-.25.25v10.5c0 .138.112.25.25.25h12.5a.25.25 0 0 0 .25-.25V2.75a.25.25 0 0 0-.25-.25ZM7.25 8a.749.749 0 0 1-.22.53l-2.25 2.25a.749.749 0 1 1-1.06-1.06L5.44 8 3.72 6.28a.749.749 0 1 1 1.06-1.06l2.25 2.25c.141.14.22.331.22.53Zm1.5 1.5h3a.75.75 0 0 1 0 1.5h-3a.75.75 0 0 1 0-1.5Z"/></svg>';
-
-			activityStore.addItem({
-				id: `terminal-done-${id}`,
-				pluginId: "core",
-				sectionId: "terminals",
-				title: `${agentLabel ?? terminal.name} finished`,
-				subtitle: subtitleParts.join(" · "),
-				icon: (terminal.agentType && getAgentIconSvg(terminal.agentType, 14)) || defaultIcon,
-				repoPath: repoPath ?? undefined,
-				dismissible: true,
-				onClick: () => navigateToTerminal(id),
-			});
-		};
-
 		const t = terminalsStore.get(id);
 		// Suppress immediately if agent has known active sub-tasks.
 		if (t && t.activeSubTasks > 0) {
@@ -1028,15 +1039,25 @@ const App: Component = () => {
 		if (t?.agentType) {
 			// Agent process: defer — if the terminal stays idle for 10s, it's truly done.
 			clearTimeout(deferredCompletionTimers.get(id));
-			deferredCompletionTimers.set(id, setTimeout(fireCompletion, DEFERRED_COMPLETION_MS));
+			deferredCompletionTimers.set(id, setTimeout(() => fireCompletion(id, durationMs), DEFERRED_COMPLETION_MS));
 		} else {
 			// Non-agent: short defer to let question detection settle awaitingInput
 			// before checking suppression (avoids completion+question double-sound).
 			clearTimeout(deferredCompletionTimers.get(id));
-			deferredCompletionTimers.set(id, setTimeout(fireCompletion, 800));
+			deferredCompletionTimers.set(id, setTimeout(() => fireCompletion(id, durationMs), 800));
 		}
 	});
+	// Agent reported its turn finished (backend task-complete, already gated on a
+	// silence window): notify now instead of waiting out the deferral. A pending
+	// deferred completion for the same cycle is then dropped by the dedupe.
+	const unsubTaskComplete = terminalsStore.onTaskComplete((id, summary) => {
+		if (Date.now() - lastWakeAt < WAKE_GRACE_MS) return;
+		if (!terminalsStore.get(id)?.agentType) return;
+		appLogger.debug("terminal", `[Notify] ${id} task-complete — ${summary}`);
+		fireCompletion(id, terminalsStore.getBusyDuration(id));
+	});
 	const unsubIdleToBusy = terminalsStore.onIdleToBusy((id) => {
+		completedThisCycle.delete(id);
 		// Snapshot the last-command-exec timestamp at busy-start so onBusyToIdle can
 		// detect whether a real command ran during this busy window.
 		busyStartExecAt.set(id, terminalsStore.get(id)?.lastCommandExecAt ?? null);
@@ -1050,6 +1071,7 @@ const App: Component = () => {
 
 	onCleanup(() => {
 		unsubBusyToIdle();
+		unsubTaskComplete();
 		unsubIdleToBusy();
 		for (const timer of deferredCompletionTimers.values()) clearTimeout(timer);
 		deferredCompletionTimers.clear();
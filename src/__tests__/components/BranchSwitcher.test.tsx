@@ -28,7 +28,7 @@ describe("BranchSwitcher", () => {
 		branchSwitcherStore.close();
 		mockInvoke.mockReset();
 		mockInvoke.mockImplementation((cmd: string) => {
-			if (cmd === "get_git_branches") return Promise.resolve(MOCK_BRANCHES);
+			if (cmd === "get_branches_detail") return Promise.resolve(MOCK_BRANCHES);
 			return Promise.resolve(undefined);
 		});
 	});
@@ -73,6 +73,25 @@ describe("BranchSwitcher", () => {
 		});
 	});
 
+	it("requests branch details and shows last-commit meta", async () => {
+		mockInvoke.mockImplementation((cmd: string) => {
+			if (cmd === "get_branches_detail")
+				return Promise.resolve([
+					{ ...MOCK_BRANCHES[1], last_commit_message: "Add login form", last_commit_date: null, ahead: 2, behind: 0 },
+					MOCK_BRANCHES[0],
+				]);
+			return Promise.resolve(undefined);
+		});
+		branchSwitcherStore.open();
+		const { container } = render(() => <BranchSwitcher {...defaultProps()} />);
+
+		await waitFor(() => {
+			expect(container.querySelectorAll("[data-testid='branch-meta']").length).toBe(1);
+		});
+		expect(container.querySelector("[data-testid='branch-meta']")?.textContent).toBe("Add login form · ↑2");
+		expect(mockInvoke).toHaveBeenCalledWith("get_branches_detail", { path: "/repo" });
+	});
+
 	it("filters branches by query", async () => {
 		branchSwitcherStore.open();
 		const { container } = render(() => <BranchSwitcher {...defaultProps()} />);
@@ -2,6 +2,7 @@ import { fireEvent, render } from "@solidjs/testing-library";
 import { describe, expect, it, vi } from "vitest";
 import { ContentRenderer, stripEventHandlers } from "../../components/ui/ContentRenderer";
 import { stripAnsi } from "../../utils/stripAnsi";
+import { mockInvoke } from "../mocks/tauri";
 
 describe("stripAnsi", () => {
 	it("strips ANSI escape codes", () => {
@@ -78,6 +79,20 @@ describe("ContentRenderer", () => {
 		expect(content!.textContent).not.toContain("\x1B");
 	});
 
+	it("colorizes ANSI code blocks through the backend converter", async () => {
+		mockInvoke.mockResolvedValueOnce('<span style="color:#cd0000">red</span><script>x</script>');
+		const raw = "```\n\x1B[31mred\x1B[0m\n```";
+		const { container } = render(() => <ContentRenderer content={raw} />);
+		const code = container.querySelector("code.ansi-block") as HTMLElement;
+		// Plain text until the backend answers, never raw escapes.
+		expect(code.textContent).not.toContain("\x1B");
+		await vi.waitFor(() => {
+			expect(code.querySelector('span[style="color:#cd0000"]')?.textContent).toBe("red");
+		});
+		expect(mockInvoke).toHaveBeenCalledWith("ansi_to_html", { text: "\x1B[31mred\x1B[0m" });
+		expect(code.innerHTML).not.toContain("<script");
+	});
+
 	it("calls onLinkClick with href when .md link is clicked", () => {
 		const onLinkClick = vi.fn();
 		const { container } = render(() => (
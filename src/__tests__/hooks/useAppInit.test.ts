@@ -5,6 +5,7 @@ import { type AppInitDeps, browserCreatedSessions, initApp } from "../../hooks/u
 import { mdTabsStore } from "../../stores/mdTabs";
 import { paneLayoutStore, resetGroupCounter } from "../../stores/paneLayout";
 import { repositoriesStore } from "../../stores/repositories";
+import { statusBarTicker } from "../../stores/statusBarTicker";
 import { terminalsStore } from "../../stores/terminals";
 import { makeTerminal } from "../helpers/store";
 import { mockInvoke } from "../mocks/tauri";
@@ -702,6 +703,44 @@ describe("initApp", () => {
 		});
 	});
 
+	describe("plugin-parsed event", () => {
+		function capturePluginParsed() {
+			const listenMock = vi.mocked(listen);
+			let callback: ((event: { payload: unknown }) => void) | null = null;
+			listenMock.mockImplementation(((event: string, handler: (event: { payload: unknown }) => void) => {
+				if (event === "plugin-parsed") callback = handler;
+				return Promise.resolve(vi.fn());
+			}) as unknown as typeof listen);
+			return { getCallback: () => callback };
+		}
+
+		afterEach(() => {
+			statusBarTicker.removeAllForPlugin("ext");
+		});
+
+		it("shows session-less plugin intents in the status bar ticker", async () => {
+			const { getCallback } = capturePluginParsed();
+			await initApp(createMockDeps());
+
+			getCallback()!({ payload: { type: "intent", plugin_id: "ext", text: "Waiting for CI", title: "CI" } });
+
+			const msg = statusBarTicker.getAll().find((m) => m.pluginId === "ext");
+			expect(msg?.text).toBe("Waiting for CI");
+			expect(msg?.label).toBe("CI");
+		});
+
+		it("progress state 0 removes the plugin's progress message", async () => {
+			const { getCallback } = capturePluginParsed();
+			await initApp(createMockDeps());
+
+			getCallback()!({ payload: { type: "progress", plugin_id: "ext", state: 1, value: 40 } });
+			expect(statusBarTicker.getAll().find((m) => m.pluginId === "ext")?.text).toBe("40%");
+
+			getCallback()!({ payload: { type: "progress", plugin_id: "ext", state: 0, value: 0 } });
+			expect(statusBarTicker.getAll().some((m) => m.pluginId === "ext")).toBe(false);
+		});
+	});
+
 	describe("session-closed event (shellState exited)", () => {
 		type SessionClosedPayload = { session_id: string; reason: string; agent_type?: string | null };
 
@@ -36,6 +36,7 @@ describe("useDictation", () => {
 		refreshStatus: vi.fn().mockResolvedValue(undefined),
 		startRecording: vi.fn().mockResolvedValue(undefined),
 		stopRecording: vi.fn().mockResolvedValue(transcribeOk("hello world")),
+		injectText: vi.fn().mockResolvedValue("hello world"),
 	};
 
 	const mockSetStatusInfo = vi.fn();
@@ -48,6 +49,7 @@ describe("useDictation", () => {
 		vi.clearAllMocks();
 
 		dictationStore.setAutoSend(false);
+		dictationStore.setTarget("os-focus");
 
 		// Reset mock state
 		mockDictationStore.state = {
@@ -179,6 +181,25 @@ describe("useDictation", () => {
 			expect(mockSetStatusInfo).toHaveBeenCalledWith("Ready");
 		});
 
+		it("delivers through the backend with the active-session target", async () => {
+			await dictationStore.saveConfig({ dictation_target: "active-session" });
+			const id = terminalsStore.add({
+				sessionId: "sess-1",
+				fontSize: 14,
+				name: "Test",
+				cwd: null,
+				awaitingInput: null,
+			});
+			terminalsStore.setActive(id);
+
+			await dictation.handleDictationStart();
+			await dictation.handleDictationStop();
+
+			expect(mockDictationStore.injectText).toHaveBeenCalledWith("hello world", "sess-1");
+			expect(mockPty.write).not.toHaveBeenCalled();
+			expect(mockSetStatusInfo).toHaveBeenCalledWith("Ready");
+		});
+
 		it("does nothing when not recording and no pending start", async () => {
 			mockDictationStore.state.recording = false;
 
@@ -33,6 +33,12 @@ describe("useGitOperations", () => {
 		getRepoStructure: vi.fn().mockResolvedValue({ worktree_paths: {}, merged_branches: [] }),
 		getRepoDiffStats: vi.fn().mockResolvedValue({ diff_stats: {}, last_commit_ts: {} }),
 		removeWorktree: vi.fn().mockResolvedValue(undefined),
+		previewWorktreeRemoval: vi.fn().mockResolvedValue({
+			has_uncommitted_changes: true,
+			unpushed_commits: 0,
+			untracked_files: [],
+			would_remove_path: "/repo/wt",
+		}),
 		createWorktree: vi.fn(),
 		renameBranch: vi.fn().mockResolvedValue(undefined),
 		createBranch: vi.fn().mockResolvedValue(undefined),
@@ -63,6 +69,7 @@ describe("useGitOperations", () => {
 		confirmRemoveRepo: vi.fn().mockResolvedValue(true),
 		confirmRemoveWorktree: vi.fn().mockResolvedValue(true),
 		confirmRemoveLockedWorktree: vi.fn().mockResolvedValue(true),
+		confirmRemoveDirtyWorktree: vi.fn().mockResolvedValue(true),
 		confirmStashAndSwitch: vi.fn().mockResolvedValue(true),
 		reportGitError: vi.fn().mockResolvedValue(false),
 	};
@@ -83,6 +90,7 @@ describe("useGitOperations", () => {
 		mockDialogs.confirmRemoveRepo.mockResolvedValue(true);
 		mockDialogs.confirmRemoveWorktree.mockResolvedValue(true);
 		mockDialogs.confirmRemoveLockedWorktree.mockResolvedValue(true);
+		mockDialogs.confirmRemoveDirtyWorktree.mockResolvedValue(true);
 		mockDialogs.confirmStashAndSwitch.mockResolvedValue(true);
 		mockDialogs.reportGitError.mockResolvedValue(false);
 		mockRepo.switchBranch.mockResolvedValue({
@@ -1385,6 +1393,40 @@ describe("useGitOperations", () => {
 		});
 	});
 
+	describe("handleRemoveBranch (dirty worktree)", () => {
+		const DIRTY_ERROR = "worktree_dirty:feature has uncommitted changes and 1 untracked file(s)";
+
+		it("shows the dry-run report and retries with force when confirmed", async () => {
+			repositoriesStore.add({ path: "/repo", displayName: "Repo" });
+			repositoriesStore.setBranch("/repo", "feature", { worktreePath: "/repo/wt" });
+			mockRepo.removeWorktree.mockRejectedValueOnce(new Error(DIRTY_ERROR)).mockResolvedValueOnce(undefined);
+
+			await gitOps.handleRemoveBranch("/repo", "feature");
+
+			expect(mockRepo.previewWorktreeRemoval).toHaveBeenCalledWith("/repo", "feature");
+			expect(mockDialogs.confirmRemoveDirtyWorktree).toHaveBeenCalledWith(
+				"feature",
+				expect.objectContaining({ has_uncommitted_changes: true }),
+				true,
+			);
+			expect(mockRepo.removeWorktree).toHaveBeenLastCalledWith("/repo", "feature", true, true);
+			expect(repositoriesStore.get("/repo")?.branches["feature"]).toBeUndefined();
+		});
+
+		it("keeps branch in store when user cancels", async () => {
+			repositoriesStore.add({ path: "/repo", displayName: "Repo" });
+			repositoriesStore.setBranch("/repo", "feature", { worktreePath: "/repo/wt" });
+			mockRepo.removeWorktree.mockRejectedValueOnce(new Error(DIRTY_ERROR));
+			mockDialogs.confirmRemoveDirtyWorktree.mockResolvedValue(false);
+
+			await gitOps.handleRemoveBranch("/repo", "feature");
+
+			expect(mockRepo.removeWorktree).toHaveBeenCalledTimes(1);
+			expect(mockDialogs.confirmRemoveLockedWorktree).not.toHaveBeenCalled();
+			expect(repositoriesStore.get("/repo")?.branches["feature"]).toBeDefined();
+		});
+	});
+
 	describe("handleRemoveBranch (locked worktree)", () => {
 		const LOCKED_ERROR = "worktree_locked:fatal: cannot remove a locked working tree, lock reason: claude agent";
 
@@ -1524,7 +1566,7 @@ describe("useGitOperations", () => {
 
 			await gitOps.handleAddWorktree("/repo");
 
-			expect(mockRepo.generateWorktreeName).toHaveBeenCalledWith(["main", "feature-1"]);
+			expect(mockRepo.generateWorktreeName).toHaveBeenCalledWith(["main", "feature-1"], "/repo");
 		});
 
 		it("skips dialog and creates worktree instantly when promptOnCreate is false", async () => {
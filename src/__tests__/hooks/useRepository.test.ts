@@ -94,6 +94,25 @@ describe("useRepository", () => {
 		});
 	});
 
+	describe("previewWorktreeRemoval()", () => {
+		it("invokes remove_worktree with dryRun and returns the report", async () => {
+			const report = {
+				has_uncommitted_changes: true,
+				unpushed_commits: 2,
+				untracked_files: ["notes.txt"],
+				would_remove_path: "/repos/my-repo__wt/feature-x",
+			};
+			mockInvoke.mockResolvedValueOnce(report);
+			const result = await repo.previewWorktreeRemoval("/repos/my-repo", "feature-x");
+			expect(mockInvoke).toHaveBeenCalledWith("remove_worktree", {
+				repoPath: "/repos/my-repo",
+				branchName: "feature-x",
+				dryRun: true,
+			});
+			expect(result).toEqual(report);
+		});
+	});
+
 	describe("createWorktree()", () => {
 		it("calls invoke with base repo and branch, returns result", async () => {
 			const expected = {
@@ -1,5 +1,9 @@
 import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
-import { DEFAULT_NOTIFICATION_CONFIG, NotificationManager, type NotificationSound } from "../notifications";
+import {
+	DEFAULT_NOTIFICATION_CONFIG,
+	NotificationManager,
+	type NotificationSound,
+} from "../notifications";
 
 // Mock the invoke module — NotificationManager delegates to Rust
 vi.mock("../invoke", () => ({
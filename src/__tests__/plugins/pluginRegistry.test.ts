@@ -1084,6 +1084,32 @@ describe("PluginHost — Tier 3c httpFetch capability gating", () => {
 			}),
 		);
 	});
+
+	it("passes allowedHosts and per-request limits to the Rust command", async () => {
+		let host: PluginHost | null = null;
+		await pluginRegistry.register(
+			makePlugin("ext", (h) => {
+				host = h;
+			}),
+			["net:http"],
+			["https://*"],
+			undefined,
+			["api.example.com", "*.cdn.example.com"],
+		);
+		await host!.httpFetch("https://api.example.com/big", { timeoutMs: 5000, maxResponseBytes: 1024 });
+		const { invoke } = await import("../../invoke");
+		expect(invoke).toHaveBeenCalledWith(
+			"plugin_http_fetch",
+			expect.objectContaining({
+				pluginId: "ext",
+				limits: {
+					timeoutMs: 5000,
+					maxResponseBytes: 1024,
+					allowedHosts: ["api.example.com", "*.cdn.example.com"],
+				},
+			}),
+		);
+	});
 });
 
 // ---------------------------------------------------------------------------
@@ -1530,3 +1556,38 @@ describe("PluginHost — getActiveRepoPath", () => {
 		repositoriesStore.remove("/my/repo");
 	});
 });
+
+// ---------------------------------------------------------------------------
+// Tier 3k: Structured events capability gating
+// ---------------------------------------------------------------------------
+
+describe("PluginHost — emitEvent capability gating", () => {
+	it("external plugin without ui:events throws on emitEvent", async () => {
+		let host: PluginHost | null = null;
+		await pluginRegistry.register(
+			makePlugin("ext", (h) => {
+				host = h;
+			}),
+			[],
+		);
+		await expect(host!.emitEvent("progress", { state: 1, value: 10 })).rejects.toThrow(PluginCapabilityError);
+	});
+
+	it("passes plugin id, type and payload to the Rust command", async () => {
+		let host: PluginHost | null = null;
+		await pluginRegistry.register(
+			makePlugin("ext", (h) => {
+				host = h;
+			}),
+			["ui:events"],
+		);
+		await host!.emitEvent("intent", { text: "Waiting for CI" }, "sess-1");
+		const { invoke } = await import("../../invoke");
+		expect(invoke).toHaveBeenCalledWith("plugin_emit_event", {
+			pluginId: "ext",
+			eventType: "intent",
+			payload: { text: "Waiting for CI" },
+			sessionId: "sess-1",
+		});
+	});
+});
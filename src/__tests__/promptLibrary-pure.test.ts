@@ -209,5 +209,23 @@ describe("promptLibrary pure functions", () => {
 				expect(newCalls).toEqual(["process_prompt_content"]);
 			});
 		});
+
+		it("passes promptId and send so only real sends are counted", async () => {
+			await testInScopeAsync(async () => {
+				const prompt = store.createPrompt({
+					name: "test",
+					content: "Hello {name}",
+					category: "custom",
+					isFavorite: false,
+				});
+				const baseline = mockInvoke.mock.calls.length;
+				await store.processContent(prompt, { name: "World" });
+				await store.processContent(prompt, { name: "World" }, { send: true });
+				const calls = mockInvoke.mock.calls.slice(baseline);
+				expect(calls[0][1]).toMatchObject({ promptId: prompt.id, send: false });
+				expect(calls[1][1]).toMatchObject({ promptId: prompt.id, send: true });
+				expect(store.getPrompt(prompt.id)?.useCount).toBe(1);
+			});
+		});
 	});
 });
@@ -60,7 +60,9 @@ describe("notificationsStore", () => {
 
 	beforeEach(async () => {
 		vi.resetModules();
-		mockInvoke.mockReset().mockResolvedValue(undefined);
+		mockInvoke
+			.mockReset()
+			.mockImplementation((cmd: string) => Promise.resolve(cmd === "notification_allowed" ? true : undefined));
 		mockSetBadgeCount.mockReset().mockResolvedValue(undefined);
 		localStorage.clear();
 
@@ -313,6 +315,15 @@ describe("notificationsStore", () => {
 			await store.play("warning");
 			expect(mockManager.play).toHaveBeenCalledWith("warning");
 		});
+
+		it("asks the Rust throttle and skips the sound when it refuses", async () => {
+			mockInvoke.mockImplementation((cmd: string) =>
+				Promise.resolve(cmd === "notification_allowed" ? false : undefined),
+			);
+			await store.play("completion", { terminalId: "t1" });
+			expect(mockInvoke).toHaveBeenCalledWith("notification_allowed", { sound: "completion", sessionId: "t1" });
+			expect(mockManager.play).not.toHaveBeenCalled();
+		});
 	});
 
 	describe("playQuestion()", () => {
@@ -412,6 +412,22 @@ describe("repositoriesStore", () => {
 		});
 	});
 
+	describe("reloadOrder()", () => {
+		it("applies the saved order and keeps repos the file doesn't list", async () => {
+			await testInScopeAsync(async () => {
+				store.add({ path: "/a", displayName: "a" });
+				store.add({ path: "/b", displayName: "b" });
+				store.add({ path: "/c", displayName: "c" });
+				mockInvoke.mockResolvedValueOnce({ repoOrder: ["/c", "/a", "/gone"] });
+
+				await store.reloadOrder();
+
+				expect(mockInvoke).toHaveBeenCalledWith("load_repositories");
+				expect(store.getOrderedRepos().map((r) => r.path)).toEqual(["/c", "/a", "/b"]);
+			});
+		});
+	});
+
 	describe("hydrate()", () => {
 		it("loads repos from Rust backend and clears stale terminals", async () => {
 			mockInvoke.mockResolvedValueOnce({
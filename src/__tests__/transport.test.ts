@@ -99,6 +99,18 @@ describe("transport", () => {
 			expect(result.path).toBe("/sessions");
 		});
 
+		it("maps list_sessions_page to GET /sessions with filters", () => {
+			const result = mapCommandToHttp("list_sessions_page", {
+				repo: "/r/app",
+				paused: false,
+				limit: 20,
+				offset: 40,
+			});
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/sessions?offset=40&repo=%2Fr%2Fapp&paused=false&limit=20");
+			expect(mapCommandToHttp("list_sessions_page", {}).path).toBe("/sessions?offset=0");
+		});
+
 		it("maps can_spawn_session to GET /stats", () => {
 			const result = mapCommandToHttp("can_spawn_session", {});
 			expect(result.method).toBe("GET");
@@ -155,6 +167,17 @@ describe("transport", () => {
 			expect(result.path).toBe("/agents");
 		});
 
+		it("maps agent transcript commands", () => {
+			expect(mapCommandToHttp("list_agent_transcripts", {})).toMatchObject({
+				method: "GET",
+				path: "/agents/transcripts",
+			});
+			const prune = mapCommandToHttp("prune_agent_transcripts", { olderThanDays: 30 });
+			expect(prune.method).toBe("POST");
+			expect(prune.path).toBe("/agents/transcripts/prune");
+			expect(prune.body).toEqual({ olderThanDays: 30 });
+		});
+
 		it("maps get_repo_info to GET /repo/info?path=", () => {
 			const result = mapCommandToHttp("get_repo_info", { path: "/my/repo" });
 			expect(result.method).toBe("GET");
@@ -173,12 +196,22 @@ describe("transport", () => {
 			expect(result.path).toBe("/repo/diff-stats?path=%2Fmy%2Frepo");
 		});
 
+		it("maps get_diff_stats scope and perFile to query params", () => {
+			const result = mapCommandToHttp("get_diff_stats", { path: "/my/repo", scope: "staged", perFile: true });
+			expect(result.path).toBe("/repo/diff-stats?path=%2Fmy%2Frepo&scope=staged&per_file=true");
+		});
+
 		it("maps get_changed_files to GET /repo/files?path=", () => {
 			const result = mapCommandToHttp("get_changed_files", { path: "/my/repo" });
 			expect(result.method).toBe("GET");
 			expect(result.path).toBe("/repo/files?path=%2Fmy%2Frepo");
 		});
 
+		it("maps get_changed_files ref comparison to base/head query params", () => {
+			const result = mapCommandToHttp("get_changed_files", { path: "/my/repo", base: "main", head: "feat/x" });
+			expect(result.path).toBe("/repo/files?path=%2Fmy%2Frepo&base=main&head=feat%2Fx");
+		});
+
 		it("maps get_github_status to GET /repo/github?path=", () => {
 			const result = mapCommandToHttp("get_github_status", { path: "/my/repo" });
 			expect(result.method).toBe("GET");
@@ -203,6 +236,174 @@ describe("transport", () => {
 			expect(result.path).toBe("/repo/ci?path=%2Fmy%2Frepo&pr_number=42");
 		});
 
+		it("maps hydrate_pr_check_details to POST /repo/ci/batch", () => {
+			const result = mapCommandToHttp("hydrate_pr_check_details", { path: "/my/repo", prNumbers: [42, 43] });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/ci/batch");
+			expect(result.body).toEqual({ path: "/my/repo", pr_numbers: [42, 43] });
+		});
+
+		it("maps get_config_value to GET /repo/config", () => {
+			const result = mapCommandToHttp("get_config_value", { path: "/my/repo", key: "user.email" });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/repo/config?path=%2Fmy%2Frepo&key=user.email");
+		});
+
+		it("maps set_config_value to POST /repo/config", () => {
+			const result = mapCommandToHttp("set_config_value", {
+				path: "/my/repo",
+				key: "user.name",
+				value: "Bot",
+				scope: "worktree",
+			});
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/config");
+			expect(result.body).toEqual({ path: "/my/repo", key: "user.name", value: "Bot", scope: "worktree" });
+		});
+
+		it("maps lfs_status and fs_read_file_info to GET routes", () => {
+			expect(mapCommandToHttp("lfs_status", { path: "/my/repo" }).path).toBe("/repo/lfs-status?path=%2Fmy%2Frepo");
+			const result = mapCommandToHttp("fs_read_file_info", { repoPath: "/my/repo", file: "a b.bin" });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/fs/read-info?repoPath=%2Fmy%2Frepo&file=a%20b.bin");
+		});
+
+		it("maps reorder_repositories to POST /config/repositories/reorder", () => {
+			const result = mapCommandToHttp("reorder_repositories", { order: ["/b", "/a"] });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/config/repositories/reorder");
+			expect(result.body).toEqual({ order: ["/b", "/a"] });
+		});
+
+		it("maps get_blame_cache_stats to GET /repo/blame-cache-stats", () => {
+			const result = mapCommandToHttp("get_blame_cache_stats", {});
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/repo/blame-cache-stats");
+		});
+
+		it("maps find_repo_for_path to GET /repo/for-path", () => {
+			const result = mapCommandToHttp("find_repo_for_path", { path: "/r/src/a b.rs" });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/repo/for-path?path=%2Fr%2Fsrc%2Fa%20b.rs");
+		});
+
+		it("maps session recording start/stop to POST /sessions/:id/record", () => {
+			const start = mapCommandToHttp("start_session_recording", { sessionId: "s1", path: "/home/u/a.cast" });
+			expect(start.method).toBe("POST");
+			expect(start.path).toBe("/sessions/s1/record");
+			expect(start.body).toEqual({ action: "start", path: "/home/u/a.cast" });
+			const stop = mapCommandToHttp("stop_session_recording", { sessionId: "s1" });
+			expect(stop.path).toBe("/sessions/s1/record");
+			expect(stop.body).toEqual({ action: "stop" });
+		});
+
+		it("maps export_session_transcript to GET /sessions/:id/export", () => {
+			const result = mapCommandToHttp("export_session_transcript", { sessionId: "s1", format: "html" });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/sessions/s1/export?format=html");
+		});
+
+		it("maps set_active_selection to PUT /config/active-selection", () => {
+			const result = mapCommandToHttp("set_active_selection", { repoPath: "/r", worktreePath: "/wt/feat" });
+			expect(result.method).toBe("PUT");
+			expect(result.path).toBe("/config/active-selection");
+			expect(result.body).toEqual({ repo_path: "/r", worktree_path: "/wt/feat" });
+		});
+
+		it("maps diff_files to GET /fs/diff", () => {
+			const result = mapCommandToHttp("diff_files", { repoPath: "/r", fileA: "a.txt", fileB: "out/b c.txt" });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/fs/diff?repoPath=%2Fr&fileA=a.txt&fileB=out%2Fb%20c.txt");
+		});
+
+		it("maps set_poll_active to POST /repo/github-poller/active", () => {
+			const result = mapCommandToHttp("set_poll_active", { active: false });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/github-poller/active");
+			expect(result.body).toEqual({ active: false });
+		});
+
+		it("maps get_pr_timeline to GET /repo/pr-timeline", () => {
+			const result = mapCommandToHttp("get_pr_timeline", { path: "/r", prNumber: 42 });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/repo/pr-timeline?path=%2Fr&pr_number=42");
+		});
+
+		it("maps broadcast_input to POST /sessions/broadcast", () => {
+			const result = mapCommandToHttp("broadcast_input", {
+				sessionIds: ["a", "b"],
+				input: "continue",
+				specialKey: "enter",
+			});
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/sessions/broadcast");
+			expect(result.body).toEqual({ session_ids: ["a", "b"], input: "continue", special_key: "enter" });
+		});
+
+		it("maps parse_conflict_markers to POST /repo/conflict-markers", () => {
+			const result = mapCommandToHttp("parse_conflict_markers", { content: "<<<<<<< HEAD\n" });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/conflict-markers");
+			expect(result.body).toEqual({ content: "<<<<<<< HEAD\n" });
+		});
+
+		it("maps get_repo_stats to GET /repo/stats", () => {
+			const result = mapCommandToHttp("get_repo_stats", { path: "/my repo" });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/repo/stats?path=%2Fmy%20repo");
+		});
+
+		it("maps get_repo_stats countLines to count_lines=true", () => {
+			const result = mapCommandToHttp("get_repo_stats", { path: "/r", countLines: true });
+			expect(result.path).toBe("/repo/stats?path=%2Fr&count_lines=true");
+		});
+
+		it("maps cherry_pick to POST /repo/cherry-pick", () => {
+			const result = mapCommandToHttp("cherry_pick", { path: "/repo", commitSha: "abc123" });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/cherry-pick");
+			expect(result.body).toEqual({ path: "/repo", commitSha: "abc123" });
+		});
+
+		it("maps scan_for_repos to GET /repo/scan", () => {
+			const result = mapCommandToHttp("scan_for_repos", { rootDir: "/src", maxDepth: 2 });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/repo/scan?root_dir=%2Fsrc&max_depth=2");
+		});
+
+		it("maps inject_text with a target session to POST /dictation/inject", () => {
+			const result = mapCommandToHttp("inject_text", { text: "hello", sessionId: "s1" });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/dictation/inject");
+			expect(result.body).toEqual({ text: "hello", session_id: "s1" });
+		});
+
+		it("maps ansi_to_html to POST /logs/ansi-html", () => {
+			const result = mapCommandToHttp("ansi_to_html", { text: "\x1b[31mred" });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/logs/ansi-html");
+			expect(result.body).toEqual({ text: "\x1b[31mred" });
+		});
+
+		it("maps remove_worktree dry run to DELETE /worktrees/:branch?dryRun=true", () => {
+			const result = mapCommandToHttp("remove_worktree", { repoPath: "/repo", branchName: "feat/x", dryRun: true });
+			expect(result.method).toBe("DELETE");
+			expect(result.path).toBe("/worktrees/feat%2Fx?repoPath=%2Frepo&deleteBranch=true&dryRun=true");
+		});
+
+		it("maps create_worktree_from_pr to POST /worktrees/from-pr", () => {
+			const result = mapCommandToHttp("create_worktree_from_pr", { repoPath: "/my/repo", prNumber: 42 });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/worktrees/from-pr");
+			expect(result.body).toEqual({ repo_path: "/my/repo", pr_number: 42 });
+			const withRemote = mapCommandToHttp("create_worktree_from_pr", {
+				repoPath: "/my/repo",
+				prNumber: 42,
+				remote: "upstream",
+			});
+			expect(withRemote.body).toEqual({ repo_path: "/my/repo", pr_number: 42, remote: "upstream" });
+		});
+
 		it("maps search_content to GET /fs/search-content", () => {
 			const result = mapCommandToHttp("search_content", {
 				repoPath: "/my/repo",
@@ -393,6 +594,14 @@ describe("transport", () => {
 		});
 
 		// --- PTY/terminal read commands (story 062) ---
+		it("maps get_session_context to GET with {context} unwrap transform", () => {
+			const result = mapCommandToHttp("get_session_context", { sessionId: "s1" });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/sessions/s1/context");
+			expect(result.transform?.({ context: "docker" })).toBe("docker");
+			expect(result.transform?.({ context: null })).toBeNull();
+		});
+
 		it("maps get_shell_state to GET with {state} unwrap transform", () => {
 			const result = mapCommandToHttp("get_shell_state", { sessionId: "s1" });
 			expect(result.method).toBe("GET");
@@ -445,6 +654,25 @@ describe("transport", () => {
 			expect(result.path).toBe("/process/stats");
 		});
 
+		it("maps get_session_resources to GET /sessions/:id/resources", () => {
+			const result = mapCommandToHttp("get_session_resources", { sessionId: "s1" });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/sessions/s1/resources");
+		});
+
+		it("maps list_orphaned_processes to GET /process/orphans", () => {
+			const result = mapCommandToHttp("list_orphaned_processes", {});
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/process/orphans");
+		});
+
+		it("maps kill_orphaned_process to POST /process/orphans/kill with confirm", () => {
+			const result = mapCommandToHttp("kill_orphaned_process", { pid: 4242, confirm: true });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/process/orphans/kill");
+			expect(result.body).toEqual({ pid: 4242, confirm: true });
+		});
+
 		it("maps terminal_get_selection_text to GET with {text} unwrap transform", () => {
 			const result = mapCommandToHttp("terminal_get_selection_text", {
 				sessionId: "s1",
@@ -579,6 +807,13 @@ describe("transport", () => {
 			expect(result.body).toEqual({ path: "/r", name: "feat", force: false });
 		});
 
+		it("maps checkout_branch to POST /repo/checkout", () => {
+			const result = mapCommandToHttp("checkout_branch", { path: "/r", branch: "feat" });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/checkout");
+			expect(result.body).toEqual({ path: "/r", branch: "feat", autostash: false });
+		});
+
 		it("maps delete_local_branch to POST", () => {
 			const result = mapCommandToHttp("delete_local_branch", {
 				repoPath: "/r",
@@ -613,6 +848,30 @@ describe("transport", () => {
 			expect(result.body).toEqual({ repoPath: "/r", branchName: "feat", force: false, stash: true });
 		});
 
+		it("maps switch_branch autostash to POST body", () => {
+			const result = mapCommandToHttp("switch_branch", {
+				repoPath: "/r",
+				branchName: "feat",
+				force: false,
+				stash: false,
+				autostash: true,
+			});
+			expect(result.body).toEqual({
+				repoPath: "/r",
+				branchName: "feat",
+				force: false,
+				stash: false,
+				autostash: true,
+			});
+		});
+
+		it("maps run_git_command with autostash to POST", () => {
+			const result = mapCommandToHttp("run_git_command", { path: "/r", args: ["pull"], autostash: true });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/run-git");
+			expect(result.body).toEqual({ path: "/r", args: ["pull"], autostash: true });
+		});
+
 		it("maps merge_and_archive_worktree to POST", () => {
 			const result = mapCommandToHttp("merge_and_archive_worktree", {
 				repoPath: "/r",
@@ -644,6 +903,52 @@ describe("transport", () => {
 			expect(result.body).toEqual({ repoPath: "/r", issueNumber: 42 });
 		});
 
+		it("maps list_repo_issues to GET with state and labels", () => {
+			const result = mapCommandToHttp("list_repo_issues", {
+				path: "/r",
+				stateFilter: "closed",
+				labels: ["bug", "P1"],
+			});
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/repo/issues/list?path=%2Fr&state=closed&labels=bug%2CP1");
+		});
+
+		it("maps list_repo_issues without filters", () => {
+			const result = mapCommandToHttp("list_repo_issues", { path: "/r" });
+			expect(result.path).toBe("/repo/issues/list?path=%2Fr");
+		});
+
+		it("maps get_issue to GET", () => {
+			const result = mapCommandToHttp("get_issue", { repoPath: "/r", issueNumber: 42 });
+			expect(result.method).toBe("GET");
+			expect(result.path).toBe("/repo/issues/get?path=%2Fr&number=42");
+		});
+
+		it("maps add_issue_comment to POST", () => {
+			const result = mapCommandToHttp("add_issue_comment", { repoPath: "/r", issueNumber: 42, body: "hi" });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/issues/comment");
+			expect(result.body).toEqual({ repoPath: "/r", issueNumber: 42, body: "hi" });
+		});
+
+		it("maps request_reviewers to POST /repo/pr-review-request", () => {
+			const result = mapCommandToHttp("request_reviewers", {
+				repoPath: "/r",
+				prNumber: 7,
+				reviewers: ["alice"],
+			});
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/pr-review-request");
+			expect(result.body).toEqual({ repoPath: "/r", prNumber: 7, reviewers: ["alice"], teamReviewers: [] });
+		});
+
+		it("maps add_pr_comment to POST /repo/pr-comment", () => {
+			const result = mapCommandToHttp("add_pr_comment", { repoPath: "/r", prNumber: 7, body: "LGTM" });
+			expect(result.method).toBe("POST");
+			expect(result.path).toBe("/repo/pr-comment");
+			expect(result.body).toEqual({ repoPath: "/r", prNumber: 7, body: "LGTM" });
+		});
+
 		it("maps get_github_viewer_login to GET", () => {
 			const result = mapCommandToHttp("get_github_viewer_login", {});
 			expect(result.method).toBe("GET");
@@ -712,6 +1017,17 @@ describe("transport", () => {
 			});
 			expect(bl.path).toBe("/config/branch-label");
 			expect(bl.body).toEqual({ repoPath: "/r", branchName: "feat", label: "x" });
+			const reset = mapCommandToHttp("reset_repo_settings", { repoPath: "/r", categories: ["branch"] });
+			expect(reset.method).toBe("POST");
+			expect(reset.path).toBe("/config/repo-settings/reset");
+			expect(reset.body).toEqual({ repoPath: "/r", categories: ["branch"] });
+			const allow = mapCommandToHttp("notification_allowed", { sound: "completion", sessionId: "t1" });
+			expect(allow.method).toBe("POST");
+			expect(allow.path).toBe("/config/notifications/allow");
+			expect(allow.body).toEqual({ sound: "completion", sessionId: "t1" });
+			expect(mapCommandToHttp("explain_env", { repoPath: "/r" }).path).toBe(
+				"/config/repo-settings/explain-env?path=%2Fr",
+			);
 			const up = mapCommandToHttp("set_project_mcp_upstreams", {
 				repoPath: "/r",
 				upstreamNames: ["a"],
@@ -944,6 +1260,17 @@ describe("transport", () => {
 			});
 			expect(ptyLines.path).toContain("maxLines=100");
 
+			// plugin_emit_event
+			const emit = mapCommandToHttp("plugin_emit_event", {
+				pluginId: "my-plugin",
+				eventType: "progress",
+				payload: { state: 1, value: 40 },
+				sessionId: "sess-1",
+			});
+			expect(emit.method).toBe("POST");
+			expect(emit.path).toBe("/api/plugins/my-plugin/events");
+			expect(emit.body).toEqual({ eventType: "progress", payload: { state: 1, value: 40 }, sessionId: "sess-1" });
+
 			// register_loaded_plugin
 			const reg = mapCommandToHttp("register_loaded_plugin", {
 				pluginId: "my-plugin",
@@ -1026,6 +1353,12 @@ describe("transport", () => {
 				"install_plugin_from_url",
 				"uninstall_plugin",
 				"delete_plugin_data",
+				"take_config_recovery_notices",
+				"dry_run_spawn",
+				"generate_mcp_config_snippet",
+				"list_mcp_servers",
+				"preview_mcp_registration",
+				"parse_output_sample",
 			]) {
 				expect(INTENTIONALLY_UNMAPPED.has(cmd)).toBe(true);
 			}
@@ -1308,6 +1641,59 @@ describe("transport", () => {
 			globalThis.WebSocket = origWs;
 		});
 
+		it("raw mode reconnect resumes with since= from the last output frame", async () => {
+			const { subscribePty } = await import("../transport");
+			vi.useFakeTimers();
+
+			const instances: {
+				url: string;
+				onopen: (() => void) | null;
+				onmessage: ((e: { data: string }) => void) | null;
+				onclose: ((e: { code: number; reason?: string }) => void) | null;
+				onerror: unknown;
+				close: () => void;
+			}[] = [];
+
+			class MockWebSocket {
+				url: string;
+				onopen: (() => void) | null = null;
+				onmessage: ((e: { data: string }) => void) | null = null;
+				onclose: ((e: { code: number; reason?: string }) => void) | null = null;
+				onerror: unknown = null;
+				close = vi.fn();
+				constructor(url: string) {
+					this.url = url;
+					instances.push(this as never);
+				}
+			}
+
+			const origWs = globalThis.WebSocket;
+			globalThis.WebSocket = MockWebSocket as unknown as typeof WebSocket;
+
+			const subscribePromise = subscribePty("sess-1", vi.fn(), vi.fn());
+			instances[0].onopen?.();
+			const unsub = await subscribePromise;
+			expect(instances[0].url).not.toContain("since=");
+
+			// Catch-up frame, then a live frame advancing the byte offset to 120.
+			instances[0].onmessage?.({
+				data: JSON.stringify({ type: "output", data: "a", offset: 0, next_offset: 100, total_written: 100 }),
+			});
+			instances[0].onmessage?.({ data: JSON.stringify({ type: "output", data: "b", offset: 100, next_offset: 120 }) });
+
+			instances[0].onclose?.({ code: 1006 });
+			await vi.advanceTimersByTimeAsync(1000);
+
+			expect(instances.length).toBe(2);
+			expect(instances[1].url).toContain("since=120");
+			expect(instances[1].url).not.toContain("offset=");
+			instances[1].onopen?.();
+
+			unsub();
+			globalThis.WebSocket = origWs;
+			vi.useRealTimers();
+		});
+
 		it("log mode reconnect resumes from the tracked cursor, not the mount offset", async () => {
 			const { subscribePty } = await import("../transport");
 			vi.useFakeTimers();
@@ -16,6 +16,8 @@ function branch(overrides: Partial<BranchDetail> = {}): BranchDetail {
 		last_commit_date: "2026-06-05",
 		last_commit_message: "init",
 		last_commit_author: "Boss",
+		last_commit_sha: "a".repeat(40),
+		worktree_path: null,
 		base_ahead: null,
 		base_behind: null,
 		base_branch: null,
@@ -10,6 +10,7 @@ export type AgentType =
 	| "goose"
 	| "grok"
 	| "droid"
+	| "cline"
 	| "git"
 	| "api";
 
@@ -25,6 +26,7 @@ export const AGENT_TYPES: readonly AgentType[] = [
 	"goose",
 	"grok",
 	"droid",
+	"cline",
 	"git",
 	"api",
 ] as const;
@@ -284,6 +286,25 @@ export const AGENTS: Record<AgentType, AgentConfig> = {
 			prompt: [/\[y\/n\]/i],
 		},
 	},
+	cline: {
+		type: "cline",
+		name: "Cline",
+		binary: "cline",
+		description: "Cline's autonomous coding agent CLI",
+		defaultHeadlessTemplate: 'cline "{prompt}"',
+		resumeCommand: null,
+		sessionDiscovery: null,
+		spawnArgs: (prompt) => {
+			return [prompt];
+		},
+		outputFormat: "text",
+		detectPatterns: {
+			rateLimit: [/rate.?limit/i, /429/, /too many requests/i],
+			completion: [],
+			error: [/error:/i, /failed:/i],
+			prompt: [/\[y\/n\]/i],
+		},
+	},
 	git: {
 		type: "git",
 		name: "Git",
@@ -369,6 +390,7 @@ export const MCP_SUPPORT: Record<AgentType, boolean> = {
 	goose: true,
 	grok: false,
 	droid: false,
+	cline: false,
 	git: false,
 	api: false,
 };
@@ -388,6 +410,7 @@ export const HOOK_SUPPORT: Record<AgentType, boolean> = {
 	goose: false,
 	grok: true,
 	droid: false,
+	cline: false,
 	git: false,
 	api: false,
 };
@@ -404,6 +427,7 @@ export const AGENT_DISPLAY: Record<AgentType, { icon: string; color: string }> =
 	goose: { icon: "G", color: "#f59e0b" },
 	grok: { icon: "G", color: "#1a1a1a" },
 	droid: { icon: "D", color: "#f97316" },
+	cline: { icon: "C", color: "#6366f1" },
 	git: { icon: "G", color: "#f05032" },
 	api: { icon: "⚡", color: "#06b6d4" },
 };
@@ -69,6 +69,8 @@ export type TerminalRow = {
 	agentIntent: string | null;
 	currentTask: string | null;
 	activeSubTasks: number;
+	/** Agent is in plan mode — badged as "planning" next to the agent name */
+	planMode?: boolean;
 	isActive: boolean;
 	isPromoted: boolean;
 };
@@ -142,6 +144,7 @@ export const ActivityDashboard: Component<ActivityDashboardProps> = (props) => {
 			// Claude Code spinner verbs are decorative garbage — suppress them
 			currentTask: term.agentType === "claude" ? null : term.currentTask,
 			activeSubTasks: term.activeSubTasks,
+			planMode: term.planMode,
 			isActive: terminalsStore.state.activeId === id,
 			isPromoted: globalWorkspaceStore.isPromoted(id),
 		};
@@ -198,7 +201,10 @@ export const ActivityDashboard: Component<ActivityDashboardProps> = (props) => {
 										</span>
 									</Show>
 								</div>
-								<span class={s.agent}>{term.agent}</span>
+								<span class={s.agent}>
+									{term.agent}
+									{term.planMode ? " · planning" : ""}
+								</span>
 								<span class={`${s.status} ${term.status.className}`}>{term.status.label}</span>
 								<span class={s.lastActivity}>{term.isWorking ? "" : formatRelativeTime(term.idleSince)}</span>
 								<button
@@ -1,14 +1,21 @@
 import { type Component, createEffect, createMemo, createSignal, For, onCleanup, Show } from "solid-js";
 import { invoke } from "../../invoke";
 import { branchSwitcherStore } from "../../stores/branchSwitcher";
+import { formatRelativeTime } from "../../utils/time";
+import type { BranchDetail } from "../GitPanel/types";
 import shared from "../shared/dialog.module.css";
 import s from "./BranchSwitcher.module.css";
 
-interface BranchInfo {
-	name: string;
-	is_current: boolean;
-	is_remote: boolean;
-	is_main: boolean;
+type BranchInfo = BranchDetail;
+
+/** "summary · 3h ago · ↑2 ↓1" for a branch, empty when nothing is known. */
+function branchMeta(b: BranchInfo): string {
+	const parts: string[] = [];
+	if (b.last_commit_message) parts.push(b.last_commit_message);
+	if (b.last_commit_date) parts.push(formatRelativeTime(new Date(b.last_commit_date).getTime()));
+	const track = [b.ahead ? `↑${b.ahead}` : "", b.behind ? `↓${b.behind}` : ""].filter(Boolean).join(" ");
+	if (track) parts.push(track);
+	return parts.join(" · ");
 }
 
 export interface BranchSwitcherProps {
@@ -36,7 +43,7 @@ export const BranchSwitcher: Component<BranchSwitcherProps> = (props) => {
 		}
 
 		setLoading(true);
-		invoke<BranchInfo[]>("get_git_branches", { path: repoPath })
+		invoke<BranchInfo[]>("get_branches_detail", { path: repoPath })
 			.then((result) => setBranches(result))
 			.catch(() => setBranches([]))
 			.finally(() => setLoading(false));
@@ -178,6 +185,13 @@ export const BranchSwitcher: Component<BranchSwitcherProps> = (props) => {
 									onMouseEnter={() => setSelectedIndex(idx())}
 								>
 									<span class={s.branchName}>{branch.name}</span>
+									<Show when={branchMeta(branch)}>
+										{(meta) => (
+											<span data-testid="branch-meta" class={s.branchMeta} title={meta()}>
+												{meta()}
+											</span>
+										)}
+									</Show>
 									<Show when={branch.is_current}>
 										<span data-testid="badge-current" class={`${s.badge} ${s.badgeCurrent}`}>
 											current
@@ -1,4 +1,5 @@
 import { type Component, createEffect, createMemo, createSignal, For, onCleanup, Show } from "solid-js";
+import { invoke } from "../../invoke";
 import {
 	type AppLogAudience,
 	type AppLogEntry,
@@ -7,6 +8,7 @@ import {
 	appLogger,
 } from "../../stores/appLogger";
 import { errorLogStore } from "../../stores/errorLog";
+import { isTauri } from "../../transport";
 import s from "./ErrorLogPanel.module.css";
 
 /** Audience tabs. "User" (default) hides app-internal telemetry so it can't bury
@@ -174,6 +176,17 @@ export const ErrorLogPanel: Component = () => {
 		navigator.clipboard.writeText(text).catch(() => {});
 	};
 
+	/** Reveal the on-disk log file (all levels, survives restarts) for bug reports. */
+	const handleShowLogFile = async () => {
+		try {
+			const path = await invoke<string>("get_log_path");
+			const { revealItemInDir } = await import("@tauri-apps/plugin-opener");
+			await revealItemInDir(path);
+		} catch (err) {
+			appLogger.error("app", "Failed to show log file", err);
+		}
+	};
+
 	return (
 		<Show when={isOpen()}>
 			<div class={s.overlay} onClick={() => errorLogStore.close()}>
@@ -284,6 +297,11 @@ export const ErrorLogPanel: Component = () => {
 							{filteredEntries().length} of {appLogger.entryCount()} entries
 						</span>
 						<span style={{ "margin-left": "auto", display: "flex", "align-items": "center", gap: "12px" }}>
+							<Show when={isTauri()}>
+								<button class={s.clearBtn} onClick={handleShowLogFile} title="Show the log file on disk">
+									Show Log File
+								</button>
+							</Show>
 							<button class={s.clearBtn} onClick={handleCopyAll} title="Copy all visible entries">
 								Copy All
 							</button>
@@ -51,6 +51,10 @@ export interface BranchDetail {
 	last_commit_date: string | null;
 	last_commit_message: string | null;
 	last_commit_author: string | null;
+	/** Full SHA of the branch tip */
+	last_commit_sha: string | null;
+	/** Worktree the local branch is checked out in (main checkout included) */
+	worktree_path: string | null;
 	/** Commits ahead of stored base branch (tuicommander-base) */
 	base_ahead: number | null;
 	/** Commits behind stored base branch (tuicommander-base) */
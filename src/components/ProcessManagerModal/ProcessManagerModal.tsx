@@ -12,6 +12,14 @@ interface ProcessStats {
 	cpu_pct: number;
 }
 
+/** Process spawned by TUIC that outlived its session (e.g. after a crash) */
+interface OrphanedProcess {
+	pid: number;
+	name: string | null;
+	session_id: string;
+	recorded_at: number;
+}
+
 type SortKey = "name" | "pid" | "cpu" | "mem";
 
 function formatMemory(kb: number): string {
@@ -27,6 +35,7 @@ interface ProcessManagerModalProps {
 
 export const ProcessManagerModal: Component<ProcessManagerModalProps> = (props) => {
 	const [processes, setProcesses] = createSignal<ProcessStats[]>([]);
+	const [orphans, setOrphans] = createSignal<OrphanedProcess[]>([]);
 	const [sortKey, setSortKey] = createSignal<SortKey>("mem");
 	const [sortAsc, setSortAsc] = createSignal(false);
 	let timer: ReturnType<typeof setInterval> | undefined;
@@ -38,6 +47,29 @@ export const ProcessManagerModal: Component<ProcessManagerModalProps> = (props)
 		} catch (err) {
 			appLogger.error("app", "Failed to get process stats", err);
 		}
+		try {
+			setOrphans(await invoke<OrphanedProcess[]>("list_orphaned_processes"));
+		} catch (err) {
+			appLogger.error("app", "Failed to list orphaned processes", err);
+		}
+	}
+
+	async function killOrphan(orphan: OrphanedProcess): Promise<void> {
+		const message = `Kill orphaned process ${orphan.name ?? "unknown"} (PID ${orphan.pid})?`;
+		let confirmed: boolean;
+		try {
+			const { confirm } = await import("@tauri-apps/plugin-dialog");
+			confirmed = await confirm(message, { title: "Kill orphaned process", kind: "warning" });
+		} catch {
+			confirmed = window.confirm(message);
+		}
+		if (!confirmed) return;
+		try {
+			await invoke("kill_orphaned_process", { pid: orphan.pid, confirm: true });
+		} catch (err) {
+			appLogger.error("app", `Failed to kill orphaned process ${orphan.pid}`, err);
+		}
+		await refresh();
 	}
 
 	onMount(() => {
@@ -159,6 +191,29 @@ export const ProcessManagerModal: Component<ProcessManagerModalProps> = (props)
 						</table>
 					</Show>
 				</div>
+				<Show when={orphans().length > 0}>
+					<div class={s.orphans}>
+						<div class={s.orphansTitle}>Orphaned processes</div>
+						<table class={s.table}>
+							<tbody>
+								<For each={orphans()}>
+									{(orphan) => (
+										<tr>
+											<td title={orphan.name ?? undefined}>{orphan.name ?? "unknown"}</td>
+											<td class={`${s.pid} ${s.right}`}>{orphan.pid}</td>
+											<td class={s.pid}>{new Date(orphan.recorded_at * 1000).toLocaleString()}</td>
+											<td class={s.right}>
+												<button class={s.killButton} onClick={() => void killOrphan(orphan)}>
+													Kill
+												</button>
+											</td>
+										</tr>
+									)}
+								</For>
+							</tbody>
+						</table>
+					</div>
+				</Show>
 				<div class={s.footer}>
 					<span>
 						<span class={s.refreshIndicator} />
@@ -163,7 +163,7 @@ export const PromptDrawer: Component<PromptDrawerProps> = (props) => {
 		const activeTerminal = terminalsStore.getActive();
 		if (!activeTerminal?.sessionId) return;
 
-		const content = await promptLibraryStore.processContent(prompt, variables);
+		const content = await promptLibraryStore.processContent(prompt, variables, { send: true });
 
 		try {
 			// "compose" target routes through the compose panel for review;
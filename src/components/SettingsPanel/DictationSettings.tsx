@@ -2,7 +2,7 @@ import { type Component, createSignal, For, onMount, Show } from "solid-js";
 import { t } from "../../i18n";
 import { invoke } from "../../invoke";
 import { appLogger } from "../../stores/appLogger";
-import type { ModelInfo } from "../../stores/dictation";
+import type { DictationTarget, ModelInfo } from "../../stores/dictation";
 import { dictationStore, WHISPER_LANGUAGES } from "../../stores/dictation";
 import { cx } from "../../utils";
 import { KeyComboCapture } from "../shared/KeyComboCapture";
@@ -223,6 +223,50 @@ export const DictationSettings: Component = () => {
 				</div>
 			</div>
 
+			{/* Injection target */}
+			<div class={s.group}>
+				<label>{t("dictation.targetLabel", "Insert into")}</label>
+				<select
+					value={dictationStore.state.target}
+					onChange={(e) => dictationStore.setTarget(e.currentTarget.value as DictationTarget)}
+				>
+					<option value="os-focus">{t("dictation.targetOsFocus", "Focused input")}</option>
+					<option value="active-session">{t("dictation.targetActiveSession", "Active terminal session")}</option>
+				</select>
+				<p class={s.hint}>
+					{t(
+						"dictation.targetHint",
+						"Focused input types into whatever field had focus; active terminal session always writes to the active terminal.",
+					)}
+				</p>
+			</div>
+
+			{/* Focus window before injecting */}
+			<div class={s.group}>
+				<label>{t("dictation.focusWindowLabel", "Focus window")}</label>
+				<div class={s.toggle}>
+					<input
+						type="checkbox"
+						checked={dictationStore.state.focusWindowOnInject}
+						onChange={(e) => dictationStore.setFocusWindowOnInject(e.currentTarget.checked)}
+					/>
+					<span>{t("dictation.focusWindowHint", "Raise the TUICommander window before inserting transcribed text")}</span>
+				</div>
+			</div>
+
+			{/* Streaming partials */}
+			<div class={s.group}>
+				<label>{t("dictation.streamingLabel", "Live preview")}</label>
+				<div class={s.toggle}>
+					<input
+						type="checkbox"
+						checked={dictationStore.state.streaming}
+						onChange={(e) => dictationStore.setStreaming(e.currentTarget.checked)}
+					/>
+					<span>{t("dictation.streamingHint", "Show interim text while recording (uses more CPU/GPU)")}</span>
+				</div>
+			</div>
+
 			{/* Language */}
 			<div class={s.group}>
 				<label>{t("dictation.languageLabel", "Language")}</label>
@@ -72,6 +72,33 @@ interface McpStatus {
 	config_path: string | null;
 }
 
+interface McpRegistrationPreview {
+	config_path: string;
+	key_path: string[];
+	entry_name: string;
+	proposed: unknown;
+	existing: unknown | null;
+	unchanged: boolean;
+}
+
+/** Ask before overwriting an MCP entry the user (or another tool) already has. */
+async function confirmMcpOverwrite(agentType: AgentType): Promise<boolean> {
+	const preview = await invoke<McpRegistrationPreview>("preview_mcp_registration", { agentType });
+	if (preview.existing === null || preview.unchanged) return true;
+	const message = [
+		`${preview.config_path} already has a "${preview.entry_name}" entry:`,
+		JSON.stringify(preview.existing, null, 2),
+		"It will be replaced with:",
+		JSON.stringify(preview.proposed, null, 2),
+	].join("\n\n");
+	try {
+		const { confirm } = await import("@tauri-apps/plugin-dialog");
+		return await confirm(message, { title: "Replace MCP entry", kind: "warning" });
+	} catch {
+		return window.confirm(message);
+	}
+}
+
 // ---------------------------------------------------------------------------
 // Sub-components
 // ---------------------------------------------------------------------------
@@ -673,6 +700,7 @@ const AgentRow: Component<{
 			if (status?.installed) {
 				await invoke("remove_agent_mcp", { agentType: props.agentType });
 			} else {
+				if (!(await confirmMcpOverwrite(props.agentType))) return;
 				await invoke("install_agent_mcp", { agentType: props.agentType });
 			}
 			await loadMcpStatus();
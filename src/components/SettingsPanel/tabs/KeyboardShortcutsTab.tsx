@@ -5,6 +5,7 @@ import { getModifierSymbol, isMacOS } from "../../../platform";
 import { appLogger } from "../../../stores/appLogger";
 import { keybindingsStore } from "../../../stores/keybindings";
 import { settingsStore } from "../../../stores/settings";
+import { toastsStore } from "../../../stores/toasts";
 import { isTauri } from "../../../transport";
 import { comboToDisplay, keyFor } from "../../../utils/hotkey";
 import { keyEventToCombo, validateGlobalHotkeyCombo } from "../../../utils/keyRecorder";
@@ -361,6 +362,43 @@ const ResetIcon = () => (
 	</svg>
 );
 
+const footerButtonStyle = {
+	background: "none",
+	border: "1px solid var(--border)",
+	"border-radius": "var(--radius-md)",
+	padding: "4px 12px",
+	color: "var(--fg-secondary)",
+	cursor: "pointer",
+	"font-size": "var(--font-sm)",
+};
+
+/** Copy the keybinding profile (with layout prefs) to the clipboard for another machine */
+async function exportProfile() {
+	try {
+		await navigator.clipboard.writeText(await keybindingsStore.exportProfile(true));
+		toastsStore.add(t("helpPanel.profileCopied", "Keybinding profile copied"), "", "info");
+	} catch (err) {
+		appLogger.error("config", "Failed to export keybinding profile", err);
+		toastsStore.add(t("helpPanel.profileExportFailed", "Export failed"), String(err), "error");
+	}
+}
+
+/** Merge a profile pasted from another machine, reporting dropped unknown actions */
+async function importProfile() {
+	try {
+		const report = await keybindingsStore.importProfile(await navigator.clipboard.readText());
+		const dropped = report.dropped.length > 0 ? `Dropped unknown: ${report.dropped.join(", ")}` : "";
+		toastsStore.add(
+			t("helpPanel.profileImported", "Imported {count} keybindings", { count: String(report.imported) }),
+			dropped,
+			dropped ? "warn" : "info",
+		);
+	} catch (err) {
+		appLogger.warn("config", "Failed to import keybinding profile", err);
+		toastsStore.add(t("helpPanel.profileImportFailed", "Import failed"), String(err), "error");
+	}
+}
+
 export const KeyboardShortcutsTab: Component = () => {
 	const [filter, setFilter] = createSignal("");
 	// editingAction holds either a static ActionName or a plugin-namespaced
@@ -775,20 +813,14 @@ export const KeyboardShortcutsTab: Component = () => {
 				</p>
 			</Show>
 
-			<div style={{ "margin-top": "16px", "text-align": "right" }}>
-				<button
-					class={s.hint}
-					onClick={() => keybindingsStore.resetAll()}
-					style={{
-						background: "none",
-						border: "1px solid var(--border)",
-						"border-radius": "var(--radius-md)",
-						padding: "4px 12px",
-						color: "var(--fg-secondary)",
-						cursor: "pointer",
-						"font-size": "var(--font-sm)",
-					}}
-				>
+			<div style={{ "margin-top": "16px", display: "flex", gap: "8px", "justify-content": "flex-end" }}>
+				<button class={s.hint} onClick={exportProfile} style={footerButtonStyle}>
+					{t("helpPanel.exportProfile", "Copy profile")}
+				</button>
+				<button class={s.hint} onClick={importProfile} style={footerButtonStyle}>
+					{t("helpPanel.importProfile", "Import profile from clipboard")}
+				</button>
+				<button class={s.hint} onClick={() => keybindingsStore.resetAll()} style={footerButtonStyle}>
 					{t("helpPanel.resetAllDefaults", "Reset all to defaults")}
 				</button>
 			</div>
@@ -5,7 +5,7 @@ import type { NotificationSound } from "../../../notifications";
 import { appLogger } from "../../../stores/appLogger";
 import { notificationsStore } from "../../../stores/notifications";
 import { isTauri } from "../../../transport";
-import { SettingSlider, SettingToggle } from "../SettingFields";
+import { SettingInput, SettingSlider, SettingToggle } from "../SettingFields";
 import s from "../Settings.module.css";
 
 interface AudioOutputDevice {
@@ -200,6 +200,43 @@ export const NotificationsTab: Component = () => {
 					</div>
 				</Show>
 
+				<SettingInput
+					type="number"
+					label={t("notifications.label.minInterval", "Minimum Interval (seconds)")}
+					value={String(notificationsStore.state.config.min_interval_secs)}
+					onInput={(v) => notificationsStore.setMinInterval(Number(v) || 0)}
+					hint={t(
+						"notifications.hint.minInterval",
+						"Repeats of the same event from the same session within this window are dropped (0 = off)",
+					)}
+				/>
+
+				<div class={s.group}>
+					<SettingToggle
+						checked={notificationsStore.state.config.quiet_hours !== null}
+						onChange={(v) => notificationsStore.setQuietHours(v ? { start: "22:00", end: "07:00" } : null)}
+						label={t("notifications.toggle.quietHours", "Quiet hours")}
+						hint={t("notifications.hint.quietHours", "Only questions and errors notify inside this window")}
+					/>
+					<Show when={notificationsStore.state.config.quiet_hours}>
+						{(quiet) => (
+							<div class={s.toggle}>
+								<input
+									type="time"
+									value={quiet().start}
+									onChange={(e) => notificationsStore.setQuietHours({ ...quiet(), start: e.currentTarget.value })}
+								/>
+								<span>{t("notifications.label.quietHoursTo", "to")}</span>
+								<input
+									type="time"
+									value={quiet().end}
+									onChange={(e) => notificationsStore.setQuietHours({ ...quiet(), end: e.currentTarget.value })}
+								/>
+							</div>
+						)}
+					</Show>
+				</div>
+
 				<div class={s.group}>
 					<label>{t("notifications.label.notificationEvents", "Notification Events")}</label>
 					<p class={s.hint} style={{ "margin-bottom": "12px" }}>
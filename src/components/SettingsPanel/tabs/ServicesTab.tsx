@@ -79,10 +79,20 @@ interface McpStatus {
 	reachable?: boolean | null;
 }
 
+/** Subset of `diagnose_remote_access` used by the firewall hint */
+interface RemoteAccessDiagnosis {
+	verdict: string;
+	advice: string;
+}
+
 interface ServerConfig {
 	enabled: boolean;
 	port: number;
 	ipv6_enabled: boolean;
+	/** "0.0.0.0" = all interfaces, otherwise a specific local IP */
+	bind_address?: string;
+	/** Restrict the wildcard bind to one interface (e.g. "tailscale0") */
+	bind_interface?: string | null;
 }
 
 interface AuthConfig {
@@ -103,6 +113,9 @@ interface ServicesConfig {
 	server: ServerConfig;
 	auth: AuthConfig;
 	relay: RelayConfig;
+	/** Named presets applied by switch_remote_profile (keyed by name) */
+	remote_profiles?: Record<string, unknown>;
+	active_remote_profile?: string | null;
 }
 
 interface AppConfig {
@@ -126,6 +139,7 @@ interface RelayStatus {
 interface LocalIpEntry {
 	ip: string;
 	label: string;
+	interface?: string;
 }
 
 interface UpstreamStatusEntry {
@@ -155,7 +169,9 @@ const NATIVE_TOOLS: { name: string; description: string; actions: string }[] = [
 		description: "Repos, GitHub PRs, worktrees",
 		actions: "list, active, prs, status, worktree_list, worktree_create, worktree_remove",
 	},
+	{ name: "issues", description: "GitHub issues", actions: "list, get, comment" },
 	{ name: "ui", description: "Panel tabs + notifications", actions: "tab, toast, confirm" },
+	{ name: "fs", description: "Repo files (writes need mcp_allow_file_writes)", actions: "read, write, list, mkdir" },
 	{
 		name: "plugin_dev_guide",
 		description: "Plugin authoring reference",
@@ -185,9 +201,13 @@ export const ServicesTab: Component = () => {
 	const [qrDataUrl, setQrDataUrl] = createSignal<string | null>(null);
 	const [tokenDuration, setTokenDuration] = createSignal(86400);
 	const [ipv6Enabled, setIpv6Enabled] = createSignal(false);
+	const [bindAddress, setBindAddress] = createSignal("0.0.0.0");
+	const [remoteProfiles, setRemoteProfiles] = createSignal<string[]>([]);
+	const [activeRemoteProfile, setActiveRemoteProfile] = createSignal("");
 	const [lanAuthBypass, setLanAuthBypass] = createSignal(false);
 	const [urlCopied, setUrlCopied] = createSignal(false);
 	const [regenerating, setRegenerating] = createSignal(false);
+	const [diagnosis, setDiagnosis] = createSignal<RemoteAccessDiagnosis | null>(null);
 	const [disabledNativeTools, setDisabledNativeTools] = createSignal<string[]>([]);
 	const [collapseTools, setCollapseTools] = createSignal<boolean>(false);
 	const [upstreamStatus, setUpstreamStatus] = createSignal<UpstreamStatusEntry[]>([]);
@@ -221,7 +241,14 @@ export const ServicesTab: Component = () => {
 		setSelectedIp(preferred.ip);
 	});
 
-	const activeIp = () => selectedIp() || localIps()?.[0]?.ip;
+	/** Specific bind address, or null when listening on all interfaces */
+	const boundIp = () => {
+		const addr = bindAddress().trim();
+		return addr && addr !== "0.0.0.0" && addr !== "::" ? addr : null;
+	};
+
+	// When pinned to one interface, that is the only address the QR can point at
+	const activeIp = () => boundIp() || selectedIp() || localIps()?.[0]?.ip;
 
 	// Connect URL fetched from backend (token never reaches JS)
 	const [connectUrl, setConnectUrl] = createSignal<string | null>(null);
@@ -288,6 +315,9 @@ export const ServicesTab: Component = () => {
 			setRaHasPassword(config.services.auth.password_hash.length > 0);
 			setTokenDuration(config.services.auth.session_token_duration_secs ?? 86400);
 			setIpv6Enabled(config.services.server.ipv6_enabled ?? false);
+			setBindAddress(config.services.server.bind_address || "0.0.0.0");
+			setRemoteProfiles(Object.keys(config.services.remote_profiles ?? {}));
+			setActiveRemoteProfile(config.services.active_remote_profile ?? "");
 			setLanAuthBypass(config.services.auth.lan_auth_bypass ?? false);
 			setDisabledNativeTools(config.disabled_native_tools ?? []);
 			setCollapseTools(config.collapse_tools ?? false);
@@ -431,6 +461,37 @@ export const ServicesTab: Component = () => {
 			<Show when={raEnabled()}>
 				<div class={s.raBody}>
 					<div class={s.raFields}>
+						<Show when={remoteProfiles().length > 0}>
+							<div class={s.group}>
+								<label>{t("services.label.remoteProfile", "Profile")}</label>
+								<select
+									class={s.input}
+									value={activeRemoteProfile()}
+									onChange={async (e) => {
+										const name = e.currentTarget.value;
+										if (!name) return;
+										try {
+											await rpc("switch_remote_profile", { name });
+											await loadRemoteConfig();
+										} catch (err) {
+											appLogger.error("config", `Failed to switch remote profile to ${name}`, err);
+										}
+									}}
+								>
+									<option value="" disabled>
+										{t("services.option.customProfile", "Custom")}
+									</option>
+									<For each={remoteProfiles()}>{(name) => <option value={name}>{name}</option>}</For>
+								</select>
+								<p class={s.hint}>
+									{t(
+										"services.hint.remoteProfile",
+										"Applies the profile's bind address, port, IPv6, LAN bypass and TLS, then restarts the server.",
+									)}
+								</p>
+							</div>
+						</Show>
+
 						<div class={s.group}>
 							<label>{t("services.label.port", "Port")}</label>
 							<input
@@ -449,6 +510,36 @@ export const ServicesTab: Component = () => {
 							<p class={s.hint}>{t("services.hint.port", "TCP port for the remote access web server")}</p>
 						</div>
 
+						<div class={s.group}>
+							<label>{t("services.label.bindAddress", "Bind Address")}</label>
+							<select
+								class={s.input}
+								value={bindAddress()}
+								onChange={(e) => {
+									const addr = e.currentTarget.value;
+									setBindAddress(addr);
+									saveConfigField((c) => {
+										c.services.server.bind_address = addr;
+									});
+								}}
+							>
+								<option value="0.0.0.0">{t("services.option.allInterfaces", "All interfaces (0.0.0.0)")}</option>
+								<For each={localIps()}>
+									{(entry) => (
+										<option value={entry.ip}>
+											{entry.label} — {entry.ip}
+										</option>
+									)}
+								</For>
+							</select>
+							<p class={s.hint}>
+								{t(
+									"services.hint.bindAddress",
+									"Listen only on this interface (e.g. Tailscale). Requires server restart.",
+								)}
+							</p>
+						</div>
+
 						{/* Username + Password side by side */}
 						<div class={s.credentialsRow}>
 							<div class={s.group} style={{ flex: "1", "min-width": 0 }}>
@@ -513,7 +604,10 @@ export const ServicesTab: Component = () => {
 						<Show when={raEnabled()}>
 							<div class={s.group}>
 								<label>{t("services.label.networkInterface", "Network Interface")}</label>
-								<Show when={(localIps()?.length ?? 0) > 1} fallback={<code class={s.url}>{activeIp() ?? "…"}</code>}>
+								<Show
+									when={!boundIp() && (localIps()?.length ?? 0) > 1}
+									fallback={<code class={s.url}>{activeIp() ?? "…"}</code>}
+								>
 									<select class={s.input} value={selectedIp()} onChange={(e) => setSelectedIp(e.currentTarget.value)}>
 										<For each={localIps()}>
 											{(entry) => (
@@ -529,8 +623,25 @@ export const ServicesTab: Component = () => {
 								</p>
 								<Show when={status()?.reachable === false}>
 									<p class={s.hint} style={{ color: "var(--warning, #e5c07b)", "margin-top": "4px" }}>
-										{t("services.hint.firewallWarning", "Port may be blocked by a firewall")}
+										{t("services.hint.firewallWarning", "Port may be blocked by a firewall")}{" "}
+										<button
+											class={s.testBtn}
+											onClick={() =>
+												rpc<RemoteAccessDiagnosis>("diagnose_remote_access")
+													.then(setDiagnosis)
+													.catch((e) => setDiagnosis({ verdict: "error", advice: String(e) }))
+											}
+										>
+											{t("services.btn.diagnose", "Diagnose")}
+										</button>
 									</p>
+									<Show when={diagnosis()}>
+										{(d) => (
+											<p class={s.hint} data-testid="remote-diagnosis" style={{ "margin-top": "4px" }}>
+												{d().advice}
+											</p>
+										)}
+									</Show>
 								</Show>
 								<Show when={status()?.reachable === true}>
 									<p class={s.hint} style={{ color: "var(--green, #98c379)", "margin-top": "4px" }}>
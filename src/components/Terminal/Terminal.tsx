@@ -11,6 +11,7 @@ import { paneLayoutStore } from "../../stores/paneLayout";
 import { rateLimitStore } from "../../stores/ratelimit";
 import { FONT_FAMILIES, settingsStore } from "../../stores/settings";
 import { type AwaitingInputType, isShellState, terminalsStore } from "../../stores/terminals";
+import { toastsStore } from "../../stores/toasts";
 import { isTauri, subscribePty, type Unsubscribe } from "../../transport";
 import { onClickKeyDown } from "../../utils/a11y";
 import { keyFor } from "../../utils/hotkey";
@@ -42,12 +43,15 @@ type ParsedEvent =
 	| { type: "usage-limit"; percentage: number; limit_type: string }
 	| { type: "usage-exhausted"; reset_time: string | null }
 	| { type: "plan-file"; path: string }
+	| { type: "plan-mode"; active: boolean }
 	| { type: "user-input"; content: string; line: number }
 	| { type: "api-error"; pattern_name: string; matched_text: string; error_kind: string }
 	| { type: "tool-error"; matched_text: string }
 	| { type: "intent"; text: string; title?: string }
+	| { type: "agent-error"; text: string }
 	| { type: "suggest"; items: string[] }
 	| { type: "slash-menu"; items: Array<{ command: string; description: string; highlighted: boolean }> }
+	| { type: "slash-command"; command: string; args?: string }
 	| {
 			type: "choice-prompt";
 			title: string;
@@ -55,9 +59,18 @@ type ParsedEvent =
 			dismiss_key?: string;
 			amend_key?: string;
 	  }
+	| {
+			type: "tool-approval";
+			tool: string;
+			command: string;
+			options: Array<{ key: string; label: string; highlighted: boolean; destructive: boolean; hint?: string }>;
+	  }
 	| { type: "active-subtasks"; count: number; task_type: string }
 	| { type: "shell-state"; state: "busy" | "idle" }
 	| { type: "agent-session-conflict"; matched_text: string; kind: "in-use" | "not-found" }
+	| { type: "build-failure"; tool: "cargo" | "npm" | "pytest" | "tsc"; summary: string }
+	| { type: "task-complete"; summary: string }
+	| { type: "token-usage"; input_tokens: number; output_tokens: number; cost_usd: number | null }
 	| { type: "agent-block"; action: "start" | "end"; line: number; exit_code?: number };
 
 export interface TerminalProps {
@@ -336,6 +349,9 @@ export const Terminal: Component<TerminalProps> = (props) => {
 						notificationsStore.playInfo();
 					}
 					break;
+				case "plan-mode":
+					terminalsStore.update(props.id, { planMode: parsed.active });
+					break;
 				case "user-input":
 					planFileNotified = false;
 					// Record the prompt row for the green scrollbar marker. line < 0 (the
@@ -412,6 +428,17 @@ export const Terminal: Component<TerminalProps> = (props) => {
 				case "tool-error":
 					terminalsStore.setAwaitingInput(props.id, "error");
 					break;
+				case "task-complete":
+					// Backend gates this on agent sessions and a silence window
+					// (check_task_complete in pty.rs); App.tsx owns the sound and dedupe.
+					terminalsStore.notifyTaskComplete(props.id, parsed.summary);
+					break;
+				case "agent-error": {
+					const label = terminalsStore.get(props.id)?.agentType ?? "Agent";
+					appLogger.error("terminal", `${label} reported: ${parsed.text}`);
+					terminalsStore.setAwaitingInput(props.id, "error");
+					break;
+				}
 				case "agent-session-conflict": {
 					const oldUuid = terminalsStore.get(props.id)?.tuicSession;
 					const newUuid = crypto.randomUUID();
@@ -510,6 +537,14 @@ export const Terminal: Component<TerminalProps> = (props) => {
 					}
 					break;
 				}
+				case "tool-approval": {
+					// Permission prompts are high-stakes: show exactly what would run.
+					const verb = parsed.tool === "Bash" ? "run" : `use ${parsed.tool} on`;
+					const name = terminalsStore.get(props.id)?.name ?? props.id;
+					appLogger.info("terminal", `[ToolApproval] ${props.id} tool=${parsed.tool} command="${parsed.command}"`);
+					toastsStore.add(`${name}: agent wants to ${verb}`, parsed.command, "warn");
+					break;
+				}
 				case "agent-block": {
 					if (parsed.action === "start") {
 						terminalsStore.handleOsc133(props.id, "A", parsed.line);
@@ -1,5 +1,4 @@
 import { convertFileSrc } from "@tauri-apps/api/core";
-import AnsiToHtml from "ansi-to-html";
 // dompurify pinned to 3.4.7 (exact) in package.json: 3.4.8 switched template
 // scrubbing to a NodeIterator walk that happy-dom implements incompletely, so under
 // the test env DOMPurify strips ALL tags (h1 gone) while letting <script> through.
@@ -9,6 +8,7 @@ import DOMPurify from "dompurify";
 import { marked, type Tokens } from "marked";
 import "./markdown-content.css";
 import { type Component, createEffect, createMemo, onCleanup, Show } from "solid-js";
+import { invoke } from "../../invoke";
 import { appLogger } from "../../stores/appLogger";
 import { stripAnsi } from "../../utils/stripAnsi";
 import { injectTweakSentinels, parseTweakComments } from "../../utils/tweakComments";
@@ -53,10 +53,14 @@ marked.setOptions({
 	breaks: true, // Convert \n to <br>
 });
 
-const ansiConverter = new AnsiToHtml({ escapeXML: true });
 const ANSI_CSI_RE = /\x1b\[[\x30-\x3f]*[\x20-\x2f]*[\x40-\x7e]/;
 
-// Custom renderer: code blocks with ANSI sequences render with colors.
+function escapeHtml(text: string): string {
+	return text.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;").replace(/"/g, "&quot;");
+}
+
+// Custom renderer: code blocks with ANSI sequences render as plain text with the
+// raw source in data-ansi; renderAnsiBlocks colorizes them after mount.
 marked.use({
 	renderer: {
 		code(token: Tokens.Code) {
@@ -64,14 +68,30 @@ marked.use({
 			const baseCls = lang ? `language-${lang}` : "";
 			if (ANSI_CSI_RE.test(token.text)) {
 				const cls = [baseCls, "ansi-block"].filter(Boolean).join(" ");
-				return `<pre><code class="${cls}">${ansiConverter.toHtml(token.text)}</code></pre>\n`;
+				const plain = escapeHtml(stripAnsi(token.text));
+				return `<pre><code class="${cls}" data-ansi="${escapeHtml(token.text)}">${plain}</code></pre>\n`;
 			}
-			const escaped = token.text.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
-			return `<pre><code${baseCls ? ` class="${baseCls}"` : ""}>${escaped}</code></pre>\n`;
+			return `<pre><code${baseCls ? ` class="${baseCls}"` : ""}>${escapeHtml(token.text)}</code></pre>\n`;
 		},
 	},
 });
 
+/** Colorize ANSI code blocks with the backend's `ansi::to_html` converter. */
+async function renderAnsiBlocks(container: HTMLElement): Promise<void> {
+	const codeEls = container.querySelectorAll<HTMLElement>("code.ansi-block[data-ansi]");
+	for (const codeEl of codeEls) {
+		const text = codeEl.dataset.ansi ?? "";
+		delete codeEl.dataset.ansi;
+		try {
+			const html = await invoke<string>("ansi_to_html", { text });
+			if (typeof html !== "string") continue;
+			codeEl.innerHTML = DOMPurify.sanitize(html, { ALLOWED_TAGS: ["span"], ALLOWED_ATTR: ["style"] });
+		} catch (err) {
+			appLogger.warn("app", "ANSI rendering failed", err);
+		}
+	}
+}
+
 /**
  * Strips ANSI escape sequences from prose sections only, leaving code fence
  * content intact so the custom marked renderer can colorize it.
@@ -217,7 +237,15 @@ export const ContentRenderer: Component<ContentRendererProps> = (props) => {
 			});
 
 			return DOMPurify.sanitize(stripEventHandlers(html), {
-				ADD_ATTR: ["data-tweak-id", "data-tweak-at", "data-tweak-comment", "data-source-line", TILDE_SENTINEL, "style"],
+				ADD_ATTR: [
+					"data-tweak-id",
+					"data-tweak-at",
+					"data-tweak-comment",
+					"data-source-line",
+					"data-ansi",
+					TILDE_SENTINEL,
+					"style",
+				],
 				ALLOWED_URI_REGEXP,
 			});
 		} catch (err) {
@@ -271,8 +299,8 @@ export const ContentRenderer: Component<ContentRendererProps> = (props) => {
 
 	let containerRef: HTMLDivElement | undefined;
 
-	// After render, set indeterminate property on [~] checkboxes (not settable via HTML attribute)
-	// and render Mermaid diagrams from ```mermaid code blocks.
+	// After render, set indeterminate property on [~] checkboxes (not settable via HTML attribute),
+	// colorize ANSI code blocks and render Mermaid diagrams from ```mermaid code blocks.
 	createEffect(() => {
 		processedContent(); // subscribe to re-renders
 		if (!containerRef) return;
@@ -284,6 +312,7 @@ export const ContentRenderer: Component<ContentRendererProps> = (props) => {
 			// Turn highlight sentinels into <span class="tweak-highlight"> wrappers.
 			const comments = tweakComments();
 			if (comments.length > 0) applyTweakDomHighlights(containerRef, comments);
+			renderAnsiBlocks(containerRef);
 			renderMermaidBlocks(containerRef);
 		});
 		onCleanup(() => cancelAnimationFrame(raf));
@@ -8,6 +8,7 @@ import { paneLayoutStore } from "../stores/paneLayout";
 import { repoSettingsStore } from "../stores/repoSettings";
 import { repositoriesStore } from "../stores/repositories";
 import { settingsStore } from "../stores/settings";
+import { statusBarTicker } from "../stores/statusBarTicker";
 import { terminalsStore } from "../stores/terminals";
 import { toastsStore } from "../stores/toasts";
 import { applyAppTheme, listenForThemeChanges, loadThemes } from "../themes";
@@ -181,8 +182,11 @@ export async function initApp(deps: AppInitDeps) {
 	// Repo watchers are started by the Rust setup closure (instant with raw notify).
 	// No frontend invoke needed — avoids IPC contention during hydration.
 
-	listen<{ repo_path: string; branch: string }>("head-changed", (event) => {
+	listen<{ repo_path: string; branch: string; activate?: boolean }>("head-changed", (event) => {
 		const { repo_path, branch } = event.payload;
+		// Backend's active selection says a worktree of this repo is shown: record
+		// the main checkout's new branch but don't switch the sidebar to it.
+		const activate = event.payload.activate !== false;
 		const repo = repositoriesStore.get(repo_path);
 		if (!repo) return;
 
@@ -210,14 +214,14 @@ export async function initApp(deps: AppInitDeps) {
 				// Merge terminal state from old → new, then remove the old entry.
 				repositoriesStore.mergeBranchState(repo_path, oldBranch, branch);
 				repositoriesStore.removeBranch(repo_path, oldBranch);
-				repositoriesStore.setActiveBranch(repo_path, branch);
+				if (activate) repositoriesStore.setActiveBranch(repo_path, branch);
 			}
 		} else {
 			// Worktree branch — just ensure target exists and activate it.
 			if (!repo.branches[branch]) {
 				repositoriesStore.setBranch(repo_path, branch, { name: branch });
 			}
-			repositoriesStore.setActiveBranch(repo_path, branch);
+			if (activate) repositoriesStore.setActiveBranch(repo_path, branch);
 		}
 
 		// Invalidate caches for this repo so next poll fetches fresh data
@@ -243,6 +247,21 @@ export async function initApp(deps: AppInitDeps) {
 	// The coalescer collapses the burst WITHOUT losing bumps (each repo is flushed
 	// next frame), so panels re-fetch exactly once. (Backend already skips emits
 	// when git-state is unchanged; this is defense-in-depth for residual bursts.)
+	// Use 500ms when idle, 1000ms when a refresh is already running so the next
+	// one doesn't race it.
+	const scheduleBranchStatsRefresh = () => {
+		const delay = activeRefresh !== null ? 1000 : 500;
+		if (branchStatsTimer) clearTimeout(branchStatsTimer);
+		branchStatsTimer = setTimeout(() => {
+			branchStatsTimer = null;
+			const result = deps.refreshAllBranchStats();
+			if (result && typeof (result as Promise<void>).then === "function") {
+				activeRefresh = (result as Promise<void>).finally(() => {
+					activeRefresh = null;
+				});
+			}
+		}, delay);
+	};
 	const revisionCoalescer = createRevisionCoalescer((repoPath) => repositoriesStore.bumpRevision(repoPath));
 	listen<{ repo_path: string }>("repo-changed", (event) => {
 		const { repo_path } = event.payload;
@@ -256,22 +275,22 @@ export async function initApp(deps: AppInitDeps) {
 		// (Not folded into the branchStatsTimer below — that setTimeout is cleared
 		// on each event, which would drop bumps and leave panels stale, story 1277-31a0.)
 		revisionCoalescer.bump(repo_path);
-		// Discover external worktree changes. Use 500ms when idle, 1000ms when a
-		// refresh is already running so the next one doesn't race it. Only the
-		// branch-stats refresh is debounced; the revision bump above is not.
-		const delay = activeRefresh !== null ? 1000 : 500;
-		if (branchStatsTimer) clearTimeout(branchStatsTimer);
-		branchStatsTimer = setTimeout(() => {
-			branchStatsTimer = null;
-			const result = deps.refreshAllBranchStats();
-			if (result && typeof (result as Promise<void>).then === "function") {
-				activeRefresh = (result as Promise<void>).finally(() => {
-					activeRefresh = null;
-				});
-			}
-		}, delay);
+		// Discover external worktree changes. Only the branch-stats refresh is
+		// debounced; the revision bump above is not.
+		scheduleBranchStatsRefresh();
 	}).catch((err) => appLogger.error("app", "Failed to register repo-changed listener", err));
 
+	// The backend reordered repositories (reorder_repositories) — pick up the new order.
+	listen<{ repo_order: string[] }>("repositories-changed", () => {
+		repositoriesStore.reloadOrder();
+	}).catch((err) => appLogger.error("app", "Failed to register repositories-changed listener", err));
+
+	// A worktree was added or pruned outside the app (e.g. `git worktree remove`
+	// in a terminal) — reconcile the sidebar instead of keeping stale entries.
+	listen<{ repo_path: string; worktrees: Record<string, string> }>("worktrees-changed", () => {
+		scheduleBranchStatsRefresh();
+	}).catch((err) => appLogger.error("app", "Failed to register worktrees-changed listener", err));
+
 	// Worktree background recreation failed — clear the pending placeholder,
 	// release the per-repo create lock, and tell the user what went wrong.
 	listen<{ repoPath: string; branch: string; reason: string }>("worktree-create-failed", (event) => {
@@ -285,6 +304,101 @@ export async function initApp(deps: AppInitDeps) {
 		toastsStore.add(title, message ?? "", safeLevel, sound === true);
 	}).catch((err) => appLogger.error("app", "Failed to register mcp-toast listener", err));
 
+	// Plugin structured events emitted with no terminal to attach to (plugin_emit_event
+	// without a session) — surfaced in the status bar ticker instead.
+	listen<{
+		type: string;
+		plugin_id: string;
+		text?: string;
+		title?: string;
+		full_line?: string;
+		state?: number;
+		value?: number;
+	}>("plugin-parsed", (event) => {
+		const { type, plugin_id: pluginId } = event.payload;
+		const id = `plugin-event-${type}`;
+		if (type === "progress" && event.payload.state === 0) {
+			statusBarTicker.removeMessage(id, pluginId);
+			return;
+		}
+		const text =
+			type === "intent"
+				? event.payload.text
+				: type === "status-line"
+					? event.payload.full_line
+					: type === "progress"
+						? `${event.payload.value ?? 0}%`
+						: undefined;
+		if (!text) return;
+		statusBarTicker.addMessage({
+			id,
+			pluginId,
+			text,
+			label: event.payload.title,
+			priority: 10,
+			ttlMs: 60_000,
+		});
+	}).catch((err) => appLogger.error("app", "Failed to register plugin-parsed listener", err));
+
+	// PTY spawn hit a transient failure (fd exhaustion, EAGAIN) and is being retried
+	listen<{ attempt: number; max_retries: number; delay_ms: number; error: string }>(
+		"session-spawn-retry",
+		(event) => {
+			const { attempt, max_retries, error } = event.payload;
+			appLogger.warn("terminal", `Terminal spawn failed, retrying (${attempt}/${max_retries})`, { error });
+			toastsStore.add("Starting terminal", `Retrying (${attempt}/${max_retries})…`, "warn");
+		},
+	).catch((err) => appLogger.error("app", "Failed to register session-spawn-retry listener", err));
+
+	// Input went unanswered past pty_wedge_timeout_secs with the same foreground process
+	listen<{ session_id: string; foreground: string | null; silent_secs: number }>(
+		"session-possibly-wedged",
+		(event) => {
+			const { session_id, foreground, silent_secs } = event.payload;
+			appLogger.warn("terminal", `Session possibly wedged (${silent_secs}s without output)`, {
+				session_id,
+				foreground,
+			});
+			toastsStore.add(
+				"Terminal not responding",
+				`${foreground ?? "The process"} produced no output for ${silent_secs}s after input. Press Ctrl+C or restart the terminal.`,
+				"warn",
+			);
+		},
+	).catch((err) => appLogger.error("app", "Failed to register session-possibly-wedged listener", err));
+
+	// Remote access was turned off after auth failures from too many IPs (auto_disable_remote_after_failures)
+	listen<{ distinct_ips: number; window_secs: number }>("remote-auto-disabled", (event) => {
+		const { distinct_ips, window_secs } = event.payload;
+		appLogger.error("network", "Remote access auto-disabled after repeated auth failures", {
+			distinct_ips,
+			window_secs,
+		});
+		toastsStore.add(
+			"Remote access disabled",
+			`Failed logins from ${distinct_ips} addresses within ${Math.round(window_secs / 60)} min. Re-enable it in Settings → Services.`,
+			"error",
+			true,
+		);
+		// Refresh the cached config so the next settings save doesn't turn it back on
+		void settingsStore.hydrate();
+	}).catch((err) => appLogger.error("app", "Failed to register remote-auto-disabled listener", err));
+
+	// Config files found corrupt at startup were moved aside and reset to defaults
+	if (isTauri()) {
+		invoke<string[]>("take_config_recovery_notices")
+			.then((backups) => {
+				for (const backup of backups ?? []) {
+					toastsStore.add(
+						"Settings reset",
+						`A corrupt config file was backed up to ${backup} and defaults were loaded.`,
+						"warn",
+					);
+				}
+			})
+			.catch((err) => appLogger.error("app", "Failed to check config recovery notices", err));
+	}
+
 	// Listen for sessions created/closed by remote clients (browser UI or other Tauri windows)
 	listen<{ session_id: string; cwd: string | null; agent_type?: string | null }>("session-created", (event) => {
 		const { session_id, cwd, agent_type } = event.payload;
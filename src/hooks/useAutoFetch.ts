@@ -1,5 +1,6 @@
 import { invoke } from "../invoke";
 import { appLogger } from "../stores/appLogger";
+import { githubStore } from "../stores/github";
 import { repoSettingsStore } from "../stores/repoSettings";
 import { repositoriesStore } from "../stores/repositories";
 
@@ -43,6 +44,8 @@ async function fetchRepo(repoPath: string): Promise<void> {
 
 /** Master tick: check all repos and fetch those whose interval has elapsed */
 function tick(): void {
+	// Paused while the window is unfocused; due repos fetch on the first tick after refocus
+	if (!githubStore.isPollActive()) return;
 	const now = Date.now();
 	const repos = repositoriesStore.getOrderedRepos();
 
@@ -1,4 +1,5 @@
 import { createSignal } from "solid-js";
+import type { WorktreeRemovalReport } from "./useRepository";
 
 export interface ConfirmOptions {
 	title: string;
@@ -108,6 +109,33 @@ export function useConfirmDialog() {
 		});
 	}
 
+	/** Confirm force-removing a worktree whose uncommitted work would be lost.
+	 *  `report` is the dry-run result; null when it could not be fetched.
+	 */
+	async function confirmRemoveDirtyWorktree(
+		branchName: string,
+		report: WorktreeRemovalReport | null,
+		deleteBranch: boolean = true,
+	): Promise<boolean> {
+		const lines: string[] = [];
+		if (!report || report.has_uncommitted_changes) lines.push("uncommitted changes to tracked files");
+		if (report && report.untracked_files.length > 0) {
+			const shown = report.untracked_files.slice(0, 5).join(", ");
+			const more = report.untracked_files.length > 5 ? `, +${report.untracked_files.length - 5} more` : "";
+			lines.push(`${report.untracked_files.length} untracked file(s): ${shown}${more}`);
+		}
+		if (report && report.unpushed_commits > 0 && deleteBranch) {
+			lines.push(`${report.unpushed_commits} unpushed commit(s) on a branch that will be force-deleted`);
+		}
+		return await confirm({
+			title: "Worktree has unsaved work",
+			message: `"${branchName}" has:\n- ${lines.join("\n- ")}\n\nForce-removing it permanently deletes this work. Continue anyway?`,
+			okLabel: "Force Remove",
+			cancelLabel: "Cancel",
+			kind: "warning",
+		});
+	}
+
 	/** Confirm closing a terminal */
 	async function confirmCloseTerminal(terminalName: string): Promise<boolean> {
 		return await confirm({
@@ -171,6 +199,7 @@ export function useConfirmDialog() {
 		confirm,
 		confirmRemoveWorktree,
 		confirmRemoveLockedWorktree,
+		confirmRemoveDirtyWorktree,
 		confirmCloseTerminal,
 		confirmRemoveRepo,
 		confirmStashAndSwitch,
@@ -26,6 +26,7 @@ export interface DictationDeps {
 		refreshStatus: () => Promise<void>;
 		startRecording: () => Promise<void>;
 		stopRecording: () => Promise<TranscribeResponse | null>;
+		injectText: (text: string, sessionId?: string) => Promise<string | null>;
 	};
 	setStatusInfo: (msg: string) => void;
 	openSettings: (tab?: string) => void;
@@ -129,8 +130,20 @@ export function useDictation(deps: DictationDeps) {
 			(el.closest(".xterm") || el.closest("[data-terminal-container]"));
 
 		const autoSend = dictationStore.state.autoSend;
+		// "active-session" ignores whatever input had focus and always types into
+		// the active terminal session.
+		const toActiveSession = dictationStore.state.target === "active-session";
 
-		if (!isTerminalInput && el && (el instanceof HTMLTextAreaElement || el instanceof HTMLInputElement)) {
+		if (dictationStore.state.focusWindowOnInject) {
+			try {
+				const { getCurrentWindow } = await import("@tauri-apps/api/window");
+				await getCurrentWindow().setFocus();
+			} catch (err) {
+				appLogger.warn("dictation", "Failed to focus window before injecting", err);
+			}
+		}
+
+		if (!toActiveSession && !isTerminalInput && el && (el instanceof HTMLTextAreaElement || el instanceof HTMLInputElement)) {
 			const start = el.selectionStart ?? el.value.length;
 			const end = el.selectionEnd ?? start;
 			const before = el.value.slice(0, start);
@@ -141,7 +154,7 @@ export function useDictation(deps: DictationDeps) {
 			deps.setStatusInfo("Ready");
 			return;
 		}
-		if (el && el.getAttribute("contenteditable") === "true") {
+		if (!toActiveSession && el && el.getAttribute("contenteditable") === "true") {
 			document.execCommand("insertText", false, text);
 			deps.setStatusInfo("Ready");
 			return;
@@ -155,6 +168,11 @@ export function useDictation(deps: DictationDeps) {
 				if (autoSend) {
 					const shellFamily = await getShellFamily(targetSessionId);
 					await sendCommand(writeFn, text, active.agentType, shellFamily);
+				} else if (toActiveSession) {
+					// The backend applies corrections and writes to the session itself.
+					if ((await deps.dictation.injectText(text, targetSessionId)) === null) {
+						throw new Error("inject_text failed");
+					}
 				} else {
 					await writeFn(text);
 				}
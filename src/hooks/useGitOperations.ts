@@ -48,6 +48,10 @@ export interface GitOperationsDeps {
 			last_commit_ts: Record<string, number | null>;
 		}>;
 		removeWorktree: (repoPath: string, branchName: string, deleteBranch: boolean, force?: boolean) => Promise<void>;
+		previewWorktreeRemoval?: (
+			repoPath: string,
+			branchName: string,
+		) => Promise<import("./useRepository").WorktreeRemovalReport>;
 		createWorktree: (
 			baseRepo: string,
 			branchName: string,
@@ -56,7 +60,7 @@ export interface GitOperationsDeps {
 		) => Promise<{ status: "ok" | "pending"; name: string; path: string; branch: string; base_repo: string }>;
 		renameBranch: (repoPath: string, oldName: string, newName: string) => Promise<void>;
 		createBranch: (repoPath: string, name: string, startPoint: string | null, checkout: boolean) => Promise<void>;
-		generateWorktreeName: (existingNames: string[]) => Promise<string>;
+		generateWorktreeName: (existingNames: string[], repoPath?: string) => Promise<string>;
 		generateCloneBranchName: (sourceBranch: string, existingNames: string[]) => Promise<string>;
 		listBaseRefOptions: (repoPath: string) => Promise<import("./useRepository").BaseRefOption[]>;
 		mergeAndArchiveWorktree: (
@@ -92,6 +96,11 @@ export interface GitOperationsDeps {
 		confirmRemoveRepo: (repoName: string) => Promise<boolean>;
 		confirmRemoveWorktree: (branchName: string) => Promise<boolean>;
 		confirmRemoveLockedWorktree?: (branchName: string, deleteBranch?: boolean) => Promise<boolean>;
+		confirmRemoveDirtyWorktree?: (
+			branchName: string,
+			report: import("./useRepository").WorktreeRemovalReport | null,
+			deleteBranch?: boolean,
+		) => Promise<boolean>;
 		confirmStashAndSwitch?: (branchName: string) => Promise<boolean>;
 		confirmOrphanCleanup?: (paths: string[]) => Promise<boolean>;
 		/** Surface a git failure in a dialog with the full output; returns true if the user chose Retry. */
@@ -724,6 +733,11 @@ export function useGitOperations(deps: GitOperationsDeps) {
 
 			// Fire-and-forget: diff stats are cosmetic, don't block branch switch
 			const selectedBranch = repositoriesStore.get(repoPath)?.branches[branchName];
+			// The backend owns the authoritative selection; its head watcher uses it
+			// to keep a main-checkout HEAD move from re-activating over a worktree.
+			invoke("set_active_selection", { repoPath, worktreePath: selectedBranch?.worktreePath ?? null }).catch(
+				(err) => appLogger.debug("git", "Failed to record active selection", err),
+			);
 			if (selectedBranch?.worktreePath) {
 				const wtPath = selectedBranch.worktreePath;
 				deps.repo
@@ -1003,10 +1017,12 @@ export function useGitOperations(deps: GitOperationsDeps) {
 			deps.setStatusInfo(`Removed ${branchName}`);
 		} catch (err) {
 			const reason = err instanceof Error ? err.message : String(err);
-			if (reason.startsWith("worktree_locked:")) {
-				// Worktree is locked by a Claude agent — ask user to confirm force removal
+			const locked = reason.startsWith("worktree_locked:");
+			if (locked || reason.startsWith("worktree_dirty:")) {
+				// Worktree is locked by a Claude agent, or has uncommitted work the
+				// backend refused to destroy — ask user to confirm force removal
 				repositoriesStore.setBranch(repoPath, branchName, { isRemoving: false });
-				appLogger.warn("git", `handleRemoveBranch: worktree locked — showing confirmation dialog`, {
+				appLogger.warn("git", `handleRemoveBranch: worktree ${locked ? "locked" : "dirty"} — showing confirmation dialog`, {
 					branchName,
 					reason,
 				});
@@ -1016,9 +1032,14 @@ export function useGitOperations(deps: GitOperationsDeps) {
 				// lock is released even when the modal subsystem errors out.
 				let forceConfirmed = false;
 				try {
-					forceConfirmed = await (deps.dialogs.confirmRemoveLockedWorktree?.(branchName, deleteBranch) ?? false);
+					if (locked) {
+						forceConfirmed = await (deps.dialogs.confirmRemoveLockedWorktree?.(branchName, deleteBranch) ?? false);
+					} else {
+						const report = await (deps.repo.previewWorktreeRemoval?.(repoPath, branchName).catch(() => null) ?? null);
+						forceConfirmed = await (deps.dialogs.confirmRemoveDirtyWorktree?.(branchName, report, deleteBranch) ?? false);
+					}
 				} catch (dialogErr) {
-					appLogger.error("git", `handleRemoveBranch: confirmRemoveLockedWorktree threw`, {
+					appLogger.error("git", `handleRemoveBranch: force-remove confirmation threw`, {
 						branchName,
 						error: dialogErr instanceof Error ? dialogErr.message : String(dialogErr),
 					});
@@ -1027,7 +1048,7 @@ export function useGitOperations(deps: GitOperationsDeps) {
 					return;
 				}
 				if (!forceConfirmed) {
-					appLogger.info("git", `handleRemoveBranch: user cancelled force removal of locked worktree`, { branchName });
+					appLogger.info("git", `handleRemoveBranch: user cancelled force removal`, { branchName });
 					clearLock();
 					return;
 				}
@@ -1262,7 +1283,7 @@ export function useGitOperations(deps: GitOperationsDeps) {
 
 		// Fetch data for the dialog in parallel
 		const [suggestedName, localBranches, worktreesDir, baseRefs] = await Promise.all([
-			deps.repo.generateWorktreeName(worktreeBranches),
+			deps.repo.generateWorktreeName(worktreeBranches, repoPath),
 			deps.repo.listLocalBranches(repoPath),
 			deps.pty.getWorktreesDir(repoPath),
 			deps.repo.listBaseRefOptions(repoPath),
@@ -1680,7 +1701,7 @@ export function useGitOperations(deps: GitOperationsDeps) {
 	const generateWorktreeName = async (): Promise<string> => {
 		const state = worktreeDialogState();
 		const worktreeBranches = state?.worktreeBranches ?? [];
-		return deps.repo.generateWorktreeName(worktreeBranches);
+		return deps.repo.generateWorktreeName(worktreeBranches, state?.repoPath);
 	};
 
 	const handleRepoSettings = (
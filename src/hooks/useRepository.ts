@@ -32,6 +32,8 @@ export interface ChangedFile {
 	status: string; // "M" | "A" | "D" | "R" | "?"
 	additions: number;
 	deletions: number;
+	/** Source path of a rename/copy (ref-comparison mode) */
+	old_path?: string;
 }
 
 /** A base ref option with metadata for grouped dropdown display */
@@ -43,6 +45,24 @@ export interface BaseRefOption {
 	is_default: boolean;
 }
 
+/** What removing a worktree would lose (remove_worktree dry run) */
+export interface WorktreeRemovalReport {
+	has_uncommitted_changes: boolean;
+	/** Commits missing from the branch's upstream (or the default branch) */
+	unpushed_commits: number;
+	untracked_files: string[];
+	would_remove_path: string;
+}
+
+/** Result of prune_orphaned_worktrees (paths that were, or with dry_run would be, removed) */
+export interface OrphanPruneReport {
+	dry_run: boolean;
+	removed: string[];
+	/** Worktree entries whose directory was already gone */
+	stale: string[];
+	kept: { path: string; reason: string }[];
+}
+
 /** Repository hook for git operations */
 export function useRepository() {
 	/** Get repository info */
@@ -95,6 +115,11 @@ export function useRepository() {
 		await invoke("remove_worktree", { repoPath, branchName, deleteBranch, force: force ?? false });
 	}
 
+	/** Report what removing a worktree would lose, without removing it */
+	async function previewWorktreeRemoval(repoPath: string, branchName: string): Promise<WorktreeRemovalReport> {
+		return await invoke<WorktreeRemovalReport>("remove_worktree", { repoPath, branchName, dryRun: true });
+	}
+
 	/** Create a new worktree with a branch */
 	async function createWorktree(
 		baseRepo: string,
@@ -177,9 +202,10 @@ export function useRepository() {
 		}
 	}
 
-	/** Generate a unique worktree branch name, avoiding collisions with existing names */
-	async function generateWorktreeName(existingNames: string[]): Promise<string> {
-		return await invoke<string>("generate_worktree_name_cmd", { existingNames });
+	/** Generate a unique worktree branch name, avoiding collisions with existing names
+	 *  and, given `repoPath`, the repo's local/remote branches and worktree directories */
+	async function generateWorktreeName(existingNames: string[], repoPath?: string): Promise<string> {
+		return await invoke<string>("generate_worktree_name_cmd", { existingNames, repoPath });
 	}
 
 	/** Generate a hybrid clone branch name: `{sanitized_source}--{random_name}` */
@@ -237,6 +263,11 @@ export function useRepository() {
 		hash: string;
 		short_hash: string;
 		subject: string;
+		author_name: string;
+		author_email: string;
+		author_initials: string;
+		/** Stable CSS color (hsl) derived from the author email */
+		author_color: string;
 	}
 
 	/** Get branches fully merged into the repo's main branch */
@@ -339,6 +370,11 @@ export function useRepository() {
 		await invoke("remove_orphan_worktree", { repoPath, worktreePath });
 	}
 
+	/** Remove every orphaned worktree at once; dirty or locked ones are kept with a reason. */
+	async function pruneOrphanedWorktrees(repoPath: string, dryRun = false): Promise<OrphanPruneReport> {
+		return await invoke<OrphanPruneReport>("prune_orphaned_worktrees", { repoPath, dryRun });
+	}
+
 	/** Merge a PR via GitHub REST API. merge_method: "merge" | "squash" | "rebase" */
 	async function mergePrViaGithub(repoPath: string, prNumber: number, mergeMethod: string): Promise<string> {
 		return await invoke<string>("merge_pr_via_github", { repoPath, prNumber, mergeMethod });
@@ -381,6 +417,7 @@ export function useRepository() {
 		renameBranch,
 		createBranch,
 		removeWorktree,
+		previewWorktreeRemoval,
 		createWorktree,
 		getWorktreePaths,
 		getChangedFiles,
@@ -399,6 +436,7 @@ export function useRepository() {
 		checkoutRemoteBranch,
 		detectOrphanWorktrees,
 		removeOrphanWorktree,
+		pruneOrphanedWorktrees,
 		mergePrViaGithub,
 		listLocalBranches,
 		switchBranch,
@@ -213,6 +213,7 @@ export function useSmartPrompts() {
 		// escape their argument inside `sh -c` / `cmd /C`.
 		const processed = await promptLibraryStore.processContent(prompt, allVars, {
 			shellSafe: effectiveMode === "shell",
+			send: true,
 		});
 
 		if (effectiveMode === "shell") {
@@ -5,12 +5,22 @@ import { isTauri } from "./transport";
 /** Notification sound types */
 export type NotificationSound = "question" | "error" | "completion" | "warning" | "info";
 
+/** Daily quiet-hours window, local "HH:MM" times; start > end wraps past midnight */
+export interface QuietHours {
+	start: string;
+	end: string;
+}
+
 /** Notification configuration */
 export interface NotificationConfig {
 	enabled: boolean;
 	volume: number; // 0.0 to 1.0
 	sounds: Record<NotificationSound, boolean>;
 	audio_device: string | null;
+	/** Minimum seconds between same-type notifications per session (0 = off) */
+	min_interval_secs: number;
+	/** Window during which only question/error notifications fire */
+	quiet_hours: QuietHours | null;
 }
 
 /** Default notification configuration */
@@ -25,6 +35,8 @@ export const DEFAULT_NOTIFICATION_CONFIG: NotificationConfig = {
 		info: true,
 	},
 	audio_device: null,
+	min_interval_secs: 0,
+	quiet_hours: null,
 };
 
 /** Notification manager — delegates audio playback to Rust via Tauri IPC.
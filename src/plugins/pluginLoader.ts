@@ -47,6 +47,8 @@ export interface PluginManifest {
 	author?: string;
 	capabilities: string[];
 	allowedUrls?: string[];
+	/** Hostnames httpFetch may reach, including redirects (`*.example.com` = subdomains). */
+	allowedHosts?: string[];
 	/** Agent types this plugin targets (e.g. ["claude"]). Empty/omitted = universal. */
 	agentTypes?: string[];
 	/** CLI binaries this plugin may execute via exec:cli (e.g. ["rtk", "mdkb"]). */
@@ -243,7 +245,13 @@ async function loadPlugin(manifest: PluginManifest): Promise<void> {
 	}
 
 	const plugin = (mod as { default: TuiPlugin }).default;
-	pluginRegistry.register(plugin, manifest.capabilities, manifest.allowedUrls, manifest.agentTypes);
+	pluginRegistry.register(
+		plugin,
+		manifest.capabilities,
+		manifest.allowedUrls,
+		manifest.agentTypes,
+		manifest.allowedHosts,
+	);
 	loadedPluginIds.add(manifest.id);
 
 	// Rust-side registration is already handled by pluginRegistry.register()
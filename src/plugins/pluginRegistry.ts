@@ -34,6 +34,7 @@ import type {
 	OutputWatcher,
 	PanelHandle,
 	PluginCapability,
+	PluginEventType,
 	PluginHost,
 	PrNotificationSnapshot,
 	RepoListEntry,
@@ -142,6 +143,7 @@ function createPluginRegistry() {
 		disposables: Disposable[],
 		capabilities: ReadonlySet<string> | null = null,
 		allowedUrls: readonly string[] = [],
+		allowedHosts: readonly string[] = [],
 	): PluginHost {
 		function track(d: Disposable): Disposable {
 			// Wrap in idempotent guard: plugins may manually dispose() and then the
@@ -633,6 +635,11 @@ function createPluginRegistry() {
 					body: options?.body ?? null,
 					allowedUrls: [...allowedUrls],
 					pluginId,
+					limits: {
+						timeoutMs: options?.timeoutMs ?? null,
+						maxResponseBytes: options?.maxResponseBytes ?? null,
+						allowedHosts: [...allowedHosts],
+					},
 				});
 			},
 
@@ -648,6 +655,20 @@ function createPluginRegistry() {
 				});
 			},
 
+			async emitEvent(
+				eventType: PluginEventType,
+				payload: Record<string, unknown>,
+				sessionId?: string,
+			): Promise<void> {
+				requireCapability(pluginId, capabilities, "ui:events");
+				await invoke("plugin_emit_event", {
+					pluginId,
+					eventType,
+					payload,
+					sessionId: sessionId ?? terminalsStore.getActive()?.sessionId ?? null,
+				});
+			},
+
 			// -- Tier 4: Scoped Tauri invoke --
 
 			async invoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
@@ -679,12 +700,15 @@ function createPluginRegistry() {
 	 *   Pass null or omit for built-in plugins (unrestricted access).
 	 * @param agentTypes - Optional list of agent types this plugin targets.
 	 *   Empty or omit for universal plugins.
+	 * @param allowedHosts - Optional hostnames httpFetch may reach (`*.domain` for subdomains).
+	 *   Empty or omit to rely on allowedUrls alone.
 	 */
 	async function register(
 		plugin: TuiPlugin,
 		capabilities?: string[],
 		allowedUrls?: string[],
 		agentTypes?: string[],
+		allowedHosts?: string[],
 	): Promise<void> {
 		// Replace existing registration for same id
 		if (plugins.has(plugin.id)) {
@@ -693,7 +717,7 @@ function createPluginRegistry() {
 
 		const disposables: Disposable[] = [];
 		const capSet = capabilities ? new Set(capabilities) : null;
-		const host = buildHost(plugin.id, disposables, capSet, allowedUrls ?? []);
+		const host = buildHost(plugin.id, disposables, capSet, allowedUrls ?? [], allowedHosts ?? []);
 
 		const pluginLogger = pluginStore.getLogger(plugin.id);
 
@@ -312,7 +312,11 @@ export type PluginCapability =
 	| "ui:context-menu"
 	| "ui:sidebar"
 	| "ui:file-icons"
-	| "ui:file-preview";
+	| "ui:file-preview"
+	| "ui:events";
+
+/** Parsed-event types a plugin may emit via emitEvent — mirrors PLUGIN_EVENT_TYPES in plugins.rs */
+export type PluginEventType = "status-line" | "progress" | "intent";
 
 /** Valid sound names for playNotificationSound — single source of truth */
 export const NOTIFICATION_SOUNDS = ["question", "error", "completion", "warning", "info"] as const;
@@ -352,6 +356,10 @@ export interface HttpFetchOptions {
 	headers?: Record<string, string>;
 	/** Request body */
 	body?: string;
+	/** Total request timeout in ms (default: 30000, max: 300000) */
+	timeoutMs?: number;
+	/** Response body size cap in bytes (default: 10 MB, max: 50 MB) */
+	maxResponseBytes?: number;
 }
 
 // ---------------------------------------------------------------------------
@@ -708,8 +716,11 @@ export interface PluginHost {
 
 	/**
 	 * Make an HTTP request. Requires "net:http" capability.
-	 * External plugins can only fetch URLs matching their manifest's allowedUrls patterns.
-	 * Non-2xx status codes are returned normally (not thrown as errors).
+	 * External plugins can only fetch URLs matching their manifest's allowedUrls patterns
+	 * (and allowedHosts, when declared — redirects included).
+	 * Non-2xx status codes are returned normally (not thrown as errors). Failures reject
+	 * with `{ kind, message }`, where kind is "rejected" | "timeout" | "size_exceeded" |
+	 * "connection" | "request".
 	 */
 	httpFetch(url: string, options?: HttpFetchOptions): Promise<HttpResponse>;
 
@@ -725,6 +736,18 @@ export interface PluginHost {
 	 */
 	execCli(binary: string, args: string[], cwd?: string): Promise<string>;
 
+	// -- Tier 3k: Structured events (capability-gated) --
+
+	/**
+	 * Emit a parsed event ("status-line", "progress", "intent") as if the output
+	 * parser had produced it, tagged with this plugin's id.
+	 * Requires "ui:events" capability.
+	 * @param eventType - One of the allowed event types
+	 * @param payload - Variant fields, e.g. { state: 1, value: 40 } for "progress"
+	 * @param sessionId - Target session; defaults to the active terminal
+	 */
+	emitEvent(eventType: PluginEventType, payload: Record<string, unknown>, sessionId?: string): Promise<void>;
+
 	// -- Tier 4: Scoped Tauri invoke (whitelisted commands only) --
 
 	/** Invoke a whitelisted Tauri command. See INVOKE_WHITELIST for allowed commands. */
@@ -12,8 +12,14 @@ interface DictationConfig {
 	device: string | null;
 	long_press_ms: number;
 	auto_send: boolean;
+	dictation_target: DictationTarget;
+	focus_window_on_inject: boolean;
+	streaming: boolean;
 }
 
+/** Where transcribed text goes: OS keyboard focus or the active terminal session. */
+export type DictationTarget = "os-focus" | "active-session";
+
 /** GPU/CPU backend reported by whisper after model load. */
 export type DictationBackend = "cpu" | "gpu";
 
@@ -94,6 +100,9 @@ interface DictationStoreState {
 	devices: AudioDevice[];
 	longPressMs: number;
 	autoSend: boolean;
+	target: DictationTarget;
+	focusWindowOnInject: boolean;
+	streaming: boolean;
 	capturingHotkey: boolean;
 	partialText: string;
 	backendInfo: DictationBackend | null;
@@ -119,6 +128,9 @@ function createDictationStore() {
 		devices: [],
 		longPressMs: 400,
 		autoSend: false,
+		target: "os-focus",
+		focusWindowOnInject: false,
+		streaming: false,
 		capturingHotkey: false,
 		partialText: "",
 		backendInfo: null,
@@ -153,6 +165,9 @@ function createDictationStore() {
 					selectedDevice: config.device ?? null,
 					longPressMs: config.long_press_ms ?? 400,
 					autoSend: config.auto_send ?? false,
+					target: config.dictation_target ?? "os-focus",
+					focusWindowOnInject: config.focus_window_on_inject ?? false,
+					streaming: config.streaming ?? false,
 				});
 			} catch (err) {
 				appLogger.error("dictation", "Failed to get dictation config", err);
@@ -169,6 +184,9 @@ function createDictationStore() {
 				device: partial.device !== undefined ? partial.device : state.selectedDevice,
 				long_press_ms: partial.long_press_ms ?? state.longPressMs,
 				auto_send: partial.auto_send ?? state.autoSend,
+				dictation_target: partial.dictation_target ?? state.target,
+				focus_window_on_inject: partial.focus_window_on_inject ?? state.focusWindowOnInject,
+				streaming: partial.streaming ?? state.streaming,
 			};
 			try {
 				await invoke("set_dictation_config", { config });
@@ -181,6 +199,10 @@ function createDictationStore() {
 				if (partial.device !== undefined) storeUpdate.selectedDevice = partial.device;
 				if (partial.long_press_ms !== undefined) storeUpdate.longPressMs = partial.long_press_ms;
 				if (partial.auto_send !== undefined) storeUpdate.autoSend = partial.auto_send;
+				if (partial.dictation_target !== undefined) storeUpdate.target = partial.dictation_target;
+				if (partial.focus_window_on_inject !== undefined)
+					storeUpdate.focusWindowOnInject = partial.focus_window_on_inject;
+				if (partial.streaming !== undefined) storeUpdate.streaming = partial.streaming;
 				setState(storeUpdate);
 			} catch (err) {
 				appLogger.error("dictation", "Failed to save dictation config", err);
@@ -207,6 +229,18 @@ function createDictationStore() {
 			actions.saveConfig({ auto_send: value });
 		},
 
+		setTarget(value: DictationTarget): void {
+			actions.saveConfig({ dictation_target: value });
+		},
+
+		setFocusWindowOnInject(value: boolean): void {
+			actions.saveConfig({ focus_window_on_inject: value });
+		},
+
+		setStreaming(value: boolean): void {
+			actions.saveConfig({ streaming: value });
+		},
+
 		setLanguage(value: string): void {
 			actions.saveConfig({ language: value });
 		},
@@ -347,10 +381,11 @@ function createDictationStore() {
 			}
 		},
 
-		/** Inject text (apply corrections) without recording */
-		async injectText(text: string): Promise<string | null> {
+		/** Inject text (apply corrections) without recording.
+		 *  With the "active-session" target the backend also writes it to `sessionId`. */
+		async injectText(text: string, sessionId?: string): Promise<string | null> {
 			try {
-				return await invoke<string>("inject_text", { text });
+				return await invoke<string>("inject_text", sessionId ? { text, sessionId } : { text });
 			} catch (err) {
 				appLogger.error("dictation", "Failed to inject text", err);
 				return null;
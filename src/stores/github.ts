@@ -23,6 +23,13 @@ interface RepoGitHubData {
 	issuesLastPolled: number;
 }
 
+/** CI check detail as returned by get_ci_checks / hydrate_pr_check_details */
+interface RawCheckDetail {
+	name: string;
+	status: string;
+	conclusion: string;
+}
+
 /** GitHub store state */
 interface GitHubStoreState {
 	repos: Record<string, RepoGitHubData>;
@@ -196,52 +203,78 @@ function createGitHubStore() {
 		);
 	}
 
+	/** Write fetched CI check details for one branch into the store and
+	 *  recompute its CheckSummary so the badge/ring stay in sync. */
+	function applyCheckDetails(repoPath: string, branch: string, rawChecks: RawCheckDetail[]): void {
+		const details: CheckDetail[] = rawChecks.map((c) => ({
+			context: c.name,
+			state: c.conclusion || c.status,
+		}));
+
+		let passed = 0,
+			failed = 0,
+			pending = 0;
+		for (const c of rawChecks) {
+			switch (c.conclusion) {
+				case "success":
+				case "neutral":
+				case "skipped":
+					passed++;
+					break;
+				case "failure":
+				case "cancelled":
+				case "timed_out":
+				case "action_required":
+				case "stale":
+					failed++;
+					break;
+				default:
+					pending++;
+					break;
+			}
+		}
+
+		setState("repos", repoPath, "branches", branch, "check_details", details);
+		setState("repos", repoPath, "branches", branch, "checks", {
+			passed,
+			failed,
+			pending,
+			total: passed + failed + pending,
+		});
+	}
+
 	/** Lazy-load CI check details for a PR and populate the store.
-	 *  Called when PrDetailPopover opens to avoid fetching check details on every poll.
-	 *  Also recomputes CheckSummary from the fresh data so the badge/ring stay in sync. */
+	 *  Called when PrDetailPopover opens to avoid fetching check details on every poll. */
 	async function loadCheckDetails(repoPath: string, branch: string, prNumber: number): Promise<void> {
 		try {
-			const rawChecks = await invoke<{ name: string; status: string; conclusion: string }[]>("get_ci_checks", {
+			const rawChecks = await invoke<RawCheckDetail[]>("get_ci_checks", {
 				path: repoPath,
 				prNumber,
 			});
-			const details: CheckDetail[] = rawChecks.map((c) => ({
-				context: c.name,
-				state: c.conclusion || c.status,
-			}));
-
-			let passed = 0,
-				failed = 0,
-				pending = 0;
-			for (const c of rawChecks) {
-				switch (c.conclusion) {
-					case "success":
-					case "neutral":
-					case "skipped":
-						passed++;
-						break;
-					case "failure":
-					case "cancelled":
-					case "timed_out":
-					case "action_required":
-					case "stale":
-						failed++;
-						break;
-					default:
-						pending++;
-						break;
-				}
-			}
+			applyCheckDetails(repoPath, branch, rawChecks);
+		} catch (err) {
+			appLogger.debug("github", `Failed to load check details for ${repoPath}:${branch}`, err);
+		}
+	}
 
-			setState("repos", repoPath, "branches", branch, "check_details", details);
-			setState("repos", repoPath, "branches", branch, "checks", {
-				passed,
-				failed,
-				pending,
-				total: passed + failed + pending,
+	/** Lazy-load CI check details for several PRs of one repo in a single
+	 *  batched request (one GraphQL call instead of one per PR). */
+	async function loadCheckDetailsBatch(
+		repoPath: string,
+		prs: { branch: string; prNumber: number }[],
+	): Promise<void> {
+		if (prs.length === 0) return;
+		try {
+			const byNumber = await invoke<Record<string, RawCheckDetail[]>>("hydrate_pr_check_details", {
+				path: repoPath,
+				prNumbers: prs.map((pr) => pr.prNumber),
 			});
+			for (const pr of prs) {
+				const rawChecks = byNumber[String(pr.prNumber)];
+				if (rawChecks) applyCheckDetails(repoPath, pr.branch, rawChecks);
+			}
 		} catch (err) {
-			appLogger.debug("github", `Failed to load check details for ${repoPath}:${branch}`, err);
+			appLogger.debug("github", `Failed to batch-load check details for ${repoPath}`, err);
 		}
 	}
 
@@ -252,6 +285,20 @@ function createGitHubStore() {
 		);
 	}
 
+	/** Whether background polling is active (window focused, or pausing disabled) */
+	let pollActive = true;
+
+	/** Forward window focus to the Rust poll gate; paused polls are skipped until refocus */
+	function setPollActive(active: boolean): void {
+		invoke<boolean>("set_poll_active", { active })
+			.then((effective) => {
+				pollActive = effective ?? active;
+			})
+			.catch((err) => appLogger.debug("github", "Failed to set poll activity", err));
+	}
+	const onWindowFocus = (): void => setPollActive(true);
+	const onWindowBlur = (): void => setPollActive(false);
+
 	/** Handle transition events from Rust poller. `type` is the raw poller tag — a
 	 *  superset of the renderable notification types (it also carries watcher-only
 	 *  `pushed`/`opened`), so gate the notification add behind `isNotificationType`. */
@@ -319,6 +366,8 @@ function createGitHubStore() {
 		}).then((unsub) => unlisteners.push(unsub));
 
 		document.addEventListener("visibilitychange", onVisibilityChange);
+		window.addEventListener("focus", onWindowFocus);
+		window.addEventListener("blur", onWindowBlur);
 	}
 
 	/** Stop Rust poller and tear down event listeners */
@@ -327,6 +376,8 @@ function createGitHubStore() {
 		for (const unsub of unlisteners) unsub();
 		unlisteners.length = 0;
 		document.removeEventListener("visibilitychange", onVisibilityChange);
+		window.removeEventListener("focus", onWindowFocus);
+		window.removeEventListener("blur", onWindowBlur);
 	}
 
 	/** Directly set remote status for a repo (used by simulator) */
@@ -363,9 +414,15 @@ function createGitHubStore() {
 			);
 		},
 		loadCheckDetails,
+		loadCheckDetailsBatch,
 		pollRepo,
 		startPolling,
 		stopPolling,
+
+		/** False while background polling is paused because the window is unfocused */
+		isPollActive(): boolean {
+			return pollActive;
+		},
 		/** Register a callback for PR terminal state transitions (merged/closed) */
 		setOnPrTerminal(
 			cb: ((repoPath: string, branch: string, prNumber: number, type: "merged" | "closed") => void) | null,
@@ -8,6 +8,14 @@ interface KeybindingOverride {
 	key: string | null;
 }
 
+/** Result of import_keybindings */
+export interface KeybindingsImportReport {
+	imported: number;
+	/** Actions in the profile this install doesn't know */
+	dropped: string[];
+	ui_prefs_imported: boolean;
+}
+
 /**
  * Dynamic action registered by a plugin. Coexists with static ACTION_NAMES.
  * Plugins register these via host.registerCommand(); they participate in
@@ -230,6 +238,22 @@ export function createKeybindingsStore() {
 			}
 		},
 
+		/** Export overrides (and optionally layout prefs) as a portable, versioned JSON profile */
+		async exportProfile(includeUiPrefs = false): Promise<string> {
+			return await invoke<string>("export_keybindings", { includeUiPrefs });
+		},
+
+		/**
+		 * Merge a profile from exportProfile into the stored overrides and reload.
+		 * Throws on an invalid or unsupported-version profile.
+		 */
+		async importProfile(json: string): Promise<KeybindingsImportReport> {
+			const knownActions = [...ACTION_NAMES, ...dynamicActions.keys()];
+			const report = await invoke<KeybindingsImportReport>("import_keybindings", { json, knownActions });
+			await this.hydrate();
+			return report;
+		},
+
 		/** Check if an action has a user override (differs from default) */
 		isOverridden(action: string): boolean {
 			// Read version for reactivity
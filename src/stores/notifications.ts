@@ -4,6 +4,7 @@ import {
 	DEFAULT_NOTIFICATION_CONFIG,
 	type NotificationConfig,
 	type NotificationSound,
+	type QuietHours,
 	notificationManager,
 } from "../notifications";
 import { isTauri } from "../transport";
@@ -62,6 +63,7 @@ function copyDefaults(): NotificationConfig {
 		...DEFAULT_NOTIFICATION_CONFIG,
 		sounds: { ...DEFAULT_NOTIFICATION_CONFIG.sounds },
 		audio_device: DEFAULT_NOTIFICATION_CONFIG.audio_device,
+		quiet_hours: null,
 	};
 }
 
@@ -137,6 +139,21 @@ function createNotificationsStore() {
 			saveConfig(state.config);
 		},
 
+		/** Set the per-session, per-type notification throttle (seconds, 0 = off) */
+		setMinInterval(secs: number): void {
+			const clamped = Math.max(0, Math.round(secs));
+			setState("config", "min_interval_secs", clamped);
+			notificationManager.updateConfig({ min_interval_secs: clamped });
+			saveConfig(state.config);
+		},
+
+		/** Set quiet hours (null = off); only questions and errors notify inside the window */
+		setQuietHours(quiet: QuietHours | null): void {
+			setState("config", "quiet_hours", quiet);
+			notificationManager.updateConfig({ quiet_hours: quiet });
+			saveConfig(state.config);
+		},
+
 		/** Enable/disable a specific sound */
 		setSoundEnabled(sound: NotificationSound, enabled: boolean): void {
 			setState("config", "sounds", sound, enabled);
@@ -153,6 +170,18 @@ function createNotificationsStore() {
 					.map((l) => l.trim())
 					.join(" <- ") ?? "unknown";
 			appLogger.debug("app", `[Notification.Play] sound=${sound} focused=${document.hasFocus()} caller=${caller}`);
+			// Quiet hours and the per-(session, type) throttle are applied in Rust.
+			const allowed = await invoke<boolean>("notification_allowed", {
+				sound,
+				sessionId: opts?.terminalId ?? null,
+			}).catch((err) => {
+				appLogger.debug("app", "[Notification.Play] throttle check failed", err);
+				return true;
+			});
+			if (!allowed) {
+				appLogger.debug("app", `[Notification.Play] throttled sound=${sound} terminal=${opts?.terminalId ?? "-"}`);
+				return;
+			}
 			await notificationManager.play(sound);
 			if (!document.hasFocus()) {
 				actions.incrementBadge();
@@ -38,6 +38,8 @@ export interface SavedPrompt {
 	variables?: PromptVariable[];
 	isFavorite: boolean;
 	lastUsed?: number;
+	/** Real sends counted by the backend (previews excluded); read-only here */
+	useCount?: number;
 	createdAt: number;
 	updatedAt: number;
 	tags?: string[];
@@ -124,9 +126,9 @@ function createPromptLibraryStore() {
 					}
 				}
 
-				const loaded = await invoke<{ prompts?: Array<{ id: string; label: string; text: string; pinned: boolean }> }>(
-					"load_prompt_library",
-				);
+				const loaded = await invoke<{
+					prompts?: Array<{ id: string; label: string; text: string; pinned: boolean; use_count?: number }>;
+				}>("load_prompt_library");
 				let migrated = false;
 				if (loaded?.prompts && loaded.prompts.length > 0) {
 					const restored: Record<string, SavedPrompt> = {};
@@ -174,6 +176,7 @@ function createPromptLibraryStore() {
 								) as SmartPlacement[];
 								migrated = true;
 							}
+							full.useCount = entry.use_count ?? 0;
 							restored[full.id] = full;
 						} catch (err) {
 							appLogger.warn("store", `Prompt "${entry.id}" has non-JSON text field, using simple format`, err);
@@ -183,6 +186,7 @@ function createPromptLibraryStore() {
 								content: entry.text,
 								category: "custom",
 								isFavorite: entry.pinned,
+								useCount: entry.use_count ?? 0,
 								createdAt: Date.now(),
 								updatedAt: Date.now(),
 							};
@@ -414,14 +418,22 @@ function createPromptLibraryStore() {
 		 * Pass `shellSafe: true` when the result will be executed via `sh -c` /
 		 * `cmd /C` so that repo-controlled variables (branch names, PR titles,
 		 * commit messages…) are shell-quoted and cannot escape the argument to
-		 * run arbitrary commands. */
+		 * run arbitrary commands.
+		 *
+		 * Pass `send: true` when the result is actually being sent so the backend
+		 * counts the use; previews leave the usage stats alone. */
 		async processContent(
 			prompt: SavedPrompt,
 			variables: Record<string, string>,
-			opts?: { shellSafe?: boolean },
+			opts?: { shellSafe?: boolean; send?: boolean },
 		): Promise<string> {
 			const cmd = opts?.shellSafe ? "process_prompt_content_shell_safe" : "process_prompt_content";
-			return invoke<string>(cmd, { content: prompt.content, variables });
+			const send = opts?.send ?? false;
+			const result = await invoke<string>(cmd, { content: prompt.content, variables, promptId: prompt.id, send });
+			if (send && state.prompts[prompt.id]) {
+				setState("prompts", prompt.id, "useCount", (n) => (n ?? 0) + 1);
+			}
+			return result;
 		},
 
 		/** Get variables from prompt content (Rust backend) */
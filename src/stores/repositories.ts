@@ -53,6 +53,7 @@ export interface RepositoryState {
 	expanded: boolean; // Whether branches are expanded/collapsed
 	collapsed: boolean; // Whether entire repo is collapsed to icon only
 	parked: boolean; // Whether repo is hidden from sidebar (recallable via popover)
+	pinned?: boolean; // Pinned repos sort first; ordering is normalized by the backend
 	branches: Record<string, BranchState>;
 	activeBranch: string | null;
 	/** Which remote connection this repo belongs to (undefined = local) */
@@ -608,6 +609,29 @@ function createRepositoriesStore() {
 			return Object.keys(state.repositories).filter((p) => !state.repositories[p]?.parked);
 		},
 
+		/** Reload the saved order after the backend reordered repositories
+		 *  (`repositories-changed`). Only ordering fields are applied, so live
+		 *  terminals and branch state are kept. */
+		async reloadOrder(): Promise<void> {
+			if (!hydrated) return;
+			try {
+				const loaded = await invoke<{
+					repoOrder?: string[];
+					groups?: Record<string, RepoGroup>;
+				}>("load_repositories");
+				const savedOrder = (loaded?.repoOrder ?? []).filter((p) => p in state.repositories);
+				const missing = state.repoOrder.filter((p) => !savedOrder.includes(p));
+				setState("repoOrder", [...savedOrder, ...missing]);
+				for (const [id, group] of Object.entries(loaded?.groups ?? {})) {
+					if (state.groups[id]) {
+						setState("groups", id, "repoOrder", group.repoOrder.filter((p) => p in state.repositories));
+					}
+				}
+			} catch (err) {
+				appLogger.error("store", "Failed to reload repository order", err);
+			}
+		},
+
 		/** Reorder repositories in the sidebar */
 		reorderRepo(fromIndex: number, toIndex: number): void {
 			setState("repoOrder", (order) => {
@@ -74,6 +74,43 @@ interface RustAppConfig {
 	standby_timeout_minutes?: number;
 	custom_launchers?: CustomLauncher[];
 	inline_blame_enabled?: boolean;
+	agent_transcript_dir?: string | null;
+	agent_transcript_max_mb?: number;
+	agent_transcript_redact?: boolean;
+	/** Parsed output event types to emit (e.g. "question", "status-line"); null/absent = all */
+	enabled_output_events?: string[] | null;
+	/** Quiet period (ms) before a burst of PTY resizes applies the last size; 0 = no debounce */
+	resize_debounce_ms?: number;
+	/** Skip background GitHub/git polling while the window is unfocused (default true) */
+	pause_polling_when_unfocused?: boolean;
+	/** Extra PTY spawn attempts on transient failures (fd exhaustion, EAGAIN); default 2 */
+	pty_spawn_retries?: number;
+	/** Seconds of silence after input (same foreground process) before a session is flagged as wedged; 0 = disabled */
+	pty_wedge_timeout_secs?: number;
+	/** Extra rate-limit / API-error regexes checked after the built-ins */
+	custom_output_patterns?: Array<{
+		name: string;
+		regex: string;
+		kind: "rate_limit" | "api_error" | "task_complete" | "token_usage";
+		error_kind?: string | null;
+		retry_after_ms?: number | null;
+	}>;
+	/** Stderr log format, read at startup; TUIC_LOG_FORMAT overrides */
+	log_format?: "text" | "json";
+	/** Log level filter read at startup ("debug", "warn", or tracing directives); RUST_LOG overrides */
+	log_level?: string | null;
+	/** Size cap for session transcript exports in bytes (oldest dropped); 0 = no cap */
+	max_export_bytes?: number;
+	/** Redact secrets in session transcript exports (default true) */
+	redact_secrets?: boolean;
+	/** Built-in output detectors to skip: "question", "rate-limit", "plan-file", "intent" */
+	disabled_parsers?: string[];
+	/** Cap on concurrent PTY sessions (default 50, clamped to 1–1000) */
+	max_concurrent_sessions?: number;
+	/** Reconstruct typed lines and emit user-input events (default true) */
+	reconstruct_user_input?: boolean;
+	/** Allow the MCP fs tool to write files and create directories (default false) */
+	mcp_allow_file_writes?: boolean;
 }
 
 // Default values
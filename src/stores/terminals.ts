@@ -62,6 +62,7 @@ export interface TerminalData {
 	agentIntent: string | null; // LLM-declared intent via intent: token
 	currentTask: string | null; // Current agent task from status-line parsing (e.g. "Reading files")
 	activeSubTasks: number; // Count of running sub-agents/background tasks from ›› status line
+	planMode: boolean; // Agent is in plan mode (planning, not executing) — from plan-mode banners
 	isRemote: boolean; // Created via HTTP/MCP (not locally by the UI)
 	agentSessionId: string | null; // Agent session ID for session-specific resume (claude, gemini, codex)
 	tuicSession: string | null; // Stable tab UUID — injected as TUIC_SESSION env var, persists across restarts
@@ -96,6 +97,7 @@ type TerminalCreateData = Omit<
 	| "agentIntent"
 	| "currentTask"
 	| "activeSubTasks"
+	| "planMode"
 	| "isRemote"
 	| "agentSessionId"
 	| "tuicSession"
@@ -210,6 +212,7 @@ function createTerminalsStore() {
 	const idleToBusyCallbacks: Array<(id: string) => void> = [];
 	const onRemoveCallbacks: Array<(id: string) => void> = [];
 	const shellExitCallbacks: Array<(id: string) => void> = [];
+	const taskCompleteCallbacks: Array<(id: string, summary: string) => void> = [];
 	// Tracks which terminals have completed their initial shell startup (reached idle at least once).
 	// Used to distinguish "busy from .zshrc startup" from "busy from a user-launched process".
 	const reachedIdleSet = new Set<string>();
@@ -393,6 +396,7 @@ function createTerminalsStore() {
 				agentIntent: null,
 				currentTask: null,
 				activeSubTasks: 0,
+				planMode: false,
 				isRemote: false,
 				agentSessionId: null,
 				tuicSession: null,
@@ -432,6 +436,7 @@ function createTerminalsStore() {
 				agentIntent: null,
 				currentTask: null,
 				activeSubTasks: 0,
+				planMode: false,
 				isRemote: false,
 				agentSessionId: null,
 				tuicSession: null,
@@ -870,6 +875,22 @@ function createTerminalsStore() {
 			for (const cb of shellExitCallbacks) cb(id);
 		},
 
+		/** Register a callback fired when an agent reports its turn finished
+		 *  (backend `task-complete` parsed event). App routes it through the
+		 *  same completion notification path as busy→idle. */
+		onTaskComplete(callback: (id: string, summary: string) => void): () => void {
+			taskCompleteCallbacks.push(callback);
+			return () => {
+				const idx = taskCompleteCallbacks.indexOf(callback);
+				if (idx >= 0) taskCompleteCallbacks.splice(idx, 1);
+			};
+		},
+
+		/** Fire the task-complete callbacks. Called by Terminal.tsx's parsed-event handler. */
+		notifyTaskComplete(id: string, summary: string): void {
+			for (const cb of taskCompleteCallbacks) cb(id, summary);
+		},
+
 		/** Mark a tab as detached to a floating window */
 		detach(tabId: string, windowLabel: string): void {
 			setState("detachedWindows", tabId, windowLabel);
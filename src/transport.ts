@@ -101,14 +101,20 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		map: (args) => ({ method: "POST", path: "/dictation/models/delete", body: { model: args.model_name } }),
 	},
 	start_dictation: { map: () => ({ method: "POST", path: "/dictation/start" }) },
-	stop_dictation_and_transcribe: { map: () => ({ method: "POST", path: "/dictation/stop" }) },
+	stop_dictation_and_transcribe: {
+		map: (args) => ({ method: "POST", path: "/dictation/stop", body: args.language ? { language: args.language } : {} }),
+	},
 	get_correction_map: { map: () => ({ method: "GET", path: "/dictation/corrections" }) },
 	set_correction_map: {
 		map: (args) => ({ method: "PUT", path: "/dictation/corrections", body: { map: args.map } }),
 	},
 	list_audio_devices: { map: () => ({ method: "GET", path: "/dictation/devices" }) },
 	inject_text: {
-		map: (args) => ({ method: "POST", path: "/dictation/inject", body: { text: args.text } }),
+		map: (args) => ({
+			method: "POST",
+			path: "/dictation/inject",
+			body: { text: args.text, session_id: args.sessionId },
+		}),
 	},
 	get_dictation_config: { map: () => ({ method: "GET", path: "/dictation/config" }) },
 	set_dictation_config: {
@@ -181,6 +187,13 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			};
 		},
 	},
+	broadcast_input: {
+		map: (args) => ({
+			method: "POST",
+			path: "/sessions/broadcast",
+			body: { session_ids: args.sessionIds, input: args.input, special_key: args.specialKey },
+		}),
+	},
 	write_pty: {
 		map: (args) => ({
 			method: "POST",
@@ -214,6 +227,26 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	close_pty: {
 		map: (args) => ({ method: "DELETE", path: `/sessions/${args.sessionId}` }),
 	},
+	start_session_recording: {
+		map: (args) => ({
+			method: "POST",
+			path: `/sessions/${args.sessionId}/record`,
+			body: { action: "start", path: args.path },
+		}),
+	},
+	export_session_transcript: {
+		map: (args) => ({
+			method: "GET",
+			path: `/sessions/${args.sessionId}/export?format=${encodeURIComponent(String(args.format))}`,
+		}),
+	},
+	stop_session_recording: {
+		map: (args) => ({
+			method: "POST",
+			path: `/sessions/${args.sessionId}/record`,
+			body: { action: "stop" },
+		}),
+	},
 	get_session_foreground_process: {
 		map: (args) => ({
 			method: "GET",
@@ -227,6 +260,13 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			path: `/sessions/${args.sessionId}/shell-family`,
 		}),
 	},
+	get_session_context: {
+		map: (args) => ({
+			method: "GET",
+			path: `/sessions/${args.sessionId}/context`,
+			transform: (data) => (data as { context: string | null }).context ?? null,
+		}),
+	},
 	get_shell_state: {
 		map: (args) => ({
 			method: "GET",
@@ -380,6 +420,17 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	get_orchestrator_stats: { map: () => ({ method: "GET", path: "/stats" }) },
 	get_session_metrics: { map: () => ({ method: "GET", path: "/metrics" }) },
 	get_process_stats: { map: () => ({ method: "GET", path: "/process/stats" }) },
+	get_session_resources: {
+		map: (_args, p) => ({ method: "GET", path: `/sessions/${p("sessionId")}/resources` }),
+	},
+	list_orphaned_processes: { map: () => ({ method: "GET", path: "/process/orphans" }) },
+	kill_orphaned_process: {
+		map: (a) => ({
+			method: "POST",
+			path: "/process/orphans/kill",
+			body: { pid: a.pid, confirm: a.confirm },
+		}),
+	},
 
 	// --- Claude Usage dashboard ---
 	get_claude_usage_api: { map: () => ({ method: "GET", path: "/claude/usage" }) },
@@ -395,6 +446,17 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		map: (_args, p) => ({ method: "GET", path: `/claude/session-stats?scope=${p("scope")}` }),
 	},
 	list_active_sessions: { map: () => ({ method: "GET", path: "/sessions" }) },
+	list_recoverable_sessions: { map: () => ({ method: "GET", path: "/sessions/recoverable" }) },
+	list_sessions_page: {
+		map: (args) => {
+			// offset is always sent so the server returns the {sessions, total} envelope
+			let path = `/sessions?offset=${encodeURIComponent(String(args.offset ?? 0))}`;
+			for (const key of ["repo", "tag", "paused", "limit"]) {
+				if (args[key] != null) path += `&${key}=${encodeURIComponent(String(args[key]))}`;
+			}
+			return { method: "GET", path };
+		},
+	},
 	can_spawn_session: {
 		map: () => ({
 			method: "GET",
@@ -423,9 +485,24 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	save_notification_config: {
 		map: (args) => ({ method: "PUT", path: "/config/notifications", body: args.config }),
 	},
+	notification_allowed: {
+		map: (args) => ({
+			method: "POST",
+			path: "/config/notifications/allow",
+			body: { sound: args.sound, sessionId: args.sessionId ?? null },
+		}),
+	},
 
 	// --- Config: UI prefs ---
 	load_ui_prefs: { map: () => ({ method: "GET", path: "/config/ui-prefs" }) },
+	get_active_selection: { map: () => ({ method: "GET", path: "/config/active-selection" }) },
+	set_active_selection: {
+		map: (args) => ({
+			method: "PUT",
+			path: "/config/active-selection",
+			body: { repo_path: args.repoPath, worktree_path: args.worktreePath ?? null },
+		}),
+	},
 	save_ui_prefs: {
 		map: (args) => ({ method: "PUT", path: "/config/ui-prefs", body: args.config }),
 	},
@@ -438,6 +515,16 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	check_has_custom_settings: {
 		map: (_args, p) => ({ method: "GET", path: `/config/repo-settings/has-custom?path=${p("path")}` }),
 	},
+	reset_repo_settings: {
+		map: (args) => ({
+			method: "POST",
+			path: "/config/repo-settings/reset",
+			body: { repoPath: args.repoPath, categories: args.categories ?? null },
+		}),
+	},
+	explain_env: {
+		map: (_args, p) => ({ method: "GET", path: `/config/repo-settings/explain-env?path=${p("repoPath")}` }),
+	},
 	load_repo_defaults: { map: () => ({ method: "GET", path: "/config/repo-defaults" }) },
 	save_repo_defaults: {
 		map: (args) => ({ method: "PUT", path: "/config/repo-defaults", body: args.config }),
@@ -448,6 +535,9 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	save_repositories: {
 		map: (args) => ({ method: "PUT", path: "/config/repositories", body: args.config }),
 	},
+	reorder_repositories: {
+		map: (args) => ({ method: "POST", path: "/config/repositories/reorder", body: { order: args.order } }),
+	},
 
 	// --- Config: pane layout ---
 	load_pane_layout: { map: () => ({ method: "GET", path: "/config/pane-layout" }) },
@@ -465,7 +555,12 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	},
 
 	// --- Config: prompt library ---
-	load_prompt_library: { map: () => ({ method: "GET", path: "/config/prompt-library" }) },
+	load_prompt_library: {
+		map: (args) => ({
+			method: "GET",
+			path: args.sort ? `/config/prompt-library?sort=${encodeURIComponent(String(args.sort))}` : "/config/prompt-library",
+		}),
+	},
 	save_prompt_library: {
 		map: (args) => ({ method: "PUT", path: "/config/prompt-library", body: args.config }),
 	},
@@ -481,6 +576,19 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	save_keybindings: {
 		map: (args) => ({ method: "PUT", path: "/config/keybindings", body: args.config }),
 	},
+	export_keybindings: {
+		map: (args) => ({
+			method: "GET",
+			path: `/config/keybindings/export?includeUiPrefs=${args.includeUiPrefs === true}`,
+		}),
+	},
+	import_keybindings: {
+		map: (args) => ({
+			method: "POST",
+			path: "/config/keybindings/import",
+			body: { json: args.json, knownActions: args.knownActions },
+		}),
+	},
 
 	// --- Config: agents ---
 	load_agents_config: { map: () => ({ method: "GET", path: "/config/agents" }) },
@@ -566,6 +674,9 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			return { method: "GET", path };
 		},
 	},
+	parse_conflict_markers: {
+		map: (args) => ({ method: "POST", path: "/repo/conflict-markers", body: { content: args.content } }),
+	},
 	get_branches_detail: {
 		map: (_args, p) => ({ method: "GET", path: `/repo/branches-detail?path=${p("path")}` }),
 	},
@@ -621,6 +732,29 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			body: { path: args.path, name: args.name, force: args.force },
 		}),
 	},
+	rebase_branch: {
+		map: (args) => ({ method: "POST", path: "/repo/rebase", body: { path: args.path, onto: args.onto } }),
+	},
+	abort_rebase: {
+		map: (args) => ({ method: "POST", path: "/repo/rebase/abort", body: { path: args.path } }),
+	},
+	cherry_pick: {
+		map: (args) => ({
+			method: "POST",
+			path: "/repo/cherry-pick",
+			body: { path: args.path, commitSha: args.commitSha },
+		}),
+	},
+	abort_cherry_pick: {
+		map: (args) => ({ method: "POST", path: "/repo/cherry-pick/abort", body: { path: args.path } }),
+	},
+	checkout_branch: {
+		map: (args) => ({
+			method: "POST",
+			path: "/repo/checkout",
+			body: { path: args.path, branch: args.branch, autostash: args.autostash ?? false },
+		}),
+	},
 	delete_local_branch: {
 		map: (args) => ({
 			method: "POST",
@@ -639,7 +773,13 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		map: (args) => ({
 			method: "POST",
 			path: "/repo/switch-branch",
-			body: { repoPath: args.repoPath, branchName: args.branchName, force: args.force, stash: args.stash },
+			body: {
+				repoPath: args.repoPath,
+				branchName: args.branchName,
+				force: args.force,
+				stash: args.stash,
+				autostash: args.autostash,
+			},
 		}),
 	},
 	merge_and_archive_worktree: {
@@ -662,10 +802,21 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		}),
 	},
 	get_diff_stats: {
-		map: (_args, p) => ({ method: "GET", path: `/repo/diff-stats?path=${p("path")}` }),
+		map: (args, p) => {
+			let path = `/repo/diff-stats?path=${p("path")}`;
+			if (args.scope != null) path += `&scope=${encodeURIComponent(String(args.scope))}`;
+			if (args.perFile) path += `&per_file=true`;
+			return { method: "GET", path };
+		},
 	},
 	get_changed_files: {
-		map: (_args, p) => ({ method: "GET", path: `/repo/files?path=${p("path")}` }),
+		map: (args, p) => {
+			let path = `/repo/files?path=${p("path")}`;
+			if (args.scope != null) path += `&scope=${p("scope")}`;
+			if (args.base != null) path += `&base=${p("base")}`;
+			if (args.head != null) path += `&head=${p("head")}`;
+			return { method: "GET", path };
+		},
 	},
 	get_file_diff: {
 		map: (args, p) => {
@@ -702,6 +853,28 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			body: { repoPath: args.repoPath, issueNumber: args.issueNumber },
 		}),
 	},
+	list_repo_issues: {
+		map: (args, p) => {
+			let path = `/repo/issues/list?path=${p("path")}`;
+			if (args.stateFilter != null) path += `&state=${encodeURIComponent(String(args.stateFilter))}`;
+			const labels = (args.labels as string[] | undefined) ?? [];
+			if (labels.length > 0) path += `&labels=${encodeURIComponent(labels.join(","))}`;
+			return { method: "GET", path };
+		},
+	},
+	get_issue: {
+		map: (_args, p) => ({
+			method: "GET",
+			path: `/repo/issues/get?path=${p("repoPath")}&number=${p("issueNumber")}`,
+		}),
+	},
+	add_issue_comment: {
+		map: (args) => ({
+			method: "POST",
+			path: "/repo/issues/comment",
+			body: { repoPath: args.repoPath, issueNumber: args.issueNumber, body: args.body },
+		}),
+	},
 	get_github_viewer_login: {
 		map: () => ({ method: "GET", path: "/github/viewer-login" }),
 	},
@@ -1004,6 +1177,13 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			body: { visible: args.visible },
 		}),
 	},
+	set_poll_active: {
+		map: (args) => ({
+			method: "POST",
+			path: "/repo/github-poller/active",
+			body: { active: args.active },
+		}),
+	},
 	github_poll_repo: {
 		map: (args) => ({
 			method: "POST",
@@ -1034,6 +1214,21 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	get_repo_summary: {
 		map: (_args, p) => ({ method: "GET", path: `/repo/summary?path=${p("repoPath")}` }),
 	},
+	get_repo_stats: {
+		map: (args, p) => {
+			const ignores = Array.isArray(args.extraIgnores) && args.extraIgnores.length > 0
+				? `&extra_ignores=${encodeURIComponent(args.extraIgnores.join(","))}`
+				: "";
+			const lines = args.countLines === true ? "&count_lines=true" : "";
+			return { method: "GET", path: `/repo/stats?path=${p("path")}${ignores}${lines}` };
+		},
+	},
+	scan_for_repos: {
+		map: (args, p) => {
+			const depth = args.maxDepth != null ? `&max_depth=${encodeURIComponent(String(args.maxDepth))}` : "";
+			return { method: "GET", path: `/repo/scan?root_dir=${p("rootDir")}${depth}` };
+		},
+	},
 	get_repo_structure: {
 		map: (_args, p) => ({ method: "GET", path: `/repo/structure?path=${p("repoPath")}` }),
 	},
@@ -1043,6 +1238,16 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	get_ci_checks: {
 		map: (_args, p) => ({ method: "GET", path: `/repo/ci?path=${p("path")}&pr_number=${p("prNumber")}` }),
 	},
+	get_pr_timeline: {
+		map: (_args, p) => ({ method: "GET", path: `/repo/pr-timeline?path=${p("path")}&pr_number=${p("prNumber")}` }),
+	},
+	hydrate_pr_check_details: {
+		map: (args) => ({
+			method: "POST",
+			path: "/repo/ci/batch",
+			body: { path: args.path, pr_numbers: args.prNumbers },
+		}),
+	},
 	rename_branch: {
 		map: (args) => ({
 			method: "POST",
@@ -1066,7 +1271,7 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		map: (args) => ({
 			method: "POST",
 			path: "/repo/run-git",
-			body: { path: args.path, args: args.args },
+			body: { path: args.path, args: args.args, autostash: args.autostash },
 		}),
 	},
 	get_working_tree_status: {
@@ -1104,7 +1309,7 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		map: (args) => ({
 			method: "POST",
 			path: "/repo/commit",
-			body: { path: args.path, message: args.message, amend: args.amend },
+			body: { path: args.path, message: args.message, amend: args.amend, files: args.files, all: args.all },
 		}),
 	},
 	get_commit_log: {
@@ -1145,6 +1350,19 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			path: `/repo/stash/show?path=${p("path")}&stash_ref=${p("stashRef")}`,
 		}),
 	},
+	get_config_value: {
+		map: (_args, p) => ({ method: "GET", path: `/repo/config?path=${p("path")}&key=${p("key")}` }),
+	},
+	set_config_value: {
+		map: (args) => ({
+			method: "POST",
+			path: "/repo/config",
+			body: { path: args.path, key: args.key, value: args.value, scope: args.scope },
+		}),
+	},
+	lfs_status: {
+		map: (_args, p) => ({ method: "GET", path: `/repo/lfs-status?path=${p("path")}` }),
+	},
 	get_file_history: {
 		map: (args, p) => {
 			let url = `/repo/file-history?path=${p("path")}&file=${p("file")}`;
@@ -1154,10 +1372,16 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		},
 	},
 	get_file_blame: {
-		map: (_args, p) => ({
-			method: "GET",
-			path: `/repo/file-blame?path=${p("path")}&file=${p("file")}`,
-		}),
+		map: (args, p) => {
+			let url = `/repo/blame?path=${p("path")}&file=${p("file")}`;
+			if (args.startLine != null) url += `&start_line=${args.startLine}`;
+			if (args.endLine != null) url += `&end_line=${args.endLine}`;
+			return { method: "GET", path: url };
+		},
+	},
+	get_blame_cache_stats: { map: () => ({ method: "GET", path: "/repo/blame-cache-stats" }) },
+	find_repo_for_path: {
+		map: (_args, p) => ({ method: "GET", path: `/repo/for-path?path=${p("path")}` }),
 	},
 
 	// --- Worktrees ---
@@ -1182,12 +1406,20 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			body: { base_repo: args.baseRepo, branch_name: args.branchName, base_ref: args.baseRef },
 		}),
 	},
+	create_worktree_from_pr: {
+		map: (args) => ({
+			method: "POST",
+			path: "/worktrees/from-pr",
+			body: { repo_path: args.repoPath, pr_number: args.prNumber, remote: args.remote },
+		}),
+	},
 	remove_worktree: {
 		map: (args, p) => {
 			const force = args.force === true ? "&force=true" : "";
+			const dryRun = args.dryRun === true ? "&dryRun=true" : "";
 			return {
 				method: "DELETE",
-				path: `/worktrees/${p("branchName")}?repoPath=${p("repoPath")}&deleteBranch=${args.deleteBranch ?? true}${force}`,
+				path: `/worktrees/${p("branchName")}?repoPath=${p("repoPath")}&deleteBranch=${args.deleteBranch ?? true}${force}${dryRun}`,
 			};
 		},
 	},
@@ -1195,7 +1427,7 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		map: (args) => ({
 			method: "POST",
 			path: "/worktrees/generate-name",
-			body: { existing_names: args.existingNames },
+			body: { existing_names: args.existingNames, repo_path: args.repoPath },
 		}),
 	},
 	finalize_merged_worktree: {
@@ -1222,6 +1454,13 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			body: { repoPath: args.repoPath, worktreePath: args.worktreePath },
 		}),
 	},
+	prune_orphaned_worktrees: {
+		map: (args) => ({
+			method: "POST",
+			path: "/worktrees/prune",
+			body: { repoPath: args.repoPath, dryRun: args.dryRun },
+		}),
+	},
 	run_setup_script: {
 		map: (args) => ({
 			method: "POST",
@@ -1233,7 +1472,13 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		map: (args) => ({
 			method: "POST",
 			path: "/repo/merge-pr",
-			body: { repoPath: args.repoPath, prNumber: args.prNumber, mergeMethod: args.mergeMethod },
+			body: {
+				repoPath: args.repoPath,
+				prNumber: args.prNumber,
+				mergeMethod: args.mergeMethod,
+				commitTitle: args.commitTitle,
+				commitMessage: args.commitMessage,
+			},
 		}),
 	},
 	get_pr_diff: {
@@ -1249,6 +1494,25 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			body: { repoPath: args.repoPath, prNumber: args.prNumber },
 		}),
 	},
+	request_reviewers: {
+		map: (args) => ({
+			method: "POST",
+			path: "/repo/pr-review-request",
+			body: {
+				repoPath: args.repoPath,
+				prNumber: args.prNumber,
+				reviewers: args.reviewers ?? [],
+				teamReviewers: args.teamReviewers ?? [],
+			},
+		}),
+	},
+	add_pr_comment: {
+		map: (args) => ({
+			method: "POST",
+			path: "/repo/pr-comment",
+			body: { repoPath: args.repoPath, prNumber: args.prNumber, body: args.body },
+		}),
+	},
 	list_local_branches: {
 		map: (_args, p) => ({ method: "GET", path: `/repo/local-branches?path=${p("repoPath")}` }),
 	},
@@ -1260,13 +1524,23 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	read_file: {
 		map: (_args, p) => ({ method: "GET", path: `/repo/file?path=${p("path")}&file=${p("file")}` }),
 	},
+	get_recent_files: {
+		map: (_args, p) => ({ method: "GET", path: `/repo/recent-files?path=${p("repoPath")}` }),
+	},
+	record_recent_file: {
+		map: (args) => ({
+			method: "POST",
+			path: "/repo/recent-files",
+			body: { path: args.repoPath, file: args.file },
+		}),
+	},
 
 	// --- Prompt processing ---
 	process_prompt_content: {
 		map: (args) => ({
 			method: "POST",
 			path: "/prompt/process",
-			body: { content: args.content, variables: args.variables },
+			body: { content: args.content, variables: args.variables, promptId: args.promptId, send: args.send },
 		}),
 	},
 	extract_prompt_variables: {
@@ -1326,6 +1600,14 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		}),
 	},
 	detect_agents: { map: () => ({ method: "GET", path: "/agents" }) },
+	list_agent_transcripts: { map: () => ({ method: "GET", path: "/agents/transcripts" }) },
+	prune_agent_transcripts: {
+		map: (args) => ({
+			method: "POST",
+			path: "/agents/transcripts/prune",
+			body: { olderThanDays: args.olderThanDays },
+		}),
+	},
 	detect_all_agent_binaries: {
 		map: (args) => ({ method: "POST", path: "/agents/detect-all", body: { binaries: args.binaries } }),
 	},
@@ -1353,6 +1635,8 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 
 	// --- MCP status ---
 	get_mcp_status: { map: () => ({ method: "GET", path: "/mcp/status" }) },
+	diagnose_remote_access: { map: () => ({ method: "GET", path: "/mcp/remote-diagnosis" }) },
+	run_diagnostics: { map: () => ({ method: "GET", path: "/health/diagnostics" }) },
 
 	// --- Network ---
 	get_local_ip: { map: () => ({ method: "GET", path: "/system/local-ip" }) },
@@ -1372,6 +1656,15 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 	fs_read_file: {
 		map: (_args, p) => ({ method: "GET", path: `/fs/read?repoPath=${p("repoPath")}&file=${p("file")}` }),
 	},
+	fs_read_file_info: {
+		map: (_args, p) => ({ method: "GET", path: `/fs/read-info?repoPath=${p("repoPath")}&file=${p("file")}` }),
+	},
+	diff_files: {
+		map: (_args, p) => ({
+			method: "GET",
+			path: `/fs/diff?repoPath=${p("repoPath")}&fileA=${p("fileA")}&fileB=${p("fileB")}`,
+		}),
+	},
 	read_editor_file: {
 		map: (_args, p) => ({ method: "GET", path: `/fs/read-editor?repoPath=${p("repoPath")}&file=${p("file")}` }),
 	},
@@ -1532,6 +1825,9 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 		map: (args) => ({ method: "GET", path: `/logs?limit=${args.limit ?? 0}` }),
 	},
 	clear_logs: { map: () => ({ method: "DELETE", path: "/logs" }) },
+	ansi_to_html: {
+		map: (args) => ({ method: "POST", path: "/logs/ansi-html", body: { text: args.text } }),
+	},
 
 	// --- Story 071: Plugin RPC commands ---
 	plugin_read_file: {
@@ -1585,6 +1881,7 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 				headers: args.headers,
 				body: args.body,
 				allowedUrls: args.allowedUrls,
+				limits: args.limits,
 			},
 		}),
 	},
@@ -1595,6 +1892,13 @@ const COMMAND_TABLE: Record<string, CommandTableEntry> = {
 			return { method: "GET", path };
 		},
 	},
+	plugin_emit_event: {
+		map: (args, p) => ({
+			method: "POST",
+			path: `/api/plugins/${p("pluginId")}/events`,
+			body: { eventType: args.eventType, payload: args.payload, sessionId: args.sessionId },
+		}),
+	},
 	register_loaded_plugin: {
 		map: (args, p) => ({
 			method: "POST",
@@ -1655,6 +1959,8 @@ export const INTENTIONALLY_UNMAPPED: ReadonlySet<string> = new Set<string>([
 	"regenerate_session_token",
 	"get_tailscale_status",
 	"recheck_tailscale_status",
+	// Switching the remote-access profile restarts the server the remote client is on.
+	"switch_remote_profile",
 	// Deep-link / OAuth callback entry points — invoked by the OS URL handler, not UI.
 	"deep_link_mcp_call",
 	"mcp_oauth_callback",
@@ -1693,6 +1999,21 @@ export const INTENTIONALLY_UNMAPPED: ReadonlySet<string> = new Set<string>([
 	"uninstall_plugin",
 	// Plugin data deletion — no frontend caller.
 	"delete_plugin_data",
+	// Log file path — only useful for revealing the file in the host's file manager.
+	"get_log_path",
+	// Config recovery notices — drained once at desktop startup; a remote client
+	// taking them would hide the notices from the host window.
+	"take_config_recovery_notices",
+	// Agent spawn dry run — no frontend caller; resolves the host's agent binary and env.
+	"dry_run_spawn",
+	// MCP config snippet — no frontend caller; describes the host's own listener.
+	"generate_mcp_config_snippet",
+	// Agent MCP config inspection — reads the host agent's config files, like the
+	// other agent_mcp commands.
+	"list_mcp_servers",
+	"preview_mcp_registration",
+	// Output-parser harness — debug builds only, for reproducing detection reports.
+	"parse_output_sample",
 ]);
 
 /** Map a Tauri invoke command + args to an HTTP method/path/body */
@@ -1863,7 +2184,8 @@ export interface WsParsedEvent {
  *
  * In Tauri: uses listen() for pty-output-{sessionId}, pty-exit-{sessionId}.
  * In browser: uses WebSocket to /sessions/{sessionId}/stream with JSON framing:
- *   - {"type":"output","data":"..."} for raw PTY output
+ *   - {"type":"output","data":"...","offset":N,"next_offset":M} for raw PTY output
+ *   - {"type":"resume","offset":N,"gap":bool} first on a `?since=` reconnect
  *   - {"type":"parsed","event":{...}} for structured events (questions, rate limits)
  *   - {"type":"exit"} / {"type":"closed"} for session lifecycle
  *
@@ -1955,9 +2277,20 @@ export async function subscribePty(
 					lastTotalWritten = (frame as Record<string, unknown>).total_written as number;
 				}
 				switch (frame.type) {
-					case "output":
+					case "output": {
+						// Raw mode: byte offset to resume from (`?since=`) after a drop
+						const next = (frame as Record<string, unknown>).next_offset;
+						if (typeof next === "number") {
+							lastTotalWritten = next;
+						}
 						onData(frame.data as string);
 						break;
+					}
+					case "resume":
+						if (frame.gap) {
+							appLogger.warn("network", `WebSocket resume for ${sessionId} lost output; replaying from offset ${frame.offset}`);
+						}
+						break;
 					case "log": {
 						// Track the monotonic line cursor so reconnect resumes from the last
 						// line we consumed instead of replaying from the mount offset (which
@@ -2019,7 +2352,8 @@ export async function subscribePty(
 		const params = new URLSearchParams();
 		if (queryFormat) params.set("format", queryFormat);
 		if (reconnectOffset != null) {
-			params.set("offset", String(reconnectOffset));
+			// Raw streams resume by byte offset; log/text streams by line offset
+			params.set(queryFormat ? "offset" : "since", String(reconnectOffset));
 		} else if (opts.logOffset != null) {
 			params.set("offset", String(opts.logOffset));
 		}
@@ -42,9 +42,18 @@ export interface PtyConfig {
 }
 
 /** PTY exit event data */
+/** How a session's process ended (backend `ExitInfo`) */
+export interface ExitInfo {
+	reason: "exited" | "signaled" | "oom" | "killed-by-us";
+	signal: number | null;
+	/** The process dumped core (WCOREDUMP) — it crashed */
+	core_dumped: boolean;
+}
+
 export interface PtyExit {
 	session_id: string;
 	code: number | null;
+	exit_info?: ExitInfo | null;
 }
 
 /**
@@ -25,6 +25,8 @@ export function branchListsEqual(a: readonly BranchDetail[], b: readonly BranchD
 			x.last_commit_date !== y.last_commit_date ||
 			x.last_commit_message !== y.last_commit_message ||
 			x.last_commit_author !== y.last_commit_author ||
+			x.last_commit_sha !== y.last_commit_sha ||
+			x.worktree_path !== y.worktree_path ||
 			x.base_ahead !== y.base_ahead ||
 			x.base_behind !== y.base_behind ||
 			x.base_branch !== y.base_branch
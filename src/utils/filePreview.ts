@@ -1,4 +1,6 @@
+import { invoke } from "../invoke";
 import { filePreviewRegistry } from "../plugins/filePreviewRegistry";
+import { appLogger } from "../stores/appLogger";
 import { diffTabsStore } from "../stores/diffTabs";
 import { editorTabsStore } from "../stores/editorTabs";
 import { mdTabsStore } from "../stores/mdTabs";
@@ -63,6 +65,9 @@ export function classifyFile(filePath: string): FileOpenTarget {
  * 2. Markdown tab for .md/.mdx
  * 3. HTML preview for media/document files
  * 4. CodeMirror editor (fallback)
+ *
+ * This is the user's explicit open, so it also records the file in the repo's
+ * recent-files list (background reads through `read_file` never do).
  */
 export function openFileAction(
 	filePath: string,
@@ -71,6 +76,10 @@ export function openFileAction(
 	line?: number,
 	onEditorTab?: (tabId: string) => void,
 ): void {
+	invoke("record_recent_file", { repoPath: fsRoot || repoPath, file: filePath }).catch((err) =>
+		appLogger.debug("app", "Failed to record recent file", { filePath, error: String(err) }),
+	);
+
 	if (line === undefined) {
 		const handler = filePreviewRegistry.getHandler(filePath);
 		if (handler) {